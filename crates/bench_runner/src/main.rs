@@ -57,6 +57,9 @@ fn main() -> Result<()> {
         let server_conf = ServerConfig {
             pg_listener,
             rpc_addr: None,
+            flight_addr: None,
+            http_addr: None,
+            metrics_addr: None,
         };
 
         let server = ComputeServer::connect(
@@ -71,6 +74,11 @@ fn main() -> Result<()> {
             None,
             Default::default(),
             None,
+            None,
+            None,
+            Default::default(),
+            None,
+            Default::default(),
             false,
             false,
         )
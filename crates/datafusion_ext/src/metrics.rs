@@ -5,12 +5,14 @@ use datafusion::{
     execution::TaskContext,
     physical_expr::PhysicalSortExpr,
     physical_plan::{
+        displayable,
         metrics::{BaselineMetrics, ExecutionPlanMetricsSet, Gauge, MetricBuilder, MetricsSet},
         DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, RecordBatchStream,
         SendableRecordBatchStream, Statistics,
     },
 };
 use futures::{Stream, StreamExt};
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -213,6 +215,16 @@ pub struct AggregatedMetrics {
     pub elapsed_compute_ns: u64,
     /// Total bytes processed.
     pub bytes_processed: u64,
+    /// Bytes processed by each data source, keyed by the name of the data
+    /// source's execution plan.
+    pub bytes_processed_by_source: HashMap<String, u64>,
+    /// Rows read by each data source, keyed the same as
+    /// `bytes_processed_by_source`.
+    pub rows_processed_by_source: HashMap<String, u64>,
+    /// Total bytes spilled to disk.
+    pub spilled_bytes: u64,
+    /// Total number of spills to disk.
+    pub spill_count: u64,
 }
 
 impl AggregatedMetrics {
@@ -224,6 +236,10 @@ impl AggregatedMetrics {
         let mut agg = AggregatedMetrics {
             elapsed_compute_ns: 0,
             bytes_processed: 0,
+            bytes_processed_by_source: HashMap::new(),
+            rows_processed_by_source: HashMap::new(),
+            spilled_bytes: 0,
+            spill_count: 0,
         };
         agg.aggregate_recurse(plan);
         agg
@@ -235,13 +251,39 @@ impl AggregatedMetrics {
             None => return,
         };
         self.elapsed_compute_ns += metrics.elapsed_compute().unwrap_or_default() as u64;
-        self.bytes_processed += metrics
-            .sum_by_name(BYTES_PROCESSED_GAUGE_NAME)
-            .map(|m| m.as_usize() as u64)
-            .unwrap_or_default();
+        self.spilled_bytes += metrics.spilled_bytes().unwrap_or_default() as u64;
+        self.spill_count += metrics.spill_count().unwrap_or_default() as u64;
+
+        if let Some(bytes) = metrics.sum_by_name(BYTES_PROCESSED_GAUGE_NAME) {
+            let bytes = bytes.as_usize() as u64;
+            let rows = metrics.output_rows().unwrap_or_default() as u64;
+            let name = source_name(plan);
+            self.bytes_processed += bytes;
+            *self
+                .bytes_processed_by_source
+                .entry(name.clone())
+                .or_default() += bytes;
+            *self.rows_processed_by_source.entry(name).or_default() += rows;
+        }
 
         for child in plan.children() {
             self.aggregate_recurse(child.as_ref());
         }
     }
 }
+
+/// Get the name of a data source plan for use when reporting metrics.
+///
+/// The name is taken from the plan's display output. The metrics adapter is
+/// skipped to report the name of the wrapped plan.
+fn source_name(plan: &dyn ExecutionPlan) -> String {
+    if let Some(adapter) = plan.as_any().downcast_ref::<DataSourceMetricsExecAdapter>() {
+        return source_name(adapter.child.as_ref());
+    }
+    let display = displayable(plan).one_line().to_string();
+    display
+        .split(|c: char| c == ':' || c.is_whitespace())
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
@@ -28,6 +28,7 @@ use datafusion::sql::planner::PlannerContext;
 use datafusion::sql::sqlparser::ast::{
     Expr as SQLExpr, Function as SQLFunction, FunctionArg, FunctionArgExpr, WindowType,
 };
+use protogen::metastore::types::catalog::FunctionType;
 use std::str::FromStr;
 
 use super::arrow_cast::ARROW_CAST_NAME;
@@ -158,14 +159,27 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
         }
 
         // finally, user-defined functions (UDF) and UDAF
+        let sql_args = function.args.clone();
         let args = self
             .function_args_to_expr(function.args, schema, planner_context)
             .await?;
 
-        if let Some(expr) = self.schema_provider.get_builtin(&name, args) {
+        if let Some(expr) = self.schema_provider.get_builtin(&name, args)? {
             return Ok(expr);
         }
 
+        // SQL functions created with `CREATE FUNCTION`, inlined with the
+        // unplanned arguments.
+        let func_ref = self.object_name_to_table_reference(function.name)?;
+        if let Some(func) = self
+            .schema_provider
+            .get_sql_function(func_ref, FunctionType::Scalar)
+        {
+            return self
+                .inline_scalar_sql_function(&name, func, sql_args, schema, planner_context)
+                .await;
+        }
+
         // Could not find the relevant function, so return an error
         Err(DataFusionError::Plan(format!("Invalid function '{name}'.")))
     }
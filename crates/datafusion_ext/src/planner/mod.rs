@@ -21,6 +21,7 @@ mod query;
 mod relation;
 mod select;
 mod set_expr;
+mod sql_function;
 mod statement;
 pub mod utils;
 mod values;
@@ -50,6 +51,7 @@ use datafusion::sql::sqlparser::ast::ExactNumberInfo;
 use datafusion::sql::sqlparser::ast::TimezoneInfo;
 use datafusion::sql::sqlparser::ast::{ColumnDef as SQLColumnDef, ColumnOption};
 use datafusion::sql::sqlparser::ast::{DataType as SQLDataType, Ident, ObjectName, TableAlias};
+use protogen::metastore::types::catalog::{FunctionType, SqlFunction};
 
 use crate::utils::make_decimal_type;
 
@@ -64,7 +66,10 @@ pub trait AsyncContextProvider: Send + Sync {
     ) -> Result<Arc<dyn TableSource>>;
 
     /// Getter for a UDF description
-    fn get_builtin(&mut self, name: &str, args: Vec<Expr>) -> Option<Expr>;
+    ///
+    /// Returns `None` if there's no builtin with the given name, and errors
+    /// if there is one but the arguments are invalid.
+    fn get_builtin(&mut self, name: &str, args: Vec<Expr>) -> Result<Option<Expr>>;
     /// Getter for a UDAF description
     async fn get_aggregate_meta(&mut self, name: &str) -> Option<Arc<AggregateUDF>>;
     /// Getter for system/user-defined variable type
@@ -81,6 +86,16 @@ pub trait AsyncContextProvider: Send + Sync {
         opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableSource>>;
 
+    /// Get a function created with `CREATE FUNCTION`.
+    ///
+    /// The function body gets inlined into the plan. Returns `None` if no
+    /// such function of the given type exists.
+    fn get_sql_function(
+        &mut self,
+        name: TableReference<'_>,
+        func_type: FunctionType,
+    ) -> Option<SqlFunction>;
+
     /// Get configuration options.
     fn options(&self) -> &ConfigOptions;
 }
@@ -90,6 +105,8 @@ pub struct SqlQueryPlanner<'a, S: AsyncContextProvider> {
     pub(crate) schema_provider: &'a mut S,
     pub(crate) options: ParserOptions,
     pub(crate) normalizer: IdentNormalizer,
+    /// Current depth of nested SQL function calls being inlined.
+    pub(crate) sql_function_depth: usize,
 }
 
 impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
@@ -105,6 +122,7 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
             schema_provider,
             options,
             normalizer: IdentNormalizer::new(normalize),
+            sql_function_depth: 0,
         }
     }
 
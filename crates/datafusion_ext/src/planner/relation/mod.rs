@@ -23,8 +23,9 @@ use crate::planner::{AsyncContextProvider, SqlQueryPlanner};
 
 use async_recursion::async_recursion;
 use datafusion::common::{DataFusionError, OwnedTableReference, Result};
+use protogen::metastore::types::catalog::FunctionType;
 
-use datafusion::logical_expr::{LogicalPlan, LogicalPlanBuilder};
+use datafusion::logical_expr::{LogicalPlan, LogicalPlanBuilder, SubqueryAlias};
 
 use datafusion::scalar::ScalarValue;
 use datafusion::sql::planner::PlannerContext;
@@ -81,6 +82,26 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
                         Some(args) => {
                             // Table factor has arguments, look up table returning
                             // function.
+
+                            // SQL functions created with `CREATE FUNCTION` get
+                            // inlined as a subquery.
+                            if let Some(func) = self
+                                .schema_provider
+                                .get_sql_function(table_ref.clone(), FunctionType::TableReturning)
+                            {
+                                let name = table_ref.to_string();
+                                let plan =
+                                    self.inline_table_sql_function(&name, func, args).await?;
+                                let plan = LogicalPlan::SubqueryAlias(SubqueryAlias::try_new(
+                                    plan,
+                                    table_ref.table().to_string(),
+                                )?);
+                                return match alias {
+                                    Some(alias) => self.apply_table_alias(plan, alias),
+                                    None => Ok(plan),
+                                };
+                            }
+
                             for arg in args {
                                 let (name, val) = self.get_constant_function_arg(arg)?;
                                 if let Some(name) = name {
@@ -255,6 +276,14 @@ fn infer_func_for_file(path: &str) -> Result<OwnedTableReference> {
             schema: "public".into(),
             table: "ndjson_scan".into(),
         },
+        "avro" => OwnedTableReference::Partial {
+            schema: "public".into(),
+            table: "avro_scan".into(),
+        },
+        "orc" => OwnedTableReference::Partial {
+            schema: "public".into(),
+            table: "orc_scan".into(),
+        },
         ext => {
             return Err(DataFusionError::Plan(format!(
                 "unable to infer how to handle file extension: {ext}"
@@ -0,0 +1,296 @@
+//! Inlining for functions created with `CREATE FUNCTION`.
+//!
+//! SQL functions act as macros. References to parameters in the function body
+//! are replaced with the (casted) arguments, and the resulting expression or
+//! query is planned in place of the function call.
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+use datafusion::arrow::datatypes::DataType;
+use datafusion::common::{DFSchema, DataFusionError, Result};
+use datafusion::logical_expr::{cast, Expr, LogicalPlan, LogicalPlanBuilder};
+use datafusion::sql::planner::{IdentNormalizer, PlannerContext};
+use datafusion::sql::sqlparser::ast::{
+    Expr as SQLExpr, Function as SQLFunction, FunctionArg, FunctionArgExpr, Ident, ObjectName,
+    Query, Statement, Value, VisitMut, VisitorMut,
+};
+use datafusion::sql::sqlparser::dialect::PostgreSqlDialect;
+use datafusion::sql::sqlparser::parser::{Parser, ParserError};
+use datafusion::sql::sqlparser::tokenizer::Token;
+use protogen::metastore::types::catalog::{FunctionType, SqlFunction};
+use protogen::metastore::types::options::InternalColumnDefinition;
+
+use super::expr::arrow_cast::ARROW_CAST_NAME;
+use crate::planner::{AsyncContextProvider, SqlQueryPlanner};
+
+/// Maximum depth of SQL functions calling other SQL functions. Guards against
+/// functions (indirectly) calling themselves.
+const MAX_SQL_FUNCTION_DEPTH: usize = 32;
+
+impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
+    /// Check that the body of a SQL function can be planned.
+    ///
+    /// Parameters are bound to `NULL`, and scalar functions are planned
+    /// against an empty schema so that the body may only reference its
+    /// parameters.
+    pub async fn validate_sql_function(
+        &mut self,
+        name: &str,
+        func_type: FunctionType,
+        func: &SqlFunction,
+    ) -> Result<()> {
+        let args = func
+            .params
+            .iter()
+            .map(|_| FunctionArg::Unnamed(FunctionArgExpr::Expr(SQLExpr::Value(Value::Null))))
+            .collect();
+
+        match func_type {
+            FunctionType::Scalar => {
+                self.inline_scalar_sql_function(
+                    name,
+                    func.clone(),
+                    args,
+                    &DFSchema::empty(),
+                    &mut PlannerContext::new(),
+                )
+                .await?;
+            }
+            FunctionType::TableReturning => {
+                self.inline_table_sql_function(name, func.clone(), args)
+                    .await?;
+            }
+            FunctionType::Aggregate => {
+                return Err(DataFusionError::NotImplemented(
+                    "Aggregate SQL functions".to_string(),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Plan a call to a scalar SQL function.
+    pub(super) async fn inline_scalar_sql_function(
+        &mut self,
+        name: &str,
+        func: SqlFunction,
+        args: Vec<FunctionArg>,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        let mut body = parse_expr_body(name, &func.body)?;
+        self.replace_sql_function_params(name, &func.params, args, &mut body)?;
+
+        let body = match &func.return_type {
+            Some(return_type) => cast_expr(body, return_type),
+            None => body,
+        };
+
+        self.enter_sql_function(name)?;
+        let expr = self
+            .sql_expr_to_logical_expr(body, schema, planner_context)
+            .await;
+        self.sql_function_depth -= 1;
+
+        expr
+    }
+
+    /// Plan a call to a table returning SQL function.
+    pub(super) async fn inline_table_sql_function(
+        &mut self,
+        name: &str,
+        func: SqlFunction,
+        args: Vec<FunctionArg>,
+    ) -> Result<LogicalPlan> {
+        let mut body = parse_query_body(name, &func.body)?;
+        self.replace_sql_function_params(name, &func.params, args, &mut body)?;
+
+        // Plan with a fresh context, CTEs from the calling query shouldn't be
+        // visible to the function body.
+        self.enter_sql_function(name)?;
+        let plan = self
+            .query_to_plan_with_context(body, &mut PlannerContext::new())
+            .await;
+        self.sql_function_depth -= 1;
+        let plan = plan?;
+
+        if func.return_columns.is_empty() {
+            return Ok(plan);
+        }
+
+        let fields = plan.schema().fields().clone();
+        if fields.len() != func.return_columns.len() {
+            return Err(DataFusionError::Plan(format!(
+                "Function '{name}' declares {} output columns, but its body returns {}",
+                func.return_columns.len(),
+                fields.len(),
+            )));
+        }
+
+        LogicalPlanBuilder::from(plan)
+            .project(
+                fields
+                    .iter()
+                    .zip(func.return_columns.iter())
+                    .map(|(field, col)| {
+                        cast(
+                            Expr::Column(field.qualified_column()),
+                            col.arrow_type.clone(),
+                        )
+                        .alias(&col.name)
+                    }),
+            )?
+            .build()
+    }
+
+    fn enter_sql_function(&mut self, name: &str) -> Result<()> {
+        if self.sql_function_depth >= MAX_SQL_FUNCTION_DEPTH {
+            return Err(DataFusionError::Plan(format!(
+                "Exceeded maximum depth of nested function calls when calling '{name}'"
+            )));
+        }
+        self.sql_function_depth += 1;
+        Ok(())
+    }
+
+    /// Bind arguments to the function's parameters, replacing all references
+    /// to the parameters in the function body.
+    ///
+    /// Arguments may be positional or named, and are cast to the declared
+    /// parameter types.
+    fn replace_sql_function_params<T: VisitMut>(
+        &self,
+        name: &str,
+        params: &[InternalColumnDefinition],
+        args: Vec<FunctionArg>,
+        body: &mut T,
+    ) -> Result<()> {
+        let mut positional = Vec::new();
+        let mut named = HashMap::new();
+        for arg in args {
+            match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => positional.push(expr),
+                FunctionArg::Named {
+                    name: arg_name,
+                    arg: FunctionArgExpr::Expr(expr),
+                } => {
+                    named.insert(self.normalizer.normalize(arg_name), expr);
+                }
+                other => {
+                    return Err(DataFusionError::Plan(format!(
+                        "Unsupported argument for function '{name}': {other}"
+                    )))
+                }
+            }
+        }
+
+        if positional.len() > params.len() {
+            return Err(DataFusionError::Plan(format!(
+                "Function '{name}' expects {} arguments, got {}",
+                params.len(),
+                positional.len(),
+            )));
+        }
+
+        let mut positional = positional.into_iter();
+        let mut bound = HashMap::with_capacity(params.len());
+        for param in params {
+            let arg = match positional.next() {
+                Some(arg) => arg,
+                None => named.remove(&param.name).ok_or_else(|| {
+                    DataFusionError::Plan(format!(
+                        "Missing argument '{}' for function '{name}'",
+                        param.name
+                    ))
+                })?,
+            };
+            bound.insert(param.name.clone(), cast_expr(arg, &param.arrow_type));
+        }
+
+        if let Some(arg_name) = named.keys().next() {
+            return Err(DataFusionError::Plan(format!(
+                "Unknown argument '{arg_name}' for function '{name}'"
+            )));
+        }
+
+        let mut replacer = ParamReplacer {
+            normalizer: &self.normalizer,
+            args: &bound,
+        };
+        let _ = body.visit(&mut replacer);
+
+        Ok(())
+    }
+}
+
+/// Replaces identifiers referencing function parameters with the bound
+/// arguments.
+///
+/// Since arguments are substituted after the node's children have been
+/// visited, identifiers within arguments are never replaced.
+struct ParamReplacer<'a> {
+    normalizer: &'a IdentNormalizer,
+    args: &'a HashMap<String, SQLExpr>,
+}
+
+impl<'a> VisitorMut for ParamReplacer<'a> {
+    type Break = ();
+
+    fn post_visit_expr(&mut self, expr: &mut SQLExpr) -> ControlFlow<Self::Break> {
+        if let SQLExpr::Identifier(ident) = expr {
+            if let Some(arg) = self.args.get(&self.normalizer.normalize(ident.clone())) {
+                *expr = arg.clone();
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Wrap an expression in an `arrow_cast` to the given type.
+fn cast_expr(expr: SQLExpr, data_type: &DataType) -> SQLExpr {
+    SQLExpr::Function(SQLFunction {
+        name: ObjectName(vec![Ident::new(ARROW_CAST_NAME)]),
+        args: vec![
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)),
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(SQLExpr::Value(
+                Value::SingleQuotedString(data_type.to_string()),
+            ))),
+        ],
+        over: None,
+        distinct: false,
+        special: false,
+        order_by: Vec::new(),
+    })
+}
+
+fn parse_expr_body(name: &str, body: &str) -> Result<SQLExpr> {
+    let invalid =
+        |e: ParserError| DataFusionError::Plan(format!("Invalid body for function '{name}': {e}"));
+
+    let mut parser = Parser::new(&PostgreSqlDialect {})
+        .try_with_sql(body)
+        .map_err(invalid)?;
+    let expr = parser.parse_expr().map_err(invalid)?;
+    let next = parser.peek_token().token;
+    if next != Token::EOF {
+        return Err(DataFusionError::Plan(format!(
+            "Invalid body for function '{name}': unexpected '{next}' after expression"
+        )));
+    }
+
+    Ok(expr)
+}
+
+fn parse_query_body(name: &str, body: &str) -> Result<Query> {
+    let invalid =
+        |e: ParserError| DataFusionError::Plan(format!("Invalid body for function '{name}': {e}"));
+
+    let mut statements = Parser::parse_sql(&PostgreSqlDialect {}, body).map_err(invalid)?;
+    match (statements.pop(), statements.is_empty()) {
+        (Some(Statement::Query(query)), true) => Ok(*query),
+        _ => Err(DataFusionError::Plan(format!(
+            "Invalid body for function '{name}': expected a single query"
+        ))),
+    }
+}
@@ -1,10 +1,15 @@
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 use datafusion::{
     common::{DFField, DFSchema, DataFusionError, OwnedTableReference, Result, ToDFSchema},
     logical_expr::{
-        builder::project, Analyze, Explain, ExprSchemable, LogicalPlan, PlanType, ToStringifiedPlan,
+        builder::project, lit, Analyze, Explain, ExprSchemable, LogicalPlan, PlanType,
+        ToStringifiedPlan,
     },
+    scalar::ScalarValue,
     sql::{
         planner::PlannerContext,
         sqlparser::ast::{self, Query, SetExpr, Statement},
@@ -52,10 +57,15 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
         }
     }
 
+    /// Plan the source of an insert, projecting it to the schema of the table.
+    ///
+    /// Table columns not present in `columns` are filled in using the
+    /// expressions in `column_defaults`, or NULL if the column has no default.
     pub async fn insert_to_source_plan(
         &mut self,
         table_name: &OwnedTableReference,
         columns: &Vec<String>,
+        column_defaults: &HashMap<String, ast::Expr>,
         source: Box<Query>,
     ) -> Result<LogicalPlan> {
         // Do a table lookup to verify the table exists
@@ -126,6 +136,43 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
             })
             .collect::<Result<Vec<datafusion::logical_expr::Expr>>>()?;
 
+        // Fill in any table columns that weren't provided.
+        let exprs = if columns.is_empty() {
+            exprs
+        } else {
+            let mut provided: HashMap<_, _> = fields
+                .iter()
+                .map(|field| field.name().clone())
+                .zip(exprs)
+                .collect();
+
+            let empty_schema = DFSchema::empty();
+            let mut all_exprs = Vec::with_capacity(table_schema.fields().len());
+            for field in table_schema.fields() {
+                let expr = match provided.remove(field.name()) {
+                    Some(expr) => expr,
+                    None => {
+                        let default = match column_defaults.get(field.name()) {
+                            Some(default) => {
+                                self.sql_to_expr(
+                                    default.clone(),
+                                    &empty_schema,
+                                    &mut PlannerContext::new(),
+                                )
+                                .await?
+                            }
+                            None => lit(ScalarValue::Null),
+                        };
+                        default
+                            .cast_to(field.data_type(), &empty_schema)?
+                            .alias(field.name())
+                    }
+                };
+                all_exprs.push(expr);
+            }
+            all_exprs
+        };
+
         let source = project(source, exprs)?;
         Ok(source)
     }
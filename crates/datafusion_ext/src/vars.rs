@@ -91,6 +91,12 @@ impl SessionVars {
      memory_limit_bytes: Option<usize>,
      max_tunnel_count: Option<usize>,
      max_credentials_count: Option<usize>,
+     max_scan_bytes: usize,
+     max_result_rows: usize,
+     enforce_unique_constraints: bool,
+     auto_compact_tables: bool,
+     compact_small_file_bytes: usize,
+     compact_small_file_threshold: usize,
      is_cloud_instance: bool,
     }
 }
@@ -201,6 +207,12 @@ impl SessionVars {
     pub fn with_max_credentials_count(self, value: usize, setter: VarType) -> Self {
         with_property!(self, max_credentials_count, setter, Some(value))
     }
+    pub fn with_max_scan_bytes(self, value: usize, setter: VarType) -> Self {
+        with_property!(self, max_scan_bytes, setter, value)
+    }
+    pub fn with_max_result_rows(self, value: usize, setter: VarType) -> Self {
+        with_property!(self, max_result_rows, setter, value)
+    }
     pub fn with_is_cloud_instance(self, value: bool, setter: VarType) -> Self {
         with_property!(self, is_cloud_instance, setter, value)
     }
@@ -180,6 +180,55 @@ pub(super) const MAX_CREDENTIALS_COUNT: ServerVar<Option<usize>> = ServerVar {
     description: "Max credentials allowed",
 };
 
+pub(super) const MAX_SCAN_BYTES: ServerVar<usize> = ServerVar {
+    name: "max_scan_bytes",
+    value: &0,
+    group: "glaredb",
+    user_configurable: true,
+    description:
+        "Max bytes a query may read from data sources, 0 for no limit. Can only be lowered once set",
+};
+
+pub(super) const MAX_RESULT_ROWS: ServerVar<usize> = ServerVar {
+    name: "max_result_rows",
+    value: &0,
+    group: "glaredb",
+    user_configurable: true,
+    description: "Max rows a query may return, 0 for no limit. Can only be lowered once set",
+};
+
+pub(super) const ENFORCE_UNIQUE_CONSTRAINTS: ServerVar<bool> = ServerVar {
+    name: "enforce_unique_constraints",
+    value: &false,
+    group: "glaredb",
+    user_configurable: true,
+    description: "Check primary key and unique constraints when inserting into native tables",
+};
+
+pub(super) const AUTO_COMPACT_TABLES: ServerVar<bool> = ServerVar {
+    name: "auto_compact_tables",
+    value: &false,
+    group: "glaredb",
+    user_configurable: true,
+    description: "Compact native tables with many small files in the background after inserts",
+};
+
+pub(super) const COMPACT_SMALL_FILE_BYTES: ServerVar<usize> = ServerVar {
+    name: "compact_small_file_bytes",
+    value: &(16 * 1024 * 1024),
+    group: "glaredb",
+    user_configurable: true,
+    description: "Files smaller than this are considered for automatic compaction",
+};
+
+pub(super) const COMPACT_SMALL_FILE_THRESHOLD: ServerVar<usize> = ServerVar {
+    name: "compact_small_file_threshold",
+    value: &32,
+    group: "glaredb",
+    user_configurable: true,
+    description: "Number of small files a native table needs before it's automatically compacted",
+};
+
 pub(super) const IS_CLOUD_INSTANCE: ServerVar<bool> = ServerVar {
     name: "is_cloud_instance",
     value: &false,
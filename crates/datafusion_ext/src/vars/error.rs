@@ -8,6 +8,9 @@ pub enum VarError {
     #[error("Variable is readonly: {0}")]
     VariableReadonly(String),
 
+    #[error("Limit can only be lowered: Variable name: {name}, Current limit: {current}")]
+    LimitCanOnlyBeLowered { name: String, current: usize },
+
     #[error("Unknown variable: {0}")]
     UnknownVariable(String),
 
@@ -38,6 +38,12 @@ pub struct SessionVarsInner {
     pub memory_limit_bytes: SessionVar<Option<usize>>,
     pub max_tunnel_count: SessionVar<Option<usize>>,
     pub max_credentials_count: SessionVar<Option<usize>>,
+    pub max_scan_bytes: SessionVar<usize>,
+    pub max_result_rows: SessionVar<usize>,
+    pub enforce_unique_constraints: SessionVar<bool>,
+    pub auto_compact_tables: SessionVar<bool>,
+    pub compact_small_file_bytes: SessionVar<usize>,
+    pub compact_small_file_threshold: SessionVar<usize>,
     pub is_cloud_instance: SessionVar<bool>,
 }
 impl SessionVarsInner {
@@ -98,6 +104,18 @@ impl SessionVarsInner {
             Ok(&self.max_tunnel_count)
         } else if name.eq_ignore_ascii_case(MAX_CREDENTIALS_COUNT.name) {
             Ok(&self.max_credentials_count)
+        } else if name.eq_ignore_ascii_case(MAX_SCAN_BYTES.name) {
+            Ok(&self.max_scan_bytes)
+        } else if name.eq_ignore_ascii_case(MAX_RESULT_ROWS.name) {
+            Ok(&self.max_result_rows)
+        } else if name.eq_ignore_ascii_case(ENFORCE_UNIQUE_CONSTRAINTS.name) {
+            Ok(&self.enforce_unique_constraints)
+        } else if name.eq_ignore_ascii_case(AUTO_COMPACT_TABLES.name) {
+            Ok(&self.auto_compact_tables)
+        } else if name.eq_ignore_ascii_case(COMPACT_SMALL_FILE_BYTES.name) {
+            Ok(&self.compact_small_file_bytes)
+        } else if name.eq_ignore_ascii_case(COMPACT_SMALL_FILE_THRESHOLD.name) {
+            Ok(&self.compact_small_file_threshold)
         } else if name.eq_ignore_ascii_case(IS_CLOUD_INSTANCE.name) {
             Ok(&self.is_cloud_instance)
         } else {
@@ -151,6 +169,18 @@ impl SessionVarsInner {
             self.max_tunnel_count.set_from_str(val, setter)
         } else if name.eq_ignore_ascii_case(MAX_CREDENTIALS_COUNT.name) {
             self.max_credentials_count.set_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(MAX_SCAN_BYTES.name) {
+            self.max_scan_bytes.set_limit_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(MAX_RESULT_ROWS.name) {
+            self.max_result_rows.set_limit_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(ENFORCE_UNIQUE_CONSTRAINTS.name) {
+            self.enforce_unique_constraints.set_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(AUTO_COMPACT_TABLES.name) {
+            self.auto_compact_tables.set_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(COMPACT_SMALL_FILE_BYTES.name) {
+            self.compact_small_file_bytes.set_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(COMPACT_SMALL_FILE_THRESHOLD.name) {
+            self.compact_small_file_threshold.set_from_str(val, setter)
         } else {
             Err(VarError::UnknownVariable(name.to_string()).into())
         }
@@ -179,6 +209,12 @@ impl SessionVarsInner {
             self.memory_limit_bytes.config_entry(),
             self.max_tunnel_count.config_entry(),
             self.max_credentials_count.config_entry(),
+            self.max_scan_bytes.config_entry(),
+            self.max_result_rows.config_entry(),
+            self.enforce_unique_constraints.config_entry(),
+            self.auto_compact_tables.config_entry(),
+            self.compact_small_file_bytes.config_entry(),
+            self.compact_small_file_threshold.config_entry(),
             self.is_cloud_instance.config_entry(),
         ]
     }
@@ -208,6 +244,12 @@ impl Default for SessionVarsInner {
             memory_limit_bytes: SessionVar::new(&MEMORY_LIMIT_BYTES),
             max_tunnel_count: SessionVar::new(&MAX_TUNNEL_COUNT),
             max_credentials_count: SessionVar::new(&MAX_CREDENTIALS_COUNT),
+            max_scan_bytes: SessionVar::new(&MAX_SCAN_BYTES),
+            max_result_rows: SessionVar::new(&MAX_RESULT_ROWS),
+            enforce_unique_constraints: SessionVar::new(&ENFORCE_UNIQUE_CONSTRAINTS),
+            auto_compact_tables: SessionVar::new(&AUTO_COMPACT_TABLES),
+            compact_small_file_bytes: SessionVar::new(&COMPACT_SMALL_FILE_BYTES),
+            compact_small_file_threshold: SessionVar::new(&COMPACT_SMALL_FILE_THRESHOLD),
             is_cloud_instance: SessionVar::new(&IS_CLOUD_INSTANCE),
         }
     }
@@ -353,6 +395,30 @@ where
     }
 }
 
+impl SessionVar<usize> {
+    /// Parse a string as a limit and set it, where 0 means no limit.
+    ///
+    /// Users may only lower a limit once one is set, lifting or removing it
+    /// errors.
+    pub(super) fn set_limit_from_str(&mut self, s: &str, setter: VarType) -> Result<()> {
+        let current = *self.value();
+        if current > 0 && matches!(setter, VarType::UserDefined) {
+            match usize::try_parse(s) {
+                Some(v) if v > 0 && v <= current => (),
+                Some(_) => {
+                    return Err(VarError::LimitCanOnlyBeLowered {
+                        name: self.name().to_string(),
+                        current,
+                    }
+                    .into())
+                }
+                None => (),
+            }
+        }
+        self.set_from_str(s, setter)
+    }
+}
+
 impl<T> AnyVar for SessionVar<T>
 where
     T: Value + ?Sized + 'static,
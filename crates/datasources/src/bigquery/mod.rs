@@ -1,7 +1,7 @@
 //! BigQuery external table implementation.
 pub mod errors;
 
-use crate::common::util;
+use crate::common::{stats, util};
 use async_channel::Receiver;
 use async_stream::stream;
 use async_trait::async_trait;
@@ -163,6 +163,9 @@ impl BigQueryAccessor {
             )
             .await?;
         let arrow_schema = bigquery_table_to_arrow_schema(&table_meta)?;
+        // Table metadata already includes the row count and size, so no need
+        // for an extra request (or caching).
+        let statistics = bigquery_table_statistics(&table_meta);
 
         Ok(BigQueryTableProvider {
             access: table_access,
@@ -170,6 +173,7 @@ impl BigQueryAccessor {
             gcp_project_id: self.gcp_project_id,
             predicate_pushdown,
             arrow_schema: Arc::new(arrow_schema),
+            statistics,
         })
     }
 }
@@ -246,6 +250,8 @@ pub struct BigQueryTableProvider {
     gcp_project_id: String,
     predicate_pushdown: bool,
     arrow_schema: ArrowSchemaRef,
+    /// Approximate statistics for the table.
+    statistics: Statistics,
 }
 
 #[async_trait]
@@ -262,6 +268,10 @@ impl TableProvider for BigQueryTableProvider {
         TableType::Base
     }
 
+    fn statistics(&self) -> Option<Statistics> {
+        Some(self.statistics.clone())
+    }
+
     fn supports_filter_pushdown(
         &self,
         _filter: &Expr,
@@ -274,7 +284,7 @@ impl TableProvider for BigQueryTableProvider {
         _ctx: &SessionState,
         projection: Option<&Vec<usize>>,
         filters: &[Expr],
-        _limit: Option<usize>,
+        limit: Option<usize>,
     ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
         // TODO: Fix duplicated key deserialization.
         let storage = {
@@ -360,12 +370,21 @@ impl TableProvider for BigQueryTableProvider {
             send.close();
         });
 
+        // Limit isn't pushed down, so only use it as a hint for the row count.
+        let statistics = stats::estimate_scan_statistics(
+            &self.statistics,
+            &self.arrow_schema,
+            projection,
+            limit,
+        );
+
         Ok(Arc::new(BigQueryExec {
             predicate,
             arrow_schema: projected_schema,
             receiver: recv,
             num_partitions,
             metrics: ExecutionPlanMetricsSet::new(),
+            statistics,
         }))
     }
 }
@@ -376,6 +395,7 @@ struct BigQueryExec {
     receiver: Receiver<BufferedArrowIpcReader>,
     num_partitions: usize,
     metrics: ExecutionPlanMetricsSet,
+    statistics: Statistics,
 }
 
 impl ExecutionPlan for BigQueryExec {
@@ -422,7 +442,7 @@ impl ExecutionPlan for BigQueryExec {
     }
 
     fn statistics(&self) -> Statistics {
-        Statistics::default()
+        self.statistics.clone()
     }
 
     fn metrics(&self) -> Option<MetricsSet> {
@@ -522,6 +542,16 @@ fn bigquery_table_to_arrow_schema(table: &Table) -> Result<ArrowSchema> {
     Ok(ArrowSchema::new(arrow_fields))
 }
 
+/// Get table statistics from the table metadata.
+///
+/// BigQuery returns these numbers as strings, and they don't include data in
+/// the streaming buffer.
+fn bigquery_table_statistics(table: &Table) -> Statistics {
+    let num_rows = table.num_rows.as_ref().and_then(|n| n.parse().ok());
+    let num_bytes = table.num_bytes.as_ref().and_then(|n| n.parse().ok());
+    stats::table_statistics(num_rows, num_bytes)
+}
+
 fn table_field_schema_to_arrow_datatype(field: &BigQuerySchema) -> Result<Field> {
     match field.mode.as_deref() {
         Some("REPEATED") => handle_repeatable_fields(field),
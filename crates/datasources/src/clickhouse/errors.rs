@@ -0,0 +1,40 @@
+#[derive(Debug, thiserror::Error)]
+pub enum ClickhouseError {
+    #[error("Invalid ClickHouse connection string: {0}")]
+    InvalidConnectionString(String),
+
+    #[error("Unsupported protocol for ClickHouse: {0}")]
+    UnsupportedProtocol(String),
+
+    #[error("ClickHouse returned an error ({status}): {message}")]
+    Server {
+        status: reqwest::StatusCode,
+        message: String,
+    },
+
+    #[error("ClickHouse returned an empty response for query: {0}")]
+    EmptyResponse(String),
+
+    #[error("Table {0}.{1} not found in ClickHouse")]
+    TableNotFound(String, String),
+
+    #[error("Unexpected column type in ClickHouse response: {0}")]
+    UnexpectedColumnType(datafusion::arrow::datatypes::DataType),
+
+    #[error(transparent)]
+    Arrow(#[from] datafusion::arrow::error::ArrowError),
+
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    #[error(transparent)]
+    UrlParse(#[from] url::ParseError),
+
+    #[error(transparent)]
+    Fmt(#[from] std::fmt::Error),
+
+    #[error(transparent)]
+    Common(#[from] crate::common::errors::DatasourceCommonError),
+}
+
+pub type Result<T, E = ClickhouseError> = std::result::Result<T, E>;
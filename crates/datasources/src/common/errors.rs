@@ -32,6 +32,9 @@ pub enum DatasourceCommonError {
 
     #[error(transparent)]
     UrlParseError(#[from] url::ParseError),
+
+    #[error(transparent)]
+    AvroError(#[from] apache_avro::Error),
 }
 
 pub type Result<T, E = DatasourceCommonError> = std::result::Result<T, E>;
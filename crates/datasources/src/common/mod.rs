@@ -12,6 +12,7 @@ use datafusion::{
 pub mod errors;
 pub mod sink;
 pub mod ssh;
+pub mod stats;
 pub mod url;
 pub mod util;
 
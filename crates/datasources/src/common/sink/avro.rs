@@ -0,0 +1,464 @@
+use apache_avro::types::Value;
+use apache_avro::{Schema as AvroSchema, Writer as AvroWriter};
+use async_trait::async_trait;
+use datafusion::arrow::array::{Array, ArrayRef, AsArray, StringArray};
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::{
+    DataType, Date32Type, Float32Type, Float64Type, Int32Type, Int64Type, Schema, TimeUnit,
+    TimestampMicrosecondType,
+};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::util::display::{ArrayFormatter, FormatOptions};
+use datafusion::common::Result as DfResult;
+use datafusion::error::DataFusionError;
+use datafusion::execution::TaskContext;
+use datafusion::physical_plan::insert::DataSink;
+use datafusion::physical_plan::DisplayAs;
+use datafusion::physical_plan::{DisplayFormatType, SendableRecordBatchStream};
+use futures::StreamExt;
+use object_store::{path::Path as ObjectPath, ObjectStore};
+use serde_json::json;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::sync::Arc;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::common::errors::Result;
+
+use super::SharedBuffer;
+
+const BUFFER_SIZE: usize = 2 * 1024 * 1024;
+
+/// Writes avro object container files to object storage.
+///
+/// Every column is written as a nullable field. Arrow types without an avro
+/// equivalent are written as strings, with nested types (lists, structs and
+/// maps) written using their display format.
+#[derive(Debug)]
+pub struct AvroSink {
+    store: Arc<dyn ObjectStore>,
+    loc: ObjectPath,
+}
+
+impl Display for AvroSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AvroSink({}:{})", self.store, self.loc)
+    }
+}
+
+impl DisplayAs for AvroSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => write!(f, "{self}"),
+            DisplayFormatType::Verbose => write!(f, "{self}"),
+        }
+    }
+}
+
+impl AvroSink {
+    pub fn from_obj_store(store: Arc<dyn ObjectStore>, loc: impl Into<ObjectPath>) -> AvroSink {
+        AvroSink {
+            store,
+            loc: loc.into(),
+        }
+    }
+
+    async fn stream_into_inner(&self, mut stream: SendableRecordBatchStream) -> Result<usize> {
+        let fields = AvroField::from_arrow_schema(&stream.schema());
+        let schema = avro_schema(&fields)?;
+
+        let (_id, mut obj_handle) = self.store.put_multipart(&self.loc).await?;
+
+        let buffer = SharedBuffer::with_capacity(BUFFER_SIZE);
+        let mut writer = AvroWriter::new(&schema, buffer.clone());
+        let mut row_count = 0;
+
+        while let Some(batch) = stream.next().await {
+            let batch = batch?;
+            for record in batch_to_records(&fields, &batch)? {
+                writer.append(record)?;
+            }
+            row_count += batch.num_rows();
+
+            writer.flush()?;
+            try_flush(&buffer, &mut obj_handle, false).await?;
+        }
+
+        // Writes out the header if nothing has been written yet.
+        writer.into_inner()?;
+        try_flush(&buffer, &mut obj_handle, true).await?;
+        obj_handle.shutdown().await?;
+
+        Ok(row_count)
+    }
+}
+
+#[async_trait]
+impl DataSink for AvroSink {
+    async fn write_all(
+        &self,
+        data: Vec<SendableRecordBatchStream>,
+        _context: &Arc<TaskContext>,
+    ) -> DfResult<u64> {
+        let mut count = 0;
+        for stream in data {
+            count += self
+                .stream_into_inner(stream)
+                .await
+                .map(|x| x as u64)
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        }
+        Ok(count)
+    }
+}
+
+async fn try_flush<W: AsyncWrite + Unpin + Send>(
+    buffer: &SharedBuffer,
+    async_writer: &mut W,
+    force: bool,
+) -> Result<()> {
+    let mut buf = buffer.buffer.try_lock().unwrap();
+    if !force && buf.len() < buf.capacity() / 2 {
+        return Ok(());
+    }
+
+    async_writer.write_all(&buf).await?;
+    async_writer.flush().await?;
+
+    buf.clear();
+
+    Ok(())
+}
+
+/// A field in the written avro record.
+#[derive(Debug, Clone, PartialEq)]
+struct AvroField {
+    /// Name of the field, sanitized to be a valid avro name.
+    name: String,
+    /// Arrow type the column is cast to before conversion.
+    datatype: DataType,
+}
+
+impl AvroField {
+    /// Create avro fields for every field in the arrow schema.
+    ///
+    /// Sanitizing names may map different columns to the same name (e.g. "a b"
+    /// and "a_b"). Avro requires unique field names, so later duplicates get a
+    /// numeric suffix.
+    fn from_arrow_schema(schema: &Schema) -> Vec<AvroField> {
+        let mut used = HashSet::with_capacity(schema.fields().len());
+        schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let base = avro_name(field.name());
+                let mut name = base.clone();
+                let mut suffix = 1;
+                while !used.insert(name.clone()) {
+                    name = format!("{base}_{suffix}");
+                    suffix += 1;
+                }
+                AvroField {
+                    name,
+                    datatype: avro_compatible_type(field.data_type()),
+                }
+            })
+            .collect()
+    }
+
+    fn avro_type(&self) -> serde_json::Value {
+        match &self.datatype {
+            DataType::Boolean => json!("boolean"),
+            DataType::Int32 => json!("int"),
+            DataType::Int64 => json!("long"),
+            DataType::Float32 => json!("float"),
+            DataType::Float64 => json!("double"),
+            DataType::Binary => json!("bytes"),
+            DataType::Date32 => json!({"type": "int", "logicalType": "date"}),
+            DataType::Timestamp(_, _) => {
+                json!({"type": "long", "logicalType": "timestamp-micros"})
+            }
+            _ => json!("string"),
+        }
+    }
+}
+
+/// Get the type that arrow values should be cast to before being converted
+/// into avro values.
+fn avro_compatible_type(datatype: &DataType) -> DataType {
+    match datatype {
+        DataType::Boolean => DataType::Boolean,
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::UInt8 | DataType::UInt16 => {
+            DataType::Int32
+        }
+        DataType::Int64 | DataType::UInt32 | DataType::UInt64 => DataType::Int64,
+        DataType::Float16 | DataType::Float32 => DataType::Float32,
+        DataType::Float64 => DataType::Float64,
+        DataType::Binary | DataType::LargeBinary | DataType::FixedSizeBinary(_) => DataType::Binary,
+        DataType::Date32 | DataType::Date64 => DataType::Date32,
+        DataType::Timestamp(_, tz) => DataType::Timestamp(TimeUnit::Microsecond, tz.clone()),
+        _ => DataType::Utf8,
+    }
+}
+
+/// Sanitize a column name into a valid avro name.
+///
+/// Avro names must start with a letter or underscore, and can only contain
+/// letters, digits and underscores.
+fn avro_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !sanitized.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn avro_schema(fields: &[AvroField]) -> Result<AvroSchema> {
+    let fields: Vec<_> = fields
+        .iter()
+        .map(|field| {
+            json!({
+                "name": field.name,
+                "type": ["null", field.avro_type()],
+            })
+        })
+        .collect();
+
+    let schema = json!({
+        "type": "record",
+        "name": "record",
+        "fields": fields,
+    });
+
+    Ok(AvroSchema::parse(&schema)?)
+}
+
+/// Convert a batch into avro records.
+fn batch_to_records(fields: &[AvroField], batch: &RecordBatch) -> Result<Vec<Value>> {
+    let columns = fields
+        .iter()
+        .zip(batch.columns())
+        .map(|(field, col)| column_to_values(col, &field.datatype))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut columns: Vec<_> = columns.into_iter().map(|col| col.into_iter()).collect();
+    let records = (0..batch.num_rows())
+        .map(|_| {
+            let record = fields
+                .iter()
+                .zip(columns.iter_mut())
+                .map(|(field, col)| (field.name.clone(), col.next().unwrap_or(Value::Null)))
+                .collect();
+            Value::Record(record)
+        })
+        .collect();
+
+    Ok(records)
+}
+
+/// Convert an arrow column into nullable avro values.
+fn column_to_values(col: &ArrayRef, datatype: &DataType) -> Result<Vec<Value>> {
+    let col = if is_nested(col.data_type()) {
+        format_as_strings(col)?
+    } else {
+        cast(col, datatype)?
+    };
+
+    let values: Vec<Option<Value>> = match datatype {
+        DataType::Boolean => col
+            .as_boolean()
+            .iter()
+            .map(|v| v.map(Value::Boolean))
+            .collect(),
+        DataType::Int32 => col
+            .as_primitive::<Int32Type>()
+            .iter()
+            .map(|v| v.map(Value::Int))
+            .collect(),
+        DataType::Int64 => col
+            .as_primitive::<Int64Type>()
+            .iter()
+            .map(|v| v.map(Value::Long))
+            .collect(),
+        DataType::Float32 => col
+            .as_primitive::<Float32Type>()
+            .iter()
+            .map(|v| v.map(Value::Float))
+            .collect(),
+        DataType::Float64 => col
+            .as_primitive::<Float64Type>()
+            .iter()
+            .map(|v| v.map(Value::Double))
+            .collect(),
+        DataType::Binary => col
+            .as_binary::<i32>()
+            .iter()
+            .map(|v| v.map(|v| Value::Bytes(v.to_vec())))
+            .collect(),
+        DataType::Date32 => col
+            .as_primitive::<Date32Type>()
+            .iter()
+            .map(|v| v.map(Value::Date))
+            .collect(),
+        DataType::Timestamp(_, _) => col
+            .as_primitive::<TimestampMicrosecondType>()
+            .iter()
+            .map(|v| v.map(Value::TimestampMicros))
+            .collect(),
+        _ => col
+            .as_string::<i32>()
+            .iter()
+            .map(|v| v.map(|v| Value::String(v.to_string())))
+            .collect(),
+    };
+
+    // All fields are a union of null and the actual type.
+    Ok(values
+        .into_iter()
+        .map(|v| match v {
+            Some(v) => Value::Union(1, Box::new(v)),
+            None => Value::Union(0, Box::new(Value::Null)),
+        })
+        .collect())
+}
+
+/// Check if a type is nested. Arrow can't cast these to strings.
+fn is_nested(datatype: &DataType) -> bool {
+    matches!(
+        datatype,
+        DataType::List(_)
+            | DataType::LargeList(_)
+            | DataType::FixedSizeList(_, _)
+            | DataType::Struct(_)
+            | DataType::Map(_, _)
+            | DataType::Union(_, _)
+    )
+}
+
+/// Format every value in a column as a string.
+fn format_as_strings(col: &ArrayRef) -> Result<ArrayRef> {
+    let formatter = ArrayFormatter::try_new(col.as_ref(), &FormatOptions::default())?;
+    let values: StringArray = (0..col.len())
+        .map(|idx| {
+            if col.is_null(idx) {
+                None
+            } else {
+                Some(formatter.value(idx).to_string())
+            }
+        })
+        .collect();
+    Ok(Arc::new(values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use apache_avro::Reader as AvroReader;
+    use datafusion::arrow::array::{Int32Array, ListArray, StructArray};
+    use datafusion::arrow::datatypes::Field;
+
+    #[test]
+    fn sanitize_names() {
+        assert_eq!("a", avro_name("a"));
+        assert_eq!("count___", avro_name("count(*)"));
+        assert_eq!("_1abc", avro_name("1abc"));
+        assert_eq!("my_col", avro_name("my col"));
+    }
+
+    #[test]
+    fn dedup_sanitized_names() {
+        let schema = Schema::new(vec![
+            Field::new("a b", DataType::Int32, true),
+            Field::new("a_b", DataType::Int32, true),
+            Field::new("a-b", DataType::Int32, true),
+            Field::new("a_b_1", DataType::Int32, true),
+        ]);
+        let names: Vec<_> = AvroField::from_arrow_schema(&schema)
+            .into_iter()
+            .map(|f| f.name)
+            .collect();
+        assert_eq!(vec!["a_b", "a_b_1", "a_b_2", "a_b_1_1"], names);
+
+        // Schema is valid with the deduplicated names.
+        let fields = AvroField::from_arrow_schema(&schema);
+        avro_schema(&fields).unwrap();
+    }
+
+    #[test]
+    fn nested_columns_as_strings() {
+        let list: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            None,
+        ]));
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![Some(3), Some(4)]));
+        let st: ArrayRef = Arc::new(StructArray::from(vec![(
+            Arc::new(Field::new("x", DataType::Int32, true)),
+            values,
+        )]));
+
+        let got = column_to_values(&list, &avro_compatible_type(list.data_type())).unwrap();
+        let expected = vec![
+            Value::Union(1, Box::new(Value::String("[1, 2]".to_string()))),
+            Value::Union(0, Box::new(Value::Null)),
+        ];
+        assert_eq!(expected, got);
+
+        let got = column_to_values(&st, &avro_compatible_type(st.data_type())).unwrap();
+        let expected = vec![
+            Value::Union(1, Box::new(Value::String("{x: 3}".to_string()))),
+            Value::Union(1, Box::new(Value::String("{x: 4}".to_string()))),
+        ];
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn roundtrip_batch() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), None])),
+                Arc::new(StringArray::from(vec![Some("abc"), Some("def")])),
+            ],
+        )
+        .unwrap();
+
+        let fields = AvroField::from_arrow_schema(&schema);
+        let avro_schema = avro_schema(&fields).unwrap();
+
+        let mut writer = AvroWriter::new(&avro_schema, Vec::new());
+        for record in batch_to_records(&fields, &batch).unwrap() {
+            writer.append(record).unwrap();
+        }
+        let buf = writer.into_inner().unwrap();
+
+        let got: Vec<_> = AvroReader::new(buf.as_slice())
+            .unwrap()
+            .map(|v| v.unwrap())
+            .collect();
+        let expected = vec![
+            Value::Record(vec![
+                ("a".to_string(), Value::Union(1, Box::new(Value::Int(1)))),
+                (
+                    "b".to_string(),
+                    Value::Union(1, Box::new(Value::String("abc".to_string()))),
+                ),
+            ]),
+            Value::Record(vec![
+                ("a".to_string(), Value::Union(0, Box::new(Value::Null))),
+                (
+                    "b".to_string(),
+                    Value::Union(1, Box::new(Value::String("def".to_string()))),
+                ),
+            ]),
+        ];
+        assert_eq!(expected, got);
+    }
+}
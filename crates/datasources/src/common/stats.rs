@@ -0,0 +1,170 @@
+//! Table statistics for external data sources.
+//!
+//! External tables don't give us statistics for free, so we fetch approximate
+//! row counts and sizes from the source's own catalog when creating a table
+//! provider. Since providers are created for every query referencing the
+//! table, fetched statistics are cached for a short while to avoid an extra
+//! round trip per query.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::physical_plan::Statistics;
+use once_cell::sync::Lazy;
+use tracing::warn;
+
+/// How long fetched statistics are considered fresh.
+const STATISTICS_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Process-wide cache for external table statistics.
+static STATISTICS_CACHE: Lazy<StatisticsCache> = Lazy::new(StatisticsCache::default);
+
+/// Cache of table statistics keyed by some string uniquely identifying the
+/// table in the external system.
+#[derive(Debug, Default)]
+pub struct StatisticsCache {
+    entries: DashMap<String, (Instant, Statistics)>,
+}
+
+impl StatisticsCache {
+    /// Get statistics for a key if they haven't expired yet.
+    pub fn get(&self, key: &str) -> Option<Statistics> {
+        let ent = self.entries.get(key)?;
+        let (fetched_at, stats) = ent.value();
+        if fetched_at.elapsed() > STATISTICS_TTL {
+            drop(ent);
+            self.entries.remove(key);
+            return None;
+        }
+        Some(stats.clone())
+    }
+
+    pub fn put(&self, key: impl Into<String>, stats: Statistics) {
+        self.entries.insert(key.into(), (Instant::now(), stats));
+    }
+}
+
+/// Get statistics for a table, using the cached value if available, otherwise
+/// fetching them with the provided future.
+///
+/// Statistics are only used as hints for the planner, so failing to fetch them
+/// never fails the query. Errors are logged and unknown statistics are
+/// returned instead.
+pub async fn get_or_fetch_statistics<Fut, E>(key: String, fetch: Fut) -> Statistics
+where
+    Fut: Future<Output = Result<Statistics, E>>,
+    E: std::fmt::Display,
+{
+    if let Some(stats) = STATISTICS_CACHE.get(&key) {
+        return stats;
+    }
+
+    match fetch.await {
+        Ok(stats) => {
+            STATISTICS_CACHE.put(key, stats.clone());
+            stats
+        }
+        Err(e) => {
+            warn!(%e, "failed to fetch table statistics");
+            Statistics::default()
+        }
+    }
+}
+
+/// Create table level statistics from an approximate row count and table size
+/// in bytes.
+pub fn table_statistics(num_rows: Option<u64>, total_byte_size: Option<u64>) -> Statistics {
+    Statistics {
+        num_rows: num_rows.and_then(|n| usize::try_from(n).ok()),
+        total_byte_size: total_byte_size.and_then(|n| usize::try_from(n).ok()),
+        column_statistics: None,
+        // Everything we get from external catalogs are estimates.
+        is_exact: false,
+    }
+}
+
+/// Estimate statistics for a scan over a table.
+///
+/// Byte size is scaled down by the number of projected columns (assuming
+/// columns are roughly the same size), and the row count is capped by the
+/// limit if one was pushed down. Filters are applied by the external system,
+/// so the row count is an upper bound in that case.
+pub fn estimate_scan_statistics(
+    table_stats: &Statistics,
+    schema: &Schema,
+    projection: Option<&Vec<usize>>,
+    limit: Option<usize>,
+) -> Statistics {
+    let total_byte_size = match (table_stats.total_byte_size, projection) {
+        (Some(size), Some(projection)) if !schema.fields.is_empty() => {
+            Some(size / schema.fields.len() * projection.len())
+        }
+        (size, _) => size,
+    };
+
+    let num_rows = match (table_stats.num_rows, limit) {
+        (Some(rows), Some(limit)) => Some(rows.min(limit)),
+        (None, Some(limit)) => Some(limit),
+        (rows, None) => rows,
+    };
+
+    Statistics {
+        num_rows,
+        total_byte_size,
+        column_statistics: None,
+        is_exact: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::datatypes::{DataType, Field};
+
+    use super::*;
+
+    #[test]
+    fn scan_statistics_estimate() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Int64, true),
+            Field::new("c", DataType::Utf8, true),
+            Field::new("d", DataType::Utf8, true),
+        ]);
+        let table_stats = table_statistics(Some(1000), Some(4000));
+
+        // No projection or limit.
+        let stats = estimate_scan_statistics(&table_stats, &schema, None, None);
+        assert_eq!(Some(1000), stats.num_rows);
+        assert_eq!(Some(4000), stats.total_byte_size);
+        assert!(!stats.is_exact);
+
+        // Projection.
+        let stats = estimate_scan_statistics(&table_stats, &schema, Some(&vec![0, 2]), None);
+        assert_eq!(Some(1000), stats.num_rows);
+        assert_eq!(Some(2000), stats.total_byte_size);
+
+        // Limit.
+        let stats = estimate_scan_statistics(&table_stats, &schema, None, Some(10));
+        assert_eq!(Some(10), stats.num_rows);
+        let stats = estimate_scan_statistics(&table_stats, &schema, None, Some(5000));
+        assert_eq!(Some(1000), stats.num_rows);
+
+        // Unknown table stats.
+        let stats = estimate_scan_statistics(&Statistics::default(), &schema, None, Some(10));
+        assert_eq!(Some(10), stats.num_rows);
+        assert_eq!(None, stats.total_byte_size);
+    }
+
+    #[test]
+    fn statistics_cache() {
+        let cache = StatisticsCache::default();
+        assert!(cache.get("pg/public.t").is_none());
+
+        cache.put("pg/public.t", table_statistics(Some(5), None));
+        let stats = cache.get("pg/public.t").unwrap();
+        assert_eq!(Some(5), stats.num_rows);
+        assert_eq!(None, stats.total_byte_size);
+    }
+}
@@ -24,6 +24,9 @@ pub enum Datasource {
     MySql,
     BigQuery,
     Snowflake,
+    SqlServer,
+    ClickHouse,
+    Sqlite,
 }
 
 /// Returns true if the literal expression encoding should be wrapped inside
@@ -37,7 +40,19 @@ fn is_literal_quotable(datasource: Datasource, lit: &ScalarValue) -> bool {
         | ScalarValue::Float32(_)
         | ScalarValue::Float64(_)
         | ScalarValue::Decimal128(..) => false,
-        ScalarValue::Binary(_) if datasource == Datasource::MySql => false,
+        ScalarValue::Binary(_)
+            if matches!(datasource, Datasource::MySql | Datasource::SqlServer) =>
+        {
+            false
+        }
+        // ClickHouse has no binary literals, binary values are decoded from a
+        // hex string with 'unhex' instead.
+        ScalarValue::Binary(_) if datasource == Datasource::ClickHouse => false,
+        // SQLite blob literals are written as X'...'.
+        ScalarValue::Binary(_) if datasource == Datasource::Sqlite => false,
+        // SQL Server doesn't have boolean literals, 'bit' values are compared
+        // against 1 and 0 instead.
+        ScalarValue::Boolean(_) if datasource == Datasource::SqlServer => false,
         _ => true,
     }
 }
@@ -58,6 +73,9 @@ pub fn encode_literal_to_text(
         buf.write_str("'")?;
     }
     match lit {
+        ScalarValue::Boolean(Some(v)) if datasource == Datasource::SqlServer => {
+            buf.write_str(if *v { "1" } else { "0" })?;
+        }
         ScalarValue::Boolean(Some(v)) => {
             if *v {
                 buf.write_str("TRUE")?;
@@ -72,9 +90,21 @@ pub fn encode_literal_to_text(
         ScalarValue::Float32(Some(v)) => encode_float(buf, *v)?,
         ScalarValue::Float64(Some(v)) => encode_float(buf, *v)?,
         ScalarValue::Utf8(Some(v)) => encode_string(buf, v)?,
-        ScalarValue::Binary(Some(v)) if datasource == Datasource::MySql => {
+        ScalarValue::Binary(Some(v))
+            if matches!(datasource, Datasource::MySql | Datasource::SqlServer) =>
+        {
             encode_binary_mysql(buf, v)?
         }
+        ScalarValue::Binary(Some(v)) if datasource == Datasource::ClickHouse => {
+            buf.write_str("unhex('")?;
+            encode_binary_snowflake(buf, v)?;
+            buf.write_str("')")?;
+        }
+        ScalarValue::Binary(Some(v)) if datasource == Datasource::Sqlite => {
+            buf.write_str("X'")?;
+            encode_binary_snowflake(buf, v)?;
+            buf.write_str("'")?;
+        }
         ScalarValue::Binary(Some(v)) if datasource == Datasource::Snowflake => {
             encode_binary_snowflake(buf, v)?
         }
@@ -162,6 +192,12 @@ pub fn normalize_batch(batch: &RecordBatch) -> Result<RecordBatch, ArrowError> {
     Ok(batch)
 }
 
+/// Generate a unique name for a table that's being filled before being
+/// renamed into place.
+pub fn temp_table_name() -> String {
+    format!("__glaredb_tmp_{}", uuid::Uuid::new_v4().simple())
+}
+
 #[cfg(test)]
 mod tests {
     use datafusion::arrow::{
@@ -235,6 +271,26 @@ mod tests {
                 literal: ScalarValue::Binary(Some(b"abc".to_vec())),
                 expected: Some("'616263'"),
             },
+            TestCase {
+                datasource: SqlServer,
+                literal: ScalarValue::Binary(Some(b"abc".to_vec())),
+                expected: Some("0x616263"),
+            },
+            TestCase {
+                datasource: SqlServer,
+                literal: ScalarValue::Boolean(Some(true)),
+                expected: Some("1"),
+            },
+            TestCase {
+                datasource: ClickHouse,
+                literal: ScalarValue::Binary(Some(b"abc".to_vec())),
+                expected: Some("unhex('616263')"),
+            },
+            TestCase {
+                datasource: Sqlite,
+                literal: ScalarValue::Binary(Some(b"abc".to_vec())),
+                expected: Some("X'616263'"),
+            },
             TestCase {
                 datasource: Postgres,
                 literal: ScalarValue::TimestampNanosecond(Some(938709124 * 1_000_000_000), None),
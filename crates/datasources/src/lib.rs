@@ -1,6 +1,7 @@
 //! Data source implementations.
 
 pub mod bigquery;
+pub mod clickhouse;
 pub mod debug;
 pub mod lake;
 pub mod mongodb;
@@ -9,5 +10,7 @@ pub mod native;
 pub mod object_store;
 pub mod postgres;
 pub mod snowflake;
+pub mod sqlite;
+pub mod sqlserver;
 
 pub mod common;
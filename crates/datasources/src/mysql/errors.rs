@@ -6,12 +6,21 @@ pub enum MysqlError {
     #[error("Unable to convert mysql row value for column {0}: {1}, datatype: {2}")]
     UnsupportedArrowType(usize, String, datafusion::arrow::datatypes::DataType),
 
+    #[error("Unable to create MySQL column '{0}' for datatype: {1}")]
+    UnsupportedColumnType(String, datafusion::arrow::datatypes::DataType),
+
     #[error("Unsupported tunnel '{0}' for MySQL")]
     UnsupportedTunnel(String),
 
+    #[error("Table '{0}' already exists")]
+    TableAlreadyExists(String),
+
     #[error(transparent)]
     Arrow(#[from] datafusion::arrow::error::ArrowError),
 
+    #[error(transparent)]
+    DataFusion(#[from] datafusion::error::DataFusionError),
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
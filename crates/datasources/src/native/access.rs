@@ -2,21 +2,29 @@ use crate::native::errors::{NativeError, Result};
 use crate::native::insert::NativeTableInsertExec;
 use async_trait::async_trait;
 use datafusion::arrow::datatypes::{DataType, Schema as ArrowSchema, TimeUnit};
+use datafusion::common::tree_node::{Transformed, TreeNode};
 use datafusion::datasource::TableProvider;
 use datafusion::error::Result as DataFusionResult;
 use datafusion::execution::context::SessionState;
 
-use datafusion::logical_expr::{LogicalPlan, TableProviderFilterPushDown, TableType};
+use datafusion::logical_expr::expr::InList;
+use datafusion::logical_expr::{BinaryExpr, LogicalPlan, TableProviderFilterPushDown, TableType};
+use datafusion::physical_expr::expressions::{cast, Column};
+use datafusion::physical_expr::PhysicalExpr;
 use datafusion::physical_plan::empty::EmptyExec;
+use datafusion::physical_plan::projection::ProjectionExec;
 use datafusion::physical_plan::{ExecutionPlan, Statistics};
 use datafusion::prelude::Expr;
+use datafusion::scalar::ScalarValue;
 use datafusion_ext::metrics::DataSourceMetricsExecAdapter;
 use deltalake::operations::create::CreateBuilder;
 use deltalake::operations::delete::DeleteBuilder;
+use deltalake::operations::optimize::{OptimizeBuilder, OptimizeType};
 use deltalake::operations::update::UpdateBuilder;
+use deltalake::operations::vacuum::VacuumBuilder;
 use deltalake::protocol::SaveMode;
 use deltalake::storage::DeltaObjectStore;
-use deltalake::{DeltaTable, DeltaTableConfig};
+use deltalake::{checkpoints, DeltaTable, DeltaTableConfig};
 use futures::StreamExt;
 use object_store::path::Path as ObjectStorePath;
 use object_store::prefix::PrefixStore;
@@ -27,11 +35,24 @@ use protogen::metastore::types::options::{
     InternalColumnDefinition, TableOptions, TableOptionsInternal,
 };
 use std::any::Any;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::fs;
 use url::Url;
 use uuid::Uuid;
 
+/// Metrics from compacting a native table.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OptimizeMetrics {
+    pub files_added: u64,
+    pub files_removed: u64,
+    pub partitions_optimized: u64,
+}
+
+/// Default retention period used when vacuuming tables. Matches the delta
+/// default of 7 days.
+pub const DEFAULT_VACUUM_RETENTION_HOURS: u64 = 168;
+
 #[derive(Debug, Clone)]
 pub struct NativeTableStorage {
     db_id: Uuid,
@@ -97,18 +118,25 @@ impl NativeTableStorage {
                         name: col.name.clone(),
                         nullable: col.nullable,
                         arrow_type: DataType::Timestamp(TimeUnit::Microsecond, tz.clone()),
+                        default_expr: col.default_expr.clone(),
+                        comment: col.comment.clone(),
                     },
                     _ => col.to_owned(),
                 };
+                // Columns are always nullable in delta. NOT NULL constraints
+                // are enforced when writing to the table.
                 builder = builder.with_column(
                     column.name.clone(),
                     (&column.arrow_type).try_into()?,
-                    column.nullable,
+                    true,
                     None,
                 );
             }
 
-            // TODO: Partitioning
+            if !opts.partition_columns.is_empty() {
+                builder = builder.with_partition_columns(opts.partition_columns.clone());
+            }
+
             NativeTable::new(builder.await?)
         };
 
@@ -246,6 +274,58 @@ impl NativeTableStorage {
         let updated_rows = builder.await?.1.num_updated_rows;
         Ok(updated_rows)
     }
+
+    /// Compact the small files of a table into larger ones.
+    ///
+    /// If `zorder_by` is non-empty, files are rewritten with rows clustered by
+    /// the given columns instead of plain bin-packing. A checkpoint is written
+    /// after compaction so that readers don't need to replay the full log.
+    pub async fn optimize_table(
+        &self,
+        table: &TableEntry,
+        zorder_by: Vec<String>,
+    ) -> Result<OptimizeMetrics> {
+        let table = self.load_table(table).await?;
+        let mut builder = OptimizeBuilder::new(table.delta.object_store(), table.delta.state);
+        if !zorder_by.is_empty() {
+            builder = builder.with_type(OptimizeType::ZOrder(zorder_by));
+        }
+        let (delta, metrics) = builder.await?;
+        checkpoints::create_checkpoint(&delta).await?;
+
+        Ok(OptimizeMetrics {
+            files_added: metrics.num_files_added,
+            files_removed: metrics.num_files_removed,
+            partitions_optimized: metrics.partitions_optimized,
+        })
+    }
+
+    /// Remove files that are no longer referenced by the table and are older
+    /// than the retention period, returning the number of files deleted.
+    ///
+    /// Retention periods shorter than the table's configured one are
+    /// rejected, since files still needed by concurrent readers or time
+    /// travel could otherwise be removed.
+    pub async fn vacuum_table(
+        &self,
+        table: &TableEntry,
+        retain_hours: Option<u64>,
+    ) -> Result<usize> {
+        let table = self.load_table(table).await?;
+        let hours = retain_hours.unwrap_or(DEFAULT_VACUUM_RETENTION_HOURS);
+        let (_, metrics) = VacuumBuilder::new(table.delta.object_store(), table.delta.state)
+            .with_retention_period(chrono::Duration::hours(hours as i64))
+            .with_enforce_retention_duration(true)
+            .await?;
+        Ok(metrics.files_deleted.len())
+    }
+
+    /// Write a checkpoint for the current version of the table.
+    pub async fn checkpoint_table(&self, table: &TableEntry) -> Result<()> {
+        let table = self.load_table(table).await?;
+        checkpoints::create_checkpoint(&table.delta).await?;
+        Ok(())
+    }
 }
 
 fn make_prefix(db_id: Uuid, tbl_id: u32) -> String {
@@ -270,6 +350,102 @@ impl NativeTable {
         Arc::new(self)
     }
 
+    /// Returns the number of active data files smaller than `size` bytes.
+    pub fn num_files_smaller_than(&self, size: i64) -> usize {
+        self.delta
+            .get_state()
+            .files()
+            .iter()
+            .filter(|add| add.size < size)
+            .count()
+    }
+
+    /// Returns the columns the table is partitioned by.
+    pub fn partition_columns(&self) -> Vec<String> {
+        self.delta
+            .get_metadata()
+            .map(|meta| meta.partition_columns.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get the schema of the table with columns in the order they were
+    /// declared in.
+    ///
+    /// Delta moves partition columns to the end of the schema, and wraps string
+    /// partition columns in dictionaries. Partitioning shouldn't be visible to
+    /// the user, so undo both here.
+    fn declared_schema(&self) -> Arc<ArrowSchema> {
+        let schema = TableProvider::schema(&self.delta);
+        if self.partition_columns().is_empty() {
+            return schema;
+        }
+
+        let delta_schema = match self.delta.get_schema() {
+            Ok(delta_schema) => delta_schema,
+            Err(_) => return schema,
+        };
+
+        let fields: Vec<_> = delta_schema
+            .get_fields()
+            .iter()
+            .filter_map(|field| schema.field_with_name(field.get_name()).ok())
+            .map(|field| match field.data_type() {
+                DataType::Dictionary(_, value_type) => {
+                    field.clone().with_data_type(value_type.as_ref().clone())
+                }
+                _ => field.clone(),
+            })
+            .collect();
+
+        Arc::new(ArrowSchema::new(fields))
+    }
+
+    /// Scan a partitioned table.
+    ///
+    /// Projections are mapped onto delta's column order, and the output is
+    /// projected back into the declared column order. Filters on partition
+    /// columns are used by delta to prune partitions, see [`delta_filters`].
+    async fn scan_partitioned(
+        &self,
+        session: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        let schema = self.declared_schema();
+        let delta_schema = TableProvider::schema(&self.delta);
+
+        let projection = match projection {
+            Some(projection) => projection.clone(),
+            None => (0..schema.fields().len()).collect(),
+        };
+        let mut delta_projection = Vec::with_capacity(projection.len());
+        for idx in projection.iter() {
+            delta_projection.push(delta_schema.index_of(schema.field(*idx).name())?);
+        }
+
+        let filters = delta_filters(&delta_schema, filters)?;
+        let plan = self
+            .delta
+            .scan(session, Some(&delta_projection), &filters, limit)
+            .await?;
+
+        let plan_schema = plan.schema();
+        let mut exprs: Vec<(Arc<dyn PhysicalExpr>, String)> = Vec::with_capacity(projection.len());
+        for (plan_idx, idx) in projection.iter().enumerate() {
+            let field = schema.field(*idx);
+            let col: Arc<dyn PhysicalExpr> = Arc::new(Column::new(field.name(), plan_idx));
+            let expr = if plan_schema.field(plan_idx).data_type() != field.data_type() {
+                cast(col, &plan_schema, field.data_type().clone())?
+            } else {
+                col
+            };
+            exprs.push((expr, field.name().clone()));
+        }
+
+        Ok(Arc::new(ProjectionExec::try_new(exprs, plan)?))
+    }
+
     /// Create a new execution plan for inserting `input` into the table.
     pub fn insert_exec(
         &self,
@@ -284,11 +460,96 @@ impl NativeTable {
         let store = self.delta.object_store();
         let snapshot = self.delta.state.clone();
         Arc::new(NativeTableInsertExec::new(
-            input, store, snapshot, save_mode,
+            input,
+            store,
+            snapshot,
+            save_mode,
+            self.partition_columns(),
         ))
     }
 }
 
+/// Rewrite filters planned against the declared schema to match delta's
+/// schema.
+///
+/// Delta wraps string partition columns in dictionaries. Literals compared to
+/// those columns are wrapped in dictionaries too so that delta is able to
+/// evaluate the filters against partition values and prune files.
+fn delta_filters(delta_schema: &ArrowSchema, filters: &[Expr]) -> DataFusionResult<Vec<Expr>> {
+    let dict_keys: HashMap<&str, &DataType> = delta_schema
+        .fields()
+        .iter()
+        .filter_map(|field| match field.data_type() {
+            DataType::Dictionary(key_type, _) => Some((field.name().as_str(), key_type.as_ref())),
+            _ => None,
+        })
+        .collect();
+    if dict_keys.is_empty() {
+        return Ok(filters.to_vec());
+    }
+
+    let dict_key = |expr: &Expr| match expr {
+        Expr::Column(col) => dict_keys.get(col.name.as_str()).copied(),
+        _ => None,
+    };
+    let dict_literal = |expr: Expr, key_type: &DataType| match expr {
+        Expr::Literal(value) => Expr::Literal(ScalarValue::Dictionary(
+            Box::new(key_type.clone()),
+            Box::new(value),
+        )),
+        expr => expr,
+    };
+
+    filters
+        .iter()
+        .map(|filter| {
+            filter.clone().transform_up(&|expr| {
+                Ok(match expr {
+                    Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+                        match (dict_key(&left), dict_key(&right)) {
+                            (Some(key_type), None) => {
+                                Transformed::Yes(Expr::BinaryExpr(BinaryExpr {
+                                    right: Box::new(dict_literal(*right, key_type)),
+                                    left,
+                                    op,
+                                }))
+                            }
+                            (None, Some(key_type)) => {
+                                Transformed::Yes(Expr::BinaryExpr(BinaryExpr {
+                                    left: Box::new(dict_literal(*left, key_type)),
+                                    right,
+                                    op,
+                                }))
+                            }
+                            _ => Transformed::No(Expr::BinaryExpr(BinaryExpr { left, op, right })),
+                        }
+                    }
+                    Expr::InList(InList {
+                        expr,
+                        list,
+                        negated,
+                    }) => match dict_key(&expr) {
+                        Some(key_type) => Transformed::Yes(Expr::InList(InList {
+                            list: list
+                                .into_iter()
+                                .map(|item| dict_literal(item, key_type))
+                                .collect(),
+                            expr,
+                            negated,
+                        })),
+                        None => Transformed::No(Expr::InList(InList {
+                            expr,
+                            list,
+                            negated,
+                        })),
+                    },
+                    expr => Transformed::No(expr),
+                })
+            })
+        })
+        .collect()
+}
+
 #[async_trait]
 impl TableProvider for NativeTable {
     fn as_any(&self) -> &dyn Any {
@@ -296,7 +557,7 @@ impl TableProvider for NativeTable {
     }
 
     fn schema(&self) -> Arc<ArrowSchema> {
-        TableProvider::schema(&self.delta)
+        self.declared_schema()
     }
 
     fn table_type(&self) -> TableType {
@@ -324,8 +585,13 @@ impl TableProvider for NativeTable {
             .num_rows
             .unwrap_or_default();
         if stats == 0 {
-            let schema = TableProvider::schema(&self.delta);
+            let schema = TableProvider::schema(self);
             Ok(Arc::new(EmptyExec::new(false, schema)))
+        } else if !self.partition_columns().is_empty() {
+            let plan = self
+                .scan_partitioned(session, projection, filters, limit)
+                .await?;
+            Ok(Arc::new(DataSourceMetricsExecAdapter::new(plan)))
         } else {
             let plan = self.delta.scan(session, projection, filters, limit).await?;
             Ok(Arc::new(DataSourceMetricsExecAdapter::new(plan)))
@@ -341,7 +607,16 @@ impl TableProvider for NativeTable {
     }
 
     fn statistics(&self) -> Option<Statistics> {
-        self.delta.statistics()
+        let stats = self.delta.statistics()?;
+        if self.partition_columns().is_empty() {
+            Some(stats)
+        } else {
+            // Column statistics follow delta's column order, not ours.
+            Some(Statistics {
+                column_statistics: None,
+                ..stats
+            })
+        }
     }
 
     async fn insert_into(
@@ -357,7 +632,9 @@ impl TableProvider for NativeTable {
 #[cfg(test)]
 mod tests {
 
-    use datafusion::arrow::datatypes::DataType;
+    use datafusion::arrow::datatypes::{DataType, Field, Schema as ArrowSchema};
+    use datafusion::prelude::{col, lit, Expr};
+    use datafusion::scalar::ScalarValue;
     use object_store_util::conf::StorageConfig;
     use protogen::metastore::types::{
         catalog::{EntryMeta, EntryType, TableEntry},
@@ -366,7 +643,38 @@ mod tests {
     use tempfile::tempdir;
     use uuid::Uuid;
 
-    use crate::native::access::NativeTableStorage;
+    use crate::native::access::{delta_filters, NativeTableStorage};
+
+    #[test]
+    fn test_delta_filters_dictionary_partitions() {
+        let delta_schema = ArrowSchema::new(vec![
+            Field::new("amount", DataType::Int32, true),
+            Field::new(
+                "region",
+                DataType::Dictionary(Box::new(DataType::UInt16), Box::new(DataType::Utf8)),
+                true,
+            ),
+        ]);
+        let dict = |s: &str| {
+            Expr::Literal(ScalarValue::Dictionary(
+                Box::new(DataType::UInt16),
+                Box::new(ScalarValue::Utf8(Some(s.to_string()))),
+            ))
+        };
+
+        let filters = vec![
+            col("region").eq(lit("eu")),
+            lit("us").eq(col("region")).and(col("amount").gt(lit(10))),
+            col("region").in_list(vec![lit("eu"), lit("us")], false),
+        ];
+        let expected = vec![
+            col("region").eq(dict("eu")),
+            dict("us").eq(col("region")).and(col("amount").gt(lit(10))),
+            col("region").in_list(vec![dict("eu"), dict("us")], false),
+        ];
+
+        assert_eq!(expected, delta_filters(&delta_schema, &filters).unwrap());
+    }
 
     #[tokio::test]
     async fn test_delete_table() {
@@ -390,13 +698,21 @@ mod tests {
                 builtin: false,
                 external: false,
                 is_temp: false,
+                comment: None,
             },
             options: TableOptions::Internal(TableOptionsInternal {
                 columns: vec![InternalColumnDefinition {
                     name: "id".to_string(),
                     nullable: true,
                     arrow_type: DataType::Int32,
+                    default_expr: None,
+                    comment: None,
                 }],
+                partition_columns: Vec::new(),
+                primary_key: None,
+                unique_constraints: Vec::new(),
+                check_constraints: Vec::new(),
+                materialized_view: None,
             }),
             tunnel_id: None,
         };
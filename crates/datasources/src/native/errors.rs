@@ -3,6 +3,9 @@ pub enum NativeError {
     #[error(transparent)]
     DeltaTable(#[from] deltalake::DeltaTableError),
 
+    #[error(transparent)]
+    DeltaProtocol(#[from] deltalake::protocol::ProtocolError),
+
     #[error(transparent)]
     ObjectStore(#[from] object_store::Error),
 
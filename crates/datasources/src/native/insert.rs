@@ -4,7 +4,9 @@ use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
 use datafusion::execution::context::SessionState;
 use datafusion::execution::TaskContext;
-use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_expr::expressions::Column;
+use datafusion::physical_expr::{PhysicalExpr, PhysicalSortExpr};
+use datafusion::physical_plan::projection::ProjectionExec;
 use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
 use datafusion::physical_plan::{
     DisplayAs, DisplayFormatType, Distribution, ExecutionPlan, Partitioning,
@@ -25,6 +27,9 @@ pub struct NativeTableInsertExec {
     store: Arc<DeltaObjectStore>,
     snapshot: DeltaTableState,
     save_mode: SaveMode,
+    /// Columns the table is partitioned by. Rows are written to a partition
+    /// directory based on the values of these columns.
+    partition_columns: Vec<String>,
 }
 
 impl NativeTableInsertExec {
@@ -33,16 +38,48 @@ impl NativeTableInsertExec {
         store: Arc<DeltaObjectStore>,
         snapshot: DeltaTableState,
         save_mode: SaveMode,
+        partition_columns: Vec<String>,
     ) -> Self {
         NativeTableInsertExec {
             input,
             store,
             snapshot,
             save_mode,
+            partition_columns,
         }
     }
 }
 
+/// Reorder the columns of the input such that partition columns come last.
+///
+/// Delta expects partition columns to come after all other columns, while the
+/// input to the insert follows the order the columns were declared in.
+fn partition_columns_last(
+    input: Arc<dyn ExecutionPlan>,
+    partition_columns: &[String],
+) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+    if partition_columns.is_empty() {
+        return Ok(input);
+    }
+
+    let schema = input.schema();
+    let mut exprs: Vec<(Arc<dyn PhysicalExpr>, String)> = Vec::with_capacity(schema.fields().len());
+    for (idx, field) in schema.fields().iter().enumerate() {
+        if !partition_columns.contains(field.name()) {
+            exprs.push((
+                Arc::new(Column::new(field.name(), idx)),
+                field.name().clone(),
+            ));
+        }
+    }
+    for name in partition_columns {
+        let idx = schema.index_of(name)?;
+        exprs.push((Arc::new(Column::new(name, idx)), name.clone()));
+    }
+
+    Ok(Arc::new(ProjectionExec::try_new(exprs, input)?))
+}
+
 fn output_schema() -> Arc<ArrowSchema> {
     Arc::new(ArrowSchema::new(vec![Field::new(
         "count",
@@ -89,6 +126,7 @@ impl ExecutionPlan for NativeTableInsertExec {
             store: self.store.clone(),
             snapshot: self.snapshot.clone(),
             save_mode: self.save_mode.clone(),
+            partition_columns: self.partition_columns.clone(),
         }))
     }
 
@@ -114,7 +152,11 @@ impl ExecutionPlan for NativeTableInsertExec {
         let builder = WriteBuilder::new(self.store.clone(), self.snapshot.clone())
             .with_input_session_state(state)
             .with_save_mode(self.save_mode.clone())
-            .with_input_execution_plan(self.input.clone());
+            .with_partition_columns(self.partition_columns.clone())
+            .with_input_execution_plan(partition_columns_last(
+                self.input.clone(),
+                &self.partition_columns,
+            )?);
 
         let input = self.input.clone();
         let output = futures::stream::once(async move {
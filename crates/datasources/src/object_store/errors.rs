@@ -17,6 +17,9 @@ pub enum ObjectStoreSourceError {
     #[error(transparent)]
     Arrow(#[from] datafusion::arrow::error::ArrowError),
 
+    #[error(transparent)]
+    Orc(#[from] orc_rust::error::Error),
+
     #[error(transparent)]
     GlobPatternError(#[from] glob::PatternError),
 
@@ -1,10 +1,15 @@
 use std::any::Any;
 use std::fmt::{Debug, Display};
+use std::str::FromStr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use datafusion::arrow::datatypes::SchemaRef;
-use datafusion::common::FileType;
+use datafusion::common::FileCompressionType;
+use datafusion::datasource::file_format::avro::AvroFormat;
+use datafusion::datasource::file_format::csv::CsvFormat;
+use datafusion::datasource::file_format::json::JsonFormat;
+use datafusion::datasource::file_format::parquet::ParquetFormat;
 use datafusion::datasource::file_format::FileFormat;
 use datafusion::datasource::physical_plan::FileScanConfig;
 use datafusion::datasource::TableProvider;
@@ -31,6 +36,7 @@ use crate::common::url::DatasourceUrl;
 use crate::object_store::gcs::GcsStoreAccess;
 use crate::object_store::generic::GenericStoreAccess;
 use crate::object_store::local::LocalStoreAccess;
+use crate::object_store::orc::OrcFormat;
 use crate::object_store::s3::S3StoreAccess;
 
 pub mod errors;
@@ -38,6 +44,7 @@ pub mod gcs;
 pub mod generic;
 pub mod http;
 pub mod local;
+pub mod orc;
 pub mod s3;
 
 pub struct MultiSourceTableProvider {
@@ -337,11 +344,81 @@ impl TableProvider for ObjStoreTableProvider {
     }
 }
 
-pub fn file_type_from_path(path: &ObjectStorePath) -> Result<FileType> {
+/// File types that can be read from object stores.
+///
+/// Includes the file types supported by DataFusion's `FileType` as well as
+/// ones that we provide our own file formats for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObjStoreFileType {
+    Avro,
+    Csv,
+    Json,
+    Orc,
+    Parquet,
+}
+
+impl ObjStoreFileType {
+    pub const AVRO: &str = "avro";
+    pub const CSV: &str = "csv";
+    pub const JSON: &str = "json";
+    pub const ORC: &str = "orc";
+    pub const PARQUET: &str = "parquet";
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Avro => Self::AVRO,
+            Self::Csv => Self::CSV,
+            Self::Json => Self::JSON,
+            Self::Orc => Self::ORC,
+            Self::Parquet => Self::PARQUET,
+        }
+    }
+
+    /// Get the file format for reading files of this type.
+    pub fn file_format(&self, compression: FileCompressionType) -> Arc<dyn FileFormat> {
+        match self {
+            Self::Avro => Arc::new(AvroFormat),
+            Self::Csv => Arc::new(
+                CsvFormat::default()
+                    .with_file_compression_type(compression)
+                    .with_schema_infer_max_rec(Some(20480)),
+            ),
+            Self::Json => Arc::new(JsonFormat::default().with_file_compression_type(compression)),
+            Self::Orc => Arc::new(OrcFormat::default()),
+            Self::Parquet => Arc::new(ParquetFormat::default()),
+        }
+    }
+}
+
+impl Display for ObjStoreFileType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ObjStoreFileType {
+    type Err = ObjectStoreSourceError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s.to_lowercase().as_str() {
+            Self::AVRO => Self::Avro,
+            Self::CSV => Self::Csv,
+            Self::JSON | "ndjson" => Self::Json,
+            Self::ORC => Self::Orc,
+            Self::PARQUET => Self::Parquet,
+            other => {
+                return Err(ObjectStoreSourceError::NotSupportFileType(
+                    other.to_string(),
+                ))
+            }
+        })
+    }
+}
+
+pub fn file_type_from_path(path: &ObjectStorePath) -> Result<ObjStoreFileType> {
     path.extension()
         .ok_or(ObjectStoreSourceError::NoFileExtension)?
         .parse()
-        .map_err(ObjectStoreSourceError::DataFusion)
 }
 
 pub fn init_session_registry<'a>(
@@ -380,7 +457,10 @@ pub fn init_session_registry<'a>(
             | TableOptions::BigQuery(_)
             | TableOptions::Mysql(_)
             | TableOptions::Mongo(_)
-            | TableOptions::Snowflake(_) => continue,
+            | TableOptions::Snowflake(_)
+            | TableOptions::SqlServer(_)
+            | TableOptions::Clickhouse(_)
+            | TableOptions::Sqlite(_) => continue,
         };
 
         let base_url = access.base_url()?;
@@ -0,0 +1,373 @@
+//! ORC file format support.
+//!
+//! DataFusion doesn't support reading ORC files, so this provides a file
+//! format that can be used with object store table providers.
+//!
+//! The ORC reader is synchronous. Files are read with ranged requests through
+//! a blocking reader, and decoded on blocking threads.
+use std::any::Any;
+use std::fmt;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::sync::Arc;
+
+use async_stream::stream;
+use async_trait::async_trait;
+use bytes::Bytes;
+use datafusion::arrow::array::new_null_array;
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::{Schema, SchemaRef};
+use datafusion::arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use datafusion::datasource::file_format::FileFormat;
+use datafusion::datasource::physical_plan::FileScanConfig;
+use datafusion::error::{DataFusionError, Result as DatafusionResult};
+use datafusion::execution::context::{SessionState, TaskContext};
+use datafusion::physical_expr::{PhysicalExpr, PhysicalSortExpr};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream,
+    Statistics,
+};
+use object_store::path::Path as ObjectStorePath;
+use object_store::{ObjectMeta, ObjectStore};
+use orc_rust::arrow_reader::{ArrowReader, Cursor};
+use orc_rust::reader::Reader;
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+
+use super::errors::{ObjectStoreSourceError, Result};
+
+/// Default number of rows to read into a single record batch.
+const DEFAULT_BATCH_SIZE: usize = 8192;
+
+/// Minimum number of bytes to request from the object store per read.
+const READ_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Reads ORC files.
+#[derive(Debug, Default)]
+pub struct OrcFormat;
+
+#[async_trait]
+impl FileFormat for OrcFormat {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn infer_schema(
+        &self,
+        _state: &SessionState,
+        store: &Arc<dyn ObjectStore>,
+        objects: &[ObjectMeta],
+    ) -> DatafusionResult<SchemaRef> {
+        let mut schemas = Vec::with_capacity(objects.len());
+        for object in objects {
+            // Only the file's footer is read to get the schema.
+            let reader = ObjectStoreReader::new(store.clone(), object);
+            let schema = tokio::task::spawn_blocking(move || {
+                let reader = open_orc_reader(reader, None, DEFAULT_BATCH_SIZE)?;
+                Ok::<_, ObjectStoreSourceError>(reader.schema().as_ref().clone())
+            })
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+            schemas.push(schema);
+        }
+        let merged = Schema::try_merge(schemas)?;
+        Ok(Arc::new(merged))
+    }
+
+    async fn infer_stats(
+        &self,
+        _state: &SessionState,
+        _store: &Arc<dyn ObjectStore>,
+        _table_schema: SchemaRef,
+        _object: &ObjectMeta,
+    ) -> DatafusionResult<Statistics> {
+        Ok(Statistics::default())
+    }
+
+    async fn create_physical_plan(
+        &self,
+        state: &SessionState,
+        conf: FileScanConfig,
+        _filters: Option<&Arc<dyn PhysicalExpr>>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        // Filters are not applied when reading. Table providers using this
+        // format must not report filters as pushed down so that they're
+        // evaluated after the scan.
+        if !conf.table_partition_cols.is_empty() {
+            return Err(DataFusionError::NotImplemented(
+                "partition columns for ORC files".to_string(),
+            ));
+        }
+
+        let projected_schema = match &conf.projection {
+            Some(projection) => Arc::new(conf.file_schema.project(projection)?),
+            None => conf.file_schema.clone(),
+        };
+        let batch_size = state.config().batch_size();
+
+        Ok(Arc::new(OrcExec {
+            conf,
+            projected_schema,
+            batch_size,
+        }))
+    }
+}
+
+/// Open an arrow reader for an ORC file.
+///
+/// If `columns` is provided, only those columns are decoded. Names not in the
+/// file are ignored.
+fn open_orc_reader(
+    reader: ObjectStoreReader,
+    columns: Option<&[String]>,
+    batch_size: usize,
+) -> Result<ArrowReader<ObjectStoreReader>> {
+    let reader = Reader::new(reader)?;
+    let cursor = match columns {
+        Some(columns) => Cursor::new(reader, columns)?,
+        None => Cursor::root(reader)?,
+    };
+    Ok(ArrowReader::new(cursor, Some(batch_size)))
+}
+
+/// A blocking reader for an object in an object store.
+///
+/// Reads are made using ranged requests, so only the parts of the file that
+/// are needed are fetched. Requests are driven to completion on the runtime
+/// the reader was created in, so this must only be used on blocking threads.
+struct ObjectStoreReader {
+    store: Arc<dyn ObjectStore>,
+    location: ObjectStorePath,
+    size: u64,
+    pos: u64,
+    /// Bytes fetched by the last request, starting at `buf_start`.
+    buf: Bytes,
+    buf_start: u64,
+    handle: Handle,
+}
+
+impl ObjectStoreReader {
+    fn new(store: Arc<dyn ObjectStore>, meta: &ObjectMeta) -> Self {
+        ObjectStoreReader {
+            store,
+            location: meta.location.clone(),
+            size: meta.size as u64,
+            pos: 0,
+            buf: Bytes::new(),
+            buf_start: 0,
+            handle: Handle::current(),
+        }
+    }
+}
+
+impl Read for ObjectStoreReader {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        if self.pos >= self.size || out.is_empty() {
+            return Ok(0);
+        }
+
+        let buf_end = self.buf_start + self.buf.len() as u64;
+        if self.pos < self.buf_start || self.pos >= buf_end {
+            let end = (self.pos + READ_CHUNK_SIZE.max(out.len()) as u64).min(self.size);
+            let range = self.pos as usize..end as usize;
+            self.buf = self
+                .handle
+                .block_on(self.store.get_range(&self.location, range))
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            self.buf_start = self.pos;
+        }
+
+        let offset = (self.pos - self.buf_start) as usize;
+        let n = out.len().min(self.buf.len() - offset);
+        out[..n].copy_from_slice(&self.buf[offset..offset + n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for ObjectStoreReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::End(offset) => self.size.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+        };
+        match pos {
+            Some(pos) => {
+                self.pos = pos;
+                Ok(pos)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+/// Execution plan for reading ORC files.
+///
+/// Each file group is read as a single partition, with the files in the group
+/// read in order.
+#[derive(Debug)]
+pub struct OrcExec {
+    conf: FileScanConfig,
+    projected_schema: SchemaRef,
+    batch_size: usize,
+}
+
+impl ExecutionPlan for OrcExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.projected_schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(self.conf.file_groups.len())
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        Err(DataFusionError::Execution(
+            "cannot replace children for OrcExec".to_string(),
+        ))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DatafusionResult<SendableRecordBatchStream> {
+        let files = self
+            .conf
+            .file_groups
+            .get(partition)
+            .cloned()
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!("invalid partition for OrcExec: {partition}"))
+            })?;
+        let store = context
+            .runtime_env()
+            .object_store(&self.conf.object_store_url)?;
+
+        let schema = self.projected_schema.clone();
+        let batch_size = self.batch_size;
+        let mut remaining = self.conf.limit;
+
+        // Only decode the projected columns. All columns are read for empty
+        // projections since row counts are still needed.
+        let columns: Option<Vec<String>> = if schema.fields().is_empty() {
+            None
+        } else {
+            Some(schema.fields().iter().map(|f| f.name().clone()).collect())
+        };
+
+        let stream_schema = schema.clone();
+        let stream = stream! {
+            for file in files {
+                if remaining == Some(0) {
+                    break;
+                }
+
+                // Decode on a blocking thread, sending batches back.
+                let (tx, mut rx) = mpsc::channel(2);
+                let reader = ObjectStoreReader::new(store.clone(), &file.object_meta);
+                let columns = columns.clone();
+                tokio::task::spawn_blocking(move || {
+                    let reader = match open_orc_reader(reader, columns.as_deref(), batch_size) {
+                        Ok(reader) => reader,
+                        Err(e) => {
+                            let _ = tx.blocking_send(Err(DataFusionError::External(Box::new(e))));
+                            return;
+                        }
+                    };
+                    for batch in reader {
+                        // Receiver dropped, stop reading the file.
+                        if tx.blocking_send(batch.map_err(DataFusionError::from)).is_err() {
+                            return;
+                        }
+                    }
+                });
+
+                while let Some(batch) = rx.recv().await {
+                    let batch = match batch.and_then(|batch| project_batch(&stream_schema, batch)) {
+                        Ok(batch) => batch,
+                        Err(e) => {
+                            yield Err(e);
+                            return;
+                        }
+                    };
+
+                    let batch = match remaining {
+                        Some(0) => break,
+                        Some(limit) if batch.num_rows() >= limit => {
+                            remaining = Some(0);
+                            batch.slice(0, limit)
+                        }
+                        Some(limit) => {
+                            remaining = Some(limit - batch.num_rows());
+                            batch
+                        }
+                        None => batch,
+                    };
+
+                    yield Ok(batch);
+                }
+            }
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(schema, stream)))
+    }
+
+    fn statistics(&self) -> Statistics {
+        self.conf.statistics.clone()
+    }
+}
+
+impl DisplayAs for OrcExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        let files: Vec<_> = self
+            .conf
+            .file_groups
+            .iter()
+            .flatten()
+            .map(|file| file.object_meta.location.as_ref())
+            .collect();
+        write!(f, "OrcExec: files=[{}]", files.join(", "))
+    }
+}
+
+/// Project a batch read from a file onto the output schema.
+///
+/// Columns are matched by name since the table schema is merged from all
+/// files. Columns missing from the file are filled with nulls.
+fn project_batch(schema: &SchemaRef, batch: RecordBatch) -> DatafusionResult<RecordBatch> {
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| match batch.schema().index_of(field.name()) {
+            Ok(idx) => Ok(cast(batch.column(idx), field.data_type())?),
+            Err(_) => Ok(new_null_array(field.data_type(), batch.num_rows())),
+        })
+        .collect::<DatafusionResult<Vec<_>>>()?;
+
+    Ok(RecordBatch::try_new_with_options(
+        schema.clone(),
+        columns,
+        &RecordBatchOptions::new().with_row_count(Some(batch.num_rows())),
+    )?)
+}
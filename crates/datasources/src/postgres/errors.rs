@@ -18,6 +18,9 @@ pub enum PostgresError {
     #[error("Unable to copy binary row value for datatype: {0}")]
     FailedBinaryCopy(datafusion::arrow::datatypes::DataType),
 
+    #[error("Unable to create Postgres column '{0}' for datatype: {1}")]
+    UnsupportedArrowType(String, datafusion::arrow::datatypes::DataType),
+
     #[error("Failed to connect to Postgres instance: {0}")]
     TokioPostgres(#[from] tokio_postgres::Error),
 
@@ -30,6 +33,9 @@ pub enum PostgresError {
     #[error(transparent)]
     Arrow(#[from] datafusion::arrow::error::ArrowError),
 
+    #[error(transparent)]
+    DataFusion(#[from] datafusion::error::DataFusionError),
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
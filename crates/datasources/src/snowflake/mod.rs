@@ -6,7 +6,7 @@ use std::sync::Mutex;
 use std::task::{Context, Poll};
 use std::{any::Any, sync::Arc};
 
-use crate::common::util;
+use crate::common::{stats, util};
 use async_trait::async_trait;
 use datafusion::arrow::datatypes::Fields;
 use datafusion::arrow::record_batch::RecordBatch;
@@ -55,12 +55,16 @@ pub struct SnowflakeTableAccess {
 
 pub struct SnowflakeAccessor {
     conn: SnowflakeConnection,
+    /// Account and database ('account/database') we're connected to. Used to
+    /// identify tables when caching statistics.
+    database_id: String,
 }
 
 impl SnowflakeAccessor {
     pub async fn connect(conn_params: SnowflakeDbConnection) -> Result<Self> {
+        let database_id = format!("{}/{}", conn_params.account_name, conn_params.database_name);
         let conn = Self::build_conn(conn_params).await?;
-        Ok(Self { conn })
+        Ok(Self { conn, database_id })
     }
 
     async fn build_conn(conn_params: SnowflakeDbConnection) -> Result<SnowflakeConnection> {
@@ -174,6 +178,55 @@ WHERE
         Ok(ArrowSchema::new(fields))
     }
 
+    /// Get statistics for the table from `information_schema.tables`.
+    async fn get_table_statistics(
+        &self,
+        schema_name: &str,
+        table_name: &str,
+    ) -> Result<Statistics> {
+        let table_schema = schema_name.to_uppercase();
+        let table_name = table_name.to_uppercase();
+
+        let res = self
+            .conn
+            .query_sync(
+                "
+SELECT
+    row_count,
+    bytes
+FROM information_schema.tables
+WHERE
+    table_name=? AND
+    table_schema=?
+                "
+                .to_string(),
+                vec![
+                    QueryBindParameter::new_text(table_name),
+                    QueryBindParameter::new_text(table_schema),
+                ],
+            )
+            .await?;
+
+        for meta in res.into_iter() {
+            let chunk = meta.take_chunk().await?;
+
+            if let Some(row) = chunk.into_row_iter().next() {
+                let row = row?;
+                let num_rows = match row.get_column_by_name("ROW_COUNT").unwrap()? {
+                    ScalarValue::Decimal128(v, _, 0) => v.and_then(|n| u64::try_from(n).ok()),
+                    _ => None,
+                };
+                let num_bytes = match row.get_column_by_name("BYTES").unwrap()? {
+                    ScalarValue::Decimal128(v, _, 0) => v.and_then(|n| u64::try_from(n).ok()),
+                    _ => None,
+                };
+                return Ok(stats::table_statistics(num_rows, num_bytes));
+            }
+        }
+
+        Ok(Statistics::default())
+    }
+
     pub async fn into_table_provider(
         self,
         table_access: SnowflakeTableAccess,
@@ -183,11 +236,21 @@ WHERE
             .get_table_schema(&table_access.schema_name, &table_access.table_name)
             .await?;
 
+        let statistics = stats::get_or_fetch_statistics(
+            format!(
+                "snowflake/{}/{}.{}",
+                self.database_id, table_access.schema_name, table_access.table_name
+            ),
+            self.get_table_statistics(&table_access.schema_name, &table_access.table_name),
+        )
+        .await;
+
         Ok(SnowflakeTableProvider {
             predicate_pushdown,
             table_access,
             accessor: Arc::new(self),
             arrow_schema: Arc::new(arrow_schema),
+            statistics,
         })
     }
 }
@@ -294,6 +357,8 @@ pub struct SnowflakeTableProvider {
     table_access: SnowflakeTableAccess,
     accessor: Arc<SnowflakeAccessor>,
     arrow_schema: ArrowSchemaRef,
+    /// Approximate statistics for the table.
+    statistics: Statistics,
 }
 
 #[async_trait]
@@ -310,6 +375,10 @@ impl TableProvider for SnowflakeTableProvider {
         TableType::Base
     }
 
+    fn statistics(&self) -> Option<Statistics> {
+        Some(self.statistics.clone())
+    }
+
     fn supports_filter_pushdown(
         &self,
         _filter: &Expr,
@@ -372,12 +441,20 @@ impl TableProvider for SnowflakeTableProvider {
 
         let num_partitions = result.num_chunks();
 
+        let statistics = stats::estimate_scan_statistics(
+            &self.statistics,
+            &self.arrow_schema,
+            projection,
+            limit,
+        );
+
         Ok(Arc::new(SnowflakeExec {
             predicate: predicate_string,
             arrow_schema: projection_schema,
             num_partitions,
             result: Mutex::new(result),
             metrics: ExecutionPlanMetricsSet::new(),
+            statistics,
         }))
     }
 }
@@ -388,6 +465,7 @@ struct SnowflakeExec {
     num_partitions: usize,
     result: Mutex<QueryResult>,
     metrics: ExecutionPlanMetricsSet,
+    statistics: Statistics,
 }
 
 impl ExecutionPlan for SnowflakeExec {
@@ -440,7 +518,7 @@ impl ExecutionPlan for SnowflakeExec {
     }
 
     fn statistics(&self) -> Statistics {
-        Statistics::default()
+        self.statistics.clone()
     }
 
     fn metrics(&self) -> Option<MetricsSet> {
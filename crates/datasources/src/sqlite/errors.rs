@@ -0,0 +1,44 @@
+#[derive(Debug, thiserror::Error)]
+pub enum SqliteError {
+    #[error("Table {0}.{1} not found in SQLite database")]
+    TableNotFound(String, String),
+
+    #[error("Unable to convert SQLite value for column '{column}' to {datatype}: {value}")]
+    InvalidValue {
+        column: String,
+        datatype: datafusion::arrow::datatypes::DataType,
+        value: String,
+    },
+
+    #[error("Missing object name in SQLite database location: {0}")]
+    MissingObjectName(String),
+
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error(transparent)]
+    Arrow(#[from] datafusion::arrow::error::ArrowError),
+
+    #[error(transparent)]
+    ObjectStore(#[from] object_store::Error),
+
+    #[error(transparent)]
+    LakeStorageOptions(#[from] crate::lake::LakeStorageOptionsError),
+
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Fmt(#[from] std::fmt::Error),
+
+    #[error(transparent)]
+    Join(#[from] tokio::task::JoinError),
+
+    #[error(transparent)]
+    Common(#[from] crate::common::errors::DatasourceCommonError),
+}
+
+pub type Result<T, E = SqliteError> = std::result::Result<T, E>;
@@ -0,0 +1,1256 @@
+//! SQLite database files as a data source.
+//!
+//! Local files are opened directly. Databases located in an object store are
+//! first downloaded to temporary storage since SQLite requires random access
+//! to a local file. Downloads are cached, and only repeated if the object's
+//! ETag changed.
+pub mod errors;
+
+use std::any::Any;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::{self, Write};
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use crate::common::url::{DatasourceUrl, DatasourceUrlType};
+use crate::common::util;
+use crate::lake::storage_options_into_object_store;
+use async_stream::stream;
+use async_trait::async_trait;
+use dashmap::DashMap;
+use datafusion::arrow::array::{
+    ArrayRef, BinaryBuilder, BooleanBuilder, Float64Builder, Int64Builder, StringBuilder,
+};
+use datafusion::arrow::datatypes::{
+    DataType, Field, Fields, Schema as ArrowSchema, SchemaRef as ArrowSchemaRef,
+};
+use datafusion::arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use datafusion::datasource::TableProvider;
+use datafusion::error::{DataFusionError, Result as DatafusionResult};
+use datafusion::execution::context::{SessionState, TaskContext};
+use datafusion::logical_expr::{Expr, Operator, TableProviderFilterPushDown, TableType};
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
+};
+use datafusion_ext::errors::ExtensionError;
+use datafusion_ext::functions::VirtualLister;
+use datafusion_ext::metrics::DataSourceMetricsStreamAdapter;
+use futures::{Stream, StreamExt};
+use object_store::path::Path as ObjectStorePath;
+use object_store::{GetOptions, ObjectStore};
+use object_store_util::temp::TempObjectStore;
+use once_cell::sync::Lazy;
+use protogen::metastore::types::options::StorageOptions;
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+use tokio::sync::{mpsc, Mutex};
+use tracing::{debug, trace};
+
+use errors::{Result, SqliteError};
+
+/// Maximum number of downloaded databases to keep in the cache.
+const MAX_CACHED_DOWNLOADS: usize = 16;
+
+/// Process-wide cache of databases downloaded from object stores.
+static DOWNLOAD_CACHE: Lazy<DownloadCache> = Lazy::new(DownloadCache::default);
+
+/// A downloaded database along with the ETag of the object it was downloaded
+/// from.
+#[derive(Debug, Clone)]
+struct CachedDownload {
+    etag: String,
+    path: PathBuf,
+    temp: Arc<TempObjectStore>,
+}
+
+/// Cache of downloaded databases keyed by location.
+///
+/// Files are removed once they're evicted and no longer used by any accessor.
+#[derive(Debug, Default)]
+struct DownloadCache {
+    entries: DashMap<String, (Instant, CachedDownload)>,
+}
+
+impl DownloadCache {
+    fn get(&self, key: &str) -> Option<CachedDownload> {
+        let mut ent = self.entries.get_mut(key)?;
+        let (used_at, download) = ent.value_mut();
+        *used_at = Instant::now();
+        Some(download.clone())
+    }
+
+    fn put(&self, key: String, download: CachedDownload) {
+        self.entries.insert(key, (Instant::now(), download));
+        while self.entries.len() > MAX_CACHED_DOWNLOADS {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|ent| ent.value().0)
+                .map(|ent| ent.key().clone());
+            match oldest {
+                Some(key) => self.entries.remove(&key),
+                None => break,
+            };
+        }
+    }
+}
+
+/// Information needed for accessing a table in a SQLite database.
+#[derive(Debug, Clone)]
+pub struct SqliteTableAccess {
+    /// The schema the table belongs to, usually 'main'.
+    pub schema: String,
+    /// The table or view name.
+    pub name: String,
+}
+
+impl SqliteTableAccess {
+    /// The schema for tables in the database file itself.
+    pub const DEFAULT_SCHEMA: &'static str = "main";
+}
+
+#[derive(Debug)]
+pub struct SqliteAccessor {
+    /// Location of the database as provided by the user.
+    location: String,
+    conn: Arc<Mutex<Connection>>,
+    /// Temporary storage for databases downloaded from object stores.
+    ///
+    /// Kept on struct to avoid deleting the file while it's being read.
+    _temp: Option<Arc<TempObjectStore>>,
+}
+
+impl SqliteAccessor {
+    /// Name of the file databases are downloaded to in temporary storage.
+    const TEMP_FILE_NAME: &'static str = "database.sqlite";
+
+    /// Open the SQLite database at the given location.
+    ///
+    /// The location may either be a path to a local file or an object store
+    /// url.
+    pub async fn connect(location: &str, storage_options: StorageOptions) -> Result<Self> {
+        let url = DatasourceUrl::try_new(location)?;
+
+        let (path, temp) = match &url {
+            DatasourceUrl::File(path) => (path.clone(), None),
+            DatasourceUrl::Url(_) => {
+                let (path, temp) = Self::download(&url, &storage_options).await?;
+                (path, Some(temp))
+            }
+        };
+
+        let conn = tokio::task::spawn_blocking(move || {
+            Connection::open_with_flags(
+                path,
+                OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            )
+        })
+        .await??;
+
+        Ok(Self {
+            location: location.to_string(),
+            conn: Arc::new(Mutex::new(conn)),
+            _temp: temp,
+        })
+    }
+
+    /// Download the database into temporary storage, returning the local path
+    /// to the downloaded file.
+    ///
+    /// A previously downloaded copy is reused if the ETag of the remote object
+    /// hasn't changed.
+    async fn download(
+        url: &DatasourceUrl,
+        storage_options: &StorageOptions,
+    ) -> Result<(PathBuf, Arc<TempObjectStore>)> {
+        if url.path().is_empty() {
+            return Err(SqliteError::MissingObjectName(url.to_string()));
+        }
+
+        let key = url.to_string();
+        let cached = DOWNLOAD_CACHE.get(&key);
+
+        let (etag, bytes) = match url.datasource_url_type() {
+            DatasourceUrlType::Http => {
+                let mut req = reqwest::Client::new().get(url.as_url()?);
+                if let Some(cached) = &cached {
+                    req = req.header(reqwest::header::IF_NONE_MATCH, &cached.etag);
+                }
+                let resp = req.send().await?;
+                if let (reqwest::StatusCode::NOT_MODIFIED, Some(cached)) = (resp.status(), &cached)
+                {
+                    trace!(%url, "reusing downloaded sqlite database");
+                    return Ok((cached.path.clone(), cached.temp.clone()));
+                }
+                let resp = resp.error_for_status()?;
+                let etag = resp
+                    .headers()
+                    .get(reqwest::header::ETAG)
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| v.to_string());
+                (etag, resp.bytes().await?)
+            }
+            _ => {
+                let store = storage_options_into_object_store(url, storage_options)?;
+                let location = ObjectStorePath::from(url.path().as_ref());
+                let meta = store.head(&location).await?;
+                if let (Some(cached), Some(etag)) = (&cached, &meta.e_tag) {
+                    if &cached.etag == etag {
+                        trace!(%url, "reusing downloaded sqlite database");
+                        return Ok((cached.path.clone(), cached.temp.clone()));
+                    }
+                }
+                // Ensure we download the version of the object the ETag is
+                // for.
+                let options = GetOptions {
+                    if_match: meta.e_tag.clone(),
+                    ..Default::default()
+                };
+                let bytes = store.get_opts(&location, options).await?.bytes().await?;
+                (meta.e_tag, bytes)
+            }
+        };
+        trace!(%url, size = bytes.len(), "downloaded sqlite database");
+
+        let temp = Arc::new(TempObjectStore::new()?);
+        temp.put(&ObjectStorePath::from(Self::TEMP_FILE_NAME), bytes)
+            .await?;
+        let path = temp.local_path().join(Self::TEMP_FILE_NAME);
+
+        if let Some(etag) = etag {
+            DOWNLOAD_CACHE.put(
+                key,
+                CachedDownload {
+                    etag,
+                    path: path.clone(),
+                    temp: temp.clone(),
+                },
+            );
+        }
+
+        Ok((path, temp))
+    }
+
+    /// Run a function against the connection on a blocking thread.
+    async fn with_conn<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&Connection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.blocking_lock();
+            f(&conn)
+        })
+        .await?
+    }
+
+    /// Validate that the location points to a readable SQLite database.
+    pub async fn validate_external_database(
+        location: &str,
+        storage_options: StorageOptions,
+    ) -> Result<()> {
+        let accessor = Self::connect(location, storage_options).await?;
+        // Opening a file is lazy, reading the schema ensures it's actually a
+        // SQLite database.
+        accessor
+            .with_conn(|conn| {
+                conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Validate access to the table in the SQLite database.
+    pub async fn validate_table_access(
+        location: &str,
+        storage_options: StorageOptions,
+        access: &SqliteTableAccess,
+    ) -> Result<()> {
+        let accessor = Self::connect(location, storage_options).await?;
+        let access = access.clone();
+        accessor
+            .with_conn(move |conn| table_schema(conn, &access.schema, &access.name).map(|_| ()))
+            .await
+    }
+
+    pub async fn into_table_provider(
+        self,
+        table_access: SqliteTableAccess,
+        predicate_pushdown: bool,
+    ) -> Result<SqliteTableProvider> {
+        let access = table_access.clone();
+        let schema = self
+            .with_conn(move |conn| table_schema(conn, &access.schema, &access.name))
+            .await?;
+
+        Ok(SqliteTableProvider {
+            predicate_pushdown,
+            table_access,
+            accessor: Arc::new(self),
+            arrow_schema: Arc::new(schema.arrow_schema),
+            filterable: Arc::new(schema.filterable),
+        })
+    }
+}
+
+#[async_trait]
+impl VirtualLister for SqliteAccessor {
+    async fn list_schemas(&self) -> Result<Vec<String>, ExtensionError> {
+        use ExtensionError::ListingErrBoxed;
+
+        self.with_conn(|conn| {
+            let mut stmt =
+                conn.prepare("SELECT name FROM pragma_database_list WHERE name <> 'temp'")?;
+            let schemas = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()?;
+            Ok(schemas)
+        })
+        .await
+        .map_err(|e| ListingErrBoxed(Box::new(e)))
+    }
+
+    async fn list_tables(&self, schema: &str) -> Result<Vec<String>, ExtensionError> {
+        use ExtensionError::ListingErrBoxed;
+
+        let query = format!(
+            "SELECT name FROM {}.sqlite_master
+             WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%'",
+            quote_ident(schema)
+        );
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(&query)?;
+            let tables = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()?;
+            Ok(tables)
+        })
+        .await
+        .map_err(|e| ListingErrBoxed(Box::new(e)))
+    }
+
+    async fn list_columns(&self, schema: &str, table: &str) -> Result<Fields, ExtensionError> {
+        use ExtensionError::ListingErrBoxed;
+
+        let (schema, table) = (schema.to_string(), table.to_string());
+        let schema = self
+            .with_conn(move |conn| table_schema(conn, &schema, &table))
+            .await
+            .map_err(|e| ListingErrBoxed(Box::new(e)))?;
+
+        Ok(schema.arrow_schema.fields)
+    }
+}
+
+/// Number of rows sampled when inferring the type of a column without a
+/// declared type.
+const INFER_SAMPLE_SIZE: usize = 1000;
+
+/// The arrow schema for a SQLite table.
+#[derive(Debug)]
+struct TableSchema {
+    arrow_schema: ArrowSchema,
+    /// Columns that filters can be pushed down for.
+    ///
+    /// Only columns with a declared text, integer, or real type are included.
+    /// SQLite applies the column's affinity to literals when comparing, so
+    /// comparisons behave as they would on the converted arrow values. This
+    /// isn't the case for columns without an affinity, or for columns
+    /// converted to booleans.
+    filterable: HashSet<String>,
+}
+
+/// Get the arrow schema for a table.
+///
+/// SQLite is dynamically typed, so the declared types of columns are only
+/// hints as to what's actually stored. We follow SQLite's type affinity rules
+/// for declared types, and fall back to sampling values for columns with a
+/// numeric affinity or without a declared type.
+fn table_schema(conn: &Connection, schema: &str, table: &str) -> Result<TableSchema> {
+    let mut stmt = conn.prepare(&format!(
+        "PRAGMA {}.table_info({})",
+        quote_ident(schema),
+        quote_ident(table)
+    ))?;
+    let columns = stmt
+        .query_map([], |row| {
+            let name: String = row.get(1)?;
+            let typ: String = row.get(2)?;
+            let not_null: bool = row.get(3)?;
+            Ok((name, typ, not_null))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if columns.is_empty() {
+        return Err(SqliteError::TableNotFound(
+            schema.to_string(),
+            table.to_string(),
+        ));
+    }
+
+    let mut fields = Vec::with_capacity(columns.len());
+    let mut filterable = HashSet::new();
+    for (name, typ, not_null) in columns {
+        let datatype = match declared_type_to_arrow(&typ) {
+            Some(datatype) => {
+                if matches!(
+                    datatype,
+                    DataType::Int64 | DataType::Float64 | DataType::Utf8
+                ) {
+                    filterable.insert(name.clone());
+                }
+                datatype
+            }
+            None => infer_column_type(conn, schema, table, &name)?,
+        };
+        fields.push(Field::new(name, datatype, !not_null));
+    }
+
+    Ok(TableSchema {
+        arrow_schema: ArrowSchema::new(fields),
+        filterable,
+    })
+}
+
+/// Get the arrow type for a column's declared type.
+///
+/// Returns `None` if the type of the column should be inferred from its
+/// values.
+///
+/// See <https://www.sqlite.org/datatype3.html#determination_of_column_affinity>
+fn declared_type_to_arrow(typ: &str) -> Option<DataType> {
+    let typ = typ.to_ascii_uppercase();
+    if typ.starts_with("BOOL") {
+        // Not an affinity, but commonly used and stored as 0 or 1.
+        Some(DataType::Boolean)
+    } else if typ.contains("INT") {
+        Some(DataType::Int64)
+    } else if typ.contains("CHAR") || typ.contains("CLOB") || typ.contains("TEXT") {
+        Some(DataType::Utf8)
+    } else if typ.contains("BLOB") {
+        Some(DataType::Binary)
+    } else if typ.contains("REAL") || typ.contains("FLOA") || typ.contains("DOUB") {
+        Some(DataType::Float64)
+    } else {
+        // Numeric affinity (or no declared type), values can be of any type.
+        None
+    }
+}
+
+/// Infer the type of a column by sampling the types of stored values.
+fn infer_column_type(
+    conn: &Connection,
+    schema: &str,
+    table: &str,
+    column: &str,
+) -> Result<DataType> {
+    let column = quote_ident(column);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT DISTINCT typeof({column}) FROM (SELECT {column} FROM {}.{} LIMIT {INFER_SAMPLE_SIZE})",
+        quote_ident(schema),
+        quote_ident(table),
+    ))?;
+    let types = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<BTreeSet<String>, _>>()?;
+
+    let datatype = sampled_types_to_arrow(&types);
+    let storage_classes = match datatype {
+        DataType::Int64 => "'integer'",
+        DataType::Float64 => "'integer', 'real'",
+        DataType::Binary => "'blob'",
+        _ => return Ok(datatype),
+    };
+
+    // Values past the sample may be of a different type. Fall back to text
+    // instead of failing when reading those values.
+    let mut stmt = conn.prepare(&format!(
+        "SELECT 1 FROM {}.{} WHERE typeof({column}) NOT IN ('null', {storage_classes}) LIMIT 1",
+        quote_ident(schema),
+        quote_ident(table),
+    ))?;
+    if stmt.exists([])? {
+        return Ok(DataType::Utf8);
+    }
+
+    Ok(datatype)
+}
+
+/// Get the arrow type able to represent all sampled SQLite storage classes.
+fn sampled_types_to_arrow(types: &BTreeSet<String>) -> DataType {
+    let types: Vec<_> = types
+        .iter()
+        .map(|s| s.as_str())
+        .filter(|s| *s != "null")
+        .collect();
+
+    match types.as_slice() {
+        ["integer"] => DataType::Int64,
+        ["integer", "real"] | ["real"] => DataType::Float64,
+        ["blob"] => DataType::Binary,
+        // Text, mixed types, or only nulls.
+        _ => DataType::Utf8,
+    }
+}
+
+pub struct SqliteTableProvider {
+    predicate_pushdown: bool,
+    table_access: SqliteTableAccess,
+    accessor: Arc<SqliteAccessor>,
+    arrow_schema: ArrowSchemaRef,
+    filterable: Arc<HashSet<String>>,
+}
+
+#[async_trait]
+impl TableProvider for SqliteTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        self.arrow_schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    fn supports_filter_pushdown(
+        &self,
+        _filter: &Expr,
+    ) -> DatafusionResult<TableProviderFilterPushDown> {
+        Ok(TableProviderFilterPushDown::Inexact)
+    }
+
+    async fn scan(
+        &self,
+        _ctx: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        // Project the schema.
+        let projected_schema = match projection {
+            Some(projection) => Arc::new(self.arrow_schema.project(projection)?),
+            None => self.arrow_schema.clone(),
+        };
+
+        // Get the projected columns, joined by a ','. This will be put in the
+        // 'SELECT ...' portion of the query.
+        let projection_string = if projected_schema.fields.is_empty() {
+            "1".to_string()
+        } else {
+            projected_schema
+                .fields
+                .iter()
+                .map(|f| quote_ident(f.name()))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        let limit_string = match limit {
+            Some(limit) => format!("LIMIT {limit}"),
+            None => String::new(),
+        };
+
+        // Build WHERE clause if predicate pushdown enabled.
+        let predicate_string = {
+            if self.predicate_pushdown {
+                exprs_to_predicate_string(filters, &self.filterable)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?
+            } else {
+                String::new()
+            }
+        };
+
+        let query: String = format!(
+            "SELECT {} FROM {}.{} {} {} {}",
+            projection_string,
+            quote_ident(&self.table_access.schema),
+            quote_ident(&self.table_access.name),
+            // [WHERE]
+            if predicate_string.is_empty() {
+                ""
+            } else {
+                "WHERE "
+            },
+            predicate_string.as_str(),
+            limit_string,
+        );
+        trace!(?query);
+
+        Ok(Arc::new(SqliteExec {
+            predicate: predicate_string,
+            table_access: self.table_access.clone(),
+            accessor: self.accessor.clone(),
+            query,
+            arrow_schema: projected_schema,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct SqliteExec {
+    predicate: String,
+    table_access: SqliteTableAccess,
+    accessor: Arc<SqliteAccessor>,
+    query: String,
+    arrow_schema: ArrowSchemaRef,
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl ExecutionPlan for SqliteExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        self.arrow_schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        Err(DataFusionError::Execution(
+            "cannot replace children for SqliteExec".to_string(),
+        ))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> DatafusionResult<SendableRecordBatchStream> {
+        let stream = SqliteQueryStream::open(
+            self.query.clone(),
+            self.accessor.clone(),
+            self.arrow_schema.clone(),
+        );
+
+        Ok(Box::pin(DataSourceMetricsStreamAdapter::new(
+            stream,
+            partition,
+            &self.metrics,
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
+
+impl DisplayAs for SqliteExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SqliteExec: location={}, schema={}, name={}, predicate={}",
+            self.accessor.location,
+            self.table_access.schema,
+            self.table_access.name,
+            if self.predicate.is_empty() {
+                "None"
+            } else {
+                self.predicate.as_str()
+            }
+        )
+    }
+}
+
+struct SqliteQueryStream {
+    arrow_schema: ArrowSchemaRef,
+    inner: Pin<Box<dyn Stream<Item = DatafusionResult<RecordBatch>> + Send>>,
+}
+
+impl SqliteQueryStream {
+    /// Number of SQLite rows to process into an arrow record batch
+    // TOOD: Allow configuration
+    const SQLITE_RECORD_BATCH_SIZE: usize = 1000;
+
+    fn open(query: String, accessor: Arc<SqliteAccessor>, arrow_schema: ArrowSchemaRef) -> Self {
+        let schema = arrow_schema.clone();
+
+        let stream = stream! {
+            // Rows are read on a blocking thread, and sent back as record
+            // batches.
+            let (tx, mut rx) = mpsc::channel(2);
+            let conn = accessor.conn.clone();
+            let handle = tokio::task::spawn_blocking(move || {
+                let conn = conn.blocking_lock();
+                let result = read_record_batches(
+                    &conn,
+                    &query,
+                    arrow_schema,
+                    Self::SQLITE_RECORD_BATCH_SIZE,
+                    |batch| tx.blocking_send(batch).is_ok(),
+                );
+                if let Err(e) = result {
+                    let _ = tx.blocking_send(Err(e));
+                }
+            });
+
+            while let Some(batch) = rx.recv().await {
+                yield batch.map_err(|e| DataFusionError::External(Box::new(e)));
+            }
+
+            handle.await.map_err(|e| DataFusionError::External(Box::new(e)))?;
+        };
+
+        Self {
+            arrow_schema: schema,
+            inner: stream.boxed(),
+        }
+    }
+}
+
+impl Stream for SqliteQueryStream {
+    type Item = DatafusionResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+impl RecordBatchStream for SqliteQueryStream {
+    fn schema(&self) -> ArrowSchemaRef {
+        self.arrow_schema.clone()
+    }
+}
+
+/// Execute the query, passing record batches of at most `batch_size` rows to
+/// `send`.
+///
+/// Stops early if `send` returns false.
+fn read_record_batches<F>(
+    conn: &Connection,
+    query: &str,
+    schema: ArrowSchemaRef,
+    batch_size: usize,
+    mut send: F,
+) -> Result<()>
+where
+    F: FnMut(Result<RecordBatch>) -> bool,
+{
+    let mut stmt = conn.prepare(query)?;
+    let mut rows = stmt.query([])?;
+
+    let mut builders = new_builders(&schema, batch_size);
+    let mut num_rows = 0;
+
+    while let Some(row) = rows.next()? {
+        for (idx, builder) in builders.iter_mut().enumerate() {
+            builder.append(schema.field(idx), row.get_ref(idx)?)?;
+        }
+        num_rows += 1;
+
+        if num_rows == batch_size {
+            let batch = finish_builders(&schema, &mut builders, num_rows);
+            if !send(batch) {
+                return Ok(());
+            }
+            num_rows = 0;
+        }
+    }
+
+    if num_rows > 0 {
+        let batch = finish_builders(&schema, &mut builders, num_rows);
+        send(batch);
+    }
+
+    Ok(())
+}
+
+fn new_builders(schema: &ArrowSchema, capacity: usize) -> Vec<ColumnBuilder> {
+    schema
+        .fields
+        .iter()
+        .map(|f| match f.data_type() {
+            DataType::Boolean => ColumnBuilder::Boolean(BooleanBuilder::with_capacity(capacity)),
+            DataType::Int64 => ColumnBuilder::Int64(Int64Builder::with_capacity(capacity)),
+            DataType::Float64 => ColumnBuilder::Float64(Float64Builder::with_capacity(capacity)),
+            DataType::Binary => ColumnBuilder::Binary(BinaryBuilder::new()),
+            // Schemas only ever contain the above types and strings.
+            _ => ColumnBuilder::Utf8(StringBuilder::new()),
+        })
+        .collect()
+}
+
+fn finish_builders(
+    schema: &ArrowSchemaRef,
+    builders: &mut [ColumnBuilder],
+    num_rows: usize,
+) -> Result<RecordBatch> {
+    let columns: Vec<_> = builders.iter_mut().map(|b| b.finish()).collect();
+    // Needed for empty projections (e.g. 'SELECT count(*)').
+    let options = RecordBatchOptions::new().with_row_count(Some(num_rows));
+    Ok(RecordBatch::try_new_with_options(
+        schema.clone(),
+        columns,
+        &options,
+    )?)
+}
+
+/// Builds an arrow array from dynamically typed SQLite values.
+enum ColumnBuilder {
+    Boolean(BooleanBuilder),
+    Int64(Int64Builder),
+    Float64(Float64Builder),
+    Utf8(StringBuilder),
+    Binary(BinaryBuilder),
+}
+
+impl ColumnBuilder {
+    /// Append a value, converting it to the builder's type if the value's
+    /// storage class doesn't match.
+    ///
+    /// SQLite lets columns hold values of any type regardless of their declared
+    /// type. Values that can't be converted to a boolean or numeric column's
+    /// type are read as NULL.
+    fn append(&mut self, field: &Field, value: ValueRef) -> Result<()> {
+        let invalid = || SqliteError::InvalidValue {
+            column: field.name().clone(),
+            datatype: field.data_type().clone(),
+            value: format!("{value:?}"),
+        };
+
+        if let ValueRef::Null = value {
+            match self {
+                Self::Boolean(b) => b.append_null(),
+                Self::Int64(b) => b.append_null(),
+                Self::Float64(b) => b.append_null(),
+                Self::Utf8(b) => b.append_null(),
+                Self::Binary(b) => b.append_null(),
+            }
+            return Ok(());
+        }
+
+        match self {
+            Self::Boolean(b) => {
+                let v = match value {
+                    ValueRef::Integer(v) => Some(v != 0),
+                    ValueRef::Real(v) => Some(v != 0.0),
+                    ValueRef::Text(v) => {
+                        match String::from_utf8_lossy(v).to_ascii_lowercase().as_str() {
+                            "1" | "t" | "true" => Some(true),
+                            "0" | "f" | "false" => Some(false),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                b.append_option(v);
+            }
+            Self::Int64(b) => {
+                let v = match value {
+                    ValueRef::Integer(v) => Some(v),
+                    ValueRef::Real(v) if v.fract() == 0.0 => Some(v as i64),
+                    ValueRef::Text(v) => std::str::from_utf8(v)
+                        .ok()
+                        .and_then(|s| s.trim().parse().ok()),
+                    _ => None,
+                };
+                b.append_option(v);
+            }
+            Self::Float64(b) => {
+                let v = match value {
+                    ValueRef::Integer(v) => Some(v as f64),
+                    ValueRef::Real(v) => Some(v),
+                    ValueRef::Text(v) => std::str::from_utf8(v)
+                        .ok()
+                        .and_then(|s| s.trim().parse().ok()),
+                    _ => None,
+                };
+                b.append_option(v);
+            }
+            Self::Utf8(b) => match value {
+                ValueRef::Integer(v) => b.append_value(v.to_string()),
+                ValueRef::Real(v) => b.append_value(v.to_string()),
+                ValueRef::Text(v) | ValueRef::Blob(v) => {
+                    b.append_value(std::str::from_utf8(v).map_err(|_| invalid())?)
+                }
+                ValueRef::Null => unreachable!("nulls handled above"),
+            },
+            Self::Binary(b) => match value {
+                ValueRef::Integer(v) => b.append_value(v.to_string()),
+                ValueRef::Real(v) => b.append_value(v.to_string()),
+                ValueRef::Text(v) | ValueRef::Blob(v) => b.append_value(v),
+                ValueRef::Null => unreachable!("nulls handled above"),
+            },
+        }
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            Self::Boolean(b) => Arc::new(b.finish()),
+            Self::Int64(b) => Arc::new(b.finish()),
+            Self::Float64(b) => Arc::new(b.finish()),
+            Self::Utf8(b) => Arc::new(b.finish()),
+            Self::Binary(b) => Arc::new(b.finish()),
+        }
+    }
+}
+
+/// Quote an identifier using double quotes.
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// Convert filtering expressions to a predicate string usable with the
+/// generated SQLite query.
+///
+/// Expressions referencing columns not in `filterable` are skipped.
+fn exprs_to_predicate_string(exprs: &[Expr], filterable: &HashSet<String>) -> Result<String> {
+    let mut ss = Vec::new();
+    let mut buf = String::new();
+    for expr in exprs {
+        if write_expr(expr, filterable, &mut buf)? {
+            ss.push(buf);
+            buf = String::new();
+        } else {
+            // Drop anything partially written for the unsupported expression.
+            buf.clear();
+        }
+    }
+    Ok(ss.join(" AND "))
+}
+
+/// Try to write the expression to the string, returning true if it was written.
+fn write_expr(expr: &Expr, filterable: &HashSet<String>, buf: &mut String) -> Result<bool> {
+    match expr {
+        Expr::Column(col) => {
+            if !filterable.contains(&col.name) {
+                return Ok(false);
+            }
+            write!(buf, "{}", quote_ident(&col.name))?;
+        }
+        Expr::Literal(val) => {
+            util::encode_literal_to_text(util::Datasource::Sqlite, buf, val)?;
+        }
+        Expr::IsNull(expr) => {
+            if write_expr(expr, filterable, buf)? {
+                write!(buf, " IS NULL")?;
+            } else {
+                return Ok(false);
+            }
+        }
+        Expr::IsNotNull(expr) => {
+            if write_expr(expr, filterable, buf)? {
+                write!(buf, " IS NOT NULL")?;
+            } else {
+                return Ok(false);
+            }
+        }
+        Expr::IsTrue(expr) => {
+            if write_expr(expr, filterable, buf)? {
+                write!(buf, " IS TRUE")?;
+            } else {
+                return Ok(false);
+            }
+        }
+        Expr::IsFalse(expr) => {
+            if write_expr(expr, filterable, buf)? {
+                write!(buf, " IS FALSE")?;
+            } else {
+                return Ok(false);
+            }
+        }
+        Expr::BinaryExpr(binary) if sqlite_operator(binary.op) => {
+            write!(buf, "(")?;
+            if !write_expr(binary.left.as_ref(), filterable, buf)? {
+                return Ok(false);
+            }
+            write!(buf, " {} ", binary.op)?;
+            if !write_expr(binary.right.as_ref(), filterable, buf)? {
+                return Ok(false);
+            }
+            write!(buf, ")")?;
+        }
+        expr => {
+            // Unsupported.
+            debug!(?expr, "Unsupported filter used");
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Returns true if SQLite supports the operator with the same syntax and
+/// semantics.
+fn sqlite_operator(op: Operator) -> bool {
+    matches!(
+        op,
+        Operator::Eq
+            | Operator::NotEq
+            | Operator::Lt
+            | Operator::LtEq
+            | Operator::Gt
+            | Operator::GtEq
+            | Operator::Plus
+            | Operator::Minus
+            | Operator::Multiply
+            | Operator::Divide
+            | Operator::Modulo
+            | Operator::And
+            | Operator::Or
+            | Operator::BitwiseAnd
+            | Operator::BitwiseOr
+            | Operator::BitwiseShiftLeft
+            | Operator::BitwiseShiftRight
+            | Operator::StringConcat
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::array::Array;
+    use datafusion::common::Column;
+    use datafusion::logical_expr::BinaryExpr;
+    use datafusion::scalar::ScalarValue;
+
+    use super::*;
+
+    fn test_conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE t (
+                 a INTEGER NOT NULL,
+                 b VARCHAR(20),
+                 c DOUBLE,
+                 d BLOB,
+                 e BOOLEAN,
+                 f NUMERIC,
+                 g
+             );
+             INSERT INTO t VALUES (1, 'one', 1.5, X'01', 1, 1, 'x');
+             INSERT INTO t VALUES (2, NULL, 2, NULL, 0, 2.5, 3);
+             INSERT INTO t VALUES (3, 3, '3.5', X'03', NULL, NULL, NULL);",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn infer_schema() {
+        let conn = test_conn();
+        let schema = table_schema(&conn, "main", "t").unwrap();
+
+        let expected = ArrowSchema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::Float64, true),
+            Field::new("d", DataType::Binary, true),
+            Field::new("e", DataType::Boolean, true),
+            // Sampled: integer and real values.
+            Field::new("f", DataType::Float64, true),
+            // Sampled: text and integer values.
+            Field::new("g", DataType::Utf8, true),
+        ]);
+        assert_eq!(expected, schema.arrow_schema);
+
+        // Only columns with a declared text or numeric type are filterable.
+        let expected: HashSet<_> = ["a", "b", "c"].into_iter().map(String::from).collect();
+        assert_eq!(expected, schema.filterable);
+
+        table_schema(&conn, "main", "missing").unwrap_err();
+    }
+
+    #[test]
+    fn infer_schema_past_sample() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "CREATE TABLE t (a, b);
+             WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < {INFER_SAMPLE_SIZE})
+             INSERT INTO t SELECT x, x FROM n;
+             INSERT INTO t VALUES (0, 'text');",
+        ))
+        .unwrap();
+
+        let schema = table_schema(&conn, "main", "t").unwrap();
+        let expected = ArrowSchema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            // Text value after the sampled rows.
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        assert_eq!(expected, schema.arrow_schema);
+    }
+
+    #[test]
+    fn read_dynamically_typed_values() {
+        let conn = test_conn();
+        let schema = Arc::new(table_schema(&conn, "main", "t").unwrap().arrow_schema);
+
+        let mut batches = Vec::new();
+        read_record_batches(&conn, "SELECT * FROM \"main\".\"t\"", schema, 2, |batch| {
+            batches.push(batch.unwrap());
+            true
+        })
+        .unwrap();
+
+        assert_eq!(2, batches.len());
+        assert_eq!(2, batches[0].num_rows());
+        assert_eq!(1, batches[1].num_rows());
+
+        // Integer stored in a column inferred as text is converted.
+        let g = batches[0]
+            .column(6)
+            .as_any()
+            .downcast_ref::<datafusion::arrow::array::StringArray>()
+            .unwrap();
+        assert_eq!("x", g.value(0));
+        assert_eq!("3", g.value(1));
+
+        // As is an integer stored in a column inferred as float.
+        let f = batches[0]
+            .column(5)
+            .as_any()
+            .downcast_ref::<datafusion::arrow::array::Float64Array>()
+            .unwrap();
+        assert_eq!(1.0, f.value(0));
+        assert_eq!(2.5, f.value(1));
+    }
+
+    #[test]
+    fn read_mismatched_values_as_null() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE t (a INT, b REAL, c BOOLEAN);
+             INSERT INTO t VALUES (1, 1.5, 1);
+             INSERT INTO t VALUES ('one', 'one and a half', 'yes');",
+        )
+        .unwrap();
+        let schema = Arc::new(table_schema(&conn, "main", "t").unwrap().arrow_schema);
+
+        let mut batches = Vec::new();
+        read_record_batches(&conn, "SELECT * FROM \"main\".\"t\"", schema, 10, |batch| {
+            batches.push(batch.unwrap());
+            true
+        })
+        .unwrap();
+
+        assert_eq!(1, batches.len());
+        for col in batches[0].columns() {
+            assert!(col.is_valid(0));
+            assert!(col.is_null(1));
+        }
+    }
+
+    #[test]
+    fn skip_unsupported_operators() {
+        let col = |name: &str| {
+            Box::new(Expr::Column(Column {
+                relation: None,
+                name: name.to_string(),
+            }))
+        };
+        let exprs = vec![
+            Expr::BinaryExpr(BinaryExpr {
+                left: col("a"),
+                op: Operator::RegexMatch,
+                right: Box::new(Expr::Literal(ScalarValue::Utf8(Some("^a".to_string())))),
+            }),
+            Expr::BinaryExpr(BinaryExpr {
+                left: col("a"),
+                op: Operator::RegexIMatch,
+                right: Box::new(Expr::Literal(ScalarValue::Utf8(Some("^a".to_string())))),
+            }),
+            Expr::BinaryExpr(BinaryExpr {
+                left: col("a"),
+                op: Operator::Eq,
+                right: Box::new(Expr::Literal(ScalarValue::Utf8(Some("a".to_string())))),
+            }),
+        ];
+
+        let filterable = ["a"].into_iter().map(String::from).collect();
+        let out = exprs_to_predicate_string(&exprs, &filterable).unwrap();
+        assert_eq!(out, "(\"a\" = 'a')");
+    }
+
+    #[test]
+    fn valid_expr_string() {
+        let exprs = vec![
+            Expr::BinaryExpr(BinaryExpr {
+                left: Box::new(Expr::Column(Column {
+                    relation: None,
+                    name: "a".to_string(),
+                })),
+                op: Operator::Lt,
+                right: Box::new(Expr::Column(Column {
+                    relation: None,
+                    name: "b".to_string(),
+                })),
+            }),
+            Expr::IsTrue(Box::new(Expr::Column(Column {
+                relation: None,
+                name: "c".to_string(),
+            }))),
+            Expr::BinaryExpr(BinaryExpr {
+                left: Box::new(Expr::Column(Column {
+                    relation: None,
+                    name: "d".to_string(),
+                })),
+                op: Operator::Eq,
+                right: Box::new(Expr::Literal(ScalarValue::Binary(Some(vec![1, 2])))),
+            }),
+        ];
+
+        let filterable = ["a", "b", "c", "d"].into_iter().map(String::from).collect();
+        let out = exprs_to_predicate_string(&exprs, &filterable).unwrap();
+        assert_eq!(
+            out,
+            "(\"a\" < \"b\") AND \"c\" IS TRUE AND (\"d\" = X'0102')"
+        )
+    }
+
+    #[test]
+    fn skip_unfilterable_columns() {
+        let exprs = vec![
+            Expr::BinaryExpr(BinaryExpr {
+                left: Box::new(Expr::Column(Column {
+                    relation: None,
+                    name: "a".to_string(),
+                })),
+                op: Operator::Eq,
+                right: Box::new(Expr::Literal(ScalarValue::Int64(Some(1)))),
+            }),
+            Expr::BinaryExpr(BinaryExpr {
+                left: Box::new(Expr::Column(Column {
+                    relation: None,
+                    name: "g".to_string(),
+                })),
+                op: Operator::Eq,
+                right: Box::new(Expr::Literal(ScalarValue::Utf8(Some("3".to_string())))),
+            }),
+        ];
+
+        let filterable = ["a"].into_iter().map(String::from).collect();
+        let out = exprs_to_predicate_string(&exprs, &filterable).unwrap();
+        assert_eq!(out, "(\"a\" = 1)")
+    }
+}
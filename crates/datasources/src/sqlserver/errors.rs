@@ -0,0 +1,42 @@
+#[derive(Debug, thiserror::Error)]
+pub enum SqlServerError {
+    #[error("Unsupported SQL Server type: {0}, column: {1}")]
+    UnsupportedSqlServerType(String, String),
+
+    #[error("Unable to convert SQL Server row value for column {0}: {1}, datatype: {2}")]
+    UnsupportedArrowType(usize, String, datafusion::arrow::datatypes::DataType),
+
+    #[error("Table {0}.{1} not found in SQL Server")]
+    TableNotFound(String, String),
+
+    #[error("Invalid SQL Server address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Unsupported tunnel '{0}' for SQL Server")]
+    UnsupportedTunnel(String),
+
+    #[error(transparent)]
+    Arrow(#[from] datafusion::arrow::error::ArrowError),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    TryFromInt(#[from] std::num::TryFromIntError),
+
+    #[error(transparent)]
+    Fmt(#[from] std::fmt::Error),
+
+    #[error(transparent)]
+    SqlServer(#[from] tiberius::error::Error),
+
+    #[error(transparent)]
+    Common(#[from] crate::common::errors::DatasourceCommonError),
+
+    #[error(transparent)]
+    SshKey(#[from] crate::common::ssh::key::SshKeyError),
+    #[error(transparent)]
+    SshTunnel(#[from] crate::common::ssh::session::SshTunnelError),
+}
+
+pub type Result<T, E = SqlServerError> = std::result::Result<T, E>;
@@ -0,0 +1,1183 @@
+pub mod errors;
+
+use std::any::Any;
+use std::fmt::{self, Write};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::common::ssh::session::SshTunnelSession;
+use crate::common::ssh::{key::SshKey, session::SshTunnelAccess};
+use crate::common::stats;
+use crate::common::util;
+use async_stream::stream;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use datafusion::arrow::datatypes::{
+    DataType, Field, Fields, Schema as ArrowSchema, SchemaRef as ArrowSchemaRef, TimeUnit,
+};
+use datafusion::arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use datafusion::datasource::TableProvider;
+use datafusion::error::{DataFusionError, Result as DatafusionResult};
+use datafusion::execution::context::{SessionState, TaskContext};
+use datafusion::logical_expr::{Expr, Operator, TableProviderFilterPushDown, TableType};
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
+};
+use datafusion_ext::errors::ExtensionError;
+use datafusion_ext::functions::VirtualLister;
+use datafusion_ext::metrics::DataSourceMetricsStreamAdapter;
+use futures::{Stream, StreamExt, TryStreamExt};
+use protogen::metastore::types::options::TunnelOptions;
+use serde::{Deserialize, Serialize};
+use tiberius::numeric::Numeric;
+use tiberius::{Client, ColumnData, Config, Row as SqlServerRow};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+use tracing::{debug, trace};
+
+use errors::{Result, SqlServerError};
+
+type SqlServerClient = Client<Compat<TcpStream>>;
+
+#[derive(Debug)]
+pub enum SqlServerDbConnection {
+    ConnectionString(String),
+    Parameters {
+        host: String,
+        port: Option<u16>,
+        user: String,
+        password: Option<String>,
+        database: String,
+    },
+}
+
+impl SqlServerDbConnection {
+    /// Get the ADO.NET style connection string for SQL Server.
+    pub fn connection_string(&self) -> String {
+        match self {
+            Self::ConnectionString(s) => s.to_owned(),
+            Self::Parameters {
+                host,
+                port,
+                user,
+                password,
+                database,
+            } => {
+                // Address
+                let mut server = format!("tcp:{host}");
+                if let Some(port) = port {
+                    write!(&mut server, ",{port}").unwrap();
+                }
+                let mut conn_str = format!("server={}", ado_value(&server));
+                // Credentials
+                write!(&mut conn_str, ";user={}", ado_value(user)).unwrap();
+                if let Some(password) = password {
+                    write!(&mut conn_str, ";password={}", ado_value(password)).unwrap();
+                }
+                // Database
+                write!(&mut conn_str, ";database={}", ado_value(database)).unwrap();
+                conn_str
+            }
+        }
+    }
+}
+
+/// Quote a value for an ADO.NET connection string if it contains characters
+/// that would otherwise be interpreted as part of the connection string.
+fn ado_value(value: &str) -> String {
+    let needs_quotes = value.contains([';', '=', '\'', '"'])
+        || value.starts_with(char::is_whitespace)
+        || value.ends_with(char::is_whitespace);
+    if needs_quotes {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Information needed for accessing an external SQL Server table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SqlServerTableAccess {
+    /// The schema the table belongs to within SQL Server.
+    pub schema: String,
+    /// The table or view name inside of SQL Server.
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct SqlServerAccessor {
+    client: Mutex<SqlServerClient>,
+    /// Address ('host:port') of the SQL Server instance we're connected to.
+    /// Used to identify tables when caching statistics.
+    address: String,
+    /// `Session` for the underlying ssh tunnel
+    ///
+    /// Kept on struct to avoid dropping ssh tunnel
+    _ssh_tunnel: Option<SshTunnelSession>,
+}
+
+impl SqlServerAccessor {
+    /// Connect to a SQL Server instance.
+    pub async fn connect(connection_string: &str, tunnel: Option<TunnelOptions>) -> Result<Self> {
+        let config = Config::from_ado_string(connection_string)?;
+        let address = config.get_addr();
+
+        let (client, _ssh_tunnel) = Self::connect_internal(config, tunnel).await?;
+
+        Ok(Self {
+            client: Mutex::new(client),
+            address,
+            _ssh_tunnel,
+        })
+    }
+
+    async fn connect_internal(
+        config: Config,
+        tunnel: Option<TunnelOptions>,
+    ) -> Result<(SqlServerClient, Option<SshTunnelSession>)> {
+        match tunnel {
+            None => Self::connect_direct(config).await,
+            Some(TunnelOptions::Ssh(ssh_options)) => {
+                let keypair = SshKey::from_bytes(&ssh_options.ssh_key)?;
+                let access = SshTunnelAccess {
+                    connection_string: ssh_options.connection_string,
+                    keypair,
+                };
+                Self::connect_with_ssh_tunnel(config, access).await
+            }
+            Some(opt) => Err(SqlServerError::UnsupportedTunnel(opt.to_string())),
+        }
+    }
+
+    async fn connect_direct(config: Config) -> Result<(SqlServerClient, Option<SshTunnelSession>)> {
+        let tcp = TcpStream::connect(config.get_addr()).await?;
+        tcp.set_nodelay(true)?;
+
+        let client = Client::connect(config, tcp.compat_write()).await?;
+
+        Ok((client, None))
+    }
+
+    async fn connect_with_ssh_tunnel(
+        mut config: Config,
+        ssh_tunnel: SshTunnelAccess,
+    ) -> Result<(SqlServerClient, Option<SshTunnelSession>)> {
+        let address = config.get_addr();
+        let (host, port) = address
+            .rsplit_once(':')
+            .and_then(|(host, port)| Some((host.to_string(), port.parse::<u16>().ok()?)))
+            .ok_or_else(|| SqlServerError::InvalidAddress(address.clone()))?;
+
+        // Open ssh tunnel
+        let (session, tunnel_addr) = ssh_tunnel.create_tunnel(&(host, port)).await?;
+
+        config.host(tunnel_addr.ip());
+        config.port(tunnel_addr.port());
+
+        let tcp = TcpStream::connect(tunnel_addr).await?;
+        tcp.set_nodelay(true)?;
+
+        let client = Client::connect(config, tcp.compat_write()).await?;
+
+        Ok((client, Some(session)))
+    }
+
+    /// Validate SQL Server external database
+    pub async fn validate_external_database(
+        connection_string: &str,
+        tunnel: Option<TunnelOptions>,
+    ) -> Result<()> {
+        let config = Config::from_ado_string(connection_string)?;
+        let (mut client, _ssh_tunnel) = Self::connect_internal(config, tunnel).await?;
+
+        client
+            .simple_query("SELECT 1")
+            .await?
+            .into_results()
+            .await?;
+        Ok(())
+    }
+
+    /// Validate SQL Server connection and access to table
+    pub async fn validate_table_access(
+        connection_string: &str,
+        access: &SqlServerTableAccess,
+        tunnel: Option<TunnelOptions>,
+    ) -> Result<()> {
+        let config = Config::from_ado_string(connection_string)?;
+        let (mut client, _ssh_tunnel) = Self::connect_internal(config, tunnel).await?;
+
+        let query = format!(
+            "SELECT TOP 0 * FROM {}.{}",
+            quote_ident(&access.schema),
+            quote_ident(&access.name)
+        );
+        client.simple_query(query).await?.into_results().await?;
+        Ok(())
+    }
+
+    /// Get the arrow schema for the SQL Server table.
+    async fn get_table_schema(&self, schema: &str, table: &str) -> Result<ArrowSchema> {
+        let mut client = self.client.lock().await;
+
+        let rows = client
+            .query(
+                "SELECT column_name, data_type, numeric_precision, numeric_scale, is_nullable
+                 FROM information_schema.columns
+                 WHERE table_schema = @P1 AND table_name = @P2
+                 ORDER BY ordinal_position",
+                &[&schema, &table],
+            )
+            .await?
+            .into_first_result()
+            .await?;
+
+        if rows.is_empty() {
+            return Err(SqlServerError::TableNotFound(
+                schema.to_string(),
+                table.to_string(),
+            ));
+        }
+
+        let mut fields = Vec::with_capacity(rows.len());
+        for row in rows {
+            let name: &str = row.try_get(0)?.unwrap_or_default();
+            let typ: &str = row.try_get(1)?.unwrap_or_default();
+            let precision: Option<u8> = row.try_get(2)?;
+            let scale: Option<i32> = row.try_get(3)?;
+            let nullable: &str = row.try_get(4)?.unwrap_or("YES");
+
+            let arrow_typ = sqlserver_to_arrow_type(name, typ, precision, scale)?;
+            fields.push(Field::new(name, arrow_typ, nullable == "YES"));
+        }
+
+        Ok(ArrowSchema::new(fields))
+    }
+
+    /// Get approximate statistics for the SQL Server table.
+    ///
+    /// Row counts and sizes are taken from partition stats of the heap or
+    /// clustered index, avoiding a full scan of the table.
+    async fn get_table_statistics(&self, schema: &str, table: &str) -> Result<Statistics> {
+        let mut client = self.client.lock().await;
+
+        let row = client
+            .query(
+                "SELECT CAST(SUM(s.row_count) AS BIGINT), CAST(SUM(s.used_page_count) * 8192 AS BIGINT)
+                 FROM sys.dm_db_partition_stats s
+                 JOIN sys.tables t ON s.object_id = t.object_id
+                 JOIN sys.schemas sc ON t.schema_id = sc.schema_id
+                 WHERE sc.name = @P1 AND t.name = @P2 AND s.index_id IN (0, 1)",
+                &[&schema, &table],
+            )
+            .await?
+            .into_row()
+            .await?;
+
+        Ok(match row {
+            Some(row) => {
+                let num_rows: Option<i64> = row.try_get(0)?;
+                let total_byte_size: Option<i64> = row.try_get(1)?;
+                stats::table_statistics(
+                    num_rows.and_then(|n| u64::try_from(n).ok()),
+                    total_byte_size.and_then(|n| u64::try_from(n).ok()),
+                )
+            }
+            None => Statistics::default(),
+        })
+    }
+
+    pub async fn into_table_provider(
+        self,
+        table_access: SqlServerTableAccess,
+        predicate_pushdown: bool,
+    ) -> Result<SqlServerTableProvider> {
+        let arrow_schema = self
+            .get_table_schema(&table_access.schema, &table_access.name)
+            .await?;
+
+        let statistics = stats::get_or_fetch_statistics(
+            format!(
+                "sqlserver/{}/{}.{}",
+                self.address, table_access.schema, table_access.name
+            ),
+            self.get_table_statistics(&table_access.schema, &table_access.name),
+        )
+        .await;
+
+        Ok(SqlServerTableProvider {
+            predicate_pushdown,
+            table_access,
+            accessor: Arc::new(self),
+            arrow_schema: Arc::new(arrow_schema),
+            statistics,
+        })
+    }
+}
+
+#[async_trait]
+impl VirtualLister for SqlServerAccessor {
+    async fn list_schemas(&self) -> Result<Vec<String>, ExtensionError> {
+        use ExtensionError::ListingErrBoxed;
+
+        let mut client = self.client.lock().await;
+
+        let rows = client
+            .simple_query("SELECT schema_name FROM information_schema.schemata")
+            .await
+            .map_err(|e| ListingErrBoxed(Box::new(e)))?
+            .into_first_result()
+            .await
+            .map_err(|e| ListingErrBoxed(Box::new(e)))?;
+
+        let schemas = rows
+            .iter()
+            .map(|row| {
+                row.try_get::<&str, _>(0)
+                    .map(|s| s.unwrap_or_default().to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ListingErrBoxed(Box::new(e)))?;
+
+        Ok(schemas)
+    }
+
+    async fn list_tables(&self, schema: &str) -> Result<Vec<String>, ExtensionError> {
+        use ExtensionError::ListingErrBoxed;
+
+        let mut client = self.client.lock().await;
+
+        let rows = client
+            .query(
+                "SELECT table_name FROM information_schema.tables WHERE table_schema = @P1",
+                &[&schema],
+            )
+            .await
+            .map_err(|e| ListingErrBoxed(Box::new(e)))?
+            .into_first_result()
+            .await
+            .map_err(|e| ListingErrBoxed(Box::new(e)))?;
+
+        let tables = rows
+            .iter()
+            .map(|row| {
+                row.try_get::<&str, _>(0)
+                    .map(|s| s.unwrap_or_default().to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ListingErrBoxed(Box::new(e)))?;
+
+        Ok(tables)
+    }
+
+    async fn list_columns(&self, schema: &str, table: &str) -> Result<Fields, ExtensionError> {
+        use ExtensionError::ListingErrBoxed;
+
+        let schema = self
+            .get_table_schema(schema, table)
+            .await
+            .map_err(|e| ListingErrBoxed(Box::new(e)))?;
+
+        Ok(schema.fields)
+    }
+}
+
+pub struct SqlServerTableProvider {
+    predicate_pushdown: bool,
+    table_access: SqlServerTableAccess,
+    accessor: Arc<SqlServerAccessor>,
+    arrow_schema: ArrowSchemaRef,
+    /// Approximate statistics for the table.
+    statistics: Statistics,
+}
+
+#[async_trait]
+impl TableProvider for SqlServerTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        self.arrow_schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    fn statistics(&self) -> Option<Statistics> {
+        Some(self.statistics.clone())
+    }
+
+    fn supports_filter_pushdown(
+        &self,
+        _filter: &Expr,
+    ) -> DatafusionResult<TableProviderFilterPushDown> {
+        Ok(TableProviderFilterPushDown::Inexact)
+    }
+
+    async fn scan(
+        &self,
+        _ctx: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        // Project the schema.
+        let projected_schema = match projection {
+            Some(projection) => Arc::new(self.arrow_schema.project(projection)?),
+            None => self.arrow_schema.clone(),
+        };
+
+        // Get the projected columns, joined by a ','. This will be put in the
+        // 'SELECT ...' portion of the query.
+        let projection_string = if projected_schema.fields.is_empty() {
+            // SQL Server doesn't allow an empty select list (e.g. when
+            // counting rows).
+            "1".to_string()
+        } else {
+            projected_schema
+                .fields
+                .iter()
+                .map(|f| quote_ident(f.name()))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        // SQL Server doesn't support 'LIMIT', use 'TOP' instead.
+        let top_string = match limit {
+            Some(limit) => format!("TOP ({limit})"),
+            None => String::new(),
+        };
+
+        // Build WHERE clause if predicate pushdown enabled.
+        let predicate_string = {
+            if self.predicate_pushdown {
+                exprs_to_predicate_string(filters)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?
+            } else {
+                String::new()
+            }
+        };
+
+        let query: String = format!(
+            "SELECT {} {} FROM {}.{} {} {}",
+            top_string,                             // [TOP (..)]
+            projection_string,                      // SELECT <str>
+            quote_ident(&self.table_access.schema), // FROM <schema>
+            quote_ident(&self.table_access.name),   // .<table>
+            // [WHERE]
+            if predicate_string.is_empty() {
+                ""
+            } else {
+                "WHERE "
+            },
+            predicate_string.as_str(), // <where-predicate>
+        );
+        trace!(?query);
+
+        let statistics = stats::estimate_scan_statistics(
+            &self.statistics,
+            &self.arrow_schema,
+            projection,
+            limit,
+        );
+
+        Ok(Arc::new(SqlServerExec {
+            predicate: predicate_string,
+            table_access: self.table_access.clone(),
+            accessor: self.accessor.clone(),
+            query,
+            arrow_schema: projected_schema,
+            metrics: ExecutionPlanMetricsSet::new(),
+            statistics,
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct SqlServerExec {
+    predicate: String,
+    table_access: SqlServerTableAccess,
+    accessor: Arc<SqlServerAccessor>,
+    query: String,
+    arrow_schema: ArrowSchemaRef,
+    metrics: ExecutionPlanMetricsSet,
+    statistics: Statistics,
+}
+
+impl ExecutionPlan for SqlServerExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        self.arrow_schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        Err(DataFusionError::Execution(
+            "cannot replace children for SqlServerExec".to_string(),
+        ))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> DatafusionResult<SendableRecordBatchStream> {
+        let stream = SqlServerQueryStream::open(
+            self.query.clone(),
+            self.accessor.clone(),
+            self.arrow_schema.clone(),
+        );
+
+        Ok(Box::pin(DataSourceMetricsStreamAdapter::new(
+            stream,
+            partition,
+            &self.metrics,
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        self.statistics.clone()
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
+
+impl DisplayAs for SqlServerExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SqlServerExec: schema={}, name={}, predicate={}",
+            self.table_access.schema,
+            self.table_access.name,
+            if self.predicate.is_empty() {
+                "None"
+            } else {
+                self.predicate.as_str()
+            }
+        )
+    }
+}
+
+struct SqlServerQueryStream {
+    arrow_schema: ArrowSchemaRef,
+    inner: Pin<Box<dyn Stream<Item = DatafusionResult<RecordBatch>> + Send>>,
+}
+
+impl SqlServerQueryStream {
+    /// Number of SQL Server rows to process into an arrow record batch
+    // TOOD: Allow configuration
+    const SQLSERVER_RECORD_BATCH_SIZE: usize = 1000;
+
+    fn open(query: String, accessor: Arc<SqlServerAccessor>, arrow_schema: ArrowSchemaRef) -> Self {
+        let schema = arrow_schema.clone();
+
+        let stream = stream! {
+            let mut client = accessor.client.lock().await;
+
+            let query_stream = client
+                .simple_query(query)
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+            let mut chunks = query_stream
+                .into_row_stream()
+                .try_chunks(Self::SQLSERVER_RECORD_BATCH_SIZE)
+                .boxed();
+
+            while let Some(rows) = chunks
+                .try_next()
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?
+            {
+                let record_batch = sqlserver_row_to_record_batch(rows, arrow_schema.clone())
+                    .map_err(|e| DataFusionError::External(Box::new(e)));
+                yield record_batch;
+            }
+        };
+
+        Self {
+            arrow_schema: schema,
+            inner: stream.boxed(),
+        }
+    }
+}
+
+impl Stream for SqlServerQueryStream {
+    type Item = DatafusionResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+impl RecordBatchStream for SqlServerQueryStream {
+    fn schema(&self) -> ArrowSchemaRef {
+        self.arrow_schema.clone()
+    }
+}
+
+/// Macro for generating the match arms when converting a `SqlServerRow` value
+/// to a record batch.
+macro_rules! make_column {
+    ($builder:ty, $rows:expr, $col_idx:expr) => {{
+        let mut arr = <$builder>::with_capacity($rows.len());
+        for row in $rows.iter() {
+            arr.append_option(row.try_get($col_idx)?);
+        }
+        Arc::new(arr.finish())
+    }};
+}
+
+/// Convert SQL Server rows into a single record batch.
+fn sqlserver_row_to_record_batch(
+    rows: Vec<SqlServerRow>,
+    schema: ArrowSchemaRef,
+) -> Result<RecordBatch> {
+    use datafusion::arrow::array::{
+        Array, BinaryBuilder, BooleanBuilder, Date32Builder, Decimal128Builder, Float32Builder,
+        Float64Builder, Int16Builder, Int32Builder, Int64Builder, StringBuilder,
+        Time64NanosecondBuilder, TimestampNanosecondBuilder, UInt8Builder,
+    };
+
+    if schema.fields.is_empty() {
+        // Only the row count matters (e.g. 'SELECT count(*)').
+        let options = RecordBatchOptions::new().with_row_count(Some(rows.len()));
+        return Ok(RecordBatch::try_new_with_options(
+            schema,
+            Vec::new(),
+            &options,
+        )?);
+    }
+
+    let mut columns: Vec<Arc<dyn Array>> = Vec::with_capacity(schema.fields.len());
+    for (col_idx, field) in schema.fields.iter().enumerate() {
+        let col: Arc<dyn Array> = match field.data_type() {
+            DataType::Boolean => make_column!(BooleanBuilder, rows, col_idx),
+            DataType::UInt8 => make_column!(UInt8Builder, rows, col_idx),
+            DataType::Int16 => make_column!(Int16Builder, rows, col_idx),
+            DataType::Int32 => make_column!(Int32Builder, rows, col_idx),
+            DataType::Int64 => make_column!(Int64Builder, rows, col_idx),
+            DataType::Float32 => make_column!(Float32Builder, rows, col_idx),
+            DataType::Float64 => make_column!(Float64Builder, rows, col_idx),
+            dt @ DataType::Decimal128(_precision, scale) => {
+                let mut arr = Decimal128Builder::new().with_data_type(dt.to_owned());
+                for row in rows.iter() {
+                    let val: Option<Numeric> = row.try_get(col_idx)?;
+                    let val = val.map(|v| {
+                        // Rescale because the scales probably don't match!
+                        let diff = *scale as i32 - v.scale() as i32;
+                        if diff >= 0 {
+                            v.value() * 10_i128.pow(diff as u32)
+                        } else {
+                            v.value() / 10_i128.pow(diff.unsigned_abs())
+                        }
+                    });
+                    arr.append_option(val);
+                }
+                Arc::new(arr.finish())
+            }
+            DataType::Timestamp(TimeUnit::Nanosecond, None) => {
+                let mut arr = TimestampNanosecondBuilder::new();
+                for row in rows.iter() {
+                    let val: Option<NaiveDateTime> = row.try_get(col_idx)?;
+                    let val = val.map(|v| v.timestamp_nanos_opt().unwrap());
+                    arr.append_option(val);
+                }
+                Arc::new(arr.finish())
+            }
+            dt @ DataType::Timestamp(TimeUnit::Nanosecond, Some(_)) => {
+                let mut arr = TimestampNanosecondBuilder::new().with_data_type(dt.to_owned());
+                for row in rows.iter() {
+                    let val: Option<DateTime<Utc>> = row.try_get(col_idx)?;
+                    let val = val.map(|v| v.timestamp_nanos_opt().unwrap());
+                    arr.append_option(val);
+                }
+                Arc::new(arr.finish())
+            }
+            DataType::Date32 => {
+                let mut arr = Date32Builder::new();
+                for row in rows.iter() {
+                    let val: Option<NaiveDate> = row.try_get(col_idx)?;
+                    let epoch_date = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+                    let val = val.map(|v| v.signed_duration_since(epoch_date).num_days() as i32);
+                    arr.append_option(val);
+                }
+                Arc::new(arr.finish())
+            }
+            DataType::Time64(TimeUnit::Nanosecond) => {
+                let mut arr = Time64NanosecondBuilder::new();
+                for row in rows.iter() {
+                    let val: Option<NaiveTime> = row.try_get(col_idx)?;
+                    let val = val.map(|v| {
+                        let nanos = v.nanosecond() as i64;
+                        let secs_since_midnight = v.num_seconds_from_midnight() as i64;
+                        (secs_since_midnight * 1_000_000_000) + nanos
+                    });
+                    arr.append_option(val);
+                }
+                Arc::new(arr.finish())
+            }
+            DataType::Utf8 => {
+                // Assumes an average of 16 bytes per item.
+                let mut arr = StringBuilder::with_capacity(rows.len(), rows.len() * 16);
+                for row in rows.iter() {
+                    // Unique identifiers are represented as strings.
+                    match row.cells().nth(col_idx).map(|(_, data)| data) {
+                        Some(ColumnData::Guid(val)) => {
+                            arr.append_option(val.map(|v| v.to_string()))
+                        }
+                        _ => arr.append_option(row.try_get::<&str, _>(col_idx)?),
+                    }
+                }
+                Arc::new(arr.finish())
+            }
+            DataType::Binary => {
+                // Assumes an average of 16 bytes per item.
+                let mut arr = BinaryBuilder::with_capacity(rows.len(), rows.len() * 16);
+                for row in rows.iter() {
+                    arr.append_option(row.try_get::<&[u8], _>(col_idx)?);
+                }
+                Arc::new(arr.finish())
+            }
+            other => {
+                return Err(SqlServerError::UnsupportedArrowType(
+                    col_idx,
+                    field.name().to_owned(),
+                    other.clone(),
+                ));
+            }
+        };
+        columns.push(col)
+    }
+
+    let batch = RecordBatch::try_new(schema, columns)?;
+    Ok(batch)
+}
+
+/// Convert a SQL Server type name (as found in `information_schema.columns`)
+/// to an arrow type.
+fn sqlserver_to_arrow_type(
+    column: &str,
+    typ: &str,
+    precision: Option<u8>,
+    scale: Option<i32>,
+) -> Result<DataType> {
+    let arrow_typ = match typ.to_lowercase().as_str() {
+        "bit" => DataType::Boolean,
+        // TINYINT is unsigned in SQL Server.
+        "tinyint" => DataType::UInt8,
+        "smallint" => DataType::Int16,
+        "int" => DataType::Int32,
+        "bigint" => DataType::Int64,
+        "real" => DataType::Float32,
+        "float" => DataType::Float64,
+        // Money types are returned as floats by the driver.
+        "money" | "smallmoney" => DataType::Float64,
+        "decimal" | "numeric" => {
+            let precision = precision.unwrap_or(18);
+            let scale = i8::try_from(scale.unwrap_or(0))?;
+            DataType::Decimal128(precision, scale)
+        }
+        "char" | "varchar" | "text" | "nchar" | "nvarchar" | "ntext" | "uniqueidentifier" => {
+            DataType::Utf8
+        }
+        "binary" | "varbinary" | "image" | "timestamp" | "rowversion" => DataType::Binary,
+        "date" => DataType::Date32,
+        "time" => DataType::Time64(TimeUnit::Nanosecond),
+        "datetime" | "datetime2" | "smalldatetime" => {
+            DataType::Timestamp(TimeUnit::Nanosecond, None)
+        }
+        "datetimeoffset" => DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+        other => {
+            return Err(SqlServerError::UnsupportedSqlServerType(
+                other.to_string(),
+                column.to_string(),
+            ))
+        }
+    };
+    Ok(arrow_typ)
+}
+
+/// Quote an identifier using brackets, escaping any closing brackets.
+fn quote_ident(ident: &str) -> String {
+    format!("[{}]", ident.replace(']', "]]"))
+}
+
+/// Convert filtering expressions to a predicate string usable with the
+/// generated SQL Server query.
+fn exprs_to_predicate_string(exprs: &[Expr]) -> Result<String> {
+    let mut ss = Vec::new();
+    let mut buf = String::new();
+    for expr in exprs {
+        if write_predicate(expr, &mut buf)? {
+            ss.push(buf);
+            buf = String::new();
+        } else {
+            // Drop anything partially written for the unsupported expression.
+            buf.clear();
+        }
+    }
+    Ok(ss.join(" AND "))
+}
+
+/// Try to write a boolean expression to the string, returning true if it was
+/// written.
+///
+/// SQL Server doesn't have a boolean type. Predicates can't be used as values,
+/// and bit values can't be used as predicates without comparing them.
+fn write_predicate(expr: &Expr, buf: &mut String) -> Result<bool> {
+    match expr {
+        // Bare columns in a filter can only be bit columns.
+        Expr::Column(_) => {
+            write_value(expr, buf)?;
+            write!(buf, " = 1")?;
+        }
+        // SQL Server has no 'IS TRUE'/'IS FALSE', compare bits instead. This
+        // is only valid for bit columns, predicates can't be compared.
+        Expr::IsTrue(expr) if matches!(expr.as_ref(), Expr::Column(_)) => {
+            write_value(expr, buf)?;
+            write!(buf, " = 1")?;
+        }
+        Expr::IsFalse(expr) if matches!(expr.as_ref(), Expr::Column(_)) => {
+            write_value(expr, buf)?;
+            write!(buf, " = 0")?;
+        }
+        Expr::IsNull(expr) => {
+            if write_value(expr, buf)? {
+                write!(buf, " IS NULL")?;
+            } else {
+                return Ok(false);
+            }
+        }
+        Expr::IsNotNull(expr) => {
+            if write_value(expr, buf)? {
+                write!(buf, " IS NOT NULL")?;
+            } else {
+                return Ok(false);
+            }
+        }
+        Expr::BinaryExpr(binary) => {
+            let write_side: fn(&Expr, &mut String) -> Result<bool> = match binary.op {
+                Operator::And | Operator::Or => write_predicate,
+                Operator::Eq
+                | Operator::NotEq
+                | Operator::Lt
+                | Operator::LtEq
+                | Operator::Gt
+                | Operator::GtEq => write_value,
+                _ => return Ok(false),
+            };
+            write!(buf, "(")?;
+            if !write_side(binary.left.as_ref(), buf)? {
+                return Ok(false);
+            }
+            write!(buf, " {} ", binary.op)?;
+            if !write_side(binary.right.as_ref(), buf)? {
+                return Ok(false);
+            }
+            write!(buf, ")")?;
+        }
+        expr => {
+            // Unsupported, including boolean literals.
+            debug!(?expr, "Unsupported filter used");
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Try to write a non-boolean expression to the string, returning true if it
+/// was written.
+fn write_value(expr: &Expr, buf: &mut String) -> Result<bool> {
+    match expr {
+        Expr::Column(col) => {
+            write!(buf, "{}", quote_ident(&col.name))?;
+        }
+        Expr::Literal(val) => {
+            util::encode_literal_to_text(util::Datasource::SqlServer, buf, val)?;
+        }
+        Expr::BinaryExpr(binary)
+            if matches!(
+                binary.op,
+                Operator::Plus
+                    | Operator::Minus
+                    | Operator::Multiply
+                    | Operator::Divide
+                    | Operator::Modulo
+            ) =>
+        {
+            write!(buf, "(")?;
+            if !write_value(binary.left.as_ref(), buf)? {
+                return Ok(false);
+            }
+            write!(buf, " {} ", binary.op)?;
+            if !write_value(binary.right.as_ref(), buf)? {
+                return Ok(false);
+            }
+            write!(buf, ")")?;
+        }
+        expr => {
+            // Unsupported, including predicates used as values.
+            debug!(?expr, "Unsupported filter used");
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::common::Column;
+    use datafusion::logical_expr::expr::Sort;
+    use datafusion::logical_expr::{BinaryExpr, Operator};
+    use datafusion::scalar::ScalarValue;
+
+    use super::*;
+
+    #[test]
+    fn connection_string() {
+        let conn_str = SqlServerDbConnection::ConnectionString(
+            "server=tcp:127.0.0.1,1433;user=sa;password=password123;database=glaredb".to_string(),
+        )
+        .connection_string();
+        assert_eq!(
+            &conn_str,
+            "server=tcp:127.0.0.1,1433;user=sa;password=password123;database=glaredb"
+        );
+
+        let conn_str = SqlServerDbConnection::Parameters {
+            host: "127.0.0.1".to_string(),
+            port: Some(1433),
+            user: "sa".to_string(),
+            password: Some("password123".to_string()),
+            database: "glaredb".to_string(),
+        };
+        let conn_str = conn_str.connection_string();
+        assert_eq!(
+            &conn_str,
+            "server=tcp:127.0.0.1,1433;user=sa;password=password123;database=glaredb"
+        );
+
+        // Missing password.
+        let conn_str = SqlServerDbConnection::Parameters {
+            host: "127.0.0.1".to_string(),
+            port: Some(1433),
+            user: "sa".to_string(),
+            password: None,
+            database: "glaredb".to_string(),
+        };
+        let conn_str = conn_str.connection_string();
+        assert_eq!(
+            &conn_str,
+            "server=tcp:127.0.0.1,1433;user=sa;database=glaredb"
+        );
+
+        // Missing port.
+        let conn_str = SqlServerDbConnection::Parameters {
+            host: "127.0.0.1".to_string(),
+            port: None,
+            user: "sa".to_string(),
+            password: Some("password123".to_string()),
+            database: "glaredb".to_string(),
+        };
+        let conn_str = conn_str.connection_string();
+        assert_eq!(
+            &conn_str,
+            "server=tcp:127.0.0.1;user=sa;password=password123;database=glaredb"
+        );
+
+        // Values with special characters are quoted.
+        let conn_str = SqlServerDbConnection::Parameters {
+            host: "127.0.0.1".to_string(),
+            port: None,
+            user: "sa".to_string(),
+            password: Some(r#"pa;ss="word"#.to_string()),
+            database: "glaredb".to_string(),
+        };
+        let conn_str = conn_str.connection_string();
+        assert_eq!(
+            &conn_str,
+            r#"server=tcp:127.0.0.1;user=sa;password="pa;ss=""word";database=glaredb"#
+        );
+
+        let config = Config::from_ado_string(&conn_str).unwrap();
+        assert_eq!(config.get_addr(), "127.0.0.1:1433");
+    }
+
+    #[test]
+    fn valid_expr_string() {
+        let exprs = vec![
+            Expr::BinaryExpr(BinaryExpr {
+                left: Box::new(Expr::Column(Column {
+                    relation: None,
+                    name: "a".to_string(),
+                })),
+                op: Operator::Lt,
+                right: Box::new(Expr::Column(Column {
+                    relation: None,
+                    name: "b".to_string(),
+                })),
+            }),
+            Expr::IsTrue(Box::new(Expr::Column(Column {
+                relation: None,
+                name: "c".to_string(),
+            }))),
+            Expr::BinaryExpr(BinaryExpr {
+                left: Box::new(Expr::Column(Column {
+                    relation: None,
+                    name: "d".to_string(),
+                })),
+                op: Operator::Eq,
+                right: Box::new(Expr::Literal(ScalarValue::Utf8(Some("abc".to_string())))),
+            }),
+        ];
+
+        let out = exprs_to_predicate_string(&exprs).unwrap();
+        assert_eq!(out, "([a] < [b]) AND [c] = 1 AND ([d] = 'abc')")
+    }
+
+    #[test]
+    fn skip_unsupported_expr_string() {
+        let exprs = vec![
+            Expr::BinaryExpr(BinaryExpr {
+                left: Box::new(Expr::Column(Column {
+                    relation: None,
+                    name: "a".to_string(),
+                })),
+                op: Operator::Lt,
+                right: Box::new(Expr::Column(Column {
+                    relation: None,
+                    name: "b".to_string(),
+                })),
+            }),
+            // Not currently supported for our expression writing.
+            Expr::Sort(Sort {
+                expr: Box::new(Expr::Column(Column {
+                    relation: None,
+                    name: "a".to_string(),
+                })),
+                asc: true,
+                nulls_first: true,
+            }),
+        ];
+
+        let out = exprs_to_predicate_string(&exprs).unwrap();
+        assert_eq!(out, "([a] < [b])")
+    }
+
+    #[test]
+    fn skip_is_true_on_predicates() {
+        let exprs = vec![Expr::IsTrue(Box::new(Expr::BinaryExpr(BinaryExpr {
+            left: Box::new(Expr::Column(Column {
+                relation: None,
+                name: "a".to_string(),
+            })),
+            op: Operator::And,
+            right: Box::new(Expr::Column(Column {
+                relation: None,
+                name: "b".to_string(),
+            })),
+        })))];
+
+        let out = exprs_to_predicate_string(&exprs).unwrap();
+        assert_eq!(out, "")
+    }
+
+    #[test]
+    fn bit_column_predicates() {
+        let col = |name: &str| {
+            Box::new(Expr::Column(Column {
+                relation: None,
+                name: name.to_string(),
+            }))
+        };
+
+        // Bare bit columns are compared against 1.
+        let out = exprs_to_predicate_string(&[*col("flag")]).unwrap();
+        assert_eq!(out, "[flag] = 1");
+
+        let exprs = vec![Expr::BinaryExpr(BinaryExpr {
+            left: col("a"),
+            op: Operator::And,
+            right: Box::new(Expr::IsFalse(col("b"))),
+        })];
+        let out = exprs_to_predicate_string(&exprs).unwrap();
+        assert_eq!(out, "([a] = 1 AND [b] = 0)");
+
+        // Bit columns compared against boolean literals.
+        let exprs = vec![Expr::BinaryExpr(BinaryExpr {
+            left: col("a"),
+            op: Operator::Eq,
+            right: Box::new(Expr::Literal(ScalarValue::Boolean(Some(true)))),
+        })];
+        let out = exprs_to_predicate_string(&exprs).unwrap();
+        assert_eq!(out, "([a] = 1)");
+    }
+
+    #[test]
+    fn skip_invalid_boolean_exprs() {
+        let col = |name: &str| {
+            Box::new(Expr::Column(Column {
+                relation: None,
+                name: name.to_string(),
+            }))
+        };
+        let exprs = vec![
+            // Bare boolean literal.
+            Expr::Literal(ScalarValue::Boolean(Some(true))),
+            // Predicate compared as a value.
+            Expr::BinaryExpr(BinaryExpr {
+                left: Box::new(Expr::BinaryExpr(BinaryExpr {
+                    left: col("a"),
+                    op: Operator::Lt,
+                    right: col("b"),
+                })),
+                op: Operator::Eq,
+                right: col("c"),
+            }),
+            // Arithmetic used as a predicate.
+            Expr::BinaryExpr(BinaryExpr {
+                left: col("a"),
+                op: Operator::Plus,
+                right: col("b"),
+            }),
+        ];
+
+        let out = exprs_to_predicate_string(&exprs).unwrap();
+        assert_eq!(out, "")
+    }
+
+    #[test]
+    fn quoted_idents() {
+        assert_eq!("[dbo]", quote_ident("dbo"));
+        assert_eq!("[my]]table]", quote_ident("my]table"));
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use sqlexec::query_history::DEFAULT_QUERY_HISTORY_SIZE;
 
 #[derive(Parser)]
 pub struct ServerArgs {
@@ -10,6 +11,48 @@ pub struct ServerArgs {
     #[clap(long, hide = true, value_parser)]
     pub rpc_bind: Option<String>,
 
+    /// TCP address to bind to for the Arrow Flight SQL interface.
+    ///
+    /// If not provided, the Flight SQL interface will not be started. Note
+    /// that clients connecting to this interface are not authenticated.
+    #[clap(long, value_parser)]
+    pub flight_bind: Option<String>,
+
+    /// TCP address to bind to for the HTTP interface.
+    ///
+    /// If not provided, the HTTP interface will not be started. Requests are
+    /// authenticated using basic auth with the same user and password as the
+    /// Postgres interface.
+    #[clap(long, value_parser)]
+    pub http_bind: Option<String>,
+
+    /// TCP address to bind to for serving Prometheus metrics.
+    ///
+    /// If not provided, metrics will not be served. Metrics are available at
+    /// the `/metrics` path.
+    #[clap(long, value_parser)]
+    pub metrics_bind: Option<String>,
+
+    /// Path to the SSL certificate to use for the Postgres interface.
+    ///
+    /// Must be provided alongside `--ssl-key`. Clients may request an
+    /// encrypted connection if set.
+    #[clap(long, value_parser)]
+    pub ssl_cert: Option<PathBuf>,
+
+    /// Path to the SSL private key to use for the Postgres interface.
+    #[clap(long, value_parser)]
+    pub ssl_key: Option<PathBuf>,
+
+    /// Path to certificate authorities to use for verifying client
+    /// certificates.
+    ///
+    /// Clients presenting a certificate signed by one of these authorities
+    /// are authenticated as the user in the certificate's common name,
+    /// without being prompted for a password.
+    #[clap(long, value_parser)]
+    pub ssl_ca: Option<PathBuf>,
+
     /// Address to the Metastore.
     ///
     /// If not provided and `local` is set to a true, an in-process
@@ -50,6 +93,62 @@ pub struct ServerArgs {
     #[clap(long, value_parser)]
     pub spill_path: Option<PathBuf>,
 
+    /// Max number of queries to execute concurrently.
+    ///
+    /// Queries exceeding this limit will wait for a running query to
+    /// complete. If not provided, the number of concurrent queries is not
+    /// limited.
+    #[clap(long, value_parser)]
+    pub max_concurrent_queries: Option<usize>,
+
+    /// Max number of seconds a query may wait to start executing when the
+    /// concurrent query limit is reached.
+    ///
+    /// If not provided, queries will wait indefinitely.
+    #[clap(long, value_parser)]
+    pub query_queue_timeout_secs: Option<u64>,
+
+    /// Record executed statements to an audit log.
+    ///
+    /// Recent records can be queried from `glare_catalog.audit_log`.
+    #[clap(long, value_parser)]
+    pub audit_log: bool,
+
+    /// Path to a file to write audit records to as newline-delimited JSON.
+    ///
+    /// Implies `--audit-log`.
+    #[clap(long, value_parser)]
+    pub audit_log_path: Option<PathBuf>,
+
+    /// Size in bytes at which the audit log file is rotated.
+    #[clap(long, value_parser, default_value_t = 100 * 1024 * 1024)]
+    pub audit_log_max_file_bytes: u64,
+
+    /// Number of rotated audit log files to keep.
+    #[clap(long, value_parser, default_value_t = 5)]
+    pub audit_log_max_files: usize,
+
+    /// Replace literals in audited statements with placeholders.
+    #[clap(long, value_parser)]
+    pub audit_log_redact_literals: bool,
+
+    /// Max number of queries to keep in `glare_catalog.query_history`.
+    #[clap(long, value_parser, default_value_t = DEFAULT_QUERY_HISTORY_SIZE)]
+    pub query_history_size: usize,
+
+    /// Persist the query history to storage so that it's kept across
+    /// restarts.
+    #[clap(long, value_parser)]
+    pub persist_query_history: bool,
+
+    /// Path to a file containing a base64 encoded 256-bit key to use for
+    /// encrypting secrets stored in the catalog.
+    ///
+    /// If not provided, secrets such as passwords and keys are stored
+    /// unencrypted.
+    #[clap(long, value_parser)]
+    pub secrets_key_file: Option<PathBuf>,
+
     /// Ignore authentication messages.
     ///
     /// (Internal)
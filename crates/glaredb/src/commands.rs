@@ -9,10 +9,16 @@ use anyhow::{anyhow, Result};
 use clap::Subcommand;
 use object_store_util::conf::StorageConfig;
 use pgsrv::auth::{LocalAuthenticator, PasswordlessAuthenticator, SingleUserAuthenticator};
+use pgsrv::ssl::SslConfig;
+use sqlexec::audit::{AuditLog, AuditLogConfig};
+use sqlexec::limits::QueryLimiter;
+use sqlexec::query_history::QueryHistoryConfig;
 use std::collections::HashMap;
 use std::fs;
 use std::net::SocketAddr;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tokio::runtime::{Builder, Runtime};
 use tracing::info;
@@ -69,6 +75,12 @@ impl RunCommand for ServerArgs {
         let Self {
             bind,
             rpc_bind,
+            flight_bind,
+            http_bind,
+            metrics_bind,
+            ssl_cert,
+            ssl_key,
+            ssl_ca,
             metastore_addr,
             user,
             password,
@@ -76,6 +88,16 @@ impl RunCommand for ServerArgs {
             service_account_path,
             storage_config,
             spill_path,
+            max_concurrent_queries,
+            query_queue_timeout_secs,
+            audit_log,
+            audit_log_path,
+            audit_log_max_file_bytes,
+            audit_log_max_files,
+            audit_log_redact_literals,
+            query_history_size,
+            persist_query_history,
+            secrets_key_file,
             ignore_pg_auth,
             disable_rpc_auth,
             segment_key,
@@ -91,6 +113,27 @@ impl RunCommand for ServerArgs {
             }),
         };
 
+        let query_limiter = match max_concurrent_queries {
+            Some(max) => QueryLimiter::new(max, query_queue_timeout_secs.map(Duration::from_secs)),
+            None => QueryLimiter::unlimited(),
+        };
+
+        let audit_log = if audit_log || audit_log_path.is_some() {
+            Some(Arc::new(AuditLog::new(AuditLogConfig {
+                path: audit_log_path,
+                max_file_bytes: audit_log_max_file_bytes,
+                max_files: audit_log_max_files,
+                redact_literals: audit_log_redact_literals,
+            })?))
+        } else {
+            None
+        };
+
+        let query_history = QueryHistoryConfig {
+            max_records: query_history_size,
+            persist: persist_query_history,
+        };
+
         let service_account_key = match service_account_path {
             Some(path) => Some(std::fs::read_to_string(path)?),
             None => None,
@@ -98,10 +141,26 @@ impl RunCommand for ServerArgs {
 
         let runtime = build_runtime("server")?;
         runtime.block_on(async move {
+            let ssl_conf = match (ssl_cert, ssl_key, ssl_ca) {
+                (Some(cert), Some(key), ca) => {
+                    Some(SslConfig::new_with_client_ca(cert, key, ca).await?)
+                }
+                (None, None, None) => None,
+                (None, None, Some(_)) => {
+                    return Err(anyhow!(
+                        "SSL cert and key must be provided when providing an SSL CA."
+                    ))
+                }
+                _ => return Err(anyhow!("Both SSL cert and key must be provided.")),
+            };
+
             let pg_listener = TcpListener::bind(bind).await?;
             let conf = ServerConfig {
                 pg_listener,
                 rpc_addr: rpc_bind.map(|s| s.parse()).transpose()?,
+                flight_addr: flight_bind.map(|s| s.parse()).transpose()?,
+                http_addr: http_bind.map(|s| s.parse()).transpose()?,
+                metrics_addr: metrics_bind.map(|s| s.parse()).transpose()?,
             };
             let server = ComputeServer::connect(
                 metastore_addr,
@@ -112,6 +171,11 @@ impl RunCommand for ServerArgs {
                 storage_config.location,
                 HashMap::from_iter(storage_config.storage_options.clone()),
                 spill_path,
+                secrets_key_file,
+                ssl_conf,
+                query_limiter,
+                audit_log,
+                query_history,
                 /* integration_testing = */ false,
                 disable_rpc_auth,
             )
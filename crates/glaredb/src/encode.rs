@@ -0,0 +1,192 @@
+use crate::args::OutputMode;
+use crate::local::JsonArrayNewLines;
+use anyhow::{anyhow, Result};
+use datafusion::arrow::csv::writer::{Writer as CsvWriter, WriterBuilder as CsvWriterBuilder};
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::ipc::writer::StreamWriter as IpcStreamWriter;
+use datafusion::arrow::json::writer::{LineDelimited as JsonLineDelimted, Writer as JsonWriter};
+use datafusion::arrow::record_batch::RecordBatch;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+/// Formats that record batches can be encoded to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeFormat {
+    Csv,
+    Json,
+    Ndjson,
+    /// Arrow IPC streaming format.
+    ArrowIpc,
+}
+
+impl EncodeFormat {
+    /// Content type to use when sending the encoded output over http.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            EncodeFormat::Csv => "text/csv",
+            EncodeFormat::Json => "application/json",
+            EncodeFormat::Ndjson => "application/x-ndjson",
+            EncodeFormat::ArrowIpc => "application/vnd.apache.arrow.stream",
+        }
+    }
+}
+
+impl TryFrom<OutputMode> for EncodeFormat {
+    type Error = anyhow::Error;
+
+    fn try_from(value: OutputMode) -> Result<Self> {
+        Ok(match value {
+            OutputMode::Csv => EncodeFormat::Csv,
+            OutputMode::Json => EncodeFormat::Json,
+            OutputMode::Ndjson => EncodeFormat::Ndjson,
+            OutputMode::Table => return Err(anyhow!("Table output cannot be encoded")),
+        })
+    }
+}
+
+/// Incrementally encodes record batches into a writer.
+pub enum BatchEncoder<W: Write> {
+    Csv(CsvWriter<W>),
+    Json(JsonWriter<W, JsonArrayNewLines>),
+    Ndjson(JsonWriter<W, JsonLineDelimted>),
+    ArrowIpc(IpcStreamWriter<W>),
+}
+
+impl<W: Write> BatchEncoder<W> {
+    pub fn try_new(format: EncodeFormat, schema: &Schema, writer: W) -> Result<Self> {
+        Ok(match format {
+            EncodeFormat::Csv => {
+                BatchEncoder::Csv(CsvWriterBuilder::new().has_headers(true).build(writer))
+            }
+            EncodeFormat::Json => BatchEncoder::Json(JsonWriter::new(writer)),
+            EncodeFormat::Ndjson => BatchEncoder::Ndjson(JsonWriter::new(writer)),
+            EncodeFormat::ArrowIpc => {
+                BatchEncoder::ArrowIpc(IpcStreamWriter::try_new(writer, schema)?)
+            }
+        })
+    }
+
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        match self {
+            BatchEncoder::Csv(w) => w.write(batch)?,
+            BatchEncoder::Json(w) => w.write(batch)?,
+            BatchEncoder::Ndjson(w) => w.write(batch)?,
+            BatchEncoder::ArrowIpc(w) => w.write(batch)?,
+        }
+        Ok(())
+    }
+
+    /// Write any trailing output and return the inner writer.
+    pub fn finish(self) -> Result<W> {
+        Ok(match self {
+            BatchEncoder::Csv(w) => w.into_inner(),
+            BatchEncoder::Json(mut w) => {
+                w.finish()?;
+                w.into_inner()
+            }
+            BatchEncoder::Ndjson(mut w) => {
+                w.finish()?;
+                w.into_inner()
+            }
+            BatchEncoder::ArrowIpc(mut w) => {
+                w.finish()?;
+                w.into_inner()?
+            }
+        })
+    }
+}
+
+/// A cloneable in-memory buffer.
+///
+/// Allows for taking the encoded output from an encoder while it's still
+/// in use.
+#[derive(Debug, Clone, Default)]
+pub struct SharedBuffer {
+    inner: Arc<Mutex<Vec<u8>>>,
+}
+
+impl SharedBuffer {
+    /// Take everything that's been written to the buffer so far.
+    pub fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.inner.lock().unwrap())
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::arrow::array::{Int32Array, StringArray};
+    use datafusion::arrow::datatypes::{DataType, Field};
+    use datafusion::arrow::ipc::reader::StreamReader;
+
+    fn test_batch() -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec![Some("hello"), None])),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn encode(format: EncodeFormat, batches: &[RecordBatch]) -> Vec<u8> {
+        let buf = SharedBuffer::default();
+        let mut encoder = BatchEncoder::try_new(format, &batches[0].schema(), buf.clone()).unwrap();
+        let mut out = Vec::new();
+        for batch in batches {
+            encoder.write(batch).unwrap();
+            out.extend(buf.take());
+        }
+        encoder.finish().unwrap();
+        out.extend(buf.take());
+        out
+    }
+
+    #[test]
+    fn encode_text() {
+        let batch = test_batch();
+
+        let out = encode(EncodeFormat::Csv, &[batch.clone(), batch.clone()]);
+        assert_eq!(
+            "a,b\n1,hello\n2,\n1,hello\n2,\n",
+            String::from_utf8(out).unwrap()
+        );
+
+        let out = encode(EncodeFormat::Ndjson, &[batch.clone()]);
+        assert_eq!(
+            "{\"a\":1,\"b\":\"hello\"}\n{\"a\":2}\n",
+            String::from_utf8(out).unwrap()
+        );
+
+        let out = encode(EncodeFormat::Json, &[batch]);
+        assert_eq!(
+            "[{\"a\":1,\"b\":\"hello\"},\n{\"a\":2}]\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn encode_arrow_ipc() {
+        let batch = test_batch();
+        let out = encode(EncodeFormat::ArrowIpc, &[batch.clone()]);
+
+        let reader = StreamReader::try_new(out.as_slice(), None).unwrap();
+        let batches: Vec<_> = reader.map(|b| b.unwrap()).collect();
+        assert_eq!(vec![batch], batches);
+    }
+}
@@ -0,0 +1,517 @@
+//! HTTP interface for running queries.
+use crate::encode::{BatchEncoder, EncodeFormat, SharedBuffer};
+use anyhow::{anyhow, Result};
+use axum::body::StreamBody;
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use bytes::Bytes;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::error::DataFusionError;
+use datafusion::physical_plan::SendableRecordBatchStream;
+use datafusion::scalar::ScalarValue;
+use datafusion::variable::VarType;
+use datafusion_ext::vars::SessionVars;
+use futures::{Stream, StreamExt};
+use pgrepr::format::Format;
+use pgsrv::auth::{LocalAuthenticator, PasswordMode};
+use serde::Deserialize;
+use serde_json::json;
+use sqlexec::engine::{Engine, SessionStorageConfig, TrackedSession};
+use sqlexec::errors::ExecError;
+use sqlexec::parser::{self, StatementWithExtensions};
+use sqlexec::planner::preprocess::{preprocess, NamedPlaceholderReplacer};
+use sqlexec::planner::privileges::RoleAuthentication;
+use sqlexec::session::ExecutionResult;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::debug;
+use uuid::Uuid;
+
+/// Body of a `POST /query` request.
+#[derive(Debug, Deserialize)]
+pub struct QueryRequest {
+    /// The SQL statement to execute.
+    pub sql: String,
+    /// Values for named parameters in the statement, e.g. `$name`.
+    #[serde(default)]
+    pub params: HashMap<String, serde_json::Value>,
+    /// Format to return query results in.
+    #[serde(default)]
+    pub format: ResponseFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseFormat {
+    #[default]
+    Ndjson,
+    Csv,
+    Arrow,
+}
+
+impl From<ResponseFormat> for EncodeFormat {
+    fn from(value: ResponseFormat) -> Self {
+        match value {
+            ResponseFormat::Ndjson => EncodeFormat::Ndjson,
+            ResponseFormat::Csv => EncodeFormat::Csv,
+            ResponseFormat::Arrow => EncodeFormat::ArrowIpc,
+        }
+    }
+}
+
+/// Error returned to the client.
+///
+/// Errors are sent as a json object with an `error` field.
+#[derive(Debug)]
+pub struct HttpError {
+    status: StatusCode,
+    message: String,
+}
+
+impl HttpError {
+    fn unauthorized(message: impl Into<String>) -> Self {
+        HttpError {
+            status: StatusCode::UNAUTHORIZED,
+            message: message.into(),
+        }
+    }
+
+    fn bad_request(message: impl Into<String>) -> Self {
+        HttpError {
+            status: StatusCode::BAD_REQUEST,
+            message: message.into(),
+        }
+    }
+
+    fn internal(message: impl Into<String>) -> Self {
+        HttpError {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<ExecError> for HttpError {
+    fn from(value: ExecError) -> Self {
+        HttpError {
+            status: exec_error_status(&value),
+            message: value.to_string(),
+        }
+    }
+}
+
+impl From<DataFusionError> for HttpError {
+    fn from(value: DataFusionError) -> Self {
+        HttpError {
+            status: datafusion_error_status(&value),
+            message: value.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for HttpError {
+    fn into_response(self) -> Response {
+        let body = Json(json!({ "error": self.message }));
+        if self.status == StatusCode::UNAUTHORIZED {
+            (
+                self.status,
+                [(header::WWW_AUTHENTICATE, "Basic realm=\"glaredb\"")],
+                body,
+            )
+                .into_response()
+        } else {
+            (self.status, body).into_response()
+        }
+    }
+}
+
+/// Handles queries sent over http.
+///
+/// Each request is executed in its own session.
+pub struct HttpHandler {
+    engine: Arc<Engine>,
+    authenticator: Arc<dyn LocalAuthenticator>,
+}
+
+impl HttpHandler {
+    pub fn new(engine: Arc<Engine>, authenticator: Arc<dyn LocalAuthenticator>) -> Self {
+        HttpHandler {
+            engine,
+            authenticator,
+        }
+    }
+
+    /// Create the router for the http interface.
+    pub fn into_router(self) -> Router {
+        Router::new()
+            .route("/query", post(query))
+            .with_state(Arc::new(self))
+    }
+
+    /// Authenticate the request using basic auth, returning the user name.
+    ///
+    /// Users are checked against their login role if roles exist, and the
+    /// authenticator otherwise.
+    async fn authenticate(&self, headers: &HeaderMap) -> Result<String, HttpError> {
+        let (user, password) = match basic_auth_credentials(headers) {
+            Some((user, password)) => (user, Some(password)),
+            None => (String::new(), None),
+        };
+
+        // Sessions are always created in the local database.
+        let role_auth = self
+            .engine
+            .authenticate_role(Uuid::nil(), &user, password.as_deref())
+            .await
+            .map_err(|e| {
+                if e.is_authentication_error() {
+                    HttpError::unauthorized(format!("Failed to authenticate: {e}"))
+                } else {
+                    e.into()
+                }
+            })?;
+
+        match (role_auth, self.authenticator.password_mode()) {
+            (RoleAuthentication::Authenticated, _) => (),
+            (RoleAuthentication::PasswordRequired, _) => {
+                return Err(HttpError::unauthorized("Missing basic auth credentials"))
+            }
+            (RoleAuthentication::Authenticator, PasswordMode::NoPassword { .. }) => (),
+            (RoleAuthentication::Authenticator, PasswordMode::RequireCleartext) => {
+                let password = password
+                    .ok_or_else(|| HttpError::unauthorized("Missing basic auth credentials"))?;
+                self.authenticator
+                    .authenticate(&user, &password, "")
+                    .map_err(|e| HttpError::unauthorized(format!("Failed to authenticate: {e}")))?;
+            }
+        }
+
+        Ok(user)
+    }
+
+    async fn execute(&self, user: String, req: QueryRequest) -> Result<Response, HttpError> {
+        const UNNAMED: String = String::new();
+
+        let mut statements =
+            parser::parse_sql(&req.sql).map_err(|e| HttpError::bad_request(e.to_string()))?;
+        if statements.len() != 1 {
+            return Err(HttpError::bad_request(format!(
+                "Expected exactly one statement, got {}",
+                statements.len()
+            )));
+        }
+        let mut stmt = statements.pop_front().unwrap();
+
+        let (names, values): (Vec<_>, Vec<_>) = req.params.into_iter().unzip();
+        if let StatementWithExtensions::Statement(inner) = &mut stmt {
+            preprocess(inner, &mut NamedPlaceholderReplacer { names: &names })
+                .map_err(|e| HttpError::bad_request(e.to_string()))?;
+        }
+
+        let vars = SessionVars::default().with_user_name(user, VarType::System);
+        let mut session = self
+            .engine
+            .new_local_session_context(vars, SessionStorageConfig::default())
+            .await?;
+
+        session
+            .prepare_statement(UNNAMED, Some(stmt), Vec::new())
+            .await?;
+        let prepared = session.get_prepared_statement(&UNNAMED)?;
+        let num_fields = prepared.output_fields().map(|f| f.len()).unwrap_or(0);
+        let params = bind_params(prepared.input_paramaters(), values)
+            .map_err(|e| HttpError::bad_request(e.to_string()))?;
+        session.bind_statement(UNNAMED, &UNNAMED, params, vec![Format::Text; num_fields])?;
+
+        match session.execute_portal(&UNNAMED, 0).await? {
+            ExecutionResult::Query { stream, .. } => {
+                let format = EncodeFormat::from(req.format);
+                let body = encode_stream(session, stream, format)
+                    .map_err(|e| HttpError::internal(e.to_string()))?;
+                Ok((
+                    [(header::CONTENT_TYPE, format.content_type())],
+                    StreamBody::new(body),
+                )
+                    .into_response())
+            }
+            ExecutionResult::Error(e) => Err(e.into()),
+            other => Ok(Json(json!({ "result": other.to_string() })).into_response()),
+        }
+    }
+}
+
+async fn query(
+    State(handler): State<Arc<HttpHandler>>,
+    headers: HeaderMap,
+    Json(req): Json<QueryRequest>,
+) -> Result<Response, HttpError> {
+    let user = handler.authenticate(&headers).await?;
+    debug!(%user, sql = %req.sql, "executing http query");
+    handler.execute(user, req).await
+}
+
+/// Get the status to respond with for an error from the engine.
+///
+/// Failed authentication and privilege checks are reported separately from
+/// errors in the request, and from failures within the server.
+fn exec_error_status(e: &ExecError) -> StatusCode {
+    if e.is_authentication_error() {
+        return StatusCode::UNAUTHORIZED;
+    }
+    if e.is_permission_denied() {
+        return StatusCode::FORBIDDEN;
+    }
+    match e {
+        ExecError::DataFusion(e) => datafusion_error_status(e),
+        ExecError::Internal(_)
+        | ExecError::Io(_)
+        | ExecError::ObjectStore(_)
+        | ExecError::ChannelSendError(_)
+        | ExecError::InvalidStorageConfig(_)
+        | ExecError::MetastoreWorker(_)
+        | ExecError::RemoteSession(_)
+        | ExecError::TonicTransport(_)
+        | ExecError::TonicStatus(_)
+        | ExecError::ReqwestError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        _ => StatusCode::BAD_REQUEST,
+    }
+}
+
+/// Get the status to respond with for an error from executing a query.
+fn datafusion_error_status(e: &DataFusionError) -> StatusCode {
+    match e {
+        DataFusionError::External(e) => match e.downcast_ref::<ExecError>() {
+            Some(e) => exec_error_status(e),
+            None => StatusCode::INTERNAL_SERVER_ERROR,
+        },
+        DataFusionError::Context(_, e) => datafusion_error_status(e),
+        DataFusionError::Internal(_)
+        | DataFusionError::IoError(_)
+        | DataFusionError::ObjectStore(_)
+        | DataFusionError::ResourcesExhausted(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        _ => StatusCode::BAD_REQUEST,
+    }
+}
+
+/// Get the user and password from a basic auth header.
+fn basic_auth_credentials(headers: &HeaderMap) -> Option<(String, String)> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let encoded = value.strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(BASE64.decode(encoded).ok()?).ok()?;
+    let (user, password) = decoded.split_once(':')?;
+    Some((user.to_string(), password.to_string()))
+}
+
+/// Convert parameter values into scalars in the order of their positional
+/// placeholders, casting to the inferred parameter types when known.
+fn bind_params<T>(
+    types: Option<&HashMap<String, Option<(T, DataType)>>>,
+    values: Vec<serde_json::Value>,
+) -> Result<Vec<ScalarValue>> {
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(idx, value)| {
+            let scalar = json_to_scalar(value)?;
+            let datatype = types
+                .and_then(|types| types.get(&format!("${}", idx + 1)))
+                .and_then(|typ| typ.as_ref().map(|(_, datatype)| datatype));
+            match datatype {
+                Some(datatype) => Ok(scalar.cast_to(datatype)?),
+                None => Ok(scalar),
+            }
+        })
+        .collect()
+}
+
+fn json_to_scalar(value: serde_json::Value) -> Result<ScalarValue> {
+    use serde_json::Value;
+    Ok(match value {
+        Value::Null => ScalarValue::Null,
+        Value::Bool(v) => ScalarValue::Boolean(Some(v)),
+        Value::Number(n) => {
+            if let Some(v) = n.as_i64() {
+                ScalarValue::Int64(Some(v))
+            } else if let Some(v) = n.as_u64() {
+                ScalarValue::UInt64(Some(v))
+            } else {
+                ScalarValue::Float64(n.as_f64())
+            }
+        }
+        Value::String(v) => ScalarValue::Utf8(Some(v)),
+        other => return Err(anyhow!("Unsupported parameter value: {other}")),
+    })
+}
+
+/// Encode a result stream, yielding the encoded output after every batch.
+///
+/// The session is held until the stream completes.
+fn encode_stream(
+    session: TrackedSession,
+    mut stream: SendableRecordBatchStream,
+    format: EncodeFormat,
+) -> Result<impl Stream<Item = Result<Bytes>>> {
+    let buf = SharedBuffer::default();
+    let mut encoder = BatchEncoder::try_new(format, &stream.schema(), buf.clone())?;
+
+    Ok(async_stream::try_stream! {
+        let _session = session;
+        while let Some(batch) = stream.next().await {
+            encoder.write(&batch?)?;
+            yield Bytes::from(buf.take());
+        }
+        encoder.finish()?;
+        yield Bytes::from(buf.take());
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use pgsrv::auth::PasswordlessAuthenticator;
+
+    async fn new_handler() -> HttpHandler {
+        let engine = Engine::from_data_dir(&None).await.unwrap();
+        HttpHandler::new(
+            Arc::new(engine),
+            Arc::new(PasswordlessAuthenticator::default()),
+        )
+    }
+
+    fn query_request(sql: &str) -> QueryRequest {
+        QueryRequest {
+            sql: sql.to_string(),
+            params: HashMap::new(),
+            format: ResponseFormat::default(),
+        }
+    }
+
+    fn basic_auth(creds: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Basic {}", BASE64.encode(creds))).unwrap(),
+        );
+        headers
+    }
+
+    async fn setup_roles(handler: &HttpHandler) {
+        for sql in [
+            "create user glaredb with superuser",
+            "create role http_nologin",
+            "create user http_writer password 'secret'",
+        ] {
+            handler
+                .execute("glaredb".to_string(), query_request(sql))
+                .await
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn parse_basic_auth() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(None, basic_auth_credentials(&headers));
+
+        // "glaredb:my:password"
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Basic Z2xhcmVkYjpteTpwYXNzd29yZA=="),
+        );
+        assert_eq!(
+            Some(("glaredb".to_string(), "my:password".to_string())),
+            basic_auth_credentials(&headers)
+        );
+    }
+
+    #[tokio::test]
+    async fn authenticate_roles() {
+        let handler = new_handler().await;
+        setup_roles(&handler).await;
+
+        let status = |result: Result<String, HttpError>| result.unwrap_err().status;
+
+        // Roles that can't log in are rejected even without a password
+        // requirement from the authenticator.
+        assert_eq!(
+            StatusCode::UNAUTHORIZED,
+            status(handler.authenticate(&basic_auth("http_nologin:")).await)
+        );
+
+        // Role passwords are checked.
+        assert_eq!(
+            StatusCode::UNAUTHORIZED,
+            status(handler.authenticate(&basic_auth("http_writer:wrong")).await)
+        );
+        assert_eq!(
+            "http_writer",
+            handler
+                .authenticate(&basic_auth("http_writer:secret"))
+                .await
+                .unwrap()
+        );
+
+        // Users without a role are rejected once roles exist.
+        assert_eq!(
+            StatusCode::UNAUTHORIZED,
+            status(handler.authenticate(&basic_auth("http_unknown:")).await)
+        );
+        assert_eq!(
+            StatusCode::UNAUTHORIZED,
+            status(handler.authenticate(&HeaderMap::new()).await)
+        );
+    }
+
+    #[tokio::test]
+    async fn error_status() {
+        let handler = new_handler().await;
+        setup_roles(&handler).await;
+
+        let status = |result: Result<Response, HttpError>| result.unwrap_err().status;
+
+        assert_eq!(
+            StatusCode::BAD_REQUEST,
+            status(
+                handler
+                    .execute("glaredb".to_string(), query_request("selec 1"))
+                    .await
+            )
+        );
+        assert_eq!(
+            StatusCode::FORBIDDEN,
+            status(
+                handler
+                    .execute(
+                        "http_writer".to_string(),
+                        query_request("create table http_denied (a int)")
+                    )
+                    .await
+            )
+        );
+    }
+
+    #[test]
+    fn bind_params_casts_to_inferred_types() {
+        let types: HashMap<String, Option<((), DataType)>> = HashMap::from([
+            ("$1".to_string(), Some(((), DataType::Int32))),
+            ("$2".to_string(), None),
+        ]);
+
+        let params = bind_params(Some(&types), vec![json!(1), json!("hello")]).unwrap();
+        assert_eq!(
+            vec![
+                ScalarValue::Int32(Some(1)),
+                ScalarValue::Utf8(Some("hello".to_string()))
+            ],
+            params
+        );
+
+        assert!(bind_params(Some(&types), vec![json!([1, 2])]).is_err());
+    }
+}
@@ -1,8 +1,11 @@
 pub mod args;
 pub mod commands;
+pub mod encode;
 mod highlighter;
+pub mod http;
 pub mod local;
 pub mod metastore;
+pub mod metrics;
 pub mod pg_proxy;
 mod prompt;
 pub mod rpc_proxy;
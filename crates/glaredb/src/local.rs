@@ -1,15 +1,13 @@
 use crate::args::{LocalClientOpts, OutputMode, StorageConfigArgs};
+use crate::encode::BatchEncoder;
 use crate::highlighter::{SQLHighlighter, SQLHinter, SQLValidator};
 use crate::prompt::SQLPrompt;
 use anyhow::{anyhow, Result};
 use arrow_util::pretty::pretty_format_batches;
 use clap::ValueEnum;
 use colored::Colorize;
-use datafusion::arrow::csv::writer::WriterBuilder as CsvWriterBuilder;
 use datafusion::arrow::error::ArrowError;
-use datafusion::arrow::json::writer::{
-    JsonFormat, LineDelimited as JsonLineDelimted, Writer as JsonWriter,
-};
+use datafusion::arrow::json::writer::JsonFormat;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::physical_plan::SendableRecordBatchStream;
 use futures::StreamExt;
@@ -297,19 +295,6 @@ async fn print_stream(
     let schema = stream.schema();
     let batches = process_stream(stream).await?;
 
-    fn write_json<F: JsonFormat>(batches: &[RecordBatch]) -> Result<()> {
-        let stdout = std::io::stdout();
-        let buf = std::io::BufWriter::new(stdout);
-        let mut writer = JsonWriter::<_, F>::new(buf);
-        for batch in batches {
-            writer.write(batch)?;
-        }
-        writer.finish()?;
-        let mut buf = writer.into_inner();
-        buf.flush()?;
-        Ok(())
-    }
-
     match mode {
         OutputMode::Table => {
             // If width not explicitly set by the user, try to get the width of ther
@@ -318,16 +303,16 @@ async fn print_stream(
             let disp = pretty_format_batches(&schema, &batches, Some(width), max_rows)?;
             println!("{disp}");
         }
-        OutputMode::Csv => {
+        mode => {
             let stdout = std::io::stdout();
             let buf = std::io::BufWriter::new(stdout);
-            let mut writer = CsvWriterBuilder::new().has_headers(true).build(buf);
-            for batch in batches {
-                writer.write(&batch)?; // CSV writer flushes per write.
+            let mut encoder = BatchEncoder::try_new(mode.try_into()?, &schema, buf)?;
+            for batch in &batches {
+                encoder.write(batch)?;
             }
+            let mut buf = encoder.finish()?;
+            buf.flush()?;
         }
-        OutputMode::Json => write_json::<JsonArrayNewLines>(&batches)?,
-        OutputMode::Ndjson => write_json::<JsonLineDelimted>(&batches)?,
     }
 
     Ok(())
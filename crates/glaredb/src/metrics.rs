@@ -0,0 +1,22 @@
+//! HTTP interface for scraping Prometheus metrics.
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use telemetry::metrics::{METRICS, METRICS_CONTENT_TYPE};
+use tracing::error;
+
+/// Create a router serving metrics on `GET /metrics`.
+pub fn router() -> Router {
+    Router::new().route("/metrics", get(metrics))
+}
+
+async fn metrics() -> Response {
+    match METRICS.encode() {
+        Ok(body) => ([(header::CONTENT_TYPE, METRICS_CONTENT_TYPE)], body).into_response(),
+        Err(e) => {
+            error!(%e, "failed to encode metrics");
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+        }
+    }
+}
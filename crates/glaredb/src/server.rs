@@ -1,10 +1,19 @@
+use crate::http::HttpHandler;
+use crate::metrics;
 use anyhow::{anyhow, Result};
+use arrow_flight::flight_service_server::FlightServiceServer;
 use metastore::util::MetastoreClientMode;
 use pgsrv::auth::LocalAuthenticator;
 use pgsrv::handler::{ProtocolHandler, ProtocolHandlerConfig};
+use pgsrv::ssl::SslConfig;
 use protogen::gen::rpcsrv::service::execution_service_server::ExecutionServiceServer;
+use rpcsrv::flight_sql::FlightSqlHandler;
 use rpcsrv::handler::RpcHandler;
-use sqlexec::engine::{Engine, EngineStorageConfig};
+use sqlexec::audit::AuditLog;
+use sqlexec::engine::{Engine, EngineStorageConfig, SessionStorageConfig};
+use sqlexec::limits::QueryLimiter;
+use sqlexec::metastore::secrets::{LocalKeyFileKms, SecretCipher};
+use sqlexec::query_history::QueryHistoryConfig;
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::path::PathBuf;
@@ -26,11 +35,24 @@ pub struct ServerConfig {
     /// Address to use for the rpc handler. If not provided, an rpc handler will
     /// not be started.
     pub rpc_addr: Option<SocketAddr>,
+
+    /// Address to use for the Flight SQL handler. If not provided, a Flight
+    /// SQL handler will not be started.
+    pub flight_addr: Option<SocketAddr>,
+
+    /// Address to use for the http handler. If not provided, an http handler
+    /// will not be started.
+    pub http_addr: Option<SocketAddr>,
+
+    /// Address to use for serving Prometheus metrics. If not provided, metrics
+    /// will not be served.
+    pub metrics_addr: Option<SocketAddr>,
 }
 
 pub struct ComputeServer {
     integration_testing: bool,
     disable_rpc_auth: bool,
+    authenticator: Arc<dyn LocalAuthenticator>,
     pg_handler: Arc<ProtocolHandler>,
     engine: Arc<Engine>,
 }
@@ -48,6 +70,11 @@ impl ComputeServer {
         location: Option<String>,
         storage_options: HashMap<String, String>,
         spill_path: Option<PathBuf>,
+        secrets_key_file: Option<PathBuf>,
+        ssl_conf: Option<SslConfig>,
+        query_limiter: QueryLimiter,
+        audit_log: Option<Arc<AuditLog>>,
+        query_history: QueryHistoryConfig,
         integration_testing: bool,
         disable_rpc_auth: bool,
     ) -> Result<Self> {
@@ -68,6 +95,19 @@ impl ComputeServer {
             }
         };
 
+        let cipher = match secrets_key_file {
+            Some(path) => {
+                debug!(?path, "encrypting catalog secrets using key file");
+                let kms = LocalKeyFileKms::from_file(path)?;
+                Some(Arc::new(SecretCipher::new(Arc::new(kms))))
+            }
+            None => None,
+        };
+
+        // Without a remote metastore or cloud storage, the server only serves
+        // the local database.
+        let local_database = metastore_addr.is_none() && service_account_key.is_none();
+
         // Create the `Engine` instance
         let engine = if let Some(location) = location {
             // TODO: try to consolidate with --data-dir and --metastore-addr options
@@ -76,7 +116,15 @@ impl ComputeServer {
                 &HashMap::from_iter(storage_options.clone()),
             )
             .await?;
-            Arc::new(engine.with_tracker(Arc::new(tracker)))
+            Arc::new(
+                engine
+                    .with_tracker(Arc::new(tracker))
+                    .with_secret_cipher(cipher)
+                    .with_query_limiter(query_limiter)
+                    .with_audit_log(audit_log)
+                    .with_query_history(query_history)
+                    .await?,
+            )
         } else {
             // Connect to metastore.
             let mode = match (metastore_addr, &data_dir) {
@@ -117,21 +165,31 @@ impl ComputeServer {
                     Arc::new(tracker),
                     spill_path,
                 )
+                .await?
+                .with_secret_cipher(cipher)
+                .with_query_limiter(query_limiter)
+                .with_audit_log(audit_log)
+                .with_query_history(query_history)
                 .await?,
             )
         };
 
+        if local_database {
+            engine
+                .schedule_materialized_views(Uuid::nil(), &SessionStorageConfig::default())
+                .await?;
+        }
+
+        let authenticator: Arc<dyn LocalAuthenticator> = Arc::from(authenticator);
         let handler_conf = ProtocolHandlerConfig {
-            authenticator,
-            // TODO: Allow specifying SSL/TLS on the GlareDB side as well. I
-            // want to hold off on doing that until we have a shared config
-            // between the proxy and GlareDB.
-            ssl_conf: None,
+            authenticator: authenticator.clone(),
+            ssl_conf,
             integration_testing,
         };
         Ok(ComputeServer {
             integration_testing,
             disable_rpc_auth,
+            authenticator,
             pg_handler: Arc::new(ProtocolHandler::new(engine.clone(), handler_conf)),
             engine,
         })
@@ -144,12 +202,31 @@ impl ComputeServer {
         } else {
             "".to_string()
         };
+        let flight_msg = if let Some(addr) = conf.flight_addr {
+            format!("\nConnect via Flight SQL: grpc://{}\n", addr)
+        } else {
+            "".to_string()
+        };
+        let http_msg = if let Some(addr) = conf.http_addr {
+            format!("\nConnect via HTTP: http://{}\n", addr)
+        } else {
+            "".to_string()
+        };
+
+        let metrics_msg = if let Some(addr) = conf.metrics_addr {
+            format!("\nMetrics available at: http://{}/metrics\n", addr)
+        } else {
+            "".to_string()
+        };
 
         info!(
-            "Starting GlareDB {}\nConnect via Postgres: postgresql://{}{}",
+            "Starting GlareDB {}\nConnect via Postgres: postgresql://{}{}{}{}{}",
             env!("CARGO_PKG_VERSION"),
             conf.pg_listener.local_addr()?,
-            rpc_msg
+            rpc_msg,
+            flight_msg,
+            http_msg,
+            metrics_msg,
         );
 
         // Shutdown handler.
@@ -215,6 +292,47 @@ impl ComputeServer {
             });
         }
 
+        // Start flight sql service.
+        if let Some(addr) = conf.flight_addr {
+            let handler = FlightSqlHandler::new(self.engine.clone(), self.authenticator.clone());
+            tokio::spawn(async move {
+                if let Err(e) = Server::builder()
+                    .trace_fn(|_| debug_span!("flight_sql_request"))
+                    .add_service(FlightServiceServer::new(handler))
+                    .serve(addr)
+                    .await
+                {
+                    error!(%e, "flight sql service died");
+                }
+            });
+        }
+
+        // Start http service.
+        if let Some(addr) = conf.http_addr {
+            let router =
+                HttpHandler::new(self.engine.clone(), self.authenticator.clone()).into_router();
+            tokio::spawn(async move {
+                if let Err(e) = axum::Server::bind(&addr)
+                    .serve(router.into_make_service())
+                    .await
+                {
+                    error!(%e, "http service died");
+                }
+            });
+        }
+
+        // Start metrics service.
+        if let Some(addr) = conf.metrics_addr {
+            tokio::spawn(async move {
+                if let Err(e) = axum::Server::bind(&addr)
+                    .serve(metrics::router().into_make_service())
+                    .await
+                {
+                    error!(%e, "metrics service died");
+                }
+            });
+        }
+
         // Postgres handler loop.
         loop {
             tokio::select! {
@@ -248,13 +366,26 @@ impl ComputeServer {
 
 #[cfg(test)]
 mod tests {
+    use std::io::Write;
     use std::time::Duration;
 
     use pgsrv::auth::SingleUserAuthenticator;
+    use rcgen::{
+        BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType, IsCa,
+    };
+    use rustls::{ClientConfig as TlsClientConfig, RootCertStore, ServerName};
+    use tempfile::NamedTempFile;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use tokio_postgres::config::SslMode;
     use tokio_postgres::{Config as ClientConfig, NoTls};
+    use tokio_rustls::TlsConnector;
 
     use super::*;
 
+    /// SSLRequest startup message: length followed by the SSL request code.
+    const SSL_REQUEST: [u8; 8] = [0, 0, 0, 8, 4, 210, 22, 47];
+
     #[tokio::test]
     async fn no_hang_on_rpc_service_start() {
         let pg_listener = TcpListener::bind("localhost:0").await.unwrap();
@@ -262,6 +393,9 @@ mod tests {
         let server_conf = ServerConfig {
             pg_listener,
             rpc_addr: Some("0.0.0.0:0".parse().unwrap()),
+            flight_addr: None,
+            http_addr: None,
+            metrics_addr: None,
         };
 
         let server = ComputeServer::connect(
@@ -276,6 +410,11 @@ mod tests {
             None,
             Default::default(),
             None,
+            None,
+            None,
+            Default::default(),
+            None,
+            Default::default(),
             false,
             false,
         )
@@ -305,4 +444,234 @@ mod tests {
             .unwrap() // Timeout error
             .unwrap(); // Query error
     }
+
+    #[tokio::test]
+    async fn role_login() {
+        let pg_listener = TcpListener::bind("localhost:0").await.unwrap();
+        let port = pg_listener.local_addr().unwrap().port();
+        let server_conf = ServerConfig {
+            pg_listener,
+            rpc_addr: None,
+            flight_addr: None,
+            http_addr: None,
+            metrics_addr: None,
+        };
+
+        let server = ComputeServer::connect(
+            None,
+            None,
+            Box::new(SingleUserAuthenticator {
+                user: "glaredb".to_string(),
+                password: "glaredb".to_string(),
+            }),
+            None,
+            None,
+            None,
+            Default::default(),
+            None,
+            None,
+            None,
+            Default::default(),
+            None,
+            Default::default(),
+            false,
+            false,
+        )
+        .await
+        .unwrap();
+        tokio::spawn(server.serve(server_conf));
+
+        async fn connect(
+            port: u16,
+            user: &str,
+            password: &str,
+        ) -> Result<tokio_postgres::Client, tokio_postgres::Error> {
+            let (client, conn) = tokio::time::timeout(
+                Duration::from_secs(5),
+                ClientConfig::new()
+                    .user(user)
+                    .password(password)
+                    .dbname("glaredb")
+                    .host("localhost")
+                    .port(port)
+                    .connect(NoTls),
+            )
+            .await
+            .unwrap()?; // Timeout error
+            tokio::spawn(conn);
+            Ok(client)
+        }
+
+        let admin = connect(port, "glaredb", "glaredb").await.unwrap();
+        admin
+            .batch_execute(
+                "CREATE USER glaredb WITH SUPERUSER;
+                 CREATE USER alice WITH PASSWORD 'secret';
+                 CREATE ROLE carol;
+                 CREATE TABLE role_login (a INT);
+                 GRANT SELECT ON TABLE role_login TO alice;",
+            )
+            .await
+            .unwrap();
+
+        // Roles with a password are authenticated using that password.
+        assert!(connect(port, "alice", "glaredb").await.is_err());
+        let alice = connect(port, "alice", "secret").await.unwrap();
+        alice
+            .simple_query("SELECT * FROM role_login")
+            .await
+            .unwrap();
+        assert!(alice
+            .simple_query("INSERT INTO role_login VALUES (1)")
+            .await
+            .is_err());
+
+        // Users without a role, or with a role that can't log in, are
+        // rejected.
+        assert!(connect(port, "bob", "glaredb").await.is_err());
+        assert!(connect(port, "carol", "glaredb").await.is_err());
+
+        // Roles without a password still use the server's authenticator.
+        assert!(connect(port, "glaredb", "secret").await.is_err());
+        connect(port, "glaredb", "glaredb").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn client_certificate_login() {
+        // Certificate authority signing both the server's and the client's
+        // certificates.
+        let mut ca_params = CertificateParams::new(Vec::new());
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = Certificate::from_params(ca_params).unwrap();
+
+        let server_cert =
+            Certificate::from_params(CertificateParams::new(vec!["localhost".to_string()]))
+                .unwrap();
+
+        let mut client_params = CertificateParams::new(Vec::new());
+        client_params.distinguished_name = DistinguishedName::new();
+        client_params
+            .distinguished_name
+            .push(DnType::CommonName, "alice");
+        let client_cert = Certificate::from_params(client_params).unwrap();
+
+        fn create_file(contents: &str) -> NamedTempFile {
+            let mut temp = NamedTempFile::new().unwrap();
+            temp.write_all(contents.as_bytes()).unwrap();
+            temp
+        }
+
+        let cert_file = create_file(&server_cert.serialize_pem_with_signer(&ca).unwrap());
+        let key_file = create_file(&server_cert.serialize_private_key_pem());
+        let ca_file = create_file(&ca.serialize_pem().unwrap());
+        let ssl_conf =
+            SslConfig::new_with_client_ca(cert_file.path(), key_file.path(), Some(ca_file.path()))
+                .await
+                .unwrap();
+
+        let pg_listener = TcpListener::bind("localhost:0").await.unwrap();
+        let port = pg_listener.local_addr().unwrap().port();
+        let server_conf = ServerConfig {
+            pg_listener,
+            rpc_addr: None,
+            flight_addr: None,
+            http_addr: None,
+            metrics_addr: None,
+        };
+
+        let server = ComputeServer::connect(
+            None,
+            None,
+            Box::new(SingleUserAuthenticator {
+                user: "glaredb".to_string(),
+                password: "glaredb".to_string(),
+            }),
+            None,
+            None,
+            None,
+            Default::default(),
+            None,
+            None,
+            Some(ssl_conf),
+            Default::default(),
+            None,
+            Default::default(),
+            false,
+            false,
+        )
+        .await
+        .unwrap();
+        tokio::spawn(server.serve(server_conf));
+
+        async fn connect(
+            port: u16,
+            user: &str,
+            password: &str,
+            ca: &Certificate,
+            client_cert: Option<&Certificate>,
+        ) -> Result<tokio_postgres::Client, tokio_postgres::Error> {
+            let mut roots = RootCertStore::empty();
+            roots
+                .add(&rustls::Certificate(ca.serialize_der().unwrap()))
+                .unwrap();
+            let builder = TlsClientConfig::builder()
+                .with_safe_defaults()
+                .with_root_certificates(roots);
+            let tls_conf = match client_cert {
+                Some(cert) => builder
+                    .with_client_auth_cert(
+                        vec![rustls::Certificate(
+                            cert.serialize_der_with_signer(ca).unwrap(),
+                        )],
+                        rustls::PrivateKey(cert.serialize_private_key_der()),
+                    )
+                    .unwrap(),
+                None => builder.with_no_client_auth(),
+            };
+
+            // Send an SSLRequest and wrap the connection ourselves, then run
+            // the startup over the encrypted stream.
+            let mut stream = TcpStream::connect(("localhost", port)).await.unwrap();
+            stream.write_all(&SSL_REQUEST).await.unwrap();
+            let mut resp = [0; 1];
+            stream.read_exact(&mut resp).await.unwrap();
+            assert_eq!(b'S', resp[0]);
+            let stream = TlsConnector::from(Arc::new(tls_conf))
+                .connect(ServerName::try_from("localhost").unwrap(), stream)
+                .await
+                .unwrap();
+
+            let (client, conn) = tokio::time::timeout(
+                Duration::from_secs(5),
+                ClientConfig::new()
+                    .user(user)
+                    .password(password)
+                    .dbname("glaredb")
+                    .ssl_mode(SslMode::Disable)
+                    .connect_raw(stream, NoTls),
+            )
+            .await
+            .unwrap()?; // Timeout error
+            tokio::spawn(conn);
+            Ok(client)
+        }
+
+        // The certificate authenticates the user in its common name without
+        // a password.
+        let alice = connect(port, "alice", "wrong", &ca, Some(&client_cert))
+            .await
+            .unwrap();
+        alice.simple_query("SELECT 1").await.unwrap();
+
+        // The certificate can't be used to log in as a different user.
+        assert!(connect(port, "bob", "wrong", &ca, Some(&client_cert))
+            .await
+            .is_err());
+
+        // Clients without a certificate fall back to password authentication.
+        assert!(connect(port, "alice", "wrong", &ca, None).await.is_err());
+        connect(port, "glaredb", "glaredb", &ca, None)
+            .await
+            .unwrap();
+    }
 }
@@ -5,12 +5,13 @@ use once_cell::sync::Lazy;
 use pgrepr::oid::FIRST_AVAILABLE_ID;
 use protogen::metastore::types::catalog::{
     CatalogEntry, CatalogState, CredentialsEntry, DatabaseEntry, DeploymentMetadata, EntryMeta,
-    EntryType, FunctionEntry, FunctionType, SchemaEntry, TableEntry, TunnelEntry, ViewEntry,
+    EntryType, FunctionEntry, FunctionType, PrivilegeGrant, RoleEntry, RuntimePreference,
+    SchemaEntry, SequenceEntry, TableEntry, TunnelEntry, ViewEntry,
 };
 use protogen::metastore::types::options::{
-    DatabaseOptions, DatabaseOptionsInternal, TableOptions, TunnelOptions,
+    DatabaseOptions, DatabaseOptionsInternal, TableOptions, TableOptionsInternal, TunnelOptions,
 };
-use protogen::metastore::types::service::Mutation;
+use protogen::metastore::types::service::{CommentObject, GrantObject, Mutation};
 use protogen::metastore::types::storage::{ExtraState, PersistedCatalog};
 use sqlbuiltins::builtins::{
     BuiltinDatabase, BuiltinSchema, BuiltinTable, BuiltinView, DATABASE_DEFAULT, DEFAULT_SCHEMA,
@@ -102,7 +103,7 @@ impl DatabaseCatalog {
         self.load_latest().await?;
 
         let mut state = self.cached.lock().await;
-        if state.version != version {
+        if state.version != version && !applies_at_any_version(&mutations) {
             return Err(MetastoreError::VersionMismatch {
                 have: version,
                 need: state.version,
@@ -113,7 +114,7 @@ impl DatabaseCatalog {
 
         // State's version number updated, but we still need to use the old
         // version number when making a request to storage.
-        let old_version = version;
+        let old_version = state.version;
 
         // TODO: Rollback on failed mutate.
         //
@@ -197,6 +198,19 @@ impl DatabaseCatalog {
     }
 }
 
+/// Whether the mutations can be applied to whatever the current catalog
+/// version is.
+///
+/// Allocating sequence values doesn't depend on the state of the catalog the
+/// client planned against, and concurrent sessions allocating from the same
+/// sequence would otherwise keep failing with version mismatches.
+fn applies_at_any_version(mutations: &[Mutation]) -> bool {
+    !mutations.is_empty()
+        && mutations
+            .iter()
+            .all(|m| matches!(m, Mutation::AdvanceSequence(_)))
+}
+
 /// A thin wrapper around a hashmap for database entries.
 ///
 /// Mutating methods on this type prevent mutating default (builtin) objects.
@@ -315,6 +329,8 @@ struct State {
     tunnel_names: HashMap<String, u32>,
     /// Map credentials names to their ids.
     credentials_names: HashMap<String, u32>,
+    /// Map role names to their ids.
+    role_names: HashMap<String, u32>,
     /// Map schema names to their ids.
     schema_names: HashMap<String, u32>,
     /// Map schema IDs to objects in the schema.
@@ -333,6 +349,7 @@ impl State {
         let mut database_names = HashMap::new();
         let mut tunnel_names = HashMap::new();
         let mut credentials_names = HashMap::new();
+        let mut role_names = HashMap::new();
         let mut schema_names = HashMap::new();
         let mut schema_objects = HashMap::new();
 
@@ -396,6 +413,17 @@ impl State {
 
                     credentials_names.insert(creds.meta.name.clone(), *oid);
                 }
+                CatalogEntry::Role(role) => {
+                    if role.meta.parent != DATABASE_PARENT_ID {
+                        return Err(MetastoreError::ObjectHasNonZeroParent {
+                            object: *oid,
+                            parent: role.meta.parent,
+                            object_type: "role",
+                        });
+                    }
+
+                    role_names.insert(role.meta.name.clone(), *oid);
+                }
                 CatalogEntry::Schema(schema) => {
                     if schema.meta.parent == DATABASE_PARENT_ID {
                         return Err(MetastoreError::ObjectHasInvalidParentId {
@@ -407,7 +435,9 @@ impl State {
 
                     schema_names.insert(schema.meta.name.clone(), *oid);
                 }
-                entry @ CatalogEntry::View(_) | entry @ CatalogEntry::Table(_) => {
+                entry @ CatalogEntry::View(_)
+                | entry @ CatalogEntry::Table(_)
+                | entry @ CatalogEntry::Sequence(_) => {
                     if entry.get_meta().parent == DATABASE_PARENT_ID {
                         return Err(MetastoreError::ObjectHasInvalidParentId {
                             object: *oid,
@@ -462,6 +492,7 @@ impl State {
             database_names,
             tunnel_names,
             credentials_names,
+            role_names,
             schema_names,
             schema_objects,
         };
@@ -569,6 +600,16 @@ impl State {
 
                 self.entries.remove(&credentials_id)?.unwrap();
             }
+            Mutation::DropRole(drop_role) => {
+                let if_exists = drop_role.if_exists;
+                let role_id = match self.role_names.remove(&drop_role.name) {
+                    None if if_exists => return Ok(()),
+                    None => return Err(MetastoreError::MissingRole(drop_role.name)),
+                    Some(id) => id,
+                };
+
+                self.entries.remove(&role_id)?.unwrap();
+            }
             Mutation::DropSchema(drop_schema) => {
                 let if_exists = drop_schema.if_exists;
                 let schema_id = match self.schema_names.remove(&drop_schema.name) {
@@ -623,8 +664,6 @@ impl State {
                     Some(objs) => objs,
                 };
 
-                // TODO: This will need to be tweaked if/when we support
-                // dropping functions.
                 let ent_id = match objs.tables.remove(&drop_object.name) {
                     None if if_exists => return Ok(()),
                     None => {
@@ -636,7 +675,10 @@ impl State {
                     Some(id) => id,
                 };
 
-                self.entries.remove(&ent_id)?.unwrap(); // Bug if doesn't exist.
+                let ent = self.entries.remove(&ent_id)?.unwrap(); // Bug if doesn't exist.
+                if let CatalogEntry::Table(_) = ent {
+                    self.drop_owned_sequences(schema_id, ent_id)?;
+                }
             }
             Mutation::CreateExternalDatabase(create_database) => {
                 validate_object_name(&create_database.name)?;
@@ -670,6 +712,7 @@ impl State {
                         builtin: false,
                         external: true,
                         is_temp: false,
+                        comment: None,
                     },
                     options: create_database.options,
                     tunnel_id,
@@ -699,6 +742,7 @@ impl State {
                         builtin: false,
                         external: false,
                         is_temp: false,
+                        comment: None,
                     },
                     options: create_tunnel.options,
                 };
@@ -729,6 +773,7 @@ impl State {
                         builtin: false,
                         external: false,
                         is_temp: false,
+                        comment: None,
                     },
                     options: create_credentials.options,
                     comment: create_credentials.comment,
@@ -738,6 +783,64 @@ impl State {
                 // Add to creadentials map
                 self.credentials_names.insert(create_credentials.name, oid);
             }
+            Mutation::CreateRole(create_role) => {
+                validate_object_name(&create_role.name)?;
+                match self.role_names.get(&create_role.name) {
+                    Some(_) if create_role.if_not_exists => return Ok(()), // Already exists, nothing to do.
+                    Some(_) => return Err(MetastoreError::DuplicateName(create_role.name)),
+                    None => (),
+                }
+
+                // Create new entry
+                let oid = self.next_oid();
+                let ent = RoleEntry {
+                    meta: EntryMeta {
+                        entry_type: EntryType::Role,
+                        id: oid,
+                        // Roles are global to the database and have no parent.
+                        parent: DATABASE_PARENT_ID,
+                        name: create_role.name.clone(),
+                        builtin: false,
+                        external: false,
+                        is_temp: false,
+                        comment: None,
+                    },
+                    superuser: create_role.superuser,
+                    login: create_role.login,
+                    grants: Vec::new(),
+                    password_hash: create_role.password_hash,
+                };
+                self.entries.insert(oid, CatalogEntry::Role(ent))?;
+
+                // Add to role map
+                self.role_names.insert(create_role.name, oid);
+            }
+            Mutation::GrantPrivileges(grant) => {
+                let object_id = self.get_grant_object_id(&grant.object)?;
+                let role = self.get_role_mut(&grant.role)?;
+
+                match role.grants.iter_mut().find(|g| g.object_id == object_id) {
+                    Some(existing) => existing.privileges.extend(grant.privileges),
+                    None => role.grants.push(PrivilegeGrant {
+                        object_id,
+                        privileges: grant.privileges,
+                    }),
+                }
+
+                for g in role.grants.iter_mut() {
+                    g.privileges.sort();
+                    g.privileges.dedup();
+                }
+            }
+            Mutation::RevokePrivileges(revoke) => {
+                let object_id = self.get_grant_object_id(&revoke.object)?;
+                let role = self.get_role_mut(&revoke.role)?;
+
+                for g in role.grants.iter_mut().filter(|g| g.object_id == object_id) {
+                    g.privileges.retain(|p| !revoke.privileges.contains(p));
+                }
+                role.grants.retain(|g| !g.privileges.is_empty());
+            }
             Mutation::CreateSchema(create_schema) => {
                 validate_object_name(&create_schema.name)?;
 
@@ -761,6 +864,7 @@ impl State {
                         builtin: false,
                         external: false,
                         is_temp: false,
+                        comment: None,
                     },
                 };
                 self.entries.insert(oid, CatalogEntry::Schema(ent))?;
@@ -784,6 +888,7 @@ impl State {
                         builtin: false,
                         external: false,
                         is_temp: false,
+                        comment: None,
                     },
                     sql: create_view.sql,
                     columns: create_view.columns,
@@ -814,6 +919,7 @@ impl State {
                         builtin: false,
                         external: false,
                         is_temp: false,
+                        comment: None,
                     },
                     options: TableOptions::Internal(create_table.options),
                     tunnel_id: None,
@@ -822,6 +928,12 @@ impl State {
                 let policy =
                     CreatePolicy::new(create_table.if_not_exists, create_table.or_replace)?;
 
+                // Sequences owned by a replaced table are recreated along with
+                // the new table.
+                if create_table.or_replace {
+                    self.drop_owned_sequences(schema_id, oid)?;
+                }
+
                 self.try_insert_table_namespace(CatalogEntry::Table(ent), schema_id, oid, policy)?;
             }
 
@@ -854,6 +966,7 @@ impl State {
                         builtin: false,
                         external: true,
                         is_temp: false,
+                        comment: None,
                     },
                     options: create_ext.options,
                     tunnel_id,
@@ -961,6 +1074,247 @@ impl State {
                     _ => unreachable!("entry should be a tunnel"),
                 };
             }
+            Mutation::RefreshMaterializedView(refresh) => {
+                let missing = || MetastoreError::MissingNamedObject {
+                    schema: refresh.schema.clone(),
+                    name: refresh.name.clone(),
+                };
+
+                let schema_id = self.get_schema_id(&refresh.schema)?;
+                let oid = match self.schema_objects.get(&schema_id) {
+                    Some(objs) => match objs.tables.get(&refresh.name) {
+                        Some(oid) => *oid,
+                        None => return Err(missing()),
+                    },
+                    None => return Err(missing()),
+                };
+
+                let view = match self.entries.get_mut(&oid)? {
+                    Some(CatalogEntry::Table(TableEntry {
+                        options: TableOptions::Internal(opts),
+                        ..
+                    })) => opts.materialized_view.as_mut(),
+                    _ => None,
+                };
+                match view {
+                    Some(view) => view.last_refreshed = Some(refresh.refreshed_at),
+                    None => return Err(MetastoreError::NotMaterializedView(refresh.name)),
+                }
+            }
+            Mutation::CreateFunction(create_function) => {
+                validate_object_name(&create_function.name)?;
+
+                let schema_id = self.get_schema_id(&create_function.schema)?;
+
+                // Sessions resolve tables and functions from the same
+                // namespace, so don't allow a function to shadow a table.
+                let (in_tables, existing) = match self.schema_objects.get(&schema_id) {
+                    Some(objs) => (
+                        objs.tables.contains_key(&create_function.name),
+                        objs.functions.get(&create_function.name).copied(),
+                    ),
+                    None => (false, None),
+                };
+                if in_tables || (existing.is_some() && !create_function.or_replace) {
+                    return Err(MetastoreError::DuplicateName(create_function.name));
+                }
+
+                let oid = existing.unwrap_or_else(|| self.next_oid());
+                let ent = FunctionEntry {
+                    meta: EntryMeta {
+                        entry_type: EntryType::Function,
+                        id: oid,
+                        parent: schema_id,
+                        name: create_function.name.clone(),
+                        builtin: false,
+                        external: false,
+                        is_temp: false,
+                        comment: None,
+                    },
+                    func_type: create_function.func_type,
+                    runtime_preference: RuntimePreference::Unspecified,
+                    sql_function: Some(create_function.sql_function),
+                };
+
+                self.entries.insert(oid, CatalogEntry::Function(ent))?;
+                self.schema_objects
+                    .entry(schema_id)
+                    .or_default()
+                    .functions
+                    .insert(create_function.name, oid);
+            }
+            Mutation::DropFunction(drop_function) => {
+                let if_exists = drop_function.if_exists;
+
+                let schema_id = match self.schema_names.get(&drop_function.schema) {
+                    None if if_exists => return Ok(()),
+                    None => return Err(MetastoreError::MissingNamedSchema(drop_function.schema)),
+                    Some(id) => *id,
+                };
+
+                let oid = match self
+                    .schema_objects
+                    .get(&schema_id)
+                    .and_then(|objs| objs.functions.get(&drop_function.name))
+                {
+                    None if if_exists => return Ok(()),
+                    None => {
+                        return Err(MetastoreError::MissingNamedObject {
+                            schema: drop_function.schema,
+                            name: drop_function.name,
+                        })
+                    }
+                    Some(oid) => *oid,
+                };
+
+                // Errors for builtin functions.
+                self.entries.remove(&oid)?;
+                if let Some(objs) = self.schema_objects.get_mut(&schema_id) {
+                    objs.functions.remove(&drop_function.name);
+                }
+            }
+            Mutation::CreateSequence(create_sequence) => {
+                validate_object_name(&create_sequence.name)?;
+
+                let schema_id = self.get_schema_id(&create_sequence.schema)?;
+
+                let owned_by = match &create_sequence.owned_by_table {
+                    Some(table) => {
+                        let oid = self
+                            .schema_objects
+                            .get(&schema_id)
+                            .and_then(|objs| objs.tables.get(table))
+                            .copied()
+                            .ok_or_else(|| MetastoreError::MissingNamedObject {
+                                schema: create_sequence.schema.clone(),
+                                name: table.clone(),
+                            })?;
+                        Some(oid)
+                    }
+                    None => None,
+                };
+
+                if create_sequence.increment == 0
+                    || create_sequence.min_value > create_sequence.max_value
+                    || create_sequence.start < create_sequence.min_value
+                    || create_sequence.start > create_sequence.max_value
+                {
+                    return Err(MetastoreError::InvalidSequence(create_sequence.name));
+                }
+
+                let oid = self.get_or_next_oid(schema_id, &create_sequence.name);
+                let ent = SequenceEntry {
+                    meta: EntryMeta {
+                        entry_type: EntryType::Sequence,
+                        id: oid,
+                        parent: schema_id,
+                        name: create_sequence.name.clone(),
+                        builtin: false,
+                        external: false,
+                        is_temp: false,
+                        comment: None,
+                    },
+                    start: create_sequence.start,
+                    increment: create_sequence.increment,
+                    min_value: create_sequence.min_value,
+                    max_value: create_sequence.max_value,
+                    last_value: create_sequence.start,
+                    is_called: false,
+                    owned_by,
+                };
+
+                let policy = CreatePolicy::new(create_sequence.if_not_exists, false)?;
+                self.try_insert_table_namespace(
+                    CatalogEntry::Sequence(ent),
+                    schema_id,
+                    oid,
+                    policy,
+                )?;
+            }
+            Mutation::AdvanceSequence(advance) => {
+                let seq = self.get_sequence_mut(&advance.schema, &advance.name)?;
+
+                let mut next = seq.next_value();
+                let mut last = seq.last_value;
+                for _ in 0..advance.count {
+                    last = next
+                        .ok_or_else(|| MetastoreError::SequenceExhausted(seq.meta.name.clone()))?;
+                    next = seq.value_after(last);
+                }
+
+                if advance.count > 0 {
+                    seq.last_value = last;
+                    seq.is_called = true;
+                }
+            }
+            Mutation::SetSequenceValue(set) => {
+                let seq = self.get_sequence_mut(&set.schema, &set.name)?;
+                if set.value < seq.min_value || set.value > seq.max_value {
+                    return Err(MetastoreError::SequenceValueOutOfBounds {
+                        name: seq.meta.name.clone(),
+                        value: set.value,
+                        min: seq.min_value,
+                        max: seq.max_value,
+                    });
+                }
+                seq.last_value = set.value;
+                seq.is_called = set.is_called;
+            }
+            Mutation::CommentOn(comment_on) => {
+                let oid = self.get_comment_object_id(&comment_on.object)?;
+                let ent = self
+                    .entries
+                    .get_mut(&oid)?
+                    .ok_or(MetastoreError::MissingEntry(oid))?;
+
+                match (&comment_on.object, ent) {
+                    (CommentObject::Database(_), CatalogEntry::Database(ent)) => {
+                        ent.meta.comment = comment_on.comment
+                    }
+                    (CommentObject::Schema(_), CatalogEntry::Schema(ent)) => {
+                        ent.meta.comment = comment_on.comment
+                    }
+                    (CommentObject::Table { .. }, CatalogEntry::Table(ent)) => {
+                        ent.meta.comment = comment_on.comment
+                    }
+                    (CommentObject::View { .. }, CatalogEntry::View(ent)) => {
+                        ent.meta.comment = comment_on.comment
+                    }
+                    (CommentObject::Column { table, column, .. }, CatalogEntry::Table(ent)) => {
+                        let columns = match &mut ent.options {
+                            TableOptions::Internal(TableOptionsInternal { columns, .. }) => columns,
+                            _ => {
+                                return Err(MetastoreError::InvalidCommentObject(format!(
+                                    "columns of external table {table}"
+                                )))
+                            }
+                        };
+                        let col = columns
+                            .iter_mut()
+                            .find(|col| &col.name == column)
+                            .ok_or_else(|| MetastoreError::MissingColumn {
+                                table: table.clone(),
+                                column: column.clone(),
+                            })?;
+                        col.comment = comment_on.comment;
+                    }
+                    (object, ent) => {
+                        let expected = match object {
+                            CommentObject::Database(_) => EntryType::Database,
+                            CommentObject::Schema(_) => EntryType::Schema,
+                            CommentObject::View { .. } => EntryType::View,
+                            CommentObject::Table { .. } | CommentObject::Column { .. } => {
+                                EntryType::Table
+                            }
+                        };
+                        return Err(MetastoreError::ObjectTypeMismatch {
+                            name: ent.get_meta().name.clone(),
+                            expected,
+                            found: ent.entry_type(),
+                        });
+                    }
+                }
+            }
             Mutation::UpdateDeploymentStorage(update_deployment_storage) => {
                 // Update the new storage size
                 self.deployment.storage_size = update_deployment_storage.new_storage_size;
@@ -1012,6 +1366,49 @@ impl State {
         Ok(())
     }
 
+    /// Drop all sequences in a schema owned by a table.
+    fn drop_owned_sequences(&mut self, schema_id: u32, table_oid: u32) -> Result<()> {
+        let owned: Vec<_> = self
+            .entries
+            .as_ref()
+            .values()
+            .filter_map(|ent| match ent {
+                CatalogEntry::Sequence(seq) if seq.owned_by == Some(table_oid) => {
+                    Some((seq.meta.id, seq.meta.name.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        for (oid, name) in owned {
+            self.entries.remove(&oid)?;
+            if let Some(objs) = self.schema_objects.get_mut(&schema_id) {
+                objs.tables.remove(&name);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_sequence_mut(&mut self, schema: &str, name: &str) -> Result<&mut SequenceEntry> {
+        let missing = || MetastoreError::MissingNamedObject {
+            schema: schema.to_string(),
+            name: name.to_string(),
+        };
+
+        let schema_id = self.get_schema_id(schema)?;
+        let oid = *self
+            .schema_objects
+            .get(&schema_id)
+            .and_then(|objs| objs.tables.get(name))
+            .ok_or_else(missing)?;
+
+        match self.entries.get_mut(&oid)? {
+            Some(CatalogEntry::Sequence(seq)) => Ok(seq),
+            _ => Err(MetastoreError::NotASequence(name.to_string())),
+        }
+    }
+
     fn get_schema_id(&self, name: &str) -> Result<u32> {
         self.schema_names
             .get(name)
@@ -1019,6 +1416,80 @@ impl State {
             .ok_or_else(|| MetastoreError::MissingNamedSchema(name.to_string()))
     }
 
+    fn get_role_mut(&mut self, name: &str) -> Result<&mut RoleEntry> {
+        let oid = *self
+            .role_names
+            .get(name)
+            .ok_or_else(|| MetastoreError::MissingRole(name.to_string()))?;
+        match self.entries.get_mut(&oid)?.expect("entry should exist") {
+            CatalogEntry::Role(role) => Ok(role),
+            ent => unreachable!("entry should be a role entry but found: {ent:?}"),
+        }
+    }
+
+    /// Resolve the object a privilege is being granted on to its oid.
+    ///
+    /// Builtin tables and views don't have stable oids, so grants on them are
+    /// rejected. Builtin databases and schemas do have stable oids.
+    fn get_grant_object_id(&self, object: &GrantObject) -> Result<u32> {
+        match object {
+            GrantObject::Database(name) => self
+                .database_names
+                .get(name)
+                .copied()
+                .ok_or_else(|| MetastoreError::MissingDatabase(name.clone())),
+            GrantObject::Schema(name) => self.get_schema_id(name),
+            GrantObject::Table { schema, name } => {
+                let schema_id = self.get_schema_id(schema)?;
+                let oid = self
+                    .schema_objects
+                    .get(&schema_id)
+                    .and_then(|objs| objs.tables.get(name))
+                    .copied()
+                    .ok_or_else(|| MetastoreError::MissingNamedObject {
+                        schema: schema.clone(),
+                        name: name.clone(),
+                    })?;
+                match self.entries.get(&oid)? {
+                    Some(ent) if ent.get_meta().builtin => Err(MetastoreError::InvalidGrantObject(
+                        format!("builtin object {schema}.{name}"),
+                    )),
+                    Some(_) => Ok(oid),
+                    None => Err(MetastoreError::MissingEntry(oid)),
+                }
+            }
+        }
+    }
+
+    /// Resolve the object a comment is being set on to its oid. Column
+    /// comments resolve to the table's oid.
+    fn get_comment_object_id(&self, object: &CommentObject) -> Result<u32> {
+        let get_object_id = |schema: &String, name: &String| {
+            let schema_id = self.get_schema_id(schema)?;
+            self.schema_objects
+                .get(&schema_id)
+                .and_then(|objs| objs.tables.get(name))
+                .copied()
+                .ok_or_else(|| MetastoreError::MissingNamedObject {
+                    schema: schema.clone(),
+                    name: name.clone(),
+                })
+        };
+
+        match object {
+            CommentObject::Database(name) => self
+                .database_names
+                .get(name)
+                .copied()
+                .ok_or_else(|| MetastoreError::MissingDatabase(name.clone())),
+            CommentObject::Schema(name) => self.get_schema_id(name),
+            CommentObject::Table { schema, name } | CommentObject::View { schema, name } => {
+                get_object_id(schema, name)
+            }
+            CommentObject::Column { schema, table, .. } => get_object_id(schema, table),
+        }
+    }
+
     fn get_tunnel_entry(&self, tunnel_name: Option<&String>) -> Result<Option<&TunnelEntry>> {
         let tunnel_entry = if let Some(tunnel) = tunnel_name {
             let tunnel_id = *self
@@ -1100,6 +1571,7 @@ impl BuiltinCatalog {
                         builtin: true,
                         external: false,
                         is_temp: false,
+                        comment: None,
                     },
                     options: DatabaseOptions::Internal(DatabaseOptionsInternal {}),
                     tunnel_id: None,
@@ -1121,6 +1593,7 @@ impl BuiltinCatalog {
                         builtin: true,
                         external: false,
                         is_temp: false,
+                        comment: None,
                     },
                 }),
             );
@@ -1144,6 +1617,7 @@ impl BuiltinCatalog {
                         builtin: true,
                         external: false,
                         is_temp: false,
+                        comment: None,
                     },
                     options: TableOptions::new_internal(table.columns.clone()),
                     tunnel_id: None,
@@ -1173,6 +1647,7 @@ impl BuiltinCatalog {
                         builtin: true,
                         external: false,
                         is_temp: false,
+                        comment: None,
                     },
                     sql: view.sql.to_string(),
                     columns: Vec::new(),
@@ -1204,9 +1679,11 @@ impl BuiltinCatalog {
                         builtin: true,
                         external: false,
                         is_temp: false,
+                        comment: None,
                     },
                     func_type: FunctionType::TableReturning,
                     runtime_preference: func.runtime_preference(),
+                    sql_function: None,
                 }),
             );
             schema_objects
@@ -1231,14 +1708,27 @@ impl BuiltinCatalog {
 mod tests {
     use super::*;
     use crate::storage::persist::Storage;
+    use datafusion::arrow::datatypes::DataType;
     use object_store::memory::InMemory;
+    use protogen::metastore::types::catalog::Privilege;
+    use protogen::metastore::types::catalog::SqlFunction;
     use protogen::metastore::types::options::DatabaseOptionsDebug;
     use protogen::metastore::types::options::TableOptionsDebug;
+    use protogen::metastore::types::options::{
+        InternalColumnDefinition, InternalMaterializedView, TableOptionsInternal,
+    };
     use protogen::metastore::types::service::AlterDatabaseRename;
     use protogen::metastore::types::service::DropDatabase;
+    use protogen::metastore::types::service::{
+        AdvanceSequence, CommentOn, CreateFunction, CreateSequence, CreateTable, DropFunction,
+        DropObject, RefreshMaterializedView, SetSequenceValue,
+    };
     use protogen::metastore::types::service::{
         CreateExternalDatabase, CreateExternalTable, CreateSchema, CreateView, DropSchema,
     };
+    use protogen::metastore::types::service::{
+        CreateRole, DropRole, GrantPrivileges, RevokePrivileges,
+    };
     use sqlbuiltins::builtins::DEFAULT_CATALOG;
     use std::collections::HashSet;
 
@@ -1780,4 +2270,526 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn grant_and_revoke_privileges() {
+        let db = new_catalog().await;
+
+        let state = db
+            .try_mutate(
+                version(&db).await,
+                vec![
+                    Mutation::CreateRole(CreateRole {
+                        name: "alice".to_string(),
+                        if_not_exists: false,
+                        superuser: false,
+                        login: true,
+                        password_hash: None,
+                    }),
+                    Mutation::CreateView(CreateView {
+                        schema: "public".to_string(),
+                        name: "v".to_string(),
+                        sql: "select 1".to_string(),
+                        or_replace: false,
+                        columns: Vec::new(),
+                    }),
+                    Mutation::GrantPrivileges(GrantPrivileges {
+                        role: "alice".to_string(),
+                        object: GrantObject::Table {
+                            schema: "public".to_string(),
+                            name: "v".to_string(),
+                        },
+                        privileges: vec![Privilege::Select, Privilege::Insert, Privilege::Select],
+                    }),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let role = state
+            .entries
+            .values()
+            .find_map(|ent| match ent {
+                CatalogEntry::Role(role) if role.meta.name == "alice" => Some(role.clone()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(1, role.grants.len());
+        assert_eq!(
+            vec![Privilege::Select, Privilege::Insert],
+            role.grants[0].privileges
+        );
+
+        // Builtin tables cannot have privileges granted on them.
+        db.try_mutate(
+            state.version,
+            vec![Mutation::GrantPrivileges(GrantPrivileges {
+                role: "alice".to_string(),
+                object: GrantObject::Table {
+                    schema: "glare_catalog".to_string(),
+                    name: "tables".to_string(),
+                },
+                privileges: vec![Privilege::Select],
+            })],
+        )
+        .await
+        .unwrap_err();
+
+        // Revoking all privileges removes the grant.
+        let state = db
+            .try_mutate(
+                state.version,
+                vec![Mutation::RevokePrivileges(RevokePrivileges {
+                    role: "alice".to_string(),
+                    object: GrantObject::Table {
+                        schema: "public".to_string(),
+                        name: "v".to_string(),
+                    },
+                    privileges: vec![Privilege::Select, Privilege::Insert],
+                })],
+            )
+            .await
+            .unwrap();
+
+        let role = state
+            .entries
+            .values()
+            .find_map(|ent| match ent {
+                CatalogEntry::Role(role) if role.meta.name == "alice" => Some(role.clone()),
+                _ => None,
+            })
+            .unwrap();
+        assert!(role.grants.is_empty());
+
+        // Granting to a missing role errors.
+        db.try_mutate(
+            state.version,
+            vec![Mutation::GrantPrivileges(GrantPrivileges {
+                role: "bob".to_string(),
+                object: GrantObject::Schema("public".to_string()),
+                privileges: vec![Privilege::Usage],
+            })],
+        )
+        .await
+        .unwrap_err();
+
+        db.try_mutate(
+            state.version,
+            vec![Mutation::DropRole(DropRole {
+                name: "alice".to_string(),
+                if_exists: false,
+            })],
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn refresh_materialized_view() {
+        let db = new_catalog().await;
+        let initial = version(&db).await;
+
+        let table = |name: &str, materialized_view| {
+            Mutation::CreateTable(CreateTable {
+                schema: "public".to_string(),
+                name: name.to_string(),
+                options: TableOptionsInternal {
+                    columns: Vec::new(),
+                    partition_columns: Vec::new(),
+                    primary_key: None,
+                    unique_constraints: Vec::new(),
+                    check_constraints: Vec::new(),
+                    materialized_view,
+                },
+                if_not_exists: false,
+                or_replace: false,
+            })
+        };
+        let state = db
+            .try_mutate(
+                initial,
+                vec![
+                    table(
+                        "mv",
+                        Some(InternalMaterializedView {
+                            sql: "SELECT 1".to_string(),
+                            refresh_interval_secs: Some(60),
+                            last_refreshed: None,
+                            owner_name: None,
+                            owner_id: None,
+                        }),
+                    ),
+                    table("plain", None),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let refresh = |name: &str| {
+            Mutation::RefreshMaterializedView(RefreshMaterializedView {
+                schema: "public".to_string(),
+                name: name.to_string(),
+                refreshed_at: 1000,
+            })
+        };
+        let state = db
+            .try_mutate(state.version, vec![refresh("mv")])
+            .await
+            .unwrap();
+
+        let view = state
+            .entries
+            .values()
+            .find_map(|ent| match ent {
+                CatalogEntry::Table(TableEntry {
+                    meta,
+                    options: TableOptions::Internal(opts),
+                    ..
+                }) if meta.name == "mv" => opts.materialized_view.clone(),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(Some(1000), view.last_refreshed);
+        assert_eq!(Some(60), view.refresh_interval_secs);
+
+        // Plain tables and missing objects can't be refreshed.
+        db.try_mutate(state.version, vec![refresh("plain")])
+            .await
+            .unwrap_err();
+        db.try_mutate(state.version, vec![refresh("missing")])
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn create_and_drop_function() {
+        let db = new_catalog().await;
+        let initial = version(&db).await;
+
+        let create = |name: &str, body: &str, or_replace| {
+            Mutation::CreateFunction(CreateFunction {
+                schema: "public".to_string(),
+                name: name.to_string(),
+                or_replace,
+                func_type: FunctionType::Scalar,
+                sql_function: SqlFunction {
+                    params: Vec::new(),
+                    return_type: Some(DataType::Int64),
+                    return_columns: Vec::new(),
+                    body: body.to_string(),
+                },
+            })
+        };
+        let drop = |name: &str, if_exists| {
+            Mutation::DropFunction(DropFunction {
+                schema: "public".to_string(),
+                name: name.to_string(),
+                if_exists,
+            })
+        };
+        let find = |state: &CatalogState, name: &str| {
+            state.entries.values().find_map(|ent| match ent {
+                CatalogEntry::Function(func) if func.meta.name == name => Some(func.clone()),
+                _ => None,
+            })
+        };
+
+        let state = db
+            .try_mutate(initial, vec![create("one", "1", false)])
+            .await
+            .unwrap();
+        let func = find(&state, "one").unwrap();
+        assert_eq!(FunctionType::Scalar, func.func_type);
+        assert_eq!("1", func.sql_function.unwrap().body);
+
+        // Duplicate names need OR REPLACE.
+        db.try_mutate(state.version, vec![create("one", "2", false)])
+            .await
+            .unwrap_err();
+        let state = db
+            .try_mutate(state.version, vec![create("one", "2", true)])
+            .await
+            .unwrap();
+        let replaced = find(&state, "one").unwrap();
+        assert_eq!(func.meta.id, replaced.meta.id);
+        assert_eq!("2", replaced.sql_function.unwrap().body);
+
+        // Builtin table functions can't be replaced or dropped.
+        db.try_mutate(state.version, vec![create("csv_scan", "1", true)])
+            .await
+            .unwrap_err();
+        db.try_mutate(state.version, vec![drop("csv_scan", false)])
+            .await
+            .unwrap_err();
+
+        let state = db
+            .try_mutate(state.version, vec![drop("one", false)])
+            .await
+            .unwrap();
+        assert!(find(&state, "one").is_none());
+
+        db.try_mutate(state.version, vec![drop("one", false)])
+            .await
+            .unwrap_err();
+        db.try_mutate(state.version, vec![drop("one", true)])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn sequences() {
+        let db = new_catalog().await;
+        let initial = version(&db).await;
+
+        let create = |name: &str, owned_by_table: Option<&str>| {
+            Mutation::CreateSequence(CreateSequence {
+                schema: "public".to_string(),
+                name: name.to_string(),
+                if_not_exists: false,
+                start: 1,
+                increment: 2,
+                min_value: 1,
+                max_value: 7,
+                owned_by_table: owned_by_table.map(String::from),
+            })
+        };
+        let advance = |count| {
+            Mutation::AdvanceSequence(AdvanceSequence {
+                schema: "public".to_string(),
+                name: "seq".to_string(),
+                count,
+            })
+        };
+        let find = |state: &CatalogState, name: &str| {
+            state.entries.values().find_map(|ent| match ent {
+                CatalogEntry::Sequence(seq) if seq.meta.name == name => Some(seq.clone()),
+                _ => None,
+            })
+        };
+
+        let state = db
+            .try_mutate(initial, vec![create("seq", None)])
+            .await
+            .unwrap();
+        let seq = find(&state, "seq").unwrap();
+        assert_eq!((1, false), (seq.last_value, seq.is_called));
+
+        // Sequences share a namespace with tables.
+        db.try_mutate(state.version, vec![create("seq", None)])
+            .await
+            .unwrap_err();
+
+        // Allocates 1, 3
+        let state = db
+            .try_mutate(state.version, vec![advance(2)])
+            .await
+            .unwrap();
+        let seq = find(&state, "seq").unwrap();
+        assert_eq!((3, true), (seq.last_value, seq.is_called));
+
+        // Allocates 5, 7, then runs out.
+        db.try_mutate(state.version, vec![advance(3)])
+            .await
+            .unwrap_err();
+        let state = db
+            .try_mutate(state.version, vec![advance(2)])
+            .await
+            .unwrap();
+        assert_eq!(7, find(&state, "seq").unwrap().last_value);
+
+        let state = db
+            .try_mutate(
+                state.version,
+                vec![Mutation::SetSequenceValue(SetSequenceValue {
+                    schema: "public".to_string(),
+                    name: "seq".to_string(),
+                    value: 2,
+                    is_called: false,
+                })],
+            )
+            .await
+            .unwrap();
+        let state = db
+            .try_mutate(state.version, vec![advance(1)])
+            .await
+            .unwrap();
+        assert_eq!(2, find(&state, "seq").unwrap().last_value);
+
+        // Sequences owned by a table are dropped with it.
+        let state = db
+            .try_mutate(
+                state.version,
+                vec![
+                    Mutation::CreateTable(CreateTable {
+                        schema: "public".to_string(),
+                        name: "tbl".to_string(),
+                        options: TableOptionsInternal {
+                            columns: Vec::new(),
+                            partition_columns: Vec::new(),
+                            primary_key: None,
+                            unique_constraints: Vec::new(),
+                            check_constraints: Vec::new(),
+                            materialized_view: None,
+                        },
+                        if_not_exists: false,
+                        or_replace: false,
+                    }),
+                    create("tbl_id_seq", Some("tbl")),
+                ],
+            )
+            .await
+            .unwrap();
+        assert!(find(&state, "tbl_id_seq").unwrap().owned_by.is_some());
+
+        let state = db
+            .try_mutate(
+                state.version,
+                vec![Mutation::DropObject(DropObject {
+                    schema: "public".to_string(),
+                    name: "tbl".to_string(),
+                    if_exists: false,
+                })],
+            )
+            .await
+            .unwrap();
+        assert!(find(&state, "tbl_id_seq").is_none());
+        assert!(find(&state, "seq").is_some());
+
+        // Values can be allocated against a stale catalog.
+        let state = db.try_mutate(initial, vec![advance(1)]).await.unwrap();
+        assert_eq!(4, find(&state, "seq").unwrap().last_value);
+
+        // Other mutations still need the latest version.
+        db.try_mutate(initial, vec![create("other", None)])
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn comments() {
+        let db = new_catalog().await;
+        let initial = version(&db).await;
+
+        let comment_on = |object: CommentObject, comment: Option<&str>| {
+            Mutation::CommentOn(CommentOn {
+                object,
+                comment: comment.map(String::from),
+            })
+        };
+        let table = |schema: &str, name: &str| CommentObject::Table {
+            schema: schema.to_string(),
+            name: name.to_string(),
+        };
+        let column = |name: &str| CommentObject::Column {
+            schema: "public".to_string(),
+            table: "tbl".to_string(),
+            column: name.to_string(),
+        };
+        let find = |state: &CatalogState, name: &str| {
+            state
+                .entries
+                .values()
+                .find(|ent| ent.get_meta().name == name)
+                .cloned()
+                .unwrap()
+        };
+
+        let state = db
+            .try_mutate(
+                initial,
+                vec![
+                    Mutation::CreateTable(CreateTable {
+                        schema: "public".to_string(),
+                        name: "tbl".to_string(),
+                        options: TableOptionsInternal {
+                            columns: InternalColumnDefinition::from_tuples([(
+                                "a",
+                                DataType::Int32,
+                                true,
+                            )]),
+                            partition_columns: Vec::new(),
+                            primary_key: None,
+                            unique_constraints: Vec::new(),
+                            check_constraints: Vec::new(),
+                            materialized_view: None,
+                        },
+                        if_not_exists: false,
+                        or_replace: false,
+                    }),
+                    Mutation::CreateView(CreateView {
+                        schema: "public".to_string(),
+                        name: "vw".to_string(),
+                        sql: "select 1".to_string(),
+                        or_replace: false,
+                        columns: Vec::new(),
+                    }),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let state = db
+            .try_mutate(
+                state.version,
+                vec![
+                    comment_on(table("public", "tbl"), Some("my table")),
+                    comment_on(column("a"), Some("my column")),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let ent = find(&state, "tbl");
+        assert_eq!(Some("my table"), ent.get_meta().comment.as_deref());
+        match ent {
+            CatalogEntry::Table(ent) => {
+                let cols = ent.get_internal_columns().unwrap();
+                assert_eq!(Some("my column"), cols[0].comment.as_deref());
+            }
+            other => panic!("unexpected entry: {other:?}"),
+        }
+
+        // Removing a comment.
+        let state = db
+            .try_mutate(
+                state.version,
+                vec![comment_on(table("public", "tbl"), None)],
+            )
+            .await
+            .unwrap();
+        assert_eq!(None, find(&state, "tbl").get_meta().comment);
+
+        // Object types must match.
+        db.try_mutate(
+            state.version,
+            vec![comment_on(table("public", "vw"), Some("not a table"))],
+        )
+        .await
+        .unwrap_err();
+
+        // Missing objects and columns.
+        db.try_mutate(
+            state.version,
+            vec![comment_on(table("public", "missing"), Some("missing"))],
+        )
+        .await
+        .unwrap_err();
+        db.try_mutate(
+            state.version,
+            vec![comment_on(column("b"), Some("missing"))],
+        )
+        .await
+        .unwrap_err();
+
+        // Builtin objects can't be commented on.
+        db.try_mutate(
+            state.version,
+            vec![comment_on(
+                CommentObject::Schema("information_schema".to_string()),
+                Some("builtin"),
+            )],
+        )
+        .await
+        .unwrap_err();
+    }
 }
@@ -32,6 +32,9 @@ pub enum MetastoreError {
     #[error("Missing credentials: {0}")]
     MissingCredentials(String),
 
+    #[error("Missing role: {0}")]
+    MissingRole(String),
+
     #[error("Missing schema: {0}")]
     MissingNamedSchema(String),
 
@@ -41,6 +44,42 @@ pub enum MetastoreError {
     #[error("Missing entry: {0}")]
     MissingEntry(u32),
 
+    #[error("Not a materialized view: {0}")]
+    NotMaterializedView(String),
+
+    #[error("Not a sequence: {0}")]
+    NotASequence(String),
+
+    #[error("Invalid sequence '{0}': increment must be non-zero, and start must be between the minimum and maximum values")]
+    InvalidSequence(String),
+
+    #[error("Sequence '{0}' has reached its limit")]
+    SequenceExhausted(String),
+
+    #[error("Value {value} is out of bounds for sequence '{name}' ({min}..{max})")]
+    SequenceValueOutOfBounds {
+        name: String,
+        value: i64,
+        min: i64,
+        max: i64,
+    },
+
+    #[error("Privileges cannot be granted on {0}")]
+    InvalidGrantObject(String),
+
+    #[error("Comments cannot be set on {0}")]
+    InvalidCommentObject(String),
+
+    #[error("'{name}' is a {found}, not a {expected}")]
+    ObjectTypeMismatch {
+        name: String,
+        expected: protogen::metastore::types::catalog::EntryType,
+        found: protogen::metastore::types::catalog::EntryType,
+    },
+
+    #[error("Missing column '{column}' in table '{table}'")]
+    MissingColumn { table: String, column: String },
+
     #[error("Tunnel '{tunnel} not supported for {action}'")]
     TunnelNotSupportedForAction {
         tunnel: String,
@@ -41,6 +41,9 @@ pub enum PgSrvError {
     #[error("Invalid user or password")]
     InvalidUserOrPassword,
 
+    #[error("Certificate common name '{common_name}' does not match user '{user}'")]
+    CertificateUserMismatch { common_name: String, user: String },
+
     /// A stringified error from cloud.
     #[error("cloud: {0}")]
     CloudResponse(String),
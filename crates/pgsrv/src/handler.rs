@@ -21,6 +21,7 @@ use pgrepr::format::Format;
 use pgrepr::scalar::Scalar;
 use sqlexec::context::local::{OutputFields, Portal, PreparedStatement};
 use sqlexec::engine::SessionStorageConfig;
+use sqlexec::planner::privileges::RoleAuthentication;
 use sqlexec::{
     engine::Engine,
     parser::{self, StatementWithExtensions},
@@ -37,7 +38,7 @@ use uuid::Uuid;
 
 pub struct ProtocolHandlerConfig {
     /// Authenticor to use on the server side.
-    pub authenticator: Box<dyn LocalAuthenticator>,
+    pub authenticator: Arc<dyn LocalAuthenticator>,
     /// SSL configuration to use on the server side.
     pub ssl_conf: Option<SslConfig>,
     /// If the server should be configured for integration tests. This is only
@@ -140,6 +141,10 @@ impl ProtocolHandler {
     {
         debug!("starting protocol with params: {:?}", params);
 
+        // Common name of a verified client certificate, if the client
+        // presented one.
+        let cert_user = conn.peer_common_name();
+
         let mut framed = FramedConn::new(conn);
 
         // Get params.
@@ -178,66 +183,115 @@ impl ProtocolHandler {
             db_id
         };
 
-        // Handle password.
-        match self.conf.authenticator.password_mode() {
-            PasswordMode::RequireCleartext => {
+        // Once roles exist, users must log in as a role that's allowed to log
+        // in. Roles with a password are authenticated with that password
+        // instead of the server's authenticator.
+        let role_auth = match self.engine.authenticate_role(db_id, &user_name, None).await {
+            Ok(role_auth) => role_auth,
+            Err(e) => {
+                let e = PgSrvError::from(e);
                 framed
-                    .send(BackendMessage::AuthenticationCleartextPassword)
+                    .send(
+                        ErrorResponse::fatal_internal(format!("Failed to authenticate: {}", e))
+                            .into(),
+                    )
                     .await?;
-                let msg = framed.read().await?;
-                match msg {
-                    Some(FrontendMessage::PasswordMessage { password }) => {
-                        match self.conf.authenticator.authenticate(
-                            &user_name,
-                            &password,
-                            &database_name,
-                        ) {
-                            Ok(sess) => sess,
-                            Err(e) => {
-                                framed
-                                    .send(
-                                        ErrorResponse::fatal_internal(format!(
-                                            "Failed to authenticate: {}",
-                                            e
-                                        ))
-                                        .into(),
-                                    )
-                                    .await?;
-                                return Err(e);
-                            }
-                        }
-                        framed.send(BackendMessage::AuthenticationOk).await?;
-                    }
-                    Some(other) => {
-                        // TODO: Send error.
-                        return Err(PgSrvError::UnexpectedFrontendMessage(Box::new(other)));
-                    }
-                    None => return Ok(()),
-                }
+                return Err(e);
             }
-            PasswordMode::NoPassword { drop_auth_messages } => {
-                if drop_auth_messages {
-                    // Send the message to frontend to ask for an auth message.
-                    // We will drop this message later on.
+        };
+        let password_mode = match role_auth {
+            RoleAuthentication::PasswordRequired => PasswordMode::RequireCleartext,
+            _ => self.conf.authenticator.password_mode(),
+        };
+
+        // Handle authentication. A verified client certificate authenticates
+        // the user in its common name, bypassing password authentication.
+        if let Some(common_name) = cert_user {
+            if common_name != user_name {
+                let e = PgSrvError::CertificateUserMismatch {
+                    common_name,
+                    user: user_name,
+                };
+                framed
+                    .send(
+                        ErrorResponse::fatal_internal(format!("Failed to authenticate: {}", e))
+                            .into(),
+                    )
+                    .await?;
+                return Err(e);
+            }
+            debug!(%user_name, "authenticated using client certificate");
+            framed.send(BackendMessage::AuthenticationOk).await?;
+        } else {
+            match password_mode {
+                PasswordMode::RequireCleartext => {
                     framed
                         .send(BackendMessage::AuthenticationCleartextPassword)
                         .await?;
-
-                    // Read the auth message from the frontend. This will be
-                    // ignored.
-                    let msg = framed.peek().await?;
+                    let msg = framed.read().await?;
                     match msg {
-                        Some(msg) if msg.is_auth_message() => {
-                            let dropped = framed.read().await?; // Drop auth message.
-                            warn!(?dropped, "dropping authentication message");
+                        Some(FrontendMessage::PasswordMessage { password }) => {
+                            let result = match role_auth {
+                                RoleAuthentication::PasswordRequired => self
+                                    .engine
+                                    .authenticate_role(db_id, &user_name, Some(&password))
+                                    .await
+                                    .map(|_| ())
+                                    .map_err(PgSrvError::from),
+                                _ => self.conf.authenticator.authenticate(
+                                    &user_name,
+                                    &password,
+                                    &database_name,
+                                ),
+                            };
+                            match result {
+                                Ok(sess) => sess,
+                                Err(e) => {
+                                    framed
+                                        .send(
+                                            ErrorResponse::fatal_internal(format!(
+                                                "Failed to authenticate: {}",
+                                                e
+                                            ))
+                                            .into(),
+                                        )
+                                        .await?;
+                                    return Err(e);
+                                }
+                            }
+                            framed.send(BackendMessage::AuthenticationOk).await?;
+                        }
+                        Some(other) => {
+                            // TODO: Send error.
+                            return Err(PgSrvError::UnexpectedFrontendMessage(Box::new(other)));
                         }
-                        Some(_msg) => (), // We peeked a message not related to auth.
-                        None => return Ok(()), // Connection closed
+                        None => return Ok(()),
                     }
                 }
+                PasswordMode::NoPassword { drop_auth_messages } => {
+                    if drop_auth_messages {
+                        // Send the message to frontend to ask for an auth message.
+                        // We will drop this message later on.
+                        framed
+                            .send(BackendMessage::AuthenticationCleartextPassword)
+                            .await?;
+
+                        // Read the auth message from the frontend. This will be
+                        // ignored.
+                        let msg = framed.peek().await?;
+                        match msg {
+                            Some(msg) if msg.is_auth_message() => {
+                                let dropped = framed.read().await?; // Drop auth message.
+                                warn!(?dropped, "dropping authentication message");
+                            }
+                            Some(_msg) => (), // We peeked a message not related to auth.
+                            None => return Ok(()), // Connection closed
+                        }
+                    }
 
-                // Nothin to do.
-                framed.send(BackendMessage::AuthenticationOk).await?;
+                    // Nothin to do.
+                    framed.send(BackendMessage::AuthenticationOk).await?;
+                }
             }
         }
         let mut vars = SessionVars::default()
@@ -349,8 +403,26 @@ where
 
     async fn run(mut self) -> Result<()> {
         self.ready_for_query().await?;
+
+        // Resolves once the session is terminated by another session (e.g.
+        // through `pg_terminate_backend`).
+        let terminated = self.session.activity().terminated();
+        tokio::pin!(terminated);
+
         loop {
-            let msg = self.conn.read().await?;
+            let msg = tokio::select! {
+                msg = self.conn.read() => msg?,
+                _ = &mut terminated => {
+                    debug!("session terminated");
+                    self.send_error(ErrorResponse::fatal(
+                        SqlState::AdminShutdown,
+                        "terminating connection due to administrator command",
+                    ))
+                    .await?;
+                    self.flush().await?;
+                    return Ok(());
+                }
+            };
 
             let msg = match msg {
                 Some(msg) => msg,
@@ -740,6 +812,24 @@ where
             ExecutionResult::DropCredentials => {
                 Self::command_complete(conn, "DROP CREDENTIALS").await?
             }
+            ExecutionResult::CreateRole => Self::command_complete(conn, "CREATE ROLE").await?,
+            ExecutionResult::DropRole => Self::command_complete(conn, "DROP ROLE").await?,
+            ExecutionResult::Grant => Self::command_complete(conn, "GRANT").await?,
+            ExecutionResult::Revoke => Self::command_complete(conn, "REVOKE").await?,
+            ExecutionResult::RefreshMaterializedView => {
+                Self::command_complete(conn, "REFRESH MATERIALIZED VIEW").await?
+            }
+            ExecutionResult::CreateFunction => {
+                Self::command_complete(conn, "CREATE FUNCTION").await?
+            }
+            ExecutionResult::DropFunctions => Self::command_complete(conn, "DROP FUNCTION").await?,
+            ExecutionResult::CreateSequence => {
+                Self::command_complete(conn, "CREATE SEQUENCE").await?
+            }
+            ExecutionResult::DropSequences => Self::command_complete(conn, "DROP SEQUENCE").await?,
+            ExecutionResult::Comment => Self::command_complete(conn, "COMMENT").await?,
+            ExecutionResult::Prepare => Self::command_complete(conn, "PREPARE").await?,
+            ExecutionResult::Deallocate => Self::command_complete(conn, "DEALLOCATE").await?,
         };
         Ok(())
     }
@@ -772,7 +862,7 @@ where
             let batch = match result {
                 Ok(r) => r,
                 Err(e) => {
-                    conn.send(ErrorResponse::error(SqlState::InternalError, e.to_string()).into())
+                    conn.send(ErrorResponse::error(SqlState::from(&e), e.to_string()).into())
                         .await?;
                     return Ok(None);
                 }
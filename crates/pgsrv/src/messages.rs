@@ -1,4 +1,5 @@
 use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::DataFusionError;
 use pgrepr::error::PgReprError;
 use pgrepr::format::Format;
 use sqlexec::errors::ExecError;
@@ -198,6 +199,16 @@ pub enum SqlState {
     // Class 42 — Syntax Error or Access Rule Violation
     SyntaxError,
 
+    // Class 53 — Insufficient Resources
+    ConfigurationLimitExceeded,
+
+    // Class 54 — Program Limit Exceeded
+    ProgramLimitExceeded,
+
+    // Class 57 — Operator Intervention
+    QueryCanceled,
+    AdminShutdown,
+
     // Class XX — Internal Error
     InternalError,
 }
@@ -209,6 +220,10 @@ impl SqlState {
             SqlState::Warning => "01000",
             SqlState::FeatureNotSupported => "0A000",
             SqlState::SyntaxError => "42601",
+            SqlState::ConfigurationLimitExceeded => "53400",
+            SqlState::ProgramLimitExceeded => "54000",
+            SqlState::QueryCanceled => "57014",
+            SqlState::AdminShutdown => "57P01",
             SqlState::InternalError => "XX000",
         }
     }
@@ -238,6 +253,14 @@ impl ErrorResponse {
         Self::error(SqlState::InternalError, msg)
     }
 
+    pub fn fatal(code: SqlState, msg: impl Into<String>) -> ErrorResponse {
+        ErrorResponse {
+            severity: ErrorSeverity::Fatal,
+            code,
+            message: msg.into(),
+        }
+    }
+
     pub fn fatal_internal(msg: impl Into<String>) -> ErrorResponse {
         ErrorResponse {
             severity: ErrorSeverity::Fatal,
@@ -247,17 +270,50 @@ impl ErrorResponse {
     }
 }
 
+impl From<&ExecError> for SqlState {
+    fn from(e: &ExecError) -> Self {
+        // TODO: Set appropriate codes for other errors.
+        match e {
+            ExecError::StatementTimeout { .. } | ExecError::QueryCanceled => {
+                SqlState::QueryCanceled
+            }
+            ExecError::SessionTerminated => SqlState::AdminShutdown,
+            ExecError::MaxScanBytesExceeded { .. } | ExecError::MaxResultRowsExceeded { .. } => {
+                SqlState::ProgramLimitExceeded
+            }
+            ExecError::QueryQueueTimeout { .. } => SqlState::ConfigurationLimitExceeded,
+            _ => SqlState::InternalError,
+        }
+    }
+}
+
+impl From<&DataFusionError> for SqlState {
+    fn from(e: &DataFusionError) -> Self {
+        match e {
+            DataFusionError::External(e) => match e.downcast_ref::<ExecError>() {
+                Some(e) => e.into(),
+                None => SqlState::InternalError,
+            },
+            _ => SqlState::InternalError,
+        }
+    }
+}
+
 impl From<ExecError> for ErrorResponse {
     fn from(e: ExecError) -> Self {
-        // TODO: Actually set appropriate codes.
-        ErrorResponse::error_internal(e.to_string())
+        ErrorResponse::error(SqlState::from(&e), e.to_string())
     }
 }
 
 impl From<&PgSrvError> for ErrorResponse {
     fn from(e: &PgSrvError) -> Self {
         // TODO: Actually set appropriate codes.
-        ErrorResponse::error_internal(e.to_string())
+        let code = match e {
+            PgSrvError::SqlExec(e) => e.into(),
+            PgSrvError::Datafusion(e) => e.into(),
+            _ => SqlState::InternalError,
+        };
+        ErrorResponse::error(code, e.to_string())
     }
 }
 
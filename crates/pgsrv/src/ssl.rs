@@ -1,5 +1,6 @@
 use crate::errors::{PgSrvError, Result};
-use rustls::{server, sign, Certificate, PrivateKey, ServerConfig};
+use rustls::server::AllowAnyAnonymousOrAuthenticatedClient;
+use rustls::{server, sign, Certificate, PrivateKey, RootCertStore, ServerConfig};
 use std::path::Path;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -18,6 +19,20 @@ pub struct SslConfig {
 impl SslConfig {
     /// Create a new ssl config using the provided cert and key files.
     pub async fn new<P: AsRef<Path>>(cert: P, key: P) -> Result<SslConfig> {
+        Self::new_with_client_ca(cert, key, None).await
+    }
+
+    /// Create a new ssl config using the provided cert and key files,
+    /// optionally verifying client certificates against the certificate
+    /// authorities in `client_ca`.
+    ///
+    /// Client certificates are optional. If a client does present a
+    /// certificate, it must be signed by one of the provided authorities.
+    pub async fn new_with_client_ca<P: AsRef<Path>>(
+        cert: P,
+        key: P,
+        client_ca: Option<P>,
+    ) -> Result<SslConfig> {
         let cert_bs = fs::read(cert).await?;
         let chain: Vec<_> = rustls_pemfile::certs(&mut cert_bs.as_slice())?
             .into_iter()
@@ -34,12 +49,29 @@ impl SslConfig {
 
         let resolver = CertResolver::new(chain, &key)?;
 
-        let config = ServerConfig::builder()
+        let builder = ServerConfig::builder()
             .with_safe_default_cipher_suites()
             .with_safe_default_kx_groups()
-            .with_safe_default_protocol_versions()?
-            .with_no_client_auth()
-            .with_cert_resolver(Arc::new(resolver));
+            .with_safe_default_protocol_versions()?;
+
+        let builder = match client_ca {
+            Some(client_ca) => {
+                let ca_bs = fs::read(client_ca).await?;
+                let mut roots = RootCertStore::empty();
+                for cert in rustls_pemfile::certs(&mut ca_bs.as_slice())? {
+                    roots.add(&Certificate(cert))?;
+                }
+                if roots.is_empty() {
+                    return Err(PgSrvError::ReadCertsAndKeys("No client CA certs found"));
+                }
+                builder.with_client_cert_verifier(
+                    AllowAnyAnonymousOrAuthenticatedClient::new(roots).boxed(),
+                )
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let config = builder.with_cert_resolver(Arc::new(resolver));
 
         Ok(SslConfig {
             config: Arc::new(config),
@@ -96,6 +128,30 @@ where
             Self::Encrypted(stream) => stream.get_ref().1.server_name().map(|s| s.to_string()),
         }
     }
+
+    /// Get the common name from the subject of the client's certificate.
+    ///
+    /// Returns `None` if the connection isn't encrypted or the client didn't
+    /// present a (verified) certificate.
+    pub fn peer_common_name(&self) -> Option<String> {
+        match self {
+            Self::Unencrypted(_) => None,
+            Self::Encrypted(stream) => stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(|certs| certs.first())
+                .and_then(|cert| common_name_from_der(&cert.0)),
+        }
+    }
+}
+
+/// Extract the common name from the subject of a DER encoded X.509
+/// certificate.
+fn common_name_from_der(der: &[u8]) -> Option<String> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+    let name = cert.subject().iter_common_name().next()?;
+    name.as_str().ok().map(|s| s.to_string())
 }
 
 impl<C> AsyncRead for Connection<C>
@@ -220,4 +276,29 @@ yISdkDEeNZpoI5xRIgPnTBQ=
 
         let _ = SslConfig::new(cert.path(), key.path()).await.unwrap();
     }
+
+    #[tokio::test]
+    async fn create_with_client_ca() {
+        let cert = create_file(TEST_CERT);
+        let key = create_file(TEST_KEY);
+        let ca = create_file(TEST_CERT);
+
+        let _ = SslConfig::new_with_client_ca(cert.path(), key.path(), Some(ca.path()))
+            .await
+            .unwrap();
+
+        let invalid_ca = create_file("invalid");
+        let _ = SslConfig::new_with_client_ca(cert.path(), key.path(), Some(invalid_ca.path()))
+            .await
+            .unwrap_err();
+    }
+
+    #[test]
+    fn common_name_from_cert() {
+        let certs = rustls_pemfile::certs(&mut TEST_CERT.as_bytes()).unwrap();
+        let name = common_name_from_der(&certs[0]);
+        assert_eq!(Some("glaredb.com".to_string()), name);
+
+        assert_eq!(None, common_name_from_der(b"invalid"));
+    }
 }
@@ -2,8 +2,10 @@ use super::options::{
     CredentialsOptions, InternalColumnDefinition, TableOptionsInternal, TunnelOptions,
 };
 use super::options::{DatabaseOptions, TableOptions};
+use crate::gen::common::arrow;
 use crate::gen::metastore::catalog;
 use crate::{FromOptionalField, ProtoConvError};
+use datafusion::arrow::datatypes::DataType;
 use proptest_derive::Arbitrary;
 use std::collections::HashMap;
 use std::fmt;
@@ -94,6 +96,8 @@ pub enum CatalogEntry {
     Tunnel(TunnelEntry),
     Function(FunctionEntry),
     Credentials(CredentialsEntry),
+    Role(RoleEntry),
+    Sequence(SequenceEntry),
 }
 
 impl CatalogEntry {
@@ -106,6 +110,8 @@ impl CatalogEntry {
             CatalogEntry::Tunnel(_) => EntryType::Tunnel,
             CatalogEntry::Function(_) => EntryType::Function,
             CatalogEntry::Credentials(_) => EntryType::Credentials,
+            CatalogEntry::Role(_) => EntryType::Role,
+            CatalogEntry::Sequence(_) => EntryType::Sequence,
         }
     }
 
@@ -119,6 +125,8 @@ impl CatalogEntry {
             CatalogEntry::Tunnel(tunnel) => &tunnel.meta,
             CatalogEntry::Function(func) => &func.meta,
             CatalogEntry::Credentials(creds) => &creds.meta,
+            CatalogEntry::Role(role) => &role.meta,
+            CatalogEntry::Sequence(seq) => &seq.meta,
         }
     }
 
@@ -132,6 +140,8 @@ impl CatalogEntry {
             CatalogEntry::Tunnel(tunnel) => &mut tunnel.meta,
             CatalogEntry::Function(func) => &mut func.meta,
             CatalogEntry::Credentials(creds) => &mut creds.meta,
+            CatalogEntry::Role(role) => &mut role.meta,
+            CatalogEntry::Sequence(seq) => &mut seq.meta,
         }
     }
 }
@@ -149,6 +159,8 @@ impl TryFrom<catalog::catalog_entry::Entry> for CatalogEntry {
             catalog::catalog_entry::Entry::Credentials(v) => {
                 CatalogEntry::Credentials(v.try_into()?)
             }
+            catalog::catalog_entry::Entry::Role(v) => CatalogEntry::Role(v.try_into()?),
+            catalog::catalog_entry::Entry::Sequence(v) => CatalogEntry::Sequence(v.try_into()?),
         })
     }
 }
@@ -169,8 +181,10 @@ impl TryFrom<CatalogEntry> for catalog::CatalogEntry {
             CatalogEntry::View(v) => catalog::catalog_entry::Entry::View(v.into()),
             CatalogEntry::Table(v) => catalog::catalog_entry::Entry::Table(v.try_into()?),
             CatalogEntry::Tunnel(v) => catalog::catalog_entry::Entry::Tunnel(v.into()),
-            CatalogEntry::Function(v) => catalog::catalog_entry::Entry::Function(v.into()),
+            CatalogEntry::Function(v) => catalog::catalog_entry::Entry::Function(v.try_into()?),
             CatalogEntry::Credentials(v) => catalog::catalog_entry::Entry::Credentials(v.into()),
+            CatalogEntry::Role(v) => catalog::catalog_entry::Entry::Role(v.into()),
+            CatalogEntry::Sequence(v) => catalog::catalog_entry::Entry::Sequence(v.into()),
         };
         Ok(catalog::CatalogEntry { entry: Some(ent) })
     }
@@ -185,6 +199,8 @@ pub enum EntryType {
     Tunnel,
     Function,
     Credentials,
+    Role,
+    Sequence,
 }
 
 impl EntryType {
@@ -197,6 +213,8 @@ impl EntryType {
             EntryType::Tunnel => "tunnel",
             EntryType::Function => "function",
             EntryType::Credentials => "credentials",
+            EntryType::Role => "role",
+            EntryType::Sequence => "sequence",
         }
     }
 }
@@ -224,6 +242,8 @@ impl TryFrom<catalog::entry_meta::EntryType> for EntryType {
             catalog::entry_meta::EntryType::Tunnel => EntryType::Tunnel,
             catalog::entry_meta::EntryType::Function => EntryType::Function,
             catalog::entry_meta::EntryType::Credentials => EntryType::Credentials,
+            catalog::entry_meta::EntryType::Role => EntryType::Role,
+            catalog::entry_meta::EntryType::Sequence => EntryType::Sequence,
         })
     }
 }
@@ -238,6 +258,8 @@ impl From<EntryType> for catalog::entry_meta::EntryType {
             EntryType::Tunnel => catalog::entry_meta::EntryType::Tunnel,
             EntryType::Function => catalog::entry_meta::EntryType::Function,
             EntryType::Credentials => catalog::entry_meta::EntryType::Credentials,
+            EntryType::Role => catalog::entry_meta::EntryType::Role,
+            EntryType::Sequence => catalog::entry_meta::EntryType::Sequence,
         }
     }
 }
@@ -262,6 +284,8 @@ pub struct EntryMeta {
     pub builtin: bool,
     pub external: bool,
     pub is_temp: bool,
+    /// User provided description of the entry.
+    pub comment: Option<String>,
 }
 
 impl From<EntryMeta> for catalog::EntryMeta {
@@ -275,6 +299,7 @@ impl From<EntryMeta> for catalog::EntryMeta {
             builtin: value.builtin,
             external: value.external,
             is_temp: value.is_temp,
+            comment: value.comment,
         }
     }
 }
@@ -290,6 +315,7 @@ impl TryFrom<catalog::EntryMeta> for EntryMeta {
             builtin: value.builtin,
             external: value.external,
             is_temp: value.is_temp,
+            comment: value.comment,
         })
     }
 }
@@ -445,7 +471,7 @@ impl From<TunnelEntry> for catalog::TunnelEntry {
     }
 }
 
-#[derive(Debug, Clone, Copy, Arbitrary, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Arbitrary, PartialEq, Eq, Hash)]
 pub enum FunctionType {
     Aggregate,
     Scalar,
@@ -552,6 +578,7 @@ pub struct FunctionEntry {
     pub meta: EntryMeta,
     pub func_type: FunctionType,
     pub runtime_preference: RuntimePreference,
+    pub sql_function: Option<SqlFunction>,
 }
 
 impl TryFrom<catalog::FunctionEntry> for FunctionEntry {
@@ -562,20 +589,88 @@ impl TryFrom<catalog::FunctionEntry> for FunctionEntry {
             meta,
             func_type: value.func_type.try_into()?,
             runtime_preference: value.runtime_preference.try_into()?,
+            sql_function: value.sql_function.optional()?,
         })
     }
 }
 
-impl From<FunctionEntry> for catalog::FunctionEntry {
-    fn from(value: FunctionEntry) -> Self {
+impl TryFrom<FunctionEntry> for catalog::FunctionEntry {
+    type Error = ProtoConvError;
+    fn try_from(value: FunctionEntry) -> Result<Self, Self::Error> {
         let func_type: catalog::function_entry::FunctionType = value.func_type.into();
         let runtime_preference: catalog::function_entry::RuntimePreference =
             value.runtime_preference.into();
-        catalog::FunctionEntry {
+        Ok(catalog::FunctionEntry {
             meta: Some(value.meta.into()),
             func_type: func_type as i32,
             runtime_preference: runtime_preference as i32,
-        }
+            sql_function: value.sql_function.map(|f| f.try_into()).transpose()?,
+        })
+    }
+}
+
+/// A function created with `CREATE FUNCTION`.
+///
+/// The body is inlined into queries calling the function during planning.
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct SqlFunction {
+    pub params: Vec<InternalColumnDefinition>,
+    /// Return type for scalar functions.
+    #[proptest(value("None"))]
+    pub return_type: Option<DataType>,
+    /// Output columns for table returning functions. Empty if the columns are
+    /// taken from the body.
+    pub return_columns: Vec<InternalColumnDefinition>,
+    /// An expression for scalar functions, or a query for table returning
+    /// functions.
+    pub body: String,
+}
+
+impl TryFrom<catalog::SqlFunction> for SqlFunction {
+    type Error = ProtoConvError;
+    fn try_from(value: catalog::SqlFunction) -> Result<Self, Self::Error> {
+        Ok(SqlFunction {
+            params: value
+                .params
+                .into_iter()
+                .map(|col| col.try_into())
+                .collect::<Result<_, _>>()?,
+            return_type: value
+                .return_type
+                .as_ref()
+                .map(DataType::try_from)
+                .transpose()?,
+            return_columns: value
+                .return_columns
+                .into_iter()
+                .map(|col| col.try_into())
+                .collect::<Result<_, _>>()?,
+            body: value.body,
+        })
+    }
+}
+
+impl TryFrom<SqlFunction> for catalog::SqlFunction {
+    type Error = ProtoConvError;
+    fn try_from(value: SqlFunction) -> Result<Self, Self::Error> {
+        Ok(catalog::SqlFunction {
+            params: value
+                .params
+                .into_iter()
+                .map(|col| col.try_into())
+                .collect::<Result<_, _>>()?,
+            return_type: value
+                .return_type
+                .as_ref()
+                .map(arrow::ArrowType::try_from)
+                .transpose()?,
+            return_columns: value
+                .return_columns
+                .into_iter()
+                .map(|col| col.try_into())
+                .collect::<Result<_, _>>()?,
+            body: value.body,
+        })
     }
 }
 
@@ -608,6 +703,225 @@ impl From<CredentialsEntry> for catalog::CredentialsEntry {
     }
 }
 
+/// A privilege that can be granted to a role.
+#[derive(Debug, Clone, Copy, Arbitrary, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Privilege {
+    Select,
+    Insert,
+    Update,
+    Delete,
+    Create,
+    Usage,
+}
+
+impl Privilege {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Privilege::Select => "SELECT",
+            Privilege::Insert => "INSERT",
+            Privilege::Update => "UPDATE",
+            Privilege::Delete => "DELETE",
+            Privilege::Create => "CREATE",
+            Privilege::Usage => "USAGE",
+        }
+    }
+}
+
+impl fmt::Display for Privilege {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl TryFrom<i32> for Privilege {
+    type Error = ProtoConvError;
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        catalog::role_entry::Privilege::from_i32(value)
+            .ok_or(ProtoConvError::UnknownEnumVariant("Privilege", value))
+            .and_then(|t| t.try_into())
+    }
+}
+
+impl TryFrom<catalog::role_entry::Privilege> for Privilege {
+    type Error = ProtoConvError;
+    fn try_from(value: catalog::role_entry::Privilege) -> Result<Self, Self::Error> {
+        Ok(match value {
+            catalog::role_entry::Privilege::Unknown => {
+                return Err(ProtoConvError::ZeroValueEnumVariant("Privilege"))
+            }
+            catalog::role_entry::Privilege::Select => Privilege::Select,
+            catalog::role_entry::Privilege::Insert => Privilege::Insert,
+            catalog::role_entry::Privilege::Update => Privilege::Update,
+            catalog::role_entry::Privilege::Delete => Privilege::Delete,
+            catalog::role_entry::Privilege::Create => Privilege::Create,
+            catalog::role_entry::Privilege::Usage => Privilege::Usage,
+        })
+    }
+}
+
+impl From<Privilege> for catalog::role_entry::Privilege {
+    fn from(value: Privilege) -> Self {
+        match value {
+            Privilege::Select => catalog::role_entry::Privilege::Select,
+            Privilege::Insert => catalog::role_entry::Privilege::Insert,
+            Privilege::Update => catalog::role_entry::Privilege::Update,
+            Privilege::Delete => catalog::role_entry::Privilege::Delete,
+            Privilege::Create => catalog::role_entry::Privilege::Create,
+            Privilege::Usage => catalog::role_entry::Privilege::Usage,
+        }
+    }
+}
+
+/// Privileges granted to a role on a single object.
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
+pub struct PrivilegeGrant {
+    /// Oid of the database, schema, or table.
+    pub object_id: u32,
+    pub privileges: Vec<Privilege>,
+}
+
+impl TryFrom<catalog::role_entry::Grant> for PrivilegeGrant {
+    type Error = ProtoConvError;
+    fn try_from(value: catalog::role_entry::Grant) -> Result<Self, Self::Error> {
+        Ok(PrivilegeGrant {
+            object_id: value.object_id,
+            privileges: value
+                .privileges
+                .into_iter()
+                .map(Privilege::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<PrivilegeGrant> for catalog::role_entry::Grant {
+    fn from(value: PrivilegeGrant) -> Self {
+        catalog::role_entry::Grant {
+            object_id: value.object_id,
+            privileges: value
+                .privileges
+                .into_iter()
+                .map(|p| catalog::role_entry::Privilege::from(p) as i32)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
+pub struct RoleEntry {
+    pub meta: EntryMeta,
+    pub superuser: bool,
+    pub login: bool,
+    pub grants: Vec<PrivilegeGrant>,
+    pub password_hash: Option<String>,
+}
+
+impl RoleEntry {
+    /// Check if this role was directly granted a privilege on the given
+    /// object.
+    ///
+    /// This does not take superuser or grants on parent objects into account.
+    pub fn has_privilege(&self, object_id: u32, privilege: Privilege) -> bool {
+        self.grants
+            .iter()
+            .any(|g| g.object_id == object_id && g.privileges.contains(&privilege))
+    }
+}
+
+impl TryFrom<catalog::RoleEntry> for RoleEntry {
+    type Error = ProtoConvError;
+    fn try_from(value: catalog::RoleEntry) -> Result<Self, Self::Error> {
+        let meta: EntryMeta = value.meta.required("meta")?;
+        Ok(RoleEntry {
+            meta,
+            superuser: value.superuser,
+            login: value.login,
+            grants: value
+                .grants
+                .into_iter()
+                .map(PrivilegeGrant::try_from)
+                .collect::<Result<_, _>>()?,
+            password_hash: value.password_hash,
+        })
+    }
+}
+
+impl From<RoleEntry> for catalog::RoleEntry {
+    fn from(value: RoleEntry) -> Self {
+        catalog::RoleEntry {
+            meta: Some(value.meta.into()),
+            superuser: value.superuser,
+            login: value.login,
+            grants: value.grants.into_iter().map(Into::into).collect(),
+            password_hash: value.password_hash,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
+pub struct SequenceEntry {
+    pub meta: EntryMeta,
+    pub start: i64,
+    pub increment: i64,
+    pub min_value: i64,
+    pub max_value: i64,
+    pub last_value: i64,
+    pub is_called: bool,
+    pub owned_by: Option<u32>,
+}
+
+impl SequenceEntry {
+    /// Get the value following `value`, or None if the sequence has been
+    /// exhausted.
+    pub fn value_after(&self, value: i64) -> Option<i64> {
+        value
+            .checked_add(self.increment)
+            .filter(|v| *v >= self.min_value && *v <= self.max_value)
+    }
+
+    /// Get the value the next call to `nextval` will return, or None if the
+    /// sequence has been exhausted.
+    pub fn next_value(&self) -> Option<i64> {
+        if self.is_called {
+            self.value_after(self.last_value)
+        } else {
+            Some(self.last_value)
+        }
+    }
+}
+
+impl TryFrom<catalog::SequenceEntry> for SequenceEntry {
+    type Error = ProtoConvError;
+    fn try_from(value: catalog::SequenceEntry) -> Result<Self, Self::Error> {
+        let meta: EntryMeta = value.meta.required("meta")?;
+        Ok(SequenceEntry {
+            meta,
+            start: value.start,
+            increment: value.increment,
+            min_value: value.min_value,
+            max_value: value.max_value,
+            last_value: value.last_value,
+            is_called: value.is_called,
+            owned_by: value.owned_by,
+        })
+    }
+}
+
+impl From<SequenceEntry> for catalog::SequenceEntry {
+    fn from(value: SequenceEntry) -> Self {
+        catalog::SequenceEntry {
+            meta: Some(value.meta.into()),
+            start: value.start,
+            increment: value.increment,
+            min_value: value.min_value,
+            max_value: value.max_value,
+            last_value: value.last_value,
+            is_called: value.is_called,
+            owned_by: value.owned_by,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -632,6 +946,24 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn roundtrip_role_entry(expected in any::<RoleEntry>()) {
+            let p: catalog::RoleEntry = expected.clone().into();
+            let got: RoleEntry = p.try_into().unwrap();
+            assert_eq!(expected, got);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip_sequence_entry(expected in any::<SequenceEntry>()) {
+            let p: catalog::SequenceEntry = expected.clone().into();
+            let got: SequenceEntry = p.try_into().unwrap();
+            assert_eq!(expected, got);
+        }
+    }
+
     #[test]
     fn convert_catalog_state_no_deployment_metadata() {
         // New `deployment` field added. Assert we can handle catalogs that
@@ -9,6 +9,7 @@ use datafusion::{
 use proptest_derive::Arbitrary;
 use std::collections::BTreeMap;
 use std::fmt;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
 pub struct InternalColumnDefinition {
@@ -17,6 +18,10 @@ pub struct InternalColumnDefinition {
     // TODO: change proptest strategy to select random DataType
     #[proptest(value("DataType::Utf8"))]
     pub arrow_type: DataType,
+    /// Default expression for the column as SQL text.
+    pub default_expr: Option<String>,
+    /// User provided description of the column.
+    pub comment: Option<String>,
 }
 
 impl InternalColumnDefinition {
@@ -34,6 +39,8 @@ impl InternalColumnDefinition {
                 name: name.into(),
                 nullable,
                 arrow_type,
+                default_expr: None,
+                comment: None,
             })
             .collect()
     }
@@ -48,6 +55,8 @@ impl InternalColumnDefinition {
                 name: field.name().clone(),
                 nullable: field.is_nullable(),
                 arrow_type: field.data_type().clone(),
+                default_expr: None,
+                comment: None,
             })
             .collect()
     }
@@ -61,6 +70,8 @@ impl TryFrom<options::InternalColumnDefinition> for InternalColumnDefinition {
             name: value.name,
             nullable: value.nullable,
             arrow_type,
+            default_expr: value.default_expr,
+            comment: value.comment,
         })
     }
 }
@@ -75,10 +86,105 @@ impl TryFrom<InternalColumnDefinition> for options::InternalColumnDefinition {
             name: value.name,
             nullable: value.nullable,
             arrow_type: Some(arrow_type),
+            default_expr: value.default_expr,
+            comment: value.comment,
         })
     }
 }
 
+/// A set of columns whose values must be unique.
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct InternalUniqueConstraint {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
+impl From<options::InternalUniqueConstraint> for InternalUniqueConstraint {
+    fn from(value: options::InternalUniqueConstraint) -> Self {
+        InternalUniqueConstraint {
+            name: value.name,
+            columns: value.columns,
+        }
+    }
+}
+
+impl From<InternalUniqueConstraint> for options::InternalUniqueConstraint {
+    fn from(value: InternalUniqueConstraint) -> Self {
+        options::InternalUniqueConstraint {
+            name: value.name,
+            columns: value.columns,
+        }
+    }
+}
+
+/// A boolean expression every row in a table must satisfy.
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct InternalCheckConstraint {
+    pub name: String,
+    /// The expression as SQL text.
+    pub expr: String,
+}
+
+impl From<options::InternalCheckConstraint> for InternalCheckConstraint {
+    fn from(value: options::InternalCheckConstraint) -> Self {
+        InternalCheckConstraint {
+            name: value.name,
+            expr: value.expr,
+        }
+    }
+}
+
+impl From<InternalCheckConstraint> for options::InternalCheckConstraint {
+    fn from(value: InternalCheckConstraint) -> Self {
+        options::InternalCheckConstraint {
+            name: value.name,
+            expr: value.expr,
+        }
+    }
+}
+
+/// Definition of a materialized view whose contents are stored in a native
+/// table.
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct InternalMaterializedView {
+    /// The query as SQL text.
+    pub sql: String,
+    /// Refresh the view every this many seconds.
+    pub refresh_interval_secs: Option<u64>,
+    /// Microseconds since the unix epoch.
+    pub last_refreshed: Option<i64>,
+    /// Name of the user that created the view.
+    pub owner_name: Option<String>,
+    /// Id of the user that created the view.
+    #[proptest(value("None"))]
+    pub owner_id: Option<Uuid>,
+}
+
+impl TryFrom<options::InternalMaterializedView> for InternalMaterializedView {
+    type Error = ProtoConvError;
+    fn try_from(value: options::InternalMaterializedView) -> Result<Self, Self::Error> {
+        Ok(InternalMaterializedView {
+            sql: value.sql,
+            refresh_interval_secs: value.refresh_interval_secs,
+            last_refreshed: value.last_refreshed,
+            owner_name: value.owner_name,
+            owner_id: value.owner_id.map(|id| Uuid::from_slice(&id)).transpose()?,
+        })
+    }
+}
+
+impl From<InternalMaterializedView> for options::InternalMaterializedView {
+    fn from(value: InternalMaterializedView) -> Self {
+        options::InternalMaterializedView {
+            sql: value.sql,
+            refresh_interval_secs: value.refresh_interval_secs,
+            last_refreshed: value.last_refreshed,
+            owner_name: value.owner_name,
+            owner_id: value.owner_id.map(|id| id.as_bytes().to_vec()),
+        }
+    }
+}
+
 // Database options
 
 #[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
@@ -91,6 +197,9 @@ pub enum DatabaseOptions {
     Mongo(DatabaseOptionsMongo),
     Snowflake(DatabaseOptionsSnowflake),
     Delta(DatabaseOptionsDeltaLake),
+    SqlServer(DatabaseOptionsSqlServer),
+    Clickhouse(DatabaseOptionsClickhouse),
+    Sqlite(DatabaseOptionsSqlite),
 }
 
 impl DatabaseOptions {
@@ -102,6 +211,9 @@ impl DatabaseOptions {
     pub const MONGO: &str = "mongo";
     pub const SNOWFLAKE: &str = "snowflake";
     pub const DELTA: &str = "delta";
+    pub const SQL_SERVER: &str = "sqlserver";
+    pub const CLICKHOUSE: &str = "clickhouse";
+    pub const SQLITE: &str = "sqlite";
 
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -113,6 +225,40 @@ impl DatabaseOptions {
             DatabaseOptions::Mongo(_) => Self::MONGO,
             DatabaseOptions::Snowflake(_) => Self::SNOWFLAKE,
             DatabaseOptions::Delta(_) => Self::DELTA,
+            DatabaseOptions::SqlServer(_) => Self::SQL_SERVER,
+            DatabaseOptions::Clickhouse(_) => Self::CLICKHOUSE,
+            DatabaseOptions::Sqlite(_) => Self::SQLITE,
+        }
+    }
+}
+
+impl SecretOptions for DatabaseOptions {
+    fn secrets_mut(&mut self) -> Vec<SecretMut<'_>> {
+        match self {
+            DatabaseOptions::Internal(_) | DatabaseOptions::Debug(_) => Vec::new(),
+            DatabaseOptions::Postgres(opts) => vec![SecretMut::String(&mut opts.connection_string)],
+            DatabaseOptions::BigQuery(opts) => {
+                vec![SecretMut::String(&mut opts.service_account_key)]
+            }
+            DatabaseOptions::Mysql(opts) => vec![SecretMut::String(&mut opts.connection_string)],
+            DatabaseOptions::Mongo(opts) => vec![SecretMut::String(&mut opts.connection_string)],
+            DatabaseOptions::Snowflake(opts) => vec![SecretMut::String(&mut opts.password)],
+            DatabaseOptions::Delta(opts) => {
+                let mut secrets = opts.storage_options.secrets_mut();
+                match &mut opts.catalog {
+                    DeltaLakeCatalog::Unity(unity) => {
+                        secrets.push(SecretMut::String(&mut unity.databricks_access_token))
+                    }
+                }
+                secrets
+            }
+            DatabaseOptions::SqlServer(opts) => {
+                vec![SecretMut::String(&mut opts.connection_string)]
+            }
+            DatabaseOptions::Clickhouse(opts) => {
+                vec![SecretMut::String(&mut opts.connection_string)]
+            }
+            DatabaseOptions::Sqlite(opts) => opts.storage_options.secrets_mut(),
         }
     }
 }
@@ -143,6 +289,13 @@ impl TryFrom<options::database_options::Options> for DatabaseOptions {
                 DatabaseOptions::Snowflake(v.try_into()?)
             }
             options::database_options::Options::Delta(v) => DatabaseOptions::Delta(v.try_into()?),
+            options::database_options::Options::Sqlserver(v) => {
+                DatabaseOptions::SqlServer(v.try_into()?)
+            }
+            options::database_options::Options::Clickhouse(v) => {
+                DatabaseOptions::Clickhouse(v.try_into()?)
+            }
+            options::database_options::Options::Sqlite(v) => DatabaseOptions::Sqlite(v.try_into()?),
         })
     }
 }
@@ -167,6 +320,13 @@ impl From<DatabaseOptions> for options::database_options::Options {
                 options::database_options::Options::Snowflake(v.into())
             }
             DatabaseOptions::Delta(v) => options::database_options::Options::Delta(v.into()),
+            DatabaseOptions::SqlServer(v) => {
+                options::database_options::Options::Sqlserver(v.into())
+            }
+            DatabaseOptions::Clickhouse(v) => {
+                options::database_options::Options::Clickhouse(v.into())
+            }
+            DatabaseOptions::Sqlite(v) => options::database_options::Options::Sqlite(v.into()),
         }
     }
 }
@@ -302,6 +462,75 @@ impl From<DatabaseOptionsMongo> for options::DatabaseOptionsMongo {
     }
 }
 
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct DatabaseOptionsSqlServer {
+    pub connection_string: String,
+}
+
+impl TryFrom<options::DatabaseOptionsSqlServer> for DatabaseOptionsSqlServer {
+    type Error = ProtoConvError;
+    fn try_from(value: options::DatabaseOptionsSqlServer) -> Result<Self, Self::Error> {
+        Ok(DatabaseOptionsSqlServer {
+            connection_string: value.connection_string,
+        })
+    }
+}
+
+impl From<DatabaseOptionsSqlServer> for options::DatabaseOptionsSqlServer {
+    fn from(value: DatabaseOptionsSqlServer) -> Self {
+        options::DatabaseOptionsSqlServer {
+            connection_string: value.connection_string,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct DatabaseOptionsClickhouse {
+    pub connection_string: String,
+}
+
+impl TryFrom<options::DatabaseOptionsClickhouse> for DatabaseOptionsClickhouse {
+    type Error = ProtoConvError;
+    fn try_from(value: options::DatabaseOptionsClickhouse) -> Result<Self, Self::Error> {
+        Ok(DatabaseOptionsClickhouse {
+            connection_string: value.connection_string,
+        })
+    }
+}
+
+impl From<DatabaseOptionsClickhouse> for options::DatabaseOptionsClickhouse {
+    fn from(value: DatabaseOptionsClickhouse) -> Self {
+        options::DatabaseOptionsClickhouse {
+            connection_string: value.connection_string,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct DatabaseOptionsSqlite {
+    pub location: String,
+    pub storage_options: StorageOptions,
+}
+
+impl TryFrom<options::DatabaseOptionsSqlite> for DatabaseOptionsSqlite {
+    type Error = ProtoConvError;
+    fn try_from(value: options::DatabaseOptionsSqlite) -> Result<Self, Self::Error> {
+        Ok(DatabaseOptionsSqlite {
+            location: value.location,
+            storage_options: value.storage_options.required("storage_options")?,
+        })
+    }
+}
+
+impl From<DatabaseOptionsSqlite> for options::DatabaseOptionsSqlite {
+    fn from(value: DatabaseOptionsSqlite) -> Self {
+        options::DatabaseOptionsSqlite {
+            location: value.location,
+            storage_options: Some(value.storage_options.into()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
 pub struct DatabaseOptionsSnowflake {
     pub account_name: String,
@@ -446,6 +675,64 @@ impl From<StorageOptions> for options::StorageOptions {
     }
 }
 
+impl StorageOptions {
+    /// Check if the value for a storage option key should be treated as a
+    /// secret.
+    pub fn is_secret_key(key: &str) -> bool {
+        const SECRET_KEY_PARTS: [&str; 5] = [
+            "secret",
+            "password",
+            "token",
+            "service_account_key",
+            "account_key",
+        ];
+        let key = key.to_lowercase();
+        SECRET_KEY_PARTS.iter().any(|part| key.contains(part))
+    }
+}
+
+impl SecretOptions for StorageOptions {
+    fn secrets_mut(&mut self) -> Vec<SecretMut<'_>> {
+        self.inner
+            .iter_mut()
+            .filter(|(k, _)| Self::is_secret_key(k))
+            .map(|(_, v)| SecretMut::String(v))
+            .collect()
+    }
+}
+
+// Secrets
+
+/// Value used in place of secrets when displaying options.
+pub const REDACTED_SECRET: &str = "********";
+
+/// A mutable reference to a secret value held in some options.
+#[derive(Debug)]
+pub enum SecretMut<'a> {
+    String(&'a mut String),
+    Bytes(&'a mut Vec<u8>),
+}
+
+/// Options that may hold secrets such as passwords, keys, and connection
+/// strings.
+pub trait SecretOptions: Clone {
+    /// Get mutable references to all secret values in the options.
+    fn secrets_mut(&mut self) -> Vec<SecretMut<'_>>;
+
+    /// Get a copy of the options with all non-empty secret values redacted.
+    fn redacted(&self) -> Self {
+        let mut opts = self.clone();
+        for secret in opts.secrets_mut() {
+            match secret {
+                SecretMut::String(s) if !s.is_empty() => *s = REDACTED_SECRET.to_string(),
+                SecretMut::Bytes(b) if !b.is_empty() => *b = REDACTED_SECRET.as_bytes().to_vec(),
+                _ => (),
+            }
+        }
+        opts
+    }
+}
+
 // Table options
 
 #[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
@@ -462,6 +749,9 @@ pub enum TableOptions {
     Snowflake(TableOptionsSnowflake),
     Delta(TableOptionsObjectStore),
     Iceberg(TableOptionsObjectStore),
+    SqlServer(TableOptionsSqlServer),
+    Clickhouse(TableOptionsClickhouse),
+    Sqlite(TableOptionsSqlite),
 }
 
 impl TableOptions {
@@ -477,9 +767,19 @@ impl TableOptions {
     pub const SNOWFLAKE: &str = "snowflake";
     pub const DELTA: &str = "delta";
     pub const ICEBERG: &str = "iceberg";
+    pub const SQL_SERVER: &str = "sqlserver";
+    pub const CLICKHOUSE: &str = "clickhouse";
+    pub const SQLITE: &str = "sqlite";
 
     pub const fn new_internal(columns: Vec<InternalColumnDefinition>) -> TableOptions {
-        TableOptions::Internal(TableOptionsInternal { columns })
+        TableOptions::Internal(TableOptionsInternal {
+            columns,
+            partition_columns: Vec::new(),
+            primary_key: None,
+            unique_constraints: Vec::new(),
+            check_constraints: Vec::new(),
+            materialized_view: None,
+        })
     }
 
     pub fn as_str(&self) -> &'static str {
@@ -496,6 +796,42 @@ impl TableOptions {
             TableOptions::Snowflake(_) => Self::SNOWFLAKE,
             TableOptions::Delta(_) => Self::DELTA,
             TableOptions::Iceberg(_) => Self::ICEBERG,
+            TableOptions::SqlServer(_) => Self::SQL_SERVER,
+            TableOptions::Clickhouse(_) => Self::CLICKHOUSE,
+            TableOptions::Sqlite(_) => Self::SQLITE,
+        }
+    }
+}
+
+impl SecretOptions for TableOptions {
+    fn secrets_mut(&mut self) -> Vec<SecretMut<'_>> {
+        match self {
+            TableOptions::Internal(_) | TableOptions::Debug(_) | TableOptions::Local(_) => {
+                Vec::new()
+            }
+            TableOptions::Postgres(opts) => vec![SecretMut::String(&mut opts.connection_string)],
+            TableOptions::BigQuery(opts) => vec![SecretMut::String(&mut opts.service_account_key)],
+            TableOptions::Mysql(opts) => vec![SecretMut::String(&mut opts.connection_string)],
+            TableOptions::Gcs(opts) => opts
+                .service_account_key
+                .iter_mut()
+                .map(SecretMut::String)
+                .collect(),
+            TableOptions::S3(opts) => opts
+                .secret_access_key
+                .iter_mut()
+                .map(SecretMut::String)
+                .collect(),
+            TableOptions::Mongo(opts) => vec![SecretMut::String(&mut opts.connection_string)],
+            TableOptions::Snowflake(opts) => vec![SecretMut::String(&mut opts.password)],
+            TableOptions::Delta(opts) | TableOptions::Iceberg(opts) => {
+                opts.storage_options.secrets_mut()
+            }
+            TableOptions::SqlServer(opts) => vec![SecretMut::String(&mut opts.connection_string)],
+            TableOptions::Clickhouse(opts) => {
+                vec![SecretMut::String(&mut opts.connection_string)]
+            }
+            TableOptions::Sqlite(opts) => opts.storage_options.secrets_mut(),
         }
     }
 }
@@ -522,6 +858,11 @@ impl TryFrom<options::table_options::Options> for TableOptions {
             options::table_options::Options::Snowflake(v) => TableOptions::Snowflake(v.try_into()?),
             options::table_options::Options::Delta(v) => TableOptions::Delta(v.try_into()?),
             options::table_options::Options::Iceberg(v) => TableOptions::Iceberg(v.try_into()?),
+            options::table_options::Options::Sqlserver(v) => TableOptions::SqlServer(v.try_into()?),
+            options::table_options::Options::Clickhouse(v) => {
+                TableOptions::Clickhouse(v.try_into()?)
+            }
+            options::table_options::Options::Sqlite(v) => TableOptions::Sqlite(v.try_into()?),
         })
     }
 }
@@ -549,6 +890,9 @@ impl TryFrom<TableOptions> for options::table_options::Options {
             TableOptions::Snowflake(v) => options::table_options::Options::Snowflake(v.into()),
             TableOptions::Delta(v) => options::table_options::Options::Delta(v.into()),
             TableOptions::Iceberg(v) => options::table_options::Options::Iceberg(v.into()),
+            TableOptions::SqlServer(v) => options::table_options::Options::Sqlserver(v.into()),
+            TableOptions::Clickhouse(v) => options::table_options::Options::Clickhouse(v.into()),
+            TableOptions::Sqlite(v) => options::table_options::Options::Sqlite(v.into()),
         })
     }
 }
@@ -565,6 +909,11 @@ impl TryFrom<TableOptions> for options::TableOptions {
 #[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
 pub struct TableOptionsInternal {
     pub columns: Vec<InternalColumnDefinition>,
+    pub partition_columns: Vec<String>,
+    pub primary_key: Option<InternalUniqueConstraint>,
+    pub unique_constraints: Vec<InternalUniqueConstraint>,
+    pub check_constraints: Vec<InternalCheckConstraint>,
+    pub materialized_view: Option<InternalMaterializedView>,
 }
 
 impl From<DFSchemaRef> for TableOptionsInternal {
@@ -577,8 +926,15 @@ impl From<DFSchemaRef> for TableOptionsInternal {
                     name: col.name().clone(),
                     nullable: col.is_nullable(),
                     arrow_type: col.data_type().clone(),
+                    default_expr: None,
+                    comment: None,
                 })
                 .collect::<Vec<_>>(),
+            partition_columns: Vec::new(),
+            primary_key: None,
+            unique_constraints: Vec::new(),
+            check_constraints: Vec::new(),
+            materialized_view: None,
         }
     }
 }
@@ -593,8 +949,15 @@ impl From<SchemaRef> for TableOptionsInternal {
                     name: col.name().clone(),
                     nullable: col.is_nullable(),
                     arrow_type: col.data_type().clone(),
+                    default_expr: None,
+                    comment: None,
                 })
                 .collect::<Vec<_>>(),
+            partition_columns: Vec::new(),
+            primary_key: None,
+            unique_constraints: Vec::new(),
+            check_constraints: Vec::new(),
+            materialized_view: None,
         }
     }
 }
@@ -608,6 +971,19 @@ impl TryFrom<options::TableOptionsInternal> for TableOptionsInternal {
                 .into_iter()
                 .map(|col| col.try_into())
                 .collect::<Result<_, _>>()?,
+            partition_columns: value.partition_columns,
+            primary_key: value.primary_key.map(Into::into),
+            unique_constraints: value
+                .unique_constraints
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            check_constraints: value
+                .check_constraints
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            materialized_view: value.materialized_view.map(TryInto::try_into).transpose()?,
         })
     }
 }
@@ -621,6 +997,19 @@ impl TryFrom<TableOptionsInternal> for options::TableOptionsInternal {
                 .into_iter()
                 .map(|col| col.try_into())
                 .collect::<Result<_, _>>()?,
+            partition_columns: value.partition_columns,
+            primary_key: value.primary_key.map(Into::into),
+            unique_constraints: value
+                .unique_constraints
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            check_constraints: value
+                .check_constraints
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            materialized_view: value.materialized_view.map(Into::into),
         })
     }
 }
@@ -734,6 +1123,90 @@ impl From<TableOptionsMysql> for options::TableOptionsMysql {
     }
 }
 
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct TableOptionsSqlServer {
+    pub connection_string: String,
+    pub schema: String,
+    pub table: String,
+}
+
+impl TryFrom<options::TableOptionsSqlServer> for TableOptionsSqlServer {
+    type Error = ProtoConvError;
+    fn try_from(value: options::TableOptionsSqlServer) -> Result<Self, Self::Error> {
+        Ok(TableOptionsSqlServer {
+            connection_string: value.connection_string,
+            schema: value.schema,
+            table: value.table,
+        })
+    }
+}
+
+impl From<TableOptionsSqlServer> for options::TableOptionsSqlServer {
+    fn from(value: TableOptionsSqlServer) -> Self {
+        options::TableOptionsSqlServer {
+            connection_string: value.connection_string,
+            schema: value.schema,
+            table: value.table,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct TableOptionsClickhouse {
+    pub connection_string: String,
+    pub database: String,
+    pub table: String,
+}
+
+impl TryFrom<options::TableOptionsClickhouse> for TableOptionsClickhouse {
+    type Error = ProtoConvError;
+    fn try_from(value: options::TableOptionsClickhouse) -> Result<Self, Self::Error> {
+        Ok(TableOptionsClickhouse {
+            connection_string: value.connection_string,
+            database: value.database,
+            table: value.table,
+        })
+    }
+}
+
+impl From<TableOptionsClickhouse> for options::TableOptionsClickhouse {
+    fn from(value: TableOptionsClickhouse) -> Self {
+        options::TableOptionsClickhouse {
+            connection_string: value.connection_string,
+            database: value.database,
+            table: value.table,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct TableOptionsSqlite {
+    pub location: String,
+    pub table: String,
+    pub storage_options: StorageOptions,
+}
+
+impl TryFrom<options::TableOptionsSqlite> for TableOptionsSqlite {
+    type Error = ProtoConvError;
+    fn try_from(value: options::TableOptionsSqlite) -> Result<Self, Self::Error> {
+        Ok(TableOptionsSqlite {
+            location: value.location,
+            table: value.table,
+            storage_options: value.storage_options.required("storage_options")?,
+        })
+    }
+}
+
+impl From<TableOptionsSqlite> for options::TableOptionsSqlite {
+    fn from(value: TableOptionsSqlite) -> Self {
+        options::TableOptionsSqlite {
+            location: value.location,
+            table: value.table,
+            storage_options: Some(value.storage_options.into()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
 pub struct TableOptionsLocal {
     pub location: String,
@@ -953,6 +1426,15 @@ impl TunnelOptions {
     }
 }
 
+impl SecretOptions for TunnelOptions {
+    fn secrets_mut(&mut self) -> Vec<SecretMut<'_>> {
+        match self {
+            TunnelOptions::Internal(_) | TunnelOptions::Debug(_) => Vec::new(),
+            TunnelOptions::Ssh(opts) => vec![SecretMut::Bytes(&mut opts.ssh_key)],
+        }
+    }
+}
+
 impl fmt::Display for TunnelOptions {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.as_str())
@@ -1075,6 +1557,42 @@ mod tests {
             assert_eq!(expected, got);
         }
     }
+
+    #[test]
+    fn redact_secrets() {
+        let opts = CredentialsOptions::Aws(CredentialsOptionsAws {
+            access_key_id: "key_id".to_string(),
+            secret_access_key: "secret".to_string(),
+        });
+        let expected = CredentialsOptions::Aws(CredentialsOptionsAws {
+            access_key_id: "key_id".to_string(),
+            secret_access_key: REDACTED_SECRET.to_string(),
+        });
+        assert_eq!(expected, opts.redacted());
+
+        let opts = TableOptions::Delta(TableOptionsObjectStore {
+            location: "s3://bucket/table".to_string(),
+            storage_options: StorageOptions {
+                inner: BTreeMap::from([
+                    ("aws_region".to_string(), "us-east-1".to_string()),
+                    ("aws_secret_access_key".to_string(), "secret".to_string()),
+                ]),
+            },
+        });
+        let expected = TableOptions::Delta(TableOptionsObjectStore {
+            location: "s3://bucket/table".to_string(),
+            storage_options: StorageOptions {
+                inner: BTreeMap::from([
+                    ("aws_region".to_string(), "us-east-1".to_string()),
+                    (
+                        "aws_secret_access_key".to_string(),
+                        REDACTED_SECRET.to_string(),
+                    ),
+                ]),
+            },
+        });
+        assert_eq!(expected, opts.redacted());
+    }
 }
 
 #[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
@@ -1098,6 +1616,16 @@ impl CredentialsOptions {
     }
 }
 
+impl SecretOptions for CredentialsOptions {
+    fn secrets_mut(&mut self) -> Vec<SecretMut<'_>> {
+        match self {
+            CredentialsOptions::Debug(_) => Vec::new(),
+            CredentialsOptions::Gcp(opts) => vec![SecretMut::String(&mut opts.service_account_key)],
+            CredentialsOptions::Aws(opts) => vec![SecretMut::String(&mut opts.secret_access_key)],
+        }
+    }
+}
+
 impl fmt::Display for CredentialsOptions {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.as_str())
@@ -1264,6 +1792,7 @@ pub enum CopyToFormatOptions {
     Csv(CopyToFormatOptionsCsv),
     Parquet(CopyToFormatOptionsParquet),
     Json(CopyToFormatOptionsJson),
+    Avro(CopyToFormatOptionsAvro),
 }
 
 impl Default for CopyToFormatOptions {
@@ -1279,12 +1808,14 @@ impl CopyToFormatOptions {
     pub const CSV: &str = "csv";
     pub const PARQUET: &str = "parquet";
     pub const JSON: &str = "json";
+    pub const AVRO: &str = "avro";
 
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Csv(_) => Self::CSV,
             Self::Parquet(_) => Self::PARQUET,
             Self::Json(_) => Self::JSON,
+            Self::Avro(_) => Self::AVRO,
         }
     }
 }
@@ -1304,3 +1835,6 @@ pub struct CopyToFormatOptionsParquet {
 pub struct CopyToFormatOptionsJson {
     pub array: bool,
 }
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CopyToFormatOptionsAvro {}
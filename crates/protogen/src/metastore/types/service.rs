@@ -1,6 +1,8 @@
+use super::catalog::{FunctionType, Privilege, SqlFunction};
 use super::options::{
     CredentialsOptions, DatabaseOptions, TableOptions, TableOptionsInternal, TunnelOptions,
 };
+use crate::gen::metastore::catalog;
 use crate::gen::metastore::service;
 use crate::{FromOptionalField, ProtoConvError};
 use proptest_derive::Arbitrary;
@@ -22,6 +24,17 @@ pub enum Mutation {
     AlterTunnelRotateKeys(AlterTunnelRotateKeys),
     CreateCredentials(CreateCredentials),
     DropCredentials(DropCredentials),
+    CreateRole(CreateRole),
+    DropRole(DropRole),
+    GrantPrivileges(GrantPrivileges),
+    RevokePrivileges(RevokePrivileges),
+    RefreshMaterializedView(RefreshMaterializedView),
+    CreateFunction(CreateFunction),
+    DropFunction(DropFunction),
+    CreateSequence(CreateSequence),
+    AdvanceSequence(AdvanceSequence),
+    SetSequenceValue(SetSequenceValue),
+    CommentOn(CommentOn),
     // Deployment metadata updates
     UpdateDeploymentStorage(UpdateDeploymentStorage),
 }
@@ -69,6 +82,27 @@ impl TryFrom<service::mutation::Mutation> for Mutation {
             service::mutation::Mutation::UpdateDeploymentStorage(v) => {
                 Mutation::UpdateDeploymentStorage(v.try_into()?)
             }
+            service::mutation::Mutation::CreateRole(v) => Mutation::CreateRole(v.try_into()?),
+            service::mutation::Mutation::DropRole(v) => Mutation::DropRole(v.try_into()?),
+            service::mutation::Mutation::GrantPrivileges(v) => {
+                Mutation::GrantPrivileges(v.try_into()?)
+            }
+            service::mutation::Mutation::RevokePrivileges(v) => {
+                Mutation::RevokePrivileges(v.try_into()?)
+            }
+            service::mutation::Mutation::RefreshMaterializedView(v) => {
+                Mutation::RefreshMaterializedView(v.try_into()?)
+            }
+            service::mutation::Mutation::CreateFunction(v) => {
+                Mutation::CreateFunction(v.try_into()?)
+            }
+            service::mutation::Mutation::DropFunction(v) => Mutation::DropFunction(v.into()),
+            service::mutation::Mutation::CreateSequence(v) => Mutation::CreateSequence(v.into()),
+            service::mutation::Mutation::AdvanceSequence(v) => Mutation::AdvanceSequence(v.into()),
+            service::mutation::Mutation::SetSequenceValue(v) => {
+                Mutation::SetSequenceValue(v.into())
+            }
+            service::mutation::Mutation::CommentOn(v) => Mutation::CommentOn(v.try_into()?),
         })
     }
 }
@@ -107,6 +141,25 @@ impl TryFrom<Mutation> for service::mutation::Mutation {
             Mutation::UpdateDeploymentStorage(v) => {
                 service::mutation::Mutation::UpdateDeploymentStorage(v.into())
             }
+            Mutation::CreateRole(v) => service::mutation::Mutation::CreateRole(v.into()),
+            Mutation::DropRole(v) => service::mutation::Mutation::DropRole(v.into()),
+            Mutation::GrantPrivileges(v) => service::mutation::Mutation::GrantPrivileges(v.into()),
+            Mutation::RevokePrivileges(v) => {
+                service::mutation::Mutation::RevokePrivileges(v.into())
+            }
+            Mutation::RefreshMaterializedView(v) => {
+                service::mutation::Mutation::RefreshMaterializedView(v.into())
+            }
+            Mutation::CreateFunction(v) => {
+                service::mutation::Mutation::CreateFunction(v.try_into()?)
+            }
+            Mutation::DropFunction(v) => service::mutation::Mutation::DropFunction(v.into()),
+            Mutation::CreateSequence(v) => service::mutation::Mutation::CreateSequence(v.into()),
+            Mutation::AdvanceSequence(v) => service::mutation::Mutation::AdvanceSequence(v.into()),
+            Mutation::SetSequenceValue(v) => {
+                service::mutation::Mutation::SetSequenceValue(v.into())
+            }
+            Mutation::CommentOn(v) => service::mutation::Mutation::CommentOn(v.into()),
         })
     }
 }
@@ -579,6 +632,503 @@ impl From<UpdateDeploymentStorage> for service::UpdateDeploymentStorage {
     }
 }
 
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
+pub struct CreateRole {
+    pub name: String,
+    pub if_not_exists: bool,
+    pub superuser: bool,
+    pub login: bool,
+    pub password_hash: Option<String>,
+}
+
+impl TryFrom<service::CreateRole> for CreateRole {
+    type Error = ProtoConvError;
+    fn try_from(value: service::CreateRole) -> Result<Self, Self::Error> {
+        Ok(CreateRole {
+            name: value.name,
+            if_not_exists: value.if_not_exists,
+            superuser: value.superuser,
+            login: value.login,
+            password_hash: value.password_hash,
+        })
+    }
+}
+
+impl From<CreateRole> for service::CreateRole {
+    fn from(value: CreateRole) -> Self {
+        service::CreateRole {
+            name: value.name,
+            if_not_exists: value.if_not_exists,
+            superuser: value.superuser,
+            login: value.login,
+            password_hash: value.password_hash,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
+pub struct DropRole {
+    pub name: String,
+    pub if_exists: bool,
+}
+
+impl TryFrom<service::DropRole> for DropRole {
+    type Error = ProtoConvError;
+    fn try_from(value: service::DropRole) -> Result<Self, Self::Error> {
+        Ok(DropRole {
+            name: value.name,
+            if_exists: value.if_exists,
+        })
+    }
+}
+
+impl From<DropRole> for service::DropRole {
+    fn from(value: DropRole) -> Self {
+        service::DropRole {
+            name: value.name,
+            if_exists: value.if_exists,
+        }
+    }
+}
+
+/// An object that privileges can be granted on.
+///
+/// Objects are referenced by name and resolved by the metastore when the
+/// mutation is applied.
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub enum GrantObject {
+    Database(String),
+    Schema(String),
+    Table { schema: String, name: String },
+}
+
+impl TryFrom<service::GrantObject> for GrantObject {
+    type Error = ProtoConvError;
+    fn try_from(value: service::GrantObject) -> Result<Self, Self::Error> {
+        let object_type = service::grant_object::ObjectType::from_i32(value.object_type).ok_or(
+            ProtoConvError::UnknownEnumVariant("ObjectType", value.object_type),
+        )?;
+        Ok(match object_type {
+            service::grant_object::ObjectType::Unknown => {
+                return Err(ProtoConvError::ZeroValueEnumVariant("ObjectType"))
+            }
+            service::grant_object::ObjectType::Database => GrantObject::Database(value.name),
+            service::grant_object::ObjectType::Schema => GrantObject::Schema(value.name),
+            service::grant_object::ObjectType::Table => GrantObject::Table {
+                schema: value.schema,
+                name: value.name,
+            },
+        })
+    }
+}
+
+impl From<GrantObject> for service::GrantObject {
+    fn from(value: GrantObject) -> Self {
+        let (object_type, schema, name) = match value {
+            GrantObject::Database(name) => (
+                service::grant_object::ObjectType::Database,
+                String::new(),
+                name,
+            ),
+            GrantObject::Schema(name) => (
+                service::grant_object::ObjectType::Schema,
+                String::new(),
+                name,
+            ),
+            GrantObject::Table { schema, name } => {
+                (service::grant_object::ObjectType::Table, schema, name)
+            }
+        };
+        service::GrantObject {
+            object_type: object_type as i32,
+            schema,
+            name,
+        }
+    }
+}
+
+fn privileges_from_proto(privileges: Vec<i32>) -> Result<Vec<Privilege>, ProtoConvError> {
+    privileges.into_iter().map(Privilege::try_from).collect()
+}
+
+fn privileges_to_proto(privileges: Vec<Privilege>) -> Vec<i32> {
+    privileges
+        .into_iter()
+        .map(|p| catalog::role_entry::Privilege::from(p) as i32)
+        .collect()
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct GrantPrivileges {
+    pub role: String,
+    pub object: GrantObject,
+    pub privileges: Vec<Privilege>,
+}
+
+impl TryFrom<service::GrantPrivileges> for GrantPrivileges {
+    type Error = ProtoConvError;
+    fn try_from(value: service::GrantPrivileges) -> Result<Self, Self::Error> {
+        Ok(GrantPrivileges {
+            role: value.role,
+            object: value.object.required("object")?,
+            privileges: privileges_from_proto(value.privileges)?,
+        })
+    }
+}
+
+impl From<GrantPrivileges> for service::GrantPrivileges {
+    fn from(value: GrantPrivileges) -> Self {
+        service::GrantPrivileges {
+            role: value.role,
+            object: Some(value.object.into()),
+            privileges: privileges_to_proto(value.privileges),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct RevokePrivileges {
+    pub role: String,
+    pub object: GrantObject,
+    pub privileges: Vec<Privilege>,
+}
+
+impl TryFrom<service::RevokePrivileges> for RevokePrivileges {
+    type Error = ProtoConvError;
+    fn try_from(value: service::RevokePrivileges) -> Result<Self, Self::Error> {
+        Ok(RevokePrivileges {
+            role: value.role,
+            object: value.object.required("object")?,
+            privileges: privileges_from_proto(value.privileges)?,
+        })
+    }
+}
+
+impl From<RevokePrivileges> for service::RevokePrivileges {
+    fn from(value: RevokePrivileges) -> Self {
+        service::RevokePrivileges {
+            role: value.role,
+            object: Some(value.object.into()),
+            privileges: privileges_to_proto(value.privileges),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
+pub struct RefreshMaterializedView {
+    pub schema: String,
+    pub name: String,
+    /// Microseconds since the unix epoch.
+    pub refreshed_at: i64,
+}
+
+impl TryFrom<service::RefreshMaterializedView> for RefreshMaterializedView {
+    type Error = ProtoConvError;
+    fn try_from(value: service::RefreshMaterializedView) -> Result<Self, Self::Error> {
+        Ok(RefreshMaterializedView {
+            schema: value.schema,
+            name: value.name,
+            refreshed_at: value.refreshed_at,
+        })
+    }
+}
+
+impl From<RefreshMaterializedView> for service::RefreshMaterializedView {
+    fn from(value: RefreshMaterializedView) -> Self {
+        service::RefreshMaterializedView {
+            schema: value.schema,
+            name: value.name,
+            refreshed_at: value.refreshed_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct CreateFunction {
+    pub schema: String,
+    pub name: String,
+    pub or_replace: bool,
+    pub func_type: FunctionType,
+    pub sql_function: SqlFunction,
+}
+
+impl TryFrom<service::CreateFunction> for CreateFunction {
+    type Error = ProtoConvError;
+    fn try_from(value: service::CreateFunction) -> Result<Self, Self::Error> {
+        Ok(CreateFunction {
+            schema: value.schema,
+            name: value.name,
+            or_replace: value.or_replace,
+            func_type: value.func_type.try_into()?,
+            sql_function: value.sql_function.required("sql_function")?,
+        })
+    }
+}
+
+impl TryFrom<CreateFunction> for service::CreateFunction {
+    type Error = ProtoConvError;
+    fn try_from(value: CreateFunction) -> Result<Self, Self::Error> {
+        let func_type: catalog::function_entry::FunctionType = value.func_type.into();
+        Ok(service::CreateFunction {
+            schema: value.schema,
+            name: value.name,
+            or_replace: value.or_replace,
+            func_type: func_type as i32,
+            sql_function: Some(value.sql_function.try_into()?),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct DropFunction {
+    pub schema: String,
+    pub name: String,
+    pub if_exists: bool,
+}
+
+impl From<service::DropFunction> for DropFunction {
+    fn from(value: service::DropFunction) -> Self {
+        DropFunction {
+            schema: value.schema,
+            name: value.name,
+            if_exists: value.if_exists,
+        }
+    }
+}
+
+impl From<DropFunction> for service::DropFunction {
+    fn from(value: DropFunction) -> Self {
+        service::DropFunction {
+            schema: value.schema,
+            name: value.name,
+            if_exists: value.if_exists,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct CreateSequence {
+    pub schema: String,
+    pub name: String,
+    pub if_not_exists: bool,
+    pub start: i64,
+    pub increment: i64,
+    pub min_value: i64,
+    pub max_value: i64,
+    pub owned_by_table: Option<String>,
+}
+
+impl From<service::CreateSequence> for CreateSequence {
+    fn from(value: service::CreateSequence) -> Self {
+        CreateSequence {
+            schema: value.schema,
+            name: value.name,
+            if_not_exists: value.if_not_exists,
+            start: value.start,
+            increment: value.increment,
+            min_value: value.min_value,
+            max_value: value.max_value,
+            owned_by_table: value.owned_by_table,
+        }
+    }
+}
+
+impl From<CreateSequence> for service::CreateSequence {
+    fn from(value: CreateSequence) -> Self {
+        service::CreateSequence {
+            schema: value.schema,
+            name: value.name,
+            if_not_exists: value.if_not_exists,
+            start: value.start,
+            increment: value.increment,
+            min_value: value.min_value,
+            max_value: value.max_value,
+            owned_by_table: value.owned_by_table,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
+pub struct AdvanceSequence {
+    pub schema: String,
+    pub name: String,
+    pub count: u64,
+}
+
+impl From<service::AdvanceSequence> for AdvanceSequence {
+    fn from(value: service::AdvanceSequence) -> Self {
+        AdvanceSequence {
+            schema: value.schema,
+            name: value.name,
+            count: value.count,
+        }
+    }
+}
+
+impl From<AdvanceSequence> for service::AdvanceSequence {
+    fn from(value: AdvanceSequence) -> Self {
+        service::AdvanceSequence {
+            schema: value.schema,
+            name: value.name,
+            count: value.count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
+pub struct SetSequenceValue {
+    pub schema: String,
+    pub name: String,
+    pub value: i64,
+    pub is_called: bool,
+}
+
+impl From<service::SetSequenceValue> for SetSequenceValue {
+    fn from(value: service::SetSequenceValue) -> Self {
+        SetSequenceValue {
+            schema: value.schema,
+            name: value.name,
+            value: value.value,
+            is_called: value.is_called,
+        }
+    }
+}
+
+impl From<SetSequenceValue> for service::SetSequenceValue {
+    fn from(value: SetSequenceValue) -> Self {
+        service::SetSequenceValue {
+            schema: value.schema,
+            name: value.name,
+            value: value.value,
+            is_called: value.is_called,
+        }
+    }
+}
+
+/// An object that a comment can be set on.
+///
+/// Like `GrantObject`, objects are referenced by name and resolved by the
+/// metastore when the mutation is applied.
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub enum CommentObject {
+    Database(String),
+    Schema(String),
+    Table {
+        schema: String,
+        name: String,
+    },
+    View {
+        schema: String,
+        name: String,
+    },
+    Column {
+        schema: String,
+        table: String,
+        column: String,
+    },
+}
+
+impl TryFrom<service::CommentObject> for CommentObject {
+    type Error = ProtoConvError;
+    fn try_from(value: service::CommentObject) -> Result<Self, Self::Error> {
+        let object_type = service::comment_object::ObjectType::from_i32(value.object_type).ok_or(
+            ProtoConvError::UnknownEnumVariant("ObjectType", value.object_type),
+        )?;
+        Ok(match object_type {
+            service::comment_object::ObjectType::Unknown => {
+                return Err(ProtoConvError::ZeroValueEnumVariant("ObjectType"))
+            }
+            service::comment_object::ObjectType::Database => CommentObject::Database(value.name),
+            service::comment_object::ObjectType::Schema => CommentObject::Schema(value.name),
+            service::comment_object::ObjectType::Table => CommentObject::Table {
+                schema: value.schema,
+                name: value.name,
+            },
+            service::comment_object::ObjectType::View => CommentObject::View {
+                schema: value.schema,
+                name: value.name,
+            },
+            service::comment_object::ObjectType::Column => CommentObject::Column {
+                schema: value.schema,
+                table: value.name,
+                column: value.column,
+            },
+        })
+    }
+}
+
+impl From<CommentObject> for service::CommentObject {
+    fn from(value: CommentObject) -> Self {
+        let (object_type, schema, name, column) = match value {
+            CommentObject::Database(name) => (
+                service::comment_object::ObjectType::Database,
+                String::new(),
+                name,
+                String::new(),
+            ),
+            CommentObject::Schema(name) => (
+                service::comment_object::ObjectType::Schema,
+                String::new(),
+                name,
+                String::new(),
+            ),
+            CommentObject::Table { schema, name } => (
+                service::comment_object::ObjectType::Table,
+                schema,
+                name,
+                String::new(),
+            ),
+            CommentObject::View { schema, name } => (
+                service::comment_object::ObjectType::View,
+                schema,
+                name,
+                String::new(),
+            ),
+            CommentObject::Column {
+                schema,
+                table,
+                column,
+            } => (
+                service::comment_object::ObjectType::Column,
+                schema,
+                table,
+                column,
+            ),
+        };
+        service::CommentObject {
+            object_type: object_type as i32,
+            schema,
+            name,
+            column,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct CommentOn {
+    pub object: CommentObject,
+    pub comment: Option<String>,
+}
+
+impl TryFrom<service::CommentOn> for CommentOn {
+    type Error = ProtoConvError;
+    fn try_from(value: service::CommentOn) -> Result<Self, Self::Error> {
+        Ok(CommentOn {
+            object: value.object.required("object")?,
+            comment: value.comment,
+        })
+    }
+}
+
+impl From<CommentOn> for service::CommentOn {
+    fn from(value: CommentOn) -> Self {
+        service::CommentOn {
+            object: Some(value.object.into()),
+            comment: value.comment,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,10 +1,11 @@
 mod copy_to;
 use crate::{
     gen::metastore::{
+        catalog::TableEntry,
         options::TableOptions,
         service::{
-            AlterDatabaseRename, AlterTunnelRotateKeys, CreateCredentials, CreateExternalDatabase,
-            CreateTunnel,
+            self, AlterDatabaseRename, AlterTunnelRotateKeys, CreateCredentials,
+            CreateExternalDatabase, CreateRole, CreateTunnel,
         },
     },
     sqlexec::common::{FullObjectReference, FullSchemaReference},
@@ -22,6 +23,26 @@ pub struct DropCredentials {
     #[prost(bool, tag = "2")]
     pub if_exists: bool,
 }
+#[derive(Clone, PartialEq, Message)]
+pub struct DropRoles {
+    #[prost(string, repeated, tag = "1")]
+    pub names: Vec<String>,
+    #[prost(bool, tag = "2")]
+    pub if_exists: bool,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct GrantPrivileges {
+    #[prost(message, repeated, tag = "1")]
+    pub grants: Vec<service::GrantPrivileges>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct RevokePrivileges {
+    #[prost(message, repeated, tag = "1")]
+    pub revokes: Vec<service::RevokePrivileges>,
+}
+
 #[derive(Clone, PartialEq, Message)]
 pub struct DropDatabase {
     #[prost(string, repeated, tag = "1")]
@@ -60,6 +81,10 @@ pub struct CreateTable {
     pub schema: Option<DfSchema>,
     #[prost(message, optional, tag = "5")]
     pub source: Option<LogicalPlanNode>,
+    #[prost(message, optional, tag = "6")]
+    pub options: Option<crate::gen::metastore::options::TableOptionsInternal>,
+    #[prost(message, repeated, tag = "7")]
+    pub sequences: Vec<service::CreateSequence>,
 }
 #[derive(Clone, PartialEq, Message)]
 pub struct CreateTempTable {
@@ -156,12 +181,88 @@ pub struct Delete {}
 #[derive(Clone, PartialEq, Message)]
 pub struct Insert {}
 
+#[derive(Clone, PartialEq, Message)]
+pub struct OptimizeTable {
+    #[prost(message, tag = "1")]
+    pub table: Option<TableEntry>,
+    #[prost(string, repeated, tag = "2")]
+    pub zorder_by: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct VacuumTable {
+    #[prost(message, tag = "1")]
+    pub table: Option<TableEntry>,
+    #[prost(uint64, optional, tag = "2")]
+    pub retain_hours: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct RefreshMaterializedView {
+    #[prost(message, tag = "1")]
+    pub table: Option<TableEntry>,
+    #[prost(message, tag = "2")]
+    pub source: Option<LogicalPlanNode>,
+    #[prost(string, tag = "3")]
+    pub schema: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CreateFunction {
+    #[prost(message, tag = "1")]
+    pub function: Option<service::CreateFunction>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct DropFunctions {
+    #[prost(message, repeated, tag = "1")]
+    pub drops: Vec<service::DropFunction>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CreateSequence {
+    #[prost(message, tag = "1")]
+    pub sequence: Option<service::CreateSequence>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct DropSequences {
+    #[prost(message, repeated, tag = "1")]
+    pub references: Vec<FullObjectReference>,
+    #[prost(bool, tag = "2")]
+    pub if_exists: bool,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommentOn {
+    #[prost(message, tag = "1")]
+    pub comment: Option<service::CommentOn>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CreateExternalDatabaseTable {
+    #[prost(message, tag = "1")]
+    pub reference: Option<FullObjectReference>,
+    #[prost(bool, tag = "2")]
+    pub if_not_exists: bool,
+    #[prost(bool, tag = "3")]
+    pub or_replace: bool,
+    #[prost(message, optional, tag = "4")]
+    pub schema: Option<DfSchema>,
+    #[prost(message, optional, tag = "5")]
+    pub source: Option<LogicalPlanNode>,
+    #[prost(message, tag = "6")]
+    pub options: Option<crate::gen::metastore::options::DatabaseOptions>,
+    #[prost(message, optional, tag = "7")]
+    pub tunnel: Option<crate::gen::metastore::options::TunnelOptions>,
+}
+
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, Message)]
 pub struct LogicalPlanExtension {
     #[prost(
         oneof = "LogicalPlanExtensionType",
-        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19"
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32"
     )]
     pub inner: Option<LogicalPlanExtensionType>,
 }
@@ -208,4 +309,31 @@ pub enum LogicalPlanExtensionType {
     SetVariable(SetVariable),
     #[prost(message, tag = "19")]
     CopyTo(CopyTo),
+    #[prost(message, tag = "20")]
+    CreateRole(CreateRole),
+    #[prost(message, tag = "21")]
+    DropRoles(DropRoles),
+    #[prost(message, tag = "22")]
+    GrantPrivileges(GrantPrivileges),
+    #[prost(message, tag = "23")]
+    RevokePrivileges(RevokePrivileges),
+    // DML
+    #[prost(message, tag = "24")]
+    OptimizeTable(OptimizeTable),
+    #[prost(message, tag = "25")]
+    VacuumTable(VacuumTable),
+    #[prost(message, tag = "26")]
+    RefreshMaterializedView(RefreshMaterializedView),
+    #[prost(message, tag = "27")]
+    CreateFunction(CreateFunction),
+    #[prost(message, tag = "28")]
+    DropFunctions(DropFunctions),
+    #[prost(message, tag = "29")]
+    CreateSequence(CreateSequence),
+    #[prost(message, tag = "30")]
+    DropSequences(DropSequences),
+    #[prost(message, tag = "31")]
+    CommentOn(CommentOn),
+    #[prost(message, tag = "32")]
+    CreateExternalDatabaseTable(CreateExternalDatabaseTable),
 }
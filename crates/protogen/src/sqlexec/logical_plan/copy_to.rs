@@ -56,7 +56,7 @@ pub struct CopyToDestinationOptionsS3 {
 
 #[derive(Clone, PartialEq, Message)]
 pub struct CopyToFormatOptions {
-    #[prost(oneof = "CopyToFormatOptionsEnum", tags = "1, 2, 3")]
+    #[prost(oneof = "CopyToFormatOptionsEnum", tags = "1, 2, 3, 4")]
     pub copy_to_format_options_enum: Option<CopyToFormatOptionsEnum>,
 }
 #[derive(Clone, PartialEq, Oneof)]
@@ -67,6 +67,8 @@ pub enum CopyToFormatOptionsEnum {
     Json(CopyToFormatOptionsJson),
     #[prost(message, tag = "3")]
     Parquet(CopyToFormatOptionsParquet),
+    #[prost(message, tag = "4")]
+    Avro(CopyToFormatOptionsAvro),
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -88,6 +90,9 @@ pub struct CopyToFormatOptionsParquet {
     pub row_group_size: u64,
 }
 
+#[derive(Clone, PartialEq, Message)]
+pub struct CopyToFormatOptionsAvro {}
+
 impl TryFrom<crate::metastore::types::options::CopyToFormatOptions> for CopyToFormatOptions {
     type Error = crate::errors::ProtoConvError;
     fn try_from(
@@ -120,6 +125,13 @@ impl TryFrom<crate::metastore::types::options::CopyToFormatOptions> for CopyToFo
                     )),
                 })
             }
+            crate::metastore::types::options::CopyToFormatOptions::Avro(_) => {
+                Ok(CopyToFormatOptions {
+                    copy_to_format_options_enum: Some(CopyToFormatOptionsEnum::Avro(
+                        CopyToFormatOptionsAvro {},
+                    )),
+                })
+            }
         }
     }
 }
@@ -156,6 +168,11 @@ impl TryFrom<CopyToFormatOptions> for crate::metastore::types::options::CopyToFo
                     },
                 ),
             ),
+            CopyToFormatOptionsEnum::Avro(_) => {
+                Ok(crate::metastore::types::options::CopyToFormatOptions::Avro(
+                    crate::metastore::types::options::CopyToFormatOptionsAvro {},
+                ))
+            }
         }
     }
 }
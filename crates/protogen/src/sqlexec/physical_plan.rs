@@ -2,6 +2,9 @@ mod postgres;
 pub use postgres::*;
 
 use crate::gen::metastore::catalog::TableEntry;
+use crate::gen::metastore::service::{
+    CommentOn, CreateFunction, CreateSequence, DropFunction, GrantPrivileges, RevokePrivileges,
+};
 use datafusion_proto::protobuf::{LogicalExprNode, Schema};
 use prost::{Message, Oneof};
 
@@ -44,6 +47,10 @@ pub struct CreateTableExec {
     pub or_replace: bool,
     #[prost(message, tag = "5")]
     pub arrow_schema: Option<Schema>,
+    #[prost(message, optional, tag = "6")]
+    pub options: Option<crate::gen::metastore::options::TableOptionsInternal>,
+    #[prost(message, repeated, tag = "7")]
+    pub sequences: Vec<CreateSequence>,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -230,6 +237,106 @@ pub struct DropTablesExec {
     pub if_exists: bool,
 }
 
+#[derive(Clone, PartialEq, Message)]
+pub struct CreateRoleExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(bool, tag = "3")]
+    pub if_not_exists: bool,
+    #[prost(bool, tag = "4")]
+    pub superuser: bool,
+    #[prost(bool, tag = "5")]
+    pub login: bool,
+    #[prost(string, optional, tag = "6")]
+    pub password_hash: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct DropRolesExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(string, repeated, tag = "2")]
+    pub names: Vec<String>,
+    #[prost(bool, tag = "3")]
+    pub if_exists: bool,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct GrantPrivilegesExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub grants: Vec<GrantPrivileges>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct RevokePrivilegesExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub revokes: Vec<RevokePrivileges>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CreateFunctionExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(message, tag = "2")]
+    pub function: Option<CreateFunction>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct DropFunctionsExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub drops: Vec<DropFunction>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CreateSequenceExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(message, tag = "2")]
+    pub sequence: Option<CreateSequence>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct DropSequencesExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub references: Vec<FullObjectReference>,
+    #[prost(bool, tag = "3")]
+    pub if_exists: bool,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CommentOnExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(message, tag = "2")]
+    pub comment: Option<CommentOn>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CreateExternalDatabaseTableExec {
+    #[prost(message, tag = "1")]
+    pub tbl_reference: Option<FullObjectReference>,
+    #[prost(bool, tag = "2")]
+    pub if_not_exists: bool,
+    #[prost(bool, tag = "3")]
+    pub or_replace: bool,
+    #[prost(message, tag = "4")]
+    pub arrow_schema: Option<Schema>,
+    #[prost(message, tag = "5")]
+    pub options: Option<crate::gen::metastore::options::DatabaseOptions>,
+    #[prost(message, optional, tag = "6")]
+    pub tunnel: Option<crate::gen::metastore::options::TunnelOptions>,
+}
+
 #[derive(Clone, PartialEq, Message)]
 pub struct SetVarExec {
     #[prost(string, tag = "1")]
@@ -252,6 +359,22 @@ pub struct UpdateSelector {
     pub expr: Option<LogicalExprNode>,
 }
 
+#[derive(Clone, PartialEq, Message)]
+pub struct ConstraintCheck {
+    #[prost(message, tag = "1")]
+    pub expr: Option<LogicalExprNode>,
+    #[prost(string, tag = "2")]
+    pub violation: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct UniqueCheck {
+    #[prost(string, repeated, tag = "1")]
+    pub columns: Vec<String>,
+    #[prost(string, tag = "2")]
+    pub violation: String,
+}
+
 #[derive(Clone, PartialEq, Message)]
 pub struct UpdateExec {
     #[prost(message, tag = "1")]
@@ -260,6 +383,10 @@ pub struct UpdateExec {
     pub updates: Vec<UpdateSelector>,
     #[prost(message, optional, tag = "3")]
     pub where_expr: Option<LogicalExprNode>,
+    #[prost(message, repeated, tag = "4")]
+    pub checks: Vec<ConstraintCheck>,
+    #[prost(message, repeated, tag = "5")]
+    pub unique_checks: Vec<UniqueCheck>,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -270,10 +397,52 @@ pub struct DeleteExec {
     pub where_expr: Option<LogicalExprNode>,
 }
 
+#[derive(Clone, PartialEq, Message)]
+pub struct OptimizeTableExec {
+    #[prost(message, tag = "1")]
+    pub table: Option<TableEntry>,
+    #[prost(string, repeated, tag = "2")]
+    pub zorder_by: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct VacuumTableExec {
+    #[prost(message, tag = "1")]
+    pub table: Option<TableEntry>,
+    #[prost(uint64, optional, tag = "2")]
+    pub retain_hours: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct RefreshMaterializedViewExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(message, tag = "2")]
+    pub table: Option<TableEntry>,
+    #[prost(string, tag = "3")]
+    pub schema: String,
+}
+
 #[derive(Clone, PartialEq, Message)]
 pub struct InsertExec {
     #[prost(bytes, tag = "1")]
     pub provider_id: Vec<u8>, // UUID
+    #[prost(message, repeated, tag = "2")]
+    pub checks: Vec<ConstraintCheck>,
+    #[prost(message, repeated, tag = "3")]
+    pub unique_checks: Vec<UniqueCheck>,
+    #[prost(message, optional, tag = "4")]
+    pub compaction: Option<TableCompaction>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct TableCompaction {
+    #[prost(message, tag = "1")]
+    pub table: Option<TableEntry>,
+    #[prost(uint64, tag = "2")]
+    pub small_file_bytes: u64,
+    #[prost(uint64, tag = "3")]
+    pub small_file_threshold: u64,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -315,7 +484,7 @@ pub struct AnalyzeExec {
 pub struct ExecutionPlanExtension {
     #[prost(
         oneof = "ExecutionPlanExtensionType",
-        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30"
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43"
     )]
     pub inner: Option<ExecutionPlanExtensionType>,
 }
@@ -387,4 +556,30 @@ pub enum ExecutionPlanExtensionType {
     AnalyzeExec(AnalyzeExec),
     #[prost(message, tag = "30")]
     DataSourceMetricsExecAdapter(DataSourceMetricsExecAdapter),
+    #[prost(message, tag = "31")]
+    CreateRoleExec(CreateRoleExec),
+    #[prost(message, tag = "32")]
+    DropRolesExec(DropRolesExec),
+    #[prost(message, tag = "33")]
+    GrantPrivilegesExec(GrantPrivilegesExec),
+    #[prost(message, tag = "34")]
+    RevokePrivilegesExec(RevokePrivilegesExec),
+    #[prost(message, tag = "35")]
+    OptimizeTableExec(OptimizeTableExec),
+    #[prost(message, tag = "36")]
+    VacuumTableExec(VacuumTableExec),
+    #[prost(message, tag = "37")]
+    RefreshMaterializedViewExec(RefreshMaterializedViewExec),
+    #[prost(message, tag = "38")]
+    CreateFunctionExec(CreateFunctionExec),
+    #[prost(message, tag = "39")]
+    DropFunctionsExec(DropFunctionsExec),
+    #[prost(message, tag = "40")]
+    CreateSequenceExec(CreateSequenceExec),
+    #[prost(message, tag = "41")]
+    DropSequencesExec(DropSequencesExec),
+    #[prost(message, tag = "42")]
+    CommentOnExec(CommentOnExec),
+    #[prost(message, tag = "43")]
+    CreateExternalDatabaseTableExec(CreateExternalDatabaseTableExec),
 }
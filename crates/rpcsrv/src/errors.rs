@@ -15,9 +15,18 @@ pub enum RpcsrvError {
     #[error("Executing physical plans is not currently supported")]
     PhysicalPlansNotSupported,
 
+    #[error("Missing statement for handle: {0}")]
+    MissingStatement(uuid::Uuid),
+
+    #[error("Expected exactly one statement, got {0}")]
+    ExpectedSingleStatement(usize),
+
     #[error("Missing key: {0}")]
     MissingAuthKey(&'static str),
 
+    #[error("Unauthenticated: {0}")]
+    Unauthenticated(String),
+
     #[error("Session initialize error: {0}")]
     SessionInitalizeError(String),
 
@@ -42,6 +51,9 @@ pub enum RpcsrvError {
     #[error(transparent)]
     Arrow(#[from] datafusion::arrow::error::ArrowError),
 
+    #[error(transparent)]
+    Flight(#[from] arrow_flight::error::FlightError),
+
     #[error("{0:?}")]
     TonicTransport(#[from] tonic::transport::Error),
 
@@ -59,6 +71,9 @@ pub type Result<T, E = RpcsrvError> = std::result::Result<T, E>;
 
 impl From<RpcsrvError> for tonic::Status {
     fn from(value: RpcsrvError) -> Self {
-        tonic::Status::from_error(Box::new(value))
+        match value {
+            RpcsrvError::Unauthenticated(msg) => tonic::Status::unauthenticated(msg),
+            value => tonic::Status::from_error(Box::new(value)),
+        }
     }
 }
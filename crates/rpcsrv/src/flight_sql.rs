@@ -0,0 +1,1139 @@
+//! Arrow Flight SQL service.
+//!
+//! Executes queries against the same engine used by the Postgres interface,
+//! returning results as arrow batches without any row-by-row encoding.
+//!
+//! Clients authenticate the same way as with the Postgres interface, using
+//! their login role if roles exist and the server's authenticator otherwise.
+//! Credentials are sent as a basic authorization header, either on every
+//! request or once during the handshake, which returns a bearer token to use
+//! for subsequent requests.
+use crate::errors::{Result, RpcsrvError};
+use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::error::FlightError;
+use arrow_flight::flight_service_server::FlightService;
+use arrow_flight::sql::server::FlightSqlService;
+use arrow_flight::sql::{
+    ActionClosePreparedStatementRequest, ActionCreatePreparedStatementRequest,
+    ActionCreatePreparedStatementResult, CommandGetCatalogs, CommandGetDbSchemas, CommandGetTables,
+    CommandPreparedStatementQuery, CommandPreparedStatementUpdate, CommandStatementQuery,
+    CommandStatementUpdate, ProstMessageExt, SqlInfo, TicketStatementQuery,
+};
+use arrow_flight::{
+    Action, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo, HandshakeRequest,
+    HandshakeResponse, IpcMessage, SchemaAsIpc, Ticket,
+};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use dashmap::DashMap;
+use datafusion::arrow::array::StringArray;
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::ipc::writer::IpcWriteOptions;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::physical_plan::SendableRecordBatchStream;
+use datafusion::scalar::ScalarValue;
+use datafusion::variable::VarType;
+use datafusion_ext::vars::SessionVars;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use once_cell::sync::Lazy;
+use pgrepr::format::Format;
+use pgsrv::auth::{LocalAuthenticator, PasswordMode};
+use prost::Message;
+use protogen::metastore::types::catalog::CatalogEntry;
+use sqlbuiltins::builtins::DEFAULT_CATALOG;
+use sqlexec::engine::{Engine, SessionStorageConfig, TrackedSession};
+use sqlexec::parser;
+use sqlexec::planner::privileges::RoleAuthentication;
+use sqlexec::session::ExecutionResult;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tonic::metadata::MetadataMap;
+use tonic::{Request, Response, Status, Streaming};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+/// Name used for the prepared statement and portal within a statement's
+/// session.
+const UNNAMED: String = String::new();
+
+/// How long a bearer token returned from the handshake remains valid.
+const TOKEN_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// How long a statement may go unused before it's dropped.
+const DEFAULT_STATEMENT_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Max number of statements kept at once. The least recently used statement
+/// is dropped when registering a new statement would exceed this.
+const DEFAULT_MAX_STATEMENTS: usize = 1024;
+
+type DoGetStream = <FlightSqlHandler as FlightService>::DoGetStream;
+
+type HandshakeStream = Pin<Box<dyn Stream<Item = Result<HandshakeResponse, Status>> + Send>>;
+
+/// Schema of the result for `CommandGetCatalogs`.
+static GET_CATALOGS_SCHEMA: Lazy<Arc<Schema>> = Lazy::new(|| {
+    Arc::new(Schema::new(vec![Field::new(
+        "catalog_name",
+        DataType::Utf8,
+        false,
+    )]))
+});
+
+/// An authenticated user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FlightUser {
+    name: String,
+    /// Stable id derived from the user name.
+    id: Uuid,
+}
+
+impl FlightUser {
+    fn new(name: String) -> Self {
+        let id = Uuid::new_v3(&Uuid::NAMESPACE_OID, name.as_bytes());
+        FlightUser { name, id }
+    }
+}
+
+/// A statement stored between requests.
+struct StoredStatement {
+    /// User that planned the statement. Only this user may use it.
+    user: FlightUser,
+    last_used: Instant,
+    stmt: Arc<Mutex<PlannedStatement>>,
+}
+
+/// A planned statement along with the session it was planned in.
+struct PlannedStatement {
+    session: TrackedSession,
+    /// Parameters to bind when executing the statement.
+    params: Vec<ScalarValue>,
+}
+
+impl PlannedStatement {
+    /// Bind the current parameters and execute the statement.
+    async fn execute(&mut self) -> Result<ExecutionResult> {
+        let num_fields = self
+            .session
+            .get_prepared_statement(&UNNAMED)?
+            .output_fields()
+            .map(|f| f.len())
+            .unwrap_or(0);
+        self.session.bind_statement(
+            UNNAMED,
+            &UNNAMED,
+            self.params.clone(),
+            vec![Format::Text; num_fields],
+        )?;
+        Ok(self.session.execute_portal(&UNNAMED, 0).await?)
+    }
+}
+
+/// Handler for the Flight SQL service.
+///
+/// Every statement is planned in its own session. Statements from
+/// `CommandStatementQuery` are dropped once they've been executed, prepared
+/// statements are kept until the client closes them. Statements that go unused
+/// for too long are dropped regardless.
+pub struct FlightSqlHandler {
+    /// Core db engine for creating sessions.
+    engine: Arc<Engine>,
+
+    /// Authenticator shared with the Postgres interface.
+    authenticator: Arc<dyn LocalAuthenticator>,
+
+    /// Users authenticated through the handshake, keyed by their bearer token.
+    tokens: DashMap<Uuid, (FlightUser, Instant)>,
+
+    /// Planned statements keyed by their handle.
+    statements: DashMap<Uuid, StoredStatement>,
+
+    statement_ttl: Duration,
+    max_statements: usize,
+}
+
+impl FlightSqlHandler {
+    pub fn new(engine: Arc<Engine>, authenticator: Arc<dyn LocalAuthenticator>) -> Self {
+        FlightSqlHandler {
+            engine,
+            authenticator,
+            tokens: DashMap::new(),
+            statements: DashMap::new(),
+            statement_ttl: DEFAULT_STATEMENT_TTL,
+            max_statements: DEFAULT_MAX_STATEMENTS,
+        }
+    }
+
+    /// Authenticate a request using its authorization header.
+    ///
+    /// Accepts either a bearer token from the handshake, or basic credentials
+    /// which are checked against the user's login role and the authenticator.
+    /// Requests without credentials are only accepted if no password is
+    /// required.
+    async fn authenticate(&self, metadata: &MetadataMap) -> Result<FlightUser> {
+        let header = match metadata.get("authorization") {
+            Some(header) => Some(header.to_str()?),
+            None => None,
+        };
+
+        if let Some(token) = header.and_then(|h| h.strip_prefix("Bearer ")) {
+            let token = Uuid::parse_str(token.trim())
+                .map_err(|_| RpcsrvError::Unauthenticated("Invalid bearer token".to_string()))?;
+            let user = match self.tokens.get(&token) {
+                Some(ent) if ent.1.elapsed() < TOKEN_TTL => ent.0.clone(),
+                _ => {
+                    return Err(RpcsrvError::Unauthenticated(
+                        "Invalid or expired bearer token".to_string(),
+                    ))
+                }
+            };
+            return Ok(user);
+        }
+
+        let (user, password) = match header.and_then(basic_auth_credentials) {
+            Some((user, password)) => (user, Some(password)),
+            None => (String::new(), None),
+        };
+
+        // Sessions are always created in the local database.
+        let role_auth = self
+            .engine
+            .authenticate_role(Uuid::nil(), &user, password.as_deref())
+            .await
+            .map_err(|e| {
+                if e.is_authentication_error() {
+                    RpcsrvError::Unauthenticated(format!("Failed to authenticate: {e}"))
+                } else {
+                    e.into()
+                }
+            })?;
+
+        match (role_auth, self.authenticator.password_mode()) {
+            (RoleAuthentication::Authenticated, _) => (),
+            (RoleAuthentication::PasswordRequired, _) => {
+                return Err(RpcsrvError::Unauthenticated(
+                    "Missing basic auth credentials".to_string(),
+                ))
+            }
+            (RoleAuthentication::Authenticator, PasswordMode::NoPassword { .. }) => (),
+            (RoleAuthentication::Authenticator, PasswordMode::RequireCleartext) => {
+                let password = password.ok_or_else(|| {
+                    RpcsrvError::Unauthenticated("Missing basic auth credentials".to_string())
+                })?;
+                self.authenticator
+                    .authenticate(&user, &password, "")
+                    .map_err(|e| {
+                        RpcsrvError::Unauthenticated(format!("Failed to authenticate: {e}"))
+                    })?;
+            }
+        }
+
+        Ok(FlightUser::new(user))
+    }
+
+    /// Authenticate the handshake, returning a bearer token for the user.
+    async fn handshake_inner(&self, metadata: &MetadataMap) -> Result<Uuid> {
+        let user = self.authenticate(metadata).await?;
+
+        self.tokens
+            .retain(|_, (_, created)| created.elapsed() < TOKEN_TTL);
+        let token = Uuid::new_v4();
+        debug!(user = %user.name, "authenticated flight sql client");
+        self.tokens.insert(token, (user, Instant::now()));
+
+        Ok(token)
+    }
+
+    async fn new_session(&self, user: &FlightUser) -> Result<TrackedSession> {
+        let vars = SessionVars::default()
+            .with_user_id(user.id, VarType::System)
+            .with_user_name(user.name.clone(), VarType::System);
+        Ok(self
+            .engine
+            .new_local_session_context(vars, SessionStorageConfig::default())
+            .await?)
+    }
+
+    /// Plan a statement in a new session, returning the planned statement and
+    /// its output schema.
+    async fn plan_statement(
+        &self,
+        user: &FlightUser,
+        query: &str,
+    ) -> Result<(PlannedStatement, Schema)> {
+        let mut statements = parser::parse_sql(query)?;
+        if statements.len() != 1 {
+            return Err(RpcsrvError::ExpectedSingleStatement(statements.len()));
+        }
+
+        let mut session = self.new_session(user).await?;
+        session
+            .prepare_statement(UNNAMED, statements.pop_front(), Vec::new())
+            .await?;
+
+        let schema = session
+            .get_prepared_statement(&UNNAMED)?
+            .output_schema()
+            .cloned()
+            .unwrap_or_else(Schema::empty);
+
+        let stmt = PlannedStatement {
+            session,
+            params: Vec::new(),
+        };
+
+        Ok((stmt, schema))
+    }
+
+    /// Plan a statement and store it, returning its handle.
+    async fn register_statement(&self, user: &FlightUser, query: &str) -> Result<(Uuid, Schema)> {
+        let (stmt, schema) = self.plan_statement(user, query).await?;
+
+        self.evict_statements();
+        let handle = Uuid::new_v4();
+        self.statements.insert(
+            handle,
+            StoredStatement {
+                user: user.clone(),
+                last_used: Instant::now(),
+                stmt: Arc::new(Mutex::new(stmt)),
+            },
+        );
+        Ok((handle, schema))
+    }
+
+    /// Drop statements that have expired, and make room for a new statement
+    /// by dropping the least recently used statements.
+    fn evict_statements(&self) {
+        self.statements
+            .retain(|_, stmt| stmt.last_used.elapsed() < self.statement_ttl);
+
+        while self.statements.len() >= self.max_statements {
+            let oldest = self
+                .statements
+                .iter()
+                .min_by_key(|stmt| stmt.last_used)
+                .map(|stmt| *stmt.key());
+            match oldest {
+                Some(handle) => {
+                    debug!(%handle, "evicting flight sql statement");
+                    self.statements.remove(&handle);
+                }
+                None => break,
+            }
+        }
+    }
+
+    fn get_statement(
+        &self,
+        user: &FlightUser,
+        handle: &[u8],
+    ) -> Result<Arc<Mutex<PlannedStatement>>> {
+        let handle = parse_handle(handle)?;
+        match self.statements.get_mut(&handle) {
+            Some(mut stored) if &stored.user == user => {
+                stored.last_used = Instant::now();
+                Ok(stored.stmt.clone())
+            }
+            _ => Err(RpcsrvError::MissingStatement(handle)),
+        }
+    }
+
+    fn remove_statement(
+        &self,
+        user: &FlightUser,
+        handle: &[u8],
+    ) -> Result<Arc<Mutex<PlannedStatement>>> {
+        let handle = parse_handle(handle)?;
+        self.statements
+            .remove_if(&handle, |_, stored| &stored.user == user)
+            .map(|(_, stored)| stored.stmt)
+            .ok_or(RpcsrvError::MissingStatement(handle))
+    }
+
+    async fn get_flight_info_statement_inner(
+        &self,
+        user: &FlightUser,
+        query: CommandStatementQuery,
+        descriptor: FlightDescriptor,
+    ) -> Result<FlightInfo> {
+        info!(query = %query.query, "planning flight sql statement");
+
+        let (handle, schema) = self.register_statement(user, &query.query).await?;
+        let ticket = TicketStatementQuery {
+            statement_handle: handle.as_bytes().to_vec().into(),
+        };
+
+        flight_info(&schema, ticket, descriptor)
+    }
+
+    async fn get_flight_info_prepared_statement_inner(
+        &self,
+        user: &FlightUser,
+        cmd: CommandPreparedStatementQuery,
+        descriptor: FlightDescriptor,
+    ) -> Result<FlightInfo> {
+        let stmt = self.get_statement(user, &cmd.prepared_statement_handle)?;
+        let schema = stmt
+            .lock()
+            .await
+            .session
+            .get_prepared_statement(&UNNAMED)?
+            .output_schema()
+            .cloned()
+            .unwrap_or_else(Schema::empty);
+
+        flight_info(&schema, cmd, descriptor)
+    }
+
+    async fn do_get_statement_inner(
+        &self,
+        user: &FlightUser,
+        ticket: TicketStatementQuery,
+    ) -> Result<DoGetStream> {
+        let stmt = self.remove_statement(user, &ticket.statement_handle)?;
+        let result = stmt.lock().await.execute().await?;
+        Ok(encode_result(result))
+    }
+
+    async fn do_get_prepared_statement_inner(
+        &self,
+        user: &FlightUser,
+        cmd: CommandPreparedStatementQuery,
+    ) -> Result<DoGetStream> {
+        let stmt = self.get_statement(user, &cmd.prepared_statement_handle)?;
+        let result = stmt.lock().await.execute().await?;
+        Ok(encode_result(result))
+    }
+
+    async fn do_put_statement_update_inner(
+        &self,
+        user: &FlightUser,
+        cmd: CommandStatementUpdate,
+    ) -> Result<i64> {
+        info!(query = %cmd.query, "executing flight sql update");
+
+        let (mut stmt, _) = self.plan_statement(user, &cmd.query).await?;
+        let result = stmt.execute().await?;
+        Ok(affected_rows(result).await?)
+    }
+
+    async fn do_put_prepared_statement_query_inner(
+        &self,
+        user: &FlightUser,
+        cmd: CommandPreparedStatementQuery,
+        params: Streaming<FlightData>,
+    ) -> Result<()> {
+        let stmt = self.get_statement(user, &cmd.prepared_statement_handle)?;
+
+        let batches: Vec<RecordBatch> =
+            arrow_flight::decode::FlightRecordBatchStream::new_from_flight_data(
+                params.map_err(FlightError::from),
+            )
+            .try_collect()
+            .await?;
+
+        // Only a single set of parameters is bound at a time, use the first
+        // row if the client sent more.
+        let params = match batches.iter().find(|batch| batch.num_rows() > 0) {
+            Some(batch) => batch
+                .columns()
+                .iter()
+                .map(|col| ScalarValue::try_from_array(col, 0))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+
+        stmt.lock().await.params = params;
+
+        Ok(())
+    }
+
+    async fn do_put_prepared_statement_update_inner(
+        &self,
+        user: &FlightUser,
+        cmd: CommandPreparedStatementUpdate,
+    ) -> Result<i64> {
+        let stmt = self.get_statement(user, &cmd.prepared_statement_handle)?;
+        let result = stmt.lock().await.execute().await?;
+        Ok(affected_rows(result).await?)
+    }
+
+    async fn do_action_create_prepared_statement_inner(
+        &self,
+        user: &FlightUser,
+        query: ActionCreatePreparedStatementRequest,
+    ) -> Result<ActionCreatePreparedStatementResult> {
+        info!(query = %query.query, "creating flight sql prepared statement");
+
+        let (handle, schema) = self.register_statement(user, &query.query).await?;
+        let parameter_schema = {
+            let stmt = self.get_statement(user, handle.as_bytes())?;
+            let stmt = stmt.lock().await;
+            let prepared = stmt.session.get_prepared_statement(&UNNAMED)?;
+            parameter_schema(prepared.input_paramaters())
+        };
+
+        Ok(ActionCreatePreparedStatementResult {
+            prepared_statement_handle: handle.as_bytes().to_vec().into(),
+            dataset_schema: encode_schema(&schema)?,
+            parameter_schema: encode_schema(&parameter_schema)?,
+        })
+    }
+
+    async fn do_get_catalogs_inner(&self) -> Result<DoGetStream> {
+        // Schemas and tables all live in the default catalog. External
+        // databases aren't listed.
+        let batch = RecordBatch::try_new(
+            GET_CATALOGS_SCHEMA.clone(),
+            vec![Arc::new(StringArray::from(vec![DEFAULT_CATALOG]))],
+        )?;
+        Ok(encode_batch(batch))
+    }
+
+    async fn do_get_schemas_inner(
+        &self,
+        user: &FlightUser,
+        query: CommandGetDbSchemas,
+    ) -> Result<DoGetStream> {
+        let session = self.new_session(user).await?;
+
+        let mut builder = query.into_builder();
+        for ent in session.get_session_catalog().iter_entries() {
+            if let CatalogEntry::Schema(schema) = ent.entry {
+                builder.append(DEFAULT_CATALOG, &schema.meta.name);
+            }
+        }
+        let batch = builder.build()?;
+        Ok(encode_batch(batch))
+    }
+
+    async fn do_get_tables_inner(
+        &self,
+        user: &FlightUser,
+        query: CommandGetTables,
+    ) -> Result<DoGetStream> {
+        let session = self.new_session(user).await?;
+
+        let mut builder = query.into_builder();
+        for ent in session.get_session_catalog().iter_entries() {
+            let schema_name = match ent.parent_entry {
+                Some(parent) => &parent.get_meta().name,
+                None => continue,
+            };
+
+            match ent.entry {
+                CatalogEntry::Table(table) => {
+                    // Only native tables have their columns stored in the
+                    // catalog.
+                    let fields: Vec<_> = table
+                        .get_internal_columns()
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|col| Field::new(&col.name, col.arrow_type.clone(), col.nullable))
+                        .collect();
+                    builder.append(
+                        DEFAULT_CATALOG,
+                        schema_name,
+                        &table.meta.name,
+                        "TABLE",
+                        &Schema::new(fields),
+                    )?;
+                }
+                CatalogEntry::View(view) => {
+                    builder.append(
+                        DEFAULT_CATALOG,
+                        schema_name,
+                        &view.meta.name,
+                        "VIEW",
+                        &Schema::empty(),
+                    )?;
+                }
+                _ => (),
+            }
+        }
+        let batch = builder.build()?;
+        Ok(encode_batch(batch))
+    }
+}
+
+#[tonic::async_trait]
+impl FlightSqlService for FlightSqlHandler {
+    type FlightService = FlightSqlHandler;
+
+    async fn do_handshake(
+        &self,
+        request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<HandshakeStream>, Status> {
+        let token = self.handshake_inner(request.metadata()).await?;
+
+        let result = HandshakeResponse {
+            protocol_version: 0,
+            payload: token.to_string().into_bytes().into(),
+        };
+        let mut response: Response<HandshakeStream> =
+            Response::new(Box::pin(stream::once(async move { Ok(result) })));
+        let header = format!("Bearer {token}")
+            .parse()
+            .map_err(RpcsrvError::from)?;
+        response.metadata_mut().insert("authorization", header);
+
+        Ok(response)
+    }
+
+    async fn get_flight_info_statement(
+        &self,
+        query: CommandStatementQuery,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let user = self.authenticate(request.metadata()).await?;
+        let info = self
+            .get_flight_info_statement_inner(&user, query, request.into_inner())
+            .await?;
+        Ok(Response::new(info))
+    }
+
+    async fn get_flight_info_prepared_statement(
+        &self,
+        cmd: CommandPreparedStatementQuery,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let user = self.authenticate(request.metadata()).await?;
+        let info = self
+            .get_flight_info_prepared_statement_inner(&user, cmd, request.into_inner())
+            .await?;
+        Ok(Response::new(info))
+    }
+
+    async fn get_flight_info_catalogs(
+        &self,
+        query: CommandGetCatalogs,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        self.authenticate(request.metadata()).await?;
+        let info = flight_info(&GET_CATALOGS_SCHEMA, query, request.into_inner())?;
+        Ok(Response::new(info))
+    }
+
+    async fn get_flight_info_schemas(
+        &self,
+        query: CommandGetDbSchemas,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        self.authenticate(request.metadata()).await?;
+        let schema = query.clone().into_builder().schema();
+        let info = flight_info(&schema, query, request.into_inner())?;
+        Ok(Response::new(info))
+    }
+
+    async fn get_flight_info_tables(
+        &self,
+        query: CommandGetTables,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        self.authenticate(request.metadata()).await?;
+        let schema = query.clone().into_builder().schema();
+        let info = flight_info(&schema, query, request.into_inner())?;
+        Ok(Response::new(info))
+    }
+
+    async fn do_get_statement(
+        &self,
+        ticket: TicketStatementQuery,
+        request: Request<Ticket>,
+    ) -> Result<Response<DoGetStream>, Status> {
+        let user = self.authenticate(request.metadata()).await?;
+        let stream = self.do_get_statement_inner(&user, ticket).await?;
+        Ok(Response::new(stream))
+    }
+
+    async fn do_get_prepared_statement(
+        &self,
+        cmd: CommandPreparedStatementQuery,
+        request: Request<Ticket>,
+    ) -> Result<Response<DoGetStream>, Status> {
+        let user = self.authenticate(request.metadata()).await?;
+        let stream = self.do_get_prepared_statement_inner(&user, cmd).await?;
+        Ok(Response::new(stream))
+    }
+
+    async fn do_get_catalogs(
+        &self,
+        _query: CommandGetCatalogs,
+        request: Request<Ticket>,
+    ) -> Result<Response<DoGetStream>, Status> {
+        self.authenticate(request.metadata()).await?;
+        let stream = self.do_get_catalogs_inner().await?;
+        Ok(Response::new(stream))
+    }
+
+    async fn do_get_schemas(
+        &self,
+        query: CommandGetDbSchemas,
+        request: Request<Ticket>,
+    ) -> Result<Response<DoGetStream>, Status> {
+        let user = self.authenticate(request.metadata()).await?;
+        let stream = self.do_get_schemas_inner(&user, query).await?;
+        Ok(Response::new(stream))
+    }
+
+    async fn do_get_tables(
+        &self,
+        query: CommandGetTables,
+        request: Request<Ticket>,
+    ) -> Result<Response<DoGetStream>, Status> {
+        let user = self.authenticate(request.metadata()).await?;
+        let stream = self.do_get_tables_inner(&user, query).await?;
+        Ok(Response::new(stream))
+    }
+
+    async fn do_put_statement_update(
+        &self,
+        cmd: CommandStatementUpdate,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<i64, Status> {
+        let user = self.authenticate(request.metadata()).await?;
+        Ok(self.do_put_statement_update_inner(&user, cmd).await?)
+    }
+
+    async fn do_put_prepared_statement_query(
+        &self,
+        cmd: CommandPreparedStatementQuery,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<<Self as FlightService>::DoPutStream>, Status> {
+        let user = self.authenticate(request.metadata()).await?;
+        self.do_put_prepared_statement_query_inner(&user, cmd, request.into_inner())
+            .await?;
+        Ok(Response::new(Box::pin(stream::empty())))
+    }
+
+    async fn do_put_prepared_statement_update(
+        &self,
+        cmd: CommandPreparedStatementUpdate,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<i64, Status> {
+        let user = self.authenticate(request.metadata()).await?;
+        Ok(self
+            .do_put_prepared_statement_update_inner(&user, cmd)
+            .await?)
+    }
+
+    async fn do_action_create_prepared_statement(
+        &self,
+        query: ActionCreatePreparedStatementRequest,
+        request: Request<Action>,
+    ) -> Result<ActionCreatePreparedStatementResult, Status> {
+        let user = self.authenticate(request.metadata()).await?;
+        Ok(self
+            .do_action_create_prepared_statement_inner(&user, query)
+            .await?)
+    }
+
+    async fn do_action_close_prepared_statement(
+        &self,
+        query: ActionClosePreparedStatementRequest,
+        request: Request<Action>,
+    ) -> Result<(), Status> {
+        let user = self.authenticate(request.metadata()).await?;
+        self.remove_statement(&user, &query.prepared_statement_handle)?;
+        Ok(())
+    }
+
+    async fn register_sql_info(&self, _id: i32, _result: &SqlInfo) {}
+}
+
+/// Get the user and password from a basic authorization header.
+fn basic_auth_credentials(header: &str) -> Option<(String, String)> {
+    let encoded = header.strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(BASE64.decode(encoded.trim()).ok()?).ok()?;
+    let (user, password) = decoded.split_once(':')?;
+    Some((user.to_string(), password.to_string()))
+}
+
+fn parse_handle(handle: &[u8]) -> Result<Uuid> {
+    Uuid::from_slice(handle).map_err(|e| RpcsrvError::InvalidId("statement", e))
+}
+
+/// Create flight info with a single endpoint using the given command as the
+/// ticket.
+fn flight_info(
+    schema: &Schema,
+    cmd: impl ProstMessageExt,
+    descriptor: FlightDescriptor,
+) -> Result<FlightInfo> {
+    let ticket = Ticket::new(cmd.as_any().encode_to_vec());
+    let info = FlightInfo::new()
+        .try_with_schema(schema)?
+        .with_endpoint(FlightEndpoint::new().with_ticket(ticket))
+        .with_descriptor(descriptor);
+    Ok(info)
+}
+
+fn encode_schema(schema: &Schema) -> Result<bytes::Bytes> {
+    let message: IpcMessage = SchemaAsIpc::new(schema, &IpcWriteOptions::default()).try_into()?;
+    Ok(message.0)
+}
+
+/// Build the parameter schema for a prepared statement.
+///
+/// Parameters are named "$1", "$2", etc. Parameters with types that couldn't be
+/// inferred are returned as nulls.
+fn parameter_schema<T>(
+    params: Option<&std::collections::HashMap<String, Option<(T, DataType)>>>,
+) -> Schema {
+    let mut params: Vec<_> = params
+        .into_iter()
+        .flatten()
+        .map(|(name, typ)| {
+            let idx: usize = name.trim_start_matches('$').parse().unwrap_or(usize::MAX);
+            let datatype = typ.as_ref().map(|(_, typ)| typ.clone());
+            (idx, name, datatype.unwrap_or(DataType::Null))
+        })
+        .collect();
+    params.sort_by_key(|(idx, _, _)| *idx);
+
+    let fields: Vec<_> = params
+        .into_iter()
+        .map(|(_, name, datatype)| Field::new(name, datatype, true))
+        .collect();
+    Schema::new(fields)
+}
+
+/// Count the number of rows affected by a statement.
+async fn affected_rows(result: ExecutionResult) -> Result<i64> {
+    let count = match result {
+        ExecutionResult::InsertSuccess { rows_inserted } => rows_inserted,
+        ExecutionResult::DeleteSuccess { deleted_rows } => deleted_rows,
+        ExecutionResult::UpdateSuccess { updated_rows } => updated_rows,
+        ExecutionResult::Query { mut stream, .. } => {
+            // Drain the stream to ensure the statement runs to completion.
+            while let Some(batch) = stream.next().await {
+                batch?;
+            }
+            0
+        }
+        ExecutionResult::Error(e) => return Err(e.into()),
+        _ => 0,
+    };
+    Ok(count as i64)
+}
+
+/// Encode the result of a statement as a stream of flight data.
+///
+/// Statements that don't produce a query result (e.g. DDL) return an empty
+/// stream.
+fn encode_result(result: ExecutionResult) -> DoGetStream {
+    match result {
+        ExecutionResult::Query { stream, .. } => encode_stream(stream),
+        ExecutionResult::Error(e) => Box::pin(stream::once(async move {
+            Err(Status::from(RpcsrvError::from(e)))
+        })),
+        _ => encode_batch(RecordBatch::new_empty(Arc::new(Schema::empty()))),
+    }
+}
+
+fn encode_stream(stream: SendableRecordBatchStream) -> DoGetStream {
+    let schema = stream.schema();
+    let stream = FlightDataEncoderBuilder::new()
+        .with_schema(schema)
+        .build(stream.map_err(|e| FlightError::ExternalError(Box::new(e))))
+        .map_err(Status::from);
+    Box::pin(stream)
+}
+
+fn encode_batch(batch: RecordBatch) -> DoGetStream {
+    let stream = FlightDataEncoderBuilder::new()
+        .with_schema(batch.schema())
+        .build(stream::once(async move { Ok(batch) }))
+        .map_err(Status::from);
+    Box::pin(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow_flight::decode::FlightRecordBatchStream;
+    use datafusion::arrow::array::{Array, Int64Array};
+    use pgsrv::auth::{PasswordlessAuthenticator, SingleUserAuthenticator};
+
+    async fn new_handler() -> FlightSqlHandler {
+        let engine = Engine::from_data_dir(&None).await.unwrap();
+        FlightSqlHandler::new(
+            Arc::new(engine),
+            Arc::new(PasswordlessAuthenticator::default()),
+        )
+    }
+
+    fn test_user() -> FlightUser {
+        FlightUser::new("glaredb".to_string())
+    }
+
+    fn auth_metadata(value: &str) -> MetadataMap {
+        let mut metadata = MetadataMap::new();
+        metadata.insert("authorization", value.parse().unwrap());
+        metadata
+    }
+
+    async fn collect(stream: DoGetStream) -> Vec<RecordBatch> {
+        FlightRecordBatchStream::new_from_flight_data(stream.map_err(FlightError::from))
+            .try_collect()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn statement_query() {
+        let handler = new_handler().await;
+        let user = test_user();
+
+        let info = handler
+            .get_flight_info_statement_inner(
+                &user,
+                CommandStatementQuery {
+                    query: "select 1::bigint as a".to_string(),
+                    ..Default::default()
+                },
+                FlightDescriptor::new_cmd(Vec::new()),
+            )
+            .await
+            .unwrap();
+
+        let ticket = info.endpoint[0].ticket.clone().unwrap();
+        let any = arrow_flight::sql::Any::decode(ticket.ticket).unwrap();
+        let ticket: TicketStatementQuery = any.unpack().unwrap().unwrap();
+
+        let stream = handler
+            .do_get_statement_inner(&user, ticket.clone())
+            .await
+            .unwrap();
+        let batches = collect(stream).await;
+
+        assert_eq!(1, batches.len());
+        let col = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(1, col.value(0));
+
+        // Statement handles are only valid for a single execution.
+        assert!(handler.do_get_statement_inner(&user, ticket).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn prepared_statement_with_params() {
+        let handler = new_handler().await;
+        let user = test_user();
+
+        let result = handler
+            .do_action_create_prepared_statement_inner(
+                &user,
+                ActionCreatePreparedStatementRequest {
+                    query: "select $1::bigint + 1 as a".to_string(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let cmd = CommandPreparedStatementQuery {
+            prepared_statement_handle: result.prepared_statement_handle.clone(),
+        };
+
+        {
+            let stmt = handler
+                .get_statement(&user, &cmd.prepared_statement_handle)
+                .unwrap();
+            stmt.lock().await.params = vec![ScalarValue::Int64(Some(41))];
+        }
+
+        let stream = handler
+            .do_get_prepared_statement_inner(&user, cmd.clone())
+            .await
+            .unwrap();
+        let batches = collect(stream).await;
+
+        let col = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(42, col.value(0));
+
+        // Statements can only be used by the user that created them.
+        let other = FlightUser::new("other".to_string());
+        assert!(handler
+            .get_statement(&other, &cmd.prepared_statement_handle)
+            .is_err());
+        assert!(handler
+            .remove_statement(&other, &cmd.prepared_statement_handle)
+            .is_err());
+
+        handler
+            .remove_statement(&user, &result.prepared_statement_handle)
+            .unwrap();
+        assert!(handler
+            .do_get_prepared_statement_inner(&user, cmd)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn get_tables() {
+        let handler = new_handler().await;
+        let user = test_user();
+
+        let (mut stmt, _) = handler
+            .plan_statement(&user, "create table flight_table (a int)")
+            .await
+            .unwrap();
+        stmt.execute().await.unwrap();
+
+        let stream = handler
+            .do_get_tables_inner(
+                &user,
+                CommandGetTables {
+                    db_schema_filter_pattern: Some("public".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let batches = collect(stream).await;
+
+        let names: Vec<_> = batches
+            .iter()
+            .flat_map(|batch| {
+                let col = batch
+                    .column_by_name("table_name")
+                    .unwrap()
+                    .as_any()
+                    .downcast_ref::<datafusion::arrow::array::StringArray>()
+                    .unwrap();
+                (0..col.len())
+                    .map(|i| col.value(i).to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(vec!["flight_table".to_string()], names);
+    }
+
+    #[tokio::test]
+    async fn authenticate() {
+        let engine = Engine::from_data_dir(&None).await.unwrap();
+        let handler = FlightSqlHandler::new(
+            Arc::new(engine),
+            Arc::new(SingleUserAuthenticator {
+                user: "glaredb".to_string(),
+                password: "secret".to_string(),
+            }),
+        );
+
+        let basic = |creds: &str| auth_metadata(&format!("Basic {}", BASE64.encode(creds)));
+
+        // Credentials are required.
+        let err = handler.authenticate(&MetadataMap::new()).await.unwrap_err();
+        assert_eq!(tonic::Code::Unauthenticated, Status::from(err).code());
+        assert!(handler.authenticate(&basic("glaredb:wrong")).await.is_err());
+
+        let user = handler
+            .authenticate(&basic("glaredb:secret"))
+            .await
+            .unwrap();
+        assert_eq!(test_user(), user);
+
+        // Tokens from the handshake can be used in place of credentials.
+        assert!(handler
+            .handshake_inner(&basic("glaredb:wrong"))
+            .await
+            .is_err());
+        let token = handler
+            .handshake_inner(&basic("glaredb:secret"))
+            .await
+            .unwrap();
+        let user = handler
+            .authenticate(&auth_metadata(&format!("Bearer {token}")))
+            .await
+            .unwrap();
+        assert_eq!(test_user(), user);
+
+        let unknown = Uuid::new_v4();
+        assert!(handler
+            .authenticate(&auth_metadata(&format!("Bearer {unknown}")))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn authenticate_roles() {
+        let handler = new_handler().await;
+        let user = test_user();
+
+        for query in [
+            "create user glaredb with superuser",
+            "create role flight_nologin",
+            "create user flight_writer password 'secret'",
+        ] {
+            let (mut stmt, _) = handler.plan_statement(&user, query).await.unwrap();
+            stmt.execute().await.unwrap();
+        }
+
+        let basic = |creds: &str| auth_metadata(&format!("Basic {}", BASE64.encode(creds)));
+        let assert_unauthenticated = |result: Result<FlightUser>| {
+            let err = result.unwrap_err();
+            assert_eq!(tonic::Code::Unauthenticated, Status::from(err).code());
+        };
+
+        // Roles that can't log in are rejected even without a password
+        // requirement from the authenticator.
+        assert_unauthenticated(handler.authenticate(&basic("flight_nologin:")).await);
+
+        // Role passwords are checked.
+        assert_unauthenticated(handler.authenticate(&basic("flight_writer:wrong")).await);
+        assert_unauthenticated(handler.authenticate(&basic("flight_writer:")).await);
+        assert_unauthenticated(
+            handler
+                .handshake_inner(&basic("flight_writer:wrong"))
+                .await
+                .map(|_| test_user()),
+        );
+        let writer = handler
+            .authenticate(&basic("flight_writer:secret"))
+            .await
+            .unwrap();
+        assert_eq!(FlightUser::new("flight_writer".to_string()), writer);
+
+        // Users without a role are rejected once roles exist.
+        assert_unauthenticated(handler.authenticate(&basic("flight_unknown:")).await);
+        assert_unauthenticated(handler.authenticate(&MetadataMap::new()).await);
+
+        let user = handler.authenticate(&basic("glaredb:")).await.unwrap();
+        assert_eq!(test_user(), user);
+    }
+
+    #[tokio::test]
+    async fn evict_statements() {
+        let mut handler = new_handler().await;
+        handler.max_statements = 2;
+        let user = test_user();
+
+        let (first, _) = handler.register_statement(&user, "select 1").await.unwrap();
+        let (second, _) = handler.register_statement(&user, "select 2").await.unwrap();
+
+        // Using the first statement makes the second the least recently used.
+        handler.get_statement(&user, first.as_bytes()).unwrap();
+        let (third, _) = handler.register_statement(&user, "select 3").await.unwrap();
+
+        assert_eq!(2, handler.statements.len());
+        assert!(handler.get_statement(&user, first.as_bytes()).is_ok());
+        assert!(handler.get_statement(&user, second.as_bytes()).is_err());
+        assert!(handler.get_statement(&user, third.as_bytes()).is_ok());
+
+        // Unused statements expire.
+        handler.statement_ttl = Duration::ZERO;
+        handler.register_statement(&user, "select 4").await.unwrap();
+        assert_eq!(1, handler.statements.len());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod errors;
+pub mod flight_sql;
 pub mod handler;
 pub mod proxy;
 
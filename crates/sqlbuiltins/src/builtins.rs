@@ -13,7 +13,9 @@
 //! database node will be able to see it, but will not be able to execute
 //! appropriately. We can revisit this if this isn't acceptable long-term.
 
-use datafusion::arrow::datatypes::{DataType, Field as ArrowField, Schema as ArrowSchema};
+use datafusion::arrow::datatypes::{
+    DataType, Field as ArrowField, Schema as ArrowSchema, TimeUnit,
+};
 use once_cell::sync::Lazy;
 use pgrepr::oid::FIRST_GLAREDB_BUILTIN_ID;
 use protogen::metastore::types::options::InternalColumnDefinition;
@@ -74,6 +76,8 @@ pub static GLARE_DATABASES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         ("builtin", DataType::Boolean, false),
         ("external", DataType::Boolean, false),
         ("datasource", DataType::Utf8, false),
+        ("options", DataType::Utf8, false),
+        ("comment", DataType::Utf8, true),
     ]),
 });
 
@@ -97,6 +101,7 @@ pub static GLARE_CREDENTIALS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         ("builtin", DataType::Boolean, false),
         ("provider", DataType::Utf8, false),
         ("comment", DataType::Utf8, false),
+        ("options", DataType::Utf8, false),
     ]),
 });
 
@@ -109,6 +114,7 @@ pub static GLARE_SCHEMAS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         ("database_name", DataType::Utf8, false),
         ("schema_name", DataType::Utf8, false),
         ("builtin", DataType::Boolean, false),
+        ("comment", DataType::Utf8, true),
     ]),
 });
 
@@ -124,6 +130,7 @@ pub static GLARE_TABLES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         ("builtin", DataType::Boolean, false),
         ("external", DataType::Boolean, false),
         ("datasource", DataType::Utf8, false),
+        ("comment", DataType::Utf8, true),
     ]),
 });
 
@@ -138,6 +145,7 @@ pub static GLARE_VIEWS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         ("view_name", DataType::Utf8, false),
         ("builtin", DataType::Boolean, false),
         ("sql", DataType::Utf8, false),
+        ("comment", DataType::Utf8, true),
     ]),
 });
 
@@ -152,6 +160,46 @@ pub static GLARE_COLUMNS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         ("column_ordinal", DataType::UInt32, false),
         ("data_type", DataType::Utf8, false),
         ("is_nullable", DataType::Boolean, false),
+        ("column_default", DataType::Utf8, true),
+        ("comment", DataType::Utf8, true),
+    ]),
+});
+
+/// Primary key, unique, and check constraints on native tables.
+///
+/// Key constraints have a row for each column in the key.
+pub static GLARE_CONSTRAINTS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "constraints",
+    columns: InternalColumnDefinition::from_tuples([
+        ("schema_oid", DataType::UInt32, false),
+        ("table_oid", DataType::UInt32, false),
+        ("schema_name", DataType::Utf8, false),
+        ("table_name", DataType::Utf8, false),
+        ("constraint_name", DataType::Utf8, false),
+        ("constraint_type", DataType::Utf8, false), // PRIMARY KEY, UNIQUE, CHECK
+        ("column_name", DataType::Utf8, true),
+        ("ordinal_position", DataType::UInt32, true),
+        ("check_expr", DataType::Utf8, true),
+    ]),
+});
+
+/// Materialized views, along with their refresh schedule.
+pub static GLARE_MATERIALIZED_VIEWS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "materialized_views",
+    columns: InternalColumnDefinition::from_tuples([
+        ("oid", DataType::UInt32, false),
+        ("schema_oid", DataType::UInt32, false),
+        ("schema_name", DataType::Utf8, false),
+        ("view_name", DataType::Utf8, false),
+        ("sql", DataType::Utf8, false),
+        ("refresh_interval_secs", DataType::UInt64, true),
+        (
+            "last_refreshed",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            true,
+        ),
     ]),
 });
 
@@ -209,6 +257,141 @@ pub static GLARE_DEPLOYMENT_METADATA: Lazy<BuiltinTable> = Lazy::new(|| BuiltinT
     ]),
 });
 
+pub static GLARE_ROLES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "roles",
+    columns: InternalColumnDefinition::from_tuples([
+        ("oid", DataType::UInt32, false),
+        ("role_name", DataType::Utf8, false),
+        ("builtin", DataType::Boolean, false),
+        ("superuser", DataType::Boolean, false),
+        ("login", DataType::Boolean, false),
+    ]),
+});
+
+pub static GLARE_PRIVILEGES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "privileges",
+    columns: InternalColumnDefinition::from_tuples([
+        ("role_oid", DataType::UInt32, false),
+        ("role_name", DataType::Utf8, false),
+        ("object_oid", DataType::UInt32, false),
+        ("object_type", DataType::Utf8, false),
+        ("schema_name", DataType::Utf8, true),
+        ("object_name", DataType::Utf8, false),
+        ("privilege", DataType::Utf8, false),
+    ]),
+});
+
+pub static GLARE_AUDIT_LOG: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "audit_log",
+    columns: InternalColumnDefinition::from_tuples([
+        (
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        ("user_name", DataType::Utf8, false),
+        ("connection_id", DataType::Utf8, false),
+        ("statement", DataType::Utf8, false),
+        (
+            "objects",
+            DataType::List(Arc::new(ArrowField::new("item", DataType::Utf8, true))),
+            false,
+        ),
+        ("result_type", DataType::Utf8, false),
+        ("execution_status", DataType::Utf8, false),
+        ("error_message", DataType::Utf8, true),
+        ("output_rows", DataType::UInt64, true),
+        ("output_bytes", DataType::UInt64, true),
+        ("duration_ms", DataType::UInt64, false),
+    ]),
+});
+
+pub static GLARE_QUERY_HISTORY: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "query_history",
+    columns: InternalColumnDefinition::from_tuples([
+        (
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        ("user_name", DataType::Utf8, false),
+        ("connection_id", DataType::Utf8, false),
+        ("query_text", DataType::Utf8, false),
+        ("plan_hash", DataType::UInt64, true),
+        ("result_type", DataType::Utf8, false),
+        ("execution_status", DataType::Utf8, false),
+        ("error_message", DataType::Utf8, true),
+        ("duration_ms", DataType::UInt64, false),
+        ("elapsed_compute_ns", DataType::UInt64, true),
+        ("output_rows", DataType::UInt64, true),
+        ("bytes_read", DataType::UInt64, false),
+        ("rows_read", DataType::UInt64, false),
+        (
+            "datasources",
+            DataType::List(Arc::new(ArrowField::new("item", DataType::Utf8, true))),
+            false,
+        ),
+        (
+            "datasource_bytes_read",
+            DataType::List(Arc::new(ArrowField::new("item", DataType::UInt64, true))),
+            false,
+        ),
+        (
+            "datasource_rows_read",
+            DataType::List(Arc::new(ArrowField::new("item", DataType::UInt64, true))),
+            false,
+        ),
+    ]),
+});
+
+pub static GLARE_SESSIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "sessions",
+    columns: InternalColumnDefinition::from_tuples([
+        ("pid", DataType::Int32, false),
+        ("user_name", DataType::Utf8, false),
+        ("connection_id", DataType::Utf8, false),
+        ("database_name", DataType::Utf8, false),
+        ("application_name", DataType::Utf8, false),
+        (
+            "backend_start",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        ("state", DataType::Utf8, false),
+        ("query", DataType::Utf8, true),
+        (
+            "query_start",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            true,
+        ),
+    ]),
+});
+
+pub static GLARE_PREPARED_STATEMENTS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "prepared_statements",
+    columns: InternalColumnDefinition::from_tuples([
+        ("name", DataType::Utf8, false),
+        ("statement", DataType::Utf8, true),
+        (
+            "prepare_time",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        (
+            "parameter_types",
+            DataType::List(Arc::new(ArrowField::new("item", DataType::Utf8, true))),
+            false,
+        ),
+        ("from_sql", DataType::Boolean, false),
+    ]),
+});
+
 impl BuiltinTable {
     /// Check if this table matches the provided schema and name.
     pub fn matches(&self, schema: &str, name: &str) -> bool {
@@ -239,6 +422,14 @@ impl BuiltinTable {
             &GLARE_SESSION_QUERY_METRICS,
             &GLARE_SSH_KEYS,
             &GLARE_DEPLOYMENT_METADATA,
+            &GLARE_ROLES,
+            &GLARE_PRIVILEGES,
+            &GLARE_AUDIT_LOG,
+            &GLARE_QUERY_HISTORY,
+            &GLARE_SESSIONS,
+            &GLARE_PREPARED_STATEMENTS,
+            &GLARE_CONSTRAINTS,
+            &GLARE_MATERIALIZED_VIEWS,
         ]
     }
 }
@@ -344,7 +535,8 @@ FROM (
         null AS user_defined_type_name,
         'NO' AS is_insertable_into,
         'NO' AS is_typed,
-        null AS commit_action
+        null AS commit_action,
+        t.comment AS table_comment
     FROM glare_catalog.tables t INNER JOIN glare_catalog.databases d ON t.database_oid = d.oid
     UNION ALL
     SELECT
@@ -359,7 +551,8 @@ FROM (
         null AS user_defined_type_name,
         'NO' AS is_insertable_into,
         'NO' AS is_typed,
-        null AS commit_action
+        null AS commit_action,
+        v.comment AS table_comment
     FROM glare_catalog.views v INNER JOIN glare_catalog.databases d ON v.database_oid = d.oid
 )",
 });
@@ -374,7 +567,7 @@ SELECT
     c.table_name AS table_name,
     c.column_name AS column_name,
     c.column_ordinal + 1 AS ordinal_position,
-    null AS column_default,
+    c.column_default AS column_default,
     c.is_nullable AS is_nullable,
     c.data_type AS data_type,
     null AS character_maximum_length,
@@ -411,7 +604,8 @@ SELECT
     null AS identity_cyle,
     null AS is_generated,
     null AS generation_expression,
-    'NO' AS is_updateable
+    'NO' AS is_updateable,
+    c.comment AS column_comment
 FROM glare_catalog.columns c
 INNER JOIN glare_catalog.schemas s ON c.schema_oid = s.oid
 INNER JOIN glare_catalog.databases d ON s.database_oid = d.oid
@@ -524,12 +718,24 @@ pub static PG_DESCRIPTION: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     schema: POSTGRES_SCHEMA,
     name: "pg_description",
     sql: "
-SELECT
-    1 AS objoid,
-    2 AS classoid,
-    3 AS objsubid,
-    4 AS description
-FROM (VALUES (NULL, NULL, NULL, NULL)) WHERE false", // Create empty table for now.
+SELECT *
+FROM (
+    SELECT t.oid AS objoid, 1259 AS classoid, 0 AS objsubid, t.comment AS description
+    FROM glare_catalog.tables t WHERE t.comment IS NOT NULL
+    UNION ALL
+    SELECT v.oid AS objoid, 1259 AS classoid, 0 AS objsubid, v.comment AS description
+    FROM glare_catalog.views v WHERE v.comment IS NOT NULL
+    UNION ALL
+    SELECT
+        c.table_oid AS objoid,
+        1259 AS classoid,
+        c.column_ordinal + 1 AS objsubid,
+        c.comment AS description
+    FROM glare_catalog.columns c WHERE c.comment IS NOT NULL
+    UNION ALL
+    SELECT s.oid AS objoid, 2615 AS classoid, 0 AS objsubid, s.comment AS description
+    FROM glare_catalog.schemas s WHERE s.comment IS NOT NULL
+)",
 });
 
 pub static PG_DATABASE: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
@@ -587,6 +793,141 @@ FROM glare_catalog.views;
 ",
 });
 
+pub static INFORMATION_SCHEMA_TABLE_PRIVILEGES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    schema: INFORMATION_SCHEMA,
+    name: "table_privileges",
+    sql: "
+SELECT
+    null AS grantor,
+    role_name AS grantee,
+    'default' AS table_catalog,
+    schema_name AS table_schema,
+    object_name AS table_name,
+    privilege AS privilege_type,
+    'NO' AS is_grantable,
+    'NO' AS with_hierarchy
+FROM glare_catalog.privileges
+WHERE object_type = 'table'",
+});
+
+pub static INFORMATION_SCHEMA_TABLE_CONSTRAINTS: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    schema: INFORMATION_SCHEMA,
+    name: "table_constraints",
+    sql: "
+SELECT DISTINCT
+    'default' AS constraint_catalog,
+    schema_name AS constraint_schema,
+    constraint_name AS constraint_name,
+    'default' AS table_catalog,
+    schema_name AS table_schema,
+    table_name AS table_name,
+    constraint_type AS constraint_type,
+    'NO' AS is_deferrable,
+    'NO' AS initially_deferred,
+    'YES' AS enforced
+FROM glare_catalog.constraints",
+});
+
+pub static INFORMATION_SCHEMA_KEY_COLUMN_USAGE: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    schema: INFORMATION_SCHEMA,
+    name: "key_column_usage",
+    sql: "
+SELECT
+    'default' AS constraint_catalog,
+    schema_name AS constraint_schema,
+    constraint_name AS constraint_name,
+    'default' AS table_catalog,
+    schema_name AS table_schema,
+    table_name AS table_name,
+    column_name AS column_name,
+    ordinal_position AS ordinal_position,
+    null AS position_in_unique_constraint
+FROM glare_catalog.constraints
+WHERE constraint_type IN ('PRIMARY KEY', 'UNIQUE')",
+});
+
+pub static INFORMATION_SCHEMA_CHECK_CONSTRAINTS: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    schema: INFORMATION_SCHEMA,
+    name: "check_constraints",
+    sql: "
+SELECT
+    'default' AS constraint_catalog,
+    schema_name AS constraint_schema,
+    constraint_name AS constraint_name,
+    check_expr AS check_clause
+FROM glare_catalog.constraints
+WHERE constraint_type = 'CHECK'",
+});
+
+pub static PG_ROLES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    schema: POSTGRES_SCHEMA,
+    name: "pg_roles",
+    sql: "
+SELECT
+    role_name as rolname,
+    superuser as rolsuper,
+    false as rolinherit,
+    superuser as rolcreaterole,
+    superuser as rolcreatedb,
+    login as rolcanlogin,
+    false as rolreplication,
+    -1 as rolconnlimit,
+    '********' as rolpassword,
+    null as rolvaliduntil,
+    superuser as rolbypassrls,
+    null as rolconfig,
+    oid as oid
+FROM glare_catalog.roles;
+",
+});
+
+pub static PG_STAT_ACTIVITY: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    schema: POSTGRES_SCHEMA,
+    name: "pg_stat_activity",
+    sql: "
+SELECT
+    null as datid,
+    database_name as datname,
+    pid as pid,
+    null as leader_pid,
+    null as usesysid,
+    user_name as usename,
+    application_name as application_name,
+    null as client_addr,
+    null as client_hostname,
+    -1 as client_port,
+    backend_start as backend_start,
+    null as xact_start,
+    query_start as query_start,
+    null as state_change,
+    null as wait_event_type,
+    null as wait_event,
+    state as state,
+    null as backend_xid,
+    null as backend_xmin,
+    null as query_id,
+    query as query,
+    'client backend' as backend_type
+FROM glare_catalog.sessions;
+",
+});
+
+pub static PG_PREPARED_STATEMENTS: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    schema: POSTGRES_SCHEMA,
+    name: "pg_prepared_statements",
+    sql: "
+SELECT
+    name as name,
+    statement as statement,
+    prepare_time as prepare_time,
+    parameter_types as parameter_types,
+    from_sql as from_sql,
+    0 as generic_plans,
+    0 as custom_plans
+FROM glare_catalog.prepared_statements;
+",
+});
+
 impl BuiltinView {
     pub fn builtins() -> Vec<&'static BuiltinView> {
         vec![
@@ -602,6 +943,13 @@ impl BuiltinView {
             &PG_DATABASE,
             &PG_TABLE,
             &PG_VIEWS,
+            &INFORMATION_SCHEMA_TABLE_PRIVILEGES,
+            &PG_ROLES,
+            &PG_STAT_ACTIVITY,
+            &PG_PREPARED_STATEMENTS,
+            &INFORMATION_SCHEMA_TABLE_CONSTRAINTS,
+            &INFORMATION_SCHEMA_KEY_COLUMN_USAGE,
+            &INFORMATION_SCHEMA_CHECK_CONSTRAINTS,
         ]
     }
 }
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::datasource::TableProvider;
+use datafusion_ext::errors::{ExtensionError, Result};
+use datafusion_ext::functions::{FuncParamValue, TableFunc, TableFuncContextProvider};
+use datasources::clickhouse::{ClickhouseAccessor, ClickhouseTableAccess};
+use protogen::metastore::types::catalog::RuntimePreference;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReadClickhouse;
+
+#[async_trait]
+impl TableFunc for ReadClickhouse {
+    fn runtime_preference(&self) -> RuntimePreference {
+        RuntimePreference::Remote
+    }
+    fn name(&self) -> &str {
+        "read_clickhouse"
+    }
+
+    async fn create_provider(
+        &self,
+        _: &dyn TableFuncContextProvider,
+        args: Vec<FuncParamValue>,
+        _opts: HashMap<String, FuncParamValue>,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let mut args = args.into_iter();
+        let (conn_str, database, table): (String, Option<String>, String) = match args.len() {
+            2 => (
+                args.next().unwrap().param_into()?,
+                None,
+                args.next().unwrap().param_into()?,
+            ),
+            3 => (
+                args.next().unwrap().param_into()?,
+                Some(args.next().unwrap().param_into()?),
+                args.next().unwrap().param_into()?,
+            ),
+            _ => return Err(ExtensionError::InvalidNumArgs),
+        };
+
+        let access = ClickhouseAccessor::connect(&conn_str)
+            .map_err(|e| ExtensionError::Access(Box::new(e)))?;
+        let database = database.unwrap_or_else(|| access.default_database().to_string());
+        let prov = access
+            .into_table_provider(
+                ClickhouseTableAccess {
+                    database,
+                    name: table,
+                },
+                true,
+            )
+            .await
+            .map_err(|e| ExtensionError::Access(Box::new(e)))?;
+
+        Ok(Arc::new(prov))
+    }
+}
@@ -2,6 +2,7 @@
 //! mod bigquery;
 
 mod bigquery;
+mod clickhouse;
 mod delta;
 mod generate_series;
 mod iceberg;
@@ -10,6 +11,8 @@ mod mysql;
 mod object_store;
 mod postgres;
 mod snowflake;
+mod sqlite;
+mod sqlserver;
 mod virtual_listing;
 
 use ::object_store::aws::AmazonS3ConfigKey;
@@ -24,14 +27,17 @@ use once_cell::sync::Lazy;
 use protogen::metastore::types::options::{CredentialsOptions, StorageOptions};
 
 use self::bigquery::ReadBigQuery;
+use self::clickhouse::ReadClickhouse;
 use self::delta::DeltaScan;
 use self::generate_series::GenerateSeries;
 use self::iceberg::{IcebergDataFiles, IcebergScan, IcebergSnapshots};
 use self::mongo::ReadMongoDb;
 use self::mysql::ReadMysql;
-use self::object_store::{CSV_SCAN, JSON_SCAN, PARQUET_SCAN};
+use self::object_store::{AVRO_SCAN, CSV_SCAN, JSON_SCAN, ORC_SCAN, PARQUET_SCAN};
 use self::postgres::ReadPostgres;
 use self::snowflake::ReadSnowflake;
+use self::sqlite::ReadSqlite;
+use self::sqlserver::ReadSqlServer;
 use self::virtual_listing::{ListColumns, ListSchemas, ListTables};
 
 /// Builtin table returning functions available for all sessions.
@@ -51,10 +57,15 @@ impl BuiltinTableFuncs {
             Arc::new(ReadMongoDb),
             Arc::new(ReadMysql),
             Arc::new(ReadSnowflake),
+            Arc::new(ReadSqlServer),
+            Arc::new(ReadClickhouse),
+            Arc::new(ReadSqlite),
             // Object store
             Arc::new(PARQUET_SCAN),
             Arc::new(CSV_SCAN),
             Arc::new(JSON_SCAN),
+            Arc::new(AVRO_SCAN),
+            Arc::new(ORC_SCAN),
             // Data lakes
             Arc::new(DeltaScan),
             Arc::new(IcebergScan),
@@ -2,10 +2,7 @@ use std::collections::HashMap;
 use std::{sync::Arc, vec};
 
 use async_trait::async_trait;
-use datafusion::common::{FileCompressionType, FileType};
-use datafusion::datasource::file_format::csv::CsvFormat;
-use datafusion::datasource::file_format::json::JsonFormat;
-use datafusion::datasource::file_format::parquet::ParquetFormat;
+use datafusion::common::FileCompressionType;
 use datafusion::datasource::file_format::FileFormat;
 use datafusion::datasource::TableProvider;
 use datafusion::execution::object_store::ObjectStoreUrl;
@@ -19,19 +16,24 @@ use datasources::object_store::gcs::GcsStoreAccess;
 use datasources::object_store::http::HttpStoreAccess;
 use datasources::object_store::local::LocalStoreAccess;
 use datasources::object_store::s3::S3StoreAccess;
-use datasources::object_store::{MultiSourceTableProvider, ObjStoreAccess};
+use datasources::object_store::{MultiSourceTableProvider, ObjStoreAccess, ObjStoreFileType};
 use futures::TryStreamExt;
 use protogen::metastore::types::catalog::RuntimePreference;
 use protogen::metastore::types::options::CredentialsOptions;
 
-pub const PARQUET_SCAN: ObjScanTableFunc = ObjScanTableFunc(FileType::PARQUET, "parquet_scan");
+pub const PARQUET_SCAN: ObjScanTableFunc =
+    ObjScanTableFunc(ObjStoreFileType::Parquet, "parquet_scan");
 
-pub const CSV_SCAN: ObjScanTableFunc = ObjScanTableFunc(FileType::CSV, "csv_scan");
+pub const CSV_SCAN: ObjScanTableFunc = ObjScanTableFunc(ObjStoreFileType::Csv, "csv_scan");
 
-pub const JSON_SCAN: ObjScanTableFunc = ObjScanTableFunc(FileType::JSON, "ndjson_scan");
+pub const JSON_SCAN: ObjScanTableFunc = ObjScanTableFunc(ObjStoreFileType::Json, "ndjson_scan");
+
+pub const AVRO_SCAN: ObjScanTableFunc = ObjScanTableFunc(ObjStoreFileType::Avro, "avro_scan");
+
+pub const ORC_SCAN: ObjScanTableFunc = ObjScanTableFunc(ObjStoreFileType::Orc, "orc_scan");
 
 #[derive(Debug, Clone)]
-pub struct ObjScanTableFunc(FileType, &'static str);
+pub struct ObjScanTableFunc(ObjStoreFileType, &'static str);
 
 #[async_trait]
 impl TableFunc for ObjScanTableFunc {
@@ -116,22 +118,7 @@ impl TableFunc for ObjScanTableFunc {
         };
 
         let Self(ft, _) = self;
-        let ft: Arc<dyn FileFormat> = match ft {
-            FileType::CSV => Arc::new(
-                CsvFormat::default()
-                    .with_file_compression_type(file_compression)
-                    .with_schema_infer_max_rec(Some(20480)),
-            ),
-            FileType::PARQUET => Arc::new(ParquetFormat::default()),
-            FileType::JSON => {
-                Arc::new(JsonFormat::default().with_file_compression_type(file_compression))
-            }
-            ft => {
-                return Err(ExtensionError::String(format!(
-                    "Unsuppored file type: {ft:?}"
-                )))
-            }
-        };
+        let ft = ft.file_format(file_compression);
 
         // Optimize creating a table provider for objects by clubbing the same
         // store together.
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::datasource::TableProvider;
+use datafusion_ext::errors::{ExtensionError, Result};
+use datafusion_ext::functions::{FuncParamValue, TableFunc, TableFuncContextProvider};
+use datasources::common::url::{DatasourceUrl, DatasourceUrlType};
+use datasources::sqlite::{SqliteAccessor, SqliteTableAccess};
+use protogen::metastore::types::catalog::RuntimePreference;
+use protogen::metastore::types::options::StorageOptions;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReadSqlite;
+
+#[async_trait]
+impl TableFunc for ReadSqlite {
+    fn runtime_preference(&self) -> RuntimePreference {
+        RuntimePreference::Unspecified
+    }
+
+    fn detect_runtime(
+        &self,
+        args: &[FuncParamValue],
+        _: RuntimePreference,
+    ) -> Result<RuntimePreference> {
+        let url: DatasourceUrl = args
+            .first()
+            .ok_or(ExtensionError::InvalidNumArgs)?
+            .param_ref_into()?;
+        Ok(match url.datasource_url_type() {
+            DatasourceUrlType::File => RuntimePreference::Local,
+            _ => RuntimePreference::Remote,
+        })
+    }
+
+    fn name(&self) -> &str {
+        "read_sqlite"
+    }
+
+    async fn create_provider(
+        &self,
+        _: &dyn TableFuncContextProvider,
+        args: Vec<FuncParamValue>,
+        opts: HashMap<String, FuncParamValue>,
+    ) -> Result<Arc<dyn TableProvider>> {
+        match args.len() {
+            2 => {
+                let mut args = args.into_iter();
+                let location: String = args.next().unwrap().param_into()?;
+                let table: String = args.next().unwrap().param_into()?;
+
+                // Any named arguments are passed along as options for
+                // accessing the object store.
+                let mut storage_options = StorageOptions::default();
+                for (key, value) in opts {
+                    storage_options.inner.insert(key, value.param_into()?);
+                }
+
+                let access = SqliteAccessor::connect(&location, storage_options)
+                    .await
+                    .map_err(|e| ExtensionError::Access(Box::new(e)))?;
+                let prov = access
+                    .into_table_provider(
+                        SqliteTableAccess {
+                            schema: SqliteTableAccess::DEFAULT_SCHEMA.to_string(),
+                            name: table,
+                        },
+                        true,
+                    )
+                    .await
+                    .map_err(|e| ExtensionError::Access(Box::new(e)))?;
+
+                Ok(Arc::new(prov))
+            }
+            _ => Err(ExtensionError::InvalidNumArgs),
+        }
+    }
+}
@@ -11,15 +11,19 @@ use datafusion_ext::functions::{
     FuncParamValue, IdentValue, TableFunc, TableFuncContextProvider, VirtualLister,
 };
 use datasources::bigquery::BigQueryAccessor;
+use datasources::clickhouse::ClickhouseAccessor;
 use datasources::debug::DebugVirtualLister;
 use datasources::mongodb::MongoAccessor;
 use datasources::mysql::MysqlAccessor;
 use datasources::postgres::PostgresAccess;
 use datasources::snowflake::{SnowflakeAccessor, SnowflakeDbConnection};
+use datasources::sqlite::SqliteAccessor;
+use datasources::sqlserver::SqlServerAccessor;
 use protogen::metastore::types::catalog::RuntimePreference;
 use protogen::metastore::types::options::{
-    DatabaseOptions, DatabaseOptionsBigQuery, DatabaseOptionsMongo, DatabaseOptionsMysql,
-    DatabaseOptionsPostgres, DatabaseOptionsSnowflake,
+    DatabaseOptions, DatabaseOptionsBigQuery, DatabaseOptionsClickhouse, DatabaseOptionsMongo,
+    DatabaseOptionsMysql, DatabaseOptionsPostgres, DatabaseOptionsSnowflake,
+    DatabaseOptionsSqlServer, DatabaseOptionsSqlite,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -272,6 +276,26 @@ async fn get_db_lister(
                 "deltalake information listing",
             ))
         }
+        DatabaseOptions::SqlServer(DatabaseOptionsSqlServer { connection_string }) => {
+            let accessor = SqlServerAccessor::connect(connection_string, None)
+                .await
+                .map_err(|e| ExtensionError::Access(Box::new(e)))?;
+            Box::new(accessor)
+        }
+        DatabaseOptions::Clickhouse(DatabaseOptionsClickhouse { connection_string }) => {
+            let accessor = ClickhouseAccessor::connect(connection_string)
+                .map_err(|e| ExtensionError::Access(Box::new(e)))?;
+            Box::new(accessor)
+        }
+        DatabaseOptions::Sqlite(DatabaseOptionsSqlite {
+            location,
+            storage_options,
+        }) => {
+            let accessor = SqliteAccessor::connect(location, storage_options.clone())
+                .await
+                .map_err(|e| ExtensionError::Access(Box::new(e)))?;
+            Box::new(accessor)
+        }
     };
     Ok(lister)
 }
@@ -46,6 +46,8 @@ pub fn validate_database_tunnel_support(database: &str, tunnel: &str) -> Result<
         | (DatabaseOptions::POSTGRES, TunnelOptions::SSH)
         // MySQL
         | (DatabaseOptions::MYSQL, TunnelOptions::SSH)
+        // SQL Server
+        | (DatabaseOptions::SQL_SERVER, TunnelOptions::SSH)
     ) {
         Ok(())
     } else {
@@ -66,6 +68,8 @@ pub fn validate_table_tunnel_support(table: &str, tunnel: &str) -> Result<()> {
         | (TableOptions::POSTGRES, TunnelOptions::SSH)
         // MySQL
         | (TableOptions::MYSQL, TunnelOptions::SSH)
+        // SQL Server
+        | (TableOptions::SQL_SERVER, TunnelOptions::SSH)
     ) {
         Ok(())
     } else {
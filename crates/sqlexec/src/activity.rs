@@ -0,0 +1,483 @@
+//! Activity of sessions across an engine.
+//!
+//! Every local session registers itself with the engine's `SessionRegistry`
+//! when it's created, and is removed once the session is dropped. Sessions
+//! report the statement they're currently executing, which is exposed through
+//! `pg_catalog.pg_stat_activity`.
+//!
+//! Sessions are identified by a "pid" to match postgres. Statements executing
+//! in a session may be canceled through its pid, and the session itself may be
+//! terminated (see `pg_cancel_backend` and `pg_terminate_backend`).
+//!
+//! The registry is shared by all databases served by the engine. Sessions may
+//! only see and signal sessions connected to the same database.
+use crate::errors::{ExecError, Result};
+use chrono::{DateTime, Utc};
+use datafusion_ext::vars::SessionVars;
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicI32, AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio_util::sync::{CancellationToken, WaitForCancellationFutureOwned};
+use tracing::debug;
+use uuid::Uuid;
+
+/// State of a session that's waiting on the client.
+pub const STATE_IDLE: &str = "idle";
+
+/// State of a session that's executing a statement.
+pub const STATE_ACTIVE: &str = "active";
+
+/// All sessions for an engine.
+#[derive(Debug)]
+pub struct SessionRegistry {
+    next_pid: AtomicI32,
+    sessions: Mutex<BTreeMap<i32, Arc<SessionEntry>>>,
+}
+
+impl Default for SessionRegistry {
+    fn default() -> Self {
+        SessionRegistry {
+            next_pid: AtomicI32::new(1),
+            sessions: Mutex::new(BTreeMap::new()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SessionEntry {
+    pid: i32,
+    user_name: String,
+    connection_id: Uuid,
+    database_id: Uuid,
+    /// Vars for the session, shared with the session. Used to get values that
+    /// may change during the session, e.g. `application_name`.
+    vars: SessionVars,
+    backend_start: DateTime<Utc>,
+    /// Canceled once the session should be terminated. Tokens for individual
+    /// statements are children of this token.
+    terminate: CancellationToken,
+    /// Incremented for every statement.
+    statement_counter: AtomicU64,
+    statement: Mutex<StatementState>,
+}
+
+#[derive(Debug)]
+struct StatementState {
+    /// Id of the last statement started.
+    id: u64,
+    active: bool,
+    query: Option<String>,
+    query_start: Option<DateTime<Utc>>,
+    cancel: CancellationToken,
+}
+
+/// A point-in-time snapshot of a session's activity.
+#[derive(Debug, Clone)]
+pub struct SessionActivityRecord {
+    pub pid: i32,
+    pub user_name: String,
+    pub connection_id: Uuid,
+    pub database_id: Uuid,
+    pub database_name: String,
+    pub application_name: String,
+    pub backend_start: DateTime<Utc>,
+    pub state: &'static str,
+    /// The most recent statement. If the session is idle, this is the last
+    /// statement that was executed.
+    pub query: Option<String>,
+    pub query_start: Option<DateTime<Utc>>,
+}
+
+impl SessionRegistry {
+    pub fn new() -> SessionRegistry {
+        Self::default()
+    }
+
+    /// Register a new session, returning a handle that unregisters the
+    /// session on drop.
+    pub fn register(self: &Arc<Self>, vars: &SessionVars) -> SessionActivity {
+        let pid = self.next_pid.fetch_add(1, Ordering::Relaxed);
+        let terminate = CancellationToken::new();
+        let entry = Arc::new(SessionEntry {
+            pid,
+            user_name: vars.user_name(),
+            connection_id: vars.connection_id(),
+            database_id: vars.database_id(),
+            vars: vars.clone(),
+            backend_start: Utc::now(),
+            statement_counter: AtomicU64::new(0),
+            statement: Mutex::new(StatementState {
+                id: 0,
+                active: false,
+                query: None,
+                query_start: None,
+                cancel: terminate.child_token(),
+            }),
+            terminate,
+        });
+        self.sessions.lock().insert(pid, entry.clone());
+        debug!(%pid, "registered session");
+
+        SessionActivity {
+            registry: self.clone(),
+            entry,
+        }
+    }
+
+    /// Get the activity for all sessions connected to a database, ordered by
+    /// pid.
+    pub fn activity(&self, database_id: Uuid) -> Vec<SessionActivityRecord> {
+        let sessions: Vec<_> = self
+            .sessions
+            .lock()
+            .values()
+            .filter(|entry| entry.database_id == database_id)
+            .cloned()
+            .collect();
+        sessions
+            .into_iter()
+            .map(|entry| {
+                let statement = entry.statement.lock();
+                SessionActivityRecord {
+                    pid: entry.pid,
+                    user_name: entry.user_name.clone(),
+                    connection_id: entry.connection_id,
+                    database_id: entry.database_id,
+                    database_name: entry.vars.database_name(),
+                    application_name: entry.vars.application_name(),
+                    backend_start: entry.backend_start,
+                    state: if statement.active {
+                        STATE_ACTIVE
+                    } else {
+                        STATE_IDLE
+                    },
+                    query: statement.query.clone(),
+                    query_start: statement.query_start,
+                }
+            })
+            .collect()
+    }
+
+    /// Cancel the statement currently executing in a session.
+    ///
+    /// Only sessions connected to `database_id` may be canceled. If
+    /// `user_name` is provided, only sessions for that user may be canceled.
+    /// Returns false if there's no such session.
+    pub fn cancel(&self, pid: i32, database_id: Uuid, user_name: Option<&str>) -> bool {
+        let entry = match self.get(pid, database_id, user_name) {
+            Some(entry) => entry,
+            None => return false,
+        };
+        let statement = entry.statement.lock();
+        if statement.active {
+            debug!(%pid, "canceling statement");
+            statement.cancel.cancel();
+        }
+        true
+    }
+
+    /// Terminate a session, canceling any statement currently executing.
+    ///
+    /// Only sessions connected to `database_id` may be terminated. If
+    /// `user_name` is provided, only sessions for that user may be terminated.
+    /// Returns false if there's no such session.
+    pub fn terminate(&self, pid: i32, database_id: Uuid, user_name: Option<&str>) -> bool {
+        let entry = match self.get(pid, database_id, user_name) {
+            Some(entry) => entry,
+            None => return false,
+        };
+        debug!(%pid, "terminating session");
+        entry.terminate.cancel();
+        true
+    }
+
+    fn get(
+        &self,
+        pid: i32,
+        database_id: Uuid,
+        user_name: Option<&str>,
+    ) -> Option<Arc<SessionEntry>> {
+        self.sessions
+            .lock()
+            .get(&pid)
+            .filter(|entry| entry.database_id == database_id)
+            .filter(|entry| match user_name {
+                Some(user_name) => entry.user_name == user_name,
+                None => true,
+            })
+            .cloned()
+    }
+}
+
+/// Signals other sessions on behalf of a session.
+#[derive(Debug, Clone)]
+pub struct BackendSignaler {
+    /// Pid of the session doing the signaling.
+    pid: i32,
+    registry: Arc<SessionRegistry>,
+    /// Database of the session doing the signaling. Only sessions connected to
+    /// the same database may be signaled.
+    database_id: Uuid,
+    /// Only sessions for this user may be signaled. If None, any session in
+    /// the database may be signaled.
+    user_name: Option<String>,
+}
+
+impl BackendSignaler {
+    pub fn new(activity: &SessionActivity, user_name: Option<String>) -> BackendSignaler {
+        BackendSignaler {
+            pid: activity.pid(),
+            registry: activity.registry().clone(),
+            database_id: activity.database_id(),
+            user_name,
+        }
+    }
+
+    /// Pid of the session doing the signaling.
+    pub fn pid(&self) -> i32 {
+        self.pid
+    }
+
+    pub fn cancel(&self, pid: i32) -> bool {
+        self.registry
+            .cancel(pid, self.database_id, self.user_name.as_deref())
+    }
+
+    pub fn terminate(&self, pid: i32) -> bool {
+        self.registry
+            .terminate(pid, self.database_id, self.user_name.as_deref())
+    }
+}
+
+/// Handle to a registered session.
+///
+/// The session is removed from the registry when this is dropped.
+#[derive(Debug)]
+pub struct SessionActivity {
+    registry: Arc<SessionRegistry>,
+    entry: Arc<SessionEntry>,
+}
+
+impl SessionActivity {
+    pub fn pid(&self) -> i32 {
+        self.entry.pid
+    }
+
+    /// Get the database this session is connected to.
+    pub fn database_id(&self) -> Uuid {
+        self.entry.database_id
+    }
+
+    /// Get the registry this session is registered with.
+    pub fn registry(&self) -> &Arc<SessionRegistry> {
+        &self.registry
+    }
+
+    /// Whether or not the session has been terminated.
+    pub fn is_terminated(&self) -> bool {
+        self.entry.terminate.is_cancelled()
+    }
+
+    /// Get a future that resolves once the session has been terminated.
+    pub fn terminated(&self) -> WaitForCancellationFutureOwned {
+        self.entry.terminate.clone().cancelled_owned()
+    }
+
+    /// Mark the session as actively executing a statement.
+    ///
+    /// The session is marked as idle once the returned statement is dropped.
+    pub fn start_statement(&self, query: impl Into<String>) -> ActiveStatement {
+        let id = self.entry.statement_counter.fetch_add(1, Ordering::Relaxed) + 1;
+        let cancel = self.entry.terminate.child_token();
+
+        let mut statement = self.entry.statement.lock();
+        *statement = StatementState {
+            id,
+            active: true,
+            query: Some(query.into()),
+            query_start: Some(Utc::now()),
+            cancel: cancel.clone(),
+        };
+
+        ActiveStatement {
+            entry: self.entry.clone(),
+            id,
+            cancel,
+        }
+    }
+}
+
+impl Drop for SessionActivity {
+    fn drop(&mut self) {
+        self.registry.sessions.lock().remove(&self.entry.pid);
+        debug!(pid = %self.entry.pid, "unregistered session");
+    }
+}
+
+/// A statement executing in a session.
+#[derive(Debug)]
+pub struct ActiveStatement {
+    entry: Arc<SessionEntry>,
+    id: u64,
+    cancel: CancellationToken,
+}
+
+impl ActiveStatement {
+    /// Get a future that resolves once the statement has been canceled.
+    pub fn canceled(&self) -> WaitForCancellationFutureOwned {
+        self.cancel.clone().cancelled_owned()
+    }
+
+    /// Get the error to return if the statement has been canceled.
+    pub fn check_canceled(&self) -> Result<()> {
+        if !self.cancel.is_cancelled() {
+            Ok(())
+        } else {
+            Err(self.canceled_error())
+        }
+    }
+
+    /// Get the error for a canceled statement.
+    pub fn canceled_error(&self) -> ExecError {
+        if self.entry.terminate.is_cancelled() {
+            ExecError::SessionTerminated
+        } else {
+            ExecError::QueryCanceled
+        }
+    }
+
+    /// Run a future to completion, erroring if the statement is canceled.
+    pub async fn with_cancel<F, T>(&self, fut: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        tokio::select! {
+            result = fut => result,
+            _ = self.cancel.cancelled() => Err(self.canceled_error()),
+        }
+    }
+}
+
+impl Drop for ActiveStatement {
+    fn drop(&mut self) {
+        let mut statement = self.entry.statement.lock();
+        // A newer statement may have already been started.
+        if statement.id == self.id {
+            statement.active = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::variable::VarType;
+
+    fn test_vars(user_name: &str) -> SessionVars {
+        SessionVars::default().with_user_name(user_name.to_string(), VarType::System)
+    }
+
+    fn test_vars_for_database(user_name: &str, database_id: Uuid) -> SessionVars {
+        test_vars(user_name).with_database_id(database_id, VarType::System)
+    }
+
+    #[test]
+    fn register_and_drop() {
+        let registry = Arc::new(SessionRegistry::new());
+        let s1 = registry.register(&test_vars("alice"));
+        let s2 = registry.register(&test_vars("bob"));
+        assert_ne!(s1.pid(), s2.pid());
+
+        let pids: Vec<_> = registry
+            .activity(Uuid::nil())
+            .into_iter()
+            .map(|a| a.pid)
+            .collect();
+        assert_eq!(vec![s1.pid(), s2.pid()], pids);
+
+        drop(s1);
+        let pids: Vec<_> = registry
+            .activity(Uuid::nil())
+            .into_iter()
+            .map(|a| a.pid)
+            .collect();
+        assert_eq!(vec![s2.pid()], pids);
+    }
+
+    #[test]
+    fn statement_state() {
+        let registry = Arc::new(SessionRegistry::new());
+        let session = registry.register(&test_vars("alice"));
+
+        let statement = session.start_statement("SELECT 1");
+        let activity = registry.activity(Uuid::nil()).pop().unwrap();
+        assert_eq!(STATE_ACTIVE, activity.state);
+        assert_eq!(Some("SELECT 1".to_string()), activity.query);
+
+        drop(statement);
+        let activity = registry.activity(Uuid::nil()).pop().unwrap();
+        assert_eq!(STATE_IDLE, activity.state);
+        assert_eq!(Some("SELECT 1".to_string()), activity.query);
+    }
+
+    #[tokio::test]
+    async fn cancel_statement() {
+        let registry = Arc::new(SessionRegistry::new());
+        let session = registry.register(&test_vars("alice"));
+        let statement = session.start_statement("SELECT 1");
+
+        // Other users can't cancel the statement.
+        assert!(registry.cancel(session.pid(), Uuid::nil(), Some("alice")));
+        assert!(!registry.cancel(session.pid(), Uuid::nil(), Some("bob")));
+        assert!(!registry.cancel(-1, Uuid::nil(), None));
+
+        let err = statement
+            .with_cancel(std::future::pending::<Result<()>>())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ExecError::QueryCanceled));
+        assert!(!session.is_terminated());
+
+        // Following statements aren't affected.
+        let statement = session.start_statement("SELECT 2");
+        statement.check_canceled().unwrap();
+    }
+
+    #[tokio::test]
+    async fn terminate_session() {
+        let registry = Arc::new(SessionRegistry::new());
+        let session = registry.register(&test_vars("alice"));
+        let statement = session.start_statement("SELECT 1");
+
+        assert!(registry.terminate(session.pid(), Uuid::nil(), None));
+        session.terminated().await;
+        assert!(matches!(
+            statement.check_canceled().unwrap_err(),
+            ExecError::SessionTerminated
+        ));
+    }
+
+    #[test]
+    fn isolate_databases() {
+        let registry = Arc::new(SessionRegistry::new());
+        let db1 = Uuid::new_v4();
+        let db2 = Uuid::new_v4();
+        let s1 = registry.register(&test_vars_for_database("alice", db1));
+        let s2 = registry.register(&test_vars_for_database("alice", db2));
+        let _statement = s2.start_statement("SELECT 'secret'");
+
+        // Sessions in other databases aren't listed.
+        let pids: Vec<_> = registry.activity(db1).into_iter().map(|a| a.pid).collect();
+        assert_eq!(vec![s1.pid()], pids);
+
+        // Even unrestricted signalers can't signal sessions in other
+        // databases.
+        let signaler = BackendSignaler::new(&s1, None);
+        assert!(!signaler.cancel(s2.pid()));
+        assert!(!signaler.terminate(s2.pid()));
+        assert!(!s2.is_terminated());
+        assert!(signaler.cancel(s1.pid()));
+    }
+}
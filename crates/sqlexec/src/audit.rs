@@ -0,0 +1,666 @@
+//! Audit logging of executed statements.
+//!
+//! Every statement executed by a session is recorded along with the user that
+//! executed it, the catalog objects it touched, and its outcome. The most
+//! recent records are kept in memory for querying via
+//! `glare_catalog.audit_log`, and may additionally be appended to a local
+//! newline-delimited JSON file which gets rotated once it reaches a configured
+//! size.
+//!
+//! Statement options (e.g. the options for `CREATE CREDENTIALS`) are always
+//! redacted since they may contain secrets. Literals in statements are only
+//! redacted if configured.
+use crate::metrics::{ExecutionStatus, UNKNOWN_RESULT_TYPE};
+use crate::parser::{CopyToSource, StatementWithExtensions};
+use chrono::{DateTime, Utc};
+use datafusion::sql::sqlparser::ast::{self, VisitMut, VisitorMut};
+use datafusion_ext::vars::SessionVars;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tracing::error;
+use uuid::Uuid;
+
+/// Number of audit records to hold in-memory. Once exceeded, the oldest record
+/// gets dropped.
+const MAX_RECENT_RECORDS: usize = 1000;
+
+/// Placeholder for redacted literals.
+const REDACTED: &str = "?";
+
+#[derive(Debug, Clone)]
+pub struct AuditLogConfig {
+    /// File to append records to. If not provided, records are only kept
+    /// in-memory.
+    pub path: Option<PathBuf>,
+    /// Size in bytes at which the file gets rotated.
+    pub max_file_bytes: u64,
+    /// Number of rotated files to keep.
+    pub max_files: usize,
+    /// Replace literals in statement text with placeholders.
+    pub redact_literals: bool,
+}
+
+impl Default for AuditLogConfig {
+    fn default() -> Self {
+        AuditLogConfig {
+            path: None,
+            max_file_bytes: 100 * 1024 * 1024,
+            max_files: 5,
+            redact_literals: false,
+        }
+    }
+}
+
+/// A single audited statement.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditRecord {
+    /// When the statement started executing.
+    pub timestamp: DateTime<Utc>,
+    pub user_name: String,
+    pub user_id: Uuid,
+    pub connection_id: Uuid,
+    pub database_id: Uuid,
+    /// Statement text, possibly redacted.
+    pub statement: String,
+    /// Names of catalog objects referenced by the statement.
+    pub objects: Vec<String>,
+    pub result_type: &'static str,
+    pub status: &'static str,
+    /// Error message if the statement failed.
+    pub error: Option<String>,
+    /// Number of rows returned. Only set for queries.
+    pub rows: Option<u64>,
+    /// Number of bytes returned. Only set for queries.
+    pub bytes: Option<u64>,
+    pub duration_ms: u64,
+}
+
+/// Audit log shared across all sessions for an engine.
+#[derive(Debug)]
+pub struct AuditLog {
+    redact_literals: bool,
+    recent: Mutex<VecDeque<AuditRecord>>,
+    /// Sends records to the thread writing to the log file.
+    writer: Option<mpsc::UnboundedSender<AuditRecord>>,
+}
+
+impl AuditLog {
+    /// Create a new audit log.
+    ///
+    /// If a path is provided, the file will be created (or appended to) and
+    /// records will be written to it on a background thread.
+    pub fn new(conf: AuditLogConfig) -> io::Result<AuditLog> {
+        let writer = match conf.path {
+            Some(path) => {
+                let mut file = RotatingFile::open(path, conf.max_file_bytes, conf.max_files)?;
+                let (tx, mut rx) = mpsc::unbounded_channel::<AuditRecord>();
+                thread::Builder::new()
+                    .name("audit-log".to_string())
+                    .spawn(move || {
+                        // Exits once the audit log is dropped.
+                        while let Some(record) = rx.blocking_recv() {
+                            if let Err(e) = file.write_record(&record) {
+                                error!(%e, "failed to write audit record");
+                            }
+                        }
+                    })?;
+                Some(tx)
+            }
+            None => None,
+        };
+
+        Ok(AuditLog {
+            redact_literals: conf.redact_literals,
+            recent: Mutex::new(VecDeque::new()),
+            writer,
+        })
+    }
+
+    /// Record a completed statement.
+    pub fn record(&self, record: AuditRecord) {
+        if let Some(writer) = &self.writer {
+            if writer.send(record.clone()).is_err() {
+                error!("audit log writer stopped, record not written to file");
+            }
+        }
+
+        let mut recent = self.recent.lock();
+        recent.push_back(record);
+        if recent.len() > MAX_RECENT_RECORDS {
+            recent.pop_front();
+        }
+    }
+
+    /// Get the most recent records, oldest first.
+    pub fn recent_records(&self) -> Vec<AuditRecord> {
+        self.recent.lock().iter().cloned().collect()
+    }
+}
+
+/// Audit details for a statement captured prior to execution.
+///
+/// If dropped without being recorded (e.g. execution was cancelled), an
+/// "aborted" record is written instead.
+#[derive(Debug)]
+pub struct PendingAudit {
+    log: Arc<AuditLog>,
+    timestamp: DateTime<Utc>,
+    started: Instant,
+    user_name: String,
+    user_id: Uuid,
+    connection_id: Uuid,
+    database_id: Uuid,
+    statement: String,
+    objects: Vec<String>,
+    recorded: bool,
+}
+
+impl PendingAudit {
+    pub fn new(
+        log: Arc<AuditLog>,
+        vars: &SessionVars,
+        stmt: Option<&StatementWithExtensions>,
+    ) -> PendingAudit {
+        let (statement, objects) = match stmt {
+            Some(stmt) => (
+                redacted_statement(stmt, log.redact_literals),
+                statement_objects(stmt),
+            ),
+            None => ("<empty>".to_string(), Vec::new()),
+        };
+
+        PendingAudit {
+            log,
+            timestamp: Utc::now(),
+            started: Instant::now(),
+            user_name: vars.user_name(),
+            user_id: vars.user_id(),
+            connection_id: vars.connection_id(),
+            database_id: vars.database_id(),
+            statement,
+            objects,
+            recorded: false,
+        }
+    }
+
+    /// Write the record for the statement to the audit log.
+    pub fn record(
+        mut self,
+        status: &ExecutionStatus,
+        result_type: &'static str,
+        error: Option<String>,
+        rows: Option<u64>,
+        bytes: Option<u64>,
+    ) {
+        self.write(status, result_type, error, rows, bytes);
+        self.recorded = true;
+    }
+
+    /// Drop the pending record without writing anything to the audit log.
+    pub fn discard(mut self) {
+        self.recorded = true;
+    }
+
+    fn write(
+        &self,
+        status: &ExecutionStatus,
+        result_type: &'static str,
+        error: Option<String>,
+        rows: Option<u64>,
+        bytes: Option<u64>,
+    ) {
+        self.log.record(AuditRecord {
+            timestamp: self.timestamp,
+            user_name: self.user_name.clone(),
+            user_id: self.user_id,
+            connection_id: self.connection_id,
+            database_id: self.database_id,
+            statement: self.statement.clone(),
+            objects: self.objects.clone(),
+            result_type,
+            status: status.as_str(),
+            error,
+            rows,
+            bytes,
+            duration_ms: self.started.elapsed().as_millis() as u64,
+        })
+    }
+}
+
+impl Drop for PendingAudit {
+    fn drop(&mut self) {
+        if !self.recorded {
+            self.write(
+                &ExecutionStatus::Aborted,
+                UNKNOWN_RESULT_TYPE,
+                None,
+                None,
+                None,
+            );
+        }
+    }
+}
+
+/// Get the text for a statement, redacting options and (optionally) literals.
+///
+/// Also used for the text kept in query metrics and the query history.
+pub(crate) fn redacted_statement(stmt: &StatementWithExtensions, redact_literals: bool) -> String {
+    let mut stmt = stmt.clone();
+    match &mut stmt {
+        StatementWithExtensions::Statement(stmt) if redact_literals => {
+            let _ = stmt.visit(&mut LiteralRedactor);
+        }
+        StatementWithExtensions::CreateTable(stmt) if redact_literals => {
+            let _ = stmt.stmt.visit(&mut LiteralRedactor);
+        }
+        StatementWithExtensions::CreateMaterializedView(stmt) if redact_literals => {
+            let _ = stmt.query.visit(&mut LiteralRedactor);
+        }
+        StatementWithExtensions::CreateFunction(stmt) if redact_literals => {
+            // The body is kept as text, redact it wholesale.
+            stmt.body = REDACTED.to_string();
+        }
+        StatementWithExtensions::Comment(stmt) if redact_literals => {
+            if let Some(comment) = &mut stmt.comment {
+                *comment = REDACTED.to_string();
+            }
+        }
+        StatementWithExtensions::Show(stmt) if redact_literals => match &mut stmt.filter {
+            Some(ast::ShowStatementFilter::Like(pattern))
+            | Some(ast::ShowStatementFilter::ILike(pattern)) => *pattern = REDACTED.to_string(),
+            Some(ast::ShowStatementFilter::Where(expr)) => {
+                let _ = expr.visit(&mut LiteralRedactor);
+            }
+            None => (),
+        },
+        StatementWithExtensions::CreateExternalTable(stmt) => stmt.options.redact(),
+        StatementWithExtensions::CreateExternalDatabase(stmt) => stmt.options.redact(),
+        StatementWithExtensions::CreateTunnel(stmt) => stmt.options.redact(),
+        StatementWithExtensions::CreateCredentials(stmt) => stmt.options.redact(),
+        StatementWithExtensions::CreateRole(stmt) => {
+            if let Some(password) = &mut stmt.password {
+                *password = REDACTED.to_string();
+            }
+        }
+        StatementWithExtensions::CopyTo(stmt) => {
+            stmt.options.redact();
+            if let CopyToSource::Query(query) = &mut stmt.source {
+                if redact_literals {
+                    let _ = query.visit(&mut LiteralRedactor);
+                }
+            }
+        }
+        _ => (),
+    }
+    stmt.to_string()
+}
+
+/// Replace literal values with placeholders.
+struct LiteralRedactor;
+
+impl VisitorMut for LiteralRedactor {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &mut ast::Expr) -> ControlFlow<Self::Break> {
+        match expr {
+            ast::Expr::Value(ast::Value::Placeholder(_)) => (),
+            ast::Expr::Value(value) => *value = ast::Value::Placeholder(REDACTED.to_string()),
+            ast::Expr::TypedString { value, .. } => *value = REDACTED.to_string(),
+            _ => (),
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Get the names of the catalog objects referenced in a statement.
+fn statement_objects(stmt: &StatementWithExtensions) -> Vec<String> {
+    fn push_unique(objects: &mut Vec<String>, name: String) {
+        if !objects.contains(&name) {
+            objects.push(name);
+        }
+    }
+
+    fn push_relations<V: ast::Visit>(objects: &mut Vec<String>, v: &V) {
+        let _ = ast::visit_relations(v, |rel| {
+            push_unique(objects, rel.to_string());
+            ControlFlow::<()>::Continue(())
+        });
+    }
+
+    let mut objects = Vec::new();
+    match stmt {
+        StatementWithExtensions::Statement(stmt) => {
+            push_relations(&mut objects, stmt);
+            match stmt {
+                ast::Statement::Drop { names, .. } => {
+                    for name in names {
+                        push_unique(&mut objects, name.to_string());
+                    }
+                }
+                ast::Statement::DropFunction { func_desc, .. } => {
+                    for desc in func_desc {
+                        push_unique(&mut objects, desc.name.to_string());
+                    }
+                }
+                ast::Statement::CreateSequence { name, .. } => {
+                    push_unique(&mut objects, name.to_string())
+                }
+                _ => (),
+            }
+        }
+        StatementWithExtensions::CreateTable(stmt) => push_relations(&mut objects, &stmt.stmt),
+        StatementWithExtensions::CreateExternalTable(stmt) => {
+            push_unique(&mut objects, stmt.name.to_string())
+        }
+        StatementWithExtensions::CreateExternalDatabase(stmt) => {
+            push_unique(&mut objects, stmt.name.to_string())
+        }
+        StatementWithExtensions::AlterDatabaseRename(stmt) => {
+            push_unique(&mut objects, stmt.name.to_string());
+            push_unique(&mut objects, stmt.new_name.to_string());
+        }
+        StatementWithExtensions::CreateTunnel(stmt) => {
+            push_unique(&mut objects, stmt.name.to_string())
+        }
+        StatementWithExtensions::AlterTunnel(stmt) => {
+            push_unique(&mut objects, stmt.name.to_string())
+        }
+        StatementWithExtensions::CreateCredentials(stmt) => {
+            push_unique(&mut objects, stmt.name.to_string())
+        }
+        StatementWithExtensions::CreateRole(stmt) => {
+            push_unique(&mut objects, stmt.name.to_string())
+        }
+        StatementWithExtensions::DropDatabase(stmt) => {
+            for name in &stmt.names {
+                push_unique(&mut objects, name.to_string());
+            }
+        }
+        StatementWithExtensions::DropTunnel(stmt) => {
+            for name in &stmt.names {
+                push_unique(&mut objects, name.to_string());
+            }
+        }
+        StatementWithExtensions::DropCredentials(stmt) => {
+            for name in &stmt.names {
+                push_unique(&mut objects, name.to_string());
+            }
+        }
+        StatementWithExtensions::DropRole(stmt) => {
+            for name in &stmt.names {
+                push_unique(&mut objects, name.to_string());
+            }
+        }
+        StatementWithExtensions::CopyTo(stmt) => match &stmt.source {
+            CopyToSource::Table(name) => push_unique(&mut objects, name.to_string()),
+            CopyToSource::Query(query) => push_relations(&mut objects, query),
+        },
+        StatementWithExtensions::Grant(stmt) => {
+            for name in &stmt.privileges.objects {
+                push_unique(&mut objects, name.to_string());
+            }
+            for role in &stmt.roles {
+                push_unique(&mut objects, role.to_string());
+            }
+        }
+        StatementWithExtensions::Revoke(stmt) => {
+            for name in &stmt.privileges.objects {
+                push_unique(&mut objects, name.to_string());
+            }
+            for role in &stmt.roles {
+                push_unique(&mut objects, role.to_string());
+            }
+        }
+        StatementWithExtensions::Optimize(stmt) => push_unique(&mut objects, stmt.name.to_string()),
+        StatementWithExtensions::Vacuum(stmt) => push_unique(&mut objects, stmt.name.to_string()),
+        StatementWithExtensions::CreateMaterializedView(stmt) => {
+            push_unique(&mut objects, stmt.name.to_string());
+            push_relations(&mut objects, stmt.query.as_ref());
+        }
+        StatementWithExtensions::RefreshMaterializedView(stmt) => {
+            push_unique(&mut objects, stmt.name.to_string())
+        }
+        StatementWithExtensions::CreateFunction(stmt) => {
+            push_unique(&mut objects, stmt.name.to_string())
+        }
+        StatementWithExtensions::Comment(stmt) => push_unique(&mut objects, stmt.name.to_string()),
+        StatementWithExtensions::Show(stmt) => {
+            if let Some(from) = &stmt.from {
+                push_unique(&mut objects, from.to_string())
+            }
+        }
+    }
+    objects
+}
+
+/// An append-only file that gets rotated once it exceeds some size.
+///
+/// Rotated files have a numeric suffix appended, with `<path>.1` being the
+/// most recently rotated file.
+struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: File,
+    /// Current size of the file.
+    size: u64,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf, max_bytes: u64, max_files: usize) -> io::Result<RotatingFile> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(RotatingFile {
+            path,
+            max_bytes,
+            max_files,
+            file,
+            size,
+        })
+    }
+
+    /// Write a record as a single line of JSON, rotating the file first if
+    /// needed.
+    fn write_record(&mut self, record: &AuditRecord) -> io::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        if self.size > 0 && self.size + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+
+        self.file.write_all(&line)?;
+        self.file.flush()?;
+        self.size += line.len() as u64;
+
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        if self.max_files == 0 {
+            self.file = File::create(&self.path)?;
+            self.size = 0;
+            return Ok(());
+        }
+
+        // Shift existing rotated files, overwriting the oldest.
+        for n in (1..self.max_files).rev() {
+            let from = rotated_path(&self.path, n);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, n + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))?;
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+
+        Ok(())
+    }
+}
+
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(format!(".{n}"));
+    PathBuf::from(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_sql;
+
+    fn parse_one(sql: &str) -> StatementWithExtensions {
+        parse_sql(sql).unwrap().pop_front().unwrap()
+    }
+
+    fn test_record(statement: &str) -> AuditRecord {
+        AuditRecord {
+            timestamp: Utc::now(),
+            user_name: "glaredb".to_string(),
+            user_id: Uuid::nil(),
+            connection_id: Uuid::nil(),
+            database_id: Uuid::nil(),
+            statement: statement.to_string(),
+            objects: Vec::new(),
+            result_type: "select",
+            status: ExecutionStatus::Success.as_str(),
+            error: None,
+            rows: Some(1),
+            bytes: Some(8),
+            duration_ms: 1,
+        }
+    }
+
+    #[test]
+    fn redact_literals() {
+        let stmt = parse_one("SELECT * FROM t1 WHERE a = 'secret' AND b > 4 AND c = $1");
+
+        let text = redacted_statement(&stmt, false);
+        assert_eq!(
+            "SELECT * FROM t1 WHERE a = 'secret' AND b > 4 AND c = $1",
+            text
+        );
+
+        let text = redacted_statement(&stmt, true);
+        assert_eq!("SELECT * FROM t1 WHERE a = ? AND b > ? AND c = $1", text);
+    }
+
+    #[test]
+    fn always_redact_options() {
+        let stmt = parse_one(
+            "CREATE CREDENTIALS my_creds PROVIDER aws OPTIONS (access_key_id = 'key', secret_access_key = 'secret')",
+        );
+        let text = redacted_statement(&stmt, false);
+        assert!(!text.contains("secret'"), "text: {text}");
+        assert!(!text.contains("'key'"), "text: {text}");
+    }
+
+    #[test]
+    fn redact_role_password() {
+        let stmt = parse_one("CREATE USER my_user WITH PASSWORD 'hunter2'");
+        let text = redacted_statement(&stmt, false);
+        assert!(!text.contains("hunter2"), "text: {text}");
+    }
+
+    #[test]
+    fn objects_touched() {
+        let stmt = parse_one(
+            "INSERT INTO s1.t1 SELECT * FROM t2 JOIN t3 ON t2.a = t3.a WHERE t2.b IN (SELECT b FROM t2)",
+        );
+        assert_eq!(
+            vec!["s1.t1".to_string(), "t2".to_string(), "t3".to_string()],
+            statement_objects(&stmt)
+        );
+
+        let stmt = parse_one("DROP TABLE t1, t2");
+        assert_eq!(
+            vec!["t1".to_string(), "t2".to_string()],
+            statement_objects(&stmt)
+        );
+
+        let stmt = parse_one("GRANT SELECT ON TABLE t1 TO reader");
+        assert_eq!(
+            vec!["t1".to_string(), "reader".to_string()],
+            statement_objects(&stmt)
+        );
+    }
+
+    #[test]
+    fn rotate_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit").join("audit.log");
+
+        let record = test_record("SELECT 1");
+        let line_len = serde_json::to_vec(&record).unwrap().len() as u64 + 1;
+
+        // Room for two records per file.
+        let mut file = RotatingFile::open(path.clone(), line_len * 2, 2).unwrap();
+        for _ in 0..7 {
+            file.write_record(&record).unwrap();
+        }
+
+        let num_lines = |path: &Path| fs::read_to_string(path).unwrap().lines().count();
+        assert_eq!(1, num_lines(&path));
+        assert_eq!(2, num_lines(&rotated_path(&path, 1)));
+        assert_eq!(2, num_lines(&rotated_path(&path, 2)));
+        assert!(!rotated_path(&path, 3).exists());
+
+        let line = fs::read_to_string(&path).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(line.trim()).unwrap();
+        assert_eq!("SELECT 1", parsed["statement"]);
+    }
+
+    #[test]
+    fn record_aborted_on_drop() {
+        let log = Arc::new(AuditLog::new(AuditLogConfig::default()).unwrap());
+        let vars = SessionVars::default();
+
+        let stmt = parse_one("SELECT 1");
+        PendingAudit::new(log.clone(), &vars, Some(&stmt)).record(
+            &ExecutionStatus::Success,
+            "select",
+            None,
+            Some(1),
+            Some(8),
+        );
+
+        let stmt = parse_one("SELECT 2");
+        drop(PendingAudit::new(log.clone(), &vars, Some(&stmt)));
+
+        let records = log.recent_records();
+        assert_eq!(2, records.len());
+        assert_eq!("success", records[0].status);
+        assert_eq!("SELECT 2", records[1].statement);
+        assert_eq!("aborted", records[1].status);
+    }
+
+    #[test]
+    fn recent_records_bounded() {
+        let log = AuditLog::new(AuditLogConfig::default()).unwrap();
+        for i in 0..(MAX_RECENT_RECORDS + 10) {
+            log.record(test_record(&format!("SELECT {i}")));
+        }
+        let records = log.recent_records();
+        assert_eq!(MAX_RECENT_RECORDS, records.len());
+        assert_eq!("SELECT 10", records[0].statement);
+    }
+}
@@ -1,9 +1,12 @@
+pub mod materialized_views;
+pub mod query_history;
 pub mod storage;
 
 use std::{collections::HashMap, fmt::Debug, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use parking_lot::Mutex;
+use telemetry::metrics::METRICS;
 use tokio::{
     sync::{mpsc, oneshot},
     task::JoinHandle,
@@ -112,6 +115,12 @@ impl JobRunner {
                         let _ = sleep_receiver.await;
 
                         let res = job.start().await;
+                        let status = if res.is_ok() { "success" } else { "fail" };
+                        METRICS
+                            .background_jobs_total
+                            .with_label_values(&[job.kind(), status])
+                            .inc();
+
                         if sender
                             .send(RequestMessage::JobComplete(job_name.clone(), res))
                             .is_err()
@@ -236,6 +245,13 @@ pub trait BgJob: Debug + Send + Sync {
     /// should have the same name anywhere even if other options vary a bit.
     fn name(&self) -> String;
 
+    /// Kind of the job, used when reporting metrics.
+    ///
+    /// Unlike `name`, this should be the same for all jobs of the same type.
+    fn kind(&self) -> &'static str {
+        "unknown"
+    }
+
     /// The job should start at this instant (and not before this).
     fn start_at(&self) -> Instant;
 
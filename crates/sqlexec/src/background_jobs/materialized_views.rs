@@ -0,0 +1,253 @@
+//! Background jobs for refreshing materialized views on a schedule.
+
+use std::{fmt, path::PathBuf, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use datafusion::{sql::sqlparser::ast::Ident, variable::VarType};
+use datafusion_ext::vars::SessionVars;
+use datasources::native::access::NativeTableStorage;
+use protogen::metastore::types::{
+    catalog::{CatalogEntry, TableEntry},
+    options::{InternalMaterializedView, TableOptions},
+};
+use telemetry::Tracker;
+use tokio::time::Instant;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::{
+    activity::SessionRegistry,
+    audit::AuditLog,
+    errors::{internal, Result},
+    limits::QueryLimiter,
+    metastore::catalog::{CatalogMutator, SessionCatalog},
+    query_history::QueryHistory,
+    session::{ExecutionResult, Session},
+};
+
+use super::{BgJob, JobRunner};
+
+/// Schedules refreshes of materialized views created with a `REFRESH EVERY`
+/// interval.
+///
+/// Refreshing a view requires planning its query, so this holds everything
+/// needed to open a new session in the view's database. Refreshes run as the
+/// user that created the view.
+#[derive(Clone)]
+pub struct MaterializedViewScheduler {
+    pub(crate) catalog_mutator: CatalogMutator,
+    pub(crate) native_tables: NativeTableStorage,
+    pub(crate) tracker: Arc<Tracker>,
+    pub(crate) spill_path: Option<PathBuf>,
+    pub(crate) background_jobs: JobRunner,
+    pub(crate) query_limiter: QueryLimiter,
+    pub(crate) audit_log: Option<Arc<AuditLog>>,
+    pub(crate) query_history: Arc<QueryHistory>,
+    pub(crate) sessions: Arc<SessionRegistry>,
+}
+
+impl fmt::Debug for MaterializedViewScheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MaterializedViewScheduler")
+            .field("database_id", &self.native_tables.db_id())
+            .finish_non_exhaustive()
+    }
+}
+
+impl MaterializedViewScheduler {
+    /// Schedule the next refresh for a materialized view.
+    ///
+    /// Does nothing if the table isn't a materialized view or doesn't refresh
+    /// on a schedule.
+    pub fn schedule(&self, table: &TableEntry) {
+        if let Some(due_at) = next_refresh_at(table) {
+            self.schedule_at(table.meta.id, due_at);
+        }
+    }
+
+    /// Schedule refreshes for all materialized views in the catalog.
+    pub fn schedule_all(&self, catalog: &SessionCatalog) {
+        for ent in catalog.iter_entries() {
+            if let CatalogEntry::Table(table) = ent.entry {
+                self.schedule(table);
+            }
+        }
+    }
+
+    fn schedule_at(&self, view_id: u32, due_at: i64) {
+        let job = Arc::new(BackgroundJobRefreshMaterializedView {
+            scheduler: self.clone(),
+            view_id,
+            due_at,
+        });
+        if let Err(error) = self.background_jobs.add(job) {
+            warn!(%view_id, %error, "failed to schedule materialized view refresh");
+        }
+    }
+
+    /// Get the latest catalog for the view's database.
+    async fn latest_catalog(&self) -> Result<SessionCatalog> {
+        let client = self
+            .catalog_mutator
+            .get_metastore_client()
+            .ok_or_else(|| internal!("missing metastore client for materialized view refresh"))?;
+        client.refresh_cached_state().await?;
+        let state = client.get_cached_state().await?;
+        Ok(SessionCatalog::new(state))
+    }
+
+    /// Open a new session as the given user.
+    fn new_session(
+        &self,
+        catalog: SessionCatalog,
+        user_id: Uuid,
+        user_name: &str,
+    ) -> Result<Session> {
+        let database_id = self.native_tables.db_id();
+        // Each session needs its own connection id to show up separately in
+        // the session registry.
+        let vars = SessionVars::default()
+            .with_user_id(user_id, VarType::System)
+            .with_user_name(user_name, VarType::System)
+            .with_connection_id(Uuid::new_v4(), VarType::System)
+            .with_database_id(database_id, VarType::System)
+            .with_is_cloud_instance(!database_id.is_nil(), VarType::System);
+
+        Session::new(
+            vars,
+            catalog,
+            self.catalog_mutator.clone(),
+            self.native_tables.clone(),
+            self.tracker.clone(),
+            self.spill_path.clone(),
+            self.background_jobs.clone(),
+            self.query_limiter.clone(),
+            self.audit_log.clone(),
+            self.query_history.clone(),
+            self.sessions.clone(),
+        )
+    }
+}
+
+/// Returns the materialized view definition for a table, if it's a
+/// materialized view.
+fn materialized_view(table: &TableEntry) -> Option<&InternalMaterializedView> {
+    match &table.options {
+        TableOptions::Internal(opts) => opts.materialized_view.as_ref(),
+        _ => None,
+    }
+}
+
+/// Returns the refresh interval (in microseconds) and the last refresh time
+/// for a materialized view that refreshes on a schedule.
+fn refresh_schedule(table: &TableEntry) -> Option<(i64, Option<i64>)> {
+    let view = materialized_view(table)?;
+    let interval = view.refresh_interval_secs? as i64 * 1_000_000;
+    Some((interval, view.last_refreshed))
+}
+
+/// Returns when the view should next be refreshed (microseconds since the
+/// unix epoch).
+fn next_refresh_at(table: &TableEntry) -> Option<i64> {
+    let (interval, last_refreshed) = refresh_schedule(table)?;
+    let last_refreshed = last_refreshed.unwrap_or_else(|| Utc::now().timestamp_micros());
+    Some(last_refreshed.saturating_add(interval))
+}
+
+/// Refreshes a materialized view once it's due.
+///
+/// The job is named after the time the view is due, so the same refresh
+/// scheduled from multiple sessions only runs once.
+#[derive(Debug)]
+pub struct BackgroundJobRefreshMaterializedView {
+    scheduler: MaterializedViewScheduler,
+    view_id: u32,
+    /// Microseconds since the unix epoch.
+    due_at: i64,
+}
+
+#[async_trait]
+impl BgJob for BackgroundJobRefreshMaterializedView {
+    fn name(&self) -> String {
+        format!(
+            "refresh_materialized_view_{}_{}_{}",
+            self.scheduler.native_tables.db_id(),
+            self.view_id,
+            self.due_at,
+        )
+    }
+
+    fn kind(&self) -> &'static str {
+        "refresh_materialized_view"
+    }
+
+    fn start_at(&self) -> Instant {
+        let wait = self.due_at - Utc::now().timestamp_micros();
+        Instant::now() + Duration::from_micros(wait.max(0) as u64)
+    }
+
+    async fn start(&self) -> Result<()> {
+        let catalog = self.scheduler.latest_catalog().await?;
+
+        // The view may have been dropped, altered, or refreshed manually since
+        // this job was scheduled.
+        let (schema, table) = match catalog.get_namespaced_by_oid(self.view_id) {
+            Some(ent) => match (ent.parent_entry, ent.entry) {
+                (Some(schema), CatalogEntry::Table(table)) => {
+                    (schema.get_meta().name.clone(), table.clone())
+                }
+                _ => return Ok(()),
+            },
+            None => return Ok(()),
+        };
+        let (interval, last_refreshed) = match refresh_schedule(&table) {
+            Some(schedule) => schedule,
+            None => return Ok(()),
+        };
+        let due_at = last_refreshed.unwrap_or(0).saturating_add(interval);
+        if due_at > Utc::now().timestamp_micros() {
+            debug!(view_id = %self.view_id, %due_at, "materialized view not yet due for refresh");
+            self.scheduler.schedule_at(self.view_id, due_at);
+            return Ok(());
+        }
+
+        let (owner_id, owner_name) = match materialized_view(&table)
+            .and_then(|view| Some((view.owner_id?, view.owner_name.clone()?)))
+        {
+            Some(owner) => owner,
+            None => {
+                warn!(
+                    view_id = %self.view_id,
+                    "materialized view has no owner, skipping scheduled refresh"
+                );
+                return Ok(());
+            }
+        };
+        let mut session = self.scheduler.new_session(catalog, owner_id, &owner_name)?;
+
+        let sql = format!(
+            "REFRESH MATERIALIZED VIEW {}.{}",
+            Ident::with_quote('"', schema),
+            Ident::with_quote('"', &table.meta.name),
+        );
+        let result = match session.sql_to_lp(&sql).await {
+            Ok(plan) => session.execute_inner(plan).await,
+            Err(e) => Err(e),
+        };
+        let result = match result {
+            Ok(ExecutionResult::Error(e)) => Err(e.into()),
+            Ok(_) => Ok(()),
+            Err(e) => Err(e),
+        };
+
+        if result.is_err() {
+            // Try again after another interval. Successful refreshes schedule
+            // the next run themselves.
+            self.scheduler
+                .schedule_at(self.view_id, Utc::now().timestamp_micros() + interval);
+        }
+
+        result
+    }
+}
@@ -0,0 +1,41 @@
+//! Background job for persisting the query history.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use tokio::time::Instant;
+
+use crate::{errors::Result, query_history::QueryHistory};
+
+use super::BgJob;
+
+#[derive(Debug)]
+pub struct BackgroundJobPersistQueryHistory {
+    history: Arc<QueryHistory>,
+}
+
+impl BackgroundJobPersistQueryHistory {
+    pub fn new(history: Arc<QueryHistory>) -> Arc<Self> {
+        Arc::new(Self { history })
+    }
+}
+
+#[async_trait]
+impl BgJob for BackgroundJobPersistQueryHistory {
+    fn name(&self) -> String {
+        "persist_query_history".to_string()
+    }
+
+    fn kind(&self) -> &'static str {
+        "persist_query_history"
+    }
+
+    fn start_at(&self) -> Instant {
+        // Batch up queries completing within the next 30 seconds.
+        Instant::now() + Duration::from_secs(30)
+    }
+
+    async fn start(&self) -> Result<()> {
+        self.history.persist().await
+    }
+}
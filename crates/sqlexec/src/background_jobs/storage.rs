@@ -3,16 +3,18 @@
 use std::{sync::Arc, time::Duration};
 
 use async_trait::async_trait;
+use datafusion_ext::vars::SessionVars;
 use datasources::native::access::NativeTableStorage;
 use protogen::metastore::types::{
     catalog::TableEntry,
     service::{Mutation, UpdateDeploymentStorage},
 };
 use tokio::time::Instant;
+use tracing::{debug, warn};
 
 use crate::{errors::Result, metastore::client::MetastoreClientHandle};
 
-use super::BgJob;
+use super::{BgJob, JobRunner};
 
 #[derive(Debug)]
 pub struct BackgroundJobStorageTracker {
@@ -36,6 +38,10 @@ impl BgJob for BackgroundJobStorageTracker {
         format!("storage_tracker_{}", self.native_store.db_id())
     }
 
+    fn kind(&self) -> &'static str {
+        "storage_tracker"
+    }
+
     fn start_at(&self) -> Instant {
         // Start after 5 minutes of scheduling the job, so we can batch jobs for
         // frequently updating tables.
@@ -87,6 +93,10 @@ impl BgJob for BackgroundJobDeleteTable {
         )
     }
 
+    fn kind(&self) -> &'static str {
+        "delete_table"
+    }
+
     fn start_at(&self) -> Instant {
         // schedule the delete task to run immediately
         Instant::now()
@@ -98,9 +108,107 @@ impl BgJob for BackgroundJobDeleteTable {
     }
 }
 
+/// Compaction of a native table to schedule after it's been written to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TableCompaction {
+    pub table: TableEntry,
+    /// Files smaller than this (in bytes) are candidates for compaction.
+    pub small_file_bytes: usize,
+    /// Number of small files the table needs before it's compacted.
+    pub small_file_threshold: usize,
+}
+
+impl TableCompaction {
+    /// Create a compaction for a table using the thresholds configured for
+    /// the session. Returns `None` if automatic compaction isn't enabled.
+    pub fn from_vars(table: &TableEntry, vars: &SessionVars) -> Option<TableCompaction> {
+        if !vars.auto_compact_tables() {
+            return None;
+        }
+        Some(TableCompaction {
+            table: table.clone(),
+            small_file_bytes: vars.compact_small_file_bytes(),
+            small_file_threshold: vars.compact_small_file_threshold(),
+        })
+    }
+
+    /// Schedule a background job compacting the table if it has too many
+    /// small files.
+    pub fn schedule(&self, native_store: NativeTableStorage, jobs: &JobRunner) {
+        let job = BackgroundJobCompactTable::new(native_store, self.clone());
+        if let Err(error) = jobs.add(job) {
+            warn!(table = %self.table.meta.name, %error, "failed to schedule table compaction");
+        }
+    }
+}
+
+/// Compacts a native table if it has accumulated too many small files.
+///
+/// Every insert into a native table writes at least one new file, so tables
+/// with frequent small appends end up with lots of tiny files.
+#[derive(Debug)]
+pub struct BackgroundJobCompactTable {
+    native_store: NativeTableStorage,
+    compaction: TableCompaction,
+}
+
+impl BackgroundJobCompactTable {
+    pub fn new(native_store: NativeTableStorage, compaction: TableCompaction) -> Arc<Self> {
+        Arc::new(Self {
+            native_store,
+            compaction,
+        })
+    }
+}
+
+#[async_trait]
+impl BgJob for BackgroundJobCompactTable {
+    fn name(&self) -> String {
+        format!(
+            "compact_table_{}_{}",
+            self.native_store.db_id(),
+            self.compaction.table.meta.id
+        )
+    }
+
+    fn kind(&self) -> &'static str {
+        "compact_table"
+    }
+
+    fn start_at(&self) -> Instant {
+        // Start after 5 minutes of scheduling the job, so we can batch jobs for
+        // frequently updating tables.
+        Instant::now() + Duration::from_secs(5 * 60)
+    }
+
+    async fn start(&self) -> Result<()> {
+        let table_entry = &self.compaction.table;
+        let table = self.native_store.load_table(table_entry).await?;
+        let num_small_files = table.num_files_smaller_than(self.compaction.small_file_bytes as i64);
+        if num_small_files < self.compaction.small_file_threshold {
+            return Ok(());
+        }
+
+        let metrics = self
+            .native_store
+            .optimize_table(table_entry, Vec::new())
+            .await?;
+        debug!(
+            table = %table_entry.meta.name,
+            files_added = metrics.files_added,
+            files_removed = metrics.files_removed,
+            "compacted table"
+        );
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use datafusion::arrow::datatypes::DataType;
+    use datafusion::variable::VarType;
+    use datafusion_ext::vars::SessionVars;
     use datasources::native::access::NativeTableStorage;
     use metastore::local::start_inprocess_inmemory;
     use object_store_util::conf::StorageConfig;
@@ -116,7 +224,58 @@ mod tests {
         metastore::client::{MetastoreClientSupervisor, DEFAULT_METASTORE_CLIENT_CONFIG},
     };
 
-    use super::BackgroundJobStorageTracker;
+    use super::{BackgroundJobStorageTracker, TableCompaction};
+
+    fn test_table_entry() -> TableEntry {
+        TableEntry {
+            meta: EntryMeta {
+                entry_type: EntryType::Table,
+                id: 12345,
+                parent: 54321,
+                name: "table_1".to_string(),
+                builtin: false,
+                external: false,
+                is_temp: false,
+                comment: None,
+            },
+            options: TableOptions::Internal(TableOptionsInternal {
+                columns: vec![InternalColumnDefinition {
+                    name: "id".to_string(),
+                    nullable: true,
+                    arrow_type: DataType::Int32,
+                    default_expr: None,
+                    comment: None,
+                }],
+                partition_columns: Vec::new(),
+                primary_key: None,
+                unique_constraints: Vec::new(),
+                check_constraints: Vec::new(),
+                materialized_view: None,
+            }),
+            tunnel_id: None,
+        }
+    }
+
+    #[test]
+    fn test_table_compaction_from_vars() {
+        let table = test_table_entry();
+
+        let vars = SessionVars::default();
+        assert_eq!(None, TableCompaction::from_vars(&table, &vars));
+
+        vars.write()
+            .set("auto_compact_tables", "true", VarType::UserDefined)
+            .unwrap();
+        let compaction = TableCompaction::from_vars(&table, &vars).unwrap();
+        assert_eq!(16 * 1024 * 1024, compaction.small_file_bytes);
+        assert_eq!(32, compaction.small_file_threshold);
+
+        vars.write()
+            .set("compact_small_file_threshold", "4", VarType::UserDefined)
+            .unwrap();
+        let compaction = TableCompaction::from_vars(&table, &vars).unwrap();
+        assert_eq!(4, compaction.small_file_threshold);
+    }
 
     #[tokio::test]
     async fn test_background_job_storage_tracker() {
@@ -133,28 +292,7 @@ mod tests {
 
         // Add some tables inside the temp dir to get a non-zero storage size.
         storage
-            .create_table(
-                &TableEntry {
-                    meta: EntryMeta {
-                        entry_type: EntryType::Table,
-                        id: 12345,
-                        parent: 54321,
-                        name: "table_1".to_string(),
-                        builtin: false,
-                        external: false,
-                        is_temp: false,
-                    },
-                    options: TableOptions::Internal(TableOptionsInternal {
-                        columns: vec![InternalColumnDefinition {
-                            name: "id".to_string(),
-                            nullable: true,
-                            arrow_type: DataType::Int32,
-                        }],
-                    }),
-                    tunnel_id: None,
-                },
-                false,
-            )
+            .create_table(&test_table_entry(), false)
             .await
             .unwrap();
 
@@ -1,3 +1,4 @@
+use crate::background_jobs::materialized_views::MaterializedViewScheduler;
 use crate::background_jobs::JobRunner;
 use crate::environment::EnvironmentReader;
 use crate::errors::{internal, ExecError, Result};
@@ -7,6 +8,8 @@ use crate::parser::StatementWithExtensions;
 use crate::planner::logical_plan::*;
 use crate::planner::session_planner::SessionPlanner;
 use crate::remote::client::{RemoteClient, RemoteSessionClient};
+use crate::sequences::SessionSequences;
+use chrono::{DateTime, Utc};
 use datafusion::arrow::datatypes::{DataType, Field as ArrowField, Schema as ArrowSchema};
 use datafusion::common::SchemaReference;
 use datafusion::execution::context::{
@@ -75,6 +78,7 @@ impl LocalSessionContext {
         metrics: SessionMetrics,
         spill_path: Option<PathBuf>,
         background_jobs: JobRunner,
+        materialized_views: MaterializedViewScheduler,
     ) -> Result<LocalSessionContext> {
         let database_id = vars.database_id();
         let runtime = new_datafusion_runtime_env(&vars, &catalog, spill_path)?;
@@ -84,7 +88,10 @@ impl LocalSessionContext {
         conf = conf
             .with_extension(Arc::new(catalog_mutator))
             .with_extension(Arc::new(native_tables.clone()))
-            .with_extension(Arc::new(TempCatalog::default()));
+            .with_extension(Arc::new(TempCatalog::default()))
+            .with_extension(Arc::new(SessionSequences::default()))
+            .with_extension(Arc::new(materialized_views))
+            .with_extension(Arc::new(background_jobs.clone()));
         let state = SessionState::with_config_rt(conf, Arc::new(runtime))
             .add_physical_optimizer_rule(Arc::new(RuntimeGroupPullUp {}));
 
@@ -133,7 +140,8 @@ impl LocalSessionContext {
         conf = conf
             .with_extension(Arc::new(CatalogMutator::empty()))
             .with_extension(Arc::new(self.get_native_tables().clone()))
-            .with_extension(Arc::new(TempCatalog::default()));
+            .with_extension(Arc::new(TempCatalog::default()))
+            .with_extension(Arc::new(SessionSequences::default()));
         let state = SessionState::with_config_rt(conf, runtime)
             .add_physical_optimizer_rule(Arc::new(RuntimeGroupPullUp {}));
 
@@ -189,7 +197,15 @@ impl LocalSessionContext {
         self.exec_client.clone()
     }
 
-    fn catalog_mutator(&self) -> Arc<CatalogMutator> {
+    pub(crate) fn get_session_sequences(&self) -> Arc<SessionSequences> {
+        self.df_ctx
+            .state()
+            .config()
+            .get_extension::<SessionSequences>()
+            .expect("local contexts should have session sequences")
+    }
+
+    pub(crate) fn catalog_mutator(&self) -> Arc<CatalogMutator> {
         self.df_ctx
             .state()
             .config()
@@ -241,7 +257,31 @@ impl LocalSessionContext {
             ));
         }
 
-        let stmt = PreparedStatement::build(stmt, self).await?;
+        let stmt = PreparedStatement::build(stmt, Vec::new(), false, self).await?;
+        self.prepared.insert(name, stmt);
+
+        Ok(())
+    }
+
+    /// Create a named prepared statement from a SQL `PREPARE` statement.
+    pub async fn prepare_sql_statement(
+        &mut self,
+        name: String,
+        stmt: StatementWithExtensions,
+        parameter_types: Vec<DataType>,
+    ) -> Result<()> {
+        let mutator = self.catalog_mutator();
+        let client = mutator.get_metastore_client();
+
+        self.catalog
+            .maybe_refresh_state(client, self.get_session_vars().force_catalog_refresh())
+            .await?;
+
+        if self.prepared.contains_key(&name) {
+            return Err(ExecError::DuplicatePreparedStatement(name));
+        }
+
+        let stmt = PreparedStatement::build(Some(stmt), parameter_types, true, self).await?;
         self.prepared.insert(name, stmt);
 
         Ok(())
@@ -313,6 +353,16 @@ impl LocalSessionContext {
         self.prepared.remove(name);
     }
 
+    /// Remove all named prepared statements.
+    pub fn remove_all_prepared_statements(&mut self) {
+        self.prepared.retain(|name, _| name.is_empty());
+    }
+
+    /// Get all prepared statements for the session, keyed by name.
+    pub fn get_prepared_statements(&self) -> &HashMap<String, PreparedStatement> {
+        &self.prepared
+    }
+
     /// Remove a portal.
     pub fn remove_portal(&mut self, name: &str) {
         self.portals.remove(name);
@@ -394,6 +444,11 @@ pub struct PreparedStatement {
     pub(crate) output_schema: Option<ArrowSchema>,
     /// Output postgres types.
     pub(crate) output_pg_types: Vec<PgType>,
+    /// When the statement was prepared.
+    pub(crate) prepare_time: DateTime<Utc>,
+    /// Whether the statement was prepared with a SQL `PREPARE` rather than
+    /// through the extended query protocol.
+    pub(crate) from_sql: bool,
 }
 
 impl PreparedStatement {
@@ -401,11 +456,14 @@ impl PreparedStatement {
     // TODO: Not sure if we want to delay the planning portion.
     async fn build(
         mut stmt: Option<StatementWithExtensions>,
+        declared_types: Vec<DataType>,
+        from_sql: bool,
         ctx: &LocalSessionContext,
     ) -> Result<Self> {
+        let prepare_time = Utc::now();
         if let Some(inner) = stmt.take() {
             // Go ahead and plan using the session context.
-            let planner = SessionPlanner::new(ctx);
+            let planner = SessionPlanner::new(ctx).with_parameter_types(declared_types.clone());
             let plan = planner.plan_ast(inner.clone()).await?;
             let schema = plan.output_schema();
             let pg_types = match &schema {
@@ -421,7 +479,7 @@ impl PreparedStatement {
 
             // Convert inferred arrow types for parameters into their associated
             // pg type.
-            let parameter_types: HashMap<_, _> = plan
+            let mut parameter_types: HashMap<_, _> = plan
                 .get_parameter_types()?
                 .into_iter()
                 .map(|(id, arrow_type)| {
@@ -430,12 +488,23 @@ impl PreparedStatement {
                 })
                 .collect();
 
+            // Fall back to declared types for parameters that we weren't able
+            // to infer a type for, or that aren't referenced at all.
+            for (idx, typ) in declared_types.into_iter().enumerate() {
+                let param = parameter_types.entry(format!("${}", idx + 1)).or_default();
+                if param.is_none() {
+                    *param = Some((arrow_to_pg_type(&typ, None), typ));
+                }
+            }
+
             Ok(PreparedStatement {
                 stmt: Some(inner),
                 plan: Some(plan),
                 parameter_types: Some(parameter_types),
                 output_schema: schema,
                 output_pg_types: pg_types,
+                prepare_time,
+                from_sql,
             })
         } else {
             // No statement to plan.
@@ -445,6 +514,8 @@ impl PreparedStatement {
                 parameter_types: None,
                 output_schema: None,
                 output_pg_types: Vec::new(),
+                prepare_time,
+                from_sql,
             })
         }
     }
@@ -459,6 +530,11 @@ impl PreparedStatement {
         })
     }
 
+    /// Returns the output schema of the statement if it produces an output.
+    pub fn output_schema(&self) -> Option<&ArrowSchema> {
+        self.output_schema.as_ref()
+    }
+
     /// Returns the type of the input parameters. Input paramets are keyed as
     /// "$n" starting at "$1".
     pub fn input_paramaters(&self) -> Option<&HashMap<String, Option<(PgType, DataType)>>> {
@@ -2,17 +2,14 @@ use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
-use datafusion::common::{FileCompressionType, FileType};
-use datafusion::datasource::file_format::csv::CsvFormat;
-use datafusion::datasource::file_format::json::JsonFormat;
-use datafusion::datasource::file_format::parquet::ParquetFormat;
-use datafusion::datasource::file_format::FileFormat;
+use datafusion::common::FileCompressionType;
 use datafusion::datasource::TableProvider;
 use datafusion::execution::context::SessionState;
 use datafusion::prelude::SessionContext;
 use datafusion_ext::functions::{FuncParamValue, TableFuncContextProvider, VirtualLister};
 use datafusion_ext::vars::SessionVars;
 use datasources::bigquery::{BigQueryAccessor, BigQueryTableAccess};
+use datasources::clickhouse::{ClickhouseAccessor, ClickhouseTableAccess};
 use datasources::common::url::DatasourceUrl;
 use datasources::debug::DebugTableType;
 use datasources::lake::delta::access::{load_table_direct, DeltaLakeAccessor};
@@ -23,18 +20,22 @@ use datasources::object_store::gcs::GcsStoreAccess;
 use datasources::object_store::generic::GenericStoreAccess;
 use datasources::object_store::local::LocalStoreAccess;
 use datasources::object_store::s3::S3StoreAccess;
-use datasources::object_store::{ObjStoreAccess, ObjStoreAccessor};
+use datasources::object_store::{ObjStoreAccess, ObjStoreAccessor, ObjStoreFileType};
 use datasources::postgres::{PostgresAccess, PostgresTableProvider, PostgresTableProviderConfig};
 use datasources::snowflake::{SnowflakeAccessor, SnowflakeDbConnection, SnowflakeTableAccess};
+use datasources::sqlite::{SqliteAccessor, SqliteTableAccess};
+use datasources::sqlserver::{SqlServerAccessor, SqlServerTableAccess};
 use protogen::metastore::types::catalog::{
     CatalogEntry, CredentialsEntry, DatabaseEntry, FunctionEntry, TableEntry,
 };
 use protogen::metastore::types::options::{
-    DatabaseOptions, DatabaseOptionsBigQuery, DatabaseOptionsDebug, DatabaseOptionsDeltaLake,
-    DatabaseOptionsMongo, DatabaseOptionsMysql, DatabaseOptionsPostgres, DatabaseOptionsSnowflake,
-    TableOptions, TableOptionsBigQuery, TableOptionsDebug, TableOptionsGcs, TableOptionsInternal,
-    TableOptionsLocal, TableOptionsMongo, TableOptionsMysql, TableOptionsObjectStore,
-    TableOptionsPostgres, TableOptionsS3, TableOptionsSnowflake, TunnelOptions,
+    DatabaseOptions, DatabaseOptionsBigQuery, DatabaseOptionsClickhouse, DatabaseOptionsDebug,
+    DatabaseOptionsDeltaLake, DatabaseOptionsMongo, DatabaseOptionsMysql, DatabaseOptionsPostgres,
+    DatabaseOptionsSnowflake, DatabaseOptionsSqlServer, DatabaseOptionsSqlite, TableOptions,
+    TableOptionsBigQuery, TableOptionsClickhouse, TableOptionsDebug, TableOptionsGcs,
+    TableOptionsInternal, TableOptionsLocal, TableOptionsMongo, TableOptionsMysql,
+    TableOptionsObjectStore, TableOptionsPostgres, TableOptionsS3, TableOptionsSnowflake,
+    TableOptionsSqlServer, TableOptionsSqlite, TunnelOptions,
 };
 use sqlbuiltins::builtins::DEFAULT_CATALOG;
 use sqlbuiltins::functions::BUILTIN_TABLE_FUNCS;
@@ -230,6 +231,39 @@ impl<'a> ExternalDispatcher<'a> {
                 let table = accessor.load_table(schema, name).await?;
                 Ok(Arc::new(table))
             }
+            DatabaseOptions::SqlServer(DatabaseOptionsSqlServer { connection_string }) => {
+                let table_access = SqlServerTableAccess {
+                    schema: schema.to_string(),
+                    name: name.to_string(),
+                };
+
+                let accessor = SqlServerAccessor::connect(connection_string, tunnel).await?;
+                let provider = accessor.into_table_provider(table_access, true).await?;
+                Ok(Arc::new(provider))
+            }
+            DatabaseOptions::Clickhouse(DatabaseOptionsClickhouse { connection_string }) => {
+                let table_access = ClickhouseTableAccess {
+                    database: schema.to_string(), // A ClickHouse database is pretty much a schema.
+                    name: name.to_string(),
+                };
+
+                let accessor = ClickhouseAccessor::connect(connection_string)?;
+                let provider = accessor.into_table_provider(table_access, true).await?;
+                Ok(Arc::new(provider))
+            }
+            DatabaseOptions::Sqlite(DatabaseOptionsSqlite {
+                location,
+                storage_options,
+            }) => {
+                let table_access = SqliteTableAccess {
+                    schema: schema.to_string(),
+                    name: name.to_string(),
+                };
+
+                let accessor = SqliteAccessor::connect(location, storage_options.clone()).await?;
+                let provider = accessor.into_table_provider(table_access, true).await?;
+                Ok(Arc::new(provider))
+            }
         }
     }
 
@@ -418,6 +452,48 @@ impl<'a> ExternalDispatcher<'a> {
                 let reader = table.table_reader().await?;
                 Ok(reader)
             }
+            TableOptions::SqlServer(TableOptionsSqlServer {
+                connection_string,
+                schema,
+                table,
+            }) => {
+                let table_access = SqlServerTableAccess {
+                    schema: schema.clone(),
+                    name: table.clone(),
+                };
+
+                let accessor = SqlServerAccessor::connect(connection_string, tunnel).await?;
+                let provider = accessor.into_table_provider(table_access, true).await?;
+                Ok(Arc::new(provider))
+            }
+            TableOptions::Clickhouse(TableOptionsClickhouse {
+                connection_string,
+                database,
+                table,
+            }) => {
+                let table_access = ClickhouseTableAccess {
+                    database: database.clone(),
+                    name: table.clone(),
+                };
+
+                let accessor = ClickhouseAccessor::connect(connection_string)?;
+                let provider = accessor.into_table_provider(table_access, true).await?;
+                Ok(Arc::new(provider))
+            }
+            TableOptions::Sqlite(TableOptionsSqlite {
+                location,
+                table,
+                storage_options,
+            }) => {
+                let table_access = SqliteTableAccess {
+                    schema: SqliteTableAccess::DEFAULT_SCHEMA.to_string(),
+                    name: table.clone(),
+                };
+
+                let accessor = SqliteAccessor::connect(location, storage_options.clone()).await?;
+                let provider = accessor.into_table_provider(table_access, true).await?;
+                Ok(Arc::new(provider))
+            }
         }
     }
 
@@ -433,19 +509,8 @@ impl<'a> ExternalDispatcher<'a> {
             .transpose()?
             .unwrap_or(FileCompressionType::UNCOMPRESSED);
 
-        let ft: FileType = file_type.parse()?;
-        let ft: Arc<dyn FileFormat> = match ft {
-            FileType::CSV => Arc::new(
-                CsvFormat::default()
-                    .with_file_compression_type(compression)
-                    .with_schema_infer_max_rec(Some(20480)),
-            ),
-            FileType::PARQUET => Arc::new(ParquetFormat::default()),
-            FileType::JSON => {
-                Arc::new(JsonFormat::default().with_file_compression_type(compression))
-            }
-            _ => return Err(DispatchError::InvalidDispatch("Unsupported file type")),
-        };
+        let ft: ObjStoreFileType = file_type.parse()?;
+        let ft = ft.file_format(compression);
 
         let accessor = ObjStoreAccessor::new(access)?;
         let objects = accessor.list_globbed(location).await?;
@@ -16,13 +16,15 @@ use datafusion_ext::functions::{FuncParamValue, TableFuncContextProvider, Virtua
 use datafusion_ext::vars::SessionVars;
 use datasources::native::access::NativeTableStorage;
 use protogen::metastore::types::catalog::{
-    CatalogEntry, CredentialsEntry, DatabaseEntry, EntryMeta, EntryType, FunctionEntry, ViewEntry,
+    CatalogEntry, CredentialsEntry, DatabaseEntry, EntryMeta, EntryType, FunctionEntry, Privilege,
+    ViewEntry,
 };
 use sqlbuiltins::functions::BUILTIN_TABLE_FUNCS;
 
-use crate::context::local::LocalSessionContext;
+use crate::context::local::{LocalSessionContext, PreparedStatement};
 use crate::parser::CustomParser;
 use crate::planner::errors::PlanError;
+use crate::planner::privileges::PrivilegeChecker;
 use crate::planner::session_planner::SessionPlanner;
 use crate::{
     dispatch::system::SystemTableDispatcher,
@@ -87,6 +89,12 @@ pub enum DispatchError {
     #[error(transparent)]
     SnowflakeDatasource(#[from] datasources::snowflake::errors::DatasourceSnowflakeError),
     #[error(transparent)]
+    SqlServerDatasource(#[from] datasources::sqlserver::errors::SqlServerError),
+    #[error(transparent)]
+    ClickhouseDatasource(#[from] datasources::clickhouse::errors::ClickhouseError),
+    #[error(transparent)]
+    SqliteDatasource(#[from] datasources::sqlite::errors::SqliteError),
+    #[error(transparent)]
     DeltaDatasource(#[from] datasources::lake::delta::errors::DeltaError),
     #[error(transparent)]
     IcebergDatasource(#[from] datasources::lake::iceberg::errors::IcebergError),
@@ -168,6 +176,7 @@ pub struct Dispatcher<'a> {
     tables: &'a NativeTableStorage,
     metrics: &'a SessionMetrics,
     temp_objects: &'a TempCatalog,
+    prepared: &'a HashMap<String, PreparedStatement>,
     view_planner: &'a dyn ViewPlanner,
     // TODO: Remove need for this.
     df_ctx: &'a DfSessionContext,
@@ -176,11 +185,13 @@ pub struct Dispatcher<'a> {
 }
 
 impl<'a> Dispatcher<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         catalog: &'a SessionCatalog,
         tables: &'a NativeTableStorage,
         metrics: &'a SessionMetrics,
         temp_objects: &'a TempCatalog,
+        prepared: &'a HashMap<String, PreparedStatement>,
         view_planner: &'a dyn ViewPlanner,
         df_ctx: &'a DfSessionContext,
         disable_local_fs_access: bool,
@@ -190,6 +201,7 @@ impl<'a> Dispatcher<'a> {
             tables,
             metrics,
             temp_objects,
+            prepared,
             view_planner,
             df_ctx,
             disable_local_fs_access,
@@ -217,10 +229,13 @@ impl<'a> Dispatcher<'a> {
                 Ok(provider)
             }
             // Dispatch to builtin tables.
-            CatalogEntry::Table(tbl) if tbl.meta.builtin => {
-                SystemTableDispatcher::new(self.catalog, self.metrics, self.temp_objects)
-                    .dispatch(&tbl)
-            }
+            CatalogEntry::Table(tbl) if tbl.meta.builtin => SystemTableDispatcher::new(
+                self.catalog,
+                self.metrics,
+                self.temp_objects,
+                self.prepared,
+            )
+            .dispatch(&tbl),
             // Dispatch to external tables.
             CatalogEntry::Table(tbl) if tbl.meta.external => {
                 ExternalDispatcher::new(self.catalog, self.df_ctx, self.disable_local_fs_access)
@@ -283,6 +298,11 @@ impl<'a> TableFuncContextProvider for Dispatcher<'a> {
     }
 
     fn get_credentials_entry(&self, name: &str) -> Option<&CredentialsEntry> {
+        // Credentials live directly in the database, so using them requires
+        // USAGE on the database.
+        PrivilegeChecker::new(self.catalog, &self.get_session_vars().user_name())
+            .check_default_database(Privilege::Usage)
+            .ok()?;
         self.catalog.resolve_credentials(name)
     }
 
@@ -1,22 +1,32 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use datafusion::arrow::array::{
-    BooleanBuilder, ListBuilder, StringBuilder, UInt32Builder, UInt64Builder,
+    BooleanBuilder, Int32Builder, ListBuilder, StringBuilder, TimestampMicrosecondBuilder,
+    UInt32Builder, UInt64Builder,
 };
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::datasource::{MemTable, TableProvider};
 use datasources::common::ssh::key::SshKey;
 use datasources::common::ssh::SshConnectionParameters;
 use protogen::metastore::types::catalog::{CatalogEntry, EntryType, TableEntry};
-use protogen::metastore::types::options::TunnelOptions;
+use protogen::metastore::types::options::{
+    CredentialsOptions, DatabaseOptions, DeltaLakeCatalog, SecretOptions, StorageOptions,
+    TableOptions, TunnelOptions,
+};
+use serde_json::{json, Value};
 use sqlbuiltins::builtins::{
-    DATABASE_DEFAULT, GLARE_COLUMNS, GLARE_CREDENTIALS, GLARE_DATABASES, GLARE_DEPLOYMENT_METADATA,
-    GLARE_FUNCTIONS, GLARE_SCHEMAS, GLARE_SESSION_QUERY_METRICS, GLARE_SSH_KEYS, GLARE_TABLES,
-    GLARE_TUNNELS, GLARE_VIEWS, SCHEMA_CURRENT_SESSION,
+    DATABASE_DEFAULT, GLARE_AUDIT_LOG, GLARE_COLUMNS, GLARE_CONSTRAINTS, GLARE_CREDENTIALS,
+    GLARE_DATABASES, GLARE_DEPLOYMENT_METADATA, GLARE_FUNCTIONS, GLARE_MATERIALIZED_VIEWS,
+    GLARE_PREPARED_STATEMENTS, GLARE_PRIVILEGES, GLARE_QUERY_HISTORY, GLARE_ROLES, GLARE_SCHEMAS,
+    GLARE_SESSIONS, GLARE_SESSION_QUERY_METRICS, GLARE_SSH_KEYS, GLARE_TABLES, GLARE_TUNNELS,
+    GLARE_VIEWS, SCHEMA_CURRENT_SESSION,
 };
 
+use crate::context::local::PreparedStatement;
 use crate::metastore::catalog::{SessionCatalog, TempCatalog};
 use crate::metrics::SessionMetrics;
+use crate::planner::privileges::PrivilegeChecker;
 
 use super::{DispatchError, Result};
 
@@ -25,6 +35,7 @@ pub struct SystemTableDispatcher<'a> {
     catalog: &'a SessionCatalog,
     metrics: &'a SessionMetrics,
     temp_objects: &'a TempCatalog,
+    prepared: &'a HashMap<String, PreparedStatement>,
 }
 
 impl<'a> SystemTableDispatcher<'a> {
@@ -32,11 +43,13 @@ impl<'a> SystemTableDispatcher<'a> {
         catalog: &'a SessionCatalog,
         metrics: &'a SessionMetrics,
         temp_objects: &'a TempCatalog,
+        prepared: &'a HashMap<String, PreparedStatement>,
     ) -> Self {
         SystemTableDispatcher {
             catalog,
             metrics,
             temp_objects,
+            prepared,
         }
     }
 
@@ -58,8 +71,12 @@ impl<'a> SystemTableDispatcher<'a> {
             Arc::new(self.build_glare_tables())
         } else if GLARE_COLUMNS.matches(schema, name) {
             Arc::new(self.build_glare_columns())
+        } else if GLARE_CONSTRAINTS.matches(schema, name) {
+            Arc::new(self.build_glare_constraints())
         } else if GLARE_VIEWS.matches(schema, name) {
             Arc::new(self.build_glare_views())
+        } else if GLARE_MATERIALIZED_VIEWS.matches(schema, name) {
+            Arc::new(self.build_glare_materialized_views())
         } else if GLARE_SCHEMAS.matches(schema, name) {
             Arc::new(self.build_glare_schemas())
         } else if GLARE_FUNCTIONS.matches(schema, name) {
@@ -70,6 +87,18 @@ impl<'a> SystemTableDispatcher<'a> {
             Arc::new(self.build_ssh_keys()?)
         } else if GLARE_DEPLOYMENT_METADATA.matches(schema, name) {
             Arc::new(self.build_glare_deployment_metadata()?)
+        } else if GLARE_ROLES.matches(schema, name) {
+            Arc::new(self.build_glare_roles())
+        } else if GLARE_PRIVILEGES.matches(schema, name) {
+            Arc::new(self.build_glare_privileges())
+        } else if GLARE_AUDIT_LOG.matches(schema, name) {
+            Arc::new(self.build_glare_audit_log())
+        } else if GLARE_QUERY_HISTORY.matches(schema, name) {
+            Arc::new(self.build_glare_query_history())
+        } else if GLARE_SESSIONS.matches(schema, name) {
+            Arc::new(self.build_glare_sessions())
+        } else if GLARE_PREPARED_STATEMENTS.matches(schema, name) {
+            Arc::new(self.build_glare_prepared_statements())
         } else {
             return Err(DispatchError::MissingBuiltinTable {
                 schema: schema.to_string(),
@@ -86,6 +115,8 @@ impl<'a> SystemTableDispatcher<'a> {
         let mut builtin = BooleanBuilder::new();
         let mut external = BooleanBuilder::new();
         let mut datasource = StringBuilder::new();
+        let mut options = StringBuilder::new();
+        let mut comment = StringBuilder::new();
 
         for db in self
             .catalog
@@ -96,6 +127,7 @@ impl<'a> SystemTableDispatcher<'a> {
             database_name.append_value(&db.entry.get_meta().name);
             builtin.append_value(db.builtin);
             external.append_value(db.entry.get_meta().external);
+            comment.append_option(db.entry.get_meta().comment.as_ref());
 
             let db = match db.entry {
                 CatalogEntry::Database(db) => db,
@@ -103,6 +135,7 @@ impl<'a> SystemTableDispatcher<'a> {
             };
 
             datasource.append_value(db.options.as_str());
+            options.append_value(database_options_json(&db.options.redacted()).to_string());
         }
 
         let batch = RecordBatch::try_new(
@@ -113,6 +146,8 @@ impl<'a> SystemTableDispatcher<'a> {
                 Arc::new(builtin.finish()),
                 Arc::new(external.finish()),
                 Arc::new(datasource.finish()),
+                Arc::new(options.finish()),
+                Arc::new(comment.finish()),
             ],
         )
         .unwrap();
@@ -165,6 +200,7 @@ impl<'a> SystemTableDispatcher<'a> {
         let mut builtin = BooleanBuilder::new();
         let mut provider = StringBuilder::new();
         let mut comment = StringBuilder::new();
+        let mut options = StringBuilder::new();
 
         for creds in self
             .catalog
@@ -182,6 +218,7 @@ impl<'a> SystemTableDispatcher<'a> {
 
             provider.append_value(creds.options.as_str());
             comment.append_value(&creds.comment);
+            options.append_value(credentials_options_json(&creds.options.redacted()).to_string());
         }
 
         let batch = RecordBatch::try_new(
@@ -192,6 +229,111 @@ impl<'a> SystemTableDispatcher<'a> {
                 Arc::new(builtin.finish()),
                 Arc::new(provider.finish()),
                 Arc::new(comment.finish()),
+                Arc::new(options.finish()),
+            ],
+        )
+        .unwrap();
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
+
+    fn build_glare_roles(&self) -> MemTable {
+        let arrow_schema = Arc::new(GLARE_ROLES.arrow_schema());
+
+        let mut oid = UInt32Builder::new();
+        let mut role_name = StringBuilder::new();
+        let mut builtin = BooleanBuilder::new();
+        let mut superuser = BooleanBuilder::new();
+        let mut login = BooleanBuilder::new();
+
+        for role in self
+            .catalog
+            .iter_entries()
+            .filter(|ent| ent.entry_type() == EntryType::Role)
+        {
+            oid.append_value(role.oid);
+            role_name.append_value(&role.entry.get_meta().name);
+            builtin.append_value(role.builtin);
+
+            let role = match role.entry {
+                CatalogEntry::Role(role) => role,
+                other => unreachable!("unexpected entry type: {other:?}"),
+            };
+
+            superuser.append_value(role.superuser);
+            login.append_value(role.login);
+        }
+
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(oid.finish()),
+                Arc::new(role_name.finish()),
+                Arc::new(builtin.finish()),
+                Arc::new(superuser.finish()),
+                Arc::new(login.finish()),
+            ],
+        )
+        .unwrap();
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
+
+    fn build_glare_privileges(&self) -> MemTable {
+        let arrow_schema = Arc::new(GLARE_PRIVILEGES.arrow_schema());
+
+        let mut role_oid = UInt32Builder::new();
+        let mut role_name = StringBuilder::new();
+        let mut object_oid = UInt32Builder::new();
+        let mut object_type = StringBuilder::new();
+        let mut schema_name = StringBuilder::new();
+        let mut object_name = StringBuilder::new();
+        let mut privilege = StringBuilder::new();
+
+        for role in self
+            .catalog
+            .iter_entries()
+            .filter(|ent| ent.entry_type() == EntryType::Role)
+        {
+            let role = match role.entry {
+                CatalogEntry::Role(role) => role,
+                other => unreachable!("unexpected entry type: {other:?}"),
+            };
+
+            for grant in &role.grants {
+                // Objects may have been dropped since the grant was made.
+                let object = match self.catalog.get_by_oid(grant.object_id) {
+                    Some(object) => object.get_meta(),
+                    None => continue,
+                };
+                let schema = match object.entry_type {
+                    EntryType::Table | EntryType::View => self
+                        .catalog
+                        .get_by_oid(object.parent)
+                        .map(|schema| schema.get_meta().name.as_str()),
+                    _ => None,
+                };
+
+                for priv_type in &grant.privileges {
+                    role_oid.append_value(role.meta.id);
+                    role_name.append_value(&role.meta.name);
+                    object_oid.append_value(object.id);
+                    object_type.append_value(object.entry_type.as_str());
+                    schema_name.append_option(schema);
+                    object_name.append_value(&object.name);
+                    privilege.append_value(priv_type.as_str());
+                }
+            }
+        }
+
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(role_oid.finish()),
+                Arc::new(role_name.finish()),
+                Arc::new(object_oid.finish()),
+                Arc::new(object_type.finish()),
+                Arc::new(schema_name.finish()),
+                Arc::new(object_name.finish()),
+                Arc::new(privilege.finish()),
             ],
         )
         .unwrap();
@@ -206,6 +348,7 @@ impl<'a> SystemTableDispatcher<'a> {
         let mut database_name = StringBuilder::new();
         let mut schema_name = StringBuilder::new();
         let mut builtin = BooleanBuilder::new();
+        let mut comment = StringBuilder::new();
 
         for schema in self
             .catalog
@@ -222,6 +365,7 @@ impl<'a> SystemTableDispatcher<'a> {
             );
             schema_name.append_value(&schema.entry.get_meta().name);
             builtin.append_value(schema.builtin);
+            comment.append_option(schema.entry.get_meta().comment.as_ref());
         }
         let batch = RecordBatch::try_new(
             arrow_schema.clone(),
@@ -231,6 +375,7 @@ impl<'a> SystemTableDispatcher<'a> {
                 Arc::new(database_name.finish()),
                 Arc::new(schema_name.finish()),
                 Arc::new(builtin.finish()),
+                Arc::new(comment.finish()),
             ],
         )
         .unwrap();
@@ -248,6 +393,7 @@ impl<'a> SystemTableDispatcher<'a> {
         let mut builtin = BooleanBuilder::new();
         let mut external = BooleanBuilder::new();
         let mut datasource = StringBuilder::new();
+        let mut comment = StringBuilder::new();
 
         for table in self
             .catalog
@@ -271,6 +417,7 @@ impl<'a> SystemTableDispatcher<'a> {
             table_name.append_value(&table.entry.get_meta().name);
             builtin.append_value(table.builtin);
             external.append_value(table.entry.get_meta().external);
+            comment.append_option(table.entry.get_meta().comment.as_ref());
 
             let table = match table.entry {
                 CatalogEntry::Table(table) => table,
@@ -291,6 +438,7 @@ impl<'a> SystemTableDispatcher<'a> {
             builtin.append_value(table.meta.builtin);
             external.append_value(table.meta.external);
             datasource.append_value(table.options.as_str());
+            comment.append_option(table.meta.comment);
         }
 
         let batch = RecordBatch::try_new(
@@ -304,6 +452,7 @@ impl<'a> SystemTableDispatcher<'a> {
                 Arc::new(builtin.finish()),
                 Arc::new(external.finish()),
                 Arc::new(datasource.finish()),
+                Arc::new(comment.finish()),
             ],
         )
         .unwrap();
@@ -321,6 +470,8 @@ impl<'a> SystemTableDispatcher<'a> {
         let mut column_ordinal = UInt32Builder::new();
         let mut data_type = StringBuilder::new();
         let mut is_nullable = BooleanBuilder::new();
+        let mut column_default = StringBuilder::new();
+        let mut comment = StringBuilder::new();
 
         for table in self
             .catalog
@@ -350,6 +501,8 @@ impl<'a> SystemTableDispatcher<'a> {
                 column_ordinal.append_value(i as u32);
                 data_type.append_value(col.arrow_type.to_string());
                 is_nullable.append_value(col.nullable);
+                column_default.append_option(col.default_expr.as_ref());
+                comment.append_option(col.comment.as_ref());
             }
         }
 
@@ -363,6 +516,93 @@ impl<'a> SystemTableDispatcher<'a> {
                 Arc::new(column_ordinal.finish()),
                 Arc::new(data_type.finish()),
                 Arc::new(is_nullable.finish()),
+                Arc::new(column_default.finish()),
+                Arc::new(comment.finish()),
+            ],
+        )
+        .unwrap();
+
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
+
+    fn build_glare_constraints(&self) -> MemTable {
+        let arrow_schema = Arc::new(GLARE_CONSTRAINTS.arrow_schema());
+
+        let mut schema_oid = UInt32Builder::new();
+        let mut table_oid = UInt32Builder::new();
+        let mut schema_name = StringBuilder::new();
+        let mut table_name = StringBuilder::new();
+        let mut constraint_name = StringBuilder::new();
+        let mut constraint_type = StringBuilder::new();
+        let mut column_name = StringBuilder::new();
+        let mut ordinal_position = UInt32Builder::new();
+        let mut check_expr = StringBuilder::new();
+
+        for table in self
+            .catalog
+            .iter_entries()
+            .filter(|ent| ent.entry_type() == EntryType::Table)
+        {
+            let ent = match table.entry {
+                CatalogEntry::Table(ent) => ent,
+                other => panic!("unexpected entry type: {:?}", other), // Bug
+            };
+
+            let options = match &ent.options {
+                TableOptions::Internal(options) => options,
+                _ => continue,
+            };
+
+            let (parent_oid, parent_name) = table
+                .parent_entry
+                .map(|ent| (ent.get_meta().id, ent.get_meta().name.as_str()))
+                .unwrap_or_default();
+
+            // Key constraints get a row per column.
+            let keys = options
+                .primary_key
+                .iter()
+                .map(|key| (key, "PRIMARY KEY"))
+                .chain(options.unique_constraints.iter().map(|key| (key, "UNIQUE")));
+            for (key, key_type) in keys {
+                for (i, col) in key.columns.iter().enumerate() {
+                    schema_oid.append_value(parent_oid);
+                    table_oid.append_value(table.oid);
+                    schema_name.append_value(parent_name);
+                    table_name.append_value(&ent.meta.name);
+                    constraint_name.append_value(&key.name);
+                    constraint_type.append_value(key_type);
+                    column_name.append_value(col);
+                    ordinal_position.append_value(i as u32 + 1);
+                    check_expr.append_null();
+                }
+            }
+
+            for check in &options.check_constraints {
+                schema_oid.append_value(parent_oid);
+                table_oid.append_value(table.oid);
+                schema_name.append_value(parent_name);
+                table_name.append_value(&ent.meta.name);
+                constraint_name.append_value(&check.name);
+                constraint_type.append_value("CHECK");
+                column_name.append_null();
+                ordinal_position.append_null();
+                check_expr.append_value(&check.expr);
+            }
+        }
+
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(schema_oid.finish()),
+                Arc::new(table_oid.finish()),
+                Arc::new(schema_name.finish()),
+                Arc::new(table_name.finish()),
+                Arc::new(constraint_name.finish()),
+                Arc::new(constraint_type.finish()),
+                Arc::new(column_name.finish()),
+                Arc::new(ordinal_position.finish()),
+                Arc::new(check_expr.finish()),
             ],
         )
         .unwrap();
@@ -380,6 +620,7 @@ impl<'a> SystemTableDispatcher<'a> {
         let mut view_name = StringBuilder::new();
         let mut builtin = BooleanBuilder::new();
         let mut sql = StringBuilder::new();
+        let mut comment = StringBuilder::new();
 
         for view in self
             .catalog
@@ -406,6 +647,7 @@ impl<'a> SystemTableDispatcher<'a> {
             view_name.append_value(&view.entry.get_meta().name);
             builtin.append_value(view.builtin);
             sql.append_value(&ent.sql);
+            comment.append_option(ent.meta.comment.as_ref());
         }
 
         let batch = RecordBatch::try_new(
@@ -418,6 +660,67 @@ impl<'a> SystemTableDispatcher<'a> {
                 Arc::new(view_name.finish()),
                 Arc::new(builtin.finish()),
                 Arc::new(sql.finish()),
+                Arc::new(comment.finish()),
+            ],
+        )
+        .unwrap();
+
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
+
+    fn build_glare_materialized_views(&self) -> MemTable {
+        let arrow_schema = Arc::new(GLARE_MATERIALIZED_VIEWS.arrow_schema());
+
+        let mut oid = UInt32Builder::new();
+        let mut schema_oid = UInt32Builder::new();
+        let mut schema_name = StringBuilder::new();
+        let mut view_name = StringBuilder::new();
+        let mut sql = StringBuilder::new();
+        let mut refresh_interval_secs = UInt64Builder::new();
+        let mut last_refreshed = TimestampMicrosecondBuilder::new().with_timezone("UTC");
+
+        for table in self
+            .catalog
+            .iter_entries()
+            .filter(|ent| ent.entry_type() == EntryType::Table)
+        {
+            let ent = match table.entry {
+                CatalogEntry::Table(ent) => ent,
+                other => panic!("unexpected entry type: {:?}", other), // Bug
+            };
+
+            let view = match &ent.options {
+                TableOptions::Internal(options) => match &options.materialized_view {
+                    Some(view) => view,
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            oid.append_value(table.oid);
+            schema_oid.append_value(ent.meta.parent);
+            schema_name.append_value(
+                table
+                    .parent_entry
+                    .map(|schema| schema.get_meta().name.as_str())
+                    .unwrap_or("<invalid>"),
+            );
+            view_name.append_value(&ent.meta.name);
+            sql.append_value(&view.sql);
+            refresh_interval_secs.append_option(view.refresh_interval_secs);
+            last_refreshed.append_option(view.last_refreshed);
+        }
+
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(oid.finish()),
+                Arc::new(schema_oid.finish()),
+                Arc::new(schema_name.finish()),
+                Arc::new(view_name.finish()),
+                Arc::new(sql.finish()),
+                Arc::new(refresh_interval_secs.finish()),
+                Arc::new(last_refreshed.finish()),
             ],
         )
         .unwrap();
@@ -451,10 +754,14 @@ impl<'a> SystemTableDispatcher<'a> {
             function_name.append_value(&ent.meta.name);
             function_type.append_value(ent.func_type.as_str());
 
-            // TODO: Actually get parameter info.
-            const EMPTY: [Option<&'static str>; 0] = [];
-            parameters.append_value(EMPTY);
-            parameter_types.append_value(EMPTY);
+            // TODO: Actually get parameter info for builtin functions.
+            let params = ent
+                .sql_function
+                .as_ref()
+                .map(|func| func.params.as_slice())
+                .unwrap_or_default();
+            parameters.append_value(params.iter().map(|p| Some(p.name.clone())));
+            parameter_types.append_value(params.iter().map(|p| Some(p.arrow_type.to_string())));
 
             builtin.append_value(func.builtin);
         }
@@ -511,6 +818,271 @@ impl<'a> SystemTableDispatcher<'a> {
         MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
     }
 
+    fn build_glare_audit_log(&self) -> MemTable {
+        let mut records = match self.metrics.audit_log() {
+            Some(log) => log.recent_records(),
+            None => Vec::new(),
+        };
+
+        // The audit log is shared by all databases, only show statements
+        // executed in this database.
+        records.retain(|record| record.database_id == self.metrics.database_id());
+
+        // Only unrestricted sessions may see statements executed by other
+        // users.
+        if !PrivilegeChecker::new(self.catalog, self.metrics.user_name()).is_unrestricted() {
+            records.retain(|record| record.user_name == self.metrics.user_name());
+        }
+
+        let mut timestamp =
+            TimestampMicrosecondBuilder::with_capacity(records.len()).with_timezone("UTC");
+        let mut user_name = StringBuilder::new();
+        let mut connection_id = StringBuilder::new();
+        let mut statement = StringBuilder::new();
+        let mut objects = ListBuilder::new(StringBuilder::new());
+        let mut result_type = StringBuilder::new();
+        let mut execution_status = StringBuilder::new();
+        let mut error_message = StringBuilder::new();
+        let mut output_rows = UInt64Builder::with_capacity(records.len());
+        let mut output_bytes = UInt64Builder::with_capacity(records.len());
+        let mut duration_ms = UInt64Builder::with_capacity(records.len());
+
+        for record in records {
+            timestamp.append_value(record.timestamp.timestamp_micros());
+            user_name.append_value(&record.user_name);
+            connection_id.append_value(record.connection_id.to_string());
+            statement.append_value(&record.statement);
+            for object in &record.objects {
+                objects.values().append_value(object);
+            }
+            objects.append(true);
+            result_type.append_value(record.result_type);
+            execution_status.append_value(record.status);
+            error_message.append_option(record.error.as_ref());
+            output_rows.append_option(record.rows);
+            output_bytes.append_option(record.bytes);
+            duration_ms.append_value(record.duration_ms);
+        }
+
+        let arrow_schema = Arc::new(GLARE_AUDIT_LOG.arrow_schema());
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(timestamp.finish()),
+                Arc::new(user_name.finish()),
+                Arc::new(connection_id.finish()),
+                Arc::new(statement.finish()),
+                Arc::new(objects.finish()),
+                Arc::new(result_type.finish()),
+                Arc::new(execution_status.finish()),
+                Arc::new(error_message.finish()),
+                Arc::new(output_rows.finish()),
+                Arc::new(output_bytes.finish()),
+                Arc::new(duration_ms.finish()),
+            ],
+        )
+        .unwrap();
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
+
+    fn build_glare_query_history(&self) -> MemTable {
+        let mut records = self.metrics.query_history().history().records();
+
+        // The history is shared by all databases, only show queries executed
+        // in this database.
+        records.retain(|record| record.database_id == self.metrics.database_id());
+
+        // Only unrestricted sessions may see queries executed by other users.
+        if !PrivilegeChecker::new(self.catalog, self.metrics.user_name()).is_unrestricted() {
+            records.retain(|record| record.user_name == self.metrics.user_name());
+        }
+
+        let mut timestamp =
+            TimestampMicrosecondBuilder::with_capacity(records.len()).with_timezone("UTC");
+        let mut user_name = StringBuilder::new();
+        let mut connection_id = StringBuilder::new();
+        let mut query_text = StringBuilder::new();
+        let mut plan_hash = UInt64Builder::with_capacity(records.len());
+        let mut result_type = StringBuilder::new();
+        let mut execution_status = StringBuilder::new();
+        let mut error_message = StringBuilder::new();
+        let mut duration_ms = UInt64Builder::with_capacity(records.len());
+        let mut elapsed_compute_ns = UInt64Builder::with_capacity(records.len());
+        let mut output_rows = UInt64Builder::with_capacity(records.len());
+        let mut bytes_read = UInt64Builder::with_capacity(records.len());
+        let mut rows_read = UInt64Builder::with_capacity(records.len());
+        let mut datasources = ListBuilder::new(StringBuilder::new());
+        let mut datasource_bytes_read = ListBuilder::new(UInt64Builder::new());
+        let mut datasource_rows_read = ListBuilder::new(UInt64Builder::new());
+
+        for record in records {
+            timestamp.append_value(record.timestamp.timestamp_micros());
+            user_name.append_value(&record.user_name);
+            connection_id.append_value(record.connection_id.to_string());
+            query_text.append_value(&record.query_text);
+            plan_hash.append_option(record.plan_hash);
+            result_type.append_value(&record.result_type);
+            execution_status.append_value(&record.execution_status);
+            error_message.append_option(record.error_message.as_ref());
+            duration_ms.append_value(record.duration_ms);
+            elapsed_compute_ns.append_option(record.elapsed_compute_ns);
+            output_rows.append_option(record.output_rows);
+            bytes_read.append_value(record.bytes_read_by_source.values().sum());
+            rows_read.append_value(record.rows_read_by_source.values().sum());
+
+            for (source, bytes) in &record.bytes_read_by_source {
+                datasources.values().append_value(source);
+                datasource_bytes_read.values().append_value(*bytes);
+                datasource_rows_read.values().append_value(
+                    record
+                        .rows_read_by_source
+                        .get(source)
+                        .copied()
+                        .unwrap_or_default(),
+                );
+            }
+            datasources.append(true);
+            datasource_bytes_read.append(true);
+            datasource_rows_read.append(true);
+        }
+
+        let arrow_schema = Arc::new(GLARE_QUERY_HISTORY.arrow_schema());
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(timestamp.finish()),
+                Arc::new(user_name.finish()),
+                Arc::new(connection_id.finish()),
+                Arc::new(query_text.finish()),
+                Arc::new(plan_hash.finish()),
+                Arc::new(result_type.finish()),
+                Arc::new(execution_status.finish()),
+                Arc::new(error_message.finish()),
+                Arc::new(duration_ms.finish()),
+                Arc::new(elapsed_compute_ns.finish()),
+                Arc::new(output_rows.finish()),
+                Arc::new(bytes_read.finish()),
+                Arc::new(rows_read.finish()),
+                Arc::new(datasources.finish()),
+                Arc::new(datasource_bytes_read.finish()),
+                Arc::new(datasource_rows_read.finish()),
+            ],
+        )
+        .unwrap();
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
+
+    fn build_glare_sessions(&self) -> MemTable {
+        // Only sessions connected to the same database are shown.
+        let session = self.metrics.activity();
+        let activity = session.registry().activity(session.database_id());
+
+        // Only unrestricted sessions may see statements executed by other
+        // users.
+        let user = self.metrics.user_name();
+        let unrestricted = PrivilegeChecker::new(self.catalog, user).is_unrestricted();
+
+        let mut pid = Int32Builder::with_capacity(activity.len());
+        let mut user_name = StringBuilder::new();
+        let mut connection_id = StringBuilder::new();
+        let mut database_name = StringBuilder::new();
+        let mut application_name = StringBuilder::new();
+        let mut backend_start =
+            TimestampMicrosecondBuilder::with_capacity(activity.len()).with_timezone("UTC");
+        let mut state = StringBuilder::new();
+        let mut query = StringBuilder::new();
+        let mut query_start =
+            TimestampMicrosecondBuilder::with_capacity(activity.len()).with_timezone("UTC");
+
+        for session in activity {
+            pid.append_value(session.pid);
+            user_name.append_value(&session.user_name);
+            connection_id.append_value(session.connection_id.to_string());
+            database_name.append_value(&session.database_name);
+            application_name.append_value(&session.application_name);
+            backend_start.append_value(session.backend_start.timestamp_micros());
+            state.append_value(session.state);
+            if unrestricted || session.user_name == user {
+                query.append_option(session.query);
+                query_start.append_option(session.query_start.map(|t| t.timestamp_micros()));
+            } else {
+                query.append_value("<insufficient privilege>");
+                query_start.append_null();
+            }
+        }
+
+        let arrow_schema = Arc::new(GLARE_SESSIONS.arrow_schema());
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(pid.finish()),
+                Arc::new(user_name.finish()),
+                Arc::new(connection_id.finish()),
+                Arc::new(database_name.finish()),
+                Arc::new(application_name.finish()),
+                Arc::new(backend_start.finish()),
+                Arc::new(state.finish()),
+                Arc::new(query.finish()),
+                Arc::new(query_start.finish()),
+            ],
+        )
+        .unwrap();
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
+
+    fn build_glare_prepared_statements(&self) -> MemTable {
+        // The unnamed prepared statement is only used by the extended query
+        // protocol and isn't shown, matching Postgres.
+        let mut prepared: Vec<_> = self
+            .prepared
+            .iter()
+            .filter(|(name, _)| !name.is_empty())
+            .collect();
+        prepared.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut name = StringBuilder::new();
+        let mut statement = StringBuilder::new();
+        let mut prepare_time =
+            TimestampMicrosecondBuilder::with_capacity(prepared.len()).with_timezone("UTC");
+        let mut parameter_types = ListBuilder::new(StringBuilder::new());
+        let mut from_sql = BooleanBuilder::new();
+
+        for (stmt_name, stmt) in prepared {
+            name.append_value(stmt_name);
+            statement.append_option(stmt.stmt.as_ref().map(|s| s.to_string()));
+            prepare_time.append_value(stmt.prepare_time.timestamp_micros());
+
+            // Parameters are keyed as "$n", order them by their position.
+            let mut params: Vec<_> = stmt
+                .input_paramaters()
+                .map(|params| params.iter().collect())
+                .unwrap_or_default();
+            params.sort_by_key(|(id, _)| id[1..].parse::<usize>().unwrap_or_default());
+            parameter_types.append_value(params.into_iter().map(|(_, typ)| {
+                Some(match typ {
+                    Some((pg_type, _)) => pg_type.name().to_string(),
+                    None => "unknown".to_string(),
+                })
+            }));
+
+            from_sql.append_value(stmt.from_sql);
+        }
+
+        let arrow_schema = Arc::new(GLARE_PREPARED_STATEMENTS.arrow_schema());
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(name.finish()),
+                Arc::new(statement.finish()),
+                Arc::new(prepare_time.finish()),
+                Arc::new(parameter_types.finish()),
+                Arc::new(from_sql.finish()),
+            ],
+        )
+        .unwrap();
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
+
     fn build_ssh_keys(&self) -> Result<MemTable> {
         let arrow_schema = Arc::new(GLARE_SSH_KEYS.arrow_schema());
 
@@ -576,3 +1148,71 @@ impl<'a> SystemTableDispatcher<'a> {
         Ok(MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap())
     }
 }
+
+/// Get database options as a JSON object for display in system tables.
+fn database_options_json(opts: &DatabaseOptions) -> Value {
+    match opts {
+        DatabaseOptions::Internal(_) | DatabaseOptions::Debug(_) => json!({}),
+        DatabaseOptions::Postgres(opts) => json!({ "connection_string": opts.connection_string }),
+        DatabaseOptions::BigQuery(opts) => json!({
+            "service_account_key": opts.service_account_key,
+            "project_id": opts.project_id,
+        }),
+        DatabaseOptions::Mysql(opts) => json!({ "connection_string": opts.connection_string }),
+        DatabaseOptions::Mongo(opts) => json!({ "connection_string": opts.connection_string }),
+        DatabaseOptions::Snowflake(opts) => json!({
+            "account_name": opts.account_name,
+            "login_name": opts.login_name,
+            "password": opts.password,
+            "database_name": opts.database_name,
+            "warehouse": opts.warehouse,
+            "role_name": opts.role_name,
+        }),
+        DatabaseOptions::Delta(opts) => {
+            let catalog = match &opts.catalog {
+                DeltaLakeCatalog::Unity(unity) => json!({
+                    "unity": {
+                        "catalog_id": unity.catalog_id,
+                        "databricks_access_token": unity.databricks_access_token,
+                        "workspace_url": unity.workspace_url,
+                    }
+                }),
+            };
+            json!({
+                "catalog": catalog,
+                "storage_options": storage_options_json(&opts.storage_options),
+            })
+        }
+        DatabaseOptions::SqlServer(opts) => json!({ "connection_string": opts.connection_string }),
+        DatabaseOptions::Clickhouse(opts) => {
+            json!({ "connection_string": opts.connection_string })
+        }
+        DatabaseOptions::Sqlite(opts) => json!({
+            "location": opts.location,
+            "storage_options": storage_options_json(&opts.storage_options),
+        }),
+    }
+}
+
+/// Get credentials options as a JSON object for display in system tables.
+fn credentials_options_json(opts: &CredentialsOptions) -> Value {
+    match opts {
+        CredentialsOptions::Debug(opts) => json!({ "table_type": opts.table_type }),
+        CredentialsOptions::Gcp(opts) => {
+            json!({ "service_account_key": opts.service_account_key })
+        }
+        CredentialsOptions::Aws(opts) => json!({
+            "access_key_id": opts.access_key_id,
+            "secret_access_key": opts.secret_access_key,
+        }),
+    }
+}
+
+fn storage_options_json(opts: &StorageOptions) -> Value {
+    Value::Object(
+        opts.inner
+            .iter()
+            .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+            .collect(),
+    )
+}
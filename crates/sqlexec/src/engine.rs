@@ -1,9 +1,17 @@
+use crate::activity::SessionRegistry;
+use crate::audit::AuditLog;
+use crate::background_jobs::materialized_views::MaterializedViewScheduler;
 use crate::background_jobs::JobRunner;
 use crate::context::remote::RemoteSessionContext;
 use crate::errors::{ExecError, Result};
+use crate::limits::QueryLimiter;
 use crate::metastore::client::{MetastoreClientSupervisor, DEFAULT_METASTORE_CLIENT_CONFIG};
+use crate::metastore::secrets::SecretCipher;
+use crate::planner::privileges::{verify_password, LoginRole, RoleAuthentication};
+use crate::query_history::{QueryHistory, QueryHistoryConfig, DEFAULT_QUERY_HISTORY_SIZE};
 use crate::session::Session;
-use std::collections::HashMap;
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
 
 use object_store::aws::AmazonS3ConfigKey;
 use std::fs;
@@ -22,6 +30,7 @@ use metastore::util::MetastoreClientMode;
 use object_store_util::conf::StorageConfig;
 use object_store_util::shared::SharedObjectStore;
 use protogen::gen::metastore::service::metastore_service_client::MetastoreServiceClient;
+use telemetry::metrics::METRICS;
 use telemetry::Tracker;
 use tonic::transport::Channel;
 use tracing::{debug, info};
@@ -216,6 +225,17 @@ pub struct Engine {
     session_counter: Arc<AtomicU64>,
     /// Background jobs to run.
     background_jobs: JobRunner,
+    /// Limits the number of concurrently executing queries across all
+    /// sessions.
+    query_limiter: QueryLimiter,
+    /// Audit log for statements executed in all sessions.
+    audit_log: Option<Arc<AuditLog>>,
+    /// History of queries executed in all sessions.
+    query_history: Arc<QueryHistory>,
+    /// Activity of all local sessions.
+    sessions: Arc<SessionRegistry>,
+    /// Databases with scheduled materialized view refreshes.
+    scheduled_databases: Mutex<HashSet<Uuid>>,
 }
 
 impl Engine {
@@ -233,6 +253,11 @@ impl Engine {
             spill_path,
             session_counter: Arc::new(AtomicU64::new(0)),
             background_jobs: JobRunner::new(Default::default()),
+            query_limiter: QueryLimiter::unlimited(),
+            audit_log: None,
+            query_history: Arc::new(QueryHistory::new(DEFAULT_QUERY_HISTORY_SIZE)),
+            sessions: Arc::new(SessionRegistry::new()),
+            scheduled_databases: Mutex::new(HashSet::new()),
         })
     }
 
@@ -284,6 +309,44 @@ impl Engine {
         self
     }
 
+    /// Encrypt secrets stored in the catalog using the provided cipher.
+    pub fn with_secret_cipher(mut self, cipher: Option<Arc<SecretCipher>>) -> Engine {
+        self.supervisor = self.supervisor.with_secret_cipher(cipher);
+        self
+    }
+
+    /// Limit the number of concurrently executing queries.
+    pub fn with_query_limiter(mut self, query_limiter: QueryLimiter) -> Engine {
+        self.query_limiter = query_limiter;
+        self
+    }
+
+    /// Record executed statements to an audit log.
+    pub fn with_audit_log(mut self, audit_log: Option<Arc<AuditLog>>) -> Engine {
+        self.audit_log = audit_log;
+        self
+    }
+
+    /// Configure the history of queries executed across all sessions.
+    ///
+    /// If persisted, the history is periodically written to the engine's
+    /// storage and loaded back in on startup.
+    pub async fn with_query_history(mut self, conf: QueryHistoryConfig) -> Result<Engine> {
+        self.query_history = if conf.persist {
+            let store = self
+                .storage
+                .storage_config(&SessionStorageConfig::default())?
+                .new_object_store()?;
+            Arc::new(
+                QueryHistory::new_persisted(conf.max_records, store, self.background_jobs.clone())
+                    .await?,
+            )
+        } else {
+            Arc::new(QueryHistory::new(conf.max_records))
+        };
+        Ok(self)
+    }
+
     /// Attempts to shutdown the engine gracefully.
     pub async fn shutdown(&self) -> Result<()> {
         self.background_jobs.close().await?;
@@ -295,6 +358,83 @@ impl Engine {
         self.session_counter.load(Ordering::Relaxed)
     }
 
+    /// Get the registry of all local sessions.
+    pub fn sessions(&self) -> &Arc<SessionRegistry> {
+        &self.sessions
+    }
+
+    /// Authenticate a user against their login role in a database.
+    ///
+    /// Once roles exist, users must log in as a role that's allowed to log in.
+    /// Roles with a password are authenticated with that password instead of
+    /// the server's authenticator. Front ends that ask for the password
+    /// separately can pass `None` to check if a password is required first.
+    ///
+    /// Every front end must call this before creating a session.
+    pub async fn authenticate_role(
+        &self,
+        database_id: Uuid,
+        user: &str,
+        password: Option<&str>,
+    ) -> Result<RoleAuthentication> {
+        let metastore = self.supervisor.init_client(database_id).await?;
+        let state = metastore.get_cached_state().await?;
+        let catalog = SessionCatalog::new(state);
+
+        match LoginRole::for_user(&catalog, user) {
+            LoginRole::NoRoles => Ok(RoleAuthentication::Authenticator),
+            LoginRole::Missing => Err(ExecError::MissingLoginRole(user.to_string())),
+            LoginRole::Role(role) if !role.login => {
+                Err(ExecError::RoleCannotLogin(user.to_string()))
+            }
+            LoginRole::Role(role) => match (role.password_hash, password) {
+                (None, _) => Ok(RoleAuthentication::Authenticator),
+                (Some(_), None) => Ok(RoleAuthentication::PasswordRequired),
+                (Some(hash), Some(password)) if verify_password(&hash, password) => {
+                    Ok(RoleAuthentication::Authenticated)
+                }
+                (Some(_), Some(_)) => Err(ExecError::InvalidUserOrPassword),
+            },
+        }
+    }
+
+    /// Schedule refreshes for all materialized views in a database.
+    ///
+    /// Scheduled refreshes don't survive restarts, so this needs to happen
+    /// once for each database after startup. Does nothing if refreshes for
+    /// the database have already been scheduled.
+    pub async fn schedule_materialized_views(
+        &self,
+        database_id: Uuid,
+        storage: &SessionStorageConfig,
+    ) -> Result<()> {
+        if self.scheduled_databases.lock().contains(&database_id) {
+            return Ok(());
+        }
+
+        let metastore = self.supervisor.init_client(database_id).await?;
+        let native = self
+            .storage
+            .new_native_tables_storage(database_id, storage)?;
+        let state = metastore.get_cached_state().await?;
+
+        let scheduler = MaterializedViewScheduler {
+            catalog_mutator: metastore.into(),
+            native_tables: native,
+            tracker: self.tracker.clone(),
+            spill_path: self.spill_path.clone(),
+            background_jobs: self.background_jobs.clone(),
+            query_limiter: self.query_limiter.clone(),
+            audit_log: self.audit_log.clone(),
+            query_history: self.query_history.clone(),
+            sessions: self.sessions.clone(),
+        };
+        scheduler.schedule_all(&SessionCatalog::new(state));
+
+        self.scheduled_databases.lock().insert(database_id);
+        Ok(())
+    }
+
     /// Create a new local session, initializing it with the provided session
     /// variables.
     pub async fn new_local_session_context(
@@ -303,6 +443,11 @@ impl Engine {
         storage: SessionStorageConfig,
     ) -> Result<TrackedSession> {
         let database_id = vars.database_id();
+        // Databases served by a shared metastore aren't known until a session
+        // connects to them.
+        self.schedule_materialized_views(database_id, &storage)
+            .await?;
+
         let metastore = self.supervisor.init_client(database_id).await?;
         let native = self
             .storage
@@ -319,10 +464,15 @@ impl Engine {
             self.tracker.clone(),
             self.spill_path.clone(),
             self.background_jobs.clone(),
+            self.query_limiter.clone(),
+            self.audit_log.clone(),
+            self.query_history.clone(),
+            self.sessions.clone(),
         )?;
 
         let prev = self.session_counter.fetch_add(1, Ordering::Relaxed);
         debug!(session_count = prev + 1, "new session opened");
+        METRICS.active_connections.inc();
 
         Ok(TrackedSession {
             inner: session,
@@ -382,6 +532,7 @@ impl Drop for TrackedSession {
     fn drop(&mut self) {
         let prev = self.session_counter.fetch_sub(1, Ordering::Relaxed);
         debug!(session_counter = prev - 1, "session closed");
+        METRICS.active_connections.dec();
     }
 }
 
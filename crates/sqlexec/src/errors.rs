@@ -24,9 +24,21 @@ pub enum ExecError {
     #[error("Unknown prepared statement with name: {0}")]
     UnknownPreparedStatement(String),
 
+    #[error("Prepared statement already exists: {0}")]
+    DuplicatePreparedStatement(String),
+
     #[error("Unknown portal with name: {0}")]
     UnknownPortal(String),
 
+    #[error("Role '{0}' does not exist")]
+    MissingLoginRole(String),
+
+    #[error("Role '{0}' is not permitted to log in")]
+    RoleCannotLogin(String),
+
+    #[error("Invalid user or password")]
+    InvalidUserOrPassword,
+
     #[error("Empty search path, unable to resolve schema")]
     EmptySearchPath,
 
@@ -81,6 +93,9 @@ pub enum ExecError {
     #[error(transparent)]
     ObjectStore(#[from] object_store::Error),
 
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+
     #[error(transparent)]
     VarError(#[from] std::env::VarError),
 
@@ -103,6 +118,27 @@ pub enum ExecError {
         current: usize,
     },
 
+    #[error("Canceling statement due to statement timeout of {timeout_ms} ms")]
+    StatementTimeout { timeout_ms: u64 },
+
+    #[error("Query read more than {limit} bytes from data sources (max_scan_bytes)")]
+    MaxScanBytesExceeded { limit: u64 },
+
+    #[error("Query returned more than {limit} rows (max_result_rows)")]
+    MaxResultRowsExceeded { limit: u64 },
+
+    #[error("Canceling statement due to user request")]
+    QueryCanceled,
+
+    #[error("Terminating connection due to administrator command")]
+    SessionTerminated,
+
+    #[error("Too many concurrent queries, timed out after waiting {waited_ms} ms for one of {max_concurrent} query slots")]
+    QueryQueueTimeout {
+        max_concurrent: usize,
+        waited_ms: u64,
+    },
+
     #[error("Invalid storage configuration: {0}")]
     InvalidStorageConfig(&'static str),
 
@@ -166,6 +202,27 @@ pub enum ExecError {
 
 pub type Result<T, E = ExecError> = std::result::Result<T, E>;
 
+impl ExecError {
+    /// Check if the error is from a user failing to authenticate as their
+    /// login role.
+    pub fn is_authentication_error(&self) -> bool {
+        matches!(
+            self,
+            ExecError::MissingLoginRole(_)
+                | ExecError::RoleCannotLogin(_)
+                | ExecError::InvalidUserOrPassword
+        )
+    }
+
+    /// Check if the error is from the session's role lacking a privilege.
+    pub fn is_permission_denied(&self) -> bool {
+        matches!(
+            self,
+            ExecError::PlanError(crate::planner::errors::PlanError::Privilege(_))
+        )
+    }
+}
+
 #[allow(unused_macros)]
 macro_rules! internal {
     ($($arg:tt)*) => {
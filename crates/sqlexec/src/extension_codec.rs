@@ -25,17 +25,24 @@ use datafusion_proto::physical_plan::PhysicalExtensionCodec;
 use protogen::metastore::types::catalog::RuntimePreference;
 use uuid::Uuid;
 
+use crate::background_jobs::storage::TableCompaction;
 use crate::errors::ExecError;
 use crate::planner::extension::{ExtensionNode, ExtensionType, PhysicalExtensionNode};
 use crate::planner::logical_plan as plan;
 use crate::planner::physical_plan::alter_database_rename::AlterDatabaseRenameExec;
 use crate::planner::physical_plan::alter_table_rename::AlterTableRenameExec;
 use crate::planner::physical_plan::alter_tunnel_rotate_keys::AlterTunnelRotateKeysExec;
+use crate::planner::physical_plan::comment_on::CommentOnExec;
+use crate::planner::physical_plan::constraint_check::{ConstraintCheck, UniqueCheck};
 use crate::planner::physical_plan::copy_to::CopyToExec;
 use crate::planner::physical_plan::create_credentials::CreateCredentialsExec;
 use crate::planner::physical_plan::create_external_database::CreateExternalDatabaseExec;
+use crate::planner::physical_plan::create_external_database_table::CreateExternalDatabaseTableExec;
 use crate::planner::physical_plan::create_external_table::CreateExternalTableExec;
+use crate::planner::physical_plan::create_function::CreateFunctionExec;
+use crate::planner::physical_plan::create_role::CreateRoleExec;
 use crate::planner::physical_plan::create_schema::CreateSchemaExec;
+use crate::planner::physical_plan::create_sequence::CreateSequenceExec;
 use crate::planner::physical_plan::create_table::CreateTableExec;
 use crate::planner::physical_plan::create_temp_table::CreateTempTableExec;
 use crate::planner::physical_plan::create_tunnel::CreateTunnelExec;
@@ -43,15 +50,23 @@ use crate::planner::physical_plan::create_view::CreateViewExec;
 use crate::planner::physical_plan::delete::DeleteExec;
 use crate::planner::physical_plan::drop_credentials::DropCredentialsExec;
 use crate::planner::physical_plan::drop_database::DropDatabaseExec;
+use crate::planner::physical_plan::drop_functions::DropFunctionsExec;
+use crate::planner::physical_plan::drop_roles::DropRolesExec;
 use crate::planner::physical_plan::drop_schemas::DropSchemasExec;
+use crate::planner::physical_plan::drop_sequences::DropSequencesExec;
 use crate::planner::physical_plan::drop_tables::DropTablesExec;
 use crate::planner::physical_plan::drop_tunnel::DropTunnelExec;
 use crate::planner::physical_plan::drop_views::DropViewsExec;
+use crate::planner::physical_plan::grant_privileges::GrantPrivilegesExec;
 use crate::planner::physical_plan::insert::InsertExec;
+use crate::planner::physical_plan::optimize_table::OptimizeTableExec;
+use crate::planner::physical_plan::refresh_materialized_view::RefreshMaterializedViewExec;
 use crate::planner::physical_plan::remote_scan::ProviderReference;
+use crate::planner::physical_plan::revoke_privileges::RevokePrivilegesExec;
 use crate::planner::physical_plan::set_var::SetVarExec;
 use crate::planner::physical_plan::show_var::ShowVarExec;
 use crate::planner::physical_plan::update::UpdateExec;
+use crate::planner::physical_plan::vacuum_table::VacuumTableExec;
 use crate::planner::physical_plan::values::ExtValuesExec;
 use crate::planner::physical_plan::{
     client_recv::ClientExchangeRecvExec, remote_scan::RemoteScanExec,
@@ -219,6 +234,87 @@ impl<'a> LogicalExtensionCodec for GlareDBExtensionCodec<'a> {
 
                 drop_credentials.into_extension()
             }
+            PlanType::CreateRole(create_role) => {
+                let create_role = plan::CreateRole::try_decode(create_role, ctx, self)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                create_role.into_extension()
+            }
+            PlanType::DropRoles(drop_roles) => {
+                let drop_roles = plan::DropRoles::try_decode(drop_roles, ctx, self)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                drop_roles.into_extension()
+            }
+            PlanType::GrantPrivileges(grant_privileges) => {
+                let grant_privileges =
+                    plan::GrantPrivileges::try_decode(grant_privileges, ctx, self)
+                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                grant_privileges.into_extension()
+            }
+            PlanType::RevokePrivileges(revoke_privileges) => {
+                let revoke_privileges =
+                    plan::RevokePrivileges::try_decode(revoke_privileges, ctx, self)
+                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                revoke_privileges.into_extension()
+            }
+            PlanType::OptimizeTable(optimize_table) => {
+                let optimize_table = plan::OptimizeTable::try_decode(optimize_table, ctx, self)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                optimize_table.into_extension()
+            }
+            PlanType::VacuumTable(vacuum_table) => {
+                let vacuum_table = plan::VacuumTable::try_decode(vacuum_table, ctx, self)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                vacuum_table.into_extension()
+            }
+            PlanType::RefreshMaterializedView(refresh) => {
+                let refresh = plan::RefreshMaterializedView::try_decode(refresh, ctx, self)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                refresh.into_extension()
+            }
+            PlanType::CreateFunction(create_function) => {
+                let create_function = plan::CreateFunction::try_decode(create_function, ctx, self)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                create_function.into_extension()
+            }
+            PlanType::DropFunctions(drop_functions) => {
+                let drop_functions = plan::DropFunctions::try_decode(drop_functions, ctx, self)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                drop_functions.into_extension()
+            }
+            PlanType::CreateSequence(create_sequence) => {
+                let create_sequence = plan::CreateSequence::try_decode(create_sequence, ctx, self)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                create_sequence.into_extension()
+            }
+            PlanType::DropSequences(drop_sequences) => {
+                let drop_sequences = plan::DropSequences::try_decode(drop_sequences, ctx, self)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                drop_sequences.into_extension()
+            }
+            PlanType::CommentOn(comment_on) => {
+                let comment_on = plan::CommentOn::try_decode(comment_on, ctx, self)
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                comment_on.into_extension()
+            }
+            PlanType::CreateExternalDatabaseTable(create_table) => {
+                let create_table =
+                    plan::CreateExternalDatabaseTable::try_decode(create_table, ctx, self)
+                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                create_table.into_extension()
+            }
             PlanType::DropDatabase(drop_database) => {
                 let drop_database = plan::DropDatabase::try_decode(drop_database, ctx, self)
                     .map_err(|e| DataFusionError::External(Box::new(e)))?;
@@ -299,6 +395,14 @@ impl<'a> LogicalExtensionCodec for GlareDBExtensionCodec<'a> {
             ExtensionType::DropDatabase => {
                 plan::DropDatabase::try_encode_extension(node, buf, self)
             }
+            ExtensionType::CreateRole => plan::CreateRole::try_encode_extension(node, buf, self),
+            ExtensionType::DropRoles => plan::DropRoles::try_encode_extension(node, buf, self),
+            ExtensionType::GrantPrivileges => {
+                plan::GrantPrivileges::try_encode_extension(node, buf, self)
+            }
+            ExtensionType::RevokePrivileges => {
+                plan::RevokePrivileges::try_encode_extension(node, buf, self)
+            }
             ExtensionType::DropSchemas => plan::DropSchemas::try_encode_extension(node, buf, self),
             ExtensionType::DropTunnel => plan::DropTunnel::try_encode_extension(node, buf, self),
             ExtensionType::DropViews => plan::DropViews::try_encode_extension(node, buf, self),
@@ -310,6 +414,29 @@ impl<'a> LogicalExtensionCodec for GlareDBExtensionCodec<'a> {
             ExtensionType::Update => plan::Update::try_encode_extension(node, buf, self),
             ExtensionType::Delete => plan::Update::try_encode_extension(node, buf, self),
             ExtensionType::Insert => plan::Insert::try_encode_extension(node, buf, self),
+            ExtensionType::OptimizeTable => {
+                plan::OptimizeTable::try_encode_extension(node, buf, self)
+            }
+            ExtensionType::VacuumTable => plan::VacuumTable::try_encode_extension(node, buf, self),
+            ExtensionType::RefreshMaterializedView => {
+                plan::RefreshMaterializedView::try_encode_extension(node, buf, self)
+            }
+            ExtensionType::CreateFunction => {
+                plan::CreateFunction::try_encode_extension(node, buf, self)
+            }
+            ExtensionType::DropFunctions => {
+                plan::DropFunctions::try_encode_extension(node, buf, self)
+            }
+            ExtensionType::CreateSequence => {
+                plan::CreateSequence::try_encode_extension(node, buf, self)
+            }
+            ExtensionType::DropSequences => {
+                plan::DropSequences::try_encode_extension(node, buf, self)
+            }
+            ExtensionType::CommentOn => plan::CommentOn::try_encode_extension(node, buf, self),
+            ExtensionType::CreateExternalDatabaseTable => {
+                plan::CreateExternalDatabaseTable::try_encode_extension(node, buf, self)
+            }
         }
         .map_err(|e| DataFusionError::External(Box::new(e)))?;
         Ok(())
@@ -492,6 +619,9 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     .arrow_schema
                     .ok_or(DataFusionError::Plan("schema is required".to_string()))?;
                 let schema: Schema = (&schema).try_into()?;
+                let options = ext.options.ok_or(protogen::ProtoConvError::RequiredField(
+                    "options".to_string(),
+                ))?;
 
                 Arc::new(CreateTableExec {
                     catalog_version: ext.catalog_version,
@@ -505,6 +635,8 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     or_replace: ext.or_replace,
                     arrow_schema: Arc::new(schema),
                     source: inputs.get(0).cloned(),
+                    options: options.try_into()?,
+                    sequences: ext.sequences.into_iter().map(Into::into).collect(),
                 })
             }
             proto::ExecutionPlanExtensionType::CreateTempTableExec(ext) => {
@@ -606,6 +738,39 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     if_exists: ext.if_exists,
                 })
             }
+            proto::ExecutionPlanExtensionType::CreateRoleExec(ext) => Arc::new(CreateRoleExec {
+                catalog_version: ext.catalog_version,
+                name: ext.name,
+                if_not_exists: ext.if_not_exists,
+                superuser: ext.superuser,
+                login: ext.login,
+                password_hash: ext.password_hash,
+            }),
+            proto::ExecutionPlanExtensionType::DropRolesExec(ext) => Arc::new(DropRolesExec {
+                catalog_version: ext.catalog_version,
+                names: ext.names,
+                if_exists: ext.if_exists,
+            }),
+            proto::ExecutionPlanExtensionType::GrantPrivilegesExec(ext) => {
+                Arc::new(GrantPrivilegesExec {
+                    catalog_version: ext.catalog_version,
+                    grants: ext
+                        .grants
+                        .into_iter()
+                        .map(|g| g.try_into())
+                        .collect::<Result<_, _>>()?,
+                })
+            }
+            proto::ExecutionPlanExtensionType::RevokePrivilegesExec(ext) => {
+                Arc::new(RevokePrivilegesExec {
+                    catalog_version: ext.catalog_version,
+                    revokes: ext
+                        .revokes
+                        .into_iter()
+                        .map(|r| r.try_into())
+                        .collect::<Result<_, _>>()?,
+                })
+            }
             proto::ExecutionPlanExtensionType::DropTablesExec(ext) => Arc::new(DropTablesExec {
                 catalog_version: ext.catalog_version,
                 tbl_references: ext.tbl_references.into_iter().map(|r| r.into()).collect(),
@@ -638,6 +803,8 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                         .try_into()?,
                     updates,
                     where_expr,
+                    checks: decode_constraint_checks(ext.checks, registry)?,
+                    unique_checks: decode_unique_checks(ext.unique_checks),
                 })
             }
             proto::ExecutionPlanExtensionType::InsertExec(ext) => {
@@ -663,6 +830,23 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                             DataFusionError::Internal("missing input source".to_string())
                         })?
                         .clone(),
+                    checks: decode_constraint_checks(ext.checks, registry)?,
+                    unique_checks: decode_unique_checks(ext.unique_checks),
+                    compaction: ext
+                        .compaction
+                        .map(|compaction| -> Result<_> {
+                            Ok(TableCompaction {
+                                table: compaction
+                                    .table
+                                    .ok_or_else(|| {
+                                        DataFusionError::Internal("missing table".to_string())
+                                    })?
+                                    .try_into()?,
+                                small_file_bytes: compaction.small_file_bytes as usize,
+                                small_file_threshold: compaction.small_file_threshold as usize,
+                            })
+                        })
+                        .transpose()?,
                 })
             }
             proto::ExecutionPlanExtensionType::DeleteExec(ext) => {
@@ -678,6 +862,98 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     where_expr,
                 })
             }
+            proto::ExecutionPlanExtensionType::OptimizeTableExec(ext) => {
+                Arc::new(OptimizeTableExec {
+                    table: ext
+                        .table
+                        .ok_or_else(|| DataFusionError::Internal("missing table".to_string()))?
+                        .try_into()?,
+                    zorder_by: ext.zorder_by,
+                })
+            }
+            proto::ExecutionPlanExtensionType::VacuumTableExec(ext) => Arc::new(VacuumTableExec {
+                table: ext
+                    .table
+                    .ok_or_else(|| DataFusionError::Internal("missing table".to_string()))?
+                    .try_into()?,
+                retain_hours: ext.retain_hours,
+            }),
+            proto::ExecutionPlanExtensionType::RefreshMaterializedViewExec(ext) => {
+                Arc::new(RefreshMaterializedViewExec {
+                    catalog_version: ext.catalog_version,
+                    schema: ext.schema,
+                    table: ext
+                        .table
+                        .ok_or_else(|| DataFusionError::Internal("missing table".to_string()))?
+                        .try_into()?,
+                    source: inputs
+                        .get(0)
+                        .cloned()
+                        .ok_or_else(|| DataFusionError::Internal("missing source".to_string()))?,
+                })
+            }
+            proto::ExecutionPlanExtensionType::CreateFunctionExec(ext) => {
+                Arc::new(CreateFunctionExec {
+                    catalog_version: ext.catalog_version,
+                    function: ext
+                        .function
+                        .ok_or_else(|| DataFusionError::Internal("missing function".to_string()))?
+                        .try_into()?,
+                })
+            }
+            proto::ExecutionPlanExtensionType::DropFunctionsExec(ext) => {
+                Arc::new(DropFunctionsExec {
+                    catalog_version: ext.catalog_version,
+                    drops: ext.drops.into_iter().map(Into::into).collect(),
+                })
+            }
+            proto::ExecutionPlanExtensionType::CreateSequenceExec(ext) => {
+                Arc::new(CreateSequenceExec {
+                    catalog_version: ext.catalog_version,
+                    sequence: ext
+                        .sequence
+                        .ok_or_else(|| DataFusionError::Internal("missing sequence".to_string()))?
+                        .into(),
+                })
+            }
+            proto::ExecutionPlanExtensionType::DropSequencesExec(ext) => {
+                Arc::new(DropSequencesExec {
+                    catalog_version: ext.catalog_version,
+                    sequence_references: ext.references.into_iter().map(|r| r.into()).collect(),
+                    if_exists: ext.if_exists,
+                })
+            }
+            proto::ExecutionPlanExtensionType::CommentOnExec(ext) => Arc::new(CommentOnExec {
+                catalog_version: ext.catalog_version,
+                comment: ext
+                    .comment
+                    .ok_or_else(|| DataFusionError::Internal("missing comment".to_string()))?
+                    .try_into()?,
+            }),
+            proto::ExecutionPlanExtensionType::CreateExternalDatabaseTableExec(ext) => {
+                let schema = ext
+                    .arrow_schema
+                    .ok_or(DataFusionError::Plan("schema is required".to_string()))?;
+                let schema: Schema = (&schema).try_into()?;
+                let options = ext.options.ok_or(protogen::ProtoConvError::RequiredField(
+                    "options".to_string(),
+                ))?;
+
+                Arc::new(CreateExternalDatabaseTableExec {
+                    tbl_reference: ext
+                        .tbl_reference
+                        .ok_or_else(|| {
+                            DataFusionError::Internal("missing table references".to_string())
+                        })?
+                        .into(),
+                    if_not_exists: ext.if_not_exists,
+                    or_replace: ext.or_replace,
+                    arrow_schema: Arc::new(schema),
+                    source: inputs.get(0).cloned(),
+                    options: options.try_into()?,
+                    tunnel: ext.tunnel.map(|t| t.try_into()).transpose()?,
+                })
+            }
             proto::ExecutionPlanExtensionType::CopyToExec(ext) => Arc::new(CopyToExec {
                 format: ext
                     .format
@@ -798,6 +1074,8 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                 if_not_exists: exec.if_not_exists,
                 or_replace: exec.or_replace,
                 arrow_schema: Some(exec.arrow_schema.clone().try_into()?),
+                options: Some(exec.options.clone().try_into()?),
+                sequences: exec.sequences.iter().cloned().map(Into::into).collect(),
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<CreateTempTableExec>() {
             proto::ExecutionPlanExtensionType::CreateTempTableExec(proto::CreateTempTableExec {
@@ -906,6 +1184,31 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                 names: exec.names.clone(),
                 if_exists: exec.if_exists,
             })
+        } else if let Some(exec) = node.as_any().downcast_ref::<CreateRoleExec>() {
+            proto::ExecutionPlanExtensionType::CreateRoleExec(proto::CreateRoleExec {
+                catalog_version: exec.catalog_version,
+                name: exec.name.clone(),
+                if_not_exists: exec.if_not_exists,
+                superuser: exec.superuser,
+                login: exec.login,
+                password_hash: exec.password_hash.clone(),
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<DropRolesExec>() {
+            proto::ExecutionPlanExtensionType::DropRolesExec(proto::DropRolesExec {
+                catalog_version: exec.catalog_version,
+                names: exec.names.clone(),
+                if_exists: exec.if_exists,
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<GrantPrivilegesExec>() {
+            proto::ExecutionPlanExtensionType::GrantPrivilegesExec(proto::GrantPrivilegesExec {
+                catalog_version: exec.catalog_version,
+                grants: exec.grants.iter().cloned().map(Into::into).collect(),
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<RevokePrivilegesExec>() {
+            proto::ExecutionPlanExtensionType::RevokePrivilegesExec(proto::RevokePrivilegesExec {
+                catalog_version: exec.catalog_version,
+                revokes: exec.revokes.iter().cloned().map(Into::into).collect(),
+            })
         } else if let Some(exec) = node.as_any().downcast_ref::<DropTablesExec>() {
             proto::ExecutionPlanExtensionType::DropTablesExec(proto::DropTablesExec {
                 catalog_version: exec.catalog_version,
@@ -943,6 +1246,8 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     .as_ref()
                     .map(|expr| expr.try_into())
                     .transpose()?,
+                checks: encode_constraint_checks(&exec.checks)?,
+                unique_checks: encode_unique_checks(&exec.unique_checks),
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<InsertExec>() {
             let id = match exec.provider {
@@ -956,6 +1261,19 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
 
             proto::ExecutionPlanExtensionType::InsertExec(proto::InsertExec {
                 provider_id: id.into_bytes().to_vec(),
+                checks: encode_constraint_checks(&exec.checks)?,
+                unique_checks: encode_unique_checks(&exec.unique_checks),
+                compaction: exec
+                    .compaction
+                    .as_ref()
+                    .map(|compaction| -> Result<_> {
+                        Ok(proto::TableCompaction {
+                            table: Some(compaction.table.clone().try_into()?),
+                            small_file_bytes: compaction.small_file_bytes as u64,
+                            small_file_threshold: compaction.small_file_threshold as u64,
+                        })
+                    })
+                    .transpose()?,
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<DeleteExec>() {
             proto::ExecutionPlanExtensionType::DeleteExec(proto::DeleteExec {
@@ -966,6 +1284,69 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     .map(|expr| expr.try_into())
                     .transpose()?,
             })
+        } else if let Some(exec) = node.as_any().downcast_ref::<OptimizeTableExec>() {
+            proto::ExecutionPlanExtensionType::OptimizeTableExec(proto::OptimizeTableExec {
+                table: Some(exec.table.clone().try_into()?),
+                zorder_by: exec.zorder_by.clone(),
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<VacuumTableExec>() {
+            proto::ExecutionPlanExtensionType::VacuumTableExec(proto::VacuumTableExec {
+                table: Some(exec.table.clone().try_into()?),
+                retain_hours: exec.retain_hours,
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<RefreshMaterializedViewExec>() {
+            proto::ExecutionPlanExtensionType::RefreshMaterializedViewExec(
+                proto::RefreshMaterializedViewExec {
+                    catalog_version: exec.catalog_version,
+                    table: Some(exec.table.clone().try_into()?),
+                    schema: exec.schema.clone(),
+                },
+            )
+        } else if let Some(exec) = node.as_any().downcast_ref::<CreateFunctionExec>() {
+            proto::ExecutionPlanExtensionType::CreateFunctionExec(proto::CreateFunctionExec {
+                catalog_version: exec.catalog_version,
+                function: Some(exec.function.clone().try_into()?),
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<DropFunctionsExec>() {
+            proto::ExecutionPlanExtensionType::DropFunctionsExec(proto::DropFunctionsExec {
+                catalog_version: exec.catalog_version,
+                drops: exec.drops.iter().cloned().map(Into::into).collect(),
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<CreateSequenceExec>() {
+            proto::ExecutionPlanExtensionType::CreateSequenceExec(proto::CreateSequenceExec {
+                catalog_version: exec.catalog_version,
+                sequence: Some(exec.sequence.clone().into()),
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<DropSequencesExec>() {
+            proto::ExecutionPlanExtensionType::DropSequencesExec(proto::DropSequencesExec {
+                catalog_version: exec.catalog_version,
+                references: exec
+                    .sequence_references
+                    .clone()
+                    .into_iter()
+                    .map(|r| r.into())
+                    .collect(),
+                if_exists: exec.if_exists,
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<CommentOnExec>() {
+            proto::ExecutionPlanExtensionType::CommentOnExec(proto::CommentOnExec {
+                catalog_version: exec.catalog_version,
+                comment: Some(exec.comment.clone().into()),
+            })
+        } else if let Some(exec) = node
+            .as_any()
+            .downcast_ref::<CreateExternalDatabaseTableExec>()
+        {
+            proto::ExecutionPlanExtensionType::CreateExternalDatabaseTableExec(
+                proto::CreateExternalDatabaseTableExec {
+                    tbl_reference: Some(exec.tbl_reference.clone().into()),
+                    if_not_exists: exec.if_not_exists,
+                    or_replace: exec.or_replace,
+                    arrow_schema: Some(exec.arrow_schema.clone().try_into()?),
+                    options: Some(exec.options.clone().into()),
+                    tunnel: exec.tunnel.clone().map(Into::into),
+                },
+            )
         } else if let Some(exec) = node.as_any().downcast_ref::<CopyToExec>() {
             proto::ExecutionPlanExtensionType::CopyToExec(proto::CopyToExec {
                 format: Some(exec.format.clone().try_into()?),
@@ -1032,3 +1413,59 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
             .map_err(|e| DataFusionError::External(Box::new(e)))
     }
 }
+
+fn encode_constraint_checks(
+    checks: &[ConstraintCheck],
+) -> Result<Vec<protogen::sqlexec::physical_plan::ConstraintCheck>> {
+    checks
+        .iter()
+        .map(|check| {
+            Ok(protogen::sqlexec::physical_plan::ConstraintCheck {
+                expr: Some((&check.expr).try_into()?),
+                violation: check.violation.clone(),
+            })
+        })
+        .collect()
+}
+
+fn decode_constraint_checks(
+    checks: Vec<protogen::sqlexec::physical_plan::ConstraintCheck>,
+    registry: &dyn FunctionRegistry,
+) -> Result<Vec<ConstraintCheck>> {
+    checks
+        .into_iter()
+        .map(|check| {
+            let expr = check
+                .expr
+                .ok_or_else(|| DataFusionError::Internal("missing expression".to_string()))?;
+            Ok(ConstraintCheck {
+                expr: parse_expr(&expr, registry)?,
+                violation: check.violation,
+            })
+        })
+        .collect()
+}
+
+fn encode_unique_checks(
+    checks: &[UniqueCheck],
+) -> Vec<protogen::sqlexec::physical_plan::UniqueCheck> {
+    checks
+        .iter()
+        .map(|check| protogen::sqlexec::physical_plan::UniqueCheck {
+            columns: check.columns.clone(),
+            violation: check.violation.clone(),
+        })
+        .collect()
+}
+
+fn decode_unique_checks(
+    checks: Vec<protogen::sqlexec::physical_plan::UniqueCheck>,
+) -> Vec<UniqueCheck> {
+    checks
+        .into_iter()
+        .map(|check| UniqueCheck {
+            columns: check.columns,
+            violation: check.violation,
+        })
+        .collect()
+}
@@ -1,7 +1,9 @@
 //! Built-in functions.
+use crate::activity::BackendSignaler;
+use datafusion::arrow::array::{Array, BooleanArray, Int64Array};
 use datafusion::arrow::datatypes::{DataType, Field};
 use datafusion::common::ScalarValue;
-use datafusion::error::Result;
+use datafusion::error::{DataFusionError, Result};
 use datafusion::logical_expr::{ColumnarValue, ScalarUDF, Signature, TypeSignature, Volatility};
 use datafusion::prelude::Expr;
 use sqlbuiltins::builtins::POSTGRES_SCHEMA;
@@ -131,10 +133,37 @@ pub enum BuiltinPostgresFunctions {
     /// select current_catalog();
     /// ```
     CurrentCatalog,
+    /// SQL function `pg_backend_pid`
+    ///
+    /// `pg_backend_pid()` -> `Integer`
+    /// ```sql
+    /// select pg_backend_pid();
+    /// ```
+    BackendPid,
+    /// SQL function `pg_cancel_backend`
+    ///
+    /// `pg_cancel_backend(pid int)` -> `Boolean`
+    /// ```sql
+    /// select pg_cancel_backend(1);
+    /// ```
+    ///
+    /// Cancel the statement currently executing in the session with the given
+    /// pid. Sessions for other users may only be canceled by superusers.
+    CancelBackend,
+    /// SQL function `pg_terminate_backend`
+    ///
+    /// `pg_terminate_backend(pid int)` -> `Boolean`
+    /// ```sql
+    /// select pg_terminate_backend(1);
+    /// ```
+    ///
+    /// Terminate the session with the given pid. Sessions for other users may
+    /// only be terminated by superusers.
+    TerminateBackend,
 }
 
 impl BuiltinPostgresFunctions {
-    fn into_expr(self, args: Vec<Expr>) -> Expr {
+    fn into_expr(self, args: Vec<Expr>, signaler: &BackendSignaler) -> Expr {
         match self {
             Self::GetUserById => udf_to_expr(pg_get_userbyid(), args),
             Self::TableIsVisible => udf_to_expr(pg_table_is_visible(), args),
@@ -149,6 +178,9 @@ impl BuiltinPostgresFunctions {
             Self::User => string_var("user"),
             Self::CurrentSchema => string_var("current_schema"),
             Self::CurrentDatabase => string_var("current_database"),
+            Self::BackendPid => Expr::Literal(ScalarValue::Int32(Some(signaler.pid()))),
+            Self::CancelBackend => udf_to_expr(pg_cancel_backend(signaler.clone()), args),
+            Self::TerminateBackend => udf_to_expr(pg_terminate_backend(signaler.clone()), args),
             Self::CurrentSchemas => {
                 // There's no good way to handle the `include_implicit` argument,
                 // but since its a binary value (true/false),
@@ -174,11 +206,11 @@ impl BuiltinScalarFunction {
     pub fn find_function(name: &str) -> Option<Self> {
         Self::from_str(name).ok()
     }
-    pub fn into_expr(self, args: Vec<Expr>) -> Expr {
+    pub fn into_expr(self, args: Vec<Expr>, signaler: &BackendSignaler) -> Expr {
         match self {
             Self::ConnectionId => string_var("connection_id"),
             Self::Version => string_var("version"),
-            Self::Pg(pg) => pg.into_expr(args),
+            Self::Pg(pg) => pg.into_expr(args, signaler),
         }
     }
 }
@@ -208,6 +240,9 @@ impl FromStr for BuiltinPostgresFunctions {
             "user" => Ok(Self::User),
             "current_schema" => Ok(Self::CurrentSchema),
             "current_database" => Ok(Self::CurrentDatabase),
+            "pg_backend_pid" => Ok(Self::BackendPid),
+            "pg_cancel_backend" => Ok(Self::CancelBackend),
+            "pg_terminate_backend" => Ok(Self::TerminateBackend),
             s => {
                 let idents: Vec<_> = s.split('.').collect();
                 if idents.len() != 2 {
@@ -368,6 +403,54 @@ fn pg_has_table_privilege() -> ScalarUDF {
     }
 }
 
+fn pg_cancel_backend(signaler: BackendSignaler) -> ScalarUDF {
+    ScalarUDF {
+        name: "pg_cancel_backend".to_string(),
+        signature: Signature::new(
+            TypeSignature::Exact(vec![DataType::Int64]),
+            Volatility::Volatile,
+        ),
+        return_type: Arc::new(|_| Ok(Arc::new(DataType::Boolean))),
+        fun: Arc::new(move |input| signal_backends(input, |pid| signaler.cancel(pid))),
+    }
+}
+
+fn pg_terminate_backend(signaler: BackendSignaler) -> ScalarUDF {
+    ScalarUDF {
+        name: "pg_terminate_backend".to_string(),
+        signature: Signature::new(
+            TypeSignature::Exact(vec![DataType::Int64]),
+            Volatility::Volatile,
+        ),
+        return_type: Arc::new(|_| Ok(Arc::new(DataType::Boolean))),
+        fun: Arc::new(move |input| signal_backends(input, |pid| signaler.terminate(pid))),
+    }
+}
+
+/// Signal each backend in the input, returning whether or not the signal was
+/// sent.
+fn signal_backends(input: &[ColumnarValue], signal: impl Fn(i32) -> bool) -> Result<ColumnarValue> {
+    let signal = |pid: i64| i32::try_from(pid).map(&signal).unwrap_or(false);
+    match input.get(0) {
+        Some(ColumnarValue::Scalar(ScalarValue::Int64(pid))) => {
+            Ok(ColumnarValue::Scalar(ScalarValue::Boolean(pid.map(signal))))
+        }
+        Some(ColumnarValue::Array(arr)) => {
+            let pids = arr.as_any().downcast_ref::<Int64Array>().ok_or_else(|| {
+                DataFusionError::Internal(format!(
+                    "expected int64 array for pid, got {}",
+                    arr.data_type()
+                ))
+            })?;
+            let signaled: BooleanArray = pids.iter().map(|pid| pid.map(signal)).collect();
+            Ok(ColumnarValue::Array(Arc::new(signaled)))
+        }
+        other => Err(DataFusionError::Internal(format!(
+            "unexpected input for pid: {other:?}"
+        ))),
+    }
+}
+
 fn get_nth_scalar_value(input: &[ColumnarValue], n: usize) -> Option<ScalarValue> {
     match input.get(n) {
         Some(input) => match input {
@@ -408,6 +491,11 @@ mod tests {
                 HasDatabasePrivilege.into(),
             ),
             ("pg_catalog.has_table_privilege", HasTablePrivilege.into()),
+            ("pg_backend_pid", BackendPid.into()),
+            ("pg_cancel_backend", CancelBackend.into()),
+            ("pg_terminate_backend", TerminateBackend.into()),
+            ("pg_catalog.pg_cancel_backend", CancelBackend.into()),
+            ("pg_catalog.pg_terminate_backend", TerminateBackend.into()),
         ];
         for (s, expected) in pairs {
             let func = BuiltinScalarFunction::from_str(s).unwrap();
@@ -1,11 +1,15 @@
 //! SQL execution.
+pub mod activity;
+pub mod audit;
 pub mod context;
 pub mod engine;
 pub mod environment;
 pub mod errors;
 pub mod extension_codec;
+pub mod limits;
 pub mod metastore;
 pub mod parser;
+pub mod query_history;
 pub mod remote;
 pub mod session;
 
@@ -15,6 +19,7 @@ mod functions;
 mod metrics;
 mod planner;
 mod resolve;
+mod sequences;
 
 pub use planner::logical_plan::LogicalPlan;
 
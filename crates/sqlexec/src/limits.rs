@@ -0,0 +1,381 @@
+//! Query-level resource limits and admission control.
+//!
+//! Limits are enforced per query:
+//!
+//! - `statement_timeout`: Max wall-clock time (in milliseconds) for a query,
+//!   including the time spent streaming results back.
+//! - `max_scan_bytes`: Max bytes a query may read from data sources.
+//! - `max_result_rows`: Max rows a query may return.
+//!
+//! Queries may also be canceled through the session's activity (see
+//! `pg_cancel_backend`).
+//!
+//! Users may lower `max_scan_bytes` and `max_result_rows` for their session,
+//! but may not raise or remove a limit once it's been set.
+//!
+//! Admission control is handled by the `QueryLimiter`, which caps the number of
+//! queries executing concurrently across all sessions for an engine. Queries
+//! exceeding the cap wait in a queue until a slot frees up. Statements that
+//! only touch session state (e.g. `SET` and `SHOW`) don't take a slot, so a
+//! session can always change its settings even while the engine is at
+//! capacity.
+use crate::activity::ActiveStatement;
+use crate::errors::{ExecError, Result};
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::physical_plan::{ExecutionPlan, RecordBatchStream, SendableRecordBatchStream};
+use datafusion_ext::metrics::AggregatedMetrics;
+use datafusion_ext::vars::SessionVars;
+use futures::{Stream, StreamExt};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::{Instant, Interval, MissedTickBehavior, Sleep};
+use tokio_util::sync::WaitForCancellationFutureOwned;
+
+/// How often to check bytes read from data sources while waiting on a query's
+/// output.
+const SCAN_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Limits the number of queries executing concurrently.
+///
+/// Cloning the limiter shares the underlying query slots.
+#[derive(Debug, Clone, Default)]
+pub struct QueryLimiter {
+    inner: Option<Arc<QueryLimiterInner>>,
+}
+
+#[derive(Debug)]
+struct QueryLimiterInner {
+    max_concurrent: usize,
+    queue_timeout: Option<Duration>,
+    semaphore: Arc<Semaphore>,
+}
+
+impl QueryLimiter {
+    /// Create a limiter that doesn't limit anything.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Create a limiter allowing at most `max_concurrent` queries to execute
+    /// at once.
+    ///
+    /// Queries waiting for longer than `queue_timeout` for a slot will error.
+    /// If no timeout is provided, queries will wait indefinitely.
+    pub fn new(max_concurrent: usize, queue_timeout: Option<Duration>) -> Self {
+        QueryLimiter {
+            inner: Some(Arc::new(QueryLimiterInner {
+                max_concurrent,
+                queue_timeout,
+                semaphore: Arc::new(Semaphore::new(max_concurrent)),
+            })),
+        }
+    }
+
+    /// Wait for a query slot.
+    ///
+    /// The slot is held until the returned permit is dropped.
+    pub async fn acquire(&self) -> Result<QueryPermit> {
+        let inner = match &self.inner {
+            Some(inner) => inner,
+            None => return Ok(QueryPermit::exempt()),
+        };
+
+        let acquire = inner.semaphore.clone().acquire_owned();
+        let permit = match inner.queue_timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, acquire).await {
+                Ok(permit) => permit,
+                Err(_) => {
+                    return Err(ExecError::QueryQueueTimeout {
+                        max_concurrent: inner.max_concurrent,
+                        waited_ms: timeout.as_millis() as u64,
+                    })
+                }
+            },
+            None => acquire.await,
+        };
+        // Semaphore is never closed.
+        let permit = permit.map_err(|e| ExecError::Internal(e.to_string()))?;
+
+        Ok(QueryPermit {
+            _permit: Some(permit),
+        })
+    }
+}
+
+/// A slot for an executing query.
+#[derive(Debug)]
+pub struct QueryPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl QueryPermit {
+    /// Get a permit for a statement that's exempt from admission control.
+    pub fn exempt() -> Self {
+        QueryPermit { _permit: None }
+    }
+}
+
+/// Per-query limits read from session variables.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QueryGuardrails {
+    pub statement_timeout: Option<Duration>,
+    pub max_scan_bytes: Option<u64>,
+    pub max_result_rows: Option<u64>,
+}
+
+impl QueryGuardrails {
+    pub fn from_vars(vars: &SessionVars) -> Self {
+        let statement_timeout = vars.statement_timeout();
+        let max_scan_bytes = vars.max_scan_bytes();
+        let max_result_rows = vars.max_result_rows();
+
+        QueryGuardrails {
+            statement_timeout: if statement_timeout > 0 {
+                Some(Duration::from_millis(statement_timeout as u64))
+            } else {
+                None
+            },
+            max_scan_bytes: if max_scan_bytes > 0 {
+                Some(max_scan_bytes as u64)
+            } else {
+                None
+            },
+            max_result_rows: if max_result_rows > 0 {
+                Some(max_result_rows as u64)
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Get the deadline for a query starting now.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.statement_timeout
+            .map(|timeout| Instant::now() + timeout)
+    }
+
+    /// Run a future to completion, erroring if the deadline is reached.
+    pub async fn with_deadline<F, T>(&self, deadline: Option<Instant>, fut: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        match deadline {
+            Some(deadline) => match tokio::time::timeout_at(deadline, fut).await {
+                Ok(result) => result,
+                Err(_) => Err(self.timeout_error()),
+            },
+            None => fut.await,
+        }
+    }
+
+    fn timeout_error(&self) -> ExecError {
+        ExecError::StatementTimeout {
+            timeout_ms: self
+                .statement_timeout
+                .map(|t| t.as_millis() as u64)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// A stream enforcing query guardrails while batches are being produced.
+///
+/// The query's permit is held, and the statement is considered active, until
+/// the stream completes or errors.
+pub struct LimitedBatchStream {
+    stream: SendableRecordBatchStream,
+    /// Plan used to get the bytes read from data sources.
+    plan: Arc<dyn ExecutionPlan>,
+    guardrails: QueryGuardrails,
+    timeout: Option<Pin<Box<Sleep>>>,
+    scan_check: Option<Interval>,
+    canceled: Option<Pin<Box<WaitForCancellationFutureOwned>>>,
+    rows: u64,
+    permit: Option<QueryPermit>,
+    statement: Option<ActiveStatement>,
+}
+
+impl LimitedBatchStream {
+    pub fn new(
+        stream: SendableRecordBatchStream,
+        plan: Arc<dyn ExecutionPlan>,
+        guardrails: QueryGuardrails,
+        deadline: Option<Instant>,
+        permit: QueryPermit,
+        statement: ActiveStatement,
+    ) -> Self {
+        let scan_check = guardrails.max_scan_bytes.map(|_| {
+            let mut interval = tokio::time::interval(SCAN_CHECK_INTERVAL);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            interval
+        });
+
+        LimitedBatchStream {
+            stream,
+            plan,
+            guardrails,
+            timeout: deadline.map(|deadline| Box::pin(tokio::time::sleep_until(deadline))),
+            scan_check,
+            canceled: Some(Box::pin(statement.canceled())),
+            rows: 0,
+            permit: Some(permit),
+            statement: Some(statement),
+        }
+    }
+
+    fn check_scan_bytes(&self) -> Result<()> {
+        if let Some(limit) = self.guardrails.max_scan_bytes {
+            let agg = AggregatedMetrics::new_from_plan(self.plan.as_ref());
+            if agg.bytes_processed > limit {
+                return Err(ExecError::MaxScanBytesExceeded { limit });
+            }
+        }
+        Ok(())
+    }
+
+    /// Release the query's permit and mark the statement as no longer
+    /// active.
+    fn finish(&mut self) {
+        self.permit = None;
+        self.statement = None;
+        self.timeout = None;
+        self.scan_check = None;
+        self.canceled = None;
+    }
+
+    /// Finish the stream with an error.
+    fn fail(&mut self, e: ExecError) -> Poll<Option<DataFusionResult<RecordBatch>>> {
+        self.finish();
+        Poll::Ready(Some(Err(DataFusionError::External(Box::new(e)))))
+    }
+}
+
+impl Stream for LimitedBatchStream {
+    type Item = DataFusionResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.permit.is_none() {
+            // Already completed or errored.
+            return Poll::Ready(None);
+        }
+
+        if let Some(canceled) = self.canceled.as_mut() {
+            if canceled.as_mut().poll(cx).is_ready() {
+                let e = match &self.statement {
+                    Some(statement) => statement.canceled_error(),
+                    None => ExecError::QueryCanceled,
+                };
+                return self.fail(e);
+            }
+        }
+
+        if let Some(timeout) = self.timeout.as_mut() {
+            if timeout.as_mut().poll(cx).is_ready() {
+                let e = self.guardrails.timeout_error();
+                return self.fail(e);
+            }
+        }
+
+        // Periodically check bytes read even if the query hasn't produced any
+        // output yet, e.g. during an aggregate.
+        let mut check_scan = false;
+        if let Some(interval) = self.scan_check.as_mut() {
+            while interval.poll_tick(cx).is_ready() {
+                check_scan = true;
+            }
+        }
+        if check_scan {
+            if let Err(e) = self.check_scan_bytes() {
+                return self.fail(e);
+            }
+        }
+
+        match self.stream.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(batch))) => {
+                // The inner stream may have done all of its work without
+                // yielding, check the deadline again.
+                if let Some(timeout) = self.timeout.as_ref() {
+                    if Instant::now() >= timeout.deadline() {
+                        let e = self.guardrails.timeout_error();
+                        return self.fail(e);
+                    }
+                }
+
+                self.rows += batch.num_rows() as u64;
+                if let Some(limit) = self.guardrails.max_result_rows {
+                    if self.rows > limit {
+                        return self.fail(ExecError::MaxResultRowsExceeded { limit });
+                    }
+                }
+                if let Err(e) = self.check_scan_bytes() {
+                    return self.fail(e);
+                }
+                Poll::Ready(Some(Ok(batch)))
+            }
+            Poll::Ready(Some(Err(e))) => {
+                self.finish();
+                Poll::Ready(Some(Err(e)))
+            }
+            Poll::Ready(None) => {
+                self.finish();
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl RecordBatchStream for LimitedBatchStream {
+    fn schema(&self) -> SchemaRef {
+        self.stream.schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn limiter_queue_timeout() {
+        let limiter = QueryLimiter::new(1, Some(Duration::from_millis(10)));
+
+        let permit = limiter.acquire().await.unwrap();
+        let err = limiter.acquire().await.unwrap_err();
+        assert!(matches!(err, ExecError::QueryQueueTimeout { .. }));
+
+        drop(permit);
+        let _permit = limiter.acquire().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn unlimited_limiter() {
+        let limiter = QueryLimiter::unlimited();
+        let _p1 = limiter.acquire().await.unwrap();
+        let _p2 = limiter.acquire().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn guardrails_deadline() {
+        let guardrails = QueryGuardrails {
+            statement_timeout: Some(Duration::from_millis(10)),
+            ..Default::default()
+        };
+        let err = guardrails
+            .with_deadline(guardrails.deadline(), async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(())
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ExecError::StatementTimeout { timeout_ms: 10 }
+        ));
+    }
+}
@@ -3,7 +3,7 @@ use parking_lot::Mutex;
 use protogen::metastore::strategy::ResolveErrorStrategy;
 use protogen::metastore::types::catalog::{
     CatalogEntry, CatalogState, CredentialsEntry, DatabaseEntry, DeploymentMetadata, EntryMeta,
-    EntryType, FunctionEntry, FunctionType, SchemaEntry, TableEntry, TunnelEntry,
+    EntryType, FunctionEntry, FunctionType, RoleEntry, SchemaEntry, TableEntry, TunnelEntry,
 };
 use protogen::metastore::types::options::{TableOptions, TableOptionsInternal};
 use protogen::metastore::types::service::Mutation;
@@ -111,6 +111,8 @@ pub struct SessionCatalog {
     tunnel_names: HashMap<String, u32>,
     /// Map credentials names to their ids.
     credentials_names: HashMap<String, u32>,
+    /// Map role names to their ids.
+    role_names: HashMap<String, u32>,
     /// Map schema names to their ids.
     schema_names: HashMap<String, u32>,
     /// Map schema IDs to objects in the schema.
@@ -125,6 +127,7 @@ impl SessionCatalog {
             database_names: HashMap::new(),
             tunnel_names: HashMap::new(),
             credentials_names: HashMap::new(),
+            role_names: HashMap::new(),
             schema_names: HashMap::new(),
             schema_objects: HashMap::new(),
         };
@@ -232,6 +235,34 @@ impl SessionCatalog {
         }
     }
 
+    /// Check if any roles exist in the catalog.
+    pub fn has_roles(&self) -> bool {
+        !self.role_names.is_empty()
+    }
+
+    /// Resolve a role by name.
+    pub fn resolve_role(&self, name: &str) -> Option<&RoleEntry> {
+        // Similar invariants as `resolve_database`. If we find an entry in the
+        // role map, it must exist in the state and must be a role.
+
+        let id = self.role_names.get(name)?;
+        let ent = self
+            .state
+            .entries
+            .get(id)
+            .expect("role name points to invalid id");
+
+        match ent {
+            CatalogEntry::Role(ent) => Some(ent),
+            _ => panic!(
+                "entry type not role; name: {}, id: {}, type: {:?}",
+                name,
+                id,
+                ent.entry_type(),
+            ),
+        }
+    }
+
     /// Resolve a schema by name.
     pub fn resolve_schema(&self, name: &str) -> Option<&SchemaEntry> {
         // Similar invariants as `resolve_database`. If we find an entry in the
@@ -374,13 +405,15 @@ impl SessionCatalog {
     fn as_namespaced_entry<'a>(&'a self, ent: &'a CatalogEntry) -> NamespacedCatalogEntry<'a> {
         let parent_entry = match ent {
             // Explicitly mention all the options to accidentally not leave anything here.
-            CatalogEntry::Database(_) | CatalogEntry::Tunnel(_) | CatalogEntry::Credentials(_) => {
-                None
-            }
+            CatalogEntry::Database(_)
+            | CatalogEntry::Tunnel(_)
+            | CatalogEntry::Credentials(_)
+            | CatalogEntry::Role(_) => None,
             CatalogEntry::Schema(_)
             | CatalogEntry::Table(_)
             | CatalogEntry::View(_)
-            | CatalogEntry::Function(_) => {
+            | CatalogEntry::Function(_)
+            | CatalogEntry::Sequence(_) => {
                 Some(self.state.entries.get(&ent.get_meta().parent).unwrap()) // Bug if it doesn't exist.
             }
         };
@@ -396,6 +429,7 @@ impl SessionCatalog {
         self.database_names.clear();
         self.tunnel_names.clear();
         self.credentials_names.clear();
+        self.role_names.clear();
         self.schema_names.clear();
         self.schema_objects.clear();
 
@@ -412,10 +446,16 @@ impl SessionCatalog {
                 CatalogEntry::Credentials(_) => {
                     self.credentials_names.insert(name, *id);
                 }
+                CatalogEntry::Role(_) => {
+                    self.role_names.insert(name, *id);
+                }
                 CatalogEntry::Schema(_) => {
                     self.schema_names.insert(name, *id);
                 }
-                CatalogEntry::Table(_) | CatalogEntry::View(_) | CatalogEntry::Function(_) => {
+                CatalogEntry::Table(_)
+                | CatalogEntry::View(_)
+                | CatalogEntry::Function(_)
+                | CatalogEntry::Sequence(_) => {
                     let schema_id = ent.get_meta().parent;
                     let ent = self.schema_objects.entry(schema_id).or_default();
                     ent.objects.insert(name, *id);
@@ -479,9 +519,15 @@ impl TempCatalog {
                     builtin: false,
                     external: false,
                     is_temp: true,
+                    comment: None,
                 },
                 options: TableOptions::Internal(TableOptionsInternal {
                     columns: Vec::new(),
+                    partition_columns: Vec::new(),
+                    primary_key: None,
+                    unique_constraints: Vec::new(),
+                    check_constraints: Vec::new(),
+                    materialized_view: None,
                 }),
                 tunnel_id: None,
             });
@@ -522,9 +568,15 @@ impl TempCatalog {
                     builtin: false,
                     external: false,
                     is_temp: true,
+                    comment: None,
                 },
                 options: TableOptions::Internal(TableOptionsInternal {
                     columns: Vec::new(),
+                    partition_columns: Vec::new(),
+                    primary_key: None,
+                    unique_constraints: Vec::new(),
+                    check_constraints: Vec::new(),
+                    materialized_view: None,
                 }),
                 tunnel_id: None,
             });
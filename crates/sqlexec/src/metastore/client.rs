@@ -48,15 +48,25 @@
 //! Note that executing a single request at a time for a database was the
 //! easiest way to accomplish the desired catalog caching behavior, and not due
 //! to any limitations in metastore itself.
+//!
+//! # Secrets
+//!
+//! If the supervisor is configured with a `SecretCipher`, workers encrypt
+//! secrets in mutations before sending them to metastore, and decrypt secrets
+//! in catalogs received from metastore before caching them. Sessions only ever
+//! see decrypted catalogs.
 
 use protogen::gen::metastore::service::metastore_service_client::MetastoreServiceClient;
 use protogen::gen::metastore::service::{FetchCatalogRequest, MutateRequest};
 use protogen::metastore::strategy::ResolveErrorStrategy;
 use protogen::metastore::types::{catalog::CatalogState, service::Mutation};
+
+use super::secrets::{SecretCipher, SecretsError};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
+use telemetry::metrics::METRICS;
 use tokio::sync::RwLock;
 use tokio::sync::{mpsc, oneshot};
 use tokio::task::JoinHandle;
@@ -78,6 +88,9 @@ pub enum MetastoreClientError {
     #[error(transparent)]
     ProtoConvError(#[from] protogen::errors::ProtoConvError),
 
+    #[error(transparent)]
+    Secrets(#[from] SecretsError),
+
     // TODO: Need to be more granular about errors from Metastore.
     #[error("Failed Metastore request: {message}")]
     MetastoreTonic {
@@ -268,6 +281,9 @@ pub struct MetastoreClientSupervisor {
 
     /// GRPC client to metastore. Cloned for each database worker.
     client: MetastoreServiceClient<Channel>,
+
+    /// Cipher for catalog secrets. Shared with each database worker.
+    cipher: Option<Arc<SecretCipher>>,
 }
 
 impl MetastoreClientSupervisor {
@@ -280,9 +296,18 @@ impl MetastoreClientSupervisor {
             workers: RwLock::new(HashMap::new()),
             client,
             worker_conf,
+            cipher: None,
         }
     }
 
+    /// Encrypt secrets in catalogs using the provided cipher.
+    ///
+    /// Should be set before any clients are initialized.
+    pub fn with_secret_cipher(mut self, cipher: Option<Arc<SecretCipher>>) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
     /// Initialize a client for a single database.
     ///
     /// This will initialize a database worker as appropriate.
@@ -318,7 +343,8 @@ impl MetastoreClientSupervisor {
         }
 
         // Slow path, need to initialize a worker.
-        let (worker, send) = StatefulWorker::init(db_id, self.client.clone()).await?;
+        let (worker, send) =
+            StatefulWorker::init(db_id, self.client.clone(), self.cipher.clone()).await?;
 
         let mut workers = self.workers.write().await;
         // Raced or the worker is finished.
@@ -407,6 +433,9 @@ struct StatefulWorker {
     /// GRPC client to metastore.
     client: MetastoreServiceClient<Channel>,
 
+    /// Cipher for catalog secrets.
+    cipher: Option<Arc<SecretCipher>>,
+
     /// Receive requests from sessions.
     recv: mpsc::Receiver<ClientRequest>,
 }
@@ -416,15 +445,21 @@ impl StatefulWorker {
     async fn init(
         db_id: Uuid,
         mut client: MetastoreServiceClient<Channel>,
+        cipher: Option<Arc<SecretCipher>>,
     ) -> Result<(StatefulWorker, mpsc::Sender<ClientRequest>)> {
+        let timer = METRICS
+            .metastore_request_duration_seconds
+            .with_label_values(&["fetch_catalog"])
+            .start_timer();
         let resp = client
             .fetch_catalog(tonic::Request::new(FetchCatalogRequest {
                 db_id: db_id.into_bytes().to_vec(),
             }))
-            .await?;
-        let resp = resp.into_inner();
+            .await;
+        timer.observe_duration();
+        let resp = resp?.into_inner();
 
-        let catalog: CatalogState = match resp.catalog {
+        let mut catalog: CatalogState = match resp.catalog {
             Some(c) => c.try_into()?,
             None => {
                 return Err(MetastoreClientError::Internal(
@@ -432,6 +467,9 @@ impl StatefulWorker {
                 ))
             }
         };
+        if let Some(cipher) = &cipher {
+            cipher.decrypt_state(&mut catalog).await?;
+        }
 
         let (send, recv) = mpsc::channel(PER_DATABASE_BUFFER);
 
@@ -441,6 +479,7 @@ impl StatefulWorker {
                 version_hint: Arc::new(AtomicU64::new(catalog.version)),
                 cached_state: Arc::new(catalog),
                 client,
+                cipher,
                 recv,
             },
             send,
@@ -516,22 +555,34 @@ impl StatefulWorker {
                 response,
                 ..
             } => {
-                let result = mutations
-                    .into_iter()
-                    .map(|m| m.try_into())
-                    .collect::<Result<_, _>>();
+                let result = match self.encrypt_mutations(mutations).await {
+                    Ok(mutations) => mutations
+                        .into_iter()
+                        .map(|m| m.try_into())
+                        .collect::<Result<_, _>>()
+                        .map_err(MetastoreClientError::from),
+                    Err(e) => Err(e),
+                };
 
                 let result = match result {
-                    Ok(mutations) => self
-                        .client
-                        .mutate_catalog(tonic::Request::new(MutateRequest {
-                            db_id: self.db_id.into_bytes().to_vec(),
-                            catalog_version: version,
-                            mutations,
-                        }))
-                        .await
-                        .map_err(MetastoreClientError::from),
-                    Err(e) => Err(MetastoreClientError::from(e)),
+                    Ok(mutations) => {
+                        let timer = METRICS
+                            .metastore_request_duration_seconds
+                            .with_label_values(&["mutate_catalog"])
+                            .start_timer();
+                        let result = self
+                            .client
+                            .mutate_catalog(tonic::Request::new(MutateRequest {
+                                db_id: self.db_id.into_bytes().to_vec(),
+                                catalog_version: version,
+                                mutations,
+                            }))
+                            .await
+                            .map_err(MetastoreClientError::from);
+                        timer.observe_duration();
+                        result
+                    }
+                    Err(e) => Err(e),
                 };
 
                 let result = match result {
@@ -542,11 +593,19 @@ impl StatefulWorker {
                             Some(catalog) => {
                                 // Update this worker's cache.
                                 let state: CatalogState = catalog.try_into().unwrap(); // TODO
-                                self.set_cached_state(state);
+                                match self.decrypt_state(state).await {
+                                    Ok(state) => {
+                                        self.set_cached_state(state);
+                                        Ok(self.cached_state.clone())
+                                    }
+                                    Err(e) => Err(e),
+                                }
+                            }
+                            _ => {
+                                error!("missing catalog state");
+                                Ok(self.cached_state.clone())
                             }
-                            _ => error!("missing catalog state"),
                         }
-                        Ok(self.cached_state.clone())
                     }
                     Err(e) => Err(e),
                 };
@@ -569,13 +628,19 @@ impl StatefulWorker {
 
     /// Fetch the latest catalog from Metastore, updating this local catalog cache.
     async fn fetch(&mut self) {
-        match self
+        let timer = METRICS
+            .metastore_request_duration_seconds
+            .with_label_values(&["fetch_catalog"])
+            .start_timer();
+        let result = self
             .client
             .fetch_catalog(tonic::Request::new(FetchCatalogRequest {
                 db_id: self.db_id.into_bytes().to_vec(),
             }))
-            .await
-        {
+            .await;
+        timer.observe_duration();
+
+        match result {
             Ok(resp) => {
                 let resp = resp.into_inner();
                 let catalog: CatalogState = match resp.catalog {
@@ -591,12 +656,31 @@ impl StatefulWorker {
                         return;
                     }
                 };
-                self.set_cached_state(catalog);
+                match self.decrypt_state(catalog).await {
+                    Ok(catalog) => self.set_cached_state(catalog),
+                    Err(e) => error!(%e, "failed to decrypt catalog state"),
+                }
             }
             Err(e) => error!(?e, "failed to fetch catalog"),
         }
     }
 
+    /// Encrypt secrets in mutations before sending them to metastore.
+    async fn encrypt_mutations(&self, mut mutations: Vec<Mutation>) -> Result<Vec<Mutation>> {
+        if let Some(cipher) = &self.cipher {
+            cipher.encrypt_mutations(&mut mutations).await?;
+        }
+        Ok(mutations)
+    }
+
+    /// Decrypt secrets in a catalog received from metastore.
+    async fn decrypt_state(&self, mut state: CatalogState) -> Result<CatalogState> {
+        if let Some(cipher) = &self.cipher {
+            cipher.decrypt_state(&mut state).await?;
+        }
+        Ok(state)
+    }
+
     /// Set the cached catalog state for this database.
     fn set_cached_state(&mut self, state: CatalogState) {
         self.cached_state = Arc::new(state);
@@ -608,10 +692,15 @@ impl StatefulWorker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::metastore::secrets::{LocalKeyFileKms, ENCRYPTED_PREFIX};
     use metastore::local::start_inprocess;
     use object_store::memory::InMemory;
     use protogen::gen::metastore::service::metastore_service_client::MetastoreServiceClient;
-    use protogen::metastore::types::service::{CreateSchema, CreateView, Mutation};
+    use protogen::metastore::types::catalog::CatalogEntry;
+    use protogen::metastore::types::options::{CredentialsOptions, CredentialsOptionsAws};
+    use protogen::metastore::types::service::{
+        CreateCredentials, CreateSchema, CreateView, Mutation,
+    };
     use tonic::transport::Channel;
 
     /// Creates a new local Metastore, returning a client connected to that
@@ -649,6 +738,70 @@ mod tests {
         assert!(new_state.version > state.version);
     }
 
+    #[tokio::test]
+    async fn mutate_encrypts_secrets() {
+        let mut client = new_local_metastore().await;
+
+        let kms = LocalKeyFileKms::from_key(&[1; 32]).unwrap();
+        let supervisor =
+            MetastoreClientSupervisor::new(client.clone(), DEFAULT_METASTORE_CLIENT_CONFIG)
+                .with_secret_cipher(Some(Arc::new(SecretCipher::new(Arc::new(kms)))));
+
+        let db_id = Uuid::nil();
+        let handle = supervisor.init_client(db_id).await.unwrap();
+
+        let options = CredentialsOptions::Aws(CredentialsOptionsAws {
+            access_key_id: "key_id".to_string(),
+            secret_access_key: "secret".to_string(),
+        });
+
+        let state = handle.get_cached_state().await.unwrap();
+        let new_state = handle
+            .try_mutate(
+                state.version,
+                vec![Mutation::CreateCredentials(CreateCredentials {
+                    name: "creds".to_string(),
+                    options: options.clone(),
+                    comment: String::new(),
+                })],
+            )
+            .await
+            .unwrap();
+
+        fn find_creds(state: &CatalogState) -> &CredentialsOptions {
+            state
+                .entries
+                .values()
+                .find_map(|ent| match ent {
+                    CatalogEntry::Credentials(creds) => Some(&creds.options),
+                    _ => None,
+                })
+                .unwrap()
+        }
+
+        // Cached state should be decrypted.
+        assert_eq!(&options, find_creds(&new_state));
+
+        // State stored in metastore should be encrypted.
+        let stored: CatalogState = client
+            .fetch_catalog(tonic::Request::new(FetchCatalogRequest {
+                db_id: db_id.into_bytes().to_vec(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .catalog
+            .unwrap()
+            .try_into()
+            .unwrap();
+        match find_creds(&stored) {
+            CredentialsOptions::Aws(aws) => {
+                assert!(aws.secret_access_key.starts_with(ENCRYPTED_PREFIX))
+            }
+            other => panic!("unexpected options: {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn out_of_date_mutate() {
         let client = new_local_metastore().await;
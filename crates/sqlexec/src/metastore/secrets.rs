@@ -0,0 +1,472 @@
+//! Encryption of secrets stored in the catalog.
+//!
+//! Secret option values (passwords, keys, connection strings, etc) are
+//! encrypted before mutations are sent to Metastore, and decrypted when catalog
+//! state is received from Metastore. Metastore only ever holds and persists the
+//! encrypted values.
+//!
+//! Values are encrypted using envelope encryption. Each value is encrypted with
+//! a data key using AES-256-GCM, and the data key itself is encrypted by a
+//! `KeyManagementService` and stored alongside the value. A single data key is
+//! generated per cipher, and decrypted data keys are cached, so the key
+//! management service is only contacted once per data key.
+//!
+//! Encrypted values replace the plaintext values in place and are prefixed
+//! with `ENCRYPTED_PREFIX`. Values without the prefix are treated as
+//! plaintext, so catalogs written before encryption was enabled continue to
+//! work.
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use parking_lot::Mutex;
+use prost::Message;
+use protogen::metastore::types::catalog::{CatalogEntry, CatalogState};
+use protogen::metastore::types::options::{SecretMut, SecretOptions};
+use protogen::metastore::types::service::Mutation;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::digest::{digest, SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SecretsError {
+    #[error("Failed to read key file '{path}': {source}")]
+    ReadKeyFile {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+
+    #[error("Secret was encrypted with an unknown key: {0}")]
+    UnknownKeyId(String),
+
+    #[error("Invalid encrypted secret: {0}")]
+    InvalidEncryptedSecret(String),
+
+    #[error("Failed to encrypt secret")]
+    Encrypt,
+
+    #[error("Failed to decrypt secret")]
+    Decrypt,
+
+    #[error("Key management service error: {0}")]
+    KeyManagementService(String),
+}
+
+type Result<T, E = SecretsError> = std::result::Result<T, E>;
+
+/// Prefix for encrypted secret values.
+pub const ENCRYPTED_PREFIX: &str = "glaredb-enc-v1:";
+
+/// Length in bytes of data keys and local keys.
+const KEY_LEN: usize = 32;
+
+/// Service for encrypting and decrypting data keys.
+///
+/// Implementations may hold a key locally, or call out to an external key
+/// management service.
+#[async_trait]
+pub trait KeyManagementService: fmt::Debug + Sync + Send {
+    /// Id of the key used to encrypt new data keys.
+    fn key_id(&self) -> &str;
+
+    /// Encrypt a data key using the current key.
+    async fn encrypt_data_key(&self, data_key: &[u8]) -> Result<Vec<u8>>;
+
+    /// Decrypt a data key that was encrypted by the key with the given id.
+    async fn decrypt_data_key(&self, key_id: &str, encrypted: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Key management using a single key read from a local file.
+pub struct LocalKeyFileKms {
+    key_id: String,
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl LocalKeyFileKms {
+    /// Read a key from a file. The file should contain a base64 encoded
+    /// 256-bit key.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents =
+            std::fs::read_to_string(path).map_err(|source| SecretsError::ReadKeyFile {
+                path: path.display().to_string(),
+                source,
+            })?;
+        let key = BASE64
+            .decode(contents.trim())
+            .map_err(|e| SecretsError::InvalidKey(e.to_string()))?;
+        Self::from_key(&key)
+    }
+
+    /// Create from a 256-bit key.
+    pub fn from_key(key: &[u8]) -> Result<Self> {
+        // Derive the id from the key itself so that secrets encrypted using a
+        // different key can be detected.
+        let key_id = digest(&SHA256, key).as_ref()[..8]
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+
+        Ok(LocalKeyFileKms {
+            key_id: format!("local:{key_id}"),
+            key: new_key(key)?,
+            rng: SystemRandom::new(),
+        })
+    }
+}
+
+impl fmt::Debug for LocalKeyFileKms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalKeyFileKms")
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl KeyManagementService for LocalKeyFileKms {
+    fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    async fn encrypt_data_key(&self, data_key: &[u8]) -> Result<Vec<u8>> {
+        seal(&self.key, &self.rng, data_key)
+    }
+
+    async fn decrypt_data_key(&self, key_id: &str, encrypted: &[u8]) -> Result<Vec<u8>> {
+        if key_id != self.key_id {
+            return Err(SecretsError::UnknownKeyId(key_id.to_string()));
+        }
+        open(&self.key, encrypted)
+    }
+}
+
+/// Serialized form of an encrypted secret.
+#[derive(Clone, PartialEq, Message)]
+struct EncryptedSecret {
+    /// Id of the key used to encrypt the data key.
+    #[prost(string, tag = "1")]
+    key_id: String,
+    /// The encrypted data key.
+    #[prost(bytes, tag = "2")]
+    data_key: Vec<u8>,
+    /// Nonce followed by the ciphertext.
+    #[prost(bytes, tag = "3")]
+    ciphertext: Vec<u8>,
+}
+
+/// Data key used for encrypting new secrets.
+#[derive(Debug)]
+struct DataKey {
+    key: LessSafeKey,
+    key_id: String,
+    encrypted: Vec<u8>,
+}
+
+/// Encrypts and decrypts secrets held in catalog mutations and state.
+#[derive(Debug)]
+pub struct SecretCipher {
+    kms: Arc<dyn KeyManagementService>,
+    rng: SystemRandom,
+    data_key: OnceCell<DataKey>,
+    /// Decrypted data keys, keyed by their encrypted form.
+    decrypted_keys: Mutex<HashMap<Vec<u8>, Arc<LessSafeKey>>>,
+}
+
+impl SecretCipher {
+    pub fn new(kms: Arc<dyn KeyManagementService>) -> SecretCipher {
+        SecretCipher {
+            kms,
+            rng: SystemRandom::new(),
+            data_key: OnceCell::new(),
+            decrypted_keys: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Encrypt all secrets in the provided mutations.
+    pub async fn encrypt_mutations(&self, mutations: &mut [Mutation]) -> Result<()> {
+        for mutation in mutations {
+            match mutation {
+                Mutation::CreateExternalDatabase(m) => self.encrypt_options(&mut m.options).await?,
+                Mutation::CreateExternalTable(m) => self.encrypt_options(&mut m.options).await?,
+                Mutation::CreateTunnel(m) => self.encrypt_options(&mut m.options).await?,
+                Mutation::CreateCredentials(m) => self.encrypt_options(&mut m.options).await?,
+                Mutation::AlterTunnelRotateKeys(m) => {
+                    self.encrypt_secret(SecretMut::Bytes(&mut m.new_ssh_key))
+                        .await?
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Decrypt all secrets in the provided catalog state.
+    pub async fn decrypt_state(&self, state: &mut CatalogState) -> Result<()> {
+        for ent in state.entries.values_mut() {
+            match ent {
+                CatalogEntry::Database(ent) => self.decrypt_options(&mut ent.options).await?,
+                CatalogEntry::Table(ent) => self.decrypt_options(&mut ent.options).await?,
+                CatalogEntry::Tunnel(ent) => self.decrypt_options(&mut ent.options).await?,
+                CatalogEntry::Credentials(ent) => self.decrypt_options(&mut ent.options).await?,
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    async fn encrypt_options(&self, opts: &mut (impl SecretOptions + Send)) -> Result<()> {
+        for secret in opts.secrets_mut() {
+            self.encrypt_secret(secret).await?;
+        }
+        Ok(())
+    }
+
+    async fn decrypt_options(&self, opts: &mut (impl SecretOptions + Send)) -> Result<()> {
+        for secret in opts.secrets_mut() {
+            self.decrypt_secret(secret).await?;
+        }
+        Ok(())
+    }
+
+    async fn encrypt_secret(&self, secret: SecretMut<'_>) -> Result<()> {
+        match secret {
+            SecretMut::String(s) => {
+                if !s.is_empty() && !s.starts_with(ENCRYPTED_PREFIX) {
+                    *s = self.encrypt(s.as_bytes()).await?;
+                }
+            }
+            SecretMut::Bytes(b) => {
+                if !b.is_empty() && !b.starts_with(ENCRYPTED_PREFIX.as_bytes()) {
+                    *b = self.encrypt(b).await?.into_bytes();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn decrypt_secret(&self, secret: SecretMut<'_>) -> Result<()> {
+        match secret {
+            SecretMut::String(s) => {
+                if let Some(encoded) = s.strip_prefix(ENCRYPTED_PREFIX) {
+                    let plaintext = self.decrypt(encoded).await?;
+                    *s = String::from_utf8(plaintext).map_err(|_| {
+                        SecretsError::InvalidEncryptedSecret("value not utf8".to_string())
+                    })?;
+                }
+            }
+            SecretMut::Bytes(b) => {
+                if let Some(encoded) = b.strip_prefix(ENCRYPTED_PREFIX.as_bytes()) {
+                    let encoded = std::str::from_utf8(encoded).map_err(|_| {
+                        SecretsError::InvalidEncryptedSecret("encoding not utf8".to_string())
+                    })?;
+                    *b = self.decrypt(encoded).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Encrypt a value, returning the encoded value with the encrypted prefix.
+    async fn encrypt(&self, plaintext: &[u8]) -> Result<String> {
+        let data_key = self
+            .data_key
+            .get_or_try_init(|| async {
+                let mut key = [0; KEY_LEN];
+                self.rng.fill(&mut key).map_err(|_| SecretsError::Encrypt)?;
+                let encrypted = self.kms.encrypt_data_key(&key).await?;
+                Ok::<_, SecretsError>(DataKey {
+                    key: new_key(&key)?,
+                    key_id: self.kms.key_id().to_string(),
+                    encrypted,
+                })
+            })
+            .await?;
+
+        let secret = EncryptedSecret {
+            key_id: data_key.key_id.clone(),
+            data_key: data_key.encrypted.clone(),
+            ciphertext: seal(&data_key.key, &self.rng, plaintext)?,
+        };
+
+        Ok(format!(
+            "{ENCRYPTED_PREFIX}{}",
+            BASE64.encode(secret.encode_to_vec())
+        ))
+    }
+
+    /// Decrypt a value encoded by `encrypt`, without the prefix.
+    async fn decrypt(&self, encoded: &str) -> Result<Vec<u8>> {
+        let buf = BASE64
+            .decode(encoded)
+            .map_err(|e| SecretsError::InvalidEncryptedSecret(e.to_string()))?;
+        let secret = EncryptedSecret::decode(buf.as_slice())
+            .map_err(|e| SecretsError::InvalidEncryptedSecret(e.to_string()))?;
+
+        let cached = self.decrypted_keys.lock().get(&secret.data_key).cloned();
+        let key = match cached {
+            Some(key) => key,
+            None => {
+                let key = self
+                    .kms
+                    .decrypt_data_key(&secret.key_id, &secret.data_key)
+                    .await?;
+                let key = Arc::new(new_key(&key)?);
+                self.decrypted_keys
+                    .lock()
+                    .insert(secret.data_key, key.clone());
+                key
+            }
+        };
+
+        open(&key, &secret.ciphertext)
+    }
+}
+
+fn new_key(key: &[u8]) -> Result<LessSafeKey> {
+    if key.len() != KEY_LEN {
+        return Err(SecretsError::InvalidKey(format!(
+            "expected {KEY_LEN} bytes, got {}",
+            key.len()
+        )));
+    }
+    let key = UnboundKey::new(&AES_256_GCM, key)
+        .map_err(|_| SecretsError::InvalidKey("unable to create key".to_string()))?;
+    Ok(LessSafeKey::new(key))
+}
+
+/// Encrypt some plaintext, returning a random nonce followed by the
+/// ciphertext.
+fn seal(key: &LessSafeKey, rng: &SystemRandom, plaintext: &[u8]) -> Result<Vec<u8>> {
+    let mut nonce = [0; NONCE_LEN];
+    rng.fill(&mut nonce).map_err(|_| SecretsError::Encrypt)?;
+
+    let mut buf = plaintext.to_vec();
+    key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut buf)
+        .map_err(|_| SecretsError::Encrypt)?;
+
+    let mut out = Vec::with_capacity(NONCE_LEN + buf.len());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&buf);
+    Ok(out)
+}
+
+/// Decrypt the output of `seal`.
+fn open(key: &LessSafeKey, sealed: &[u8]) -> Result<Vec<u8>> {
+    if sealed.len() < NONCE_LEN {
+        return Err(SecretsError::Decrypt);
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| SecretsError::Decrypt)?;
+
+    let mut buf = ciphertext.to_vec();
+    let plaintext = key
+        .open_in_place(nonce, Aad::empty(), &mut buf)
+        .map_err(|_| SecretsError::Decrypt)?;
+    Ok(plaintext.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use protogen::metastore::types::catalog::{CredentialsEntry, EntryMeta, EntryType};
+    use protogen::metastore::types::options::{CredentialsOptions, CredentialsOptionsAws};
+    use protogen::metastore::types::service::CreateCredentials;
+
+    fn new_cipher(key: u8) -> SecretCipher {
+        let kms = LocalKeyFileKms::from_key(&[key; KEY_LEN]).unwrap();
+        SecretCipher::new(Arc::new(kms))
+    }
+
+    fn aws_options(secret: &str) -> CredentialsOptions {
+        CredentialsOptions::Aws(CredentialsOptionsAws {
+            access_key_id: "key_id".to_string(),
+            secret_access_key: secret.to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn encrypt_decrypt_roundtrip() {
+        let cipher = new_cipher(1);
+
+        let mut mutations = vec![Mutation::CreateCredentials(CreateCredentials {
+            name: "creds".to_string(),
+            options: aws_options("secret"),
+            comment: String::new(),
+        })];
+        cipher.encrypt_mutations(&mut mutations).await.unwrap();
+
+        let options = match mutations.pop().unwrap() {
+            Mutation::CreateCredentials(m) => m.options,
+            other => panic!("unexpected mutation: {other:?}"),
+        };
+        let encrypted = match &options {
+            CredentialsOptions::Aws(aws) => aws.secret_access_key.clone(),
+            other => panic!("unexpected options: {other:?}"),
+        };
+        assert!(encrypted.starts_with(ENCRYPTED_PREFIX));
+
+        let mut state = CatalogState {
+            version: 1,
+            entries: HashMap::from([(
+                1,
+                CatalogEntry::Credentials(CredentialsEntry {
+                    meta: EntryMeta {
+                        entry_type: EntryType::Credentials,
+                        id: 1,
+                        parent: 0,
+                        name: "creds".to_string(),
+                        builtin: false,
+                        external: false,
+                        is_temp: false,
+                        comment: None,
+                    },
+                    options,
+                    comment: String::new(),
+                }),
+            )]),
+            deployment: Default::default(),
+        };
+
+        // A cipher using a different key should fail to decrypt.
+        new_cipher(2)
+            .decrypt_state(&mut state.clone())
+            .await
+            .unwrap_err();
+
+        cipher.decrypt_state(&mut state).await.unwrap();
+        match state.entries.get(&1).unwrap() {
+            CatalogEntry::Credentials(ent) => assert_eq!(aws_options("secret"), ent.options),
+            other => panic!("unexpected entry: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn plaintext_passthrough() {
+        let cipher = new_cipher(1);
+
+        let mut ssh_key = b"plaintext".to_vec();
+        cipher
+            .decrypt_secret(SecretMut::Bytes(&mut ssh_key))
+            .await
+            .unwrap();
+        assert_eq!(b"plaintext".to_vec(), ssh_key);
+
+        // Empty values aren't encrypted.
+        let mut empty = String::new();
+        cipher
+            .encrypt_secret(SecretMut::String(&mut empty))
+            .await
+            .unwrap();
+        assert_eq!("", empty);
+    }
+}
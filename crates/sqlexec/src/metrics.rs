@@ -1,14 +1,21 @@
+use crate::activity::SessionActivity;
+use crate::audit::{redacted_statement, AuditLog, PendingAudit};
 use crate::context::local::Portal;
+use crate::query_history::{plan_hash, QueryHistory, SessionQueryHistory};
+use chrono::{DateTime, Utc};
 use datafusion::arrow::datatypes::SchemaRef;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::error::Result as DatafusionResult;
 use datafusion::physical_plan::{ExecutionPlan, RecordBatchStream, SendableRecordBatchStream};
+use datafusion_ext::metrics::AggregatedMetrics;
 use futures::stream::{Stream, StreamExt};
 use serde_json::json;
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Instant;
+use telemetry::metrics::METRICS;
 use telemetry::Tracker;
 use tokio::sync::mpsc;
 use tracing::error;
@@ -19,7 +26,7 @@ use uuid::Uuid;
 const MAX_METRICS_HISTORY: usize = 100;
 
 /// Result type used when we don't know the result of a query yet.
-const UNKNOWN_RESULT_TYPE: &str = "unknown";
+pub(crate) const UNKNOWN_RESULT_TYPE: &str = "unknown";
 
 /// Holds some number of query metrics for a session.
 ///
@@ -28,10 +35,17 @@ const UNKNOWN_RESULT_TYPE: &str = "unknown";
 #[derive(Debug)]
 pub struct SessionMetrics {
     user_id: Uuid,
+    user_name: String,
     database_id: Uuid,
     connection_id: Uuid,
 
     tracker: Arc<Tracker>,
+    /// Audit log shared with all other sessions.
+    audit_log: Option<Arc<AuditLog>>,
+    /// Query history shared with all other sessions.
+    query_history: SessionQueryHistory,
+    /// Activity for this session, registered with all other sessions.
+    activity: SessionActivity,
 
     completed_rx: mpsc::Receiver<QueryMetrics>,
     completed_tx: mpsc::Sender<QueryMetrics>,
@@ -40,18 +54,29 @@ pub struct SessionMetrics {
 }
 
 impl SessionMetrics {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user_id: Uuid,
+        user_name: String,
         database_id: Uuid,
         connection_id: Uuid,
         tracker: Arc<Tracker>,
+        audit_log: Option<Arc<AuditLog>>,
+        query_history: Arc<QueryHistory>,
+        activity: SessionActivity,
     ) -> SessionMetrics {
         let (tx, rx) = mpsc::channel(1);
+        let query_history =
+            SessionQueryHistory::new(query_history, user_name.clone(), connection_id, database_id);
         SessionMetrics {
             user_id,
+            user_name,
             database_id,
             connection_id,
             tracker,
+            audit_log,
+            query_history,
+            activity,
             completed_rx: rx,
             completed_tx: tx,
             metrics: VecDeque::new(),
@@ -101,6 +126,31 @@ impl SessionMetrics {
         }
     }
 
+    /// Name of the user for this session.
+    pub fn user_name(&self) -> &str {
+        &self.user_name
+    }
+
+    /// Database this session is connected to.
+    pub fn database_id(&self) -> Uuid {
+        self.database_id
+    }
+
+    /// Get the audit log if auditing is enabled.
+    pub fn audit_log(&self) -> Option<&Arc<AuditLog>> {
+        self.audit_log.as_ref()
+    }
+
+    /// Get the server-wide query history.
+    pub fn query_history(&self) -> &SessionQueryHistory {
+        &self.query_history
+    }
+
+    /// Get the activity for this session.
+    pub fn activity(&self) -> &SessionActivity {
+        &self.activity
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &QueryMetrics> {
         self.metrics.iter()
     }
@@ -114,6 +164,9 @@ impl SessionMetrics {
 pub enum ExecutionStatus {
     Success,
     Fail,
+    /// Output stream was dropped before it was fully consumed (e.g. the
+    /// client disconnected or closed the portal).
+    Aborted,
     Unknown,
 }
 
@@ -122,6 +175,7 @@ impl ExecutionStatus {
         match self {
             ExecutionStatus::Success => "success",
             ExecutionStatus::Fail => "fail",
+            ExecutionStatus::Aborted => "aborted",
             ExecutionStatus::Unknown => "unknown",
         }
     }
@@ -144,6 +198,23 @@ pub struct QueryMetrics {
     pub elapsed_compute_ns: Option<u64>,
     /// Number of output rows. Currently only set for SELECT queries.
     pub output_rows: Option<u64>,
+    /// Size in bytes of the output batches. Currently only set for SELECT
+    /// queries.
+    pub output_bytes: Option<u64>,
+    /// Bytes read by each data source. Currently only set for SELECT queries.
+    pub bytes_read_by_source: BTreeMap<String, u64>,
+    /// Rows read by each data source. Currently only set for SELECT queries.
+    pub rows_read_by_source: BTreeMap<String, u64>,
+    /// Hash of the query's logical plan.
+    pub plan_hash: Option<u64>,
+    /// When execution of the query started.
+    pub started: Instant,
+    /// Wall-clock time execution of the query started.
+    pub timestamp: DateTime<Utc>,
+    /// Pending audit record if auditing is enabled.
+    pub audit: Option<PendingAudit>,
+    /// Server-wide history to record the query to.
+    pub history: Option<SessionQueryHistory>,
 }
 
 impl QueryMetrics {
@@ -152,17 +223,75 @@ impl QueryMetrics {
     /// The returned set of metrics should be updated during query execution.
     pub fn new_for_portal(portal: &Portal) -> QueryMetrics {
         QueryMetrics {
+            // Query text is kept in the query history (which may be
+            // persisted), so secrets in options and passwords are redacted.
             query_text: portal
                 .stmt
                 .stmt
-                .clone()
-                .map(|stmt| stmt.to_string())
+                .as_ref()
+                .map(|stmt| redacted_statement(stmt, false))
                 .unwrap_or("<empty>".to_string()),
             result_type: UNKNOWN_RESULT_TYPE,
             execution_status: ExecutionStatus::Unknown,
             error_message: None,
             elapsed_compute_ns: None,
             output_rows: None,
+            output_bytes: None,
+            bytes_read_by_source: BTreeMap::new(),
+            rows_read_by_source: BTreeMap::new(),
+            plan_hash: portal.stmt.plan.as_ref().and_then(plan_hash),
+            started: Instant::now(),
+            timestamp: Utc::now(),
+            audit: None,
+            history: None,
+        }
+    }
+
+    /// Update metrics for a query that's been executed using the executed
+    /// plan, also recording data source and spill metrics to the server's
+    /// Prometheus metrics.
+    fn record_plan(&mut self, plan: &dyn ExecutionPlan) {
+        if let Some(exec_metrics) = plan.metrics() {
+            self.elapsed_compute_ns = exec_metrics.elapsed_compute().map(|v| v as u64);
+            self.output_rows = exec_metrics.output_rows().map(|v| v as u64);
+        }
+
+        let agg = AggregatedMetrics::new_from_plan(plan);
+        for (source, bytes) in &agg.bytes_processed_by_source {
+            METRICS
+                .datasource_bytes_read_total
+                .with_label_values(&[source.as_str()])
+                .inc_by(*bytes);
+        }
+        METRICS.spilled_bytes_total.inc_by(agg.spilled_bytes);
+        METRICS.spill_count_total.inc_by(agg.spill_count);
+
+        self.bytes_read_by_source = agg.bytes_processed_by_source.into_iter().collect();
+        self.rows_read_by_source = agg.rows_processed_by_source.into_iter().collect();
+    }
+
+    /// Record the completed query to the server's Prometheus metrics, the
+    /// audit log, and the query history.
+    pub fn observe(&mut self) {
+        let status = self.execution_status.as_str();
+        METRICS.queries_total.with_label_values(&[status]).inc();
+        METRICS
+            .query_duration_seconds
+            .with_label_values(&[status])
+            .observe(self.started.elapsed().as_secs_f64());
+
+        if let Some(audit) = self.audit.take() {
+            audit.record(
+                &self.execution_status,
+                self.result_type,
+                self.error_message.clone(),
+                self.output_rows,
+                self.output_bytes,
+            );
+        }
+
+        if let Some(history) = &self.history {
+            history.record(self);
         }
     }
 }
@@ -185,9 +314,10 @@ impl BatchStreamWithMetricSender {
     pub fn new(
         stream: SendableRecordBatchStream,
         plan: Arc<dyn ExecutionPlan>,
-        pending: QueryMetrics,
+        mut pending: QueryMetrics,
         sender: mpsc::Sender<QueryMetrics>,
     ) -> Self {
+        pending.output_bytes = Some(0);
         BatchStreamWithMetricSender {
             stream,
             plan,
@@ -197,6 +327,22 @@ impl BatchStreamWithMetricSender {
     }
 }
 
+impl Drop for BatchStreamWithMetricSender {
+    fn drop(&mut self) {
+        // Stream dropped without completing or erroring. Still record the
+        // query so that it shows up in the audit log and query history.
+        if let Some(mut metrics) = self.pending.take() {
+            metrics.execution_status = ExecutionStatus::Aborted;
+            metrics.record_plan(self.plan.as_ref());
+            metrics.observe();
+
+            if let Err(e) = self.sender.try_send(metrics) {
+                error!(%e, "failed to send aborted metrics on channel");
+            }
+        }
+    }
+}
+
 impl RecordBatchStream for BatchStreamWithMetricSender {
     fn schema(&self) -> SchemaRef {
         self.stream.schema()
@@ -213,12 +359,8 @@ impl Stream for BatchStreamWithMetricSender {
 
                 if let Some(mut metrics) = self.pending.take() {
                     metrics.execution_status = ExecutionStatus::Success;
-
-                    if let Some(exec_metrics) = self.plan.metrics() {
-                        metrics.elapsed_compute_ns =
-                            exec_metrics.elapsed_compute().map(|v| v as u64);
-                        metrics.output_rows = exec_metrics.output_rows().map(|v| v as u64);
-                    }
+                    metrics.record_plan(self.plan.as_ref());
+                    metrics.observe();
 
                     if let Err(e) = self.sender.try_send(metrics) {
                         error!(%e,"failed to send completed metrics on channel");
@@ -239,11 +381,8 @@ impl Stream for BatchStreamWithMetricSender {
 
                     // The query may have failed, but having these execution
                     // stats may be useful anyways.
-                    if let Some(exec_metrics) = self.plan.metrics() {
-                        metrics.elapsed_compute_ns =
-                            exec_metrics.elapsed_compute().map(|v| v as u64);
-                        metrics.output_rows = exec_metrics.output_rows().map(|v| v as u64);
-                    }
+                    metrics.record_plan(self.plan.as_ref());
+                    metrics.observe();
 
                     if let Err(e) = self.sender.try_send(metrics) {
                         error!(%e,"failed to send completed metrics on channel");
@@ -252,6 +391,12 @@ impl Stream for BatchStreamWithMetricSender {
 
                 Poll::Ready(Some(Err(e)))
             }
+            Poll::Ready(Some(Ok(batch))) => {
+                if let Some(metrics) = self.pending.as_mut() {
+                    *metrics.output_bytes.get_or_insert(0) += batch.get_array_memory_size() as u64;
+                }
+                Poll::Ready(Some(Ok(batch)))
+            }
             poll => poll,
         }
     }
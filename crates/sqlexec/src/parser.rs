@@ -4,7 +4,7 @@ use crate::errors::Result;
 use datafusion::sql::sqlparser::ast::{self, Ident, ObjectName};
 use datafusion::sql::sqlparser::dialect::GenericDialect;
 use datafusion::sql::sqlparser::keywords::Keyword;
-use datafusion::sql::sqlparser::parser::{Parser, ParserError};
+use datafusion::sql::sqlparser::parser::{IsOptional, Parser, ParserError};
 use datafusion::sql::sqlparser::tokenizer::{Token, Tokenizer, Word};
 use std::collections::BTreeMap;
 use std::collections::VecDeque;
@@ -18,6 +18,12 @@ pub fn parse_sql(sql: &str) -> Result<VecDeque<StatementWithExtensions>> {
     Ok(stmts)
 }
 
+/// Parse a single sql expression, e.g. a column default stored in the catalog.
+pub fn parse_sql_expr(sql: &str) -> Result<ast::Expr, ParserError> {
+    let dialect = GenericDialect {};
+    Parser::new(&dialect).try_with_sql(sql)?.parse_expr()
+}
+
 /// DDL extension for GlareDB's external tables.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CreateExternalTableStmt {
@@ -263,6 +269,436 @@ impl fmt::Display for DropCredentialsStmt {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateRoleStmt {
+    pub name: Ident,
+    pub if_not_exists: bool,
+    /// If this was created with `CREATE USER` instead of `CREATE ROLE`.
+    ///
+    /// Users can log in by default, roles cannot.
+    pub is_user: bool,
+    pub superuser: Option<bool>,
+    pub login: Option<bool>,
+    pub password: Option<String>,
+}
+
+impl fmt::Display for CreateRoleStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CREATE ")?;
+        if self.is_user {
+            write!(f, "USER ")?;
+        } else {
+            write!(f, "ROLE ")?;
+        }
+        if self.if_not_exists {
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        write!(f, "{}", self.name)?;
+        if self.superuser.is_some() || self.login.is_some() || self.password.is_some() {
+            write!(f, " WITH")?;
+        }
+        match self.superuser {
+            Some(true) => write!(f, " SUPERUSER")?,
+            Some(false) => write!(f, " NOSUPERUSER")?,
+            None => (),
+        }
+        match self.login {
+            Some(true) => write!(f, " LOGIN")?,
+            Some(false) => write!(f, " NOLOGIN")?,
+            None => (),
+        }
+        if let Some(password) = &self.password {
+            write!(f, " PASSWORD '{}'", password.replace('\'', "''"))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropRoleStmt {
+    pub names: Vec<Ident>,
+    pub if_exists: bool,
+}
+
+impl fmt::Display for DropRoleStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DROP ROLE ")?;
+        if self.if_exists {
+            write!(f, "IF EXISTS ")?;
+        }
+        let mut sep = "";
+        for name in self.names.iter() {
+            write!(f, "{sep}{name}")?;
+            sep = ", ";
+        }
+        Ok(())
+    }
+}
+
+/// A `CREATE TABLE` statement with a `PARTITION BY` clause.
+///
+/// The table definition itself is parsed by `sqlparser`, the partition columns
+/// are parsed by us.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateTableStmt {
+    /// The underlying `CREATE TABLE` statement.
+    pub stmt: ast::Statement,
+    /// Columns to partition the table by.
+    pub partition_by: Vec<Ident>,
+}
+
+impl fmt::Display for CreateTableStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} PARTITION BY (", self.stmt)?;
+        let mut sep = "";
+        for col in self.partition_by.iter() {
+            write!(f, "{sep}{col}")?;
+            sep = ", ";
+        }
+        write!(f, ")")
+    }
+}
+
+/// `OPTIMIZE [TABLE] <name> [ZORDER BY (col, ...)]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptimizeStmt {
+    /// Table to compact.
+    pub name: ObjectName,
+    /// Columns to cluster the compacted files by.
+    pub zorder_by: Vec<Ident>,
+}
+
+impl fmt::Display for OptimizeStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OPTIMIZE {}", self.name)?;
+        if !self.zorder_by.is_empty() {
+            write!(f, " ZORDER BY (")?;
+            let mut sep = "";
+            for col in self.zorder_by.iter() {
+                write!(f, "{sep}{col}")?;
+                sep = ", ";
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+/// `VACUUM [TABLE] <name> [RETAIN <n> HOURS]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VacuumStmt {
+    /// Table to vacuum.
+    pub name: ObjectName,
+    /// Optional retention period for unreferenced files. Must not be shorter
+    /// than the table's configured retention.
+    pub retain_hours: Option<u64>,
+}
+
+impl fmt::Display for VacuumStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VACUUM {}", self.name)?;
+        if let Some(hours) = self.retain_hours {
+            write!(f, " RETAIN {hours} HOURS")?;
+        }
+        Ok(())
+    }
+}
+
+/// `CREATE [OR REPLACE] MATERIALIZED VIEW [IF NOT EXISTS] <name> [(col, ...)]
+/// [REFRESH EVERY '<interval>'] AS <query>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateMaterializedViewStmt {
+    /// Name of the view.
+    pub name: ObjectName,
+    /// Replace the view if it exists.
+    pub or_replace: bool,
+    /// Optionally don't error if the view exists.
+    pub if_not_exists: bool,
+    /// Optional column aliases.
+    pub columns: Vec<Ident>,
+    /// How often the view should be refreshed, e.g. '1 hour'.
+    pub refresh_every: Option<String>,
+    /// The query whose results are stored.
+    pub query: Box<ast::Query>,
+}
+
+impl fmt::Display for CreateMaterializedViewStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CREATE {or_replace}MATERIALIZED VIEW {if_not_exists}{name}",
+            or_replace = if self.or_replace { "OR REPLACE " } else { "" },
+            if_not_exists = if self.if_not_exists {
+                "IF NOT EXISTS "
+            } else {
+                ""
+            },
+            name = self.name,
+        )?;
+        if !self.columns.is_empty() {
+            write!(f, " (")?;
+            let mut sep = "";
+            for col in self.columns.iter() {
+                write!(f, "{sep}{col}")?;
+                sep = ", ";
+            }
+            write!(f, ")")?;
+        }
+        if let Some(interval) = &self.refresh_every {
+            let interval = ast::Value::SingleQuotedString(interval.clone());
+            write!(f, " REFRESH EVERY {interval}")?;
+        }
+        write!(f, " AS {}", self.query)
+    }
+}
+
+/// `REFRESH MATERIALIZED VIEW <name>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshMaterializedViewStmt {
+    /// Name of the view.
+    pub name: ObjectName,
+}
+
+impl fmt::Display for RefreshMaterializedViewStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "REFRESH MATERIALIZED VIEW {}", self.name)
+    }
+}
+
+/// What a SQL function returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FunctionReturns {
+    /// `RETURNS <type>`
+    Scalar(ast::DataType),
+    /// `RETURNS TABLE [(col type, ...)]`
+    Table(Vec<ast::ColumnDef>),
+}
+
+impl fmt::Display for FunctionReturns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scalar(data_type) => write!(f, "{data_type}"),
+            Self::Table(columns) => {
+                write!(f, "TABLE")?;
+                if !columns.is_empty() {
+                    write!(f, " (")?;
+                    let mut sep = "";
+                    for col in columns.iter() {
+                        write!(f, "{sep}{col}")?;
+                        sep = ", ";
+                    }
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// `CREATE [OR REPLACE] FUNCTION <name> ([param type, ...])
+/// RETURNS { <type> | TABLE [(col type, ...)] } AS '<body>'`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateFunctionStmt {
+    /// Name of the function.
+    pub name: ObjectName,
+    /// Replace the function if it exists.
+    pub or_replace: bool,
+    /// Function parameters.
+    pub params: Vec<ast::ColumnDef>,
+    /// Return type of the function.
+    pub returns: FunctionReturns,
+    /// SQL body, an expression for scalar functions or a query for table
+    /// functions.
+    pub body: String,
+}
+
+impl fmt::Display for CreateFunctionStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CREATE {or_replace}FUNCTION {name}(",
+            or_replace = if self.or_replace { "OR REPLACE " } else { "" },
+            name = self.name,
+        )?;
+        let mut sep = "";
+        for param in self.params.iter() {
+            write!(f, "{sep}{param}")?;
+            sep = ", ";
+        }
+        let body = ast::Value::SingleQuotedString(self.body.clone());
+        write!(f, ") RETURNS {} AS {body}", self.returns)
+    }
+}
+
+/// The type of object privileges are being granted on or revoked from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrantObjectType {
+    Database,
+    Schema,
+    Table,
+}
+
+impl fmt::Display for GrantObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrantObjectType::Database => write!(f, "DATABASE"),
+            GrantObjectType::Schema => write!(f, "SCHEMA"),
+            GrantObjectType::Table => write!(f, "TABLE"),
+        }
+    }
+}
+
+/// Privileges and the objects they apply to in a GRANT or REVOKE statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivilegesOnObjects {
+    /// Privileges to grant or revoke. `None` if `ALL PRIVILEGES`.
+    pub privileges: Option<Vec<Ident>>,
+    pub object_type: GrantObjectType,
+    pub objects: Vec<ObjectName>,
+}
+
+impl fmt::Display for PrivilegesOnObjects {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.privileges {
+            Some(privileges) => {
+                let mut sep = "";
+                for privilege in privileges.iter() {
+                    write!(f, "{sep}{privilege}")?;
+                    sep = ", ";
+                }
+            }
+            None => write!(f, "ALL PRIVILEGES")?,
+        }
+        write!(f, " ON {} ", self.object_type)?;
+        let mut sep = "";
+        for object in self.objects.iter() {
+            write!(f, "{sep}{object}")?;
+            sep = ", ";
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrantStmt {
+    pub privileges: PrivilegesOnObjects,
+    pub roles: Vec<Ident>,
+}
+
+impl fmt::Display for GrantStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "GRANT {} TO ", self.privileges)?;
+        let mut sep = "";
+        for role in self.roles.iter() {
+            write!(f, "{sep}{role}")?;
+            sep = ", ";
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevokeStmt {
+    pub privileges: PrivilegesOnObjects,
+    pub roles: Vec<Ident>,
+}
+
+impl fmt::Display for RevokeStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "REVOKE {} FROM ", self.privileges)?;
+        let mut sep = "";
+        for role in self.roles.iter() {
+            write!(f, "{sep}{role}")?;
+            sep = ", ";
+        }
+        Ok(())
+    }
+}
+
+/// The type of object a comment is set on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentObjectType {
+    Database,
+    Schema,
+    Table,
+    View,
+    Column,
+}
+
+impl fmt::Display for CommentObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommentObjectType::Database => write!(f, "DATABASE"),
+            CommentObjectType::Schema => write!(f, "SCHEMA"),
+            CommentObjectType::Table => write!(f, "TABLE"),
+            CommentObjectType::View => write!(f, "VIEW"),
+            CommentObjectType::Column => write!(f, "COLUMN"),
+        }
+    }
+}
+
+/// `COMMENT ON <object_type> <name> IS { '<comment>' | NULL }`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentStmt {
+    pub object_type: CommentObjectType,
+    /// Name of the object. For columns, the last part is the column name.
+    pub name: ObjectName,
+    /// The comment, `None` if the comment is being removed.
+    pub comment: Option<String>,
+}
+
+impl fmt::Display for CommentStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "COMMENT ON {} {} IS ", self.object_type, self.name)?;
+        match &self.comment {
+            Some(comment) => write!(f, "'{}'", comment.replace('\'', "''")),
+            None => write!(f, "NULL"),
+        }
+    }
+}
+
+/// The type of objects listed by a `SHOW` statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShowObjectType {
+    Databases,
+    Schemas,
+    Tables,
+}
+
+impl fmt::Display for ShowObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShowObjectType::Databases => write!(f, "DATABASES"),
+            ShowObjectType::Schemas => write!(f, "SCHEMAS"),
+            ShowObjectType::Tables => write!(f, "TABLES"),
+        }
+    }
+}
+
+/// `SHOW { DATABASES | SCHEMAS [FROM <database>] | TABLES [FROM [<database>.]<schema>] }
+/// [LIKE '<pattern>' | ILIKE '<pattern>' | WHERE <expr>]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShowStmt {
+    pub object_type: ShowObjectType,
+    /// The database (for schemas) or schema (for tables) to list objects
+    /// from.
+    pub from: Option<ObjectName>,
+    pub filter: Option<ast::ShowStatementFilter>,
+}
+
+impl fmt::Display for ShowStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SHOW {}", self.object_type)?;
+        if let Some(from) = &self.from {
+            write!(f, " FROM {from}")?;
+        }
+        if let Some(filter) = &self.filter {
+            write!(f, " {filter}")?;
+        }
+        Ok(())
+    }
+}
+
 /// A source for a COPY TO statement.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CopyToSource {
@@ -313,6 +749,8 @@ impl fmt::Display for CopyToStmt {
 pub enum StatementWithExtensions {
     /// Statement parsed by `sqlparser`.
     Statement(ast::Statement),
+    /// Create table with partitioning extension.
+    CreateTable(CreateTableStmt),
     /// Create external table extension.
     CreateExternalTable(CreateExternalTableStmt),
     /// Create external database extension.
@@ -333,12 +771,35 @@ pub enum StatementWithExtensions {
     DropCredentials(DropCredentialsStmt),
     /// Copy To extension.
     CopyTo(CopyToStmt),
+    /// Create role (or user) extension.
+    CreateRole(CreateRoleStmt),
+    /// Drop role (or user) extension.
+    DropRole(DropRoleStmt),
+    /// Grant privileges extension.
+    Grant(GrantStmt),
+    /// Revoke privileges extension.
+    Revoke(RevokeStmt),
+    /// Optimize (compact) table extension.
+    Optimize(OptimizeStmt),
+    /// Vacuum table extension.
+    Vacuum(VacuumStmt),
+    /// Create materialized view extension.
+    CreateMaterializedView(CreateMaterializedViewStmt),
+    /// Refresh materialized view extension.
+    RefreshMaterializedView(RefreshMaterializedViewStmt),
+    /// Create (SQL) function extension.
+    CreateFunction(CreateFunctionStmt),
+    /// Comment on object extension.
+    Comment(CommentStmt),
+    /// Show databases, schemas, or tables extension.
+    Show(ShowStmt),
 }
 
 impl fmt::Display for StatementWithExtensions {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             StatementWithExtensions::Statement(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::CreateTable(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::CreateExternalTable(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::CreateExternalDatabase(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::DropDatabase(stmt) => write!(f, "{}", stmt),
@@ -349,6 +810,17 @@ impl fmt::Display for StatementWithExtensions {
             StatementWithExtensions::CreateCredentials(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::DropCredentials(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::CopyTo(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::CreateRole(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::DropRole(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Grant(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Revoke(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Optimize(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Vacuum(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::CreateMaterializedView(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::RefreshMaterializedView(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::CreateFunction(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Comment(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Show(stmt) => write!(f, "{}", stmt),
         }
     }
 }
@@ -408,9 +880,44 @@ impl<'a> CustomParser<'a> {
                     self.parser.next_token();
                     self.parse_copy()
                 }
-                _ => Ok(StatementWithExtensions::Statement(
-                    self.parser.parse_statement()?,
-                )),
+                Keyword::GRANT => {
+                    self.parser.next_token();
+                    self.parse_grant()
+                }
+                Keyword::REVOKE => {
+                    self.parser.next_token();
+                    self.parse_revoke()
+                }
+                Keyword::COMMENT => {
+                    self.parser.next_token();
+                    self.parse_comment()
+                }
+                Keyword::SHOW => {
+                    // Listing objects is handled here, everything else (e.g.
+                    // showing variables) is left to sqlparser.
+                    let next = self.parser.peek_nth_token(1).to_string().to_uppercase();
+                    if matches!(next.as_str(), "DATABASES" | "SCHEMAS" | "TABLES") {
+                        self.parser.next_token();
+                        self.parse_show()
+                    } else {
+                        Ok(StatementWithExtensions::Statement(
+                            self.parser.parse_statement()?,
+                        ))
+                    }
+                }
+                _ => {
+                    if self.consume_token(&Token::make_keyword("OPTIMIZE")) {
+                        self.parse_optimize()
+                    } else if self.consume_token(&Token::make_keyword("VACUUM")) {
+                        self.parse_vacuum()
+                    } else if self.consume_token(&Token::make_keyword("REFRESH")) {
+                        self.parse_refresh()
+                    } else {
+                        Ok(StatementWithExtensions::Statement(
+                            self.parser.parse_statement()?,
+                        ))
+                    }
+                }
             },
             _ => Ok(StatementWithExtensions::Statement(
                 self.parser.parse_statement()?,
@@ -441,6 +948,21 @@ impl<'a> CustomParser<'a> {
         } else if self.parser.parse_keyword(Keyword::CREDENTIALS) {
             // CREATE CREDENTIALS ...
             self.parse_create_credentials()
+        } else if self.parser.parse_keyword(Keyword::ROLE) {
+            // CREATE ROLE ...
+            self.parse_create_role(false)
+        } else if self.parser.parse_keyword(Keyword::USER) {
+            // CREATE USER ...
+            self.parse_create_role(true)
+        } else if self
+            .parser
+            .parse_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW])
+        {
+            // CREATE MATERIALIZED VIEW ...
+            self.parse_create_materialized_view(or_replace)
+        } else if self.parser.parse_keyword(Keyword::FUNCTION) {
+            // CREATE FUNCTION ...
+            self.parse_create_function(or_replace)
         } else {
             // Fall back to underlying parser.
 
@@ -450,9 +972,27 @@ impl<'a> CustomParser<'a> {
                 self.parser.prev_token();
             }
 
-            Ok(StatementWithExtensions::Statement(
-                self.parser.parse_create()?,
-            ))
+            let stmt = self.parser.parse_create()?;
+
+            if matches!(stmt, ast::Statement::CreateTable { .. })
+                && self
+                    .parser
+                    .parse_keywords(&[Keyword::PARTITION, Keyword::BY])
+            {
+                // CREATE TABLE ... PARTITION BY (col, ...)
+                self.expect_token(&Token::LParen)?;
+                let partition_by = self
+                    .parser
+                    .parse_comma_separated(Parser::parse_identifier)?;
+                self.expect_token(&Token::RParen)?;
+
+                return Ok(StatementWithExtensions::CreateTable(CreateTableStmt {
+                    stmt,
+                    partition_by,
+                }));
+            }
+
+            Ok(StatementWithExtensions::Statement(stmt))
         }
     }
 
@@ -776,6 +1316,11 @@ impl<'a> CustomParser<'a> {
         } else if self.consume_token(&Token::make_keyword("CREDENTIALS")) {
             // DROP CREDENTIALS ...
             self.parse_drop_credentials()
+        } else if self.parser.parse_keyword(Keyword::ROLE)
+            || self.parser.parse_keyword(Keyword::USER)
+        {
+            // DROP ROLE ... | DROP USER ...
+            self.parse_drop_role()
         } else {
             // Fall back to underlying parser.
             Ok(StatementWithExtensions::Statement(
@@ -834,37 +1379,395 @@ impl<'a> CustomParser<'a> {
         ))
     }
 
-    fn parse_alter_database(&mut self) -> Result<StatementWithExtensions, ParserError> {
-        let name = self.parser.parse_identifier()?;
-        validate_ident(&name)?;
+    fn parse_drop_role(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
 
-        if !self.parser.parse_keywords(&[Keyword::RENAME, Keyword::TO]) {
-            return self.expected("RENAME TO", self.parser.peek_token().token);
-        }
+        let names = self
+            .parser
+            .parse_comma_separated(Parser::parse_identifier)?;
 
-        let new_name = self.parser.parse_identifier()?;
-        validate_ident(&new_name)?;
+        for name in names.iter() {
+            validate_ident(name)?;
+        }
 
-        Ok(StatementWithExtensions::AlterDatabaseRename(
-            AlterDatabaseRenameStmt { name, new_name },
-        ))
+        Ok(StatementWithExtensions::DropRole(DropRoleStmt {
+            names,
+            if_exists,
+        }))
     }
 
-    fn parse_alter_tunnel(&mut self) -> Result<StatementWithExtensions, ParserError> {
-        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+    fn parse_create_role(&mut self, is_user: bool) -> Result<StatementWithExtensions, ParserError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
 
         let name = self.parser.parse_identifier()?;
         validate_ident(&name)?;
 
-        let mut action = None;
-
-        if self.consume_token(&Token::make_keyword("ROTATE"))
-            && self.consume_token(&Token::make_keyword("KEYS"))
-        {
-            action = Some(AlterTunnelAction::RotateKeys);
-        }
+        let _ = self.parser.parse_keyword(Keyword::WITH);
 
-        if let Some(action) = action {
+        let mut superuser = None;
+        let mut login = None;
+        let mut password = None;
+        loop {
+            if self.consume_token(&Token::make_keyword("SUPERUSER")) {
+                superuser = Some(true);
+            } else if self.consume_token(&Token::make_keyword("NOSUPERUSER")) {
+                superuser = Some(false);
+            } else if self.consume_token(&Token::make_keyword("LOGIN")) {
+                login = Some(true);
+            } else if self.consume_token(&Token::make_keyword("NOLOGIN")) {
+                login = Some(false);
+            } else if self.parser.parse_keyword(Keyword::PASSWORD) {
+                password = Some(self.parser.parse_literal_string()?);
+            } else {
+                break;
+            }
+        }
+
+        Ok(StatementWithExtensions::CreateRole(CreateRoleStmt {
+            name,
+            if_not_exists,
+            is_user,
+            superuser,
+            login,
+            password,
+        }))
+    }
+
+    /// Parse a SQL GRANT statement.
+    fn parse_grant(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let privileges = self.parse_privileges_on_objects()?;
+        self.parser.expect_keyword(Keyword::TO)?;
+        let roles = self.parse_role_names()?;
+        Ok(StatementWithExtensions::Grant(GrantStmt {
+            privileges,
+            roles,
+        }))
+    }
+
+    /// Parse a SQL REVOKE statement.
+    fn parse_revoke(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let privileges = self.parse_privileges_on_objects()?;
+        self.parser.expect_keyword(Keyword::FROM)?;
+        let roles = self.parse_role_names()?;
+        Ok(StatementWithExtensions::Revoke(RevokeStmt {
+            privileges,
+            roles,
+        }))
+    }
+
+    /// Parse `COMMENT ON <object_type> <name> IS { '<comment>' | NULL }`.
+    fn parse_comment(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        self.parser.expect_keyword(Keyword::ON)?;
+
+        let object_type = if self.parser.parse_keyword(Keyword::DATABASE) {
+            CommentObjectType::Database
+        } else if self.parser.parse_keyword(Keyword::SCHEMA) {
+            CommentObjectType::Schema
+        } else if self.parser.parse_keyword(Keyword::TABLE) {
+            CommentObjectType::Table
+        } else if self.parser.parse_keyword(Keyword::VIEW) {
+            CommentObjectType::View
+        } else if self.parser.parse_keyword(Keyword::COLUMN) {
+            CommentObjectType::Column
+        } else {
+            return self.expected(
+                "DATABASE, SCHEMA, TABLE, VIEW or COLUMN",
+                self.parser.peek_token().token,
+            );
+        };
+
+        let name = self.parser.parse_object_name()?;
+        validate_object_name(&name)?;
+        if object_type == CommentObjectType::Column && name.0.len() < 2 {
+            return Err(ParserError::ParserError(format!(
+                "Expected column name to be qualified with a table name, found: {name}"
+            )));
+        }
+
+        self.parser.expect_keyword(Keyword::IS)?;
+        let comment = if self.parser.parse_keyword(Keyword::NULL) {
+            None
+        } else {
+            Some(self.parser.parse_literal_string()?)
+        };
+
+        Ok(StatementWithExtensions::Comment(CommentStmt {
+            object_type,
+            name,
+            comment,
+        }))
+    }
+
+    /// Parse `SHOW { DATABASES | SCHEMAS [FROM <database>] | TABLES [FROM
+    /// [<database>.]<schema>] } [<filter>]`.
+    fn parse_show(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let object_type = if self.consume_token(&Token::make_keyword("DATABASES")) {
+            ShowObjectType::Databases
+        } else if self.consume_token(&Token::make_keyword("SCHEMAS")) {
+            ShowObjectType::Schemas
+        } else if self.consume_token(&Token::make_keyword("TABLES")) {
+            ShowObjectType::Tables
+        } else {
+            return self.expected(
+                "DATABASES, SCHEMAS or TABLES",
+                self.parser.peek_token().token,
+            );
+        };
+
+        let from = if object_type != ShowObjectType::Databases
+            && self
+                .parser
+                .parse_one_of_keywords(&[Keyword::FROM, Keyword::IN])
+                .is_some()
+        {
+            let name = self.parser.parse_object_name()?;
+            validate_object_name(&name)?;
+            let max_parts = match object_type {
+                ShowObjectType::Tables => 2,
+                _ => 1,
+            };
+            if name.0.len() > max_parts {
+                return Err(ParserError::ParserError(format!(
+                    "Too many parts in name for SHOW {object_type}, found: {name}"
+                )));
+            }
+            Some(name)
+        } else {
+            None
+        };
+
+        let filter = self.parser.parse_show_statement_filter()?;
+
+        Ok(StatementWithExtensions::Show(ShowStmt {
+            object_type,
+            from,
+            filter,
+        }))
+    }
+
+    /// Parse `<privileges> ON [DATABASE | SCHEMA | TABLE] <objects>`.
+    fn parse_privileges_on_objects(&mut self) -> Result<PrivilegesOnObjects, ParserError> {
+        let privileges = if self.parser.parse_keyword(Keyword::ALL) {
+            let _ = self.parser.parse_keyword(Keyword::PRIVILEGES);
+            None
+        } else {
+            Some(
+                self.parser
+                    .parse_comma_separated(Parser::parse_identifier)?,
+            )
+        };
+
+        self.parser.expect_keyword(Keyword::ON)?;
+
+        let object_type = if self.parser.parse_keyword(Keyword::DATABASE) {
+            GrantObjectType::Database
+        } else if self.parser.parse_keyword(Keyword::SCHEMA) {
+            GrantObjectType::Schema
+        } else {
+            let _ = self.parser.parse_keyword(Keyword::TABLE);
+            GrantObjectType::Table
+        };
+
+        let objects = self
+            .parser
+            .parse_comma_separated(Parser::parse_object_name)?;
+        for object in objects.iter() {
+            validate_object_name(object)?;
+        }
+
+        Ok(PrivilegesOnObjects {
+            privileges,
+            object_type,
+            objects,
+        })
+    }
+
+    fn parse_role_names(&mut self) -> Result<Vec<Ident>, ParserError> {
+        let roles = self
+            .parser
+            .parse_comma_separated(Parser::parse_identifier)?;
+        for role in roles.iter() {
+            validate_ident(role)?;
+        }
+        Ok(roles)
+    }
+
+    /// Parse `OPTIMIZE [TABLE] <name> [ZORDER BY (col, ...)]`.
+    fn parse_optimize(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let _ = self.parser.parse_keyword(Keyword::TABLE);
+
+        let name = self.parser.parse_object_name()?;
+        validate_object_name(&name)?;
+
+        let mut zorder_by = Vec::new();
+        if self.consume_token(&Token::make_keyword("ZORDER")) {
+            self.parser.expect_keyword(Keyword::BY)?;
+            self.expect_token(&Token::LParen)?;
+            zorder_by = self
+                .parser
+                .parse_comma_separated(Parser::parse_identifier)?;
+            self.expect_token(&Token::RParen)?;
+        }
+
+        Ok(StatementWithExtensions::Optimize(OptimizeStmt {
+            name,
+            zorder_by,
+        }))
+    }
+
+    fn parse_create_materialized_view(
+        &mut self,
+        or_replace: bool,
+    ) -> Result<StatementWithExtensions, ParserError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+
+        let name = self.parser.parse_object_name()?;
+        validate_object_name(&name)?;
+
+        let columns = self
+            .parser
+            .parse_parenthesized_column_list(IsOptional::Optional, false)?;
+
+        let mut refresh_every = None;
+        if self.consume_token(&Token::make_keyword("REFRESH")) {
+            self.expect_token(&Token::make_keyword("EVERY"))?;
+            let _ = self.parser.parse_keyword(Keyword::INTERVAL);
+            refresh_every = Some(self.parser.parse_literal_string()?);
+        }
+
+        self.parser.expect_keyword(Keyword::AS)?;
+        let query = Box::new(self.parser.parse_query()?);
+
+        Ok(StatementWithExtensions::CreateMaterializedView(
+            CreateMaterializedViewStmt {
+                name,
+                or_replace,
+                if_not_exists,
+                columns,
+                refresh_every,
+                query,
+            },
+        ))
+    }
+
+    /// Parse `CREATE [OR REPLACE] FUNCTION ...`.
+    fn parse_create_function(
+        &mut self,
+        or_replace: bool,
+    ) -> Result<StatementWithExtensions, ParserError> {
+        let name = self.parser.parse_object_name()?;
+        validate_object_name(&name)?;
+
+        self.parser.expect_token(&Token::LParen)?;
+        let params = if self.parser.consume_token(&Token::RParen) {
+            Vec::new()
+        } else {
+            let params = self
+                .parser
+                .parse_comma_separated(|p| p.parse_column_def())?;
+            self.parser.expect_token(&Token::RParen)?;
+            params
+        };
+
+        self.parser.expect_keyword(Keyword::RETURNS)?;
+        let returns = if self.parser.parse_keyword(Keyword::TABLE) {
+            let mut columns = Vec::new();
+            if self.parser.consume_token(&Token::LParen) {
+                columns = self
+                    .parser
+                    .parse_comma_separated(|p| p.parse_column_def())?;
+                self.parser.expect_token(&Token::RParen)?;
+            }
+            FunctionReturns::Table(columns)
+        } else {
+            FunctionReturns::Scalar(self.parser.parse_data_type()?)
+        };
+
+        self.parser.expect_keyword(Keyword::AS)?;
+        let body = match self.parser.next_token().token {
+            Token::SingleQuotedString(s) => s,
+            Token::DollarQuotedString(s) => s.value,
+            other => return self.expected("function body as a string", other),
+        };
+
+        Ok(StatementWithExtensions::CreateFunction(
+            CreateFunctionStmt {
+                name,
+                or_replace,
+                params,
+                returns,
+                body,
+            },
+        ))
+    }
+
+    /// Parse `REFRESH MATERIALIZED VIEW <name>`.
+    fn parse_refresh(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        self.parser
+            .expect_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW])?;
+
+        let name = self.parser.parse_object_name()?;
+        validate_object_name(&name)?;
+
+        Ok(StatementWithExtensions::RefreshMaterializedView(
+            RefreshMaterializedViewStmt { name },
+        ))
+    }
+
+    /// Parse `VACUUM [TABLE] <name> [RETAIN <n> HOURS]`.
+    fn parse_vacuum(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let _ = self.parser.parse_keyword(Keyword::TABLE);
+
+        let name = self.parser.parse_object_name()?;
+        validate_object_name(&name)?;
+
+        let mut retain_hours = None;
+        if self.consume_token(&Token::make_keyword("RETAIN")) {
+            retain_hours = Some(self.parser.parse_literal_uint()?);
+            self.expect_token(&Token::make_keyword("HOURS"))?;
+        }
+
+        Ok(StatementWithExtensions::Vacuum(VacuumStmt {
+            name,
+            retain_hours,
+        }))
+    }
+
+    fn parse_alter_database(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let name = self.parser.parse_identifier()?;
+        validate_ident(&name)?;
+
+        if !self.parser.parse_keywords(&[Keyword::RENAME, Keyword::TO]) {
+            return self.expected("RENAME TO", self.parser.peek_token().token);
+        }
+
+        let new_name = self.parser.parse_identifier()?;
+        validate_ident(&new_name)?;
+
+        Ok(StatementWithExtensions::AlterDatabaseRename(
+            AlterDatabaseRenameStmt { name, new_name },
+        ))
+    }
+
+    fn parse_alter_tunnel(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+
+        let name = self.parser.parse_identifier()?;
+        validate_ident(&name)?;
+
+        let mut action = None;
+
+        if self.consume_token(&Token::make_keyword("ROTATE"))
+            && self.consume_token(&Token::make_keyword("KEYS"))
+        {
+            action = Some(AlterTunnelAction::RotateKeys);
+        }
+
+        if let Some(action) = action {
             Ok(StatementWithExtensions::AlterTunnel(AlterTunnelStmt {
                 name,
                 if_exists,
@@ -1094,6 +1997,236 @@ mod tests {
         }
     }
 
+    #[test]
+    fn create_role_roundtrips() {
+        let test_cases = [
+            "CREATE ROLE analyst",
+            "CREATE ROLE IF NOT EXISTS analyst",
+            "CREATE USER alice WITH LOGIN",
+            "CREATE ROLE admin WITH SUPERUSER NOLOGIN",
+            "CREATE USER bob WITH PASSWORD 'it''s secret'",
+        ];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+    }
+
+    #[test]
+    fn create_table_partition_by_roundtrips() {
+        let test_cases = [
+            "CREATE TABLE events (id INT, event_date DATE) PARTITION BY (event_date)",
+            "CREATE TABLE IF NOT EXISTS events (id INT, region TEXT, day DATE) PARTITION BY (region, day)",
+        ];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert!(matches!(stmt, StatementWithExtensions::CreateTable(_)));
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+
+        // Missing parentheses.
+        CustomParser::parse_sql("CREATE TABLE events (a INT) PARTITION BY a").unwrap_err();
+    }
+
+    #[test]
+    fn optimize_vacuum_roundtrips() {
+        let test_cases = [
+            "OPTIMIZE events",
+            "OPTIMIZE public.events ZORDER BY (region, day)",
+            "VACUUM events",
+            "VACUUM public.events RETAIN 24 HOURS",
+        ];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+
+        // Optional TABLE keyword.
+        let stmt = CustomParser::parse_sql("OPTIMIZE TABLE events")
+            .unwrap()
+            .pop_front()
+            .unwrap();
+        assert_eq!("OPTIMIZE events", stmt.to_string().as_str());
+
+        CustomParser::parse_sql("OPTIMIZE events ZORDER BY region").unwrap_err();
+        CustomParser::parse_sql("VACUUM events RETAIN 24").unwrap_err();
+    }
+
+    #[test]
+    fn materialized_view_roundtrips() {
+        let test_cases = [
+            "CREATE MATERIALIZED VIEW mv AS SELECT * FROM t",
+            "CREATE OR REPLACE MATERIALIZED VIEW IF NOT EXISTS public.mv (a, b) AS SELECT 1, 2",
+            "CREATE MATERIALIZED VIEW mv REFRESH EVERY '1 hour' AS SELECT count(*) FROM t",
+            "REFRESH MATERIALIZED VIEW public.mv",
+        ];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+
+        // Optional INTERVAL keyword.
+        let stmt = CustomParser::parse_sql(
+            "CREATE MATERIALIZED VIEW mv REFRESH EVERY INTERVAL '5 minutes' AS SELECT 1",
+        )
+        .unwrap()
+        .pop_front()
+        .unwrap();
+        assert_eq!(
+            "CREATE MATERIALIZED VIEW mv REFRESH EVERY '5 minutes' AS SELECT 1",
+            stmt.to_string().as_str()
+        );
+
+        CustomParser::parse_sql("CREATE MATERIALIZED VIEW mv REFRESH '1 hour' AS SELECT 1")
+            .unwrap_err();
+        CustomParser::parse_sql("REFRESH VIEW mv").unwrap_err();
+    }
+
+    #[test]
+    fn create_function_roundtrips() {
+        let test_cases = [
+            "CREATE FUNCTION f() RETURNS INT AS '1'",
+            "CREATE OR REPLACE FUNCTION public.add(a INT, b INT) RETURNS BIGINT AS 'a + b'",
+            "CREATE FUNCTION f(n INT) RETURNS TABLE AS 'SELECT * FROM t LIMIT n'",
+            "CREATE FUNCTION f(n INT) RETURNS TABLE (a INT, b TEXT) AS 'SELECT a, b FROM t WHERE a > n'",
+            "CREATE FUNCTION f(s TEXT) RETURNS TEXT AS 'concat(s, ''!'')'",
+        ];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+
+        // Dollar quoted bodies.
+        let stmt =
+            CustomParser::parse_sql("CREATE FUNCTION f(s TEXT) RETURNS TEXT AS $$s || 'a'$$")
+                .unwrap()
+                .pop_front()
+                .unwrap();
+        assert_eq!(
+            "CREATE FUNCTION f(s TEXT) RETURNS TEXT AS 's || ''a'''",
+            stmt.to_string().as_str()
+        );
+
+        CustomParser::parse_sql("CREATE FUNCTION f(a INT) AS 'a'").unwrap_err();
+        CustomParser::parse_sql("CREATE FUNCTION f(a INT) RETURNS INT AS a").unwrap_err();
+    }
+
+    #[test]
+    fn drop_role_roundtrips() {
+        let test_cases = ["DROP ROLE analyst", "DROP ROLE IF EXISTS analyst, alice"];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+    }
+
+    #[test]
+    fn grant_revoke_roundtrips() {
+        let test_cases = [
+            "GRANT SELECT ON TABLE t1 TO analyst",
+            "GRANT SELECT, INSERT ON TABLE public.t1, t2 TO analyst, alice",
+            "GRANT ALL PRIVILEGES ON SCHEMA public TO analyst",
+            "GRANT CREATE ON DATABASE default TO analyst",
+            "REVOKE DELETE ON TABLE t1 FROM analyst",
+            "REVOKE ALL PRIVILEGES ON DATABASE default FROM analyst, alice",
+        ];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+    }
+
+    #[test]
+    fn comment_roundtrips() {
+        let test_cases = [
+            "COMMENT ON TABLE t1 IS 'events from the app'",
+            "COMMENT ON COLUMN public.t1.a IS 'it''s a column'",
+            "COMMENT ON SCHEMA public IS NULL",
+            "COMMENT ON DATABASE my_pg IS 'replica'",
+            "COMMENT ON VIEW v1 IS ''",
+        ];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+
+        CustomParser::parse_sql("COMMENT ON COLUMN a IS 'missing table'").unwrap_err();
+        CustomParser::parse_sql("COMMENT ON FUNCTION f IS 'unsupported'").unwrap_err();
+    }
+
+    #[test]
+    fn show_roundtrips() {
+        let test_cases = [
+            "SHOW DATABASES",
+            "SHOW SCHEMAS",
+            "SHOW SCHEMAS FROM my_pg",
+            "SHOW TABLES",
+            "SHOW TABLES FROM public",
+            "SHOW TABLES FROM my_pg.public LIKE 'user%'",
+            "SHOW TABLES WHERE table_name <> 't1'",
+        ];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+
+        // IN is an alias for FROM.
+        let stmt = CustomParser::parse_sql("SHOW TABLES IN public")
+            .unwrap()
+            .pop_front()
+            .unwrap();
+        assert_eq!("SHOW TABLES FROM public", stmt.to_string().as_str());
+
+        // Variables are still handled by sqlparser.
+        let stmt = CustomParser::parse_sql("SHOW search_path")
+            .unwrap()
+            .pop_front()
+            .unwrap();
+        assert!(matches!(
+            stmt,
+            StatementWithExtensions::Statement(ast::Statement::ShowVariable { .. })
+        ));
+
+        CustomParser::parse_sql("SHOW SCHEMAS FROM db.public").unwrap_err();
+        CustomParser::parse_sql("SHOW TABLES FROM db.public.t1").unwrap_err();
+    }
+
     #[test]
     fn alter_tunnel_roundtrips() {
         let test_cases = [
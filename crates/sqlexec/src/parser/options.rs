@@ -1,10 +1,7 @@
 use std::{collections::BTreeMap, fmt};
 
-use datafusion::{
-    common::{parsers::CompressionTypeVariant, FileType},
-    sql::sqlparser::parser::ParserError,
-};
-use datasources::{debug::DebugTableType, mongodb::MongoProtocol};
+use datafusion::{common::parsers::CompressionTypeVariant, sql::sqlparser::parser::ParserError};
+use datasources::{debug::DebugTableType, mongodb::MongoProtocol, object_store::ObjStoreFileType};
 use protogen::metastore::types::options::StorageOptions;
 
 /// Contains the value parsed from Options(...).
@@ -162,8 +159,8 @@ impl ParseOptionValue<DebugTableType> for OptionValue {
     }
 }
 
-impl ParseOptionValue<FileType> for OptionValue {
-    fn parse_opt(self) -> Result<FileType, ParserError> {
+impl ParseOptionValue<ObjStoreFileType> for OptionValue {
+    fn parse_opt(self) -> Result<ObjStoreFileType, ParserError> {
         let opt = match self {
             Self::QuotedLiteral(s) | Self::UnquotedLiteral(s) => {
                 s.parse().map_err(|e| parser_err!("{e}"))?
@@ -228,6 +225,13 @@ impl StmtOptions {
         self.m.is_empty()
     }
 
+    /// Replace all option values with `?`.
+    pub fn redact(&mut self) {
+        for val in self.m.values_mut() {
+            *val = OptionValue::UnquotedLiteral("?".to_string());
+        }
+    }
+
     pub fn remove_optional<T>(&mut self, k: &str) -> Result<Option<T>, ParserError>
     where
         OptionValue: ParseOptionValue<T>,
@@ -1,12 +1,15 @@
+use crate::activity::BackendSignaler;
 use crate::context::local::LocalSessionContext;
 use crate::dispatch::DispatchError;
 use crate::dispatch::Dispatcher;
 use crate::errors::ExecError;
 use crate::functions::BuiltinScalarFunction;
 use crate::planner::errors::PlanError;
+use crate::planner::privileges::PrivilegeChecker;
 use crate::remote::client::RemoteSessionClient;
 use crate::resolve::EntryResolver;
 use crate::resolve::ResolvedEntry;
+use crate::sequences::{SequenceFunction, SequenceHandle};
 use async_trait::async_trait;
 use datafusion::arrow::datatypes::DataType;
 use datafusion::common::OwnedTableReference;
@@ -23,7 +26,8 @@ use datafusion_ext::planner::AsyncContextProvider;
 
 use datafusion_ext::runtime::table_provider::RuntimeAwareTableProvider;
 use protogen::metastore::types::catalog::{
-    CatalogEntry, DatabaseEntry, FunctionEntry, RuntimePreference, TableEntry,
+    CatalogEntry, DatabaseEntry, FunctionEntry, FunctionType, Privilege, RuntimePreference,
+    SqlFunction, TableEntry,
 };
 use protogen::metastore::types::options::TableOptions;
 use protogen::rpcsrv::types::service::ResolvedTableReference;
@@ -68,6 +72,7 @@ impl<'a> PartialContextProvider<'a> {
             self.ctx.get_native_tables(),
             self.ctx.get_metrics(),
             &self.resolver.temp_objects,
+            self.ctx.get_prepared_statements(),
             self.ctx,
             self.ctx.df_ctx(),
             self.ctx.get_session_vars().is_cloud_instance(),
@@ -183,6 +188,11 @@ impl<'a> PartialContextProvider<'a> {
         let ent = self
             .resolver
             .resolve_entry_from_reference(reference.clone())?;
+        PrivilegeChecker::new(
+            self.ctx.get_session_catalog(),
+            &self.ctx.get_session_vars().user_name(),
+        )
+        .check_resolved(&ent, Privilege::Select)?;
         use ResolvedEntry::*;
         let provider = match (ent, self.ctx.exec_client()) {
             // (view, _)
@@ -348,6 +358,41 @@ impl<'a> PartialContextProvider<'a> {
         })
     }
 
+    /// Plan a call to a sequence function, resolving the sequence it
+    /// references.
+    fn sequence_function(&self, func: SequenceFunction, args: Vec<Expr>) -> DataFusionResult<Expr> {
+        let name = func.sequence_name(&args)?;
+        let reference = TableReference::from(name.as_str()).to_owned_reference();
+        let seq = match self.resolver.resolve_entry_from_reference(reference) {
+            Ok(ResolvedEntry::Entry(CatalogEntry::Sequence(seq))) => seq,
+            _ => return Err(DataFusionError::Plan(format!("Missing sequence: {name}"))),
+        };
+
+        let catalog = self.ctx.get_session_catalog();
+        PrivilegeChecker::new(catalog, &self.ctx.get_session_vars().user_name())
+            .check_entry(
+                &CatalogEntry::Sequence(seq.clone()),
+                func.required_privilege(),
+            )
+            .map_err(|e| DataFusionError::Plan(e.to_string()))?;
+
+        let schema = catalog
+            .get_by_oid(seq.meta.parent)
+            .map(|ent| ent.get_meta().name.clone())
+            .ok_or_else(|| {
+                DataFusionError::Internal(format!("Missing schema for sequence: {name}"))
+            })?;
+
+        let handle = SequenceHandle::new(
+            schema,
+            &seq,
+            catalog.version(),
+            self.ctx.catalog_mutator(),
+            self.ctx.get_session_sequences(),
+        );
+        func.into_expr(handle, args)
+    }
+
     async fn handle_table_entry_dispatch(
         &mut self,
         ent: &CatalogEntry,
@@ -392,8 +437,28 @@ impl<'a> AsyncContextProvider for PartialContextProvider<'a> {
         Ok(Arc::new(DefaultTableSource::new(Arc::new(provider))))
     }
 
-    fn get_builtin(&mut self, name: &str, args: Vec<Expr>) -> Option<Expr> {
-        BuiltinScalarFunction::find_function(name).map(|f| f.into_expr(args))
+    fn get_builtin(&mut self, name: &str, args: Vec<Expr>) -> DataFusionResult<Option<Expr>> {
+        if let Some(func) = SequenceFunction::find_function(name) {
+            return self.sequence_function(func, args).map(Some);
+        }
+
+        let func = match BuiltinScalarFunction::find_function(name) {
+            Some(func) => func,
+            None => return Ok(None),
+        };
+
+        // Superusers may signal any session, everyone else may only signal
+        // their own sessions.
+        let metrics = self.ctx.get_metrics();
+        let checker = PrivilegeChecker::new(self.ctx.get_session_catalog(), metrics.user_name());
+        let user_name = if checker.is_unrestricted() {
+            None
+        } else {
+            Some(metrics.user_name().to_string())
+        };
+        let signaler = BackendSignaler::new(metrics.activity(), user_name);
+
+        Ok(Some(func.into_expr(args, &signaler)))
     }
 
     async fn get_variable_type(&mut self, _variable_names: &[String]) -> Option<DataType> {
@@ -416,6 +481,24 @@ impl<'a> AsyncContextProvider for PartialContextProvider<'a> {
             .map_err(|e| DataFusionError::External(Box::new(e)))
     }
 
+    fn get_sql_function(
+        &mut self,
+        name: TableReference<'_>,
+        func_type: FunctionType,
+    ) -> Option<SqlFunction> {
+        match self
+            .resolver
+            .resolve_entry_from_reference(name.to_owned_reference())
+        {
+            Ok(ResolvedEntry::Entry(CatalogEntry::Function(func)))
+                if func.func_type == func_type =>
+            {
+                func.sql_function
+            }
+            _ => None,
+        }
+    }
+
     fn options(&self) -> &ConfigOptions {
         self.state.config_options()
     }
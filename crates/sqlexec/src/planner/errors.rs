@@ -24,6 +24,9 @@ pub enum PlanError {
     #[error(transparent)]
     Dispatch(#[from] crate::dispatch::DispatchError),
 
+    #[error(transparent)]
+    Privilege(#[from] crate::planner::privileges::PrivilegeError),
+
     #[error("Invalid tunnel '{tunnel}': {reason}")]
     InvalidTunnel { tunnel: String, reason: String },
 
@@ -43,12 +46,43 @@ pub enum PlanError {
     #[error("Invalid view statement: {msg}")]
     InvalidViewStatement { msg: &'static str },
 
+    #[error("Invalid partition column '{column}': {reason}")]
+    InvalidPartitionColumn {
+        column: String,
+        reason: &'static str,
+    },
+
+    #[error("Invalid constraint: {msg}")]
+    InvalidConstraint { msg: String },
+
     #[error("Invalid delete statement: {msg}")]
     InvalidDeleteStatement { msg: &'static str },
 
     #[error("Invalid number of column aliases for view body; sql: {sql}, aliases: {aliases:?}")]
     InvalidNumberOfAliasesForView { sql: String, aliases: Vec<String> },
 
+    #[error("Invalid refresh interval '{0}': expected a positive interval of days, hours, minutes, or seconds")]
+    InvalidRefreshInterval(String),
+
+    #[error("'{0}' is not a materialized view")]
+    NotMaterializedView(String),
+
+    #[error("Invalid function definition: {0}")]
+    InvalidFunctionDefinition(String),
+
+    #[error("Invalid sequence: {0}")]
+    InvalidSequence(String),
+
+    #[error("'{0}' is not a sequence")]
+    NotASequence(String),
+
+    #[error("Wrong number of parameters for prepared statement \"{name}\": expected {expected}, got {got}")]
+    WrongNumberOfParameters {
+        name: String,
+        expected: usize,
+        got: usize,
+    },
+
     #[error("An ssh connection is not supported datasource for CREATE EXTERNAL TABLE. An ssh connection must be provided as an optional ssh_tunnel with another connection type")]
     ExternalTableWithSsh,
 
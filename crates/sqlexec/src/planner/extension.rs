@@ -17,10 +17,13 @@ use datafusion::{
 };
 
 use super::logical_plan::{
-    AlterDatabaseRename, AlterTableRename, AlterTunnelRotateKeys, CopyTo, CreateCredentials,
-    CreateExternalDatabase, CreateExternalTable, CreateSchema, CreateTable, CreateTempTable,
-    CreateTunnel, CreateView, Delete, DropCredentials, DropDatabase, DropSchemas, DropTables,
-    DropTunnel, DropViews, Insert, SetVariable, ShowVariable, Update,
+    AlterDatabaseRename, AlterTableRename, AlterTunnelRotateKeys, CommentOn, CopyTo,
+    CreateCredentials, CreateExternalDatabase, CreateExternalDatabaseTable, CreateExternalTable,
+    CreateFunction, CreateRole, CreateSchema, CreateSequence, CreateTable, CreateTempTable,
+    CreateTunnel, CreateView, Delete, DropCredentials, DropDatabase, DropFunctions, DropRoles,
+    DropSchemas, DropSequences, DropTables, DropTunnel, DropViews, GrantPrivileges, Insert,
+    OptimizeTable, RefreshMaterializedView, RevokePrivileges, SetVariable, ShowVariable, Update,
+    VacuumTable,
 };
 
 /// This tracks all of our extensions so that we can ensure an exhaustive match on anywhere that uses the extension
@@ -51,6 +54,19 @@ pub enum ExtensionType {
     Update,
     Insert,
     Delete,
+    CreateRole,
+    DropRoles,
+    GrantPrivileges,
+    RevokePrivileges,
+    OptimizeTable,
+    VacuumTable,
+    RefreshMaterializedView,
+    CreateFunction,
+    DropFunctions,
+    CreateSequence,
+    DropSequences,
+    CommentOn,
+    CreateExternalDatabaseTable,
 }
 
 impl FromStr for ExtensionType {
@@ -80,6 +96,19 @@ impl FromStr for ExtensionType {
             Update::EXTENSION_NAME => Self::Update,
             Insert::EXTENSION_NAME => Self::Insert,
             Delete::EXTENSION_NAME => Self::Delete,
+            CreateRole::EXTENSION_NAME => Self::CreateRole,
+            DropRoles::EXTENSION_NAME => Self::DropRoles,
+            GrantPrivileges::EXTENSION_NAME => Self::GrantPrivileges,
+            RevokePrivileges::EXTENSION_NAME => Self::RevokePrivileges,
+            OptimizeTable::EXTENSION_NAME => Self::OptimizeTable,
+            VacuumTable::EXTENSION_NAME => Self::VacuumTable,
+            RefreshMaterializedView::EXTENSION_NAME => Self::RefreshMaterializedView,
+            CreateFunction::EXTENSION_NAME => Self::CreateFunction,
+            DropFunctions::EXTENSION_NAME => Self::DropFunctions,
+            CreateSequence::EXTENSION_NAME => Self::CreateSequence,
+            DropSequences::EXTENSION_NAME => Self::DropSequences,
+            CommentOn::EXTENSION_NAME => Self::CommentOn,
+            CreateExternalDatabaseTable::EXTENSION_NAME => Self::CreateExternalDatabaseTable,
             _ => return Err(internal!("unknown extension type: {}", s)),
         })
     }
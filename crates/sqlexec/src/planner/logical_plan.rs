@@ -1,11 +1,16 @@
 mod alter_database_rename;
 mod alter_table_rename;
 mod alter_tunnel_rotate_keys;
+mod comment_on;
 mod copy_to;
 mod create_credentials;
 mod create_external_database;
+mod create_external_database_table;
 mod create_external_table;
+mod create_function;
+mod create_role;
 mod create_schema;
+mod create_sequence;
 mod create_table;
 mod create_temp_table;
 mod create_tunnel;
@@ -13,17 +18,26 @@ mod create_view;
 mod delete;
 mod drop_credentials;
 mod drop_database;
+mod drop_functions;
+mod drop_roles;
 mod drop_schemas;
+mod drop_sequences;
 mod drop_tables;
 mod drop_tunnel;
 mod drop_views;
+mod grant_privileges;
 mod insert;
+mod optimize_table;
+mod refresh_materialized_view;
+mod revoke_privileges;
 mod set_variable;
 mod show_variable;
 mod update;
+mod vacuum_table;
 
 use crate::errors::{internal, Result};
-use crate::planner::extension::ExtensionNode;
+use crate::parser::StatementWithExtensions;
+use crate::planner::extension::{ExtensionNode, ExtensionType};
 
 use datafusion::arrow::datatypes::{DataType, Schema as ArrowSchema};
 use datafusion::common::{DFField, DFSchema, DFSchemaRef};
@@ -49,11 +63,16 @@ use std::sync::Arc;
 pub use alter_database_rename::*;
 pub use alter_table_rename::*;
 pub use alter_tunnel_rotate_keys::*;
+pub use comment_on::*;
 pub use copy_to::*;
 pub use create_credentials::*;
 pub use create_external_database::*;
+pub use create_external_database_table::*;
 pub use create_external_table::*;
+pub use create_function::*;
+pub use create_role::*;
 pub use create_schema::*;
+pub use create_sequence::*;
 pub use create_table::*;
 pub use create_temp_table::*;
 pub use create_tunnel::*;
@@ -61,14 +80,22 @@ pub use create_view::*;
 pub use delete::*;
 pub use drop_credentials::*;
 pub use drop_database::*;
+pub use drop_functions::*;
+pub use drop_roles::*;
 pub use drop_schemas::*;
+pub use drop_sequences::*;
 pub use drop_tables::*;
 pub use drop_tunnel::*;
 pub use drop_views::*;
+pub use grant_privileges::*;
 pub use insert::*;
+pub use optimize_table::*;
+pub use refresh_materialized_view::*;
+pub use revoke_privileges::*;
 pub use set_variable::*;
 pub use show_variable::*;
 pub use update::*;
+pub use vacuum_table::*;
 
 use super::physical_plan::{
     GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA, GENERIC_OPERATION_PHYSICAL_SCHEMA,
@@ -101,6 +128,8 @@ pub enum LogicalPlan {
     Datafusion(DfLogicalPlan),
     /// Plans related to transaction management.
     Transaction(TransactionPlan),
+    /// Plans for managing prepared statements through SQL.
+    PreparedStatement(PreparedStatementPlan),
 }
 
 impl LogicalPlan {
@@ -124,6 +153,19 @@ impl LogicalPlan {
         }
     }
 
+    /// Check if the plan only touches session state, e.g. setting or showing
+    /// a variable.
+    pub fn only_touches_session_state(&self) -> bool {
+        match self {
+            LogicalPlan::Transaction(_) | LogicalPlan::PreparedStatement(_) => true,
+            LogicalPlan::Datafusion(DfLogicalPlan::Extension(ext)) => matches!(
+                ext.node.name().parse::<ExtensionType>(),
+                Ok(ExtensionType::SetVariable | ExtensionType::ShowVariable)
+            ),
+            _ => false,
+        }
+    }
+
     /// Get parameter types for the logical plan.
     ///
     /// Note this will only try to get the parameters if the plan is a
@@ -254,3 +296,24 @@ impl From<TransactionPlan> for LogicalPlan {
         LogicalPlan::Transaction(plan)
     }
 }
+
+#[derive(Clone, Debug)]
+pub enum PreparedStatementPlan {
+    /// Create a named prepared statement (`PREPARE`).
+    Prepare {
+        name: String,
+        statement: StatementWithExtensions,
+        /// Declared types for the statement's parameters. Parameters without
+        /// a declared type will have their types inferred.
+        parameter_types: Vec<DataType>,
+    },
+    /// Remove a prepared statement, or all prepared statements if no name is
+    /// provided (`DEALLOCATE`).
+    Deallocate { name: Option<String> },
+}
+
+impl From<PreparedStatementPlan> for LogicalPlan {
+    fn from(plan: PreparedStatementPlan) -> Self {
+        LogicalPlan::PreparedStatement(plan)
+    }
+}
@@ -0,0 +1,139 @@
+use protogen::FromOptionalField;
+
+use super::*;
+
+/// Create a table inside of an external database, optionally populating it
+/// with the results of a query.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CreateExternalDatabaseTable {
+    /// Reference to the table. The database is the name of the external
+    /// database.
+    pub tbl_reference: OwnedFullObjectReference,
+    pub if_not_exists: bool,
+    pub or_replace: bool,
+    pub schema: DFSchemaRef,
+    pub source: Option<DfLogicalPlan>,
+    /// Options of the external database the table is created in.
+    pub options: DatabaseOptions,
+    /// Tunnel used to access the external database.
+    pub tunnel: Option<TunnelOptions>,
+}
+
+impl UserDefinedLogicalNodeCore for CreateExternalDatabaseTable {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        match self.source {
+            Some(ref src) => vec![src],
+            None => vec![],
+        }
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &GENERIC_OPERATION_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Self::EXTENSION_NAME)
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for CreateExternalDatabaseTable {
+    type ProtoRepr = protogen::sqlexec::logical_plan::CreateExternalDatabaseTable;
+    const EXTENSION_NAME: &'static str = "CreateExternalDatabaseTable";
+    fn try_decode(
+        proto: Self::ProtoRepr,
+        ctx: &SessionContext,
+        codec: &dyn LogicalExtensionCodec,
+    ) -> std::result::Result<Self, ProtoConvError> {
+        let reference = proto
+            .reference
+            .ok_or(ProtoConvError::RequiredField(
+                "table_name is required".to_string(),
+            ))?
+            .into();
+        let schema = proto
+            .schema
+            .ok_or(ProtoConvError::RequiredField(
+                "schema name is required".to_string(),
+            ))?
+            .try_into()?;
+
+        let source = proto
+            .source
+            .map(|src| src.try_into_logical_plan(ctx, codec))
+            .transpose()
+            .map_err(ProtoConvError::DataFusionError)?;
+
+        Ok(Self {
+            tbl_reference: reference,
+            if_not_exists: proto.if_not_exists,
+            or_replace: proto.or_replace,
+            schema,
+            source,
+            options: proto.options.required("options")?,
+            tunnel: proto.tunnel.map(TryInto::try_into).transpose()?,
+        })
+    }
+
+    fn try_downcast_extension(extension: &LogicalPlanExtension) -> Result<Self> {
+        match extension.node.as_any().downcast_ref::<Self>() {
+            Some(s) => Ok(s.clone()),
+            None => Err(internal!(
+                "CreateExternalDatabaseTable::try_from_extension: unsupported extension",
+            )),
+        }
+    }
+
+    fn try_encode(&self, buf: &mut Vec<u8>, codec: &dyn LogicalExtensionCodec) -> Result<()> {
+        use protogen::sqlexec::logical_plan as protogen;
+
+        let schema: Option<datafusion_proto::protobuf::DfSchema> = (&self.schema).try_into().ok();
+
+        let source = self
+            .source
+            .as_ref()
+            .map(|src| {
+                LogicalPlanNode::try_from_logical_plan(src, codec)
+                    .map_err(|e| internal!("unable to encode source: {}", e.to_string()))
+            })
+            .transpose()?;
+
+        let create_table = protogen::CreateExternalDatabaseTable {
+            reference: Some(self.tbl_reference.clone().into()),
+            if_not_exists: self.if_not_exists,
+            or_replace: self.or_replace,
+            schema,
+            source,
+            options: Some(self.options.clone().into()),
+            tunnel: self.tunnel.clone().map(Into::into),
+        };
+
+        let extension =
+            protogen::LogicalPlanExtensionType::CreateExternalDatabaseTable(create_table);
+
+        let lp_extension = protogen::LogicalPlanExtension {
+            inner: Some(extension),
+        };
+
+        lp_extension
+            .encode(buf)
+            .map_err(|e| internal!("{}", e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use super::*;
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CreateFunction {
+    pub function: protogen::metastore::types::service::CreateFunction,
+}
+
+impl UserDefinedLogicalNodeCore for CreateFunction {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        vec![]
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &GENERIC_OPERATION_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "CreateFunction")
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for CreateFunction {
+    type ProtoRepr = protogen::sqlexec::logical_plan::CreateFunction;
+    const EXTENSION_NAME: &'static str = "CreateFunction";
+    fn try_decode(
+        proto: Self::ProtoRepr,
+        _ctx: &SessionContext,
+        _codec: &dyn LogicalExtensionCodec,
+    ) -> std::result::Result<Self, ProtoConvError> {
+        Ok(Self {
+            function: proto
+                .function
+                .ok_or(ProtoConvError::RequiredField("function".to_string()))?
+                .try_into()?,
+        })
+    }
+    fn try_downcast_extension(extension: &LogicalPlanExtension) -> Result<Self> {
+        match extension.node.as_any().downcast_ref::<Self>() {
+            Some(s) => Ok(s.clone()),
+            None => Err(internal!("CreateFunction::try_decode_extension failed",)),
+        }
+    }
+
+    fn try_encode(&self, buf: &mut Vec<u8>, _codec: &dyn LogicalExtensionCodec) -> Result<()> {
+        use ::protogen::sqlexec::logical_plan::{
+            self as protogen, LogicalPlanExtension, LogicalPlanExtensionType,
+        };
+
+        let proto = protogen::CreateFunction {
+            function: Some(self.function.clone().try_into()?),
+        };
+
+        let plan_type = LogicalPlanExtensionType::CreateFunction(proto);
+
+        let lp_extension = LogicalPlanExtension {
+            inner: Some(plan_type),
+        };
+
+        lp_extension
+            .encode(buf)
+            .map_err(|e| internal!("{}", e.to_string()))?;
+
+        Ok(())
+    }
+}
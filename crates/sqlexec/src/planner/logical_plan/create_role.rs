@@ -0,0 +1,91 @@
+use super::*;
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CreateRole {
+    pub name: String,
+    pub if_not_exists: bool,
+    pub superuser: bool,
+    pub login: bool,
+    pub password_hash: Option<String>,
+}
+
+impl UserDefinedLogicalNodeCore for CreateRole {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        vec![]
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &GENERIC_OPERATION_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "CreateRole")
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for CreateRole {
+    type ProtoRepr = protogen::gen::metastore::service::CreateRole;
+    const EXTENSION_NAME: &'static str = "CreateRole";
+
+    fn try_decode(
+        proto: Self::ProtoRepr,
+        _ctx: &SessionContext,
+        _codec: &dyn LogicalExtensionCodec,
+    ) -> std::result::Result<Self, ProtoConvError> {
+        Ok(Self {
+            name: proto.name,
+            if_not_exists: proto.if_not_exists,
+            superuser: proto.superuser,
+            login: proto.login,
+            password_hash: proto.password_hash,
+        })
+    }
+
+    fn try_downcast_extension(extension: &LogicalPlanExtension) -> Result<Self> {
+        match extension.node.as_any().downcast_ref::<Self>() {
+            Some(s) => Ok(s.clone()),
+            None => Err(internal!("CreateRole::try_decode_extension failed",)),
+        }
+    }
+
+    fn try_encode(&self, buf: &mut Vec<u8>, _codec: &dyn LogicalExtensionCodec) -> Result<()> {
+        use ::protogen::sqlexec::logical_plan::{LogicalPlanExtension, LogicalPlanExtensionType};
+
+        use protogen::gen::metastore::service as protogen;
+
+        let proto = protogen::CreateRole {
+            name: self.name.clone(),
+            if_not_exists: self.if_not_exists,
+            superuser: self.superuser,
+            login: self.login,
+            password_hash: self.password_hash.clone(),
+        };
+
+        let plan_type = LogicalPlanExtensionType::CreateRole(proto);
+
+        let lp_extension = LogicalPlanExtension {
+            inner: Some(plan_type),
+        };
+
+        lp_extension
+            .encode(buf)
+            .map_err(|e| internal!("{}", e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use super::*;
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CreateSequence {
+    pub sequence: protogen::metastore::types::service::CreateSequence,
+}
+
+impl UserDefinedLogicalNodeCore for CreateSequence {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        vec![]
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &GENERIC_OPERATION_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "CreateSequence")
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for CreateSequence {
+    type ProtoRepr = protogen::sqlexec::logical_plan::CreateSequence;
+    const EXTENSION_NAME: &'static str = "CreateSequence";
+    fn try_decode(
+        proto: Self::ProtoRepr,
+        _ctx: &SessionContext,
+        _codec: &dyn LogicalExtensionCodec,
+    ) -> std::result::Result<Self, ProtoConvError> {
+        Ok(Self {
+            sequence: proto
+                .sequence
+                .ok_or(ProtoConvError::RequiredField("sequence".to_string()))?
+                .into(),
+        })
+    }
+    fn try_downcast_extension(extension: &LogicalPlanExtension) -> Result<Self> {
+        match extension.node.as_any().downcast_ref::<Self>() {
+            Some(s) => Ok(s.clone()),
+            None => Err(internal!("CreateSequence::try_decode_extension failed",)),
+        }
+    }
+
+    fn try_encode(&self, buf: &mut Vec<u8>, _codec: &dyn LogicalExtensionCodec) -> Result<()> {
+        use ::protogen::sqlexec::logical_plan::{
+            self as protogen, LogicalPlanExtension, LogicalPlanExtensionType,
+        };
+
+        let proto = protogen::CreateSequence {
+            sequence: Some(self.sequence.clone().into()),
+        };
+
+        let plan_type = LogicalPlanExtensionType::CreateSequence(proto);
+
+        let lp_extension = LogicalPlanExtension {
+            inner: Some(plan_type),
+        };
+
+        lp_extension
+            .encode(buf)
+            .map_err(|e| internal!("{}", e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use datafusion::prelude::SessionContext;
+use protogen::metastore::types::options::TableOptionsInternal;
 
 use super::*;
 
@@ -9,6 +10,10 @@ pub struct CreateTable {
     pub or_replace: bool,
     pub schema: DFSchemaRef,
     pub source: Option<DfLogicalPlan>,
+    /// Options for the new table, including column defaults and constraints.
+    pub options: TableOptionsInternal,
+    /// Sequences backing serial and identity columns.
+    pub sequences: Vec<protogen::metastore::types::service::CreateSequence>,
 }
 
 impl UserDefinedLogicalNodeCore for CreateTable {
@@ -65,6 +70,13 @@ impl ExtensionNode for CreateTable {
             ))?
             .try_into()?;
 
+        let options = proto
+            .options
+            .ok_or(ProtoConvError::RequiredField(
+                "options is required".to_string(),
+            ))?
+            .try_into()?;
+
         let source = proto
             .source
             .map(|src| src.try_into_logical_plan(ctx, codec))
@@ -77,6 +89,8 @@ impl ExtensionNode for CreateTable {
             or_replace: proto.or_replace,
             schema,
             source,
+            options,
+            sequences: proto.sequences.into_iter().map(Into::into).collect(),
         })
     }
     fn try_downcast_extension(extension: &LogicalPlanExtension) -> Result<Self> {
@@ -103,12 +117,20 @@ impl ExtensionNode for CreateTable {
             })
             .transpose()?;
 
+        let options = self
+            .options
+            .clone()
+            .try_into()
+            .map_err(|e: ProtoConvError| internal!("unable to encode options: {}", e))?;
+
         let create_table = protogen::CreateTable {
             reference: Some(self.tbl_reference.clone().into()),
             if_not_exists: self.if_not_exists,
             or_replace: self.or_replace,
             schema,
             source,
+            options: Some(options),
+            sequences: self.sequences.iter().cloned().map(Into::into).collect(),
         };
 
         let extension = protogen::LogicalPlanExtensionType::CreateTable(create_table);
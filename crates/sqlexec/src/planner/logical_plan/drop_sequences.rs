@@ -0,0 +1,92 @@
+use super::*;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DropSequences {
+    pub sequence_references: Vec<OwnedFullObjectReference>,
+    pub if_exists: bool,
+}
+
+impl UserDefinedLogicalNodeCore for DropSequences {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        vec![]
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &GENERIC_OPERATION_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "DropSequences")
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for DropSequences {
+    type ProtoRepr = protogen::sqlexec::logical_plan::DropSequences;
+    const EXTENSION_NAME: &'static str = "DropSequences";
+    fn try_decode(
+        proto: Self::ProtoRepr,
+        _ctx: &SessionContext,
+        _codec: &dyn LogicalExtensionCodec,
+    ) -> std::result::Result<Self, ProtoConvError> {
+        let references = proto
+            .references
+            .into_iter()
+            .map(|r| r.into())
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            sequence_references: references,
+            if_exists: proto.if_exists,
+        })
+    }
+    fn try_downcast_extension(extension: &LogicalPlanExtension) -> Result<Self> {
+        match extension.node.as_any().downcast_ref::<Self>() {
+            Some(s) => Ok(s.clone()),
+            None => Err(internal!(
+                "DropSequences::try_decode_extension: unsupported extension",
+            )),
+        }
+    }
+
+    fn try_encode(&self, buf: &mut Vec<u8>, _codec: &dyn LogicalExtensionCodec) -> Result<()> {
+        use protogen::sqlexec::logical_plan as protogen;
+        let references = self
+            .sequence_references
+            .clone()
+            .into_iter()
+            .map(|r| r.into())
+            .collect::<Vec<_>>();
+
+        let drop_sequences = protogen::DropSequences {
+            references,
+            if_exists: self.if_exists,
+        };
+        let plan_type = protogen::LogicalPlanExtensionType::DropSequences(drop_sequences);
+
+        let lp_extension = protogen::LogicalPlanExtension {
+            inner: Some(plan_type),
+        };
+
+        lp_extension
+            .encode(buf)
+            .map_err(|e| internal!("{}", e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -4,6 +4,8 @@ use std::hash::Hash;
 use datafusion::prelude::SessionContext;
 use protogen::metastore::types::catalog::RuntimePreference;
 
+use crate::background_jobs::storage::TableCompaction;
+use crate::planner::physical_plan::constraint_check::{ConstraintCheck, UniqueCheck};
 use crate::planner::physical_plan::remote_scan::ProviderReference;
 
 use super::*;
@@ -13,6 +15,11 @@ pub struct Insert {
     pub source: DfLogicalPlan,
     pub provider: ProviderReference,
     pub runtime_preference: RuntimePreference,
+    pub checks: Vec<ConstraintCheck>,
+    pub unique_checks: Vec<UniqueCheck>,
+    /// Compaction to schedule once rows have been inserted into a native
+    /// table.
+    pub compaction: Option<TableCompaction>,
 }
 
 impl UserDefinedLogicalNodeCore for Insert {
@@ -39,9 +46,14 @@ impl UserDefinedLogicalNodeCore for Insert {
     fn from_template(
         &self,
         _exprs: &[datafusion::prelude::Expr],
-        _inputs: &[DfLogicalPlan],
+        inputs: &[DfLogicalPlan],
     ) -> Self {
-        self.clone()
+        // The source may have been rewritten, e.g. when replacing
+        // placeholders for a prepared statement.
+        Self {
+            source: inputs[0].clone(),
+            ..self.clone()
+        }
     }
 }
 
@@ -0,0 +1,99 @@
+use protogen::metastore::types::catalog::TableEntry;
+
+use super::*;
+use crate::planner::physical_plan::optimize_table::OPTIMIZE_TABLE_PHYSICAL_SCHEMA;
+
+pub static OPTIMIZE_TABLE_LOGICAL_SCHEMA: Lazy<DFSchemaRef> = Lazy::new(|| {
+    Arc::new(
+        OPTIMIZE_TABLE_PHYSICAL_SCHEMA
+            .as_ref()
+            .clone()
+            .try_into()
+            .unwrap(),
+    )
+});
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OptimizeTable {
+    pub table: TableEntry,
+    pub zorder_by: Vec<String>,
+}
+
+impl UserDefinedLogicalNodeCore for OptimizeTable {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        Vec::new()
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &OPTIMIZE_TABLE_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        Vec::new()
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Self::EXTENSION_NAME)
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for OptimizeTable {
+    type ProtoRepr = protogen::sqlexec::logical_plan::OptimizeTable;
+    const EXTENSION_NAME: &'static str = "OptimizeTable";
+
+    fn try_decode(
+        proto: Self::ProtoRepr,
+        _ctx: &SessionContext,
+        _codec: &dyn LogicalExtensionCodec,
+    ) -> std::result::Result<Self, ProtoConvError> {
+        Ok(Self {
+            table: proto
+                .table
+                .ok_or(ProtoConvError::RequiredField("table".to_string()))?
+                .try_into()?,
+            zorder_by: proto.zorder_by,
+        })
+    }
+
+    fn try_downcast_extension(extension: &LogicalPlanExtension) -> Result<Self> {
+        match extension.node.as_any().downcast_ref::<Self>() {
+            Some(s) => Ok(s.clone()),
+            None => Err(internal!("OptimizeTable::try_decode_extension failed",)),
+        }
+    }
+
+    fn try_encode(&self, buf: &mut Vec<u8>, _codec: &dyn LogicalExtensionCodec) -> Result<()> {
+        use ::protogen::sqlexec::logical_plan::{
+            self as protogen, LogicalPlanExtension, LogicalPlanExtensionType,
+        };
+
+        let proto = protogen::OptimizeTable {
+            table: Some(self.table.clone().try_into()?),
+            zorder_by: self.zorder_by.clone(),
+        };
+
+        let plan_type = LogicalPlanExtensionType::OptimizeTable(proto);
+
+        let lp_extension = LogicalPlanExtension {
+            inner: Some(plan_type),
+        };
+
+        lp_extension
+            .encode(buf)
+            .map_err(|e| internal!("{}", e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use protogen::metastore::types::catalog::TableEntry;
+
+use super::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RefreshMaterializedView {
+    /// Name of the schema containing the view.
+    pub schema: String,
+    /// The native table backing the view.
+    pub table: TableEntry,
+    /// The view's query, projected to match the table's schema.
+    pub source: DfLogicalPlan,
+}
+
+impl UserDefinedLogicalNodeCore for RefreshMaterializedView {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        vec![&self.source]
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &GENERIC_OPERATION_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        Vec::new()
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Self::EXTENSION_NAME)
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for RefreshMaterializedView {
+    type ProtoRepr = protogen::sqlexec::logical_plan::RefreshMaterializedView;
+    const EXTENSION_NAME: &'static str = "RefreshMaterializedView";
+
+    fn try_decode(
+        proto: Self::ProtoRepr,
+        ctx: &SessionContext,
+        codec: &dyn LogicalExtensionCodec,
+    ) -> std::result::Result<Self, ProtoConvError> {
+        Ok(Self {
+            schema: proto.schema,
+            table: proto
+                .table
+                .ok_or(ProtoConvError::RequiredField("table".to_string()))?
+                .try_into()?,
+            source: proto
+                .source
+                .ok_or(ProtoConvError::RequiredField("source".to_string()))?
+                .try_into_logical_plan(ctx, codec)
+                .map_err(ProtoConvError::DataFusionError)?,
+        })
+    }
+
+    fn try_downcast_extension(extension: &LogicalPlanExtension) -> Result<Self> {
+        match extension.node.as_any().downcast_ref::<Self>() {
+            Some(s) => Ok(s.clone()),
+            None => Err(internal!(
+                "RefreshMaterializedView::try_decode_extension failed",
+            )),
+        }
+    }
+
+    fn try_encode(&self, buf: &mut Vec<u8>, codec: &dyn LogicalExtensionCodec) -> Result<()> {
+        use ::protogen::sqlexec::logical_plan::{
+            self as protogen, LogicalPlanExtension, LogicalPlanExtensionType,
+        };
+
+        let source = LogicalPlanNode::try_from_logical_plan(&self.source, codec)
+            .map_err(|e| internal!("unable to encode source: {}", e.to_string()))?;
+
+        let proto = protogen::RefreshMaterializedView {
+            schema: self.schema.clone(),
+            table: Some(self.table.clone().try_into()?),
+            source: Some(source),
+        };
+
+        let plan_type = LogicalPlanExtensionType::RefreshMaterializedView(proto);
+
+        let lp_extension = LogicalPlanExtension {
+            inner: Some(plan_type),
+        };
+
+        lp_extension
+            .encode(buf)
+            .map_err(|e| internal!("{}", e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use datafusion::prelude::SessionContext;
 use protogen::metastore::types::catalog::TableEntry;
 
+use crate::planner::physical_plan::constraint_check::{ConstraintCheck, UniqueCheck};
+
 use super::*;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -8,6 +10,9 @@ pub struct Update {
     pub table: TableEntry,
     pub updates: Vec<(String, Expr)>,
     pub where_expr: Option<Expr>,
+    pub checks: Vec<ConstraintCheck>,
+    /// Unique constraints to enforce, empty if not enforcing uniqueness.
+    pub unique_checks: Vec<UniqueCheck>,
 }
 
 impl UserDefinedLogicalNodeCore for Update {
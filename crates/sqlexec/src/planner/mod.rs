@@ -2,8 +2,8 @@ pub mod errors;
 pub mod extension;
 pub mod logical_plan;
 pub mod physical_plan;
+pub mod preprocess;
+pub mod privileges;
 pub mod session_planner;
 
 pub(crate) mod context_builder;
-
-mod preprocess;
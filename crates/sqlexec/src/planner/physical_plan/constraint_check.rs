@@ -0,0 +1,294 @@
+//! Enforcement of table constraints when writing to native tables.
+//!
+//! Delta doesn't know about our constraints, so NOT NULL and CHECK
+//! constraints are checked against rows before they're written. Unique
+//! constraints are only checked on insert and update if the session opts in,
+//! since it requires scanning the table.
+//!
+//! Enforcement is best-effort. Checks that scan the table run separately from
+//! the write, and writes from other sessions aren't serialized against them,
+//! so concurrent writes may still produce rows violating a constraint.
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::common::cast::as_boolean_array;
+use datafusion::common::{Column, ToDFSchema};
+use datafusion::dataframe::DataFrame;
+use datafusion::datasource::{MemTable, TableProvider};
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::context::{SessionContext, SessionState};
+use datafusion::execution::TaskContext;
+use datafusion::logical_expr::{count, lit, not, when, Expr};
+use datafusion::physical_expr::{PhysicalExpr, PhysicalSortExpr};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream,
+    Statistics,
+};
+use futures::StreamExt;
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+/// A boolean expression rows written to a table must satisfy.
+///
+/// Like in Postgres, a row only fails the check if the expression evaluates to
+/// false. NULL is considered passing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConstraintCheck {
+    pub expr: Expr,
+    /// Error message returned when a row fails the check.
+    pub violation: String,
+}
+
+/// Columns whose combined values must be unique across all rows in a table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UniqueCheck {
+    pub columns: Vec<String>,
+    /// Error message returned when a duplicate is found.
+    pub violation: String,
+}
+
+/// Passes through batches from its input, erroring if any row fails one of the
+/// checks.
+#[derive(Debug, Clone)]
+pub struct ConstraintCheckExec {
+    input: Arc<dyn ExecutionPlan>,
+    checks: Vec<(Arc<dyn PhysicalExpr>, String)>,
+}
+
+impl ConstraintCheckExec {
+    pub fn try_new(
+        input: Arc<dyn ExecutionPlan>,
+        checks: &[ConstraintCheck],
+        state: &SessionState,
+    ) -> DataFusionResult<Self> {
+        let df_schema = input.schema().to_dfschema()?;
+        let checks = checks
+            .iter()
+            .map(|check| {
+                let expr = state.create_physical_expr(&check.expr, &df_schema)?;
+                Ok((expr, check.violation.clone()))
+            })
+            .collect::<DataFusionResult<Vec<_>>>()?;
+
+        Ok(ConstraintCheckExec { input, checks })
+    }
+}
+
+impl ExecutionPlan for ConstraintCheckExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.input.output_partitioning()
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.input.output_ordering()
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(ConstraintCheckExec {
+            input: children[0].clone(),
+            checks: self.checks.clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        let checks = self.checks.clone();
+        let stream = self.input.execute(partition, context)?.map(move |batch| {
+            let batch = batch?;
+            check_batch(&checks, &batch)?;
+            Ok(batch)
+        });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        self.input.statistics()
+    }
+}
+
+impl DisplayAs for ConstraintCheckExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ConstraintCheckExec")
+    }
+}
+
+fn check_batch(
+    checks: &[(Arc<dyn PhysicalExpr>, String)],
+    batch: &RecordBatch,
+) -> DataFusionResult<()> {
+    for (expr, violation) in checks {
+        let result = expr.evaluate(batch)?.into_array(batch.num_rows());
+        let result = as_boolean_array(&result)?;
+        if result.iter().any(|v| v == Some(false)) {
+            return Err(DataFusionError::Execution(violation.clone()));
+        }
+    }
+    Ok(())
+}
+
+/// Check that inserting `batches` into `table` doesn't result in duplicate
+/// values for any of the unique checks.
+///
+/// Rows with a NULL in any of the check's columns are never considered
+/// duplicates.
+pub async fn check_unique(
+    table: Arc<dyn TableProvider>,
+    schema: SchemaRef,
+    batches: Vec<RecordBatch>,
+    checks: &[UniqueCheck],
+    state: SessionState,
+) -> DataFusionResult<()> {
+    let ctx = SessionContext::with_state(state);
+    let inserted: Arc<dyn TableProvider> = Arc::new(MemTable::try_new(schema, vec![batches])?);
+
+    for check in checks {
+        let columns = check_columns(check);
+        let existing = ctx.read_table(table.clone())?.select(columns.clone())?;
+        let new = ctx.read_table(inserted.clone())?.select(columns)?;
+
+        if has_duplicates(existing.union(new)?, check).await? {
+            return Err(DataFusionError::Execution(check.violation.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that applying `updates` to the rows in `table` matching `where_expr`
+/// doesn't result in duplicate values for any of the unique checks.
+///
+/// Checks on columns that aren't being updated are skipped.
+pub async fn check_unique_after_update(
+    table: Arc<dyn TableProvider>,
+    updates: &[(String, Expr)],
+    where_expr: Option<Expr>,
+    checks: &[UniqueCheck],
+    state: SessionState,
+) -> DataFusionResult<()> {
+    let ctx = SessionContext::with_state(state);
+
+    for check in checks {
+        if !check
+            .columns
+            .iter()
+            .any(|name| updates.iter().any(|(column, _)| column == name))
+        {
+            continue;
+        }
+
+        // Values of the check's columns once the update has been applied.
+        let columns = check
+            .columns
+            .iter()
+            .map(|name| {
+                let current = Expr::Column(Column::from_name(name));
+                let updated = match updates.iter().find(|(column, _)| column == name) {
+                    Some((_, expr)) => match &where_expr {
+                        Some(where_expr) => {
+                            when(where_expr.clone(), expr.clone()).otherwise(current)?
+                        }
+                        None => expr.clone(),
+                    },
+                    None => current,
+                };
+                Ok(updated.alias(name))
+            })
+            .collect::<DataFusionResult<Vec<_>>>()?;
+
+        let updated = ctx.read_table(table.clone())?.select(columns)?;
+        if has_duplicates(updated, check).await? {
+            return Err(DataFusionError::Execution(check.violation.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+fn check_columns(check: &UniqueCheck) -> Vec<Expr> {
+    check
+        .columns
+        .iter()
+        .map(|name| Expr::Column(Column::from_name(name)))
+        .collect()
+}
+
+/// Check if any rows have the same values for all of the check's columns.
+///
+/// Rows with a NULL in any of the check's columns are never considered
+/// duplicates.
+async fn has_duplicates(rows: DataFrame, check: &UniqueCheck) -> DataFusionResult<bool> {
+    let columns = check_columns(check);
+    let not_null = columns
+        .iter()
+        .map(|col| col.clone().is_not_null())
+        .reduce(Expr::and)
+        .unwrap_or_else(|| lit(true));
+
+    let duplicates = rows
+        .filter(not_null)?
+        .aggregate(columns, vec![count(lit(1)).alias("__row_count")])?
+        .filter(Expr::Column(Column::from_name("__row_count")).gt(lit(1_i64)))?
+        .limit(0, Some(1))?
+        .collect()
+        .await?;
+
+    Ok(duplicates.iter().any(|batch| batch.num_rows() > 0))
+}
+
+/// Check that none of the rows in `table` matching `where_expr` fail any of the
+/// checks.
+///
+/// Used to validate updates before they're applied, with the checks having
+/// the updated values substituted in.
+pub async fn check_existing_rows(
+    table: Arc<dyn TableProvider>,
+    where_expr: Option<Expr>,
+    checks: &[ConstraintCheck],
+    state: SessionState,
+) -> DataFusionResult<()> {
+    let ctx = SessionContext::with_state(state);
+
+    for check in checks {
+        let failing = not(check.expr.clone());
+        let predicate = match &where_expr {
+            Some(where_expr) => where_expr.clone().and(failing),
+            None => failing,
+        };
+
+        let violations = ctx
+            .read_table(table.clone())?
+            .filter(predicate)?
+            .limit(0, Some(1))?
+            .collect()
+            .await?;
+
+        if violations.iter().any(|batch| batch.num_rows() > 0) {
+            return Err(DataFusionError::Execution(check.violation.clone()));
+        }
+    }
+
+    Ok(())
+}
@@ -9,6 +9,7 @@ use datafusion::physical_plan::{
     stream::RecordBatchStreamAdapter, DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning,
     SendableRecordBatchStream, Statistics,
 };
+use datasources::common::sink::avro::AvroSink;
 use datasources::common::sink::csv::{CsvSink, CsvSinkOpts};
 use datasources::common::sink::json::{JsonSink, JsonSinkOpts};
 use datasources::common::sink::parquet::{ParquetSink, ParquetSinkOpts};
@@ -165,6 +166,7 @@ fn get_sink_for_obj(
                 array: json_opts.array,
             },
         )),
+        CopyToFormatOptions::Avro(_) => Box::new(AvroSink::from_obj_store(store, path)),
     };
     Ok(sink)
 }
@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use datafusion::{
+    arrow::{datatypes::SchemaRef, record_batch::RecordBatch},
+    error::{DataFusionError, Result as DataFusionResult},
+    execution::TaskContext,
+    physical_expr::PhysicalSortExpr,
+    physical_plan::{
+        execute_stream, stream::RecordBatchStreamAdapter, DisplayAs, DisplayFormatType,
+        ExecutionPlan, Partitioning, SendableRecordBatchStream, Statistics,
+    },
+};
+use datasources::mysql::MysqlAccessor;
+use datasources::postgres::PostgresAccess;
+use futures::stream;
+use protogen::metastore::types::options::{
+    DatabaseOptions, DatabaseOptionsMysql, DatabaseOptionsPostgres, TunnelOptions,
+};
+use tracing::info;
+
+use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
+use crate::planner::logical_plan::OwnedFullObjectReference;
+
+/// Create a table in an external database, inserting rows from the source if
+/// provided.
+#[derive(Debug, Clone)]
+pub struct CreateExternalDatabaseTableExec {
+    pub tbl_reference: OwnedFullObjectReference,
+    pub if_not_exists: bool,
+    pub or_replace: bool,
+    pub arrow_schema: SchemaRef,
+    pub source: Option<Arc<dyn ExecutionPlan>>,
+    pub options: DatabaseOptions,
+    pub tunnel: Option<TunnelOptions>,
+}
+
+impl ExecutionPlan for CreateExternalDatabaseTableExec {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        match &self.source {
+            Some(source) => vec![source.clone()],
+            None => vec![],
+        }
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(CreateExternalDatabaseTableExec {
+            tbl_reference: self.tbl_reference.clone(),
+            if_not_exists: self.if_not_exists,
+            or_replace: self.or_replace,
+            arrow_schema: self.arrow_schema.clone(),
+            source: children.get(0).cloned(),
+            options: self.options.clone(),
+            tunnel: self.tunnel.clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "CreateExternalDatabaseTableExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let this = self.clone();
+        let stream = stream::once(this.create_table(context));
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+impl DisplayAs for CreateExternalDatabaseTableExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "CreateExternalDatabaseTableExec")
+    }
+}
+
+impl CreateExternalDatabaseTableExec {
+    async fn create_table(self, context: Arc<TaskContext>) -> DataFusionResult<RecordBatch> {
+        let schema = self.tbl_reference.schema.clone().into_owned();
+        let name = self.tbl_reference.name.clone().into_owned();
+
+        // Rows are written as part of creating the table so that the remote
+        // side can make the whole operation atomic.
+        let input = match self.source {
+            Some(source) => Some(execute_stream(source, context)?),
+            None => None,
+        };
+
+        let created = match self.options {
+            DatabaseOptions::Postgres(DatabaseOptionsPostgres { connection_string }) => {
+                let access = PostgresAccess::new_from_conn_str(connection_string, self.tunnel);
+                access
+                    .create_table(
+                        &schema,
+                        &name,
+                        &self.arrow_schema,
+                        self.if_not_exists,
+                        self.or_replace,
+                        input,
+                    )
+                    .await
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?
+            }
+            DatabaseOptions::Mysql(DatabaseOptionsMysql { connection_string }) => {
+                let accessor = MysqlAccessor::connect(&connection_string, self.tunnel)
+                    .await
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+                accessor
+                    .create_table(
+                        &schema,
+                        &name,
+                        &self.arrow_schema,
+                        self.if_not_exists,
+                        self.or_replace,
+                        input,
+                    )
+                    .await
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?
+            }
+            other => {
+                return Err(DataFusionError::Execution(format!(
+                    "creating tables in {} databases is not supported",
+                    other.as_str()
+                )))
+            }
+        };
+
+        // An existing table is left untouched when IF NOT EXISTS is given.
+        if created.is_some() {
+            info!(database = %self.tbl_reference.database, %schema, %name, "external database table created");
+        }
+
+        Ok(new_operation_batch("create_table"))
+    }
+}
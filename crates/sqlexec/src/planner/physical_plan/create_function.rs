@@ -0,0 +1,103 @@
+use crate::metastore::catalog::CatalogMutator;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::{
+    stream::RecordBatchStreamAdapter, DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning,
+    SendableRecordBatchStream, Statistics,
+};
+use futures::stream;
+use protogen::metastore::types::service::{self, Mutation};
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
+
+#[derive(Debug, Clone)]
+pub struct CreateFunctionExec {
+    pub catalog_version: u64,
+    pub function: service::CreateFunction,
+}
+
+impl ExecutionPlan for CreateFunctionExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        Err(DataFusionError::Plan(
+            "Cannot change children for CreateFunctionExec".to_string(),
+        ))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "CreateFunctionExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let mutator = context
+            .session_config()
+            .get_extension::<CatalogMutator>()
+            .expect("context should have catalog mutator");
+
+        let stream = stream::once(create_function(mutator, self.clone()));
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+impl DisplayAs for CreateFunctionExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CreateFunctionExec")
+    }
+}
+
+async fn create_function(
+    mutator: Arc<CatalogMutator>,
+    plan: CreateFunctionExec,
+) -> DataFusionResult<RecordBatch> {
+    mutator
+        .mutate(
+            plan.catalog_version,
+            [Mutation::CreateFunction(plan.function)],
+        )
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to create function: {e}")))?;
+
+    Ok(new_operation_batch("create_function"))
+}
@@ -0,0 +1,114 @@
+use crate::metastore::catalog::CatalogMutator;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::{
+    stream::RecordBatchStreamAdapter, DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning,
+    SendableRecordBatchStream, Statistics,
+};
+use futures::stream;
+use protogen::metastore::types::service::{self, Mutation};
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
+
+#[derive(Debug, Clone)]
+pub struct CreateRoleExec {
+    pub catalog_version: u64,
+    pub name: String,
+    pub if_not_exists: bool,
+    pub superuser: bool,
+    pub login: bool,
+    /// Hash of the role's password, never the password itself.
+    pub password_hash: Option<String>,
+}
+
+impl ExecutionPlan for CreateRoleExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        Err(DataFusionError::Plan(
+            "Cannot change children for CreateRoleExec".to_string(),
+        ))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "CreateRoleExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let mutator = context
+            .session_config()
+            .get_extension::<CatalogMutator>()
+            .expect("context should have catalog mutator");
+
+        let stream = stream::once(create_role(mutator, self.clone()));
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+impl DisplayAs for CreateRoleExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CreateRoleExec")
+    }
+}
+
+async fn create_role(
+    mutator: Arc<CatalogMutator>,
+    plan: CreateRoleExec,
+) -> DataFusionResult<RecordBatch> {
+    mutator
+        .mutate(
+            plan.catalog_version,
+            [Mutation::CreateRole(service::CreateRole {
+                name: plan.name,
+                if_not_exists: plan.if_not_exists,
+                superuser: plan.superuser,
+                login: plan.login,
+                password_hash: plan.password_hash,
+            })],
+        )
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to create role: {e}")))?;
+
+    Ok(new_operation_batch("create_role"))
+}
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use chrono::Utc;
 use datafusion::{
     arrow::{datatypes::SchemaRef, record_batch::RecordBatch},
     datasource::TableProvider,
@@ -14,12 +15,13 @@ use datafusion::{
 };
 use datasources::native::access::{NativeTable, NativeTableStorage};
 use futures::stream;
-use protogen::metastore::types::{service, service::Mutation};
+use protogen::metastore::types::{options::TableOptionsInternal, service, service::Mutation};
 use sqlbuiltins::builtins::DEFAULT_CATALOG;
 use tracing::info;
 
 use super::GENERIC_OPERATION_PHYSICAL_SCHEMA;
 use crate::{
+    background_jobs::materialized_views::MaterializedViewScheduler,
     errors::ExecError,
     metastore::catalog::{CatalogMutator, SessionCatalog},
     planner::{logical_plan::OwnedFullObjectReference, physical_plan::new_operation_batch},
@@ -34,6 +36,10 @@ pub struct CreateTableExec {
     pub or_replace: bool,
     pub arrow_schema: SchemaRef,
     pub source: Option<Arc<dyn ExecutionPlan>>,
+    /// Options for the new table, including column defaults and constraints.
+    pub options: TableOptionsInternal,
+    /// Sequences owned by the table, created along with it.
+    pub sequences: Vec<service::CreateSequence>,
 }
 
 impl ExecutionPlan for CreateTableExec {
@@ -71,6 +77,8 @@ impl ExecutionPlan for CreateTableExec {
             or_replace: self.or_replace,
             arrow_schema: self.arrow_schema.clone(),
             source: children.get(0).cloned(),
+            options: self.options.clone(),
+            sequences: self.sequences.clone(),
         }))
     }
 
@@ -122,17 +130,22 @@ impl CreateTableExec {
         context: Arc<TaskContext>,
     ) -> DataFusionResult<RecordBatch> {
         let or_replace = self.or_replace;
+        let mut options = self.options;
+        if let Some(view) = options.materialized_view.as_mut() {
+            // The view's query is inserted below.
+            view.last_refreshed = Some(Utc::now().timestamp_micros());
+        }
+        let mutations = [Mutation::CreateTable(service::CreateTable {
+            schema: self.tbl_reference.schema.clone().into_owned(),
+            name: self.tbl_reference.name.clone().into_owned(),
+            options,
+            if_not_exists: self.if_not_exists,
+            or_replace,
+        })]
+        .into_iter()
+        .chain(self.sequences.into_iter().map(Mutation::CreateSequence));
         let state = mutator
-            .mutate(
-                self.catalog_version,
-                [Mutation::CreateTable(service::CreateTable {
-                    schema: self.tbl_reference.schema.clone().into_owned(),
-                    name: self.tbl_reference.name.clone().into_owned(),
-                    options: self.arrow_schema.into(),
-                    if_not_exists: self.if_not_exists,
-                    or_replace,
-                })],
-            )
+            .mutate(self.catalog_version, mutations)
             .await
             .map_err(|e| {
                 DataFusionError::Execution(format!("failed to create table in catalog: {e}"))
@@ -162,24 +175,31 @@ impl CreateTableExec {
         })?;
 
         match (source, or_replace) {
-            (Some(input), overwrite) => insert(&table, input, overwrite, context).await?,
+            (Some(input), overwrite) => insert(&table, input, overwrite, context.clone()).await?,
 
             // if it's a 'replace' and there is no insert, we overwrite with an empty table
             (None, true) => {
                 let input = Arc::new(EmptyExec::new(false, TableProvider::schema(&table)));
-                insert(&table, input, true, context).await?
+                insert(&table, input, true, context.clone()).await?
             }
             (None, false) => {}
         };
         info!(loc = %table.storage_location(), "native table created");
 
+        if let Some(scheduler) = context
+            .session_config()
+            .get_extension::<MaterializedViewScheduler>()
+        {
+            scheduler.schedule(ent);
+        }
+
         // TODO: Add storage tracking job.
 
         Ok(new_operation_batch("create_table"))
     }
 }
 
-async fn insert(
+pub(super) async fn insert(
     tbl: &NativeTable,
     input: Arc<dyn ExecutionPlan>,
     overwrite: bool,
@@ -7,17 +7,23 @@ use datafusion::execution::context::SessionState;
 use datafusion::execution::TaskContext;
 use datafusion::physical_expr::PhysicalSortExpr;
 use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::memory::MemoryExec;
 use datafusion::physical_plan::{
-    stream::RecordBatchStreamAdapter, DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning,
-    SendableRecordBatchStream, Statistics,
+    collect, stream::RecordBatchStreamAdapter, DisplayAs, DisplayFormatType, ExecutionPlan,
+    Partitioning, SendableRecordBatchStream, Statistics,
 };
 use datafusion::scalar::ScalarValue;
+use datasources::native::access::NativeTableStorage;
 use futures::{stream, StreamExt};
 
 use std::any::Any;
 use std::fmt;
 use std::sync::Arc;
 
+use crate::background_jobs::storage::TableCompaction;
+use crate::background_jobs::JobRunner;
+
+use super::constraint_check::{check_unique, ConstraintCheck, ConstraintCheckExec, UniqueCheck};
 use super::remote_scan::ProviderReference;
 use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
 
@@ -25,6 +31,12 @@ use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL
 pub struct InsertExec {
     pub provider: ProviderReference,
     pub source: Arc<dyn ExecutionPlan>,
+    /// Checks rows from the source must pass before being inserted.
+    pub checks: Vec<ConstraintCheck>,
+    /// Unique constraints to enforce, empty if not enforcing uniqueness.
+    pub unique_checks: Vec<UniqueCheck>,
+    /// Compaction to schedule after a successful insert.
+    pub compaction: Option<TableCompaction>,
 }
 
 impl ExecutionPlan for InsertExec {
@@ -55,6 +67,9 @@ impl ExecutionPlan for InsertExec {
         Ok(Arc::new(InsertExec {
             provider: self.provider.clone(),
             source: children.get(0).unwrap().clone(),
+            checks: self.checks.clone(),
+            unique_checks: self.unique_checks.clone(),
+            compaction: self.compaction.clone(),
         }))
     }
 
@@ -77,7 +92,26 @@ impl ExecutionPlan for InsertExec {
                 )),
                 ProviderReference::Provider(provider) => {
                     // TODO: Add background job to track storage for native tables.
-                    Self::do_insert(provider, this.source, context).await
+                    let batch = Self::do_insert(
+                        provider,
+                        this.source,
+                        &this.checks,
+                        &this.unique_checks,
+                        context.clone(),
+                    )
+                    .await?;
+
+                    if let Some(compaction) = &this.compaction {
+                        let config = context.session_config();
+                        if let (Some(storage), Some(jobs)) = (
+                            config.get_extension::<NativeTableStorage>(),
+                            config.get_extension::<JobRunner>(),
+                        ) {
+                            compaction.schedule(storage.as_ref().clone(), &jobs);
+                        }
+                    }
+
+                    Ok(batch)
                 }
             }
         });
@@ -103,6 +137,8 @@ impl InsertExec {
     pub async fn do_insert(
         table: Arc<dyn TableProvider>,
         source: Arc<dyn ExecutionPlan>,
+        checks: &[ConstraintCheck],
+        unique_checks: &[UniqueCheck],
         context: Arc<TaskContext>,
     ) -> DataFusionResult<RecordBatch> {
         let state =
@@ -114,6 +150,30 @@ impl InsertExec {
             source
         };
 
+        let source: Arc<dyn ExecutionPlan> = if checks.is_empty() {
+            source
+        } else {
+            Arc::new(ConstraintCheckExec::try_new(source, checks, &state)?)
+        };
+
+        // Checking uniqueness requires the full set of rows being inserted, so
+        // buffer them up before writing anything.
+        let source: Arc<dyn ExecutionPlan> = if unique_checks.is_empty() {
+            source
+        } else {
+            let schema = source.schema();
+            let batches = collect(source, context.clone()).await?;
+            check_unique(
+                table.clone(),
+                schema.clone(),
+                batches.clone(),
+                unique_checks,
+                state.clone(),
+            )
+            .await?;
+            Arc::new(MemoryExec::try_new(&[batches], schema, None)?)
+        };
+
         let exec = table.insert_into(&state, source, false).await?;
 
         let mut stream = exec.execute(0, context)?;
@@ -3,11 +3,17 @@ pub mod alter_table_rename;
 pub mod alter_tunnel_rotate_keys;
 pub mod client_recv;
 pub mod client_send;
+pub mod comment_on;
+pub mod constraint_check;
 pub mod copy_to;
 pub mod create_credentials;
 pub mod create_external_database;
+pub mod create_external_database_table;
 pub mod create_external_table;
+pub mod create_function;
+pub mod create_role;
 pub mod create_schema;
+pub mod create_sequence;
 pub mod create_table;
 pub mod create_temp_table;
 pub mod create_tunnel;
@@ -15,18 +21,26 @@ pub mod create_view;
 pub mod delete;
 pub mod drop_credentials;
 pub mod drop_database;
+pub mod drop_functions;
+pub mod drop_roles;
 pub mod drop_schemas;
+pub mod drop_sequences;
 pub mod drop_tables;
 pub mod drop_temp_tables;
 pub mod drop_tunnel;
 pub mod drop_views;
+pub mod grant_privileges;
 pub mod insert;
+pub mod optimize_table;
+pub mod refresh_materialized_view;
 pub mod remote_exec;
 pub mod remote_scan;
+pub mod revoke_privileges;
 pub mod send_recv;
 pub mod set_var;
 pub mod show_var;
 pub mod update;
+pub mod vacuum_table;
 pub mod values;
 
 use crate::planner::extension::PhysicalExtensionNode;
@@ -0,0 +1,120 @@
+use datafusion::arrow::array::UInt64Array;
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::{
+    stream::RecordBatchStreamAdapter, DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning,
+    SendableRecordBatchStream, Statistics,
+};
+use datasources::native::access::NativeTableStorage;
+use futures::stream;
+use once_cell::sync::Lazy;
+use protogen::metastore::types::catalog::TableEntry;
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+/// Arrow schema for the metrics output of an `OPTIMIZE`.
+pub static OPTIMIZE_TABLE_PHYSICAL_SCHEMA: Lazy<Arc<Schema>> = Lazy::new(|| {
+    Arc::new(Schema::new(vec![
+        Field::new("files_added", DataType::UInt64, false),
+        Field::new("files_removed", DataType::UInt64, false),
+        Field::new("partitions_optimized", DataType::UInt64, false),
+    ]))
+});
+
+#[derive(Debug, Clone)]
+pub struct OptimizeTableExec {
+    pub table: TableEntry,
+    pub zorder_by: Vec<String>,
+}
+
+impl ExecutionPlan for OptimizeTableExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        OPTIMIZE_TABLE_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        Err(DataFusionError::Plan(
+            "Cannot change children for OptimizeTableExec".to_string(),
+        ))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "OptimizeTableExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let storage = context
+            .session_config()
+            .get_extension::<NativeTableStorage>()
+            .expect("context should have native table storage");
+
+        let stream = stream::once(optimize(self.clone(), storage));
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+impl DisplayAs for OptimizeTableExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "OptimizeTableExec")
+    }
+}
+
+async fn optimize(
+    plan: OptimizeTableExec,
+    storage: impl AsRef<NativeTableStorage>,
+) -> DataFusionResult<RecordBatch> {
+    let storage = storage.as_ref();
+
+    let metrics = storage
+        .optimize_table(&plan.table, plan.zorder_by)
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to optimize: {e}")))?;
+
+    let batch = RecordBatch::try_new(
+        OPTIMIZE_TABLE_PHYSICAL_SCHEMA.clone(),
+        vec![
+            Arc::new(UInt64Array::from(vec![metrics.files_added])),
+            Arc::new(UInt64Array::from(vec![metrics.files_removed])),
+            Arc::new(UInt64Array::from(vec![metrics.partitions_optimized])),
+        ],
+    )?;
+
+    Ok(batch)
+}
@@ -0,0 +1,156 @@
+use crate::background_jobs::materialized_views::MaterializedViewScheduler;
+use crate::metastore::catalog::{CatalogMutator, SessionCatalog};
+use chrono::Utc;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::{
+    coalesce_partitions::CoalescePartitionsExec, stream::RecordBatchStreamAdapter, DisplayAs,
+    DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream, Statistics,
+};
+use datasources::native::access::NativeTableStorage;
+use futures::stream;
+use protogen::metastore::types::catalog::{CatalogEntry, TableEntry};
+use protogen::metastore::types::service::{self, Mutation};
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use super::create_table::insert;
+use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
+
+/// Replaces the contents of a materialized view with the output of its query.
+#[derive(Debug, Clone)]
+pub struct RefreshMaterializedViewExec {
+    pub catalog_version: u64,
+    /// Name of the schema containing the view.
+    pub schema: String,
+    pub table: TableEntry,
+    pub source: Arc<dyn ExecutionPlan>,
+}
+
+impl ExecutionPlan for RefreshMaterializedViewExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.source.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.len() != 1 {
+            return Err(DataFusionError::Plan(
+                "RefreshMaterializedViewExec requires exactly one child".to_string(),
+            ));
+        }
+        Ok(Arc::new(RefreshMaterializedViewExec {
+            catalog_version: self.catalog_version,
+            schema: self.schema.clone(),
+            table: self.table.clone(),
+            source: children[0].clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "RefreshMaterializedViewExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let mutator = context
+            .session_config()
+            .get_extension::<CatalogMutator>()
+            .expect("context should have catalog mutator");
+        let storage = context
+            .session_config()
+            .get_extension::<NativeTableStorage>()
+            .expect("context should have native table storage");
+
+        let stream = stream::once(refresh(self.clone(), mutator, storage, context));
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+impl DisplayAs for RefreshMaterializedViewExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RefreshMaterializedViewExec")
+    }
+}
+
+async fn refresh(
+    plan: RefreshMaterializedViewExec,
+    mutator: Arc<CatalogMutator>,
+    storage: Arc<NativeTableStorage>,
+    context: Arc<TaskContext>,
+) -> DataFusionResult<RecordBatch> {
+    let table = storage
+        .load_table(&plan.table)
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to load view table: {e}")))?;
+
+    let source = if plan.source.output_partitioning().partition_count() != 1 {
+        Arc::new(CoalescePartitionsExec::new(plan.source))
+    } else {
+        plan.source
+    };
+    insert(&table, source, true, context.clone()).await?;
+
+    let state = mutator
+        .mutate(
+            plan.catalog_version,
+            [Mutation::RefreshMaterializedView(
+                service::RefreshMaterializedView {
+                    schema: plan.schema,
+                    name: plan.table.meta.name.clone(),
+                    refreshed_at: Utc::now().timestamp_micros(),
+                },
+            )],
+        )
+        .await
+        .map_err(|e| {
+            DataFusionError::Execution(format!("failed to record materialized view refresh: {e}"))
+        })?;
+
+    if let Some(scheduler) = context
+        .session_config()
+        .get_extension::<MaterializedViewScheduler>()
+    {
+        let catalog = SessionCatalog::new(state);
+        if let Some(CatalogEntry::Table(table)) = catalog.get_by_oid(plan.table.meta.id) {
+            scheduler.schedule(table);
+        }
+    }
+
+    Ok(new_operation_batch("refresh_materialized_view"))
+}
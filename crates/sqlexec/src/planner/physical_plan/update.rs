@@ -1,6 +1,7 @@
 use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::context::SessionState;
 use datafusion::execution::TaskContext;
 use datafusion::physical_expr::PhysicalSortExpr;
 use datafusion::physical_plan::{
@@ -15,6 +16,9 @@ use std::any::Any;
 use std::fmt;
 use std::sync::Arc;
 
+use super::constraint_check::{
+    check_existing_rows, check_unique_after_update, ConstraintCheck, UniqueCheck,
+};
 use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
 
 #[derive(Debug, Clone)]
@@ -22,6 +26,11 @@ pub struct UpdateExec {
     pub table: TableEntry,
     pub updates: Vec<(String, Expr)>,
     pub where_expr: Option<Expr>,
+    /// Checks the updated rows must pass, with the updated values already
+    /// substituted into the expressions.
+    pub checks: Vec<ConstraintCheck>,
+    /// Unique constraints to enforce, empty if not enforcing uniqueness.
+    pub unique_checks: Vec<UniqueCheck>,
 }
 
 impl ExecutionPlan for UpdateExec {
@@ -70,7 +79,9 @@ impl ExecutionPlan for UpdateExec {
             .get_extension::<NativeTableStorage>()
             .expect("context should have native table storage");
 
-        let stream = stream::once(update(self.clone(), storage));
+        let state =
+            SessionState::with_config_rt(context.session_config().clone(), context.runtime_env());
+        let stream = stream::once(update(self.clone(), storage, state));
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
             self.schema(),
@@ -92,9 +103,41 @@ impl DisplayAs for UpdateExec {
 async fn update(
     plan: UpdateExec,
     storage: impl AsRef<NativeTableStorage>,
+    state: SessionState,
 ) -> DataFusionResult<RecordBatch> {
     let storage = storage.as_ref();
 
+    // Constraints are validated by scanning the table prior to applying the
+    // update. This is best-effort: the scan and the update aren't atomic, and
+    // nothing serializes concurrent writes from other sessions, so a
+    // concurrent insert or update may still introduce a violation.
+    if !plan.checks.is_empty() || !plan.unique_checks.is_empty() {
+        let table = storage
+            .load_table(&plan.table)
+            .await
+            .map_err(|e| DataFusionError::Execution(format!("failed to load table: {e}")))?
+            .into_table_provider();
+        if !plan.checks.is_empty() {
+            check_existing_rows(
+                table.clone(),
+                plan.where_expr.clone(),
+                &plan.checks,
+                state.clone(),
+            )
+            .await?;
+        }
+        if !plan.unique_checks.is_empty() {
+            check_unique_after_update(
+                table,
+                &plan.updates,
+                plan.where_expr.clone(),
+                &plan.unique_checks,
+                state,
+            )
+            .await?;
+        }
+    }
+
     let num_updated = storage
         .update_rows_where(&plan.table, plan.updates, plan.where_expr)
         .await
@@ -0,0 +1,116 @@
+use datafusion::arrow::array::UInt64Array;
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::{
+    stream::RecordBatchStreamAdapter, DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning,
+    SendableRecordBatchStream, Statistics,
+};
+use datasources::native::access::NativeTableStorage;
+use futures::stream;
+use once_cell::sync::Lazy;
+use protogen::metastore::types::catalog::TableEntry;
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+/// Arrow schema for the metrics output of a `VACUUM`.
+pub static VACUUM_TABLE_PHYSICAL_SCHEMA: Lazy<Arc<Schema>> = Lazy::new(|| {
+    Arc::new(Schema::new(vec![Field::new(
+        "files_deleted",
+        DataType::UInt64,
+        false,
+    )]))
+});
+
+#[derive(Debug, Clone)]
+pub struct VacuumTableExec {
+    pub table: TableEntry,
+    pub retain_hours: Option<u64>,
+}
+
+impl ExecutionPlan for VacuumTableExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        VACUUM_TABLE_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        Err(DataFusionError::Plan(
+            "Cannot change children for VacuumTableExec".to_string(),
+        ))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "VacuumTableExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let storage = context
+            .session_config()
+            .get_extension::<NativeTableStorage>()
+            .expect("context should have native table storage");
+
+        let stream = stream::once(vacuum(self.clone(), storage));
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+impl DisplayAs for VacuumTableExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VacuumTableExec")
+    }
+}
+
+async fn vacuum(
+    plan: VacuumTableExec,
+    storage: impl AsRef<NativeTableStorage>,
+) -> DataFusionResult<RecordBatch> {
+    let storage = storage.as_ref();
+
+    let files_deleted = storage
+        .vacuum_table(&plan.table, plan.retain_hours)
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to vacuum: {e}")))?;
+
+    let batch = RecordBatch::try_new(
+        VACUUM_TABLE_PHYSICAL_SCHEMA.clone(),
+        vec![Arc::new(UInt64Array::from(vec![files_deleted as u64]))],
+    )?;
+
+    Ok(batch)
+}
@@ -11,6 +11,9 @@ pub enum PreprocessError {
 
     #[error("Casting expressions to regclass unsupported")]
     ExprUnsupportedRegclassCast,
+
+    #[error("Missing value for parameter '{0}'")]
+    MissingParameter(String),
 }
 
 pub fn preprocess<V>(statement: &mut ast::Statement, visitor: &mut V) -> Result<(), PreprocessError>
@@ -89,3 +92,71 @@ impl ast::VisitorMut for EscapedStringToDoubleQuoted {
         ControlFlow::Continue(())
     }
 }
+
+/// Replace named placeholders (`$name`) with positional placeholders (`$1`).
+///
+/// The position of a named placeholder is determined by the index of its name
+/// in `names`. Positional placeholders are left as is.
+pub struct NamedPlaceholderReplacer<'a> {
+    pub names: &'a [String],
+}
+
+impl<'a> ast::VisitorMut for NamedPlaceholderReplacer<'a> {
+    type Break = PreprocessError;
+
+    fn post_visit_expr(&mut self, expr: &mut ast::Expr) -> ControlFlow<Self::Break> {
+        if let ast::Expr::Value(ast::Value::Placeholder(placeholder)) = expr {
+            let name = match placeholder.strip_prefix('$') {
+                Some(name) if name.parse::<usize>().is_err() => name,
+                _ => return ControlFlow::Continue(()),
+            };
+
+            match self.names.iter().position(|n| n == name) {
+                Some(idx) => *placeholder = format!("${}", idx + 1),
+                None => {
+                    return ControlFlow::Break(PreprocessError::MissingParameter(name.to_string()))
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{self, StatementWithExtensions};
+
+    fn replace_named(sql: &str, names: &[&str]) -> Result<String, PreprocessError> {
+        let names: Vec<_> = names.iter().map(|s| s.to_string()).collect();
+        let mut stmt = match parser::parse_sql(sql).unwrap().pop_front().unwrap() {
+            StatementWithExtensions::Statement(stmt) => stmt,
+            other => panic!("unexpected statement: {other}"),
+        };
+        preprocess(&mut stmt, &mut NamedPlaceholderReplacer { names: &names })?;
+        Ok(stmt.to_string())
+    }
+
+    #[test]
+    fn named_placeholders() {
+        assert_eq!(
+            "SELECT * FROM t WHERE a = $2 AND b = $1 AND c = $2",
+            replace_named(
+                "select * from t where a = $a and b = $b and c = $a",
+                &["b", "a"]
+            )
+            .unwrap()
+        );
+
+        // Positional placeholders are untouched.
+        assert_eq!(
+            "SELECT $1, $1",
+            replace_named("select $1, $a", &["a"]).unwrap()
+        );
+
+        assert!(matches!(
+            replace_named("select $missing", &["a"]),
+            Err(PreprocessError::MissingParameter(name)) if name == "missing"
+        ));
+    }
+}
@@ -0,0 +1,319 @@
+//! Privilege checks for catalog objects.
+//!
+//! Sessions are unrestricted until the first role is created, which keeps
+//! deployments that never create roles working as before. Once roles exist, a
+//! session is unrestricted only if its user name resolves to a superuser role.
+//! Users with a non-superuser role are restricted to what's been granted to
+//! the role, and users without a role are denied access to everything but
+//! builtins and temp objects.
+//!
+//! Privileges are inherited downwards: a privilege granted on a database
+//! applies to all schemas and tables within it, and a privilege granted on a
+//! schema applies to all tables and views within it.
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use protogen::metastore::types::catalog::{CatalogEntry, Privilege, RoleEntry};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use sqlbuiltins::builtins::DEFAULT_CATALOG;
+use std::num::NonZeroU32;
+
+use crate::metastore::catalog::SessionCatalog;
+use crate::resolve::ResolvedEntry;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PrivilegeError {
+    #[error("Permission denied: role '{role}' does not have {privilege} privilege on {object_type} '{object}'")]
+    PermissionDenied {
+        role: String,
+        privilege: Privilege,
+        object_type: &'static str,
+        object: String,
+    },
+
+    #[error("Permission denied: user '{user}' does not have a role")]
+    MissingRole { user: String },
+
+    #[error("Permission denied: only superusers may {action}")]
+    SuperuserRequired { action: &'static str },
+
+    #[error("The first role must be a superuser role for the current user '{user}', e.g. CREATE USER {user} WITH SUPERUSER")]
+    FirstRoleNotCurrentSuperuser { user: String },
+
+    #[error("Cannot drop the role of the current user '{user}' while other roles exist")]
+    DropCurrentRole { user: String },
+
+    #[error("Failed to hash password")]
+    HashPassword,
+}
+
+type Result<T, E = PrivilegeError> = std::result::Result<T, E>;
+
+/// What a session is allowed to access.
+enum Access<'a> {
+    /// No roles exist, or the user is a superuser.
+    Unrestricted,
+    /// Restricted to the privileges granted to a role.
+    Role(&'a RoleEntry),
+    /// Roles exist but the user doesn't have one.
+    Denied { user: String },
+}
+
+/// Checks privileges for the current session's role.
+pub struct PrivilegeChecker<'a> {
+    catalog: &'a SessionCatalog,
+    access: Access<'a>,
+}
+
+impl<'a> PrivilegeChecker<'a> {
+    /// Create a new checker for the given user.
+    pub fn new(catalog: &'a SessionCatalog, user: &str) -> Self {
+        let access = match catalog.resolve_role(user) {
+            Some(role) if role.superuser => Access::Unrestricted,
+            Some(role) => Access::Role(role),
+            None if !catalog.has_roles() => Access::Unrestricted,
+            None => Access::Denied {
+                user: user.to_string(),
+            },
+        };
+        PrivilegeChecker { catalog, access }
+    }
+
+    /// Whether or not the session can bypass all privilege checks.
+    pub fn is_unrestricted(&self) -> bool {
+        matches!(self.access, Access::Unrestricted)
+    }
+
+    /// Error if the session isn't unrestricted.
+    pub fn check_superuser(&self, action: &'static str) -> Result<()> {
+        if self.is_unrestricted() {
+            Ok(())
+        } else {
+            Err(PrivilegeError::SuperuserRequired { action })
+        }
+    }
+
+    /// Check that the session has a privilege on an entry, or on one of the
+    /// entry's parents.
+    ///
+    /// Temp objects are always accessible. Builtin tables and views can
+    /// always be read.
+    pub fn check_entry(&self, ent: &CatalogEntry, privilege: Privilege) -> Result<()> {
+        if self.is_unrestricted() {
+            return Ok(());
+        }
+
+        let meta = ent.get_meta();
+        if meta.is_temp {
+            return Ok(());
+        }
+        if meta.builtin
+            && privilege == Privilege::Select
+            && matches!(ent, CatalogEntry::Table(_) | CatalogEntry::View(_))
+        {
+            return Ok(());
+        }
+
+        let role = match &self.access {
+            Access::Role(role) => role,
+            Access::Denied { user } => {
+                return Err(PrivilegeError::MissingRole { user: user.clone() })
+            }
+            Access::Unrestricted => return Ok(()),
+        };
+
+        let mut current = Some(ent);
+        while let Some(ent) = current {
+            let meta = ent.get_meta();
+            if role.has_privilege(meta.id, privilege) {
+                return Ok(());
+            }
+            current = match ent {
+                CatalogEntry::Schema(_)
+                | CatalogEntry::Table(_)
+                | CatalogEntry::View(_)
+                | CatalogEntry::Sequence(_) => self.catalog.get_by_oid(meta.parent),
+                _ => None,
+            };
+        }
+
+        Err(PrivilegeError::PermissionDenied {
+            role: role.meta.name.clone(),
+            privilege,
+            object_type: meta.entry_type.as_str(),
+            object: meta.name.clone(),
+        })
+    }
+
+    /// Check that the session has a privilege on a resolved entry.
+    ///
+    /// Privileges on tables in external databases are checked against the
+    /// database entry.
+    pub fn check_resolved(&self, ent: &ResolvedEntry, privilege: Privilege) -> Result<()> {
+        match ent {
+            ResolvedEntry::Entry(ent) => self.check_entry(ent, privilege),
+            ResolvedEntry::NeedsExternalResolution { db_ent, .. } => {
+                match self.catalog.get_by_oid(db_ent.meta.id) {
+                    Some(ent) => self.check_entry(ent, privilege),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// Check that the session has a privilege on a schema.
+    ///
+    /// Missing schemas are not checked here and are expected to error during
+    /// planning or execution.
+    pub fn check_schema(&self, name: &str, privilege: Privilege) -> Result<()> {
+        if self.is_unrestricted() {
+            return Ok(());
+        }
+        let ent = self
+            .catalog
+            .resolve_schema(name)
+            .and_then(|schema| self.catalog.get_by_oid(schema.meta.id));
+        match ent {
+            Some(ent) => self.check_entry(ent, privilege),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that the session has a privilege on the default database.
+    ///
+    /// This is used for objects that live directly in the database, e.g.
+    /// schemas, external databases, tunnels, and credentials.
+    pub fn check_default_database(&self, privilege: Privilege) -> Result<()> {
+        if self.is_unrestricted() {
+            return Ok(());
+        }
+        let ent = self
+            .catalog
+            .resolve_database(DEFAULT_CATALOG)
+            .and_then(|db| self.catalog.get_by_oid(db.meta.id));
+        match ent {
+            Some(ent) => self.check_entry(ent, privilege),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The role a user logs in as.
+#[derive(Debug, Clone)]
+pub enum LoginRole {
+    /// No roles exist. Any user accepted by the server's authenticator may log
+    /// in.
+    NoRoles,
+    /// The user's role.
+    Role(RoleEntry),
+    /// Roles exist, but the user doesn't have one.
+    Missing,
+}
+
+impl LoginRole {
+    pub fn for_user(catalog: &SessionCatalog, user: &str) -> Self {
+        match catalog.resolve_role(user) {
+            Some(role) => LoginRole::Role(role.clone()),
+            None if catalog.has_roles() => LoginRole::Missing,
+            None => LoginRole::NoRoles,
+        }
+    }
+}
+
+/// How a user must authenticate for their login role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoleAuthentication {
+    /// No roles exist, or the role doesn't have a password. The server's
+    /// authenticator decides if the user may log in.
+    Authenticator,
+    /// The role has a password, but none was provided.
+    PasswordRequired,
+    /// The provided password matches the role's password.
+    Authenticated,
+}
+
+const PASSWORD_HASH_SCHEME: &str = "pbkdf2-sha256";
+const PASSWORD_HASH_ITERATIONS: u32 = 100_000;
+const PASSWORD_SALT_LEN: usize = 16;
+const PASSWORD_HASH_LEN: usize = 32;
+
+/// Hash a role's password for storing in the catalog.
+///
+/// The returned string includes the scheme, iteration count, and salt, e.g.
+/// `pbkdf2-sha256$100000$<salt>$<hash>`.
+pub fn hash_password(password: &str) -> Result<String> {
+    let mut salt = [0; PASSWORD_SALT_LEN];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| PrivilegeError::HashPassword)?;
+
+    let iterations = NonZeroU32::new(PASSWORD_HASH_ITERATIONS).unwrap();
+    let mut hash = [0; PASSWORD_HASH_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        &salt,
+        password.as_bytes(),
+        &mut hash,
+    );
+
+    Ok(format!(
+        "{PASSWORD_HASH_SCHEME}${iterations}${}${}",
+        BASE64.encode(salt),
+        BASE64.encode(hash)
+    ))
+}
+
+/// Verify a password against a hash from `hash_password`.
+pub fn verify_password(password_hash: &str, password: &str) -> bool {
+    let mut parts = password_hash.split('$');
+    let (scheme, iterations, salt, hash) =
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(scheme), Some(iterations), Some(salt), Some(hash)) => {
+                (scheme, iterations, salt, hash)
+            }
+            _ => return false,
+        };
+    if scheme != PASSWORD_HASH_SCHEME {
+        return false;
+    }
+
+    let iterations = match iterations.parse().ok().and_then(NonZeroU32::new) {
+        Some(iterations) => iterations,
+        None => return false,
+    };
+    let (salt, hash) = match (BASE64.decode(salt), BASE64.decode(hash)) {
+        (Ok(salt), Ok(hash)) => (salt, hash),
+        _ => return false,
+    };
+
+    // Constant time comparison.
+    pbkdf2::verify(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        &salt,
+        password.as_bytes(),
+        &hash,
+    )
+    .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn password_hash() {
+        let hash = hash_password("secret").unwrap();
+        assert!(hash.starts_with("pbkdf2-sha256$100000$"));
+        assert!(!hash.contains("secret"));
+
+        assert!(verify_password(&hash, "secret"));
+        assert!(!verify_password(&hash, "Secret"));
+        assert!(!verify_password(&hash, ""));
+        assert!(!verify_password("secret", "secret"));
+
+        // Salted, hashing again gives a different hash.
+        assert_ne!(hash, hash_password("secret").unwrap());
+    }
+}
@@ -1,12 +1,19 @@
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::Arc;
 
+use datafusion::arrow::compute::kernels::cast_utils::parse_interval_month_day_nano;
 use datafusion::arrow::datatypes::{
-    DataType, Field, Schema, TimeUnit, DECIMAL128_MAX_PRECISION, DECIMAL_DEFAULT_SCALE,
+    DataType, Field, IntervalMonthDayNanoType, Schema, TimeUnit, DECIMAL128_MAX_PRECISION,
+    DECIMAL_DEFAULT_SCALE,
 };
 use datafusion::common::parsers::CompressionTypeVariant;
-use datafusion::common::{FileType, OwnedSchemaReference, OwnedTableReference, ToDFSchema};
-use datafusion::logical_expr::{cast, col, LogicalPlanBuilder};
+use datafusion::common::tree_node::{Transformed, TreeNode};
+use datafusion::common::{Column, DFSchema, OwnedSchemaReference, OwnedTableReference, ToDFSchema};
+use datafusion::config::ExtensionOptions;
+use datafusion::logical_expr::{cast, col, lit, Expr, ExprSchemable, LogicalPlanBuilder};
+use datafusion::optimizer::simplify_expressions::{ExprSimplifier, SimplifyContext};
+use datafusion::physical_expr::execution_props::ExecutionProps;
 use datafusion::sql::planner::{object_name_to_table_reference, IdentNormalizer, PlannerContext};
 use datafusion::sql::sqlparser::ast::AlterTableOperation;
 use datafusion::sql::sqlparser::ast::{self, Ident, ObjectName, ObjectType};
@@ -14,6 +21,7 @@ use datafusion::sql::TableReference;
 use datafusion_ext::planner::SqlQueryPlanner;
 use datafusion_ext::AsyncContextProvider;
 use datasources::bigquery::{BigQueryAccessor, BigQueryTableAccess};
+use datasources::clickhouse::{ClickhouseAccessor, ClickhouseTableAccess};
 use datasources::common::ssh::{key::SshKey, SshConnection, SshConnectionParameters};
 use datasources::common::url::{DatasourceUrl, DatasourceUrlType};
 use datasources::debug::DebugTableType;
@@ -25,24 +33,35 @@ use datasources::object_store::gcs::GcsStoreAccess;
 use datasources::object_store::generic::GenericStoreAccess;
 use datasources::object_store::local::LocalStoreAccess;
 use datasources::object_store::s3::S3StoreAccess;
-use datasources::object_store::{file_type_from_path, ObjStoreAccess, ObjStoreAccessor};
+use datasources::object_store::{
+    file_type_from_path, ObjStoreAccess, ObjStoreAccessor, ObjStoreFileType,
+};
 use datasources::postgres::{PostgresAccess, PostgresDbConnection};
 use datasources::snowflake::{SnowflakeAccessor, SnowflakeDbConnection, SnowflakeTableAccess};
+use datasources::sqlite::{SqliteAccessor, SqliteTableAccess};
+use datasources::sqlserver::{SqlServerAccessor, SqlServerDbConnection, SqlServerTableAccess};
 use object_store::aws::AmazonS3ConfigKey;
 use object_store::gcp::GoogleConfigKey;
-use protogen::metastore::types::catalog::RuntimePreference;
+use protogen::metastore::types::catalog::{
+    CatalogEntry, FunctionType, Privilege, RuntimePreference, SqlFunction, TableEntry,
+};
 use protogen::metastore::types::options::{
     CopyToDestinationOptions, CopyToDestinationOptionsGcs, CopyToDestinationOptionsLocal,
-    CopyToDestinationOptionsS3, CopyToFormatOptions, CopyToFormatOptionsCsv,
-    CopyToFormatOptionsJson, CopyToFormatOptionsParquet, CredentialsOptions, CredentialsOptionsAws,
-    CredentialsOptionsDebug, CredentialsOptionsGcp, DatabaseOptions, DatabaseOptionsBigQuery,
-    DatabaseOptionsDebug, DatabaseOptionsDeltaLake, DatabaseOptionsMongo, DatabaseOptionsMysql,
-    DatabaseOptionsPostgres, DatabaseOptionsSnowflake, DeltaLakeCatalog, DeltaLakeUnityCatalog,
-    StorageOptions, TableOptions, TableOptionsBigQuery, TableOptionsDebug, TableOptionsGcs,
-    TableOptionsLocal, TableOptionsMongo, TableOptionsMysql, TableOptionsObjectStore,
-    TableOptionsPostgres, TableOptionsS3, TableOptionsSnowflake, TunnelOptions, TunnelOptionsDebug,
+    CopyToDestinationOptionsS3, CopyToFormatOptions, CopyToFormatOptionsAvro,
+    CopyToFormatOptionsCsv, CopyToFormatOptionsJson, CopyToFormatOptionsParquet,
+    CredentialsOptions, CredentialsOptionsAws, CredentialsOptionsDebug, CredentialsOptionsGcp,
+    DatabaseOptions, DatabaseOptionsBigQuery, DatabaseOptionsClickhouse, DatabaseOptionsDebug,
+    DatabaseOptionsDeltaLake, DatabaseOptionsMongo, DatabaseOptionsMysql, DatabaseOptionsPostgres,
+    DatabaseOptionsSnowflake, DatabaseOptionsSqlServer, DatabaseOptionsSqlite, DeltaLakeCatalog,
+    DeltaLakeUnityCatalog, InternalCheckConstraint, InternalColumnDefinition,
+    InternalMaterializedView, InternalUniqueConstraint, StorageOptions, TableOptions,
+    TableOptionsBigQuery, TableOptionsClickhouse, TableOptionsDebug, TableOptionsGcs,
+    TableOptionsInternal, TableOptionsLocal, TableOptionsMongo, TableOptionsMysql,
+    TableOptionsObjectStore, TableOptionsPostgres, TableOptionsS3, TableOptionsSnowflake,
+    TableOptionsSqlServer, TableOptionsSqlite, TunnelOptions, TunnelOptionsDebug,
     TunnelOptionsInternal, TunnelOptionsSsh,
 };
+use protogen::metastore::types::service::{self, CommentObject, GrantObject};
 use sqlbuiltins::builtins::{CURRENT_SESSION_SCHEMA, DEFAULT_CATALOG};
 use sqlbuiltins::validation::{
     validate_copyto_dest_creds_support, validate_copyto_dest_format_support,
@@ -51,45 +70,71 @@ use sqlbuiltins::validation::{
 };
 use tracing::debug;
 
+use crate::background_jobs::storage::TableCompaction;
 use crate::context::local::LocalSessionContext;
 use crate::parser::options::StmtOptions;
 use crate::parser::{
     self, validate_ident, validate_object_name, AlterDatabaseRenameStmt, AlterTunnelAction,
-    AlterTunnelStmt, CopyToSource, CopyToStmt, CreateCredentialsStmt, CreateExternalDatabaseStmt,
-    CreateExternalTableStmt, CreateTunnelStmt, DropCredentialsStmt, DropDatabaseStmt,
-    DropTunnelStmt, StatementWithExtensions,
+    AlterTunnelStmt, CommentObjectType, CommentStmt, CopyToSource, CopyToStmt,
+    CreateCredentialsStmt, CreateExternalDatabaseStmt, CreateExternalTableStmt, CreateFunctionStmt,
+    CreateMaterializedViewStmt, CreateRoleStmt, CreateTunnelStmt, DropCredentialsStmt,
+    DropDatabaseStmt, DropRoleStmt, DropTunnelStmt, FunctionReturns, GrantObjectType, GrantStmt,
+    OptimizeStmt, PrivilegesOnObjects, RefreshMaterializedViewStmt, RevokeStmt, ShowObjectType,
+    ShowStmt, StatementWithExtensions, VacuumStmt,
 };
 use crate::planner::errors::{internal, PlanError, Result};
 use crate::planner::logical_plan::*;
 use crate::planner::preprocess::{preprocess, CastRegclassReplacer, EscapedStringToDoubleQuoted};
+use crate::planner::privileges::{hash_password, PrivilegeChecker, PrivilegeError};
 use crate::remote::table::StubRemoteTableProvider;
 use crate::resolve::EntryResolver;
 
 use super::context_builder::PartialContextProvider;
 use super::extension::ExtensionNode;
+use super::physical_plan::constraint_check::{ConstraintCheck, UniqueCheck};
 use super::physical_plan::remote_scan::ProviderReference;
 
 /// Plan SQL statements for a session.
 pub struct SessionPlanner<'a> {
     ctx: &'a LocalSessionContext,
+    /// Declared types for placeholders ("$1", "$2", ...) in the statement
+    /// being planned.
+    parameter_types: Vec<DataType>,
 }
 
 impl<'a> SessionPlanner<'a> {
     pub fn new(ctx: &'a LocalSessionContext) -> Self {
-        SessionPlanner { ctx }
+        SessionPlanner {
+            ctx,
+            parameter_types: Vec::new(),
+        }
+    }
+
+    /// Use the provided types for placeholders when planning queries.
+    pub fn with_parameter_types(mut self, parameter_types: Vec<DataType>) -> Self {
+        self.parameter_types = parameter_types;
+        self
     }
 
     pub async fn plan_ast(&self, mut statement: StatementWithExtensions) -> Result<LogicalPlan> {
         debug!(%statement, "planning sql statement");
 
         // Run replacers as needed.
-        if let StatementWithExtensions::Statement(inner) = &mut statement {
+        let inner = match &mut statement {
+            StatementWithExtensions::Statement(inner) => Some(inner),
+            StatementWithExtensions::CreateTable(stmt) => Some(&mut stmt.stmt),
+            _ => None,
+        };
+        if let Some(inner) = inner {
             preprocess(inner, &mut CastRegclassReplacer { ctx: self.ctx })?;
             preprocess(inner, &mut EscapedStringToDoubleQuoted)?;
         }
 
         match statement {
             StatementWithExtensions::Statement(stmt) => self.plan_statement(stmt).await,
+            StatementWithExtensions::CreateTable(stmt) => {
+                self.plan_create_table(stmt.stmt, stmt.partition_by).await
+            }
             StatementWithExtensions::CreateExternalTable(stmt) => {
                 self.plan_create_external_table(stmt).await
             }
@@ -106,13 +151,39 @@ impl<'a> SessionPlanner<'a> {
             StatementWithExtensions::CreateCredentials(stmt) => self.plan_create_credentials(stmt),
             StatementWithExtensions::DropCredentials(stmt) => self.plan_drop_credentials(stmt),
             StatementWithExtensions::CopyTo(stmt) => self.plan_copy_to(stmt).await,
+            StatementWithExtensions::CreateRole(stmt) => self.plan_create_role(stmt),
+            StatementWithExtensions::DropRole(stmt) => self.plan_drop_role(stmt),
+            StatementWithExtensions::Grant(stmt) => self.plan_grant(stmt),
+            StatementWithExtensions::Revoke(stmt) => self.plan_revoke(stmt),
+            StatementWithExtensions::Optimize(stmt) => self.plan_optimize(stmt),
+            StatementWithExtensions::Vacuum(stmt) => self.plan_vacuum(stmt),
+            StatementWithExtensions::CreateMaterializedView(stmt) => {
+                self.plan_create_materialized_view(stmt).await
+            }
+            StatementWithExtensions::RefreshMaterializedView(stmt) => {
+                self.plan_refresh_materialized_view(stmt).await
+            }
+            StatementWithExtensions::CreateFunction(stmt) => self.plan_create_function(stmt).await,
+            StatementWithExtensions::Comment(stmt) => self.plan_comment(stmt),
+            StatementWithExtensions::Show(stmt) => self.plan_show(stmt).await,
         }
     }
 
+    /// Get a privilege checker for the session's user.
+    fn privileges(&self) -> PrivilegeChecker<'a> {
+        PrivilegeChecker::new(
+            self.ctx.get_session_catalog(),
+            &self.ctx.get_session_vars().user_name(),
+        )
+    }
+
     async fn plan_create_external_database(
         &self,
         mut stmt: CreateExternalDatabaseStmt,
     ) -> Result<LogicalPlan> {
+        self.privileges()
+            .check_default_database(Privilege::Create)?;
+
         let datasource = normalize_ident(stmt.datasource);
 
         let tunnel = stmt.tunnel.map(normalize_ident);
@@ -182,6 +253,43 @@ impl<'a> SessionPlanner<'a> {
                     })?;
                 DatabaseOptions::Mysql(DatabaseOptionsMysql { connection_string })
             }
+            DatabaseOptions::SQL_SERVER => {
+                let connection_string = get_sqlserver_conn_str(m)?;
+                SqlServerAccessor::validate_external_database(&connection_string, tunnel_options)
+                    .await
+                    .map_err(|e| PlanError::InvalidExternalDatabase {
+                        source: Box::new(e),
+                    })?;
+                DatabaseOptions::SqlServer(DatabaseOptionsSqlServer { connection_string })
+            }
+            DatabaseOptions::CLICKHOUSE => {
+                let connection_string: String = m.remove_required("connection_string")?;
+                ClickhouseAccessor::validate_external_database(&connection_string)
+                    .await
+                    .map_err(|e| PlanError::InvalidExternalDatabase {
+                        source: Box::new(e),
+                    })?;
+                DatabaseOptions::Clickhouse(DatabaseOptionsClickhouse { connection_string })
+            }
+            DatabaseOptions::SQLITE => {
+                let location: String = m.remove_required("location")?;
+
+                let mut storage_options = StorageOptions::try_from(m)?;
+                if let Some(creds) = creds_options {
+                    storage_options_with_credentials(&mut storage_options, creds);
+                }
+
+                SqliteAccessor::validate_external_database(&location, storage_options.clone())
+                    .await
+                    .map_err(|e| PlanError::InvalidExternalDatabase {
+                        source: Box::new(e),
+                    })?;
+
+                DatabaseOptions::Sqlite(DatabaseOptionsSqlite {
+                    location,
+                    storage_options,
+                })
+            }
             DatabaseOptions::MONGO => {
                 let connection_string = get_mongo_conn_str(m)?;
                 // Validate the accessor
@@ -270,6 +378,14 @@ impl<'a> SessionPlanner<'a> {
         &self,
         mut stmt: CreateExternalTableStmt,
     ) -> Result<LogicalPlan> {
+        // Check privileges before attempting to connect to the external
+        // source.
+        let tbl_reference = self
+            .ctx
+            .resolve_table_ref(object_name_to_table_ref(stmt.name.clone())?)?;
+        self.privileges()
+            .check_schema(&tbl_reference.schema, Privilege::Create)?;
+
         let datasource = normalize_ident(stmt.datasource);
 
         let tunnel = stmt.tunnel.map(normalize_ident);
@@ -369,6 +485,92 @@ impl<'a> SessionPlanner<'a> {
                     table: access.name,
                 })
             }
+            TableOptions::SQL_SERVER => {
+                let connection_string = get_sqlserver_conn_str(m)?;
+                let schema = m.remove_required("schema")?;
+                let table = m.remove_required("table")?;
+
+                let access = SqlServerTableAccess {
+                    schema,
+                    name: table,
+                };
+
+                SqlServerAccessor::validate_table_access(
+                    &connection_string,
+                    &access,
+                    tunnel_options,
+                )
+                .await
+                .map_err(|e| PlanError::InvalidExternalTable {
+                    source: Box::new(e),
+                })?;
+
+                TableOptions::SqlServer(TableOptionsSqlServer {
+                    connection_string,
+                    schema: access.schema,
+                    table: access.name,
+                })
+            }
+            TableOptions::CLICKHOUSE => {
+                let connection_string: String = m.remove_required("connection_string")?;
+                let database: Option<String> = m.remove_optional("database")?;
+                let table = m.remove_required("table")?;
+
+                // Use the database from the connection string if one isn't
+                // provided for the table.
+                let database = match database {
+                    Some(database) => database,
+                    None => ClickhouseAccessor::connect(&connection_string)
+                        .map_err(|e| PlanError::InvalidExternalTable {
+                            source: Box::new(e),
+                        })?
+                        .default_database()
+                        .to_string(),
+                };
+
+                let access = ClickhouseTableAccess {
+                    database,
+                    name: table,
+                };
+
+                ClickhouseAccessor::validate_table_access(&connection_string, &access)
+                    .await
+                    .map_err(|e| PlanError::InvalidExternalTable {
+                        source: Box::new(e),
+                    })?;
+
+                TableOptions::Clickhouse(TableOptionsClickhouse {
+                    connection_string,
+                    database: access.database,
+                    table: access.name,
+                })
+            }
+            TableOptions::SQLITE => {
+                let location: String = m.remove_required("location")?;
+                let table: String = m.remove_required("table")?;
+
+                let mut storage_options = StorageOptions::try_from(m)?;
+                if let Some(creds) = creds_options {
+                    storage_options_with_credentials(&mut storage_options, creds);
+                }
+
+                let access = SqliteTableAccess {
+                    schema: SqliteTableAccess::DEFAULT_SCHEMA.to_string(),
+                    name: table,
+                };
+
+                SqliteAccessor::validate_table_access(&location, storage_options.clone(), &access)
+                    .await
+                    .map_err(|e| PlanError::InvalidExternalTable {
+                        source: Box::new(e),
+                    })?;
+
+                TableOptions::Sqlite(TableOptionsSqlite {
+                    location,
+                    table: access.name,
+                    storage_options,
+                })
+            }
             TableOptions::MONGO => {
                 let connection_string = get_mongo_conn_str(m)?;
                 let database = m.remove_required("database")?;
@@ -430,7 +632,7 @@ impl<'a> SessionPlanner<'a> {
 
                 TableOptions::Local(TableOptionsLocal {
                     location,
-                    file_type: format!("{file_type:?}").to_lowercase(),
+                    file_type: file_type.to_string(),
                     compression: compression.map(|c| c.to_string()),
                 })
             }
@@ -457,7 +659,7 @@ impl<'a> SessionPlanner<'a> {
                     bucket,
                     service_account_key,
                     location,
-                    file_type: format!("{file_type:?}").to_lowercase(),
+                    file_type: file_type.to_string(),
                     compression: compression.map(|c| c.to_string()),
                 })
             }
@@ -498,7 +700,7 @@ impl<'a> SessionPlanner<'a> {
                     access_key_id,
                     secret_access_key,
                     location,
-                    file_type: format!("{file_type:?}").to_lowercase(),
+                    file_type: file_type.to_string(),
                     compression: compression.map(|c| c.to_string()),
                 })
             }
@@ -560,6 +762,9 @@ impl<'a> SessionPlanner<'a> {
     }
 
     fn plan_create_tunnel(&self, mut stmt: CreateTunnelStmt) -> Result<LogicalPlan> {
+        self.privileges()
+            .check_default_database(Privilege::Create)?;
+
         let m = &mut stmt.options;
 
         let tunnel_type = normalize_ident(stmt.tunnel);
@@ -591,6 +796,9 @@ impl<'a> SessionPlanner<'a> {
     }
 
     fn plan_create_credentials(&self, mut stmt: CreateCredentialsStmt) -> Result<LogicalPlan> {
+        self.privileges()
+            .check_default_database(Privilege::Create)?;
+
         let m = &mut stmt.options;
 
         let provider = normalize_ident(stmt.provider);
@@ -640,10 +848,66 @@ impl<'a> SessionPlanner<'a> {
 
             ast::Statement::Query(q) => {
                 let mut planner = SqlQueryPlanner::new(&mut context_provider);
-                let plan = planner.query_to_plan(*q).await?;
+                let mut planner_context = PlannerContext::new()
+                    .with_prepare_param_data_types(self.parameter_types.clone());
+                let plan = planner
+                    .query_to_plan_with_context(*q, &mut planner_context)
+                    .await?;
                 Ok(LogicalPlan::Datafusion(plan))
             }
 
+            // PREPARE name [ ( data_type [, ...] ) ] AS statement
+            ast::Statement::Prepare {
+                name,
+                data_types,
+                statement,
+            } => {
+                validate_ident(&name)?;
+                let parameter_types = data_types
+                    .iter()
+                    .map(convert_data_type)
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(PreparedStatementPlan::Prepare {
+                    name: normalize_ident(name),
+                    statement: StatementWithExtensions::Statement(*statement),
+                    parameter_types,
+                }
+                .into())
+            }
+
+            // EXECUTE name [ ( parameter [, ...] ) ]
+            ast::Statement::Execute { name, parameters } => {
+                self.plan_execute(name, parameters).await
+            }
+
+            // DEALLOCATE [ PREPARE ] { name | ALL }
+            ast::Statement::Deallocate { name, .. } => {
+                validate_ident(&name)?;
+                let name = if name.quote_style.is_none() && name.value.eq_ignore_ascii_case("all") {
+                    None
+                } else {
+                    Some(normalize_ident(name))
+                };
+                Ok(PreparedStatementPlan::Deallocate { name }.into())
+            }
+
+            // DESCRIBE <query>
+            ast::Statement::Explain {
+                describe_alias: true,
+                statement,
+                ..
+            } => self.plan_describe_statement(*statement).await,
+
+            // DESCRIBE <table>
+            ast::Statement::ExplainTable { table_name, .. } => {
+                self.plan_show_columns(table_name, None).await
+            }
+
+            // SHOW COLUMNS { FROM | IN } <table> [ LIKE <pattern> | WHERE <expr> ]
+            ast::Statement::ShowColumns {
+                table_name, filter, ..
+            } => self.plan_show_columns(table_name, filter).await,
+
             ast::Statement::Explain {
                 verbose,
                 statement,
@@ -680,6 +944,9 @@ impl<'a> SessionPlanner<'a> {
                     }
                 };
 
+                self.privileges()
+                    .check_default_database(Privilege::Create)?;
+
                 Ok(CreateSchema {
                     schema_reference: self.ctx.resolve_schema_ref(schema_name),
                     if_not_exists,
@@ -690,105 +957,13 @@ impl<'a> SessionPlanner<'a> {
             // Normal tables OR Tables generated from a source query.
             // CREATE TABLE
             // CREATE TABLE table2 AS (SELECT * FROM table1);
-            ast::Statement::CreateTable {
+            stmt @ ast::Statement::CreateTable {
                 external: false,
-                if_not_exists,
-                or_replace,
                 engine: None,
-                name,
-                columns,
-                query,
-                temporary,
                 ..
-            } => {
-                validate_object_name(&name)?;
-                let table_name = object_name_to_table_ref(name)?;
-
-                let (source, arrow_cols) = if let Some(q) = query {
-                    let mut ctx = context_provider;
-
-                    let mut planner = SqlQueryPlanner::new(&mut ctx);
-
-                    let source = planner.query_to_plan(*q).await?;
-                    let df_fields = source.schema().fields();
-
-                    let mut columns = columns.into_iter();
-                    let mut fields = Vec::with_capacity(df_fields.len());
-                    for df_field in df_fields {
-                        let field = df_field.field().as_ref().clone();
-                        let field = if let Some(column) = columns.next() {
-                            // If we have a cast for the column, we can update the schema.
-                            validate_ident(&column.name)?;
-                            let name = normalize_ident(column.name);
-                            let data_type = convert_data_type(&column.data_type)?;
-                            field.with_name(name).with_data_type(data_type)
-                        } else {
-                            field
-                        };
-                        fields.push(field);
-                    }
-
-                    // Update the source plan with the new schema casts and alias.
-                    let project_exprs: Vec<_> = fields
-                        .iter()
-                        .zip(df_fields.iter())
-                        .map(|(field, df_field)| {
-                            cast(col(df_field.name()), field.data_type().clone())
-                                .alias(field.name())
-                        })
-                        .collect();
-
-                    let source = LogicalPlanBuilder::from(source)
-                        .project(project_exprs)?
-                        .build()?;
-
-                    (Some(source), fields)
-                } else {
-                    let mut arrow_cols = Vec::with_capacity(columns.len());
-                    for column in columns.into_iter() {
-                        validate_ident(&column.name)?;
-                        let name = normalize_ident(column.name);
-                        let data_type = convert_data_type(&column.data_type)?;
-                        let field = Field::new(name, data_type, /* nullable = */ true);
-                        arrow_cols.push(field);
-                    }
-                    (None, arrow_cols)
-                };
+            } => self.plan_create_table(stmt, Vec::new()).await,
 
-                if temporary {
-                    let table_name = match table_name {
-                        TableReference::Bare { table } => table.into_owned(),
-                        _ => return Err(internal!("cannot specify schema with temporary tables")),
-                    };
-                    let df_schema = Schema::new(arrow_cols.clone());
-                    let df_schema = df_schema.to_dfschema_ref()?;
-
-                    let plan = CreateTempTable {
-                        tbl_reference: FullObjectReference {
-                            database: DEFAULT_CATALOG.into(),
-                            schema: CURRENT_SESSION_SCHEMA.into(),
-                            name: table_name.into(),
-                        },
-                        schema: df_schema,
-                        if_not_exists,
-                        or_replace,
-                        source,
-                    };
-
-                    Ok(plan.into_logical_plan())
-                } else {
-                    let df_schema = Schema::new(arrow_cols.clone());
-                    let df_schema = df_schema.to_dfschema_ref()?;
-                    let create_table = CreateTable {
-                        tbl_reference: self.ctx.resolve_table_ref(table_name)?,
-                        schema: df_schema,
-                        if_not_exists,
-                        or_replace,
-                        source,
-                    };
-                    Ok(create_table.into_logical_plan())
-                }
-            }
+            stmt @ ast::Statement::CreateSequence { .. } => self.plan_create_sequence(stmt),
 
             // Views
             ast::Statement::CreateView {
@@ -801,7 +976,11 @@ impl<'a> SessionPlanner<'a> {
                 ..
             } => {
                 validate_object_name(&name)?;
-                let name = object_name_to_table_ref(name)?;
+                let view_reference = self
+                    .ctx
+                    .resolve_table_ref(object_name_to_table_ref(name)?)?;
+                self.privileges()
+                    .check_schema(&view_reference.schema, Privilege::Create)?;
 
                 if !with_options.is_empty() {
                     return Err(PlanError::UnsupportedFeature("view options"));
@@ -836,7 +1015,7 @@ impl<'a> SessionPlanner<'a> {
                     })
                 } else {
                     Ok(CreateView {
-                        view_reference: self.ctx.resolve_table_ref(name)?,
+                        view_reference,
                         sql: query_string,
                         columns,
                         or_replace,
@@ -861,6 +1040,12 @@ impl<'a> SessionPlanner<'a> {
                 validate_object_name(&table_name)?;
                 let table_name = object_name_to_table_ref(table_name)?;
 
+                if !self.privileges().is_unrestricted() {
+                    let resolver = EntryResolver::from_context(self.ctx);
+                    let ent = resolver.resolve_entry_from_reference(table_name.clone())?;
+                    self.privileges().check_resolved(&ent, Privilege::Insert)?;
+                }
+
                 let columns = columns
                     .into_iter()
                     .map(|col| {
@@ -869,14 +1054,63 @@ impl<'a> SessionPlanner<'a> {
                     })
                     .collect::<Result<Vec<_>>>()?;
 
+                // Defaults, constraints, and compaction only exist for native
+                // tables.
+                let table_entry = EntryResolver::from_context(self.ctx)
+                    .resolve_entry_from_reference(table_name.clone())
+                    .ok()
+                    .and_then(|ent| ent.try_into_table_entry().ok())
+                    .filter(|ent| matches!(ent.options, TableOptions::Internal(_)));
+                let table_options = table_entry.as_ref().and_then(|ent| match &ent.options {
+                    TableOptions::Internal(options) => Some(options.clone()),
+                    _ => None,
+                });
+                let compaction = table_entry
+                    .as_ref()
+                    .filter(|ent| !ent.meta.is_temp)
+                    .and_then(|ent| TableCompaction::from_vars(ent, &self.ctx.get_session_vars()));
+
+                let mut defaults = HashMap::new();
+                if let Some(options) = &table_options {
+                    for column in &options.columns {
+                        if let Some(default_expr) = &column.default_expr {
+                            let expr = parser::parse_sql_expr(default_expr)?;
+                            defaults.insert(column.name.clone(), expr);
+                        }
+                    }
+                }
+
                 let mut planner = SqlQueryPlanner::new(&mut context_provider);
                 let source = planner
-                    .insert_to_source_plan(&table_name, &columns, source)
+                    .insert_to_source_plan(&table_name, &columns, &defaults, source)
                     .await?;
 
                 let state = self.ctx.df_ctx().state();
                 let mut ctx_provider = PartialContextProvider::new(self.ctx, &state)?;
 
+                let (checks, unique_checks) = match &table_options {
+                    Some(options) => {
+                        let schema = source.schema().as_ref().clone();
+                        let checks = self
+                            .plan_constraint_checks(
+                                &mut ctx_provider,
+                                table_name.table(),
+                                options,
+                                &schema,
+                                None,
+                            )
+                            .await?;
+                        let unique_checks =
+                            if self.ctx.get_session_vars().enforce_unique_constraints() {
+                                unique_checks(options)
+                            } else {
+                                Vec::new()
+                            };
+                        (checks, unique_checks)
+                    }
+                    None => (Vec::new(), Vec::new()),
+                };
+
                 let provider = ctx_provider.table_provider(table_name.clone()).await?;
                 let (runtime_preference, provider) = match (
                     provider.preference,
@@ -899,6 +1133,9 @@ impl<'a> SessionPlanner<'a> {
                     source,
                     provider,
                     runtime_preference,
+                    checks,
+                    unique_checks,
+                    compaction,
                 }
                 .into_logical_plan())
             }
@@ -912,9 +1149,16 @@ impl<'a> SessionPlanner<'a> {
 
                 validate_object_name(&table_name)?;
                 let new_name = object_name_to_table_ref(table_name)?;
+
+                let tbl_reference = self.ctx.resolve_table_ref(name)?;
+                let new_tbl_reference = self.ctx.resolve_table_ref(new_name)?;
+                let privileges = self.privileges();
+                privileges.check_schema(&tbl_reference.schema, Privilege::Create)?;
+                privileges.check_schema(&new_tbl_reference.schema, Privilege::Create)?;
+
                 Ok(AlterTableRename {
-                    tbl_reference: self.ctx.resolve_table_ref(name)?,
-                    new_tbl_reference: self.ctx.resolve_table_ref(new_name)?,
+                    tbl_reference,
+                    new_tbl_reference,
                 }
                 .into_logical_plan())
             }
@@ -926,11 +1170,14 @@ impl<'a> SessionPlanner<'a> {
                 names,
                 ..
             } => {
+                let privileges = self.privileges();
                 let mut refs = Vec::with_capacity(names.len());
                 for name in names.into_iter() {
                     validate_object_name(&name)?;
                     let r = object_name_to_table_ref(name)?;
-                    refs.push(self.ctx.resolve_table_ref(r)?);
+                    let r = self.ctx.resolve_table_ref(r)?;
+                    privileges.check_schema(&r.schema, Privilege::Create)?;
+                    refs.push(r);
                 }
 
                 let plan = DropTables {
@@ -947,11 +1194,14 @@ impl<'a> SessionPlanner<'a> {
                 names,
                 ..
             } => {
+                let privileges = self.privileges();
                 let mut refs = Vec::with_capacity(names.len());
                 for name in names.into_iter() {
                     validate_object_name(&name)?;
                     let r = object_name_to_table_ref(name)?;
-                    refs.push(self.ctx.resolve_table_ref(r)?);
+                    let r = self.ctx.resolve_table_ref(r)?;
+                    privileges.check_schema(&r.schema, Privilege::Create)?;
+                    refs.push(r);
                 }
                 Ok(DropViews {
                     if_exists,
@@ -960,6 +1210,67 @@ impl<'a> SessionPlanner<'a> {
                 .into_logical_plan())
             }
 
+            // Drop sequences
+            ast::Statement::Drop {
+                object_type: ObjectType::Sequence,
+                if_exists,
+                names,
+                ..
+            } => {
+                let privileges = self.privileges();
+                let catalog = self.ctx.get_session_catalog();
+                let mut refs = Vec::with_capacity(names.len());
+                for name in names.into_iter() {
+                    validate_object_name(&name)?;
+                    let r = object_name_to_table_ref(name)?;
+                    let r = self.ctx.resolve_table_ref(r)?;
+                    privileges.check_schema(&r.schema, Privilege::Create)?;
+                    match catalog.resolve_entry(DEFAULT_CATALOG, &r.schema, &r.name) {
+                        Some(CatalogEntry::Sequence(seq)) if seq.owned_by.is_some() => {
+                            return Err(PlanError::InvalidSequence(format!(
+                                "cannot drop sequence '{}' because a table column depends on it",
+                                r.name
+                            )))
+                        }
+                        Some(CatalogEntry::Sequence(_)) | None => (),
+                        Some(_) => return Err(PlanError::NotASequence(r.name.into_owned())),
+                    }
+                    refs.push(r);
+                }
+                Ok(DropSequences {
+                    if_exists,
+                    sequence_references: refs,
+                }
+                .into_logical_plan())
+            }
+
+            // Drop functions
+            ast::Statement::DropFunction {
+                if_exists,
+                func_desc,
+                ..
+            } => {
+                let privileges = self.privileges();
+                let mut drops = Vec::with_capacity(func_desc.len());
+                for desc in func_desc.into_iter() {
+                    if desc.args.is_some() {
+                        return Err(PlanError::UnsupportedFeature(
+                            "DROP FUNCTION with argument types",
+                        ));
+                    }
+                    validate_object_name(&desc.name)?;
+                    let r = object_name_to_table_ref(desc.name)?;
+                    let r = self.ctx.resolve_table_ref(r)?;
+                    privileges.check_schema(&r.schema, Privilege::Create)?;
+                    drops.push(service::DropFunction {
+                        schema: r.schema.into_owned(),
+                        name: r.name.into_owned(),
+                        if_exists,
+                    });
+                }
+                Ok(DropFunctions { drops }.into_logical_plan())
+            }
+
             // Drop schemas
             ast::Statement::Drop {
                 object_type: ObjectType::Schema,
@@ -968,6 +1279,9 @@ impl<'a> SessionPlanner<'a> {
                 names,
                 ..
             } => {
+                self.privileges()
+                    .check_default_database(Privilege::Create)?;
+
                 let mut refs = Vec::with_capacity(names.len());
                 for name in names.into_iter() {
                     validate_object_name(&name)?;
@@ -1003,6 +1317,11 @@ impl<'a> SessionPlanner<'a> {
                 // Normalize variables
                 let mut variable: Vec<_> = variable.into_iter().map(normalize_ident).collect();
 
+                // SHOW ALL
+                if variable.len() == 1 && variable[0] == "all" {
+                    return self.plan_show_all();
+                }
+
                 let variable = if is_show_transaction_isolation_level(&variable) {
                     // SHOW TRANSACTION ISOLATION LEVEL
                     // Alias of "SHOW transaction_isolation".
@@ -1069,9 +1388,9 @@ impl<'a> SessionPlanner<'a> {
                 };
 
                 let resolver = EntryResolver::from_context(self.ctx);
-                let ent = resolver
-                    .resolve_entry_from_reference(table_name)?
-                    .try_into_table_entry()?;
+                let ent = resolver.resolve_entry_from_reference(table_name)?;
+                self.privileges().check_resolved(&ent, Privilege::Delete)?;
+                let ent = ent.try_into_table_entry()?;
                 // External deletes not supported yet.
                 if ent.meta.external {
                     return Err(PlanError::UnsupportedFeature("DELETE with external tables"));
@@ -1136,18 +1455,42 @@ impl<'a> SessionPlanner<'a> {
                 };
 
                 let resolver = EntryResolver::from_context(self.ctx);
-                let ent = resolver
-                    .resolve_entry_from_reference(table_name)?
-                    .try_into_table_entry()?;
+                let ent = resolver.resolve_entry_from_reference(table_name)?;
+                self.privileges().check_resolved(&ent, Privilege::Update)?;
+                let ent = ent.try_into_table_entry()?;
                 // External updates not supported yet.
                 if ent.meta.external {
                     return Err(PlanError::UnsupportedFeature("UPDATE with external tables"));
                 }
 
+                let (checks, unique_checks) = match &ent.options {
+                    TableOptions::Internal(options) => {
+                        let checks = self
+                            .plan_constraint_checks(
+                                &mut context_provider,
+                                &ent.meta.name,
+                                options,
+                                &schema,
+                                Some(updates.as_slice()),
+                            )
+                            .await?;
+                        let unique_checks =
+                            if self.ctx.get_session_vars().enforce_unique_constraints() {
+                                unique_checks(options)
+                            } else {
+                                Vec::new()
+                            };
+                        (checks, unique_checks)
+                    }
+                    _ => (Vec::new(), Vec::new()),
+                };
+
                 Ok(Update {
                     table: ent,
                     updates,
                     where_expr,
+                    checks,
+                    unique_checks,
                 }
                 .into_logical_plan())
             }
@@ -1156,61 +1499,696 @@ impl<'a> SessionPlanner<'a> {
         }
     }
 
-    fn plan_drop_database(&self, stmt: DropDatabaseStmt) -> Result<LogicalPlan> {
-        let mut names = Vec::with_capacity(stmt.names.len());
-        for name in stmt.names.into_iter() {
-            validate_ident(&name)?;
-            let name = normalize_ident(name);
-            names.push(name);
-        }
+    /// Plan a `CREATE TABLE` statement, optionally partitioning the table by
+    /// the provided columns.
+    async fn plan_create_table(
+        &self,
+        statement: ast::Statement,
+        partition_by: Vec<Ident>,
+    ) -> Result<LogicalPlan> {
+        let (if_not_exists, or_replace, name, columns, constraints, query, temporary) =
+            match statement {
+                ast::Statement::CreateTable {
+                    external: false,
+                    if_not_exists,
+                    or_replace,
+                    engine: None,
+                    name,
+                    columns,
+                    constraints,
+                    query,
+                    temporary,
+                    ..
+                } => (
+                    if_not_exists,
+                    or_replace,
+                    name,
+                    columns,
+                    constraints,
+                    query,
+                    temporary,
+                ),
+                other => return Err(PlanError::UnsupportedSQLStatement(other.to_string())),
+            };
 
-        Ok(DropDatabase {
-            names,
-            if_exists: stmt.if_exists,
-        }
-        .into_logical_plan())
-    }
+        validate_object_name(&name)?;
+        let table_name = object_name_to_table_ref(name)?;
 
-    fn plan_drop_tunnel(&self, stmt: DropTunnelStmt) -> Result<LogicalPlan> {
-        let mut names = Vec::with_capacity(stmt.names.len());
-        for name in stmt.names.into_iter() {
-            validate_ident(&name)?;
-            let name = normalize_ident(name);
-            names.push(name);
-        }
+        let mut column_options = Vec::new();
+        let mut serial_columns = Vec::new();
+        let (source, arrow_cols) = if let Some(q) = query {
+            if !constraints.is_empty() || columns.iter().any(|c| !c.options.is_empty()) {
+                return Err(PlanError::UnsupportedFeature(
+                    "constraints with CREATE TABLE AS",
+                ));
+            }
 
-        Ok(DropTunnel {
-            names,
-            if_exists: stmt.if_exists,
-        }
-        .into_logical_plan())
-    }
+            let state = self.ctx.df_ctx().state();
+            let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+
+            let mut planner = SqlQueryPlanner::new(&mut context_provider);
+
+            let source = planner.query_to_plan(*q).await?;
+            let df_fields = source.schema().fields();
+
+            let mut columns = columns.into_iter();
+            let mut fields = Vec::with_capacity(df_fields.len());
+            for df_field in df_fields {
+                // Columns created from a query never have a NOT NULL
+                // constraint.
+                let field = df_field.field().as_ref().clone().with_nullable(true);
+                let field = if let Some(column) = columns.next() {
+                    // If we have a cast for the column, we can update the schema.
+                    validate_ident(&column.name)?;
+                    let name = normalize_ident(column.name);
+                    let data_type = convert_data_type(&column.data_type)?;
+                    field.with_name(name).with_data_type(data_type)
+                } else {
+                    field
+                };
+                fields.push(field);
+            }
 
-    fn plan_drop_credentials(&self, stmt: DropCredentialsStmt) -> Result<LogicalPlan> {
-        let mut names = Vec::with_capacity(stmt.names.len());
-        for name in stmt.names.into_iter() {
-            validate_ident(&name)?;
-            let name = normalize_ident(name);
-            names.push(name);
-        }
+            // Update the source plan with the new schema casts and alias.
+            let project_exprs: Vec<_> = fields
+                .iter()
+                .zip(df_fields.iter())
+                .map(|(field, df_field)| {
+                    cast(col(df_field.name()), field.data_type().clone()).alias(field.name())
+                })
+                .collect();
 
-        Ok(DropCredentials {
-            names,
-            if_exists: stmt.if_exists,
-        }
-        .into_logical_plan())
-    }
+            let source = LogicalPlanBuilder::from(source)
+                .project(project_exprs)?
+                .build()?;
 
-    fn plan_alter_tunnel(&self, stmt: AlterTunnelStmt) -> Result<LogicalPlan> {
-        validate_ident(&stmt.name)?;
-        let name = normalize_ident(stmt.name);
+            (Some(source), fields)
+        } else {
+            let mut arrow_cols = Vec::with_capacity(columns.len());
+            for column in columns.into_iter() {
+                validate_ident(&column.name)?;
+                let name = normalize_ident(column.name);
+                let data_type = match serial_type(&column.data_type) {
+                    Some(data_type) => {
+                        serial_columns.push(name.clone());
+                        data_type
+                    }
+                    None => convert_data_type(&column.data_type)?,
+                };
+                let field = Field::new(&name, data_type, /* nullable = */ true);
+                arrow_cols.push(field);
+                column_options.push((name, column.options));
+            }
+            (None, arrow_cols)
+        };
 
-        let plan = match stmt.action {
-            AlterTunnelAction::RotateKeys => {
-                let new_ssh_key = SshKey::generate_random()?;
-                let new_ssh_key = new_ssh_key.to_bytes()?;
-                AlterTunnelRotateKeys {
-                    name,
+        let mut partition_columns = Vec::with_capacity(partition_by.len());
+        for ident in partition_by {
+            validate_ident(&ident)?;
+            let column = normalize_ident(ident);
+            let field = match arrow_cols.iter().find(|f| f.name() == &column) {
+                Some(field) => field,
+                None => {
+                    return Err(PlanError::InvalidPartitionColumn {
+                        column,
+                        reason: "column does not exist in the table",
+                    })
+                }
+            };
+            if field.data_type().is_nested() {
+                return Err(PlanError::InvalidPartitionColumn {
+                    column,
+                    reason: "nested types cannot be used as partition columns",
+                });
+            }
+            if partition_columns.contains(&column) {
+                return Err(PlanError::InvalidPartitionColumn {
+                    column,
+                    reason: "column specified more than once",
+                });
+            }
+            partition_columns.push(column);
+        }
+        if !partition_columns.is_empty() && partition_columns.len() == arrow_cols.len() {
+            return Err(PlanError::InvalidPartitionColumn {
+                column: partition_columns.join(", "),
+                reason: "table must have at least one non-partition column",
+            });
+        }
+
+        if temporary {
+            if !partition_columns.is_empty() {
+                return Err(PlanError::UnsupportedFeature(
+                    "partitioned temporary tables",
+                ));
+            }
+            if !constraints.is_empty()
+                || !serial_columns.is_empty()
+                || column_options.iter().any(|(_, opts)| !opts.is_empty())
+            {
+                return Err(PlanError::UnsupportedFeature(
+                    "constraints and defaults on temporary tables",
+                ));
+            }
+            let table_name = match table_name {
+                TableReference::Bare { table } => table.into_owned(),
+                _ => return Err(internal!("cannot specify schema with temporary tables")),
+            };
+            let df_schema = Schema::new(arrow_cols.clone());
+            let df_schema = df_schema.to_dfschema_ref()?;
+
+            let plan = CreateTempTable {
+                tbl_reference: FullObjectReference {
+                    database: DEFAULT_CATALOG.into(),
+                    schema: CURRENT_SESSION_SCHEMA.into(),
+                    name: table_name.into(),
+                },
+                schema: df_schema,
+                if_not_exists,
+                or_replace,
+                source,
+            };
+
+            Ok(plan.into_logical_plan())
+        } else {
+            let tbl_reference = self.ctx.resolve_table_ref(table_name)?;
+            if tbl_reference.database != DEFAULT_CATALOG {
+                if !partition_columns.is_empty() {
+                    return Err(PlanError::UnsupportedFeature(
+                        "partitioned tables in external databases",
+                    ));
+                }
+                if !constraints.is_empty()
+                    || !serial_columns.is_empty()
+                    || column_options.iter().any(|(_, opts)| !opts.is_empty())
+                {
+                    return Err(PlanError::UnsupportedFeature(
+                        "constraints and defaults on tables in external databases",
+                    ));
+                }
+                return self.plan_create_external_database_table(
+                    tbl_reference,
+                    arrow_cols,
+                    if_not_exists,
+                    or_replace,
+                    source,
+                );
+            }
+            self.privileges()
+                .check_schema(&tbl_reference.schema, Privilege::Create)?;
+
+            let (options, sequences) = self
+                .plan_table_options(
+                    &tbl_reference,
+                    &arrow_cols,
+                    column_options,
+                    serial_columns,
+                    constraints,
+                )
+                .await?;
+            // If the table already exists, so do its sequences.
+            let sequences = sequences
+                .into_iter()
+                .map(|seq| service::CreateSequence {
+                    if_not_exists,
+                    ..seq
+                })
+                .collect();
+
+            let df_schema = Schema::new(arrow_cols);
+            let df_schema = df_schema.to_dfschema_ref()?;
+            let create_table = CreateTable {
+                tbl_reference,
+                schema: df_schema,
+                if_not_exists,
+                or_replace,
+                source,
+                options: TableOptionsInternal {
+                    partition_columns,
+                    ..options
+                },
+                sequences,
+            };
+            Ok(create_table.into_logical_plan())
+        }
+    }
+
+    /// Plan creating a table inside of an external database.
+    ///
+    /// The table is created on the remote side during execution, so only
+    /// databases we're able to write to are supported.
+    fn plan_create_external_database_table(
+        &self,
+        tbl_reference: OwnedFullObjectReference,
+        fields: Vec<Field>,
+        if_not_exists: bool,
+        or_replace: bool,
+        source: Option<datafusion::logical_expr::LogicalPlan>,
+    ) -> Result<LogicalPlan> {
+        let catalog = self.ctx.get_session_catalog();
+        let db = catalog
+            .resolve_database(&tbl_reference.database)
+            .ok_or_else(|| {
+                PlanError::String(format!("missing database: {}", tbl_reference.database))
+            })?;
+
+        if let Some(ent) = catalog.get_by_oid(db.meta.id) {
+            self.privileges().check_entry(ent, Privilege::Create)?;
+        }
+
+        match &db.options {
+            DatabaseOptions::Postgres(_) | DatabaseOptions::Mysql(_) => (),
+            _ => {
+                return Err(PlanError::UnsupportedFeature(
+                    "CREATE TABLE in external databases other than Postgres and MySQL",
+                ))
+            }
+        }
+
+        let tunnel = match db.tunnel_id {
+            Some(tunnel_id) => match catalog.get_by_oid(tunnel_id) {
+                Some(CatalogEntry::Tunnel(tunnel)) => Some(tunnel.options.clone()),
+                _ => return Err(internal!("missing tunnel for database: {}", db.meta.name)),
+            },
+            None => None,
+        };
+
+        let schema = Schema::new(fields).to_dfschema_ref()?;
+        let plan = CreateExternalDatabaseTable {
+            tbl_reference,
+            if_not_exists,
+            or_replace,
+            schema,
+            source,
+            options: db.options.clone(),
+            tunnel,
+        };
+        Ok(plan.into_logical_plan())
+    }
+
+    /// Plan column options and table constraints for a new native table.
+    ///
+    /// Constraints without an explicit name are named the same way Postgres
+    /// names them, e.g. "t_pkey" or "t_a_check".
+    ///
+    /// Serial and identity columns get a default pulling from a sequence owned
+    /// by the table. The sequences that need to be created alongside the table
+    /// are returned with the options.
+    async fn plan_table_options(
+        &self,
+        tbl_reference: &OwnedFullObjectReference,
+        fields: &[Field],
+        column_options: Vec<(String, Vec<ast::ColumnOptionDef>)>,
+        serial_columns: Vec<String>,
+        constraints: Vec<ast::TableConstraint>,
+    ) -> Result<(TableOptionsInternal, Vec<service::CreateSequence>)> {
+        let table_name = tbl_reference.name.as_ref();
+
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+
+        let empty_schema = DFSchema::empty();
+        let table_schema = Schema::new(fields.to_vec()).to_dfschema()?;
+
+        let mut not_null = HashSet::new();
+        let mut defaults = HashMap::new();
+        // (name, default name, columns, is primary)
+        let mut uniques = Vec::new();
+        // (name, default name, expression)
+        let mut checks = Vec::new();
+        // (column, sequence options)
+        let mut identities: Vec<_> = serial_columns
+            .into_iter()
+            .map(|column| (column, Vec::new()))
+            .collect();
+
+        for (column, options) in column_options {
+            for def in options {
+                match def.option {
+                    ast::ColumnOption::Null => {
+                        not_null.remove(&column);
+                    }
+                    ast::ColumnOption::NotNull => {
+                        not_null.insert(column.clone());
+                    }
+                    ast::ColumnOption::Default(expr) => {
+                        // Make sure the default can be planned without any
+                        // columns, and that it can be cast to the column's
+                        // type.
+                        let data_type = table_schema
+                            .field_with_unqualified_name(&column)?
+                            .data_type()
+                            .clone();
+                        planner
+                            .sql_to_expr(expr.clone(), &empty_schema, &mut PlannerContext::new())
+                            .await?
+                            .cast_to(&data_type, &empty_schema)?;
+                        defaults.insert(column.clone(), expr.to_string());
+                    }
+                    ast::ColumnOption::Unique { is_primary } => {
+                        let default_name = if is_primary {
+                            format!("{table_name}_pkey")
+                        } else {
+                            format!("{table_name}_{column}_key")
+                        };
+                        uniques.push((def.name, default_name, vec![column.clone()], is_primary));
+                    }
+                    ast::ColumnOption::Check(expr) => {
+                        let default_name = format!("{table_name}_{column}_check");
+                        checks.push((def.name, default_name, expr));
+                    }
+                    // GENERATED { ALWAYS | BY DEFAULT } AS IDENTITY
+                    //
+                    // Explicit values are always allowed, even for GENERATED
+                    // ALWAYS.
+                    ast::ColumnOption::Generated {
+                        sequence_options,
+                        generation_expr: None,
+                        ..
+                    } => {
+                        identities.push((column.clone(), sequence_options.unwrap_or_default()));
+                    }
+                    other => {
+                        return Err(PlanError::InvalidConstraint {
+                            msg: format!("unsupported column option: {other}"),
+                        })
+                    }
+                }
+            }
+        }
+
+        for constraint in constraints {
+            match constraint {
+                ast::TableConstraint::Unique {
+                    name,
+                    columns,
+                    is_primary,
+                } => {
+                    let columns = columns
+                        .into_iter()
+                        .map(|col| {
+                            validate_ident(&col)?;
+                            Ok(normalize_ident(col))
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    let default_name = if is_primary {
+                        format!("{table_name}_pkey")
+                    } else {
+                        format!("{table_name}_{}_key", columns.join("_"))
+                    };
+                    uniques.push((name, default_name, columns, is_primary));
+                }
+                ast::TableConstraint::Check { name, expr } => {
+                    let default_name = format!("{table_name}_check");
+                    checks.push((name, default_name, *expr));
+                }
+                other => {
+                    return Err(PlanError::InvalidConstraint {
+                        msg: format!("unsupported constraint: {other}"),
+                    })
+                }
+            }
+        }
+
+        let mut names = HashSet::new();
+        let mut constraint_name = |name: Option<Ident>, default_name: String| -> Result<String> {
+            match name {
+                Some(name) => {
+                    validate_ident(&name)?;
+                    let name = normalize_ident(name);
+                    if !names.insert(name.clone()) {
+                        return Err(PlanError::InvalidConstraint {
+                            msg: format!(
+                            "constraint \"{name}\" for relation \"{table_name}\" already exists"
+                        ),
+                        });
+                    }
+                    Ok(name)
+                }
+                None => {
+                    let mut name = default_name.clone();
+                    let mut suffix = 1;
+                    while names.contains(&name) {
+                        name = format!("{default_name}{suffix}");
+                        suffix += 1;
+                    }
+                    names.insert(name.clone());
+                    Ok(name)
+                }
+            }
+        };
+
+        let mut primary_key = None;
+        let mut unique_constraints = Vec::new();
+        for (name, default_name, columns, is_primary) in uniques {
+            for (idx, column) in columns.iter().enumerate() {
+                if table_schema.field_with_unqualified_name(column).is_err() {
+                    return Err(PlanError::InvalidConstraint {
+                        msg: format!("column \"{column}\" named in key does not exist"),
+                    });
+                }
+                if columns[..idx].contains(column) {
+                    return Err(PlanError::InvalidConstraint {
+                        msg: format!("column \"{column}\" appears twice in key"),
+                    });
+                }
+            }
+
+            let constraint = InternalUniqueConstraint {
+                name: constraint_name(name, default_name)?,
+                columns,
+            };
+            if is_primary {
+                if primary_key.is_some() {
+                    return Err(PlanError::InvalidConstraint {
+                        msg: format!(
+                            "multiple primary keys for table \"{table_name}\" are not allowed"
+                        ),
+                    });
+                }
+                // Primary key columns are implicitly NOT NULL.
+                not_null.extend(constraint.columns.iter().cloned());
+                primary_key = Some(constraint);
+            } else {
+                unique_constraints.push(constraint);
+            }
+        }
+
+        let mut sequences = Vec::with_capacity(identities.len());
+        for (column, options) in identities {
+            if defaults.contains_key(&column) {
+                return Err(PlanError::InvalidConstraint {
+                    msg: format!(
+                        "both default and identity specified for column \"{column}\" of table \"{table_name}\""
+                    ),
+                });
+            }
+            let data_type = table_schema
+                .field_with_unqualified_name(&column)?
+                .data_type()
+                .clone();
+            let sequence = service::CreateSequence {
+                owned_by_table: Some(table_name.to_string()),
+                ..plan_sequence(
+                    &tbl_reference.schema,
+                    format!("{table_name}_{column}_seq"),
+                    &data_type,
+                    options,
+                )?
+            };
+            let seq_reference =
+                TableReference::partial(sequence.schema.as_str(), sequence.name.as_str());
+            defaults.insert(
+                column.clone(),
+                format!("nextval('{}')", quoted_table_ref(seq_reference)),
+            );
+            not_null.insert(column);
+            sequences.push(sequence);
+        }
+
+        let mut check_constraints = Vec::with_capacity(checks.len());
+        for (name, default_name, expr) in checks {
+            let data_type = planner
+                .sql_to_expr(expr.clone(), &table_schema, &mut PlannerContext::new())
+                .await?
+                .get_type(&table_schema)?;
+            if data_type != DataType::Boolean {
+                return Err(PlanError::InvalidConstraint {
+                    msg: format!("check constraint must be a boolean expression, got {data_type}"),
+                });
+            }
+            check_constraints.push(InternalCheckConstraint {
+                name: constraint_name(name, default_name)?,
+                expr: expr.to_string(),
+            });
+        }
+
+        let columns = fields
+            .iter()
+            .map(|field| InternalColumnDefinition {
+                name: field.name().clone(),
+                nullable: !not_null.contains(field.name()),
+                arrow_type: field.data_type().clone(),
+                default_expr: defaults.remove(field.name()),
+                comment: None,
+            })
+            .collect();
+
+        let options = TableOptionsInternal {
+            columns,
+            partition_columns: Vec::new(),
+            primary_key,
+            unique_constraints,
+            check_constraints,
+            materialized_view: None,
+        };
+        Ok((options, sequences))
+    }
+
+    /// Plan the checks that rows written to a native table must pass.
+    ///
+    /// When planning for an update, only constraints involving the updated
+    /// columns are checked, with the new values substituted in for the columns.
+    async fn plan_constraint_checks(
+        &self,
+        context_provider: &mut PartialContextProvider<'_>,
+        table_name: &str,
+        options: &TableOptionsInternal,
+        schema: &DFSchema,
+        updates: Option<&[(String, Expr)]>,
+    ) -> Result<Vec<ConstraintCheck>> {
+        let is_updated = |column: &str| match updates {
+            Some(updates) => updates.iter().any(|(name, _)| name == column),
+            None => true,
+        };
+
+        let mut checks = Vec::new();
+        for column in &options.columns {
+            if column.nullable || !is_updated(&column.name) {
+                continue;
+            }
+            checks.push(ConstraintCheck {
+                expr: Expr::Column(Column::from_name(&column.name)).is_not_null(),
+                violation: format!(
+                    "null value in column \"{}\" of relation \"{table_name}\" violates not-null constraint",
+                    column.name
+                ),
+            });
+        }
+
+        let mut planner = SqlQueryPlanner::new(context_provider);
+        for check in &options.check_constraints {
+            let expr = parser::parse_sql_expr(&check.expr)?;
+            let expr = planner
+                .sql_to_expr(expr, schema, &mut PlannerContext::new())
+                .await?;
+            if !expr.to_columns()?.iter().any(|col| is_updated(&col.name)) {
+                continue;
+            }
+            checks.push(ConstraintCheck {
+                expr,
+                violation: format!(
+                    "new row for relation \"{table_name}\" violates check constraint \"{}\"",
+                    check.name
+                ),
+            });
+        }
+
+        let updates = match updates {
+            Some(updates) => updates,
+            None => return Ok(checks),
+        };
+
+        let mut substituted = Vec::with_capacity(checks.len());
+        for check in checks {
+            let expr = check.expr.transform(&|expr| {
+                Ok(match expr {
+                    Expr::Column(column) => {
+                        match updates.iter().find(|(name, _)| name == &column.name) {
+                            Some((_, value)) => Transformed::Yes(value.clone()),
+                            None => Transformed::No(Expr::Column(column)),
+                        }
+                    }
+                    other => Transformed::No(other),
+                })
+            })?;
+            substituted.push(ConstraintCheck {
+                expr,
+                violation: check.violation,
+            });
+        }
+
+        Ok(substituted)
+    }
+
+    fn plan_drop_database(&self, stmt: DropDatabaseStmt) -> Result<LogicalPlan> {
+        self.privileges()
+            .check_default_database(Privilege::Create)?;
+
+        let mut names = Vec::with_capacity(stmt.names.len());
+        for name in stmt.names.into_iter() {
+            validate_ident(&name)?;
+            let name = normalize_ident(name);
+            names.push(name);
+        }
+
+        Ok(DropDatabase {
+            names,
+            if_exists: stmt.if_exists,
+        }
+        .into_logical_plan())
+    }
+
+    fn plan_drop_tunnel(&self, stmt: DropTunnelStmt) -> Result<LogicalPlan> {
+        self.privileges()
+            .check_default_database(Privilege::Create)?;
+
+        let mut names = Vec::with_capacity(stmt.names.len());
+        for name in stmt.names.into_iter() {
+            validate_ident(&name)?;
+            let name = normalize_ident(name);
+            names.push(name);
+        }
+
+        Ok(DropTunnel {
+            names,
+            if_exists: stmt.if_exists,
+        }
+        .into_logical_plan())
+    }
+
+    fn plan_drop_credentials(&self, stmt: DropCredentialsStmt) -> Result<LogicalPlan> {
+        self.privileges()
+            .check_default_database(Privilege::Create)?;
+
+        let mut names = Vec::with_capacity(stmt.names.len());
+        for name in stmt.names.into_iter() {
+            validate_ident(&name)?;
+            let name = normalize_ident(name);
+            names.push(name);
+        }
+
+        Ok(DropCredentials {
+            names,
+            if_exists: stmt.if_exists,
+        }
+        .into_logical_plan())
+    }
+
+    fn plan_alter_tunnel(&self, stmt: AlterTunnelStmt) -> Result<LogicalPlan> {
+        self.privileges()
+            .check_default_database(Privilege::Create)?;
+
+        validate_ident(&stmt.name)?;
+        let name = normalize_ident(stmt.name);
+
+        let plan = match stmt.action {
+            AlterTunnelAction::RotateKeys => {
+                let new_ssh_key = SshKey::generate_random()?;
+                let new_ssh_key = new_ssh_key.to_bytes()?;
+                AlterTunnelRotateKeys {
+                    name,
                     if_exists: stmt.if_exists,
                     new_ssh_key,
                 }
@@ -1221,6 +2199,9 @@ impl<'a> SessionPlanner<'a> {
     }
 
     fn plan_alter_database_rename(&self, stmt: AlterDatabaseRenameStmt) -> Result<LogicalPlan> {
+        self.privileges()
+            .check_default_database(Privilege::Create)?;
+
         validate_ident(&stmt.name)?;
         let name = normalize_ident(stmt.name);
 
@@ -1230,6 +2211,798 @@ impl<'a> SessionPlanner<'a> {
         Ok(AlterDatabaseRename { name, new_name }.into_logical_plan())
     }
 
+    fn plan_create_role(&self, stmt: CreateRoleStmt) -> Result<LogicalPlan> {
+        self.privileges().check_superuser("create roles")?;
+
+        validate_ident(&stmt.name)?;
+        let name = normalize_ident(stmt.name);
+        let superuser = stmt.superuser.unwrap_or(false);
+
+        // Users without a role lose access once the first role is created, so
+        // require that role to be a superuser role for the current user.
+        let user = self.ctx.get_session_vars().user_name();
+        if !self.ctx.get_session_catalog().has_roles() && (name != user || !superuser) {
+            return Err(PrivilegeError::FirstRoleNotCurrentSuperuser { user }.into());
+        }
+
+        let password_hash = stmt
+            .password
+            .map(|password| hash_password(&password))
+            .transpose()?;
+
+        Ok(CreateRole {
+            name,
+            if_not_exists: stmt.if_not_exists,
+            superuser,
+            login: stmt.login.unwrap_or(stmt.is_user),
+            password_hash,
+        }
+        .into_logical_plan())
+    }
+
+    fn plan_drop_role(&self, stmt: DropRoleStmt) -> Result<LogicalPlan> {
+        self.privileges().check_superuser("drop roles")?;
+
+        let mut names = Vec::with_capacity(stmt.names.len());
+        for name in stmt.names.into_iter() {
+            validate_ident(&name)?;
+            let name = normalize_ident(name);
+            names.push(name);
+        }
+
+        // Dropping the current user's role while other roles remain would
+        // leave the user without access.
+        let user = self.ctx.get_session_vars().user_name();
+        if names.contains(&user) {
+            let catalog = self.ctx.get_session_catalog();
+            let remaining = catalog
+                .iter_entries()
+                .filter(|ent| match ent.entry {
+                    CatalogEntry::Role(role) => !names.contains(&role.meta.name),
+                    _ => false,
+                })
+                .count();
+            if remaining > 0 {
+                return Err(PrivilegeError::DropCurrentRole { user }.into());
+            }
+        }
+
+        Ok(DropRoles {
+            names,
+            if_exists: stmt.if_exists,
+        }
+        .into_logical_plan())
+    }
+
+    fn plan_grant(&self, stmt: GrantStmt) -> Result<LogicalPlan> {
+        self.privileges().check_superuser("grant privileges")?;
+
+        let (objects, privileges) = self.plan_privileges_on_objects(stmt.privileges)?;
+
+        let mut grants = Vec::with_capacity(objects.len() * stmt.roles.len());
+        for role in stmt.roles.into_iter().map(normalize_ident) {
+            for object in objects.iter() {
+                grants.push(service::GrantPrivileges {
+                    role: role.clone(),
+                    object: object.clone(),
+                    privileges: privileges.clone(),
+                });
+            }
+        }
+
+        Ok(GrantPrivileges { grants }.into_logical_plan())
+    }
+
+    fn plan_revoke(&self, stmt: RevokeStmt) -> Result<LogicalPlan> {
+        self.privileges().check_superuser("revoke privileges")?;
+
+        let (objects, privileges) = self.plan_privileges_on_objects(stmt.privileges)?;
+
+        let mut revokes = Vec::with_capacity(objects.len() * stmt.roles.len());
+        for role in stmt.roles.into_iter().map(normalize_ident) {
+            for object in objects.iter() {
+                revokes.push(service::RevokePrivileges {
+                    role: role.clone(),
+                    object: object.clone(),
+                    privileges: privileges.clone(),
+                });
+            }
+        }
+
+        Ok(RevokePrivileges { revokes }.into_logical_plan())
+    }
+
+    fn plan_comment(&self, stmt: CommentStmt) -> Result<LogicalPlan> {
+        let privileges = self.privileges();
+
+        let object = match stmt.object_type {
+            CommentObjectType::Database | CommentObjectType::Schema => {
+                let mut idents = stmt.name.0;
+                if idents.len() != 1 {
+                    return Err(PlanError::String(format!(
+                        "Expected a single identifier for {}, got: {}",
+                        stmt.object_type,
+                        ObjectName(idents)
+                    )));
+                }
+                let name = normalize_ident(idents.pop().unwrap());
+                if stmt.object_type == CommentObjectType::Database {
+                    privileges.check_default_database(Privilege::Create)?;
+                    CommentObject::Database(name)
+                } else {
+                    privileges.check_schema(&name, Privilege::Create)?;
+                    CommentObject::Schema(name)
+                }
+            }
+            CommentObjectType::Table | CommentObjectType::View => {
+                let r = self
+                    .ctx
+                    .resolve_table_ref(object_name_to_table_ref(stmt.name)?)?;
+                if r.database != DEFAULT_CATALOG {
+                    return Err(PlanError::UnsupportedFeature(
+                        "comments on objects in external databases",
+                    ));
+                }
+                privileges.check_schema(&r.schema, Privilege::Create)?;
+                let (schema, name) = (r.schema.into_owned(), r.name.into_owned());
+                if stmt.object_type == CommentObjectType::Table {
+                    CommentObject::Table { schema, name }
+                } else {
+                    CommentObject::View { schema, name }
+                }
+            }
+            CommentObjectType::Column => {
+                let mut idents = stmt.name.0;
+                // Parser ensures the column is qualified by a table.
+                let column = normalize_ident(idents.pop().unwrap());
+                let r = self
+                    .ctx
+                    .resolve_table_ref(object_name_to_table_ref(ObjectName(idents))?)?;
+                if r.database != DEFAULT_CATALOG {
+                    return Err(PlanError::UnsupportedFeature(
+                        "comments on objects in external databases",
+                    ));
+                }
+                privileges.check_schema(&r.schema, Privilege::Create)?;
+                CommentObject::Column {
+                    schema: r.schema.into_owned(),
+                    table: r.name.into_owned(),
+                    column,
+                }
+            }
+        };
+
+        Ok(CommentOn {
+            comment: service::CommentOn {
+                object,
+                comment: stmt.comment,
+            },
+        }
+        .into_logical_plan())
+    }
+
+    fn plan_optimize(&self, stmt: OptimizeStmt) -> Result<LogicalPlan> {
+        let table = self.resolve_native_table(stmt.name, "OPTIMIZE with external tables")?;
+        let zorder_by = stmt.zorder_by.into_iter().map(normalize_ident).collect();
+        Ok(OptimizeTable { table, zorder_by }.into_logical_plan())
+    }
+
+    fn plan_vacuum(&self, stmt: VacuumStmt) -> Result<LogicalPlan> {
+        let table = self.resolve_native_table(stmt.name, "VACUUM with external tables")?;
+        Ok(VacuumTable {
+            table,
+            retain_hours: stmt.retain_hours,
+        }
+        .into_logical_plan())
+    }
+
+    /// Plan a `CREATE MATERIALIZED VIEW` as creating a native table from the
+    /// view's query, remembering the query so the table can be refreshed.
+    async fn plan_create_materialized_view(
+        &self,
+        stmt: CreateMaterializedViewStmt,
+    ) -> Result<LogicalPlan> {
+        let tbl_reference = self
+            .ctx
+            .resolve_table_ref(object_name_to_table_ref(stmt.name)?)?;
+        self.privileges()
+            .check_schema(&tbl_reference.schema, Privilege::Create)?;
+
+        let refresh_interval_secs = stmt
+            .refresh_every
+            .map(|interval| parse_refresh_interval(&interval))
+            .transpose()?;
+
+        let sql = stmt.query.to_string();
+        let source = self.plan_materialized_view_query(*stmt.query).await?;
+
+        let columns: Vec<_> = stmt.columns.into_iter().map(normalize_ident).collect();
+        let df_fields = source.schema().fields();
+        if !columns.is_empty() && df_fields.len() != columns.len() {
+            return Err(PlanError::InvalidNumberOfAliasesForView {
+                sql,
+                aliases: columns,
+            });
+        }
+        let fields: Vec<_> = df_fields
+            .iter()
+            .enumerate()
+            .map(|(idx, df_field)| {
+                let field = df_field.field().as_ref().clone().with_nullable(true);
+                match columns.get(idx) {
+                    Some(name) => field.with_name(name),
+                    None => field,
+                }
+            })
+            .collect();
+        let source = project_to_fields(source, &fields)?;
+
+        let (options, _) = self
+            .plan_table_options(&tbl_reference, &fields, Vec::new(), Vec::new(), Vec::new())
+            .await?;
+
+        // Scheduled refreshes run as the user creating the view.
+        let vars = self.ctx.get_session_vars();
+        let df_schema = Schema::new(fields).to_dfschema_ref()?;
+        Ok(CreateTable {
+            tbl_reference,
+            schema: df_schema,
+            if_not_exists: stmt.if_not_exists,
+            or_replace: stmt.or_replace,
+            source: Some(source),
+            options: TableOptionsInternal {
+                materialized_view: Some(InternalMaterializedView {
+                    sql,
+                    refresh_interval_secs,
+                    last_refreshed: None,
+                    owner_name: Some(vars.user_name()),
+                    owner_id: Some(vars.user_id()),
+                }),
+                ..options
+            },
+            sequences: Vec::new(),
+        }
+        .into_logical_plan())
+    }
+
+    /// Plan a `REFRESH MATERIALIZED VIEW`, re-planning the view's stored query
+    /// against the current catalog.
+    async fn plan_refresh_materialized_view(
+        &self,
+        stmt: RefreshMaterializedViewStmt,
+    ) -> Result<LogicalPlan> {
+        let name = stmt.name.to_string();
+        let table = self.resolve_native_table(stmt.name, "REFRESH with external tables")?;
+        let view = match &table.options {
+            TableOptions::Internal(opts) => opts.materialized_view.clone(),
+            _ => None,
+        }
+        .ok_or(PlanError::NotMaterializedView(name))?;
+
+        let schema = match self.ctx.get_session_catalog().get_by_oid(table.meta.parent) {
+            Some(ent) => ent.get_meta().name.clone(),
+            None => return Err(internal!("missing schema for table: {}", table.meta.name)),
+        };
+
+        let query = match parser::parse_sql(&view.sql)?.pop_front() {
+            Some(StatementWithExtensions::Statement(ast::Statement::Query(q))) => *q,
+            _ => return Err(internal!("invalid materialized view query: {}", view.sql)),
+        };
+        let source = self.plan_materialized_view_query(query).await?;
+
+        let fields: Vec<_> = match &table.options {
+            TableOptions::Internal(opts) => opts
+                .columns
+                .iter()
+                .map(|col| Field::new(&col.name, col.arrow_type.clone(), col.nullable))
+                .collect(),
+            _ => unreachable!("options checked above"),
+        };
+        if source.schema().fields().len() != fields.len() {
+            return Err(PlanError::InvalidViewStatement {
+                msg: "materialized view query no longer matches the view's columns",
+            });
+        }
+        let source = project_to_fields(source, &fields)?;
+
+        Ok(RefreshMaterializedView {
+            schema,
+            table,
+            source,
+        }
+        .into_logical_plan())
+    }
+
+    async fn plan_materialized_view_query(
+        &self,
+        query: ast::Query,
+    ) -> Result<datafusion::logical_expr::LogicalPlan> {
+        if matches!(
+            query.body.as_ref(),
+            ast::SetExpr::Insert(_) | ast::SetExpr::Update(_)
+        ) {
+            return Err(PlanError::InvalidViewStatement {
+                msg: "materialized view body must be a query",
+            });
+        }
+
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+        Ok(planner.query_to_plan(query).await?)
+    }
+
+    /// Plan a `CREATE FUNCTION`. The function body is planned with its
+    /// parameters bound to NULL to catch errors early.
+    async fn plan_create_function(&self, stmt: CreateFunctionStmt) -> Result<LogicalPlan> {
+        let reference = self
+            .ctx
+            .resolve_table_ref(object_name_to_table_ref(stmt.name)?)?;
+        self.privileges()
+            .check_schema(&reference.schema, Privilege::Create)?;
+
+        let params = convert_function_columns(stmt.params)?;
+        let (func_type, return_type, return_columns) = match stmt.returns {
+            FunctionReturns::Scalar(data_type) => (
+                FunctionType::Scalar,
+                Some(convert_data_type(&data_type)?),
+                Vec::new(),
+            ),
+            FunctionReturns::Table(columns) => (
+                FunctionType::TableReturning,
+                None,
+                convert_function_columns(columns)?,
+            ),
+        };
+        let sql_function = SqlFunction {
+            params,
+            return_type,
+            return_columns,
+            body: stmt.body,
+        };
+
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+        planner
+            .validate_sql_function(&reference.name, func_type, &sql_function)
+            .await?;
+
+        Ok(CreateFunction {
+            function: service::CreateFunction {
+                schema: reference.schema.into_owned(),
+                name: reference.name.into_owned(),
+                or_replace: stmt.or_replace,
+                func_type,
+                sql_function,
+            },
+        }
+        .into_logical_plan())
+    }
+
+    /// Plan a `CREATE SEQUENCE`.
+    fn plan_create_sequence(&self, statement: ast::Statement) -> Result<LogicalPlan> {
+        let (temporary, if_not_exists, name, data_type, sequence_options, owned_by) =
+            match statement {
+                ast::Statement::CreateSequence {
+                    temporary,
+                    if_not_exists,
+                    name,
+                    data_type,
+                    sequence_options,
+                    owned_by,
+                } => (
+                    temporary,
+                    if_not_exists,
+                    name,
+                    data_type,
+                    sequence_options,
+                    owned_by,
+                ),
+                other => return Err(PlanError::UnsupportedSQLStatement(other.to_string())),
+            };
+
+        if temporary {
+            return Err(PlanError::UnsupportedFeature("temporary sequences"));
+        }
+        if owned_by.is_some() {
+            return Err(PlanError::UnsupportedFeature("OWNED BY for sequences"));
+        }
+
+        validate_object_name(&name)?;
+        let reference = self
+            .ctx
+            .resolve_table_ref(object_name_to_table_ref(name)?)?;
+        self.privileges()
+            .check_schema(&reference.schema, Privilege::Create)?;
+
+        let data_type = match data_type {
+            Some(data_type) => convert_data_type(&data_type)?,
+            None => DataType::Int64,
+        };
+
+        let sequence = service::CreateSequence {
+            if_not_exists,
+            ..plan_sequence(
+                &reference.schema,
+                reference.name.into_owned(),
+                &data_type,
+                sequence_options,
+            )?
+        };
+
+        Ok(CreateSequence { sequence }.into_logical_plan())
+    }
+
+    /// Plan an `EXECUTE` of a named prepared statement.
+    ///
+    /// The arguments must be constant expressions. They're cast to the types
+    /// of the statement's parameters and substituted into the already planned
+    /// statement.
+    async fn plan_execute(&self, name: Ident, parameters: Vec<ast::Expr>) -> Result<LogicalPlan> {
+        validate_ident(&name)?;
+        let name = normalize_ident(name);
+        let prepared = self.ctx.get_prepared_statement(&name)?;
+        let mut plan = match &prepared.plan {
+            Some(plan) => plan.clone(),
+            None => {
+                return Err(PlanError::String(format!(
+                    "prepared statement \"{name}\" does not contain a statement"
+                )))
+            }
+        };
+
+        let parameter_types = prepared.input_paramaters().cloned().unwrap_or_default();
+        if parameters.len() != parameter_types.len() {
+            return Err(PlanError::WrongNumberOfParameters {
+                name,
+                expected: parameter_types.len(),
+                got: parameters.len(),
+            });
+        }
+
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+
+        let empty_schema = DFSchema::empty();
+        let props = ExecutionProps::new();
+        let simplifier = ExprSimplifier::new(
+            SimplifyContext::new(&props).with_schema(Arc::new(empty_schema.clone())),
+        );
+
+        let mut scalars = Vec::with_capacity(parameters.len());
+        for (idx, param) in parameters.into_iter().enumerate() {
+            let mut expr = planner
+                .sql_to_expr(param, &empty_schema, &mut PlannerContext::new())
+                .await?;
+            if let Some(Some((_, data_type))) = parameter_types.get(&format!("${}", idx + 1)) {
+                expr = expr.cast_to(data_type, &empty_schema)?;
+            }
+            match simplifier.simplify(expr)? {
+                Expr::Literal(scalar) => scalars.push(scalar),
+                other => {
+                    return Err(PlanError::String(format!(
+                        "parameters to EXECUTE must be constant expressions, got: {other}"
+                    )))
+                }
+            }
+        }
+
+        plan.replace_placeholders(scalars)?;
+        Ok(plan)
+    }
+
+    /// Plan `SHOW DATABASES`, `SHOW SCHEMAS`, or `SHOW TABLES`.
+    ///
+    /// Schemas and tables in the default database are read from the catalog.
+    /// Schemas and tables in external databases are listed by the database's
+    /// virtual lister (the `list_schemas` and `list_tables` functions).
+    async fn plan_show(&self, stmt: ShowStmt) -> Result<LogicalPlan> {
+        let mut from: Vec<String> = stmt
+            .from
+            .map(|name| name.0.into_iter().map(normalize_ident).collect())
+            .unwrap_or_default();
+
+        let (column, sql) = match stmt.object_type {
+            ShowObjectType::Databases => (
+                "database_name",
+                "SELECT database_name FROM glare_catalog.databases".to_string(),
+            ),
+            ShowObjectType::Schemas => {
+                let database = from.pop().unwrap_or_else(|| DEFAULT_CATALOG.to_string());
+                let sql = if self.is_external_database(&database)? {
+                    format!(
+                        "SELECT schema_name FROM list_schemas({})",
+                        quote_ident(&database)
+                    )
+                } else {
+                    "SELECT schema_name FROM glare_catalog.schemas".to_string()
+                };
+                ("schema_name", sql)
+            }
+            ShowObjectType::Tables => {
+                let schema = match from.pop() {
+                    Some(schema) => schema,
+                    None => self.ctx.first_nonimplicit_schema()?,
+                };
+                let database = from.pop().unwrap_or_else(|| DEFAULT_CATALOG.to_string());
+                let sql = if self.is_external_database(&database)? {
+                    format!(
+                        "SELECT table_name FROM list_tables({}, {})",
+                        quote_ident(&database),
+                        quote_ident(&schema)
+                    )
+                } else {
+                    if self
+                        .ctx
+                        .get_session_catalog()
+                        .resolve_schema(&schema)
+                        .is_none()
+                    {
+                        return Err(PlanError::String(format!(
+                            "schema \"{schema}\" does not exist"
+                        )));
+                    }
+                    let schema = ast::Value::SingleQuotedString(schema);
+                    format!(
+                        "SELECT table_name FROM glare_catalog.tables WHERE schema_name = {schema} \
+                         UNION ALL \
+                         SELECT view_name FROM glare_catalog.views WHERE schema_name = {schema}"
+                    )
+                };
+                ("table_name", sql)
+            }
+        };
+
+        let plan = self.plan_query_sql(&sql).await?;
+        let plan = self.apply_show_filter(plan, column, stmt.filter).await?;
+        let plan = LogicalPlanBuilder::from(plan)
+            .sort(vec![col(column).sort(true, false)])?
+            .build()?;
+        Ok(LogicalPlan::Datafusion(plan))
+    }
+
+    /// Plan `SHOW COLUMNS FROM <table>` and `DESCRIBE <table>`.
+    async fn plan_show_columns(
+        &self,
+        table_name: ObjectName,
+        filter: Option<ast::ShowStatementFilter>,
+    ) -> Result<LogicalPlan> {
+        validate_object_name(&table_name)?;
+        let plan = match object_name_to_table_ref(table_name.clone())? {
+            TableReference::Full {
+                catalog,
+                schema,
+                table,
+            } if self.is_external_database(&catalog)? => {
+                // Avoid creating a provider for the table, the lister only
+                // needs the column metadata.
+                let sql = format!(
+                    "SELECT column_name, data_type, nullable FROM list_columns({}, {}, {})",
+                    quote_ident(&catalog),
+                    quote_ident(&schema),
+                    quote_ident(&table)
+                );
+                self.plan_query_sql(&sql).await?
+            }
+            _ => {
+                let plan = self
+                    .plan_query_sql(&format!("SELECT * FROM {table_name}"))
+                    .await?;
+                describe_schema(plan.schema())?
+            }
+        };
+        let plan = self.apply_show_filter(plan, "column_name", filter).await?;
+        Ok(LogicalPlan::Datafusion(plan))
+    }
+
+    /// Plan `DESCRIBE <query>`, describing the columns the query would
+    /// return.
+    async fn plan_describe_statement(&self, statement: ast::Statement) -> Result<LogicalPlan> {
+        let query = match statement {
+            ast::Statement::Query(query) => *query,
+            other => {
+                return Err(PlanError::UnsupportedSQLStatement(format!(
+                    "DESCRIBE {other}"
+                )))
+            }
+        };
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+        let plan = planner.query_to_plan(query).await?;
+        Ok(LogicalPlan::Datafusion(describe_schema(plan.schema())?))
+    }
+
+    /// Plan `SHOW ALL`, listing every session variable.
+    fn plan_show_all(&self) -> Result<LogicalPlan> {
+        let rows: Vec<_> = self
+            .ctx
+            .get_session_vars()
+            .entries()
+            .into_iter()
+            .map(|entry| {
+                vec![
+                    lit(entry.key),
+                    lit(entry.value.unwrap_or_default()),
+                    lit(entry.description),
+                ]
+            })
+            .collect();
+        let plan = LogicalPlanBuilder::values(rows)?
+            .project([
+                col("column1").alias("name"),
+                col("column2").alias("setting"),
+                col("column3").alias("description"),
+            ])?
+            .sort(vec![col("name").sort(true, false)])?
+            .build()?;
+        Ok(LogicalPlan::Datafusion(plan))
+    }
+
+    /// Plan a query generated from SQL text.
+    ///
+    /// Used for statements that are rewritten into queries over the system
+    /// tables or table functions.
+    async fn plan_query_sql(&self, sql: &str) -> Result<datafusion::logical_expr::LogicalPlan> {
+        let query = match parser::parse_sql(sql)?.pop_front() {
+            Some(StatementWithExtensions::Statement(ast::Statement::Query(q))) => *q,
+            _ => return Err(internal!("invalid generated query: {sql}")),
+        };
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+        Ok(planner.query_to_plan(query).await?)
+    }
+
+    /// Apply the `LIKE`, `ILIKE`, or `WHERE` filter of a `SHOW` statement.
+    ///
+    /// `LIKE` and `ILIKE` patterns match against `column`.
+    async fn apply_show_filter(
+        &self,
+        plan: datafusion::logical_expr::LogicalPlan,
+        column: &str,
+        filter: Option<ast::ShowStatementFilter>,
+    ) -> Result<datafusion::logical_expr::LogicalPlan> {
+        let expr = match filter {
+            None => return Ok(plan),
+            Some(ast::ShowStatementFilter::Like(pattern)) => col(column).like(lit(pattern)),
+            Some(ast::ShowStatementFilter::ILike(pattern)) => col(column).ilike(lit(pattern)),
+            Some(ast::ShowStatementFilter::Where(expr)) => {
+                let state = self.ctx.df_ctx().state();
+                let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+                let mut planner = SqlQueryPlanner::new(&mut context_provider);
+                planner
+                    .sql_to_expr(expr, plan.schema(), &mut PlannerContext::new())
+                    .await?
+            }
+        };
+        Ok(LogicalPlanBuilder::from(plan).filter(expr)?.build()?)
+    }
+
+    /// Check if a database is external, erroring if it doesn't exist.
+    fn is_external_database(&self, name: &str) -> Result<bool> {
+        match self.ctx.get_session_catalog().resolve_database(name) {
+            Some(db) => Ok(!matches!(db.options, DatabaseOptions::Internal(_))),
+            None => Err(PlanError::String(format!(
+                "database \"{name}\" does not exist"
+            ))),
+        }
+    }
+
+    /// Resolve a table that's stored natively, requiring that the user can
+    /// modify it.
+    fn resolve_native_table(
+        &self,
+        name: ast::ObjectName,
+        external_feature: &'static str,
+    ) -> Result<TableEntry> {
+        validate_object_name(&name)?;
+        let table_ref = object_name_to_table_ref(name)?;
+        let resolver = EntryResolver::from_context(self.ctx);
+        let ent = resolver.resolve_entry_from_reference(table_ref)?;
+        self.privileges().check_resolved(&ent, Privilege::Update)?;
+        let ent = ent.try_into_table_entry()?;
+        if ent.meta.external {
+            return Err(PlanError::UnsupportedFeature(external_feature));
+        }
+        Ok(ent)
+    }
+
+    /// Resolve the objects and privileges for a GRANT or REVOKE.
+    fn plan_privileges_on_objects(
+        &self,
+        stmt: PrivilegesOnObjects,
+    ) -> Result<(Vec<GrantObject>, Vec<Privilege>)> {
+        // Privileges that make sense for the object type. Schema and database
+        // privileges are inherited by the objects they contain, so every
+        // privilege is valid for them.
+        let valid: &[Privilege] = match stmt.object_type {
+            GrantObjectType::Table => &[
+                Privilege::Select,
+                Privilege::Insert,
+                Privilege::Update,
+                Privilege::Delete,
+            ],
+            GrantObjectType::Schema | GrantObjectType::Database => &[
+                Privilege::Select,
+                Privilege::Insert,
+                Privilege::Update,
+                Privilege::Delete,
+                Privilege::Create,
+                Privilege::Usage,
+            ],
+        };
+
+        let privileges = match stmt.privileges {
+            None => valid.to_vec(),
+            Some(idents) => {
+                let mut privileges = Vec::with_capacity(idents.len());
+                for ident in idents {
+                    let privilege = match normalize_ident(ident).as_str() {
+                        "select" => Privilege::Select,
+                        "insert" => Privilege::Insert,
+                        "update" => Privilege::Update,
+                        "delete" => Privilege::Delete,
+                        "create" => Privilege::Create,
+                        "usage" => Privilege::Usage,
+                        other => {
+                            return Err(PlanError::String(format!("Unknown privilege: {other}")))
+                        }
+                    };
+                    if !valid.contains(&privilege) {
+                        return Err(PlanError::String(format!(
+                            "Privilege {privilege} is not valid for {}",
+                            stmt.object_type
+                        )));
+                    }
+                    privileges.push(privilege);
+                }
+                privileges
+            }
+        };
+
+        let mut objects = Vec::with_capacity(stmt.objects.len());
+        for name in stmt.objects {
+            let object = match stmt.object_type {
+                GrantObjectType::Database | GrantObjectType::Schema => {
+                    let mut idents = name.0;
+                    if idents.len() != 1 {
+                        return Err(PlanError::String(format!(
+                            "Expected a single identifier for {}, got: {}",
+                            stmt.object_type,
+                            ObjectName(idents)
+                        )));
+                    }
+                    let name = normalize_ident(idents.pop().unwrap());
+                    if stmt.object_type == GrantObjectType::Database {
+                        GrantObject::Database(name)
+                    } else {
+                        GrantObject::Schema(name)
+                    }
+                }
+                GrantObjectType::Table => {
+                    let r = self
+                        .ctx
+                        .resolve_table_ref(object_name_to_table_ref(name)?)?;
+                    if r.database != DEFAULT_CATALOG {
+                        return Err(PlanError::UnsupportedFeature(
+                            "privileges on tables in external databases",
+                        ));
+                    }
+                    GrantObject::Table {
+                        schema: r.schema.into_owned(),
+                        name: r.name.into_owned(),
+                    }
+                }
+            };
+            objects.push(object);
+        }
+
+        Ok((objects, privileges))
+    }
+
     async fn plan_copy_to(&self, stmt: CopyToStmt) -> Result<LogicalPlan> {
         let query = match stmt.source {
             CopyToSource::Table(table) => {
@@ -1397,6 +3170,9 @@ impl<'a> SessionPlanner<'a> {
                 let array = m.remove_optional::<bool>("array")?.unwrap_or(false);
                 CopyToFormatOptions::Json(CopyToFormatOptionsJson { array })
             }
+            Some(CopyToFormatOptions::AVRO) => {
+                CopyToFormatOptions::Avro(CopyToFormatOptionsAvro {})
+            }
             Some(other) => return Err(internal!("unsupported output format: {other}")),
         };
 
@@ -1463,7 +3239,7 @@ async fn validate_and_get_file_type_and_compression(
     access: Arc<dyn ObjStoreAccess>,
     location: &str,
     m: &mut StmtOptions,
-) -> Result<(FileType, Option<CompressionTypeVariant>)> {
+) -> Result<(ObjStoreFileType, Option<CompressionTypeVariant>)> {
     let accessor =
         ObjStoreAccessor::new(access.clone()).map_err(|e| PlanError::InvalidExternalTable {
             source: Box::new(e),
@@ -1495,7 +3271,7 @@ async fn validate_and_get_file_type_and_compression(
             .and_then(|ext| ext.parse().ok()),
     };
 
-    let file_type = match m.remove_optional::<FileType>("file_type")? {
+    let file_type = match m.remove_optional::<ObjStoreFileType>("file_type")? {
         Some(file_type) => file_type,
         None => {
             let mut ft = None;
@@ -1516,6 +3292,41 @@ async fn validate_and_get_file_type_and_compression(
     Ok((file_type, compression))
 }
 
+/// Cast and rename the output of a plan to match the given fields by position.
+fn project_to_fields(
+    plan: datafusion::logical_expr::LogicalPlan,
+    fields: &[Field],
+) -> Result<datafusion::logical_expr::LogicalPlan> {
+    let exprs: Vec<_> = fields
+        .iter()
+        .zip(plan.schema().fields().iter())
+        .map(|(field, df_field)| {
+            cast(
+                Expr::Column(df_field.qualified_column()),
+                field.data_type().clone(),
+            )
+            .alias(field.name())
+        })
+        .collect();
+    Ok(LogicalPlanBuilder::from(plan).project(exprs)?.build()?)
+}
+
+/// Parse the interval for `REFRESH EVERY` into seconds.
+fn parse_refresh_interval(interval: &str) -> Result<u64> {
+    let invalid = || PlanError::InvalidRefreshInterval(interval.to_string());
+    let value = parse_interval_month_day_nano(interval).map_err(|_| invalid())?;
+    let (months, days, nanos) = IntervalMonthDayNanoType::to_parts(value);
+    // Months don't have a fixed length.
+    if months != 0 || days < 0 || nanos < 0 {
+        return Err(invalid());
+    }
+    let secs = days as u64 * 24 * 60 * 60 + nanos as u64 / 1_000_000_000;
+    if secs == 0 {
+        return Err(invalid());
+    }
+    Ok(secs)
+}
+
 /// Resolves an ident (unquoted -> lowercase else case sensitive).
 fn normalize_ident(ident: Ident) -> String {
     let normalizer = IdentNormalizer::new(/* normalize = */ true);
@@ -1539,6 +3350,40 @@ fn quoted_table_ref(table_ref: TableReference<'_>) -> String {
     }
 }
 
+/// Quote an identifier for use in generated SQL.
+fn quote_ident(ident: &str) -> String {
+    Ident::with_quote('"', ident).to_string()
+}
+
+/// Create a plan returning a row for each field in the schema. Columns match
+/// the output of the `list_columns` function.
+fn describe_schema(schema: &DFSchema) -> Result<datafusion::logical_expr::LogicalPlan> {
+    if schema.fields().is_empty() {
+        return Err(PlanError::String(
+            "relation does not have any columns to describe".to_string(),
+        ));
+    }
+    let rows: Vec<_> = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            vec![
+                lit(field.name().clone()),
+                lit(field.data_type().to_string()),
+                lit(field.is_nullable()),
+            ]
+        })
+        .collect();
+    let plan = LogicalPlanBuilder::values(rows)?
+        .project([
+            col("column1").alias("column_name"),
+            col("column2").alias("data_type"),
+            col("column3").alias("nullable"),
+        ])?
+        .build()?;
+    Ok(plan)
+}
+
 fn object_name_to_schema_ref(name: ObjectName) -> Result<OwnedSchemaReference> {
     let r = match object_name_to_table_ref(name)? {
         // Table becomes the schema and schema becomes the catalog.
@@ -1557,6 +3402,156 @@ fn object_name_to_schema_ref(name: ObjectName) -> Result<OwnedSchemaReference> {
 /// NOTE: This and `convert_simple_data_type` were both taken from datafusion's
 /// sql planner. These functions were made internal in version 15.0. Light
 /// modifications were made to fit our use case.
+/// Unique checks for a table's primary key and unique constraints.
+fn unique_checks(options: &TableOptionsInternal) -> Vec<UniqueCheck> {
+    options
+        .primary_key
+        .iter()
+        .chain(options.unique_constraints.iter())
+        .map(|constraint| UniqueCheck {
+            columns: constraint.columns.clone(),
+            violation: format!(
+                "duplicate key value violates unique constraint \"{}\"",
+                constraint.name
+            ),
+        })
+        .collect()
+}
+
+/// Convert parameter or output column definitions of a SQL function.
+fn convert_function_columns(columns: Vec<ast::ColumnDef>) -> Result<Vec<InternalColumnDefinition>> {
+    let mut names = HashSet::with_capacity(columns.len());
+    let mut defs = Vec::with_capacity(columns.len());
+    for column in columns {
+        validate_ident(&column.name)?;
+        let name = normalize_ident(column.name);
+        if !names.insert(name.clone()) {
+            return Err(PlanError::InvalidFunctionDefinition(format!(
+                "duplicate column name: {name}"
+            )));
+        }
+        defs.push(InternalColumnDefinition {
+            name,
+            nullable: true,
+            arrow_type: convert_data_type(&column.data_type)?,
+            default_expr: None,
+            comment: None,
+        });
+    }
+    Ok(defs)
+}
+
+/// Plan a new sequence, filling in unspecified options with the same defaults
+/// Postgres uses.
+fn plan_sequence(
+    schema: &str,
+    name: String,
+    data_type: &DataType,
+    options: Vec<ast::SequenceOptions>,
+) -> Result<service::CreateSequence> {
+    let (type_min, type_max) = match data_type {
+        DataType::Int16 => (i16::MIN as i64, i16::MAX as i64),
+        DataType::Int32 => (i32::MIN as i64, i32::MAX as i64),
+        DataType::Int64 => (i64::MIN, i64::MAX),
+        other => {
+            return Err(PlanError::InvalidSequence(format!(
+                "sequence type must be smallint, integer, or bigint, got {other}"
+            )))
+        }
+    };
+
+    let mut increment = 1;
+    let mut min_value = None;
+    let mut max_value = None;
+    let mut start = None;
+    for option in options {
+        match option {
+            ast::SequenceOptions::IncrementBy(expr, _) => increment = sequence_option_value(&expr)?,
+            ast::SequenceOptions::MinValue(ast::MinMaxValue::Some(expr)) => {
+                min_value = Some(sequence_option_value(&expr)?)
+            }
+            ast::SequenceOptions::MinValue(_) => min_value = None,
+            ast::SequenceOptions::MaxValue(ast::MinMaxValue::Some(expr)) => {
+                max_value = Some(sequence_option_value(&expr)?)
+            }
+            ast::SequenceOptions::MaxValue(_) => max_value = None,
+            ast::SequenceOptions::StartWith(expr, _) => start = Some(sequence_option_value(&expr)?),
+            // Values are allocated a batch at a time, there's nothing to cache.
+            ast::SequenceOptions::Cache(_) => (),
+            // NO CYCLE
+            ast::SequenceOptions::Cycle(true) => (),
+            ast::SequenceOptions::Cycle(false) => {
+                return Err(PlanError::UnsupportedFeature("CYCLE for sequences"))
+            }
+        }
+    }
+
+    if increment == 0 {
+        return Err(PlanError::InvalidSequence(
+            "INCREMENT must not be zero".to_string(),
+        ));
+    }
+    let min_value = min_value.unwrap_or(if increment > 0 { 1 } else { type_min });
+    let max_value = max_value.unwrap_or(if increment > 0 { type_max } else { -1 });
+    if min_value < type_min || max_value > type_max {
+        return Err(PlanError::InvalidSequence(format!(
+            "MINVALUE ({min_value}) and MAXVALUE ({max_value}) must be within the bounds of {data_type}"
+        )));
+    }
+    if min_value >= max_value {
+        return Err(PlanError::InvalidSequence(format!(
+            "MINVALUE ({min_value}) must be less than MAXVALUE ({max_value})"
+        )));
+    }
+    let start = start.unwrap_or(if increment > 0 { min_value } else { max_value });
+    if start < min_value || start > max_value {
+        return Err(PlanError::InvalidSequence(format!(
+            "START value ({start}) cannot be outside of MINVALUE ({min_value}) and MAXVALUE ({max_value})"
+        )));
+    }
+
+    Ok(service::CreateSequence {
+        schema: schema.to_string(),
+        name,
+        if_not_exists: false,
+        start,
+        increment,
+        min_value,
+        max_value,
+        owned_by_table: None,
+    })
+}
+
+fn sequence_option_value(expr: &ast::Expr) -> Result<i64> {
+    let value = match expr {
+        ast::Expr::Value(ast::Value::Number(n, _)) => n.parse().ok(),
+        ast::Expr::UnaryOp {
+            op: ast::UnaryOperator::Minus,
+            expr,
+        } => match expr.as_ref() {
+            ast::Expr::Value(ast::Value::Number(n, _)) => format!("-{n}").parse().ok(),
+            _ => None,
+        },
+        _ => None,
+    };
+    value.ok_or_else(|| PlanError::InvalidSequence(format!("expected an integer, got {expr}")))
+}
+
+/// Get the integer type for one of the serial pseudo-types.
+fn serial_type(sql_type: &ast::DataType) -> Option<DataType> {
+    match sql_type {
+        ast::DataType::Custom(name, modifiers) if name.0.len() == 1 && modifiers.is_empty() => {
+            match name.0[0].value.to_lowercase().as_str() {
+                "smallserial" | "serial2" => Some(DataType::Int16),
+                "serial" | "serial4" => Some(DataType::Int32),
+                "bigserial" | "serial8" => Some(DataType::Int64),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 fn convert_data_type(sql_type: &ast::DataType) -> Result<DataType> {
     match sql_type {
         ast::DataType::Array(Some(inner_sql_type)) => {
@@ -1716,6 +3711,28 @@ fn get_mysql_conn_str(m: &mut StmtOptions) -> Result<String> {
     Ok(conn.connection_string())
 }
 
+fn get_sqlserver_conn_str(m: &mut StmtOptions) -> Result<String> {
+    let conn = match m.remove_optional("connection_string")? {
+        Some(conn_str) => SqlServerDbConnection::ConnectionString(conn_str),
+        None => {
+            let host = m.remove_required("host")?;
+            let port = m.remove_optional("port")?;
+            let user = m.remove_required("user")?;
+            let password = m.remove_optional("password")?;
+            let database = m.remove_required("database")?;
+            SqlServerDbConnection::Parameters {
+                host,
+                port,
+                user,
+                password,
+                database,
+            }
+        }
+    };
+
+    Ok(conn.connection_string())
+}
+
 fn get_mongo_conn_str(m: &mut StmtOptions) -> Result<String> {
     let conn = match m.remove_optional("connection_string")? {
         Some(conn_str) => MongoDbConnection::ConnectionString(conn_str),
@@ -0,0 +1,324 @@
+//! Server-wide history of executed queries.
+//!
+//! Unlike session query metrics, the history is shared by all sessions for an
+//! engine and outlives any single session. The history is bounded, with the
+//! oldest queries being dropped once the limit is reached.
+//!
+//! The history may optionally be persisted to the engine's storage. When
+//! persisted, the history is written out periodically as newline-delimited
+//! JSON, and loaded back in when the engine starts.
+use crate::background_jobs::query_history::BackgroundJobPersistQueryHistory;
+use crate::background_jobs::JobRunner;
+use crate::errors::Result;
+use crate::metrics::QueryMetrics;
+use crate::planner::logical_plan::LogicalPlan;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use object_store::path::Path as ObjectStorePath;
+use object_store::ObjectStore;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Default number of queries to keep in the history.
+pub const DEFAULT_QUERY_HISTORY_SIZE: usize = 1000;
+
+/// Path in the engine's storage to persist the history to.
+const HISTORY_PATH: &str = "query_history/history.ndjson";
+
+#[derive(Debug, Clone, Copy)]
+pub struct QueryHistoryConfig {
+    /// Max number of queries to keep.
+    pub max_records: usize,
+    /// Persist the history to the engine's storage.
+    pub persist: bool,
+}
+
+impl Default for QueryHistoryConfig {
+    fn default() -> Self {
+        QueryHistoryConfig {
+            max_records: DEFAULT_QUERY_HISTORY_SIZE,
+            persist: false,
+        }
+    }
+}
+
+/// A single query in the history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryHistoryRecord {
+    /// When the query started executing.
+    pub timestamp: DateTime<Utc>,
+    pub user_name: String,
+    pub connection_id: Uuid,
+    pub database_id: Uuid,
+    pub query_text: String,
+    /// Hash of the query's logical plan. Queries with the same plan have the
+    /// same hash.
+    pub plan_hash: Option<u64>,
+    pub result_type: String,
+    pub execution_status: String,
+    pub error_message: Option<String>,
+    pub duration_ms: u64,
+    pub elapsed_compute_ns: Option<u64>,
+    pub output_rows: Option<u64>,
+    /// Bytes read by each data source.
+    pub bytes_read_by_source: BTreeMap<String, u64>,
+    /// Rows read by each data source.
+    pub rows_read_by_source: BTreeMap<String, u64>,
+}
+
+#[derive(Debug)]
+pub struct QueryHistory {
+    max_records: usize,
+    records: Mutex<VecDeque<QueryHistoryRecord>>,
+    persistence: Option<Persistence>,
+}
+
+#[derive(Debug)]
+struct Persistence {
+    store: Arc<dyn ObjectStore>,
+    jobs: JobRunner,
+    /// Whether or not a job to persist the history has been scheduled.
+    scheduled: AtomicBool,
+}
+
+impl QueryHistory {
+    /// Create a new in-memory history.
+    pub fn new(max_records: usize) -> QueryHistory {
+        QueryHistory {
+            max_records,
+            records: Mutex::new(VecDeque::new()),
+            persistence: None,
+        }
+    }
+
+    /// Create a new history that's persisted to the provided store, loading
+    /// any previously persisted queries.
+    pub(crate) async fn new_persisted(
+        max_records: usize,
+        store: Arc<dyn ObjectStore>,
+        jobs: JobRunner,
+    ) -> Result<QueryHistory> {
+        let mut records = VecDeque::new();
+
+        match store.get(&ObjectStorePath::from(HISTORY_PATH)).await {
+            Ok(result) => {
+                let buf = result.bytes().await?;
+                for line in buf.split(|b| *b == b'\n').filter(|line| !line.is_empty()) {
+                    match serde_json::from_slice(line) {
+                        Ok(record) => records.push_back(record),
+                        Err(e) => warn!(%e, "skipping invalid query history record"),
+                    }
+                }
+            }
+            Err(object_store::Error::NotFound { .. }) => (),
+            Err(e) => return Err(e.into()),
+        }
+
+        while records.len() > max_records {
+            records.pop_front();
+        }
+        debug!(num_records = records.len(), "loaded query history");
+
+        Ok(QueryHistory {
+            max_records,
+            records: Mutex::new(records),
+            persistence: Some(Persistence {
+                store,
+                jobs,
+                scheduled: AtomicBool::new(false),
+            }),
+        })
+    }
+
+    /// Add a query to the history.
+    pub fn push(self: &Arc<Self>, record: QueryHistoryRecord) {
+        {
+            let mut records = self.records.lock();
+            records.push_back(record);
+            if records.len() > self.max_records {
+                records.pop_front();
+            }
+        }
+
+        if let Some(persistence) = &self.persistence {
+            if !persistence.scheduled.swap(true, Ordering::Relaxed) {
+                let job = BackgroundJobPersistQueryHistory::new(self.clone());
+                if let Err(e) = persistence.jobs.add(job) {
+                    debug!(%e, "failed to schedule persisting query history");
+                    persistence.scheduled.store(false, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Get all queries in the history, oldest first.
+    pub fn records(&self) -> Vec<QueryHistoryRecord> {
+        self.records.lock().iter().cloned().collect()
+    }
+
+    /// Write the history out to storage.
+    ///
+    /// Does nothing if the history isn't persisted.
+    pub(crate) async fn persist(&self) -> Result<()> {
+        let persistence = match &self.persistence {
+            Some(persistence) => persistence,
+            None => return Ok(()),
+        };
+        // Allow queries completing from now on to schedule another job.
+        persistence.scheduled.store(false, Ordering::Relaxed);
+
+        let mut buf = Vec::new();
+        for record in self.records() {
+            serde_json::to_writer(&mut buf, &record)?;
+            buf.push(b'\n');
+        }
+
+        persistence
+            .store
+            .put(&ObjectStorePath::from(HISTORY_PATH), Bytes::from(buf))
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Records a session's completed queries to the server-wide history.
+#[derive(Debug, Clone)]
+pub struct SessionQueryHistory {
+    history: Arc<QueryHistory>,
+    user_name: String,
+    connection_id: Uuid,
+    database_id: Uuid,
+}
+
+impl SessionQueryHistory {
+    pub fn new(
+        history: Arc<QueryHistory>,
+        user_name: String,
+        connection_id: Uuid,
+        database_id: Uuid,
+    ) -> SessionQueryHistory {
+        SessionQueryHistory {
+            history,
+            user_name,
+            connection_id,
+            database_id,
+        }
+    }
+
+    /// Get the server-wide history.
+    pub fn history(&self) -> &QueryHistory {
+        &self.history
+    }
+
+    /// Add a completed query to the history.
+    pub fn record(&self, metrics: &QueryMetrics) {
+        self.history.push(QueryHistoryRecord {
+            timestamp: metrics.timestamp,
+            user_name: self.user_name.clone(),
+            connection_id: self.connection_id,
+            database_id: self.database_id,
+            query_text: metrics.query_text.clone(),
+            plan_hash: metrics.plan_hash,
+            result_type: metrics.result_type.to_string(),
+            execution_status: metrics.execution_status.as_str().to_string(),
+            error_message: metrics.error_message.clone(),
+            duration_ms: metrics.started.elapsed().as_millis() as u64,
+            elapsed_compute_ns: metrics.elapsed_compute_ns,
+            output_rows: metrics.output_rows,
+            bytes_read_by_source: metrics.bytes_read_by_source.clone(),
+            rows_read_by_source: metrics.rows_read_by_source.clone(),
+        })
+    }
+}
+
+/// Compute a hash for a logical plan.
+///
+/// Only plans executed through datafusion are hashed. FNV-1a is used over the
+/// plan's display output to keep hashes stable across builds since hashes may
+/// be persisted.
+pub(crate) fn plan_hash(plan: &LogicalPlan) -> Option<u64> {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    match plan {
+        LogicalPlan::Datafusion(plan) => {
+            let display = plan.display_indent().to_string();
+            let hash = display.bytes().fold(FNV_OFFSET_BASIS, |hash, b| {
+                (hash ^ b as u64).wrapping_mul(FNV_PRIME)
+            });
+            Some(hash)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object_store::memory::InMemory;
+
+    fn test_record(query_text: &str) -> QueryHistoryRecord {
+        QueryHistoryRecord {
+            timestamp: Utc::now(),
+            user_name: "glaredb".to_string(),
+            connection_id: Uuid::nil(),
+            database_id: Uuid::nil(),
+            query_text: query_text.to_string(),
+            plan_hash: Some(1),
+            result_type: "query".to_string(),
+            execution_status: "success".to_string(),
+            error_message: None,
+            duration_ms: 1,
+            elapsed_compute_ns: Some(10),
+            output_rows: Some(1),
+            bytes_read_by_source: BTreeMap::from([("MemoryExec".to_string(), 8)]),
+            rows_read_by_source: BTreeMap::from([("MemoryExec".to_string(), 1)]),
+        }
+    }
+
+    #[test]
+    fn bounded_history() {
+        let history = Arc::new(QueryHistory::new(3));
+        for i in 0..5 {
+            history.push(test_record(&format!("SELECT {i}")));
+        }
+
+        let queries: Vec<_> = history
+            .records()
+            .into_iter()
+            .map(|r| r.query_text)
+            .collect();
+        assert_eq!(vec!["SELECT 2", "SELECT 3", "SELECT 4"], queries);
+    }
+
+    #[tokio::test]
+    async fn persist_and_load() {
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let jobs = JobRunner::new(Default::default());
+
+        let history = Arc::new(
+            QueryHistory::new_persisted(10, store.clone(), jobs.clone())
+                .await
+                .unwrap(),
+        );
+        assert!(history.records().is_empty());
+
+        for i in 0..3 {
+            history.push(test_record(&format!("SELECT {i}")));
+        }
+        history.persist().await.unwrap();
+
+        // Load back with a smaller bound, keeping only the most recent.
+        let loaded = QueryHistory::new_persisted(2, store, jobs).await.unwrap();
+        let records = loaded.records();
+        assert_eq!(2, records.len());
+        assert_eq!("SELECT 1", records[0].query_text);
+        assert_eq!(Some(&8), records[1].bytes_read_by_source.get("MemoryExec"));
+    }
+}
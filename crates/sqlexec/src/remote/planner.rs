@@ -21,21 +21,29 @@ use std::sync::Arc;
 use crate::metastore::catalog::{SessionCatalog, TempCatalog};
 use crate::planner::extension::ExtensionType;
 use crate::planner::logical_plan::{
-    AlterDatabaseRename, AlterTableRename, AlterTunnelRotateKeys, CopyTo, CreateCredentials,
-    CreateExternalDatabase, CreateExternalTable, CreateSchema, CreateTable, CreateTempTable,
-    CreateTunnel, CreateView, Delete, DropCredentials, DropDatabase, DropSchemas, DropTables,
-    DropTunnel, DropViews, Insert, SetVariable, ShowVariable, Update,
+    AlterDatabaseRename, AlterTableRename, AlterTunnelRotateKeys, CommentOn, CopyTo,
+    CreateCredentials, CreateExternalDatabase, CreateExternalDatabaseTable, CreateExternalTable,
+    CreateFunction, CreateRole, CreateSchema, CreateSequence, CreateTable, CreateTempTable,
+    CreateTunnel, CreateView, Delete, DropCredentials, DropDatabase, DropFunctions, DropRoles,
+    DropSchemas, DropSequences, DropTables, DropTunnel, DropViews, GrantPrivileges, Insert,
+    OptimizeTable, RefreshMaterializedView, RevokePrivileges, SetVariable, ShowVariable, Update,
+    VacuumTable,
 };
 use crate::planner::physical_plan::alter_database_rename::AlterDatabaseRenameExec;
 use crate::planner::physical_plan::alter_table_rename::AlterTableRenameExec;
 use crate::planner::physical_plan::alter_tunnel_rotate_keys::AlterTunnelRotateKeysExec;
 use crate::planner::physical_plan::client_recv::ClientExchangeRecvExec;
 use crate::planner::physical_plan::client_send::ClientExchangeSendExec;
+use crate::planner::physical_plan::comment_on::CommentOnExec;
 use crate::planner::physical_plan::copy_to::CopyToExec;
 use crate::planner::physical_plan::create_credentials::CreateCredentialsExec;
 use crate::planner::physical_plan::create_external_database::CreateExternalDatabaseExec;
+use crate::planner::physical_plan::create_external_database_table::CreateExternalDatabaseTableExec;
 use crate::planner::physical_plan::create_external_table::CreateExternalTableExec;
+use crate::planner::physical_plan::create_function::CreateFunctionExec;
+use crate::planner::physical_plan::create_role::CreateRoleExec;
 use crate::planner::physical_plan::create_schema::CreateSchemaExec;
+use crate::planner::physical_plan::create_sequence::CreateSequenceExec;
 use crate::planner::physical_plan::create_table::CreateTableExec;
 use crate::planner::physical_plan::create_temp_table::CreateTempTableExec;
 use crate::planner::physical_plan::create_tunnel::CreateTunnelExec;
@@ -43,18 +51,26 @@ use crate::planner::physical_plan::create_view::CreateViewExec;
 use crate::planner::physical_plan::delete::DeleteExec;
 use crate::planner::physical_plan::drop_credentials::DropCredentialsExec;
 use crate::planner::physical_plan::drop_database::DropDatabaseExec;
+use crate::planner::physical_plan::drop_functions::DropFunctionsExec;
+use crate::planner::physical_plan::drop_roles::DropRolesExec;
 use crate::planner::physical_plan::drop_schemas::DropSchemasExec;
+use crate::planner::physical_plan::drop_sequences::DropSequencesExec;
 use crate::planner::physical_plan::drop_tables::DropTablesExec;
 use crate::planner::physical_plan::drop_temp_tables::DropTempTablesExec;
 use crate::planner::physical_plan::drop_tunnel::DropTunnelExec;
 use crate::planner::physical_plan::drop_views::DropViewsExec;
+use crate::planner::physical_plan::grant_privileges::GrantPrivilegesExec;
 use crate::planner::physical_plan::insert::InsertExec;
+use crate::planner::physical_plan::optimize_table::OptimizeTableExec;
+use crate::planner::physical_plan::refresh_materialized_view::RefreshMaterializedViewExec;
 use crate::planner::physical_plan::remote_exec::RemoteExecutionExec;
 use crate::planner::physical_plan::remote_scan::ProviderReference;
+use crate::planner::physical_plan::revoke_privileges::RevokePrivilegesExec;
 use crate::planner::physical_plan::send_recv::SendRecvJoinExec;
 use crate::planner::physical_plan::set_var::SetVarExec;
 use crate::planner::physical_plan::show_var::ShowVarExec;
 use crate::planner::physical_plan::update::UpdateExec;
+use crate::planner::physical_plan::vacuum_table::VacuumTableExec;
 
 use super::client::RemoteSessionClient;
 
@@ -155,6 +171,8 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                     or_replace: lp.or_replace,
                     arrow_schema: Arc::new(lp.schema.as_ref().into()),
                     source: physical_inputs.get(0).cloned(),
+                    options: lp.options.clone(),
+                    sequences: lp.sequences.clone(),
                 })))
             }
             ExtensionType::CreateTempTable => {
@@ -259,6 +277,39 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                     if_exists: lp.if_exists,
                 })))
             }
+            ExtensionType::CreateRole => {
+                let lp = require_downcast_lp::<CreateRole>(node);
+                Ok(Some(Arc::new(CreateRoleExec {
+                    catalog_version: self.catalog.version(),
+                    name: lp.name.clone(),
+                    if_not_exists: lp.if_not_exists,
+                    superuser: lp.superuser,
+                    login: lp.login,
+                    password_hash: lp.password_hash.clone(),
+                })))
+            }
+            ExtensionType::DropRoles => {
+                let lp = require_downcast_lp::<DropRoles>(node);
+                Ok(Some(Arc::new(DropRolesExec {
+                    catalog_version: self.catalog.version(),
+                    names: lp.names.clone(),
+                    if_exists: lp.if_exists,
+                })))
+            }
+            ExtensionType::GrantPrivileges => {
+                let lp = require_downcast_lp::<GrantPrivileges>(node);
+                Ok(Some(Arc::new(GrantPrivilegesExec {
+                    catalog_version: self.catalog.version(),
+                    grants: lp.grants.clone(),
+                })))
+            }
+            ExtensionType::RevokePrivileges => {
+                let lp = require_downcast_lp::<RevokePrivileges>(node);
+                Ok(Some(Arc::new(RevokePrivilegesExec {
+                    catalog_version: self.catalog.version(),
+                    revokes: lp.revokes.clone(),
+                })))
+            }
             ExtensionType::DropDatabase => {
                 let lp = require_downcast_lp::<DropDatabase>(node);
                 let exec = DropDatabaseExec {
@@ -335,6 +386,8 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                     table: lp.table.clone(),
                     updates: lp.updates.clone(),
                     where_expr: lp.where_expr.clone(),
+                    checks: lp.checks.clone(),
+                    unique_checks: lp.unique_checks.clone(),
                 })))
             }
             ExtensionType::Insert => {
@@ -350,6 +403,9 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                 let exec = Arc::new(InsertExec {
                     provider,
                     source: physical_inputs.get(0).unwrap().clone(),
+                    checks: lp.checks.clone(),
+                    unique_checks: lp.unique_checks.clone(),
+                    compaction: lp.compaction.clone(),
                 });
                 let exec = Arc::new(RuntimeGroupExec::new(lp.runtime_preference, exec));
                 Ok(Some(exec))
@@ -361,6 +417,79 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                     where_expr: lp.where_expr.clone(),
                 })))
             }
+            ExtensionType::OptimizeTable => {
+                let lp = require_downcast_lp::<OptimizeTable>(node);
+                Ok(Some(Arc::new(OptimizeTableExec {
+                    table: lp.table.clone(),
+                    zorder_by: lp.zorder_by.clone(),
+                })))
+            }
+            ExtensionType::VacuumTable => {
+                let lp = require_downcast_lp::<VacuumTable>(node);
+                Ok(Some(Arc::new(VacuumTableExec {
+                    table: lp.table.clone(),
+                    retain_hours: lp.retain_hours,
+                })))
+            }
+            ExtensionType::RefreshMaterializedView => {
+                let lp = require_downcast_lp::<RefreshMaterializedView>(node);
+                Ok(Some(Arc::new(RefreshMaterializedViewExec {
+                    catalog_version: self.catalog.version(),
+                    schema: lp.schema.clone(),
+                    table: lp.table.clone(),
+                    source: physical_inputs.get(0).cloned().ok_or_else(|| {
+                        DataFusionError::Internal("missing source for refresh".to_string())
+                    })?,
+                })))
+            }
+            ExtensionType::CreateFunction => {
+                let lp = require_downcast_lp::<CreateFunction>(node);
+                Ok(Some(Arc::new(CreateFunctionExec {
+                    catalog_version: self.catalog.version(),
+                    function: lp.function.clone(),
+                })))
+            }
+            ExtensionType::DropFunctions => {
+                let lp = require_downcast_lp::<DropFunctions>(node);
+                Ok(Some(Arc::new(DropFunctionsExec {
+                    catalog_version: self.catalog.version(),
+                    drops: lp.drops.clone(),
+                })))
+            }
+            ExtensionType::CreateSequence => {
+                let lp = require_downcast_lp::<CreateSequence>(node);
+                Ok(Some(Arc::new(CreateSequenceExec {
+                    catalog_version: self.catalog.version(),
+                    sequence: lp.sequence.clone(),
+                })))
+            }
+            ExtensionType::DropSequences => {
+                let lp = require_downcast_lp::<DropSequences>(node);
+                Ok(Some(Arc::new(DropSequencesExec {
+                    catalog_version: self.catalog.version(),
+                    sequence_references: lp.sequence_references.clone(),
+                    if_exists: lp.if_exists,
+                })))
+            }
+            ExtensionType::CommentOn => {
+                let lp = require_downcast_lp::<CommentOn>(node);
+                Ok(Some(Arc::new(CommentOnExec {
+                    catalog_version: self.catalog.version(),
+                    comment: lp.comment.clone(),
+                })))
+            }
+            ExtensionType::CreateExternalDatabaseTable => {
+                let lp = require_downcast_lp::<CreateExternalDatabaseTable>(node);
+                Ok(Some(Arc::new(CreateExternalDatabaseTableExec {
+                    tbl_reference: lp.tbl_reference.clone(),
+                    if_not_exists: lp.if_not_exists,
+                    or_replace: lp.or_replace,
+                    arrow_schema: Arc::new(lp.schema.as_ref().into()),
+                    source: physical_inputs.get(0).cloned(),
+                    options: lp.options.clone(),
+                    tunnel: lp.tunnel.clone(),
+                })))
+            }
         }
     }
 }
@@ -0,0 +1,509 @@
+//! Sequence functions.
+//!
+//! Values are allocated from sequences through metastore mutations, so
+//! allocation is safe across sessions. Metastore applies allocations at its
+//! latest catalog version, so concurrent sessions don't conflict with each
+//! other.
+//!
+//! Every mutation persists the full catalog and bumps its version, which is
+//! far too expensive to do for every call to `nextval`. Sessions instead
+//! allocate at least [`SEQUENCE_CACHE_SIZE`] values at a time and hand them
+//! out locally until they run out, similar to the `CACHE` option in Postgres.
+//! Values are still unique across sessions, but aren't necessarily handed out
+//! in order across sessions, and any values left in a session's cache when it
+//! ends are never used. Calling `setval` discards the calling session's cache,
+//! but other sessions keep using the values they've already allocated.
+use crate::metastore::catalog::CatalogMutator;
+use datafusion::arrow::array::{Array, BooleanArray, Int64Array};
+use datafusion::arrow::datatypes::DataType;
+use datafusion::common::ScalarValue;
+use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_expr::{ColumnarValue, ScalarUDF, Signature, TypeSignature, Volatility};
+use datafusion::prelude::{random, Expr};
+use parking_lot::Mutex;
+use protogen::metastore::types::catalog::{CatalogEntry, CatalogState, Privilege, SequenceEntry};
+use protogen::metastore::types::service::{AdvanceSequence, Mutation, SetSequenceValue};
+use sqlbuiltins::builtins::POSTGRES_SCHEMA;
+use std::collections::HashMap;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// Functions operating on sequences.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SequenceFunction {
+    /// SQL function `nextval`
+    ///
+    /// `nextval(sequence text)` -> `Int64`
+    /// ```sql
+    /// select nextval('my_seq');
+    /// ```
+    NextVal,
+    /// SQL function `currval`
+    ///
+    /// `currval(sequence text)` -> `Int64`
+    /// ```sql
+    /// select currval('my_seq');
+    /// ```
+    ///
+    /// Returns the value most recently returned by `nextval` for the sequence
+    /// in this session.
+    CurrVal,
+    /// SQL function `setval`
+    ///
+    /// `setval(sequence text, value bigint [, is_called boolean])` -> `Int64`
+    /// ```sql
+    /// select setval('my_seq', 10);
+    /// ```
+    ///
+    /// If `is_called` is false, the next call to `nextval` will return
+    /// `value`. Otherwise the next call returns the value after it.
+    SetVal,
+}
+
+impl SequenceFunction {
+    pub fn find_function(name: &str) -> Option<Self> {
+        Self::from_str(name).ok()
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::NextVal => "nextval",
+            Self::CurrVal => "currval",
+            Self::SetVal => "setval",
+        }
+    }
+
+    /// The privilege a session needs on a sequence to call this function.
+    pub const fn required_privilege(&self) -> Privilege {
+        match self {
+            Self::NextVal | Self::SetVal => Privilege::Update,
+            Self::CurrVal => Privilege::Select,
+        }
+    }
+
+    /// Get the name of the sequence from the function arguments.
+    pub fn sequence_name(&self, args: &[Expr]) -> Result<String> {
+        match args.get(0) {
+            Some(Expr::Literal(ScalarValue::Utf8(Some(name)))) => Ok(name.clone()),
+            _ => Err(DataFusionError::Plan(format!(
+                "{}: first argument must be a sequence name literal",
+                self.name()
+            ))),
+        }
+    }
+
+    pub fn into_expr(self, seq: SequenceHandle, mut args: Vec<Expr>) -> Result<Expr> {
+        let expected = match self {
+            Self::NextVal | Self::CurrVal => "1",
+            Self::SetVal => "2 or 3",
+        };
+        let valid = match self {
+            Self::NextVal | Self::CurrVal => args.len() == 1,
+            Self::SetVal => args.len() == 2 || args.len() == 3,
+        };
+        if !valid {
+            return Err(DataFusionError::Plan(format!(
+                "{} expects {expected} arguments, got {}",
+                self.name(),
+                args.len()
+            )));
+        }
+
+        let udf = match self {
+            Self::NextVal => {
+                // Scalar UDFs aren't told how many rows are in a batch, pass
+                // in a volatile expression to get an array of the right
+                // length.
+                args.push(random());
+                nextval(seq)
+            }
+            Self::CurrVal => currval(seq),
+            Self::SetVal => setval(seq),
+        };
+
+        Ok(
+            Expr::ScalarUDF(datafusion::logical_expr::expr::ScalarUDF::new(
+                udf.into(),
+                args,
+            ))
+            .alias(self.name()),
+        )
+    }
+}
+
+impl FromStr for SequenceFunction {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        let name = match s.split_once('.') {
+            Some((schema, name)) if schema == POSTGRES_SCHEMA => name,
+            Some(_) => "",
+            None => s.as_str(),
+        };
+        match name {
+            "nextval" => Ok(Self::NextVal),
+            "currval" => Ok(Self::CurrVal),
+            "setval" => Ok(Self::SetVal),
+            _ => Err(DataFusionError::NotImplemented(format!(
+                "SequenceFunction::from_str({s})"
+            ))),
+        }
+    }
+}
+
+/// Minimum number of values a session allocates from a sequence at once.
+pub const SEQUENCE_CACHE_SIZE: usize = 32;
+
+/// Values allocated to a session that haven't been returned by `nextval` yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CachedValues {
+    next: i64,
+    increment: i64,
+    remaining: usize,
+}
+
+/// Sequence state for a single session.
+#[derive(Debug, Default)]
+pub struct SessionSequences {
+    /// Last value returned by `nextval` for each sequence, keyed by oid.
+    current: Mutex<HashMap<u32, i64>>,
+    /// Preallocated values for each sequence, keyed by oid.
+    cached: Mutex<HashMap<u32, CachedValues>>,
+}
+
+impl SessionSequences {
+    pub fn current_value(&self, oid: u32) -> Option<i64> {
+        self.current.lock().get(&oid).copied()
+    }
+
+    pub fn set_current_value(&self, oid: u32, value: i64) {
+        self.current.lock().insert(oid, value);
+    }
+
+    /// Take up to `count` preallocated values for a sequence.
+    fn take_cached(&self, oid: u32, count: usize) -> Vec<i64> {
+        let mut cached = self.cached.lock();
+        let vals = match cached.get_mut(&oid) {
+            Some(vals) => vals,
+            None => return Vec::new(),
+        };
+
+        let n = count.min(vals.remaining);
+        let values: Vec<_> = (0..n as i64)
+            .map(|i| vals.next + i * vals.increment)
+            .collect();
+        vals.remaining -= n;
+        vals.next += n as i64 * vals.increment;
+        if vals.remaining == 0 {
+            cached.remove(&oid);
+        }
+
+        values
+    }
+
+    /// Store preallocated values for a sequence, replacing anything
+    /// previously cached.
+    fn set_cached(&self, oid: u32, next: i64, increment: i64, remaining: usize) {
+        let mut cached = self.cached.lock();
+        if remaining == 0 {
+            cached.remove(&oid);
+        } else {
+            cached.insert(
+                oid,
+                CachedValues {
+                    next,
+                    increment,
+                    remaining,
+                },
+            );
+        }
+    }
+
+    /// Discard any preallocated values for a sequence.
+    fn clear_cached(&self, oid: u32) {
+        self.cached.lock().remove(&oid);
+    }
+}
+
+/// A sequence resolved during planning.
+#[derive(Clone)]
+pub struct SequenceHandle {
+    oid: u32,
+    schema: String,
+    name: String,
+    mutator: Arc<CatalogMutator>,
+    session: Arc<SessionSequences>,
+    /// Latest catalog version we know about. Updated after every mutation to
+    /// avoid retrying `setval` against a stale catalog. Allocations aren't
+    /// checked against the version.
+    version: Arc<AtomicU64>,
+}
+
+impl SequenceHandle {
+    pub fn new(
+        schema: impl Into<String>,
+        seq: &SequenceEntry,
+        catalog_version: u64,
+        mutator: Arc<CatalogMutator>,
+        session: Arc<SessionSequences>,
+    ) -> Self {
+        SequenceHandle {
+            oid: seq.meta.id,
+            schema: schema.into(),
+            name: seq.meta.name.clone(),
+            mutator,
+            session,
+            version: Arc::new(AtomicU64::new(catalog_version)),
+        }
+    }
+
+    /// Get the next `count` values from the sequence, using values cached in
+    /// the session before allocating more from metastore.
+    fn next_values(&self, count: usize) -> Result<Vec<i64>> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut values = self.session.take_cached(self.oid, count);
+        let needed = count - values.len();
+        if needed > 0 {
+            let (first, increment, allocated) = self.allocate(needed)?;
+            values.extend((0..needed as i64).map(|i| first + i * increment));
+            self.session.set_cached(
+                self.oid,
+                first + needed as i64 * increment,
+                increment,
+                allocated - needed,
+            );
+        }
+
+        if let Some(last) = values.last() {
+            self.session.set_current_value(self.oid, *last);
+        }
+
+        Ok(values)
+    }
+
+    /// Allocate at least `count` values from metastore, returning the first
+    /// value, the increment, and the number of values allocated.
+    ///
+    /// Allocates a full cache's worth of values if the sequence has enough
+    /// left, otherwise only `count`.
+    fn allocate(&self, count: usize) -> Result<(i64, i64, usize)> {
+        let batch = count.max(SEQUENCE_CACHE_SIZE);
+        let (state, allocated) = match self.advance(batch) {
+            Ok(state) => (state, batch),
+            Err(_) if batch > count => (self.advance(count)?, count),
+            Err(e) => return Err(e),
+        };
+        let seq = self.get_sequence(&state)?;
+
+        // The sequence is left at the last value we allocated, work backwards
+        // from there.
+        let first = seq.last_value - (allocated as i64 - 1) * seq.increment;
+        Ok((first, seq.increment, allocated))
+    }
+
+    fn advance(&self, count: usize) -> Result<Arc<CatalogState>> {
+        self.mutate(Mutation::AdvanceSequence(AdvanceSequence {
+            schema: self.schema.clone(),
+            name: self.name.clone(),
+            count: count as u64,
+        }))
+    }
+
+    fn set_value(&self, value: i64, is_called: bool) -> Result<()> {
+        self.session.clear_cached(self.oid);
+        self.mutate(Mutation::SetSequenceValue(SetSequenceValue {
+            schema: self.schema.clone(),
+            name: self.name.clone(),
+            value,
+            is_called,
+        }))?;
+
+        if is_called {
+            self.session.set_current_value(self.oid, value);
+        }
+
+        Ok(())
+    }
+
+    fn current_value(&self) -> Result<i64> {
+        self.session.current_value(self.oid).ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "currval of sequence '{}' is not yet defined in this session",
+                self.name
+            ))
+        })
+    }
+
+    fn mutate(&self, mutation: Mutation) -> Result<Arc<CatalogState>> {
+        let version = self.version.load(Ordering::Relaxed);
+        let state = block_on(self.mutator.mutate(version, [mutation]))?
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        self.version.fetch_max(state.version, Ordering::Relaxed);
+        Ok(state)
+    }
+
+    fn get_sequence<'a>(&self, state: &'a CatalogState) -> Result<&'a SequenceEntry> {
+        match state.entries.get(&self.oid) {
+            Some(CatalogEntry::Sequence(seq)) => Ok(seq),
+            _ => Err(DataFusionError::Execution(format!(
+                "Sequence '{}' no longer exists",
+                self.name
+            ))),
+        }
+    }
+}
+
+/// Run a future to completion from within a synchronous function.
+///
+/// Scalar functions are synchronous, but allocating values requires a round
+/// trip to metastore.
+fn block_on<F: Future>(fut: F) -> Result<F::Output> {
+    let handle = Handle::try_current().map_err(|e| {
+        DataFusionError::Execution(format!("Sequence functions require a runtime: {e}"))
+    })?;
+    if handle.runtime_flavor() == RuntimeFlavor::CurrentThread {
+        return Err(DataFusionError::Execution(
+            "Sequence functions require a multi-threaded runtime".to_string(),
+        ));
+    }
+    Ok(tokio::task::block_in_place(|| handle.block_on(fut)))
+}
+
+fn nextval(seq: SequenceHandle) -> ScalarUDF {
+    ScalarUDF {
+        name: "nextval".to_string(),
+        signature: Signature::new(
+            TypeSignature::Exact(vec![DataType::Utf8, DataType::Float64]),
+            Volatility::Volatile,
+        ),
+        return_type: Arc::new(|_| Ok(Arc::new(DataType::Int64))),
+        fun: Arc::new(move |input| match input.get(1) {
+            Some(ColumnarValue::Array(arr)) => {
+                let values = seq.next_values(arr.len())?;
+                Ok(ColumnarValue::Array(Arc::new(Int64Array::from(values))))
+            }
+            _ => {
+                let values = seq.next_values(1)?;
+                Ok(ColumnarValue::Scalar(ScalarValue::Int64(Some(values[0]))))
+            }
+        }),
+    }
+}
+
+fn currval(seq: SequenceHandle) -> ScalarUDF {
+    ScalarUDF {
+        name: "currval".to_string(),
+        signature: Signature::new(
+            TypeSignature::Exact(vec![DataType::Utf8]),
+            Volatility::Volatile,
+        ),
+        return_type: Arc::new(|_| Ok(Arc::new(DataType::Int64))),
+        fun: Arc::new(move |_| {
+            Ok(ColumnarValue::Scalar(ScalarValue::Int64(Some(
+                seq.current_value()?,
+            ))))
+        }),
+    }
+}
+
+fn setval(seq: SequenceHandle) -> ScalarUDF {
+    ScalarUDF {
+        name: "setval".to_string(),
+        signature: Signature::new(
+            TypeSignature::OneOf(vec![
+                TypeSignature::Exact(vec![DataType::Utf8, DataType::Int64]),
+                TypeSignature::Exact(vec![DataType::Utf8, DataType::Int64, DataType::Boolean]),
+            ]),
+            Volatility::Volatile,
+        ),
+        return_type: Arc::new(|_| Ok(Arc::new(DataType::Int64))),
+        fun: Arc::new(move |input| {
+            let is_called = match input.get(2) {
+                None => Ok(true),
+                Some(ColumnarValue::Scalar(ScalarValue::Boolean(Some(v)))) => Ok(*v),
+                Some(ColumnarValue::Array(arr)) if arr.len() == 1 && arr.is_valid(0) => arr
+                    .as_any()
+                    .downcast_ref::<BooleanArray>()
+                    .map(|arr| arr.value(0))
+                    .ok_or_else(|| {
+                        DataFusionError::Internal("expected boolean for is_called".to_string())
+                    }),
+                _ => Err(DataFusionError::Execution(
+                    "setval: is_called must be a non-null constant".to_string(),
+                )),
+            }?;
+
+            match input.get(1) {
+                Some(ColumnarValue::Scalar(ScalarValue::Int64(Some(value)))) => {
+                    seq.set_value(*value, is_called)?;
+                    Ok(ColumnarValue::Scalar(ScalarValue::Int64(Some(*value))))
+                }
+                Some(ColumnarValue::Array(arr)) => {
+                    let values = arr.as_any().downcast_ref::<Int64Array>().ok_or_else(|| {
+                        DataFusionError::Internal(format!(
+                            "expected int64 array for value, got {}",
+                            arr.data_type()
+                        ))
+                    })?;
+                    for value in values.iter() {
+                        let value = value.ok_or_else(|| {
+                            DataFusionError::Execution("setval: value must not be null".to_string())
+                        })?;
+                        seq.set_value(value, is_called)?;
+                    }
+                    Ok(ColumnarValue::Array(Arc::new(values.clone())))
+                }
+                _ => Err(DataFusionError::Execution(
+                    "setval: value must not be null".to_string(),
+                )),
+            }
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequence_funcs_from_str() {
+        let pairs = [
+            ("nextval", SequenceFunction::NextVal),
+            ("CURRVAL", SequenceFunction::CurrVal),
+            ("pg_catalog.setval", SequenceFunction::SetVal),
+        ];
+        for (s, expected) in pairs {
+            assert_eq!(expected, SequenceFunction::from_str(s).unwrap());
+        }
+
+        for s in ["nextvals", "public.nextval", "pg_catalog.nextval.foo"] {
+            assert!(SequenceFunction::from_str(s).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn take_cached_values() {
+        let seqs = SessionSequences::default();
+        assert!(seqs.take_cached(1, 3).is_empty());
+
+        seqs.set_cached(1, 10, 5, 4);
+        assert_eq!(vec![10, 15, 20], seqs.take_cached(1, 3));
+        assert!(seqs.take_cached(2, 1).is_empty());
+        assert_eq!(vec![25], seqs.take_cached(1, 3));
+        assert!(seqs.take_cached(1, 1).is_empty());
+
+        seqs.set_cached(1, -1, -1, 2);
+        seqs.clear_cached(1);
+        assert!(seqs.take_cached(1, 1).is_empty());
+
+        seqs.set_cached(1, 1, 1, 0);
+        assert!(seqs.take_cached(1, 1).is_empty());
+    }
+}
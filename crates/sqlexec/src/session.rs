@@ -27,13 +27,20 @@ use pgrepr::format::Format;
 use telemetry::Tracker;
 use uuid::Uuid;
 
+use crate::activity::{SessionActivity, SessionRegistry};
+use crate::audit::{AuditLog, PendingAudit};
+use crate::background_jobs::materialized_views::MaterializedViewScheduler;
 use crate::background_jobs::JobRunner;
 use crate::context::local::{LocalSessionContext, Portal, PreparedStatement};
 use crate::environment::EnvironmentReader;
 use crate::errors::Result;
-use crate::metrics::{BatchStreamWithMetricSender, ExecutionStatus, QueryMetrics, SessionMetrics};
+use crate::limits::{LimitedBatchStream, QueryGuardrails, QueryLimiter, QueryPermit};
+use crate::metrics::{
+    BatchStreamWithMetricSender, ExecutionStatus, QueryMetrics, SessionMetrics, UNKNOWN_RESULT_TYPE,
+};
 use crate::parser::StatementWithExtensions;
 use crate::planner::logical_plan::*;
+use crate::query_history::QueryHistory;
 
 /// Results from a sql statement execution.
 pub enum ExecutionResult {
@@ -95,6 +102,30 @@ pub enum ExecutionResult {
     DropTunnel,
     /// Credentials are dropped.
     DropCredentials,
+    /// Role created.
+    CreateRole,
+    /// Role(s) dropped.
+    DropRole,
+    /// Privileges granted.
+    Grant,
+    /// Privileges revoked.
+    Revoke,
+    /// Materialized view refreshed.
+    RefreshMaterializedView,
+    /// Function created.
+    CreateFunction,
+    /// Function(s) dropped.
+    DropFunctions,
+    /// Sequence created.
+    CreateSequence,
+    /// Sequence(s) dropped.
+    DropSequences,
+    /// Comment set on an object.
+    Comment,
+    /// Prepared statement created.
+    Prepare,
+    /// Prepared statement(s) removed.
+    Deallocate,
 }
 
 impl ExecutionResult {
@@ -178,6 +209,18 @@ impl ExecutionResult {
             ExecutionResult::DropDatabase => "drop_database",
             ExecutionResult::DropTunnel => "drop_tunnel",
             ExecutionResult::DropCredentials => "drop_credentials",
+            ExecutionResult::CreateRole => "create_role",
+            ExecutionResult::DropRole => "drop_role",
+            ExecutionResult::Grant => "grant",
+            ExecutionResult::Revoke => "revoke",
+            ExecutionResult::RefreshMaterializedView => "refresh_materialized_view",
+            ExecutionResult::CreateFunction => "create_function",
+            ExecutionResult::DropFunctions => "drop_functions",
+            ExecutionResult::CreateSequence => "create_sequence",
+            ExecutionResult::DropSequences => "drop_sequences",
+            ExecutionResult::Comment => "comment",
+            ExecutionResult::Prepare => "prepare",
+            ExecutionResult::Deallocate => "deallocate",
         }
     }
 
@@ -199,6 +242,16 @@ impl ExecutionResult {
                 | ExecutionResult::DropDatabase
                 | ExecutionResult::DropTunnel
                 | ExecutionResult::DropCredentials
+                | ExecutionResult::CreateRole
+                | ExecutionResult::DropRole
+                | ExecutionResult::Grant
+                | ExecutionResult::Revoke
+                | ExecutionResult::RefreshMaterializedView
+                | ExecutionResult::CreateFunction
+                | ExecutionResult::DropFunctions
+                | ExecutionResult::CreateSequence
+                | ExecutionResult::DropSequences
+                | ExecutionResult::Comment
         )
     }
 
@@ -237,6 +290,18 @@ impl ExecutionResult {
             "drop_database" => ExecutionResult::DropDatabase,
             "drop_tunnel" => ExecutionResult::DropTunnel,
             "drop_credentials" => ExecutionResult::DropCredentials,
+            "create_role" => ExecutionResult::CreateRole,
+            "drop_role" => ExecutionResult::DropRole,
+            "grant" => ExecutionResult::Grant,
+            "revoke" => ExecutionResult::Revoke,
+            "refresh_materialized_view" => ExecutionResult::RefreshMaterializedView,
+            "create_function" => ExecutionResult::CreateFunction,
+            "drop_functions" => ExecutionResult::DropFunctions,
+            "create_sequence" => ExecutionResult::CreateSequence,
+            "drop_sequences" => ExecutionResult::DropSequences,
+            "comment" => ExecutionResult::Comment,
+            "prepare" => ExecutionResult::Prepare,
+            "deallocate" => ExecutionResult::Deallocate,
             _ => return None,
         })
     }
@@ -293,6 +358,18 @@ impl fmt::Display for ExecutionResult {
             ExecutionResult::DropDatabase => write!(f, "Database(s) dropped"),
             ExecutionResult::DropTunnel => write!(f, "Tunnel(s) dropped"),
             ExecutionResult::DropCredentials => write!(f, "Credentials dropped"),
+            ExecutionResult::CreateRole => write!(f, "Role created"),
+            ExecutionResult::DropRole => write!(f, "Role(s) dropped"),
+            ExecutionResult::Grant => write!(f, "Privileges granted"),
+            ExecutionResult::Revoke => write!(f, "Privileges revoked"),
+            ExecutionResult::RefreshMaterializedView => write!(f, "Materialized view refreshed"),
+            ExecutionResult::CreateFunction => write!(f, "Function created"),
+            ExecutionResult::DropFunctions => write!(f, "Function(s) dropped"),
+            ExecutionResult::CreateSequence => write!(f, "Sequence created"),
+            ExecutionResult::DropSequences => write!(f, "Sequence(s) dropped"),
+            ExecutionResult::Comment => write!(f, "Comment set"),
+            ExecutionResult::Prepare => write!(f, "Prepared statement created"),
+            ExecutionResult::Deallocate => write!(f, "Prepared statement(s) deallocated"),
         }
     }
 }
@@ -328,6 +405,8 @@ impl RecordBatchStream for StreamAndFirstResult {
 /// in the future (e.g. consensus).
 pub struct Session {
     pub(crate) ctx: LocalSessionContext,
+    /// Limits the number of concurrently executing queries.
+    query_limiter: QueryLimiter,
 }
 
 impl Session {
@@ -344,12 +423,33 @@ impl Session {
         tracker: Arc<Tracker>,
         spill_path: Option<PathBuf>,
         background_jobs: JobRunner,
+        query_limiter: QueryLimiter,
+        audit_log: Option<Arc<AuditLog>>,
+        query_history: Arc<QueryHistory>,
+        sessions: Arc<SessionRegistry>,
     ) -> Result<Session> {
+        let materialized_views = MaterializedViewScheduler {
+            catalog_mutator: catalog_mutator.clone(),
+            native_tables: native_tables.clone(),
+            tracker: tracker.clone(),
+            spill_path: spill_path.clone(),
+            background_jobs: background_jobs.clone(),
+            query_limiter: query_limiter.clone(),
+            audit_log: audit_log.clone(),
+            query_history: query_history.clone(),
+            sessions: sessions.clone(),
+        };
+
+        let activity = sessions.register(&vars);
         let metrics = SessionMetrics::new(
             vars.user_id(),
+            vars.user_name(),
             vars.database_id(),
             vars.connection_id(),
             tracker,
+            audit_log,
+            query_history,
+            activity,
         );
 
         let ctx = LocalSessionContext::new(
@@ -360,9 +460,10 @@ impl Session {
             metrics,
             spill_path,
             background_jobs,
+            materialized_views,
         )?;
 
-        Ok(Session { ctx })
+        Ok(Session { ctx, query_limiter })
     }
 
     pub async fn attach_remote_session(
@@ -420,6 +521,11 @@ impl Session {
         Ok(stream)
     }
 
+    /// Get the activity for this session.
+    pub fn activity(&self) -> &SessionActivity {
+        self.ctx.get_metrics().activity()
+    }
+
     pub fn get_session_vars(&self) -> SessionVars {
         self.ctx.get_session_vars().clone()
     }
@@ -440,7 +546,28 @@ impl Session {
         // prior to execute (which we also do).
         self.ctx.get_metrics_mut().flush_completed();
 
-        self.ctx.prepare_statement(name, stmt, params).await
+        // Statements failing to plan (e.g. due to missing privileges) won't
+        // reach execution, audit them here.
+        let audit =
+            self.ctx.get_metrics().audit_log().map(|log| {
+                PendingAudit::new(log.clone(), &self.ctx.get_session_vars(), stmt.as_ref())
+            });
+
+        let result = self.ctx.prepare_statement(name, stmt, params).await;
+        if let Some(audit) = audit {
+            match &result {
+                Err(e) => audit.record(
+                    &ExecutionStatus::Fail,
+                    UNKNOWN_RESULT_TYPE,
+                    Some(e.to_string()),
+                    None,
+                    None,
+                ),
+                // Audited when executed.
+                Ok(_) => audit.discard(),
+            }
+        }
+        result
     }
 
     pub fn get_prepared_statement(&self, name: &str) -> Result<&PreparedStatement> {
@@ -482,6 +609,28 @@ impl Session {
         // try to open a transaction for some queries.
         match plan {
             LogicalPlan::Transaction(_plan) => Ok(ExecutionResult::EmptyQuery),
+            LogicalPlan::PreparedStatement(plan) => match plan {
+                PreparedStatementPlan::Prepare {
+                    name,
+                    statement,
+                    parameter_types,
+                } => {
+                    self.ctx
+                        .prepare_sql_statement(name, statement, parameter_types)
+                        .await?;
+                    Ok(ExecutionResult::Prepare)
+                }
+                PreparedStatementPlan::Deallocate { name: Some(name) } => {
+                    // Error if the statement doesn't exist.
+                    self.ctx.get_prepared_statement(&name)?;
+                    self.ctx.remove_prepared_statement(&name);
+                    Ok(ExecutionResult::Deallocate)
+                }
+                PreparedStatementPlan::Deallocate { name: None } => {
+                    self.ctx.remove_all_prepared_statements();
+                    Ok(ExecutionResult::Deallocate)
+                }
+            },
             LogicalPlan::Datafusion(plan) => {
                 let physical = self.create_physical_plan(plan).await?;
                 let stream = self.execute_physical(physical.clone())?;
@@ -544,12 +693,57 @@ impl Session {
 
         // Create "base" metrics.
         let mut metrics = QueryMetrics::new_for_portal(portal);
+        metrics.history = Some(self.ctx.get_metrics().query_history().clone());
+        if let Some(log) = self.ctx.get_metrics().audit_log() {
+            metrics.audit = Some(PendingAudit::new(
+                log.clone(),
+                &self.ctx.get_session_vars(),
+                portal.stmt.stmt.as_ref(),
+            ));
+        }
 
-        let stream = match self.execute_inner(plan).await {
+        // Mark the session as active. The statement stays active until
+        // dropped, which for queries is when the output stream completes.
+        let statement = self
+            .ctx
+            .get_metrics()
+            .activity()
+            .start_statement(metrics.query_text.clone());
+
+        // Wait for a slot before starting execution. The statement timeout
+        // only applies once the query has been admitted. Statements only
+        // touching session state don't need a slot, so they're never queued
+        // behind other queries.
+        let acquire = async {
+            if plan.only_touches_session_state() {
+                Ok(QueryPermit::exempt())
+            } else {
+                self.query_limiter.acquire().await
+            }
+        };
+        let permit = match statement.with_cancel(acquire).await {
+            Ok(permit) => permit,
+            Err(e) => {
+                metrics.execution_status = ExecutionStatus::Fail;
+                metrics.error_message = Some(e.to_string());
+                metrics.observe();
+                self.ctx.get_metrics_mut().push_metric(metrics);
+                return Err(e);
+            }
+        };
+        let guardrails = QueryGuardrails::from_vars(&self.ctx.get_session_vars());
+        let deadline = guardrails.deadline();
+
+        let result = statement
+            .with_cancel(guardrails.with_deadline(deadline, self.execute_inner(plan)))
+            .await;
+
+        let stream = match result {
             Ok(stream) => match stream {
                 ExecutionResult::Error(e) => {
                     metrics.execution_status = ExecutionStatus::Fail;
                     metrics.error_message = Some(e.to_string());
+                    metrics.observe();
                     return Err(e.into());
                 }
                 stream => {
@@ -561,6 +755,14 @@ impl Session {
                             // Swap out the batch stream with one that will send
                             // metrics at the completions of the stream.
                             let sender = self.ctx.get_metrics().get_sender();
+                            let stream = Box::pin(LimitedBatchStream::new(
+                                stream,
+                                plan.clone(),
+                                guardrails,
+                                deadline,
+                                permit,
+                                statement,
+                            ));
                             ExecutionResult::Query {
                                 stream: Box::pin(BatchStreamWithMetricSender::new(
                                     stream,
@@ -571,13 +773,17 @@ impl Session {
                                 plan,
                             }
                         }
-                        other => other,
+                        other => {
+                            metrics.observe();
+                            other
+                        }
                     }
                 }
             },
             Err(e) => {
                 metrics.execution_status = ExecutionStatus::Fail;
                 metrics.error_message = Some(e.to_string());
+                metrics.observe();
 
                 // Ensure we push the metrics for this failed query even though
                 // we're returning an error. This allows for querying for and
@@ -1,4 +1,6 @@
 //! Small crate for telemetry code.
+pub mod metrics;
+
 use segment::message::{BatchMessage, Message, Track, User};
 use segment::{Batcher, Client, HttpClient};
 use tokio::sync::mpsc;
@@ -0,0 +1,179 @@
+//! Prometheus metrics for server deployments.
+//!
+//! Metrics are recorded to a global registry and can be rendered using the
+//! Prometheus text format with `Metrics::encode`.
+use once_cell::sync::Lazy;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+
+/// Global metrics.
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+/// Buckets (in seconds) to use for query durations.
+const QUERY_DURATION_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0,
+];
+
+/// Buckets (in seconds) to use for metastore request durations.
+const METASTORE_DURATION_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+];
+
+#[derive(Debug)]
+pub struct Metrics {
+    registry: Registry,
+
+    /// Number of currently open sessions.
+    pub active_connections: IntGauge,
+
+    /// Number of executed queries by status.
+    pub queries_total: IntCounterVec,
+
+    /// Query durations by status.
+    pub query_duration_seconds: HistogramVec,
+
+    /// Bytes read by data sources, by the data source's execution plan.
+    pub datasource_bytes_read_total: IntCounterVec,
+
+    /// Bytes spilled to disk during query execution.
+    pub spilled_bytes_total: IntCounter,
+
+    /// Number of times query execution spilled to disk.
+    pub spill_count_total: IntCounter,
+
+    /// Round-trip durations of requests to metastore by operation.
+    pub metastore_request_duration_seconds: HistogramVec,
+
+    /// Number of completed background jobs by kind and status.
+    pub background_jobs_total: IntCounterVec,
+}
+
+impl Metrics {
+    fn new() -> Metrics {
+        let registry =
+            Registry::new_custom(Some("glaredb".to_string()), None).expect("valid registry prefix");
+
+        let active_connections =
+            IntGauge::new("active_connections", "Number of currently open sessions.").unwrap();
+
+        let queries_total = IntCounterVec::new(
+            Opts::new("queries_total", "Number of executed queries."),
+            &["status"],
+        )
+        .unwrap();
+
+        let query_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("query_duration_seconds", "Query durations in seconds.")
+                .buckets(QUERY_DURATION_BUCKETS.to_vec()),
+            &["status"],
+        )
+        .unwrap();
+
+        let datasource_bytes_read_total = IntCounterVec::new(
+            Opts::new(
+                "datasource_bytes_read_total",
+                "Bytes read from data sources during query execution.",
+            ),
+            &["datasource"],
+        )
+        .unwrap();
+
+        let spilled_bytes_total = IntCounter::new(
+            "spilled_bytes_total",
+            "Bytes spilled to disk during query execution.",
+        )
+        .unwrap();
+
+        let spill_count_total = IntCounter::new(
+            "spill_count_total",
+            "Number of times query execution spilled to disk.",
+        )
+        .unwrap();
+
+        let metastore_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "metastore_request_duration_seconds",
+                "Round-trip durations of requests to metastore in seconds.",
+            )
+            .buckets(METASTORE_DURATION_BUCKETS.to_vec()),
+            &["operation"],
+        )
+        .unwrap();
+
+        let background_jobs_total = IntCounterVec::new(
+            Opts::new(
+                "background_jobs_total",
+                "Number of completed background jobs.",
+            ),
+            &["job", "status"],
+        )
+        .unwrap();
+
+        registry
+            .register(Box::new(active_connections.clone()))
+            .unwrap();
+        registry.register(Box::new(queries_total.clone())).unwrap();
+        registry
+            .register(Box::new(query_duration_seconds.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(datasource_bytes_read_total.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(spilled_bytes_total.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(spill_count_total.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(metastore_request_duration_seconds.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(background_jobs_total.clone()))
+            .unwrap();
+
+        Metrics {
+            registry,
+            active_connections,
+            queries_total,
+            query_duration_seconds,
+            datasource_bytes_read_total,
+            spilled_bytes_total,
+            spill_count_total,
+            metastore_request_duration_seconds,
+            background_jobs_total,
+        }
+    }
+
+    /// Encode all metrics using the Prometheus text format.
+    pub fn encode(&self) -> Result<String, prometheus::Error> {
+        let mut buf = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buf)?;
+        // Text encoder always produces valid utf8.
+        Ok(String::from_utf8(buf).unwrap_or_default())
+    }
+}
+
+/// Content type to use when serving encoded metrics.
+pub const METRICS_CONTENT_TYPE: &str = prometheus::TEXT_FORMAT;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_metrics() {
+        METRICS.queries_total.with_label_values(&["success"]).inc();
+        METRICS
+            .query_duration_seconds
+            .with_label_values(&["success"])
+            .observe(0.2);
+
+        let out = METRICS.encode().unwrap();
+        assert!(out.contains("glaredb_queries_total{status=\"success\"}"));
+        assert!(out.contains("glaredb_query_duration_seconds_bucket"));
+        assert!(out.contains("glaredb_active_connections"));
+    }
+}
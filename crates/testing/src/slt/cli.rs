@@ -10,6 +10,7 @@ use anyhow::{anyhow, Result};
 use clap::Parser;
 use glaredb::args::StorageConfigArgs;
 use glaredb::server::{ComputeServer, ServerConfig};
+use sqlexec::audit::AuditLog;
 use tokio::{net::TcpListener, runtime::Builder, sync::mpsc, time::Instant};
 use tokio_postgres::config::Config as ClientConfig;
 use uuid::Uuid;
@@ -188,6 +189,9 @@ impl Cli {
                     } else {
                         None
                     },
+                    flight_addr: None,
+                    http_addr: None,
+                    metrics_addr: None,
                 };
 
                 let server = ComputeServer::connect(
@@ -202,6 +206,11 @@ impl Cli {
                     self.storage_config.location.clone(),
                     HashMap::from_iter(self.storage_config.storage_options.clone()),
                     None,
+                    None,
+                    None,
+                    Default::default(),
+                    Some(Arc::new(AuditLog::new(Default::default())?)),
+                    Default::default(),
                     /* integration_testing = */ true,
                     /* disable_rpc_auth = */ self.rpc_test,
                 )
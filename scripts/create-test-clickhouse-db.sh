@@ -0,0 +1,62 @@
+#!/usr/bin/env bash
+
+# Spins up a ClickHouse docker container and loads it with data to test
+# external ClickHouse connections against it.
+
+set -e
+
+CLICKHOUSE_IMAGE="clickhouse/clickhouse-server:23.8"
+CONTAINER_NAME="glaredb_clickhouse_test"
+
+DB_USER="glaredb"
+DB_PASSWORD="password"
+DB_NAME="glaredb_test"
+DB_HOST="127.0.0.1"
+DB_PORT=8124
+
+# Remove container if it exists
+if [[ -n "$(docker ps -a -q -f name=$CONTAINER_NAME)" ]]; then
+    docker rm -f $CONTAINER_NAME > /dev/null
+fi
+
+# Create new container for ClickHouse (exposing the HTTP interface)
+CONTAINER_PORT=8123
+docker run \
+    -p $DB_PORT:$CONTAINER_PORT \
+    --name "${CONTAINER_NAME}" \
+    -e CLICKHOUSE_DB="${DB_NAME}" \
+    -e CLICKHOUSE_USER="${DB_USER}" \
+    -e CLICKHOUSE_PASSWORD="${DB_PASSWORD}" \
+    -d $CLICKHOUSE_IMAGE > /dev/null
+
+CLIENT="docker exec -i $CONTAINER_NAME clickhouse-client --user $DB_USER --password $DB_PASSWORD --database $DB_NAME"
+
+# Let the database server start
+#
+# This loop basically waits for the database to start by testing the connection
+# through clickhouse-client. It keeps on retrying until it times out (set to
+# 60s).
+INIT_TIME=$(date +%s)
+CONNECTED="not yet"
+while [[ -n "$CONNECTED" ]]; do
+  set +e
+  CONNECTED=$($CLIENT --query "SELECT 1" 2>&1 > /dev/null)
+  set -e
+
+  CURRENT_TIME=$(date +%s)
+  CURRENT_TIME=$((CURRENT_TIME - 60))
+  if [[ "$CURRENT_TIME" -gt "$INIT_TIME" ]]; then
+    echo "Timed out waiting for ClickHouse server to start!"
+    exit 1
+  fi
+done
+
+# Setup tables.
+$CLIENT --multiquery < testdata/sqllogictests_clickhouse/data/setup-test-clickhouse-db.sql
+
+# Load data into the test container.
+$CLIENT --query "INSERT INTO bikeshare_stations FORMAT CSVWithNames" \
+    < testdata/sqllogictests_datasources_common/data/bikeshare_stations.csv
+
+# This URI is expected by sqllogictests_clickhouse.
+echo "http://${DB_USER}:${DB_PASSWORD}@${DB_HOST}:${DB_PORT}/${DB_NAME}"
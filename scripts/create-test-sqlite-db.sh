@@ -0,0 +1,28 @@
+#!/usr/bin/env bash
+
+# Creates a SQLite database file and loads it with data to test external
+# SQLite databases against it.
+#
+# Requires the `sqlite3` cli.
+
+set -e
+
+DB_DIR=$(mktemp -d)
+DB_PATH="${DB_DIR}/glaredb_test.sqlite"
+
+# Setup tables.
+sqlite3 "$DB_PATH" < testdata/sqllogictests_sqlite/data/setup-test-sqlite-db.sql
+
+# Load data into the test database.
+sqlite3 "$DB_PATH" \
+    ".import --csv --skip 1 testdata/sqllogictests_datasources_common/data/bikeshare_stations.csv bikeshare_stations"
+
+# The CSV import inserts empty strings for missing values, replace them with
+# NULLs to match the other datasources.
+COLUMNS=$(sqlite3 "$DB_PATH" "SELECT name FROM pragma_table_info('bikeshare_stations')")
+for COLUMN in $COLUMNS; do
+    sqlite3 "$DB_PATH" "UPDATE bikeshare_stations SET $COLUMN = NULL WHERE $COLUMN = ''"
+done
+
+# This path is expected by sqllogictests_sqlite.
+echo "$DB_PATH"
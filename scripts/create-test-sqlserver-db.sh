@@ -0,0 +1,64 @@
+#!/usr/bin/env bash
+
+# Spins up a SQL Server docker container and loads it with data to test
+# external SQL Server connection against it.
+
+set -e
+
+SQLSERVER_IMAGE="mcr.microsoft.com/mssql/server:2022-latest"
+CONTAINER_NAME="glaredb_sqlserver_test"
+
+DB_USER="sa"
+DB_PASSWORD="Password123!"
+DB_NAME="glaredb_test"
+DB_HOST="127.0.0.1"
+DB_PORT=1434
+
+# Remove container if it exists
+if [[ -n "$(docker ps -a -q -f name=$CONTAINER_NAME)" ]]; then
+    docker rm -f $CONTAINER_NAME > /dev/null
+fi
+
+# Create new container for SQL Server
+CONTAINER_PORT=1433
+CONTAINER_ID=$(docker run \
+  -p $DB_PORT:$CONTAINER_PORT \
+  --name "${CONTAINER_NAME}" \
+  -e ACCEPT_EULA=Y \
+  -e MSSQL_SA_PASSWORD="${DB_PASSWORD}" \
+  -d $SQLSERVER_IMAGE)
+
+SQLCMD="docker exec $CONTAINER_NAME /opt/mssql-tools18/bin/sqlcmd -C -b -S localhost -U $DB_USER -P $DB_PASSWORD"
+# Let the database server start
+#
+# This loop basically waits for the database to start by testing the connection
+# through sqlcmd. It keeps on retrying until it times out (set to 60s).
+INIT_TIME=$(date +%s)
+CONNECTED="not yet"
+while [[ -n "$CONNECTED" ]]; do
+  set +e
+  CONNECTED=$($SQLCMD -Q "SELECT 1" 2>&1 > /dev/null)
+  set -e
+
+  CURRENT_TIME=$(date +%s)
+  CURRENT_TIME=$((CURRENT_TIME - 60))
+  if [[ "$CURRENT_TIME" -gt "$INIT_TIME" ]]; then
+    echo "Timed out waiting for SQL Server to start!"
+    exit 1
+  fi
+done
+
+# Copy over the data files so they can be bulk inserted by the server.
+docker exec $CONTAINER_NAME mkdir -p /tmp/testdata
+docker cp ./testdata/sqllogictests_datasources_common/data/bikeshare_stations.csv $CONTAINER_NAME:/tmp/testdata/
+docker cp ./testdata/sqllogictests_sqlserver/data/setup-test-sqlserver-db.sql $CONTAINER_NAME:/tmp/testdata/
+
+# Load data into the test container
+$SQLCMD -Q "CREATE DATABASE ${DB_NAME}" > /dev/null
+$SQLCMD -d $DB_NAME -i /tmp/testdata/setup-test-sqlserver-db.sql > /dev/null
+
+# This connection string is expected by sqllogictests_sqlserver.
+echo "server=tcp:${DB_HOST},${DB_PORT};user=${DB_USER};password=${DB_PASSWORD};database=${DB_NAME};TrustServerCertificate=true"
+
+CONTAINER_HOST=$(docker inspect -f '{{range.NetworkSettings.Networks}}{{.IPAddress}}{{end}}' $CONTAINER_ID)
+echo "server=tcp:${CONTAINER_HOST},${CONTAINER_PORT};user=${DB_USER};password=${DB_PASSWORD};database=${DB_NAME};TrustServerCertificate=true"
@@ -0,0 +1,295 @@
+"""Generates Avro and ORC copies of the bikeshare stations test data.
+
+Writes `bikeshare_stations.avro` and `bikeshare_stations.orc` next to
+`bikeshare_stations.csv` in `sqllogictests_datasources_common/data`.
+
+Only depends on the python standard library. Both files are written without
+compression. The ORC file uses a single stripe with DIRECT_V2 encoded columns
+and no row indexes.
+
+Usage:
+
+    $ python testdata/generate_avro_orc.py
+"""
+
+import csv
+import json
+import os
+import struct
+from datetime import datetime, timezone
+
+DATA_DIR = os.path.join(
+    os.path.dirname(os.path.abspath(__file__)),
+    "sqllogictests_datasources_common",
+    "data",
+)
+
+# (name, type) for every column in the CSV.
+COLUMNS = [
+    ("station_id", "long"),
+    ("name", "string"),
+    ("status", "string"),
+    ("address", "string"),
+    ("alternate_name", "string"),
+    ("city_asset_number", "long"),
+    ("property_type", "string"),
+    ("number_of_docks", "long"),
+    ("power_type", "string"),
+    ("footprint_length", "long"),
+    ("footprint_width", "double"),
+    ("notes", "string"),
+    ("council_district", "long"),
+    ("modified_date", "timestamp"),
+]
+
+
+def read_rows():
+    path = os.path.join(DATA_DIR, "bikeshare_stations.csv")
+    with open(path, newline="") as f:
+        reader = csv.DictReader(f)
+        rows = []
+        for record in reader:
+            row = []
+            for name, typ in COLUMNS:
+                value = record[name]
+                if value == "":
+                    row.append(None)
+                elif typ == "long":
+                    row.append(int(value))
+                elif typ == "double":
+                    row.append(float(value))
+                elif typ == "timestamp":
+                    row.append(datetime.strptime(value, "%Y-%m-%d %H:%M:%S"))
+                else:
+                    row.append(value)
+            rows.append(row)
+        return rows
+
+
+def varint(n):
+    out = bytearray()
+    while True:
+        b = n & 0x7F
+        n >>= 7
+        if n:
+            out.append(b | 0x80)
+        else:
+            out.append(b)
+            return bytes(out)
+
+
+def zigzag(n):
+    return (n << 1) ^ (n >> 63)
+
+
+# Avro
+
+
+def avro_long(n):
+    return varint(zigzag(n))
+
+
+def avro_bytes(b):
+    return avro_long(len(b)) + b
+
+
+def avro_value(typ, value):
+    if typ == "long":
+        return avro_long(value)
+    if typ == "double":
+        return struct.pack("<d", value)
+    if typ == "timestamp":
+        micros = int(value.replace(tzinfo=timezone.utc).timestamp()) * 1_000_000
+        return avro_long(micros)
+    return avro_bytes(value.encode("utf-8"))
+
+
+def write_avro(rows):
+    fields = []
+    for name, typ in COLUMNS:
+        if typ == "timestamp":
+            typ = {"type": "long", "logicalType": "timestamp-micros"}
+        fields.append({"name": name, "type": ["null", typ]})
+    schema = {"type": "record", "name": "bikeshare_stations", "fields": fields}
+
+    sync = bytes(range(16))
+
+    header = bytearray(b"Obj\x01")
+    metadata = {
+        "avro.schema": json.dumps(schema).encode("utf-8"),
+        "avro.codec": b"null",
+    }
+    header += avro_long(len(metadata))
+    for key, value in metadata.items():
+        header += avro_bytes(key.encode("utf-8"))
+        header += avro_bytes(value)
+    header += avro_long(0)
+    header += sync
+
+    block = bytearray()
+    for row in rows:
+        for (_, typ), value in zip(COLUMNS, row):
+            if value is None:
+                block += avro_long(0)
+            else:
+                block += avro_long(1)
+                block += avro_value(typ, value)
+
+    out = header + avro_long(len(rows)) + avro_long(len(block)) + block + sync
+    with open(os.path.join(DATA_DIR, "bikeshare_stations.avro"), "wb") as f:
+        f.write(out)
+
+
+# ORC
+
+# Type kinds
+ORC_LONG = 4
+ORC_DOUBLE = 6
+ORC_STRING = 7
+ORC_STRUCT = 12
+
+# Stream kinds
+ORC_PRESENT = 0
+ORC_DATA = 1
+ORC_LENGTH = 2
+
+# Column encodings
+ORC_DIRECT = 0
+ORC_DIRECT_V2 = 2
+
+
+def pb_varint(field, n):
+    return varint(field << 3) + varint(n)
+
+
+def pb_bytes(field, b):
+    return varint((field << 3) | 2) + varint(len(b)) + b
+
+
+def pb_packed(field, values):
+    return pb_bytes(field, b"".join(varint(v) for v in values))
+
+
+def orc_byte_rle(data):
+    """Byte RLE using literal runs only."""
+    out = bytearray()
+    for i in range(0, len(data), 128):
+        chunk = data[i : i + 128]
+        out.append(256 - len(chunk))
+        out += chunk
+    return bytes(out)
+
+
+def orc_present(values):
+    bits = bytearray()
+    for i in range(0, len(values), 8):
+        byte = 0
+        for j, value in enumerate(values[i : i + 8]):
+            if value is not None:
+                byte |= 0x80 >> j
+        bits.append(byte)
+    return orc_byte_rle(bits)
+
+
+def orc_int_rle_v2(values, signed):
+    """Integer RLEv2 using DIRECT runs with a fixed 64 bit width."""
+    out = bytearray()
+    for i in range(0, len(values), 512):
+        chunk = values[i : i + 512]
+        length = len(chunk) - 1
+        # Header: encoding (DIRECT = 1), width (64 bits is encoded as 31), and
+        # run length - 1.
+        out.append(0x40 | (31 << 1) | (length >> 8))
+        out.append(length & 0xFF)
+        for value in chunk:
+            value = zigzag(value) if signed else value
+            out += struct.pack(">Q", value)
+    return bytes(out)
+
+
+def write_orc(rows):
+    streams = []  # (kind, column, bytes)
+    encodings = [ORC_DIRECT]
+    types = [
+        pb_varint(1, ORC_STRUCT)
+        + pb_packed(2, range(1, len(COLUMNS) + 1))
+        + b"".join(pb_bytes(3, name.encode("utf-8")) for name, _ in COLUMNS)
+    ]
+    statistics = [pb_varint(1, len(rows))]
+
+    for idx, (_, typ) in enumerate(COLUMNS):
+        column = idx + 1
+        values = [row[idx] for row in rows]
+        present = [v for v in values if v is not None]
+
+        if len(present) != len(values):
+            streams.append((ORC_PRESENT, column, orc_present(values)))
+
+        if typ == "long":
+            kind = ORC_LONG
+            streams.append((ORC_DATA, column, orc_int_rle_v2(present, signed=True)))
+        elif typ == "double":
+            kind = ORC_DOUBLE
+            data = b"".join(struct.pack("<d", v) for v in present)
+            streams.append((ORC_DATA, column, data))
+        else:
+            # Timestamps are written as strings, ORC timestamps are relative
+            # to the writer's timezone.
+            kind = ORC_STRING
+            if typ == "timestamp":
+                present = [v.strftime("%Y-%m-%d %H:%M:%S") for v in present]
+            encoded = [v.encode("utf-8") for v in present]
+            streams.append((ORC_DATA, column, b"".join(encoded)))
+            lengths = [len(v) for v in encoded]
+            streams.append((ORC_LENGTH, column, orc_int_rle_v2(lengths, signed=False)))
+
+        encodings.append(ORC_DIRECT_V2)
+        types.append(pb_varint(1, kind))
+        statistics.append(
+            pb_varint(1, len(present)) + pb_varint(10, int(len(present) != len(values)))
+        )
+
+    data = b"".join(s for _, _, s in streams)
+    stripe_footer = b"".join(
+        pb_bytes(1, pb_varint(1, kind) + pb_varint(2, column) + pb_varint(3, len(s)))
+        for kind, column, s in streams
+    ) + b"".join(pb_bytes(2, pb_varint(1, e)) for e in encodings)
+
+    header = b"ORC"
+    stripe_info = (
+        pb_varint(1, len(header))
+        + pb_varint(2, 0)
+        + pb_varint(3, len(data))
+        + pb_varint(4, len(stripe_footer))
+        + pb_varint(5, len(rows))
+    )
+    content_length = len(header) + len(data) + len(stripe_footer)
+
+    footer = (
+        pb_varint(1, len(header))
+        + pb_varint(2, content_length)
+        + pb_bytes(3, stripe_info)
+        + b"".join(pb_bytes(4, t) for t in types)
+        + pb_varint(6, len(rows))
+        + b"".join(pb_bytes(7, s) for s in statistics)
+        + pb_varint(8, 0)
+    )
+    metadata = b""
+    postscript = (
+        pb_varint(1, len(footer))
+        + pb_varint(2, 0)  # No compression
+        + pb_packed(4, [0, 12])
+        + pb_varint(5, len(metadata))
+        + pb_bytes(8000, b"ORC")
+    )
+
+    out = header + data + stripe_footer + metadata + footer + postscript
+    out += bytes([len(postscript)])
+    with open(os.path.join(DATA_DIR, "bikeshare_stations.orc"), "wb") as f:
+        f.write(out)
+
+
+if __name__ == "__main__":
+    rows = read_rows()
+    write_avro(rows)
+    write_orc(rows)
@@ -0,0 +1,63 @@
+-- Setup a simple table with some of the supported ClickHouse datatypes.
+CREATE TABLE IF NOT EXISTS datatypes (
+    c1  Bool,
+    c2  Int8,
+    c3  UInt8,
+    c4  Int16,
+    c5  UInt16,
+    c6  Int32,
+    c7  UInt32,
+    c8  Int64,
+    c9  UInt64,
+    c10 Float32,
+    c11 Float64,
+    c12 String,
+    c13 LowCardinality(String),
+    c14 Date,
+    c15 DateTime('UTC'),
+    c16 DateTime64(6, 'UTC'),
+    c17 UUID,
+    c18 Enum8('a' = 1, 'b' = 2),
+    c19 Nullable(String)
+) ENGINE = MergeTree ORDER BY tuple();
+
+INSERT INTO datatypes
+VALUES (
+    true,
+    -128,
+    255,
+    -32768,
+    65535,
+    -2147483648,
+    4294967295,
+    -9223372036854775808,
+    18446744073709551615,
+    4.5,
+    6.7,
+    'abc',
+    'def',
+    '1999-09-30',
+    '1999-09-30 16:32:04',
+    '1999-09-30 16:32:04.123456',
+    '292a485f-a56a-4938-8f1a-bbbbbbbbbbb1',
+    'b',
+    NULL
+);
+
+-- bikeshare_stations table for testing datasources.
+CREATE TABLE IF NOT EXISTS bikeshare_stations (
+    station_id        Nullable(Int32),
+    name              Nullable(String),
+    status            Nullable(String),
+    address           Nullable(String),
+    alternate_name    Nullable(String),
+    city_asset_number Nullable(Int32),
+    property_type     Nullable(String),
+    number_of_docks   Nullable(Int32),
+    power_type        Nullable(String),
+    footprint_length  Nullable(Int32),
+    footprint_width   Nullable(Float32),
+    notes             Nullable(String),
+    council_district  Nullable(Int32),
+    modified_date     Nullable(DateTime('UTC'))
+) ENGINE = MergeTree ORDER BY tuple();
@@ -0,0 +1,78 @@
+-- Setup a simple table with the SQLite storage classes and some commonly
+-- declared types.
+CREATE TABLE IF NOT EXISTS datatypes (
+    c1 BOOLEAN,
+    c2 INTEGER,
+    c3 BIGINT,
+    c4 REAL,
+    c5 DOUBLE,
+    c6 TEXT,
+    c7 VARCHAR(10),
+    c8 BLOB,
+    c9 NUMERIC,
+    c10,
+    c11 TEXT
+);
+
+INSERT INTO datatypes
+VALUES (
+    1,
+    -2147483648,
+    9223372036854775807,
+    4.5,
+    6.7,
+    'abc',
+    'def',
+    X'616263',
+    123,
+    1.5,
+    NULL
+);
+
+INSERT INTO datatypes
+VALUES (
+    0,
+    2147483647,
+    -9223372036854775808,
+    -4.5,
+    -6.7,
+    'ghi',
+    'jkl',
+    X'',
+    456,
+    2,
+    'mno'
+);
+
+-- Values stored with a different storage class than the column's declared
+-- (or inferred) type.
+CREATE TABLE IF NOT EXISTS mixed (
+    id INTEGER,
+    g,
+    b BOOLEAN
+);
+
+INSERT INTO mixed VALUES (1, 'x', 'true');
+INSERT INTO mixed VALUES (2, 3, 'false');
+
+-- bikeshare_stations table for testing datasources.
+CREATE TABLE IF NOT EXISTS bikeshare_stations (
+    station_id        INTEGER,
+    name              TEXT,
+    status            TEXT,
+    address           TEXT,
+    alternate_name    TEXT,
+    city_asset_number INTEGER,
+    property_type     TEXT,
+    number_of_docks   INTEGER,
+    power_type        TEXT,
+    footprint_length  INTEGER,
+    footprint_width   REAL,
+    notes             TEXT,
+    council_district  INTEGER,
+    modified_date     TEXT
+);
+
+-- A view to test reading views.
+CREATE VIEW IF NOT EXISTS active_stations AS
+    SELECT * FROM bikeshare_stations WHERE status = 'active';
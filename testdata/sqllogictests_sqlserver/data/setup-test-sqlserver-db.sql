@@ -0,0 +1,117 @@
+-- Setup a simple table with all the supported SQL Server datatypes.
+CREATE TABLE dbo.numeric_datatypes (
+    c1  BIT,
+    c2  TINYINT,
+    c3  SMALLINT,
+    c4  INT,
+    c5  BIGINT,
+    c6  REAL,
+    c7  FLOAT,
+    c8  DECIMAL(5,2),
+    c9  MONEY
+);
+
+INSERT INTO dbo.numeric_datatypes
+VALUES (
+    1,
+    255,
+    -32768,
+    -2147483648,
+    -300000000,
+    4.5,
+    6.7,
+    123.45,
+    12.34
+),
+(
+    NULL,
+    NULL,
+    NULL,
+    NULL,
+    NULL,
+    NULL,
+    NULL,
+    NULL,
+    NULL
+);
+
+CREATE TABLE dbo.string_datatypes (
+    c1 CHAR(1),
+    c2 VARCHAR(100),
+    c3 NVARCHAR(100),
+    c4 TEXT,
+    c5 VARBINARY(100),
+    c6 UNIQUEIDENTIFIER
+);
+
+INSERT INTO dbo.string_datatypes
+VALUES (
+    'a',
+    'bc',
+    N'def',
+    'ghi',
+    CAST('bin' AS VARBINARY(100)),
+    '6f9619ff-8b86-d011-b42d-00c04fc964ff'
+),
+(
+    NULL,
+    NULL,
+    NULL,
+    NULL,
+    NULL,
+    NULL
+);
+
+CREATE TABLE dbo.date_time_datatypes (
+    c1 DATE,
+    c2 TIME,
+    c3 DATETIME,
+    c4 DATETIME2,
+    c5 SMALLDATETIME,
+    c6 DATETIMEOFFSET
+);
+
+INSERT INTO dbo.date_time_datatypes
+VALUES (
+    '1999-09-30',
+    '16:32:04',
+    '1999-09-30 16:32:04',
+    '1999-09-30 16:32:04',
+    '1999-09-30 16:32:00',
+    '1999-09-30 16:32:04 +05:30'
+),
+(
+    NULL,
+    NULL,
+    NULL,
+    NULL,
+    NULL,
+    NULL
+);
+
+-- bikeshare_stations table for testing datasources.
+CREATE TABLE dbo.bikeshare_stations (
+    station_id        INT,
+    name              NVARCHAR(MAX),
+    status            NVARCHAR(MAX),
+    address           NVARCHAR(MAX),
+    alternate_name    NVARCHAR(MAX),
+    city_asset_number INT,
+    property_type     NVARCHAR(MAX),
+    number_of_docks   INT,
+    power_type        NVARCHAR(MAX),
+    footprint_length  INT,
+    footprint_width   REAL,
+    notes             NVARCHAR(MAX),
+    council_district  INT,
+    modified_date     DATETIME2
+);
+
+BULK INSERT dbo.bikeshare_stations
+    FROM '/tmp/testdata/bikeshare_stations.csv'
+    WITH (
+        FORMAT = 'CSV',
+        FIRSTROW = 2,
+        FIELDTERMINATOR = ',',
+        ROWTERMINATOR = '0x0a'
+    );